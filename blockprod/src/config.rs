@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use mempool::{tx_accumulator::TxTemplatePolicy, FeeRate};
+
 /// The blockprod subsystem configuration.
 #[derive(Debug)]
 pub struct BlockProdConfig {
@@ -23,4 +25,20 @@ pub struct BlockProdConfig {
     /// If true, blocks with non-PoS consensus types will always be created with timestamps
     /// bigger than or equal to the current time.
     pub use_current_time_if_non_pos: bool,
+    /// Mempool transactions with a lower fee rate are not included into produced blocks.
+    pub min_tx_fee_rate_for_inclusion: Option<FeeRate>,
+    /// Block space (in bytes) reserved for explicitly requested and locally originating
+    /// transactions.
+    pub reserved_block_space_for_priority_txs: usize,
+}
+
+impl BlockProdConfig {
+    /// The template policy for blocks with the given maximum transaction data size
+    pub fn tx_template_policy(&self, max_block_size: usize) -> TxTemplatePolicy {
+        TxTemplatePolicy {
+            min_fee_rate: self.min_tx_fee_rate_for_inclusion,
+            non_priority_size_limit: max_block_size
+                .saturating_sub(self.reserved_block_space_for_priority_txs),
+        }
+    }
 }
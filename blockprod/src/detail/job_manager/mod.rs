@@ -283,6 +283,7 @@ impl JobManager {
                                         "Chainstate subscriber failed to send new tip",
                                     );
                                 }
+                                ChainstateEvent::LongFork(_, _, _) => {}
                             },
                         );
 
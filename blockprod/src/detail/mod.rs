@@ -34,7 +34,7 @@ use common::{
         },
         Block, ChainConfig, PoolId, RequiredConsensus, SignedTransaction, Transaction,
     },
-    primitives::{BlockHeight, Id},
+    primitives::{BlockHeight, Id, Idable},
     time_getter::TimeGetter,
 };
 use consensus::{
@@ -479,18 +479,27 @@ impl BlockProduction {
             finalize_block_data,
         ) = self.pull_consensus_data(input_data.clone(), self.time_getter.clone()).await?;
 
-        let collected_transactions = collect_transactions(
+        let block_height = current_tip_index.block_height().next_height();
+        let (collected_transactions, predicted_fees) = collect_transactions(
             &self.mempool_handle,
             &self.chain_config,
             current_tip_index.block_id(),
             current_tip_median_time_past,
+            block_height,
             transactions.clone(),
             transaction_ids.clone(),
             packing_strategy,
+            self.blockprod_config.tx_template_policy(
+                self.chain_config.max_block_size_from_std_scripts(block_height),
+            ),
         )
         .await?
         .ok_or(BlockProductionError::RecoverableMempoolError)?;
 
+        // The fees of the explicitly provided transactions are unknown to the accumulator,
+        // so the prediction is only meaningful if all the transactions come from the mempool.
+        let predicted_fees = transactions.is_empty().then_some(predicted_fees);
+
         let block_body = BlockBody::new(block_reward, collected_transactions);
 
         // A synchronous channel that sends only when the mining/staking is done
@@ -532,6 +541,14 @@ impl BlockProduction {
 
         let signed_block_header = solver_result?;
         let block = Block::new_from_header(signed_block_header, block_body.clone())?;
+
+        if let Some(predicted_fees) = predicted_fees {
+            let block_id = block.get_id();
+            self.chainstate_handle
+                .call_mut(move |cs| cs.add_block_fee_prediction(block_id, predicted_fees))
+                .await?;
+        }
+
         Ok((block, job_finished_receiver))
     }
 
@@ -50,7 +50,7 @@ use crypto::{
 };
 use mempool::{
     error::{BlockConstructionError, TxValidationError},
-    tx_accumulator::{DefaultTxAccumulator, PackingStrategy},
+    tx_accumulator::{DefaultTxAccumulator, PackingStrategy, TxTemplatePolicy},
     tx_origin::LocalTxOrigin,
     TxOptions,
 };
@@ -108,9 +108,11 @@ mod collect_transactions {
                 &chain_config,
                 current_tip,
                 DUMMY_TIMESTAMP,
+                BlockHeight::one(),
                 vec![],
                 vec![],
                 PackingStrategy::FillSpaceFromMempool,
+                TxTemplatePolicy::default(),
             )
             .await;
 
@@ -155,9 +157,11 @@ mod collect_transactions {
                 &chain_config,
                 current_tip,
                 DUMMY_TIMESTAMP,
+                BlockHeight::one(),
                 vec![],
                 vec![],
                 PackingStrategy::LeaveEmptySpace,
+                TxTemplatePolicy::default(),
             )
             .await;
 
@@ -206,9 +210,11 @@ mod collect_transactions {
                     &chain_config,
                     current_tip,
                     DUMMY_TIMESTAMP,
+                    BlockHeight::one(),
                     vec![],
                     vec![],
                     PackingStrategy::FillSpaceFromMempool,
+                    TxTemplatePolicy::default(),
                 )
                 .await;
 
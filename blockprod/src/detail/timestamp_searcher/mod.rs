@@ -175,8 +175,7 @@ impl TimestampSearchData {
                     )
                 } else {
                     let block_id = get_block_id_from_height(chainstate, cur_height)?
-                        .classify(chain_config)
-                        .chain_block_id()
+                        .to_block_id(chain_config)
                         .expect("Genesis at non-zero height");
 
                     let block_index = get_existing_block_index(chainstate, &block_id)?;
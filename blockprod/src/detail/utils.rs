@@ -27,10 +27,12 @@ use common::{
         Block, ChainConfig, GenBlock, PoSStatus, PoolId, RequiredConsensus, SignedTransaction,
         Transaction,
     },
-    primitives::{Amount, BlockHeight, Id, Idable},
+    primitives::{Amount, BlockHeight, Fee, Id, Idable},
 };
 use mempool::{
-    tx_accumulator::{DefaultTxAccumulator, PackingStrategy, TransactionAccumulator},
+    tx_accumulator::{
+        DefaultTxAccumulator, PackingStrategy, TransactionAccumulator, TxTemplatePolicy,
+    },
     MempoolHandle,
 };
 
@@ -287,22 +289,28 @@ pub fn timestamp_add_secs(
     Ok(timestamp)
 }
 
-/// Collect transactions from the mempool.
+/// Collect transactions from the mempool, together with their total fees as computed by
+/// the mempool. Note that the fees of the explicitly provided transactions are not included.
 /// Ok(None) means that a recoverable error happened (such as that the mempool tip moved).
 pub async fn collect_transactions(
     mempool_handle: &MempoolHandle,
     chain_config: &ChainConfig,
     current_tip: Id<GenBlock>,
     current_tip_median_time_past: BlockTimestamp,
+    block_height: BlockHeight,
     transactions: Vec<SignedTransaction>,
     transaction_ids: Vec<Id<Transaction>>,
     packing_strategy: PackingStrategy,
-) -> Result<Option<Vec<SignedTransaction>>, BlockProductionError> {
-    let mut accumulator = Box::new(DefaultTxAccumulator::new(
-        chain_config.max_block_size_from_std_scripts(),
-        current_tip,
-        current_tip_median_time_past,
-    ));
+    template_policy: TxTemplatePolicy,
+) -> Result<Option<(Vec<SignedTransaction>, Fee)>, BlockProductionError> {
+    let mut accumulator = Box::new(
+        DefaultTxAccumulator::new(
+            chain_config.max_block_size_from_std_scripts(block_height),
+            current_tip,
+            current_tip_median_time_past,
+        )
+        .with_template_policy(template_policy),
+    );
 
     for transaction in transactions.into_iter() {
         let transaction_id = transaction.transaction().get_id();
@@ -316,8 +324,12 @@ pub async fn collect_transactions(
         .call(move |mempool| mempool.collect_txs(accumulator, transaction_ids, packing_strategy))
         .await??;
 
-    let transactions = returned_accumulator
-        .map(|returned_accumulator| returned_accumulator.transactions().to_vec());
+    let transactions = returned_accumulator.map(|returned_accumulator| {
+        (
+            returned_accumulator.transactions().to_vec(),
+            returned_accumulator.total_fees(),
+        )
+    });
 
     Ok(transactions)
 }
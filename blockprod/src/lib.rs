@@ -144,6 +144,8 @@ pub fn test_blockprod_config() -> BlockProdConfig {
         min_peers_to_produce_blocks: 0,
         skip_ibd_check: false,
         use_current_time_if_non_pos: false,
+        min_tx_fee_rate_for_inclusion: None,
+        reserved_block_space_for_priority_txs: 0,
     }
 }
 
@@ -209,7 +211,9 @@ mod tests {
                                 tip_sx.send(()).unwrap();
                             }
                         }
-                        mempool::event::MempoolEvent::TransactionProcessed(_) => (),
+                        mempool::event::MempoolEvent::TransactionProcessed(_)
+                        | mempool::event::MempoolEvent::TransactionReplaced(_)
+                        | mempool::event::MempoolEvent::TransactionRemoved(_) => (),
                     }
                 }))
             })
@@ -274,7 +278,14 @@ mod tests {
             max_db_commit_attempts: Default::default(),
             max_orphan_blocks: Default::default(),
             min_max_bootstrap_import_buffer_sizes: Default::default(),
+            fork_alert_depth: Default::default(),
+            max_block_index_cache_memory: Default::default(),
+            max_utxo_cache_memory: Default::default(),
+            max_orphan_blocks_memory: Default::default(),
+            max_cache_memory: Default::default(),
             allow_checkpoints_mismatch: Default::default(),
+            enable_block_filter_index: Default::default(),
+            undo_data_retention_depth: Default::default(),
         };
 
         let mempool_config = MempoolConfig::new();
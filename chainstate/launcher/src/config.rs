@@ -16,25 +16,44 @@
 //! Chainstate launcher configuration
 
 use chainstate::ChainstateConfig;
+use storage_lmdb::{memsize::MemSize, SyncMode};
+
+/// LMDB tuning options; `None` means that the backend's default is used.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LmdbConfig {
+    /// The initial size of the memory map
+    pub initial_map_size: Option<MemSize>,
+    /// The minimum amount by which the memory map grows when it's resized
+    pub min_resize_step: Option<MemSize>,
+    /// The maximum amount by which the memory map grows when it's resized
+    pub max_resize_step: Option<MemSize>,
+    /// The maximum number of simultaneous read transactions
+    pub max_readers: Option<u32>,
+    /// How durable the commits are
+    pub sync_mode: SyncMode,
+}
 
 /// Storage type to use
 #[must_use]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StorageBackendConfig {
-    Lmdb,
+    Lmdb(LmdbConfig),
+    Sqlite,
     InMemory,
 }
 
 impl Default for StorageBackendConfig {
     fn default() -> Self {
-        Self::Lmdb
+        Self::Lmdb(LmdbConfig::default())
     }
 }
 
 impl StorageBackendConfig {
     pub fn subdirectory_name(&self) -> Option<&str> {
         match self {
-            StorageBackendConfig::Lmdb => Some(crate::SUBDIRECTORY_LMDB),
+            StorageBackendConfig::Lmdb(_) => Some(crate::SUBDIRECTORY_LMDB),
+            StorageBackendConfig::Sqlite => Some(crate::SUBDIRECTORY_SQLITE),
             StorageBackendConfig::InMemory => None,
         }
     }
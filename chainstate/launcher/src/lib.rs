@@ -18,11 +18,13 @@
 mod config;
 mod storage_compatibility;
 
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use chainstate::InitializationError;
 use chainstate_storage::Transactional;
-use storage_lmdb::resize_callback::MapResizeCallback;
+use storage_lmdb::{
+    headroom::MapHeadroomCallback, resize_callback::MapResizeCallback, DatabaseResizeSettings,
+};
 
 // Some useful reexports
 pub use chainstate::{
@@ -30,11 +32,18 @@ pub use chainstate::{
     ChainstateSubsystem, DefaultTransactionVerificationStrategy,
 };
 pub use common::chain::ChainConfig;
-pub use config::{ChainstateLauncherConfig, StorageBackendConfig};
+pub use config::{ChainstateLauncherConfig, LmdbConfig, StorageBackendConfig};
+pub use storage_lmdb::{memsize::MemSize, SyncMode};
 
 /// Subdirectory under `datadir` where LMDB chainstate database is placed
 pub const SUBDIRECTORY_LMDB: &str = "chainstate-lmdb";
 
+/// Subdirectory under `datadir` where Sqlite chainstate database is placed
+pub const SUBDIRECTORY_SQLITE: &str = "chainstate-sqlite";
+
+/// Name of the Sqlite chainstate database file inside [SUBDIRECTORY_SQLITE]
+pub const SQLITE_DATABASE_FILE_NAME: &str = "chainstate.sqlite";
+
 pub use storage_compatibility::check_storage_compatibility;
 
 fn make_chainstate_and_storage_impl<B: storage::SharedBackend + 'static>(
@@ -64,7 +73,56 @@ fn make_chainstate_and_storage_impl<B: storage::SharedBackend + 'static>(
     Ok(chainstate)
 }
 
+fn make_lmdb_backend(
+    path: PathBuf,
+    config: LmdbConfig,
+    resize_callback: MapResizeCallback,
+) -> storage_lmdb::Lmdb {
+    let LmdbConfig {
+        initial_map_size,
+        min_resize_step,
+        max_resize_step,
+        max_readers,
+        sync_mode,
+    } = config;
+
+    let to_usize =
+        |size: MemSize| size.as_bytes().try_into().expect("MemSize to usize conversion failed");
+    let default_resize_settings = DatabaseResizeSettings::default();
+    let resize_settings = DatabaseResizeSettings {
+        min_resize_step: min_resize_step.map_or(default_resize_settings.min_resize_step, to_usize),
+        max_resize_step: max_resize_step.map_or(default_resize_settings.max_resize_step, to_usize),
+        ..default_resize_settings
+    };
+
+    let lmdb = storage_lmdb::Lmdb::new(
+        path,
+        initial_map_size.map(Into::into).unwrap_or_default(),
+        resize_settings,
+        resize_callback,
+    )
+    .with_sync_mode(sync_mode)
+    .with_headroom_callback(MapHeadroomCallback::new(Box::new(|headroom_info| {
+        if !headroom_info.has_enough_space() {
+            logging::log::info!(
+                "Lmdb map is too small for the upcoming write: {:?}",
+                headroom_info
+            );
+        }
+        metrics::node::LMDB_MAP_SIZE.set(headroom_info.map_size as u64);
+        metrics::node::LMDB_MAP_USED_SIZE.set(headroom_info.used_size as u64);
+        metrics::node::LMDB_ESTIMATED_WRITE_SIZE.set(headroom_info.estimated_size as u64);
+    })));
+
+    match max_readers {
+        Some(max_readers) => lmdb.with_max_readers(max_readers),
+        None => lmdb,
+    }
+}
+
 /// Create chainstate together with its storage
+///
+/// The LMDB map resizes are logged and reported via the node metrics.
 pub fn make_chainstate(
     datadir: &std::path::Path,
     chain_config: Arc<ChainConfig>,
@@ -75,22 +133,29 @@ pub fn make_chainstate(
         chainstate_config,
     } = config;
 
-    let lmdb_resize_callback = MapResizeCallback::new(Box::new(|resize_info| {
-        logging::log::info!("Lmdb resize happened: {:?}", resize_info)
+    let lmdb_resize_callback = MapResizeCallback::new(Box::new(move |resize_info| {
+        logging::log::info!("Lmdb resize happened: {:?}", resize_info);
+        metrics::node::LMDB_MAP_RESIZES.inc();
+        metrics::node::LMDB_MAP_SIZE.set(resize_info.new_size);
     }));
 
     // There is some code duplication because `make_chainstate_and_storage_impl` is called with
     // a different set of generic parameters in each case.
     match storage_backend {
-        StorageBackendConfig::Lmdb => {
-            let storage = storage_lmdb::Lmdb::new(
+        StorageBackendConfig::Lmdb(lmdb_config) => {
+            let storage = make_lmdb_backend(
                 datadir.join(SUBDIRECTORY_LMDB),
-                Default::default(),
-                Default::default(),
+                lmdb_config,
                 lmdb_resize_callback,
             );
             make_chainstate_and_storage_impl(storage, chain_config, chainstate_config)
         }
+        StorageBackendConfig::Sqlite => {
+            let storage = storage_sqlite::SharedSqlite::new(storage_sqlite::Sqlite::new(
+                datadir.join(SUBDIRECTORY_SQLITE).join(SQLITE_DATABASE_FILE_NAME),
+            ));
+            make_chainstate_and_storage_impl(storage, chain_config, chainstate_config)
+        }
         StorageBackendConfig::InMemory => {
             let storage = storage_inmemory::InMemory::new();
             make_chainstate_and_storage_impl(storage, chain_config, chainstate_config)
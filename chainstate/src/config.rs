@@ -15,11 +15,17 @@
 
 use std::time::Duration;
 
-use common::chain::{config::ChainType, ChainConfig};
-use utils::make_config_setting;
+use common::{
+    chain::{config::ChainType, ChainConfig},
+    primitives::BlockDistance,
+};
+use utils::{ensure, make_config_setting};
 
 const DEFAULT_MIN_IMPORT_BUFFER_SIZE: usize = 1 << 22; // 4 MB
 const DEFAULT_MAX_IMPORT_BUFFER_SIZE: usize = 1 << 26; // 64 MB
+const DEFAULT_MAX_BLOCK_INDEX_CACHE_MEMORY: usize = 1 << 27; // 128 MB
+const DEFAULT_MAX_UTXO_CACHE_MEMORY: usize = 1 << 28; // 256 MB
+const DEFAULT_MAX_ORPHAN_BLOCKS_MEMORY: usize = 1 << 27; // 128 MB
 
 make_config_setting!(MaxDbCommitAttempts, usize, 10);
 make_config_setting!(MaxOrphanBlocks, usize, 512);
@@ -32,6 +38,18 @@ make_config_setting!(
     )
 );
 make_config_setting!(MaxTipAge, Duration, Duration::from_secs(60 * 60 * 24));
+make_config_setting!(ForkAlertDepth, u64, 6);
+make_config_setting!(
+    MaxBlockIndexCacheMemory,
+    usize,
+    DEFAULT_MAX_BLOCK_INDEX_CACHE_MEMORY
+);
+make_config_setting!(MaxUtxoCacheMemory, usize, DEFAULT_MAX_UTXO_CACHE_MEMORY);
+make_config_setting!(
+    MaxOrphanBlocksMemory,
+    usize,
+    DEFAULT_MAX_ORPHAN_BLOCKS_MEMORY
+);
 
 /// The chainstate subsystem configuration.
 #[derive(Debug, Clone, Default)]
@@ -50,12 +68,47 @@ pub struct ChainstateConfig {
     /// tip time is less than this value.
     pub max_tip_age: MaxTipAge,
 
+    /// A warning is emitted when a block is added to a branch that forked off the main chain
+    /// more than this number of blocks ago.
+    pub fork_alert_depth: ForkAlertDepth,
+
+    /// The maximum amount of memory, in bytes, used to cache block indices.
+    pub max_block_index_cache_memory: MaxBlockIndexCacheMemory,
+
+    /// The maximum amount of memory, in bytes, used to cache utxos when a long chain of blocks
+    /// is verified in memory (e.g. a reorg); the unmodified utxos are evicted when exceeded.
+    pub max_utxo_cache_memory: MaxUtxoCacheMemory,
+
+    /// The maximum amount of memory, in bytes, used by the orphan blocks pool; random orphans
+    /// are evicted when exceeded.
+    pub max_orphan_blocks_memory: MaxOrphanBlocksMemory,
+
+    /// The global limit of the memory, in bytes, used by all the chainstate caches together.
+    ///
+    /// The memory used by the caches is accounted at runtime. Each cache may use the memory
+    /// the others leave unused, up to its own limit; when the global limit is reached, the caches
+    /// shrink to their shares of it, which are proportional to their own limits.
+    pub max_cache_memory: Option<usize>,
+
     /// If true, additional computationally-expensive consistency checks will be performed by
     /// the chainstate. The default value depends on the chain type.
     pub enable_heavy_checks: Option<bool>,
 
     /// If true, blocks and block headers will not be rejected if checkpoints mismatch is detected.
     pub allow_checkpoints_mismatch: Option<bool>,
+
+    /// If true, compact block filters will be built for the main chain blocks and served to
+    /// light clients.
+    pub enable_block_filter_index: Option<bool>,
+
+    /// If set, the undo data is only kept for this number of the most recent main chain blocks;
+    /// blocks below that depth can no longer be disconnected. The undo data is kept forever
+    /// by default. The depth can't be less than the max reorg depth of the chain.
+    ///
+    /// Note that the queries that need the undo data (utxo diffs, historical balances) fail
+    /// for the pruned blocks; and the compact block filters can't be built for them, so
+    /// the filter index can't be enabled after the undo data has been pruned.
+    pub undo_data_retention_depth: Option<u64>,
 }
 
 impl ChainstateConfig {
@@ -82,6 +135,40 @@ impl ChainstateConfig {
         self
     }
 
+    pub fn with_fork_alert_depth(mut self, fork_alert_depth: u64) -> Self {
+        self.fork_alert_depth = fork_alert_depth.into();
+        self
+    }
+
+    pub fn with_max_block_index_cache_memory(mut self, max_memory: usize) -> Self {
+        self.max_block_index_cache_memory = max_memory.into();
+        self
+    }
+
+    pub fn with_max_utxo_cache_memory(mut self, max_memory: usize) -> Self {
+        self.max_utxo_cache_memory = max_memory.into();
+        self
+    }
+
+    pub fn with_max_orphan_blocks_memory(mut self, max_memory: usize) -> Self {
+        self.max_orphan_blocks_memory = max_memory.into();
+        self
+    }
+
+    pub fn with_max_cache_memory(mut self, max_memory: usize) -> Self {
+        self.max_cache_memory = Some(max_memory);
+        self
+    }
+
+    /// The memory limits of the individual caches.
+    pub fn cache_memory_limits(&self) -> CacheMemoryLimits {
+        CacheMemoryLimits {
+            block_index_cache: *self.max_block_index_cache_memory,
+            utxo_cache: *self.max_utxo_cache_memory,
+            orphan_blocks: *self.max_orphan_blocks_memory,
+        }
+    }
+
     pub fn with_heavy_checks_enabled(mut self, enable: bool) -> Self {
         self.enable_heavy_checks = Some(enable);
         self
@@ -101,4 +188,110 @@ impl ChainstateConfig {
     pub fn checkpoints_mismatch_allowed(&self) -> bool {
         self.allow_checkpoints_mismatch.unwrap_or(false)
     }
+
+    pub fn with_block_filter_index_enabled(mut self, enable: bool) -> Self {
+        self.enable_block_filter_index = Some(enable);
+        self
+    }
+
+    pub fn block_filter_index_enabled(&self) -> bool {
+        self.enable_block_filter_index.unwrap_or(false)
+    }
+
+    pub fn with_undo_data_retention_depth(mut self, depth: u64) -> Self {
+        self.undo_data_retention_depth = Some(depth);
+        self
+    }
+
+    /// Check that the config can be used with the chain.
+    pub fn validate(&self, chain_config: &ChainConfig) -> Result<(), ChainstateConfigError> {
+        if let Some(retention_depth) = self.undo_data_retention_depth {
+            let max_depth_for_reorg = chain_config.max_depth_for_reorg();
+            // Blocks can only be disconnected while their undo data is available
+            ensure!(
+                BlockDistance::new(retention_depth.try_into().unwrap_or(i64::MAX))
+                    >= max_depth_for_reorg,
+                ChainstateConfigError::UndoDataRetentionDepthTooLow {
+                    retention_depth,
+                    max_depth_for_reorg,
+                }
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ChainstateConfigError {
+    #[error("Undo data retention depth {retention_depth} is less than the max reorg depth {max_depth_for_reorg}")]
+    UndoDataRetentionDepthTooLow {
+        retention_depth: u64,
+        max_depth_for_reorg: BlockDistance,
+    },
+}
+
+/// The maximum amounts of memory, in bytes, the individual chainstate caches may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMemoryLimits {
+    pub block_index_cache: usize,
+    pub utxo_cache: usize,
+    pub orphan_blocks: usize,
+}
+
+impl CacheMemoryLimits {
+    pub fn total(&self) -> usize {
+        self.block_index_cache
+            .saturating_add(self.utxo_cache)
+            .saturating_add(self.orphan_blocks)
+    }
+
+    /// Shrink the limits proportionally, so that their sum doesn't exceed `max_total`.
+    pub fn scaled_down_to(self, max_total: usize) -> Self {
+        let total = self.total();
+        if total <= max_total {
+            return self;
+        }
+
+        let scale = |limit: usize| (limit as u128 * max_total as u128 / total as u128) as usize;
+        Self {
+            block_index_cache: scale(self.block_index_cache),
+            utxo_cache: scale(self.utxo_cache),
+            orphan_blocks: scale(self.orphan_blocks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_memory_limits() {
+        let config = ChainstateConfig::new()
+            .with_max_block_index_cache_memory(100)
+            .with_max_utxo_cache_memory(300)
+            .with_max_orphan_blocks_memory(600);
+        let limits = CacheMemoryLimits {
+            block_index_cache: 100,
+            utxo_cache: 300,
+            orphan_blocks: 600,
+        };
+        assert_eq!(config.cache_memory_limits(), limits);
+
+        // The global limit is not reached
+        assert_eq!(limits.scaled_down_to(1000), limits);
+
+        // The limits are shrunk proportionally
+        assert_eq!(
+            limits.scaled_down_to(500),
+            CacheMemoryLimits {
+                block_index_cache: 50,
+                utxo_cache: 150,
+                orphan_blocks: 300,
+            }
+        );
+
+        assert_eq!(limits.scaled_down_to(0).total(), 0);
+    }
 }
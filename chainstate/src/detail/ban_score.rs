@@ -83,6 +83,8 @@ impl BanScore for BlockError {
             BlockError::InvariantErrorPoolDataPresentBalanceMissing(_, _) => 0,
 
             BlockError::UnexpectedHeightRange(_, _) => 0,
+            BlockError::UndoDataPruned { .. } => 0,
+            BlockError::UndoDataMissingForBlockFilter(_) => 0,
 
             BlockError::TokensAccountingError(err) => err.ban_score(),
             BlockError::OrdersAccountingError(err) => err.ban_score(),
@@ -358,6 +360,7 @@ impl BanScore for TokensError {
             TokensError::InvariantBrokenUndoIssuanceOnNonexistentToken(_) => 100,
             TokensError::InvariantBrokenRegisterIssuanceWithDuplicateId(_) => 100,
             TokensError::TokenMetadataUriTooLarge(_) => 100,
+            TokensError::TokenTickerAlreadyTaken(_, _, _) => 100,
         }
     }
 }
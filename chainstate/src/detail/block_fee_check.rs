@@ -0,0 +1,135 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use common::{
+    chain::Block,
+    primitives::{Amount, BlockHeight, Fee, Id},
+};
+use logging::log;
+use utils::bounded_fifo_map::BoundedFifoMap;
+
+/// The number of locally produced blocks for which the predicted fees are remembered until
+/// the blocks are connected.
+const MAX_PENDING_FEE_PREDICTIONS: usize = 100;
+
+/// The number of most recent fee discrepancies that are kept for inspection.
+const MAX_RECENT_FEE_DISCREPANCIES: usize = 100;
+
+/// Total fees of a block, as computed when the block was connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectedBlockFees {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    pub fees: Fee,
+}
+
+/// A locally produced block whose fees computed at connect time differ from the fees
+/// predicted when its transactions were collected from the mempool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct BlockFeeDiscrepancy {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    pub predicted_fees: Amount,
+    pub actual_fees: Amount,
+}
+
+/// Results of comparing the predicted fees of locally produced blocks with the actual ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct BlockFeeCheckStats {
+    /// Number of connected blocks for which a fee prediction was available
+    pub checked_blocks: u64,
+    /// Number of those blocks whose actual fees differed from the prediction
+    pub discrepancy_count: u64,
+    /// The most recent discrepancies, oldest first
+    pub recent_discrepancies: Vec<BlockFeeDiscrepancy>,
+}
+
+/// In-memory bookkeeping of the fee predictions for locally produced blocks.
+///
+/// A mismatch between the predicted and the actual fees means that the mempool and the
+/// transaction verifier disagree about the fee of some transaction.
+pub struct BlockFeeChecks {
+    predictions: BoundedFifoMap<Id<Block>, Fee>,
+    checked_blocks: u64,
+    discrepancy_count: u64,
+    recent_discrepancies: VecDeque<BlockFeeDiscrepancy>,
+}
+
+impl Default for BlockFeeChecks {
+    fn default() -> Self {
+        Self {
+            predictions: BoundedFifoMap::new(MAX_PENDING_FEE_PREDICTIONS),
+            checked_blocks: 0,
+            discrepancy_count: 0,
+            recent_discrepancies: VecDeque::new(),
+        }
+    }
+}
+
+impl BlockFeeChecks {
+    /// Remember the fees predicted for a block that hasn't been connected yet.
+    pub fn add_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee) {
+        self.predictions.insert(block_id, predicted_fees);
+    }
+
+    /// Compare the fees of a connected block with the prediction, if there is one.
+    pub fn check_connected_block(&mut self, connected: &ConnectedBlockFees) {
+        let predicted_fees = match self.predictions.remove(&connected.block_id) {
+            Some(fees) => fees,
+            None => return,
+        };
+        self.checked_blocks += 1;
+
+        if predicted_fees == connected.fees {
+            log::debug!(
+                "Fees of block {} match the prediction: {:?}",
+                connected.block_id,
+                connected.fees
+            );
+            return;
+        }
+
+        log::warn!(
+            "Fees of locally produced block {} at height {} differ from the prediction: predicted {:?}, actual {:?}",
+            connected.block_id,
+            connected.block_height,
+            predicted_fees,
+            connected.fees
+        );
+
+        self.discrepancy_count += 1;
+        self.recent_discrepancies.push_back(BlockFeeDiscrepancy {
+            block_id: connected.block_id,
+            block_height: connected.block_height,
+            predicted_fees: predicted_fees.0,
+            actual_fees: connected.fees.0,
+        });
+        while self.recent_discrepancies.len() > MAX_RECENT_FEE_DISCREPANCIES {
+            self.recent_discrepancies.pop_front();
+        }
+    }
+
+    pub fn stats(&self) -> BlockFeeCheckStats {
+        BlockFeeCheckStats {
+            checked_blocks: self.checked_blocks,
+            discrepancy_count: self.discrepancy_count,
+            recent_discrepancies: self.recent_discrepancies.iter().cloned().collect(),
+        }
+    }
+}
@@ -0,0 +1,480 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common::{
+    chain::{
+        output_value::OutputValue,
+        tokens::{TokenId, TokenIssuance},
+        AccountCommand, Block, Destination, SignedTransaction, Transaction, TxInput, TxOutput,
+    },
+    primitives::{BlockHeight, Id, Idable},
+};
+use serialization::Encode;
+use utils_networking::broadcaster;
+use utxo::{UtxosBlockUndo, UtxosTxUndo};
+
+/// A filter that selects the transactions relevant to a particular subscriber, e.g. a wallet
+/// or an indexer.
+///
+/// A transaction is relevant if any of its outputs, or any of the outputs it spends, pays to
+/// one of the destinations or involves one of the tokens. Account commands that manage one of
+/// the tokens are relevant as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockFilter {
+    destinations: BTreeSet<Destination>,
+    token_ids: BTreeSet<TokenId>,
+}
+
+impl BlockFilter {
+    pub fn new(destinations: BTreeSet<Destination>, token_ids: BTreeSet<TokenId>) -> Self {
+        Self {
+            destinations,
+            token_ids,
+        }
+    }
+
+    pub fn destinations(&self) -> &BTreeSet<Destination> {
+        &self.destinations
+    }
+
+    pub fn token_ids(&self) -> &BTreeSet<TokenId> {
+        &self.token_ids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.destinations.is_empty() && self.token_ids.is_empty()
+    }
+
+    fn item_matches(&self, item: BlockFilterItem<'_>) -> bool {
+        match item {
+            BlockFilterItem::Destination(destination) => self.destinations.contains(destination),
+            BlockFilterItem::Token(token_id) => self.token_ids.contains(token_id),
+        }
+    }
+
+    fn output_matches(&self, output: &TxOutput) -> bool {
+        output_items(output).into_iter().flatten().any(|item| self.item_matches(item))
+    }
+
+    fn input_matches(&self, input: &TxInput, spent_output: Option<&TxOutput>) -> bool {
+        input_items(input, spent_output)
+            .into_iter()
+            .flatten()
+            .any(|item| self.item_matches(item))
+    }
+
+    /// Check whether the transaction is relevant to the filter; `tx_undo` contains the outputs
+    /// spent by the transaction, if known.
+    pub fn transaction_matches(&self, tx: &Transaction, tx_undo: Option<&UtxosTxUndo>) -> bool {
+        let spent_outputs = tx_undo.map(|undo| undo.inner());
+
+        tx.outputs().iter().any(|output| self.output_matches(output))
+            || tx.inputs().iter().enumerate().any(|(idx, input)| {
+                let spent_output = spent_outputs
+                    .and_then(|outputs| outputs.get(idx))
+                    .and_then(|utxo| utxo.as_ref())
+                    .map(|utxo| utxo.output());
+                self.input_matches(input, spent_output)
+            })
+    }
+
+    /// Select the parts of the block that are relevant to the filter; return `None` if there
+    /// are none. `undo` is the block's undo data, which is needed to check the spent outputs.
+    pub fn filter_block(
+        &self,
+        block: &Block,
+        block_height: BlockHeight,
+        undo: Option<&UtxosBlockUndo>,
+    ) -> Option<FilteredBlock> {
+        let reward_matches =
+            block.block_reward().outputs().iter().any(|output| self.output_matches(output));
+
+        let transactions = block
+            .transactions()
+            .iter()
+            .filter(|tx| {
+                let tx_undo = undo.and_then(|undo| undo.tx_undos().get(&tx.transaction().get_id()));
+                self.transaction_matches(tx.transaction(), tx_undo)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        (reward_matches || !transactions.is_empty()).then(|| FilteredBlock {
+            block_id: block.get_id(),
+            block_height,
+            reward_matches,
+            transactions,
+        })
+    }
+}
+
+/// An item that the transactions are filtered by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockFilterItem<'a> {
+    Destination(&'a Destination),
+    Token(&'a TokenId),
+}
+
+impl BlockFilterItem<'_> {
+    /// The element that represents the item in compact block filters.
+    pub fn compact_filter_element(&self) -> Vec<u8> {
+        match self {
+            BlockFilterItem::Destination(destination) => {
+                let mut element = vec![0];
+                destination.encode_to(&mut element);
+                element
+            }
+            BlockFilterItem::Token(token_id) => {
+                let mut element = vec![1];
+                token_id.encode_to(&mut element);
+                element
+            }
+        }
+    }
+}
+
+/// The items of a single output or input; no output or input has more than 3 of them.
+type BlockFilterItems<'a> = [Option<BlockFilterItem<'a>>; 3];
+
+fn value_item(value: &OutputValue) -> Option<BlockFilterItem<'_>> {
+    value.token_id().map(BlockFilterItem::Token)
+}
+
+fn output_items(output: &TxOutput) -> BlockFilterItems<'_> {
+    let dest = |dest| Some(BlockFilterItem::Destination(dest));
+    let token = |token_id| Some(BlockFilterItem::Token(token_id));
+
+    match output {
+        TxOutput::Transfer(value, destination)
+        | TxOutput::LockThenTransfer(value, destination, _) => {
+            [value_item(value), dest(destination), None]
+        }
+        TxOutput::Burn(value) => [value_item(value), None, None],
+        TxOutput::CreateStakePool(_, data) => {
+            [dest(data.staker()), dest(data.decommission_key()), None]
+        }
+        TxOutput::ProduceBlockFromStake(destination, _)
+        | TxOutput::CreateDelegationId(destination, _) => [dest(destination), None, None],
+        TxOutput::DelegateStaking(_, _) | TxOutput::DataDeposit(_) => [None, None, None],
+        TxOutput::IssueFungibleToken(issuance) => match issuance.as_ref() {
+            TokenIssuance::V1(issuance) => [dest(&issuance.authority), None, None],
+        },
+        TxOutput::IssueNft(token_id, _, destination) => [token(token_id), dest(destination), None],
+        TxOutput::Htlc(value, htlc) => {
+            [value_item(value), dest(&htlc.spend_key), dest(&htlc.refund_key)]
+        }
+        TxOutput::CreateOrder(data) => {
+            [value_item(data.ask()), value_item(data.give()), dest(data.conclude_key())]
+        }
+    }
+}
+
+fn input_items<'a>(input: &'a TxInput, spent_output: Option<&'a TxOutput>) -> BlockFilterItems<'a> {
+    let token = |token_id| Some(BlockFilterItem::Token(token_id));
+
+    match input {
+        TxInput::Utxo(_) => spent_output.map_or([None, None, None], output_items),
+        TxInput::Account(_) | TxInput::OrderAccountCommand(_) => [None, None, None],
+        TxInput::AccountCommand(_, command) => match command {
+            AccountCommand::MintTokens(token_id, _)
+            | AccountCommand::UnmintTokens(token_id)
+            | AccountCommand::LockTokenSupply(token_id)
+            | AccountCommand::FreezeToken(token_id, _)
+            | AccountCommand::UnfreezeToken(token_id)
+            | AccountCommand::ChangeTokenMetadataUri(token_id, _) => [token(token_id), None, None],
+            AccountCommand::ChangeTokenAuthority(token_id, destination) => {
+                [token(token_id), Some(BlockFilterItem::Destination(destination)), None]
+            }
+            AccountCommand::ConcludeOrder(_) => [None, None, None],
+            AccountCommand::FillOrder(_, _, destination) => {
+                [Some(BlockFilterItem::Destination(destination)), None, None]
+            }
+        },
+    }
+}
+
+/// The elements of the compact filter of the block, i.e. all the items that [BlockFilter]
+/// may match the block's reward and transactions by. `undo` is the block's undo data, which
+/// contains the spent outputs.
+pub fn compact_filter_elements(block: &Block, undo: Option<&UtxosBlockUndo>) -> BTreeSet<Vec<u8>> {
+    let reward_items = block.block_reward().outputs().iter().flat_map(output_items);
+
+    let tx_items = block.transactions().iter().flat_map(|tx| {
+        let tx = tx.transaction();
+        let spent_outputs = undo
+            .and_then(|undo| undo.tx_undos().get(&tx.get_id()))
+            .map(|tx_undo| tx_undo.inner());
+
+        let output_items = tx.outputs().iter().flat_map(output_items);
+        let input_items = tx.inputs().iter().enumerate().flat_map(move |(idx, input)| {
+            let spent_output = spent_outputs
+                .and_then(|outputs| outputs.get(idx))
+                .and_then(|utxo| utxo.as_ref())
+                .map(|utxo| utxo.output());
+            input_items(input, spent_output)
+        });
+        output_items.chain(input_items)
+    });
+
+    reward_items
+        .chain(tx_items)
+        .flatten()
+        .map(|item| item.compact_filter_element())
+        .collect()
+}
+
+/// The parts of a main chain block that are relevant to a particular filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilteredBlock {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    /// Whether any of the block reward outputs is relevant to the filter.
+    pub reward_matches: bool,
+    /// The relevant transactions, in the block order.
+    pub transactions: Vec<SignedTransaction>,
+}
+
+/// An event delivered to a filtered subscriber.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilteredChainEvent {
+    /// The blocks above the specified height have been disconnected from the main chain;
+    /// the subscriber should forget about the ones it has received before.
+    Reorg(BlockHeight),
+    /// A block with relevant transactions has been connected to the main chain.
+    NewBlock(FilteredBlock),
+}
+
+pub struct FilteredSubscriber {
+    filter: BlockFilter,
+    events: broadcaster::Broadcaster<FilteredChainEvent>,
+}
+
+impl FilteredSubscriber {
+    pub fn new(filter: BlockFilter) -> (Self, broadcaster::Receiver<FilteredChainEvent>) {
+        let mut events = broadcaster::Broadcaster::new();
+        let receiver = events.subscribe();
+        (Self { filter, events }, receiver)
+    }
+
+    pub fn filter(&self) -> &BlockFilter {
+        &self.filter
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.events.num_subscribers() == 0
+    }
+
+    pub fn send(&mut self, event: &FilteredChainEvent) {
+        self.events.broadcast(event)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use common::{
+        chain::{
+            block::{timestamp::BlockTimestamp, BlockReward, ConsensusData},
+            signature::inputsig::InputWitness,
+            OutPointSourceId, UtxoOutPoint,
+        },
+        primitives::{Amount, H256},
+    };
+    use crypto::key::{KeyKind, PrivateKey};
+    use randomness::Rng;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+    use utxo::Utxo;
+
+    fn random_destination(rng: &mut impl Rng) -> Destination {
+        let (_, pk) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        Destination::PublicKey(pk)
+    }
+
+    fn make_tx(inputs: Vec<TxInput>, outputs: Vec<TxOutput>) -> SignedTransaction {
+        let witnesses = vec![InputWitness::NoSignature(None); inputs.len()];
+        SignedTransaction::new(Transaction::new(0, inputs, outputs).unwrap(), witnesses).unwrap()
+    }
+
+    fn random_input(rng: &mut impl Rng) -> TxInput {
+        TxInput::Utxo(UtxoOutPoint::new(
+            OutPointSourceId::Transaction(Id::new(H256::random_using(rng))),
+            0,
+        ))
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn filter_block(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let dest = random_destination(&mut rng);
+        let other_dest = random_destination(&mut rng);
+        let token_id = TokenId::random_using(&mut rng);
+        let other_token_id = TokenId::random_using(&mut rng);
+        let coins = OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1000)));
+
+        let filter = BlockFilter::new([dest.clone()].into(), [token_id].into());
+
+        // Pays to the filtered destination.
+        let tx_to_dest = make_tx(
+            vec![random_input(&mut rng)],
+            vec![TxOutput::Transfer(coins.clone(), dest.clone())],
+        );
+        // Transfers the filtered token to some other destination.
+        let tx_with_token = make_tx(
+            vec![random_input(&mut rng)],
+            vec![TxOutput::Transfer(
+                OutputValue::TokenV1(token_id, Amount::from_atoms(1)),
+                other_dest.clone(),
+            )],
+        );
+        // Spends an output that belonged to the filtered destination.
+        let tx_spending_from_dest = make_tx(
+            vec![random_input(&mut rng)],
+            vec![TxOutput::Transfer(coins.clone(), other_dest.clone())],
+        );
+        // Irrelevant.
+        let unrelated_tx = make_tx(
+            vec![random_input(&mut rng)],
+            vec![
+                TxOutput::Transfer(coins.clone(), other_dest.clone()),
+                TxOutput::Burn(OutputValue::TokenV1(other_token_id, Amount::from_atoms(1))),
+            ],
+        );
+
+        let block = Block::new(
+            vec![
+                tx_to_dest.clone(),
+                tx_with_token.clone(),
+                tx_spending_from_dest.clone(),
+                unrelated_tx,
+            ],
+            Id::new(H256::random_using(&mut rng)),
+            BlockTimestamp::from_int_seconds(rng.gen()),
+            ConsensusData::None,
+            BlockReward::new(vec![]),
+        )
+        .unwrap();
+        let height = BlockHeight::new(rng.gen_range(1..1000));
+
+        let undo = UtxosBlockUndo::from_data(
+            None,
+            [(
+                tx_spending_from_dest.transaction().get_id(),
+                UtxosTxUndo::new(vec![Some(Utxo::new_for_blockchain(
+                    TxOutput::Transfer(coins, dest.clone()),
+                    BlockHeight::new(0),
+                ))]),
+            )]
+            .into(),
+            BTreeSet::new(),
+            BTreeSet::new(),
+        );
+
+        assert_eq!(
+            filter.filter_block(&block, height, Some(&undo)),
+            Some(FilteredBlock {
+                block_id: block.get_id(),
+                block_height: height,
+                reward_matches: false,
+                transactions: vec![
+                    tx_to_dest.clone(),
+                    tx_with_token.clone(),
+                    tx_spending_from_dest
+                ],
+            })
+        );
+
+        // Without the undo data, the spending transaction can't be detected.
+        assert_eq!(
+            filter.filter_block(&block, height, None),
+            Some(FilteredBlock {
+                block_id: block.get_id(),
+                block_height: height,
+                reward_matches: false,
+                transactions: vec![tx_to_dest, tx_with_token],
+            })
+        );
+
+        let other_filter = BlockFilter::new([random_destination(&mut rng)].into(), [].into());
+        assert_eq!(other_filter.filter_block(&block, height, Some(&undo)), None);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn compact_filter(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let dest = random_destination(&mut rng);
+        let spent_dest = random_destination(&mut rng);
+        let reward_dest = random_destination(&mut rng);
+        let token_id = TokenId::random_using(&mut rng);
+        let coins = OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1000)));
+
+        let tx = make_tx(
+            vec![random_input(&mut rng)],
+            vec![TxOutput::Transfer(
+                OutputValue::TokenV1(token_id, Amount::from_atoms(1)),
+                dest.clone(),
+            )],
+        );
+        let block = Block::new(
+            vec![tx.clone()],
+            Id::new(H256::random_using(&mut rng)),
+            BlockTimestamp::from_int_seconds(rng.gen()),
+            ConsensusData::None,
+            BlockReward::new(vec![TxOutput::Transfer(coins.clone(), reward_dest.clone())]),
+        )
+        .unwrap();
+        let undo = UtxosBlockUndo::from_data(
+            None,
+            [(
+                tx.transaction().get_id(),
+                UtxosTxUndo::new(vec![Some(Utxo::new_for_blockchain(
+                    TxOutput::Transfer(coins, spent_dest.clone()),
+                    BlockHeight::new(0),
+                ))]),
+            )]
+            .into(),
+            BTreeSet::new(),
+            BTreeSet::new(),
+        );
+
+        let element = |item: BlockFilterItem| item.compact_filter_element();
+        assert_eq!(
+            compact_filter_elements(&block, Some(&undo)),
+            BTreeSet::from([
+                element(BlockFilterItem::Destination(&dest)),
+                element(BlockFilterItem::Destination(&spent_dest)),
+                element(BlockFilterItem::Destination(&reward_dest)),
+                element(BlockFilterItem::Token(&token_id)),
+            ])
+        );
+        assert_eq!(
+            compact_filter_elements(&block, None),
+            BTreeSet::from([
+                element(BlockFilterItem::Destination(&dest)),
+                element(BlockFilterItem::Destination(&reward_dest)),
+                element(BlockFilterItem::Token(&token_id)),
+            ])
+        );
+    }
+}
@@ -0,0 +1,345 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use chainstate_types::BlockIndex;
+use common::{chain::Block, primitives::Id};
+use serialization::Encode;
+use utils::bounded_fifo_map::BoundedFifoMap;
+
+use super::cache_memory::{CacheKind, CacheMemoryBudget};
+
+/// Block indices of the committed blocks, shared by all the chainstate db transactions.
+///
+/// The cache only ever contains the values that are present in the database; the block index
+/// writes done via a read-write transaction are only applied to it after the transaction has
+/// been committed (see `ChainstateRef::commit_db_tx`).
+///
+/// The memory usage is accounted in the chainstate cache memory budget; when the cache can't
+/// grow any more, the entries that were inserted first are evicted.
+pub struct BlockIndexCache {
+    memory_budget: Arc<CacheMemoryBudget>,
+    inner: RwLock<BlockIndexCacheInner>,
+}
+
+struct BlockIndexCacheInner {
+    entries: BoundedFifoMap<Id<Block>, BlockIndex>,
+    memory_usage: usize,
+}
+
+impl BlockIndexCache {
+    pub fn new(memory_budget: Arc<CacheMemoryBudget>) -> Self {
+        Self {
+            memory_budget,
+            inner: RwLock::new(BlockIndexCacheInner {
+                entries: BoundedFifoMap::unbounded(),
+                memory_usage: 0,
+            }),
+        }
+    }
+
+    /// The amount of memory the cache may currently use.
+    pub fn max_memory_usage(&self) -> usize {
+        self.memory_budget.max_usage(CacheKind::BlockIndexCache)
+    }
+
+    /// The approximate memory usage of the cached entries.
+    pub fn memory_usage(&self) -> usize {
+        self.read().memory_usage
+    }
+
+    /// The approximate memory needed to cache the block index.
+    pub fn entry_size(block_index: &BlockIndex) -> usize {
+        std::mem::size_of::<(Id<Block>, BlockIndex)>()
+            + std::mem::size_of::<Id<Block>>()
+            + block_index.block_header().encoded_size()
+    }
+
+    pub fn get(&self, block_id: &Id<Block>) -> Option<BlockIndex> {
+        let result = self.read().entries.get(block_id).cloned();
+
+        if result.is_some() {
+            metrics::node::BLOCK_INDEX_CACHE_HITS.inc();
+        } else {
+            metrics::node::BLOCK_INDEX_CACHE_MISSES.inc();
+        }
+
+        result
+    }
+
+    /// Insert or update the entry for the block, evicting the oldest entries if needed.
+    pub fn insert(&self, block_index: BlockIndex) {
+        let entry_size = Self::entry_size(&block_index);
+        let max_memory_usage = self.max_memory_usage();
+        if entry_size > max_memory_usage {
+            return;
+        }
+
+        let mut inner = self.write();
+        let block_id = *block_index.block_id();
+        if let Some(old_block_index) = inner.entries.insert(block_id, block_index) {
+            inner.memory_usage -= Self::entry_size(&old_block_index);
+        }
+        inner.memory_usage += entry_size;
+
+        while inner.memory_usage > max_memory_usage {
+            let (_, old_block_index) =
+                inner.entries.pop_oldest().expect("cache can't be empty at this point");
+            inner.memory_usage -= Self::entry_size(&old_block_index);
+        }
+
+        self.update_memory_usage(inner.memory_usage);
+    }
+
+    pub fn remove(&self, block_id: &Id<Block>) {
+        let mut inner = self.write();
+        if let Some(old_block_index) = inner.entries.remove(block_id) {
+            inner.memory_usage -= Self::entry_size(&old_block_index);
+        }
+
+        self.update_memory_usage(inner.memory_usage);
+    }
+
+    /// Apply the block index changes made by a committed transaction; `None` means that
+    /// the block index has been deleted.
+    pub fn apply_changes(&self, changes: BTreeMap<Id<Block>, Option<BlockIndex>>) {
+        for (block_id, block_index) in changes {
+            match block_index {
+                Some(block_index) => self.insert(block_index),
+                None => self.remove(&block_id),
+            }
+        }
+    }
+
+    fn update_memory_usage(&self, memory_usage: usize) {
+        self.memory_budget.set_usage(CacheKind::BlockIndexCache, memory_usage);
+        metrics::node::BLOCK_INDEX_CACHE_MEMORY_USAGE.set(memory_usage as u64);
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BlockIndexCacheInner> {
+        self.inner.read().expect("poisoned lock")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BlockIndexCacheInner> {
+        self.inner.write().expect("poisoned lock")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use chainstate_storage::{inmemory::Store, BlockchainStorageRead, Transactional};
+    use chainstate_types::BlockStatus;
+    use common::{
+        chain::{
+            block::{timestamp::BlockTimestamp, BlockReward, ConsensusData},
+            config::create_unit_test_config,
+            ChainConfig,
+        },
+        primitives::{BlockHeight, Idable},
+        Uint256,
+    };
+
+    use super::*;
+    use crate::{
+        detail::tx_verification_strategy::DefaultTransactionVerificationStrategy, BlockSource,
+        CacheMemoryLimits, Chainstate, ChainstateConfig,
+    };
+
+    fn make_memory_budget(
+        block_index_cache: usize,
+        orphan_blocks: usize,
+        max_total: Option<usize>,
+    ) -> Arc<CacheMemoryBudget> {
+        let limits = CacheMemoryLimits {
+            block_index_cache,
+            utxo_cache: 0,
+            orphan_blocks,
+        };
+        Arc::new(CacheMemoryBudget::new(limits, max_total))
+    }
+
+    fn chain_blocks(chain_config: &ChainConfig, count: usize) -> Vec<Block> {
+        let initial_time = chain_config.genesis_block().timestamp().as_int_seconds();
+        let mut prev = chain_config.genesis_block_id();
+        (0..count)
+            .map(|i| {
+                let block = Block::new(
+                    vec![],
+                    prev,
+                    BlockTimestamp::from_int_seconds(initial_time + i as u64 + 1),
+                    ConsensusData::None,
+                    BlockReward::new(Vec::new()),
+                )
+                .expect("Block creation failed");
+                prev = block.get_id().into();
+                block
+            })
+            .collect()
+    }
+
+    fn make_block_index(block: &Block, height: u64) -> BlockIndex {
+        BlockIndex::new(
+            block,
+            Uint256::ZERO,
+            block.prev_block_id(),
+            BlockHeight::new(height),
+            block.timestamp(),
+            0,
+            BlockStatus::new(),
+        )
+    }
+
+    fn assert_cached(cache: &BlockIndexCache, block_index: &BlockIndex) {
+        let cached = cache.get(block_index.block_id()).expect("block index must be cached");
+        assert!(cached.is_identical_to(block_index));
+    }
+
+    fn assert_not_cached(cache: &BlockIndexCache, block_index: &BlockIndex) {
+        assert!(cache.get(block_index.block_id()).is_none());
+    }
+
+    #[test]
+    fn insert_evict_remove() {
+        let chain_config = create_unit_test_config();
+        let block_indices = chain_blocks(&chain_config, 5)
+            .iter()
+            .enumerate()
+            .map(|(i, block)| make_block_index(block, i as u64 + 1))
+            .collect::<Vec<_>>();
+        let entry_size = BlockIndexCache::entry_size(&block_indices[0]);
+
+        let budget = make_memory_budget(entry_size * 3, 0, None);
+        let cache = BlockIndexCache::new(Arc::clone(&budget));
+        for block_index in &block_indices {
+            cache.insert(block_index.clone());
+        }
+
+        // The entries that were inserted first have been evicted
+        for block_index in &block_indices[..2] {
+            assert_not_cached(&cache, block_index);
+        }
+        for block_index in &block_indices[2..] {
+            assert_cached(&cache, block_index);
+        }
+        assert_eq!(cache.memory_usage(), entry_size * 3);
+
+        // Updating an entry doesn't evict anything
+        let updated = block_indices[2].clone().with_status(BlockStatus::new_fully_checked());
+        cache.insert(updated.clone());
+        assert_cached(&cache, &updated);
+        assert_cached(&cache, &block_indices[4]);
+        assert_eq!(cache.memory_usage(), entry_size * 3);
+
+        cache.remove(block_indices[3].block_id());
+        assert_not_cached(&cache, &block_indices[3]);
+        assert_eq!(cache.memory_usage(), entry_size * 2);
+
+        cache.apply_changes(BTreeMap::from([
+            (*block_indices[0].block_id(), Some(block_indices[0].clone())),
+            (*block_indices[4].block_id(), None),
+        ]));
+        assert_cached(&cache, &block_indices[0]);
+        assert_not_cached(&cache, &block_indices[4]);
+        assert_eq!(cache.memory_usage(), entry_size * 2);
+        assert_eq!(budget.usage(CacheKind::BlockIndexCache), entry_size * 2);
+    }
+
+    #[test]
+    fn shrink_under_memory_pressure() {
+        let chain_config = create_unit_test_config();
+        let block_indices = chain_blocks(&chain_config, 5)
+            .iter()
+            .enumerate()
+            .map(|(i, block)| make_block_index(block, i as u64 + 1))
+            .collect::<Vec<_>>();
+        let entry_size = BlockIndexCache::entry_size(&block_indices[0]);
+
+        // Both caches get half of the global limit, but either may use all of it
+        let budget = make_memory_budget(entry_size * 4, entry_size * 4, Some(entry_size * 4));
+        let cache = BlockIndexCache::new(Arc::clone(&budget));
+        for block_index in &block_indices[..4] {
+            cache.insert(block_index.clone());
+        }
+        assert_eq!(cache.memory_usage(), entry_size * 4);
+
+        // The orphan blocks take their share back, so the cache shrinks to its own share
+        budget.set_usage(CacheKind::OrphanBlocks, entry_size * 2);
+        cache.insert(block_indices[4].clone());
+        assert_eq!(cache.memory_usage(), entry_size * 2);
+        for block_index in &block_indices[..3] {
+            assert_not_cached(&cache, block_index);
+        }
+        for block_index in &block_indices[3..] {
+            assert_cached(&cache, block_index);
+        }
+        assert_eq!(budget.total_usage(), entry_size * 4);
+    }
+
+    #[test]
+    fn cache_matches_storage() {
+        utils::concurrency::model(|| {
+            let chain_config = Arc::new(create_unit_test_config());
+            let blocks = chain_blocks(&chain_config, 20);
+            let entry_size = BlockIndexCache::entry_size(&make_block_index(&blocks[0], 1));
+            let cached_count = 10;
+
+            let mut chainstate = Chainstate::new(
+                chain_config.clone(),
+                ChainstateConfig::new()
+                    .with_max_block_index_cache_memory(entry_size * cached_count),
+                Store::new_empty().unwrap(),
+                DefaultTransactionVerificationStrategy::new(),
+                None,
+                Default::default(),
+            )
+            .unwrap();
+
+            for block in &blocks {
+                chainstate.process_block(block.clone().into(), BlockSource::Local).unwrap();
+            }
+
+            let check_cache =
+                |chainstate: &Chainstate<Store, DefaultTransactionVerificationStrategy>| {
+                    let cache = &chainstate.block_index_cache;
+                    assert!(cache.memory_usage() <= cache.max_memory_usage());
+
+                    let db_tx = chainstate.chainstate_storage.transaction_ro().unwrap();
+                    for block in &blocks {
+                        if let Some(block_index) = cache.get(&block.get_id()) {
+                            let stored = db_tx.get_block_index(&block.get_id()).unwrap().unwrap();
+                            assert!(block_index.is_identical_to(&stored));
+                        }
+                    }
+                };
+            check_cache(&chainstate);
+
+            // The most recent mainchain blocks are loaded into the cache at startup
+            chainstate.block_index_cache =
+                BlockIndexCache::new(Arc::clone(&chainstate.cache_memory_budget));
+            chainstate.load_block_index_cache().unwrap();
+            check_cache(&chainstate);
+            for (i, block) in blocks.iter().enumerate() {
+                let is_cached = chainstate.block_index_cache.get(&block.get_id()).is_some();
+                assert_eq!(is_cached, i >= blocks.len() - cached_count);
+            }
+        });
+    }
+}
@@ -253,7 +253,7 @@ where
     }
 
     fn gen_block_id_to_normal(&self, id: &Id<GenBlock>) -> Option<Id<Block>> {
-        id.classify(self.chain_config()).chain_block_id()
+        id.to_block_id(self.chain_config())
     }
 }
 
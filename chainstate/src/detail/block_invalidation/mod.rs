@@ -145,8 +145,7 @@ impl<'a, S: BlockchainStorage, V: TransactionVerificationStrategy> BlockInvalida
 
         let best_block_id = best_block_index
             .block_id()
-            .classify(&self.chainstate.chain_config)
-            .chain_block_id()
+            .to_block_id(&self.chainstate.chain_config)
             .expect("Attempt to invalidate genesis");
 
         ensure!(
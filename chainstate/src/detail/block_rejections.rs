@@ -0,0 +1,218 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use common::{
+    chain::Block,
+    primitives::{time::Time, Id},
+};
+use p2p_types::PeerId;
+use utils::bounded_fifo_map::BoundedFifoMap;
+
+use super::{ban_score::BanScore, BlockError, BlockSource};
+
+/// Repeated rejections of the same block with the same error are logged in full at most once
+/// per this period; the rest are only counted and reported as a summary afterwards.
+pub const BLOCK_REJECTION_SUMMARY_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// The maximum number of (block id, error) pairs whose rejections are being tracked.
+const MAX_TRACKED_BLOCK_REJECTIONS: usize = 1000;
+
+/// What should be logged for a particular block rejection.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BlockRejectionLogAction {
+    /// The error should be logged in full.
+    Log,
+    /// The error has already been logged recently; nothing should be logged.
+    Suppress,
+    /// The error has been suppressed during the last period; a summary should be logged.
+    Summarize(BlockRejectionSummary),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BlockRejectionSummary {
+    /// The number of rejections since the error was last logged, including the current one.
+    pub rejection_count: u64,
+    /// The number of distinct peers the rejected block came from.
+    pub peer_count: usize,
+    /// The time since the error was last logged.
+    pub elapsed: Duration,
+}
+
+struct RejectionStats {
+    period_start: Time,
+    suppressed_count: u64,
+    peers: BTreeSet<PeerId>,
+}
+
+type RejectionKey = (Id<Block>, String);
+
+/// In-memory statistics of the recent block rejections, used to deduplicate the corresponding
+/// log messages.
+///
+/// Only the errors that affect the ban score of the peer are deduplicated, because these are
+/// the ones that a misbehaving peer can trigger at will; other errors signify a local problem
+/// and are always logged.
+pub struct RecentBlockRejections {
+    rejections: BoundedFifoMap<RejectionKey, RejectionStats>,
+}
+
+impl Default for RecentBlockRejections {
+    fn default() -> Self {
+        Self {
+            rejections: BoundedFifoMap::new(MAX_TRACKED_BLOCK_REJECTIONS),
+        }
+    }
+}
+
+impl RecentBlockRejections {
+    /// Record the rejection of the block and decide what should be logged about it.
+    pub fn record(
+        &mut self,
+        block_id: Id<Block>,
+        error: &BlockError,
+        source: BlockSource,
+        now: Time,
+    ) -> BlockRejectionLogAction {
+        if error.ban_score() == 0 {
+            return BlockRejectionLogAction::Log;
+        }
+
+        let key = (block_id, error.to_string());
+
+        let stats = match self.rejections.get_mut(&key) {
+            Some(stats) => stats,
+            None => {
+                self.rejections.insert(
+                    key,
+                    RejectionStats {
+                        period_start: now,
+                        suppressed_count: 0,
+                        peers: BTreeSet::new(),
+                    },
+                );
+                return BlockRejectionLogAction::Log;
+            }
+        };
+
+        if let BlockSource::Peer(peer_id) = source {
+            stats.peers.insert(peer_id);
+        }
+
+        let elapsed = now.saturating_sub(stats.period_start);
+        if elapsed < BLOCK_REJECTION_SUMMARY_PERIOD {
+            stats.suppressed_count += 1;
+            return BlockRejectionLogAction::Suppress;
+        }
+
+        let action = if stats.suppressed_count == 0 {
+            BlockRejectionLogAction::Log
+        } else {
+            BlockRejectionLogAction::Summarize(BlockRejectionSummary {
+                rejection_count: stats.suppressed_count + 1,
+                peer_count: stats.peers.len(),
+                elapsed,
+            })
+        };
+
+        stats.period_start = now;
+        stats.suppressed_count = 0;
+        stats.peers.clear();
+
+        action
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use common::primitives::H256;
+
+    fn time(secs: u64) -> Time {
+        Time::from_secs_since_epoch(secs)
+    }
+
+    #[test]
+    fn repeated_rejections_are_summarized() {
+        let mut rejections = RecentBlockRejections::default();
+        let block_id = Id::<Block>::new(H256::from_low_u64_be(1));
+        let error = BlockError::InvalidBlockAlreadyProcessed(block_id);
+        let period = BLOCK_REJECTION_SUMMARY_PERIOD.as_secs();
+
+        assert_eq!(
+            rejections.record(
+                block_id,
+                &error,
+                BlockSource::Peer(PeerId::from_u64(1)),
+                time(0)
+            ),
+            BlockRejectionLogAction::Log
+        );
+        for i in 1..10 {
+            assert_eq!(
+                rejections.record(
+                    block_id,
+                    &error,
+                    BlockSource::Peer(PeerId::from_u64(i % 3)),
+                    time(i)
+                ),
+                BlockRejectionLogAction::Suppress
+            );
+        }
+        assert_eq!(
+            rejections.record(block_id, &error, BlockSource::Local, time(period)),
+            BlockRejectionLogAction::Summarize(BlockRejectionSummary {
+                rejection_count: 10,
+                peer_count: 3,
+                elapsed: BLOCK_REJECTION_SUMMARY_PERIOD,
+            })
+        );
+
+        // No rejections during the next period, so the error is logged in full again.
+        assert_eq!(
+            rejections.record(block_id, &error, BlockSource::Local, time(period * 2)),
+            BlockRejectionLogAction::Log
+        );
+
+        // A different block is tracked separately.
+        let other_block_id = Id::<Block>::new(H256::from_low_u64_be(2));
+        let other_error = BlockError::InvalidBlockAlreadyProcessed(other_block_id);
+        assert_eq!(
+            rejections.record(
+                other_block_id,
+                &other_error,
+                BlockSource::Local,
+                time(period * 2)
+            ),
+            BlockRejectionLogAction::Log
+        );
+    }
+
+    #[test]
+    fn errors_without_ban_score_are_always_logged() {
+        let mut rejections = RecentBlockRejections::default();
+        let block_id = Id::<Block>::new(H256::from_low_u64_be(1));
+        let error = BlockError::BlockAlreadyProcessed(block_id);
+
+        for i in 0..10 {
+            assert_eq!(
+                rejections.record(block_id, &error, BlockSource::Local, time(i)),
+                BlockRejectionLogAction::Log
+            );
+        }
+    }
+}
@@ -13,16 +13,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::io::{BufRead, Write};
+use std::{
+    collections::BTreeSet,
+    io::{BufRead, Write},
+};
 
-use chainstate_storage::BlockchainStorageRead;
+use chainstate_storage::{
+    BlockchainStorage, BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
+};
 use chainstate_types::{BlockIndex, PropertyQueryError};
-use common::{chain::Block, primitives::id::WithId};
+use common::{
+    chain::{Block, ChainConfig, GenBlock, GenBlockId},
+    primitives::{id::WithId, Id, Idable},
+};
+use logging::log;
 use serialization::{Decode, Encode};
+use utils::ensure;
 
-use crate::{BlockError, ChainstateConfig};
+use crate::{BlockError, BlockSource, ChainstateConfig};
 
-use super::{query::ChainstateQuery, tx_verification_strategy::TransactionVerificationStrategy};
+use super::{
+    query::ChainstateQuery, tx_verification_strategy::TransactionVerificationStrategy, Chainstate,
+};
+
+/// The storage space taken by an imported block together with its undo data and the index
+/// entries it creates, relative to the size of the block in the bootstrap file. This is a rough
+/// upper estimate; it's used to make sure the storage can fit a chunk of blocks before importing it.
+const IMPORTED_BLOCK_SPACE_FACTOR: usize = 3;
 
 #[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
 pub enum BootstrapError {
@@ -34,6 +51,15 @@ pub enum BootstrapError {
     BlockProcessing(#[from] BlockError),
     #[error("Block import error: {0}")]
     FailedToReadProperty(#[from] PropertyQueryError),
+    #[error("Blockchain storage error: {0}")]
+    StorageError(#[from] chainstate_storage::Error),
+    #[error("Parent {parent_id} of block {block_id} is neither known nor precedes it in the file")]
+    UnknownParent {
+        block_id: Id<Block>,
+        parent_id: Id<GenBlock>,
+    },
+    #[error("Block {0} body doesn't match the merkle roots in its header")]
+    MerkleRootMismatch(Id<Block>),
 }
 
 impl From<std::io::Error> for BootstrapError {
@@ -42,60 +68,265 @@ impl From<std::io::Error> for BootstrapError {
     }
 }
 
-pub fn import_bootstrap_stream<P, S: std::io::Read>(
+/// The progress of a bootstrap import, reported after each imported chunk of blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootstrapImportProgress {
+    /// The last block imported by a previous, interrupted, import, if any.
+    pub resumed_after: Option<Id<Block>>,
+    /// The number of blocks processed so far.
+    pub blocks_imported: u64,
+    /// The number of blocks skipped so far because they had already been imported.
+    pub blocks_skipped: u64,
+    /// The number of bytes consumed from the stream so far.
+    pub bytes_read: u64,
+}
+
+/// The chainstate operations that the bootstrap import relies on.
+pub trait BootstrapImportTarget {
+    fn chain_config(&self) -> &ChainConfig;
+
+    /// Whether the block has already been processed and stored.
+    fn is_block_stored(&self, block_id: &Id<Block>) -> Result<bool, BootstrapError>;
+
+    fn process_block(&mut self, block: WithId<Block>) -> Result<Option<BlockIndex>, BlockError>;
+
+    /// Make sure the storage has room for the specified amount of data, growing it if needed.
+    fn reserve_storage_space(&mut self, size: usize) -> Result<(), BootstrapError>;
+
+    /// The last block imported by an unfinished import.
+    fn last_imported_block_id(&self) -> Result<Option<Id<Block>>, BootstrapError>;
+
+    /// Record the last imported block, or clear the record once the import is finished.
+    fn set_last_imported_block_id(
+        &mut self,
+        block_id: Option<Id<Block>>,
+    ) -> Result<(), BootstrapError>;
+}
+
+impl<S: BlockchainStorage, V: TransactionVerificationStrategy> BootstrapImportTarget
+    for Chainstate<S, V>
+{
+    fn chain_config(&self) -> &ChainConfig {
+        &self.chain_config
+    }
+
+    fn is_block_stored(&self, block_id: &Id<Block>) -> Result<bool, BootstrapError> {
+        let block_index = self.query()?.get_block_index_for_any_block(block_id)?;
+        Ok(block_index.is_some_and(|block_index| block_index.is_persisted()))
+    }
+
+    fn process_block(&mut self, block: WithId<Block>) -> Result<Option<BlockIndex>, BlockError> {
+        Chainstate::process_block(self, block, BlockSource::Local)
+    }
+
+    fn reserve_storage_space(&mut self, size: usize) -> Result<(), BootstrapError> {
+        // The storage checks the space when a write transaction is started with a size estimate;
+        // nothing has to be written in it.
+        let db_tx = self.chainstate_storage.transaction_rw(Some(size))?;
+        db_tx.abort();
+        Ok(())
+    }
+
+    fn last_imported_block_id(&self) -> Result<Option<Id<Block>>, BootstrapError> {
+        let db_tx = self.chainstate_storage.transaction_ro()?;
+        Ok(db_tx.get_last_bootstrap_imported_block_id()?)
+    }
+
+    fn set_last_imported_block_id(
+        &mut self,
+        block_id: Option<Id<Block>>,
+    ) -> Result<(), BootstrapError> {
+        let mut db_tx = self.chainstate_storage.transaction_rw(None)?;
+        match block_id {
+            Some(block_id) => db_tx.set_last_bootstrap_imported_block_id(&block_id)?,
+            None => db_tx.del_last_bootstrap_imported_block_id()?,
+        }
+        db_tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Import the blocks from the stream, chunk by chunk.
+///
+/// The headers of each chunk are validated before any of its blocks are processed, so that
+/// a corrupted file is detected as early as possible. After each chunk, the id of its last block
+/// is recorded in the storage and the progress is reported. Blocks that are already stored
+/// are skipped, so an interrupted import can be resumed by importing the same file again.
+pub fn import_bootstrap_stream<T: BootstrapImportTarget, S: std::io::Read>(
     expected_magic_bytes: &[u8],
     file_reader: &mut std::io::BufReader<S>,
-    process_block_func: &mut P,
+    target: &mut T,
+    progress_callback: &mut dyn FnMut(BootstrapImportProgress),
     chainstate_config: &ChainstateConfig,
-) -> Result<(), BootstrapError>
-where
-    P: FnMut(WithId<Block>) -> Result<Option<BlockIndex>, BlockError>,
-{
+) -> Result<(), BootstrapError> {
     // min: The smallest buffer size, after which another read is triggered from the bootstrap file
     // max: The largest buffer size, after which reading the file is stopped
     // NOTE: both sizes MUST be larger than the largest block in the blockchain + 4 bytes for magic bytes
     let (min_buffer_size, max_buffer_size) =
         *chainstate_config.min_max_bootstrap_import_buffer_sizes;
 
+    let resumed_after = target.last_imported_block_id()?;
+    if let Some(block_id) = resumed_after {
+        log::info!("Resuming bootstrap import, the last imported block was {block_id}");
+    }
+
+    let mut progress = BootstrapImportProgress {
+        resumed_after,
+        blocks_imported: 0,
+        blocks_skipped: 0,
+        bytes_read: 0,
+    };
+
     // It's more reasonable to use a VeqDeque, but it's incompatible with the windows() method which is needed to search for magic bytes
     // There's a performance hit behind this, but we don't care. Anyone is free to optimize this.
     let mut buffer_queue = Vec::<u8>::new();
+    let mut eof_reached = false;
 
     loop {
         if buffer_queue.len() < min_buffer_size + expected_magic_bytes.len() {
-            fill_buffer(&mut buffer_queue, file_reader, max_buffer_size)?;
+            eof_reached = fill_buffer(&mut buffer_queue, file_reader, max_buffer_size)?;
+        }
+
+        let (chunk, consumed) = decode_chunk(
+            &buffer_queue,
+            expected_magic_bytes,
+            min_buffer_size,
+            eof_reached,
+        )?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        let chunk = check_chunk_headers(target, chunk)?;
+        target.reserve_storage_space(consumed.saturating_mul(IMPORTED_BLOCK_SPACE_FACTOR))?;
+
+        let mut last_block_id = None;
+        for block in chunk {
+            let block_id = block.get_id();
+            if target.is_block_stored(&block_id)? {
+                progress.blocks_skipped += 1;
+            } else {
+                target.process_block(block)?;
+                progress.blocks_imported += 1;
+            }
+            last_block_id = Some(block_id);
+        }
+
+        target.set_last_imported_block_id(last_block_id)?;
+
+        progress.bytes_read += consumed as u64;
+        progress_callback(progress);
+
+        // consume the buffer from the front
+        buffer_queue = buffer_queue[consumed..].to_vec();
+    }
+
+    // The import is complete, so there is nothing to resume.
+    target.set_last_imported_block_id(None)?;
+
+    Ok(())
+}
+
+/// Decode the blocks at the front of the buffer; return them and the number of consumed bytes.
+///
+/// A block is only decoded if it's guaranteed to be fully present in the buffer, i.e. if there are
+/// at least `min_buffer_size` bytes after its magic bytes or if the end of stream has been reached.
+/// The first block is always decoded though, so that the import can always make progress.
+fn decode_chunk(
+    buffer: &[u8],
+    expected_magic_bytes: &[u8],
+    min_buffer_size: usize,
+    eof_reached: bool,
+) -> Result<(Vec<Block>, usize), BootstrapError> {
+    let mut blocks = Vec::new();
+    let mut consumed = 0;
+
+    loop {
+        let remaining = &buffer[consumed..];
+
+        if !blocks.is_empty()
+            && !eof_reached
+            && remaining.len() < min_buffer_size + expected_magic_bytes.len()
+        {
+            break;
         }
 
         // locate magic bytes to recognize the start of a block
-        let current_pos = buffer_queue
+        let current_pos = remaining
             .windows(expected_magic_bytes.len())
             .position(|window| window == expected_magic_bytes);
 
-        // read the block after the magic bytes
-        let block = match current_pos {
-            Some(v) => Block::decode(&mut &buffer_queue[v + expected_magic_bytes.len()..])?,
+        let block_start = match current_pos {
+            Some(v) => v + expected_magic_bytes.len(),
             None => break,
         };
-        let block_len = block.encoded_size();
-        process_block_func(block.into())?;
 
-        // consume the buffer from the front
-        buffer_queue = buffer_queue[expected_magic_bytes.len() + block_len..].to_vec();
+        // read the block after the magic bytes
+        let block = Block::decode(&mut &remaining[block_start..])?;
+
+        consumed += block_start + block.encoded_size();
+        blocks.push(block);
     }
 
-    Ok(())
+    Ok((blocks, consumed))
+}
+
+/// Check that every block of the chunk builds on a known block or on a preceding block
+/// of the chunk, and that block bodies match their headers; this is done for the entire chunk
+/// before any of its blocks is processed.
+fn check_chunk_headers<T: BootstrapImportTarget>(
+    target: &T,
+    chunk: Vec<Block>,
+) -> Result<Vec<WithId<Block>>, BootstrapError> {
+    let mut chunk_block_ids = BTreeSet::new();
+
+    chunk
+        .into_iter()
+        .map(|block| {
+            let block = WithId::new(block);
+            let block_id = block.get_id();
+            let parent_id = block.prev_block_id();
+
+            let parent_known = match parent_id.classify(target.chain_config()) {
+                GenBlockId::Genesis(_) => true,
+                GenBlockId::Block(parent_id) => {
+                    chunk_block_ids.contains(&parent_id) || target.is_block_stored(&parent_id)?
+                }
+            };
+            ensure!(
+                parent_known,
+                BootstrapError::UnknownParent {
+                    block_id,
+                    parent_id
+                }
+            );
+
+            let merkle_proxy = block
+                .body()
+                .merkle_tree_proxy()
+                .map_err(|_| BootstrapError::MerkleRootMismatch(block_id))?;
+            ensure!(
+                block.merkle_root() == merkle_proxy.merkle_tree().root()
+                    && block.witness_merkle_root() == merkle_proxy.witness_merkle_tree().root(),
+                BootstrapError::MerkleRootMismatch(block_id)
+            );
+
+            chunk_block_ids.insert(block_id);
+            Ok(block)
+        })
+        .collect()
 }
 
 fn fill_buffer<S: std::io::Read>(
     buffer_queue: &mut Vec<u8>,
     reader: &mut std::io::BufReader<S>,
     max_buffer_size: usize,
-) -> Result<(), BootstrapError> {
+) -> Result<bool, BootstrapError> {
     while buffer_queue.len() < max_buffer_size {
         let buf_len = {
             let data = reader.fill_buf()?;
             if data.is_empty() {
-                break;
+                return Ok(true);
             }
             buffer_queue.extend(data.iter());
             data.len()
@@ -103,7 +334,7 @@ fn fill_buffer<S: std::io::Read>(
         reader.consume(buf_len);
     }
 
-    Ok(())
+    Ok(false)
 }
 
 pub fn export_bootstrap_stream<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy>(
@@ -0,0 +1,172 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::CacheMemoryLimits;
+
+/// The chainstate caches whose memory usage is accounted against the global limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    BlockIndexCache,
+    UtxoCache,
+    OrphanBlocks,
+}
+
+impl CacheKind {
+    const ALL: [CacheKind; 3] =
+        [CacheKind::BlockIndexCache, CacheKind::UtxoCache, CacheKind::OrphanBlocks];
+
+    fn index(self) -> usize {
+        match self {
+            CacheKind::BlockIndexCache => 0,
+            CacheKind::UtxoCache => 1,
+            CacheKind::OrphanBlocks => 2,
+        }
+    }
+
+    fn limit(self, limits: &CacheMemoryLimits) -> usize {
+        match self {
+            CacheKind::BlockIndexCache => limits.block_index_cache,
+            CacheKind::UtxoCache => limits.utxo_cache,
+            CacheKind::OrphanBlocks => limits.orphan_blocks,
+        }
+    }
+}
+
+/// Accounts the memory used by the chainstate caches at runtime.
+///
+/// Each cache registers its current usage here and asks how much it may use before growing.
+/// A cache may always grow up to its share of the global limit (its own limit scaled down
+/// proportionally, see `CacheMemoryLimits::scaled_down_to`) and may also use the memory that
+/// the other caches leave unused, up to its own limit. When the global limit is reached,
+/// the caches that went over their shares shrink back to them as they evict entries.
+///
+/// The utxo cache only exists while blocks are being checked in memory (e.g. during a reorg).
+/// It gets the memory that is available when it's created and its usage isn't registered,
+/// because it's released right after the check.
+pub struct CacheMemoryBudget {
+    limits: CacheMemoryLimits,
+    shares: CacheMemoryLimits,
+    max_total: Option<usize>,
+    usage: [AtomicUsize; 3],
+}
+
+impl CacheMemoryBudget {
+    pub fn new(limits: CacheMemoryLimits, max_total: Option<usize>) -> Self {
+        let shares = match max_total {
+            Some(max_total) => limits.scaled_down_to(max_total),
+            None => limits,
+        };
+
+        let result = Self {
+            limits,
+            shares,
+            max_total,
+            usage: Default::default(),
+        };
+        metrics::node::CACHE_MEMORY_LIMIT.set(result.max_total() as u64);
+        result
+    }
+
+    /// The global limit, or the sum of the individual limits if it's not set or is higher.
+    pub fn max_total(&self) -> usize {
+        let total = self.limits.total();
+        self.max_total.map_or(total, |max_total| std::cmp::min(max_total, total))
+    }
+
+    /// The memory currently used by the cache.
+    pub fn usage(&self, kind: CacheKind) -> usize {
+        self.usage[kind.index()].load(Ordering::Relaxed)
+    }
+
+    /// The memory currently used by all the caches.
+    pub fn total_usage(&self) -> usize {
+        CacheKind::ALL
+            .iter()
+            .fold(0, |total, kind| total.saturating_add(self.usage(*kind)))
+    }
+
+    pub fn set_usage(&self, kind: CacheKind, usage: usize) {
+        self.usage[kind.index()].store(usage, Ordering::Relaxed);
+        metrics::node::CACHE_MEMORY_USAGE.set(self.total_usage() as u64);
+    }
+
+    /// The amount of memory the cache may use at the moment, given the usage of the other caches.
+    pub fn max_usage(&self, kind: CacheKind) -> usize {
+        let limit = kind.limit(&self.limits);
+        match self.max_total {
+            Some(max_total) => {
+                let used_by_others = self.total_usage().saturating_sub(self.usage(kind));
+                let available = max_total.saturating_sub(used_by_others);
+                std::cmp::min(limit, std::cmp::max(available, kind.limit(&self.shares)))
+            }
+            None => limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_memory_is_shared() {
+        let limits = CacheMemoryLimits {
+            block_index_cache: 100,
+            utxo_cache: 300,
+            orphan_blocks: 600,
+        };
+        let budget = CacheMemoryBudget::new(limits, Some(500));
+        assert_eq!(budget.max_total(), 500);
+
+        // Nothing is used, so every cache may grow up to its own limit or the global one
+        assert_eq!(budget.max_usage(CacheKind::BlockIndexCache), 100);
+        assert_eq!(budget.max_usage(CacheKind::UtxoCache), 300);
+        assert_eq!(budget.max_usage(CacheKind::OrphanBlocks), 500);
+
+        // The orphan blocks use the memory left unused by the other caches
+        budget.set_usage(CacheKind::OrphanBlocks, 450);
+        assert_eq!(budget.total_usage(), 450);
+        assert_eq!(budget.max_usage(CacheKind::OrphanBlocks), 500);
+
+        // The other caches may still grow up to their shares, which makes the orphan blocks
+        // go over the global limit, so they have to shrink back to their own share
+        assert_eq!(budget.max_usage(CacheKind::BlockIndexCache), 50);
+        assert_eq!(budget.max_usage(CacheKind::UtxoCache), 150);
+        budget.set_usage(CacheKind::BlockIndexCache, 50);
+        budget.set_usage(CacheKind::UtxoCache, 150);
+        assert_eq!(budget.max_usage(CacheKind::OrphanBlocks), 300);
+
+        budget.set_usage(CacheKind::UtxoCache, 0);
+        assert_eq!(budget.max_usage(CacheKind::OrphanBlocks), 450);
+    }
+
+    #[test]
+    fn no_global_limit() {
+        let limits = CacheMemoryLimits {
+            block_index_cache: 100,
+            utxo_cache: 300,
+            orphan_blocks: 600,
+        };
+        let budget = CacheMemoryBudget::new(limits, None);
+        assert_eq!(budget.max_total(), 1000);
+
+        budget.set_usage(CacheKind::OrphanBlocks, 600);
+        assert_eq!(budget.max_usage(CacheKind::BlockIndexCache), 100);
+        assert_eq!(budget.max_usage(CacheKind::UtxoCache), 300);
+        assert_eq!(budget.max_usage(CacheKind::OrphanBlocks), 600);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_types::{BlockIndex, ChainSplitRecord, ChainSplitTip};
+use common::{
+    chain::{block::timestamp::BlockTimestamp, Block},
+    primitives::{BlockDistance, Id},
+    Uint256,
+};
+use utils::bounded_fifo_map::BoundedFifoMap;
+
+/// Two competing tips are considered a chain split worth recording if their heights differ by at
+/// most this number of blocks.
+pub const CHAIN_SPLIT_RECORD_MAX_DEPTH: BlockDistance = BlockDistance::new(6);
+
+/// The maximum number of chain split records kept in the database; the oldest ones are removed
+/// first.
+pub const MAX_CHAIN_SPLIT_RECORDS: usize = 100;
+
+/// The number of recently processed blocks for which the first-seen time is remembered.
+const MAX_RECENT_BLOCK_OBSERVATIONS: usize = 1000;
+
+/// In-memory information about the recently processed blocks, which is used to populate
+/// chain split records.
+pub struct RecentBlockObservations {
+    first_seen: BoundedFifoMap<Id<Block>, BlockTimestamp>,
+}
+
+impl Default for RecentBlockObservations {
+    fn default() -> Self {
+        Self {
+            first_seen: BoundedFifoMap::new(MAX_RECENT_BLOCK_OBSERVATIONS),
+        }
+    }
+}
+
+impl RecentBlockObservations {
+    /// Remember when the block was seen for the first time.
+    pub fn observe(&mut self, block_id: Id<Block>, now: BlockTimestamp) {
+        if !self.first_seen.contains_key(&block_id) {
+            self.first_seen.insert(block_id, now);
+        }
+    }
+
+    fn make_tip(&self, block_index: &BlockIndex) -> ChainSplitTip {
+        ChainSplitTip::new(
+            *block_index.block_id(),
+            block_index.block_height(),
+            self.first_seen.get(block_index.block_id()).copied(),
+        )
+    }
+
+    /// Create a chain split record if the competing tips are close enough to each other.
+    ///
+    /// Apart from the heights, the chain trust of the competing tip must be comparable to the
+    /// best one, i.e. not less than `min_chain_trust`, which is the chain trust of the mainchain
+    /// block `CHAIN_SPLIT_RECORD_MAX_DEPTH` blocks below the best tip.
+    pub fn make_record_if_split(
+        &self,
+        best_tip: &BlockIndex,
+        competing_tip: &BlockIndex,
+        min_chain_trust: Uint256,
+        now: BlockTimestamp,
+    ) -> Option<ChainSplitRecord> {
+        let distance = (best_tip.block_height() - competing_tip.block_height())?;
+        if distance.to_int().unsigned_abs() > CHAIN_SPLIT_RECORD_MAX_DEPTH.to_int().unsigned_abs() {
+            return None;
+        }
+        if competing_tip.chain_trust() < min_chain_trust {
+            return None;
+        }
+
+        Some(ChainSplitRecord::new(
+            self.make_tip(best_tip),
+            self.make_tip(competing_tip),
+            now,
+        ))
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use chainstate_types::{BlockIndex, GenBlockIndex};
+use common::{
+    chain::GenBlock,
+    primitives::{BlockHeight, Id, H256},
+};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint,
+)]
+pub enum ChainTipStatus {
+    /// The tip of the main chain
+    Active,
+    /// The tip of a branch that is not part of the main chain and contains no blocks known to be
+    /// invalid. Note that blocks outside the main chain may not have been fully validated.
+    ValidFork,
+    /// The tip of a branch that contains an invalid block
+    Invalid,
+}
+
+/// A block that has no known children, i.e. the tip of the main chain or of one of its forks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct ChainTip {
+    pub block_id: Id<GenBlock>,
+    pub block_height: BlockHeight,
+    pub chain_trust: H256,
+    /// The number of blocks between the tip and the main chain; zero for the main chain tip.
+    pub branch_length: u64,
+    pub status: ChainTipStatus,
+}
+
+impl ChainTip {
+    pub fn new_active(block_index: &GenBlockIndex) -> Self {
+        Self {
+            block_id: block_index.block_id(),
+            block_height: block_index.block_height(),
+            chain_trust: block_index.chain_trust().into(),
+            branch_length: 0,
+            status: ChainTipStatus::Active,
+        }
+    }
+
+    /// Create a tip for a block outside the main chain.
+    pub fn new_fork(block_index: &BlockIndex, branch_length: u64) -> Self {
+        let status = if block_index.status().is_ok() {
+            ChainTipStatus::ValidFork
+        } else {
+            ChainTipStatus::Invalid
+        };
+
+        Self {
+            block_id: (*block_index.block_id()).into(),
+            block_height: block_index.block_height(),
+            chain_trust: block_index.chain_trust().into(),
+            branch_length,
+            status,
+        }
+    }
+}
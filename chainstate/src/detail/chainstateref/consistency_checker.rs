@@ -13,21 +13,146 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+};
 
 use chainstate_storage::BlockchainStorageRead;
-use chainstate_types::BlockIndex;
+use chainstate_types::{BlockIndex, TipStorageTag};
 use common::{
-    chain::{Block, ChainConfig, GenBlock},
-    primitives::{BlockHeight, Id, Idable},
+    chain::{Block, ChainConfig, GenBlock, PoolId, TxOutput, UtxoOutPoint},
+    primitives::{Amount, BlockHeight, Id, Idable},
 };
 use itertools::{EitherOrBoth, Itertools};
 use logging::log;
+use pos_accounting::PoSAccountingStorageRead;
+use thiserror::Error;
+use utxo::UtxosStorageRead;
+
+use crate::detail::utxo_diff::BlockUtxoDiff;
 
 use super::calc_min_height_with_allowed_reorg;
 
-// Certain tests check for this panic message.
-const PANIC_MSG: &str = "Inconsistent chainstate";
+/// How thorough the database verification should be; each level includes the checks
+/// of the previous ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DbVerificationLevel {
+    /// Check the block map, the block index map and the block-by-height map.
+    BlockIndex,
+    /// Also re-derive the utxo set changes of the last mainchain blocks from their undo data.
+    Utxos,
+    /// Also check the pos accounting data of the pools that have staking utxos.
+    Accounting,
+}
+
+/// An inconsistency found in the chainstate database.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum DbVerificationError {
+    #[error("Best block id not stored")]
+    BestBlockIdMissing,
+    #[error("Block index data missing for block {0}")]
+    BlockIndexMissing(Id<Block>),
+    #[error("Block {0} is not stored but its index is marked as persisted")]
+    UnexpectedPersistedFlag(Id<Block>),
+    #[error("Block {0} is not stored but its index has ok status")]
+    UnexpectedOkStatus(Id<Block>),
+    #[error("Block {0} is stored but its index is not marked as persisted")]
+    PersistedFlagMissing(Id<Block>),
+    #[error("Block id from BlockIndex {id_in_block_index} doesn't match {block_id}")]
+    BlockIndexIdMismatch {
+        block_id: Id<Block>,
+        id_in_block_index: Id<Block>,
+    },
+    #[error("Block {0} is marked as persisted but is not stored")]
+    PersistedBlockMissing(Id<Block>),
+    #[error("Calculated block id {calculated_block_id} doesn't match {block_id}")]
+    BlockIdMismatch {
+        block_id: Id<Block>,
+        calculated_block_id: Id<Block>,
+    },
+    #[error("Block headers are different in the index and the block itself for block {0}")]
+    BlockHeaderMismatch(Id<Block>),
+    #[error("Block {0} parent index not found")]
+    ParentBlockIndexMissing(Id<Block>),
+    #[error("Parent block {parent_id} of persisted block {block_id} is not persisted")]
+    ParentNotPersisted {
+        block_id: Id<Block>,
+        parent_id: Id<Block>,
+    },
+    #[error("Parent block {parent_id} of ok block {block_id} is not ok")]
+    ParentNotOk {
+        block_id: Id<Block>,
+        parent_id: Id<Block>,
+    },
+    #[error("Parent block {parent_id} is less valid than its child {block_id}")]
+    ParentLessValid {
+        block_id: Id<Block>,
+        parent_id: Id<Block>,
+    },
+    #[error("No block at zero height")]
+    NoBlockAtZeroHeight,
+    #[error("Block {0} at zero height is not genesis")]
+    NonGenesisAtZeroHeight(Id<GenBlock>),
+    #[error("Block at max height {block_id} is not the same as the best block {best_block_id}")]
+    MaxHeightBlockNotBest {
+        block_id: Id<GenBlock>,
+        best_block_id: Id<GenBlock>,
+    },
+    #[error(
+        "The stored min_height_with_allowed_reorg {stored} is less then the calculated value {calculated}"
+    )]
+    MinHeightWithAllowedReorgTooLow {
+        stored: BlockHeight,
+        calculated: BlockHeight,
+    },
+    #[error("Gap in the block-by-height map found - {height} follows {prev_height}")]
+    BlockHeightMapGap {
+        prev_height: BlockHeight,
+        height: BlockHeight,
+    },
+    #[error("Genesis at non-zero height {0}")]
+    GenesisAtNonZeroHeight(BlockHeight),
+    #[error(
+        "Block {prev_id} at height {prev_height} is not a parent of the next block {block_id}"
+    )]
+    MainchainParentMismatch {
+        block_id: Id<Block>,
+        prev_id: Id<GenBlock>,
+        prev_height: BlockHeight,
+    },
+    #[error("Mainchain block {0} must be persisted")]
+    MainchainBlockNotPersisted(Id<Block>),
+    #[error("Mainchain block {0} must be fully valid")]
+    MainchainBlockNotFullyValid(Id<Block>),
+    #[error("Best block for utxos {best_block_for_utxos} is not the best block {best_block_id}")]
+    UtxosBestBlockMismatch {
+        best_block_for_utxos: Id<GenBlock>,
+        best_block_id: Id<GenBlock>,
+    },
+    #[error("Undo data not found for mainchain block {0}")]
+    UndoDataMissing(Id<Block>),
+    #[error("Utxo {outpoint:?} created by block {block_id} is missing or different")]
+    CreatedUtxoMismatch {
+        block_id: Id<Block>,
+        outpoint: UtxoOutPoint,
+    },
+    #[error("Utxo {outpoint:?} spent by block {block_id} is still present")]
+    SpentUtxoPresent {
+        block_id: Id<Block>,
+        outpoint: UtxoOutPoint,
+    },
+    #[error("Pool {0} has a staking utxo but no data")]
+    PoolDataMissing(PoolId),
+    #[error("Pool {0} has a staking utxo but no balance")]
+    PoolBalanceMissing(PoolId),
+    #[error("Balance {balance:?} of pool {pool_id} doesn't match the sum of the staker balance and delegation shares {expected:?}")]
+    PoolBalanceMismatch {
+        pool_id: PoolId,
+        balance: Amount,
+        expected: Option<Amount>,
+    },
+}
 
 pub struct ConsistencyChecker<'a, DbTx> {
     db_tx: &'a DbTx,
@@ -39,9 +164,11 @@ pub struct ConsistencyChecker<'a, DbTx> {
     /// The entire block-by-height map.
     block_by_height_map: BTreeMap<BlockHeight, Id<GenBlock>>,
     /// Best block id from the db.
-    best_block_id: Id<GenBlock>,
+    best_block_id: Option<Id<GenBlock>>,
     /// The min_height_with_allowed_reorg from the db.
     min_height_with_allowed_reorg: BlockHeight,
+    /// The min_height_with_undo_data from the db.
+    min_height_with_undo_data: BlockHeight,
 }
 
 impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
@@ -52,11 +179,10 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
         let block_map_keys = db_tx.get_block_map_keys()?;
         let block_index_map = db_tx.get_block_index_map()?;
         let block_by_height_map = db_tx.get_block_by_height_map()?;
-        let best_block_id = db_tx.get_best_block_id()?.unwrap_or_else(|| {
-            panic!("{PANIC_MSG}: best block id not stored");
-        });
+        let best_block_id = db_tx.get_best_block_id()?;
         let min_height_with_allowed_reorg =
             db_tx.get_min_height_with_allowed_reorg()?.unwrap_or(0.into());
+        let min_height_with_undo_data = db_tx.get_min_height_with_undo_data()?.unwrap_or(0.into());
 
         Ok(Self {
             db_tx,
@@ -66,23 +192,45 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
             block_by_height_map,
             best_block_id,
             min_height_with_allowed_reorg,
+            min_height_with_undo_data,
         })
     }
 
-    pub fn check(&self) -> Result<(), chainstate_storage::Error> {
-        log::debug!("Running chainstate consistency checks");
+    /// Run the checks of the specified level and return all the found inconsistencies.
+    ///
+    /// `depth` is the number of the last mainchain blocks whose utxo changes will be re-derived;
+    /// it's ignored for the `BlockIndex` level.
+    pub fn check(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, chainstate_storage::Error> {
+        log::debug!("Running chainstate consistency checks, level: {level:?}, depth: {depth}");
+
+        let mut errors = Vec::new();
 
-        self.check_block_index_consistency()?;
-        self.check_block_height_map_consistency();
+        self.check_block_index_consistency(&mut errors)?;
+        self.check_block_height_map_consistency(&mut errors);
 
         // TODO: add consistency checks for other maps in the chainstate db.
         // https://github.com/mintlayer/mintlayer-core/issues/1710
 
-        Ok(())
+        if level >= DbVerificationLevel::Utxos {
+            if level >= DbVerificationLevel::Accounting {
+                self.check_accounting_consistency(&mut errors)?;
+            }
+
+            self.check_utxo_consistency(depth, &mut errors)?;
+        }
+
+        Ok(errors)
     }
 
     /// Check the block map vs block index map consistency.
-    fn check_block_index_consistency(&self) -> Result<(), chainstate_storage::Error> {
+    fn check_block_index_consistency(
+        &self,
+        errors: &mut Vec<DbVerificationError>,
+    ) -> Result<(), chainstate_storage::Error> {
         // Loop over block_map_keys and block_index_map simultaneously via merge_join_by, looking
         // for ids that are present in one of them and missing in the other.
         for merged in self
@@ -95,19 +243,18 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
             let (block_id, block_index) = match merged {
                 EitherOrBoth::Left(block_id) => {
                     // The block object is present, the index object is not.
-                    panic!("{PANIC_MSG}: block index data missing for block {block_id}");
+                    errors.push(DbVerificationError::BlockIndexMissing(*block_id));
+                    continue;
                 }
                 EitherOrBoth::Right((block_id, block_index)) => {
                     // The block index object is present, the block object is not;
                     // The persistence flag must be unset and the status must not be "ok".
-                    assert!(
-                        !block_index.is_persisted(),
-                        "{PANIC_MSG}: block {block_id} must not be persisted"
-                    );
-                    assert!(
-                        !block_index.status().is_ok(),
-                        "{PANIC_MSG}: block {block_id} must not be ok"
-                    );
+                    if block_index.is_persisted() {
+                        errors.push(DbVerificationError::UnexpectedPersistedFlag(*block_id));
+                    }
+                    if block_index.status().is_ok() {
+                        errors.push(DbVerificationError::UnexpectedOkStatus(*block_id));
+                    }
 
                     (block_id, block_index)
                 }
@@ -115,10 +262,9 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
                     // Both the block and block index objects are present.
 
                     // The persistence flag must be set.
-                    assert!(
-                        block_index.is_persisted(),
-                        "{PANIC_MSG}: block {block_id} must be persisted"
-                    );
+                    if !block_index.is_persisted() {
+                        errors.push(DbVerificationError::PersistedFlagMissing(*block_id));
+                    }
 
                     (block_id, block_index)
                 }
@@ -126,61 +272,73 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
 
             // Check that the id stored in the block index matches the supposed id of the block.
             let block_id_in_block_index = block_index.block_id();
-            assert_eq!(
-                block_id,
-                block_id_in_block_index,
-                "{PANIC_MSG}: block id from BlockIndex {block_id_in_block_index} doesn't match {block_id}"
-            );
+            if block_id != block_id_in_block_index {
+                errors.push(DbVerificationError::BlockIndexIdMismatch {
+                    block_id: *block_id,
+                    id_in_block_index: *block_id_in_block_index,
+                });
+            }
 
             // If the block is persisted, calculate its id and check that it matches the id
             // that was used as the key. Also compare the block header stored in the index vs the one
             // in the block itself.
             if block_index.is_persisted() {
-                let block =
-                    self.db_tx.get_block(*block_id)?.expect("The block is known to be present");
-                let calculated_block_id = block.get_id();
-                assert_eq!(
-                    calculated_block_id, *block_id,
-                    "{PANIC_MSG}: calculated block id {calculated_block_id} doesn't match {block_id}"
-                );
-
-                assert_eq!(
-                    block.header(),
-                    block_index.block_header(),
-                    "{PANIC_MSG}: block headers are different in the index and the block itself for block {block_id}"
-                );
+                match self.db_tx.get_block(*block_id)? {
+                    Some(block) => {
+                        let calculated_block_id = block.get_id();
+                        if calculated_block_id != *block_id {
+                            errors.push(DbVerificationError::BlockIdMismatch {
+                                block_id: *block_id,
+                                calculated_block_id,
+                            });
+                        }
+
+                        if block.header() != block_index.block_header() {
+                            errors.push(DbVerificationError::BlockHeaderMismatch(*block_id));
+                        }
+                    }
+                    None => {
+                        // Only possible if the persisted flag is set incorrectly, which has
+                        // already been reported above.
+                    }
+                }
             }
 
             // Check the parent, if it's not genesis.
-            if let Some(parent_id) =
-                block_index.prev_block_id().classify(self.chain_config).chain_block_id()
-            {
-                let parent_block_index =
-                    self.block_index_map.get(&parent_id).unwrap_or_else(|| {
-                        panic!("{PANIC_MSG}: block {block_id} parent index not found");
-                    });
-                if block_index.is_persisted() {
+            if let Some(parent_id) = block_index.prev_block_id().to_block_id(self.chain_config) {
+                let parent_block_index = match self.block_index_map.get(&parent_id) {
+                    Some(parent_block_index) => parent_block_index,
+                    None => {
+                        errors.push(DbVerificationError::ParentBlockIndexMissing(*block_id));
+                        continue;
+                    }
+                };
+
+                if block_index.is_persisted() && !parent_block_index.is_persisted() {
                     // If this block is persisted, the parent must be too.
-                    assert!(
-                        parent_block_index.is_persisted(),
-                        "{PANIC_MSG}: parent block {parent_id} of persisted block {block_id} is not persisted"
-                    );
+                    errors.push(DbVerificationError::ParentNotPersisted {
+                        block_id: *block_id,
+                        parent_id,
+                    });
                 }
 
-                if block_index.status().is_ok() {
+                if block_index.status().is_ok() && !parent_block_index.status().is_ok() {
                     // If a block has ok status, its parent must also be ok.
-                    assert!(
-                        parent_block_index.status().is_ok(),
-                        "{PANIC_MSG}: parent block {parent_id} of ok block {block_id} is not ok"
-                    );
+                    errors.push(DbVerificationError::ParentNotOk {
+                        block_id: *block_id,
+                        parent_id,
+                    });
                 }
 
                 // In any case, the parent block must be at least as valid as the child.
-                assert!(
-                    parent_block_index.status().last_valid_stage()
-                        >= block_index.status().last_valid_stage(),
-                    "{PANIC_MSG}: parent block {parent_id} is less valid than its child {block_id}"
-                );
+                if parent_block_index.status().last_valid_stage()
+                    < block_index.status().last_valid_stage()
+                {
+                    errors.push(DbVerificationError::ParentLessValid {
+                        block_id: *block_id,
+                        parent_id,
+                    });
+                }
             }
         }
 
@@ -188,29 +346,44 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
     }
 
     /// Check consistency of the block-by-height map.
-    fn check_block_height_map_consistency(&self) {
+    fn check_block_height_map_consistency(&self, errors: &mut Vec<DbVerificationError>) {
         // The block at zero height must be the genesis.
-        let block_at_zero_height = *self.block_by_height_map.get(&0.into()).unwrap_or_else(|| {
-            panic!("{PANIC_MSG}: no block at zero height");
-        });
-        assert_eq!(
-            block_at_zero_height,
-            self.chain_config.genesis_block_id(),
-            "{PANIC_MSG}: block at zero height is not genesis"
-        );
+        match self.block_by_height_map.get(&0.into()) {
+            Some(block_at_zero_height) => {
+                if *block_at_zero_height != self.chain_config.genesis_block_id() {
+                    errors.push(DbVerificationError::NonGenesisAtZeroHeight(
+                        *block_at_zero_height,
+                    ));
+                }
+            }
+            None => {
+                errors.push(DbVerificationError::NoBlockAtZeroHeight);
+            }
+        }
 
         // The block at the max height must be the same as best_block_id.
-        let (max_height, block_at_max_height_id) = self
-            .block_by_height_map
-            .iter()
-            .next_back()
-            .expect("The map is known to be non-empty");
-        assert_eq!(
-            *block_at_max_height_id,
+        let max_height = match (
+            self.block_by_height_map.iter().next_back(),
             self.best_block_id,
-            "{PANIC_MSG}: block at max height {block_at_max_height_id} is not the same as the best block {bb}",
-            bb = self.best_block_id
-        );
+        ) {
+            (Some((max_height, block_at_max_height_id)), Some(best_block_id)) => {
+                if *block_at_max_height_id != best_block_id {
+                    errors.push(DbVerificationError::MaxHeightBlockNotBest {
+                        block_id: *block_at_max_height_id,
+                        best_block_id,
+                    });
+                }
+                *max_height
+            }
+            (Some((max_height, _)), None) => {
+                errors.push(DbVerificationError::BestBlockIdMissing);
+                *max_height
+            }
+            (None, _) => {
+                // The missing genesis has already been reported.
+                return;
+            }
+        };
 
         // The min_height_with_allowed_reorg value must be consistent with the one calculated
         // from the current max height,
@@ -218,45 +391,229 @@ impl<'a, DbTx: BlockchainStorageRead> ConsistencyChecker<'a, DbTx> {
         // for the stored value to become bigger than the one calculated from the current tip
         // if some mainchain blocks were invalidated in the past.
         let calculated_min_height_with_allowed_reorg =
-            calc_min_height_with_allowed_reorg(self.chain_config, *max_height);
-        assert!(
-            self.min_height_with_allowed_reorg >= calculated_min_height_with_allowed_reorg,
-            "The stored min_height_with_allowed_reorg {} is less then the calculated value {}",
-            self.min_height_with_allowed_reorg,
-            calculated_min_height_with_allowed_reorg
-        );
+            calc_min_height_with_allowed_reorg(self.chain_config, max_height);
+        if self.min_height_with_allowed_reorg < calculated_min_height_with_allowed_reorg {
+            errors.push(DbVerificationError::MinHeightWithAllowedReorgTooLow {
+                stored: self.min_height_with_allowed_reorg,
+                calculated: calculated_min_height_with_allowed_reorg,
+            });
+        }
 
         // Check the consistency of the map itself.
         for ((prev_height, prev_id), (cur_height, cur_id)) in
             self.block_by_height_map.iter().tuple_windows()
         {
-            assert_eq!(
-                cur_height,
-                &prev_height.next_height(),
-                "{PANIC_MSG}: gap in the block-by-height map found - {cur_height} follows {prev_height}"
-            );
-
-            let cur_id = cur_id.classify(self.chain_config).chain_block_id().unwrap_or_else(|| {
-                panic!("{PANIC_MSG}: genesis at non-zero-height {cur_height}");
-            });
-            let cur_block_index = self.block_index_map.get(&cur_id).unwrap_or_else(|| {
-                panic!("{PANIC_MSG}: block {cur_id} index not found");
-            });
-            assert_eq!(
-                cur_block_index.prev_block_id(),
-                prev_id,
-                "{PANIC_MSG}: block {prev_id} at height {prev_height} is not a parent of the next block {cur_id}"
-            );
+            if *cur_height != prev_height.next_height() {
+                errors.push(DbVerificationError::BlockHeightMapGap {
+                    prev_height: *prev_height,
+                    height: *cur_height,
+                });
+            }
+
+            let cur_id = match cur_id.to_block_id(self.chain_config) {
+                Some(cur_id) => cur_id,
+                None => {
+                    errors.push(DbVerificationError::GenesisAtNonZeroHeight(*cur_height));
+                    continue;
+                }
+            };
+            let cur_block_index = match self.block_index_map.get(&cur_id) {
+                Some(cur_block_index) => cur_block_index,
+                None => {
+                    errors.push(DbVerificationError::BlockIndexMissing(cur_id));
+                    continue;
+                }
+            };
+            if cur_block_index.prev_block_id() != prev_id {
+                errors.push(DbVerificationError::MainchainParentMismatch {
+                    block_id: cur_id,
+                    prev_id: *prev_id,
+                    prev_height: *prev_height,
+                });
+            }
 
             // Since the map contains mainchain blocks, they must be persisted and have the fully checked status.
-            assert!(
-                cur_block_index.is_persisted(),
-                "{PANIC_MSG}: mainchain block {cur_id} must be persisted"
-            );
-            assert!(
-                cur_block_index.status().is_fully_valid(),
-                "{PANIC_MSG}: mainchain block {cur_id} must be fully valid"
-            );
+            if !cur_block_index.is_persisted() {
+                errors.push(DbVerificationError::MainchainBlockNotPersisted(cur_id));
+            }
+            if !cur_block_index.status().is_fully_valid() {
+                errors.push(DbVerificationError::MainchainBlockNotFullyValid(cur_id));
+            }
+        }
+    }
+
+    /// Roll back the utxo set through the last `depth` mainchain blocks, checking that each
+    /// block's created utxos are present and its spent ones are absent at the moment the block
+    /// is disconnected.
+    fn check_utxo_consistency(
+        &self,
+        depth: usize,
+        errors: &mut Vec<DbVerificationError>,
+    ) -> Result<(), chainstate_storage::Error> {
+        let best_block_for_utxos = self.db_tx.get_best_block_for_utxos()?;
+        if let Some(best_block_id) = self.best_block_id {
+            if best_block_for_utxos != best_block_id {
+                errors.push(DbVerificationError::UtxosBestBlockMismatch {
+                    best_block_for_utxos,
+                    best_block_id,
+                });
+                // The utxo set doesn't correspond to the mainchain, so there is nothing to re-derive.
+                return Ok(());
+            }
         }
+
+        let mainchain_blocks = self
+            .block_by_height_map
+            .iter()
+            .rev()
+            .filter_map(|(height, id)| id.to_block_id(self.chain_config).map(|id| (*height, id)))
+            .take(depth);
+
+        // The utxo set is rolled back in memory; only the changes are stored here, `None` meaning
+        // that the utxo has been removed.
+        let mut rolled_back_utxos = BTreeMap::<UtxoOutPoint, Option<TxOutput>>::new();
+
+        for (height, block_id) in mainchain_blocks {
+            if height < self.min_height_with_undo_data {
+                // The undo data of the older blocks has been pruned.
+                break;
+            }
+
+            let block = match self.db_tx.get_block(block_id)? {
+                Some(block) => block,
+                None => {
+                    // Already reported by the block index checks; the utxo set can't be rolled
+                    // back any further.
+                    break;
+                }
+            };
+            let undo = match self.db_tx.get_undo_data(block_id)? {
+                Some(undo) => undo,
+                None => {
+                    errors.push(DbVerificationError::UndoDataMissing(block_id));
+                    break;
+                }
+            };
+
+            let diff = BlockUtxoDiff::from_block_and_undo(&block, height, undo);
+            let spent_in_block =
+                diff.spent.iter().map(|entry| &entry.outpoint).collect::<BTreeSet<_>>();
+            let created_in_block =
+                diff.created.iter().map(|entry| &entry.outpoint).collect::<BTreeSet<_>>();
+
+            for entry in &diff.created {
+                // Utxos created and spent in the same block never reach the utxo set.
+                if spent_in_block.contains(&entry.outpoint) {
+                    continue;
+                }
+
+                let output = self.get_rolled_back_utxo(&rolled_back_utxos, &entry.outpoint)?;
+                rolled_back_utxos.insert(entry.outpoint.clone(), None);
+
+                if output.as_ref() != Some(&entry.output) {
+                    errors.push(DbVerificationError::CreatedUtxoMismatch {
+                        block_id,
+                        outpoint: entry.outpoint.clone(),
+                    });
+                }
+            }
+
+            for entry in &diff.spent {
+                if created_in_block.contains(&entry.outpoint) {
+                    continue;
+                }
+
+                let output = self.get_rolled_back_utxo(&rolled_back_utxos, &entry.outpoint)?;
+                rolled_back_utxos.insert(entry.outpoint.clone(), Some(entry.output.clone()));
+
+                if output.is_some() {
+                    errors.push(DbVerificationError::SpentUtxoPresent {
+                        block_id,
+                        outpoint: entry.outpoint.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every pool that has a staking utxo in the utxo set has its data and balance
+    /// in the tip accounting storage, and that the balance equals the staker balance plus
+    /// the delegation shares.
+    /// Get the utxo from the partially rolled back utxo set.
+    fn get_rolled_back_utxo(
+        &self,
+        rolled_back_utxos: &BTreeMap<UtxoOutPoint, Option<TxOutput>>,
+        outpoint: &UtxoOutPoint,
+    ) -> Result<Option<TxOutput>, chainstate_storage::Error> {
+        match rolled_back_utxos.get(outpoint) {
+            Some(output) => Ok(output.clone()),
+            None => Ok(self.db_tx.get_utxo(outpoint)?.map(|utxo| utxo.take_output())),
+        }
+    }
+
+    fn check_accounting_consistency(
+        &self,
+        errors: &mut Vec<DbVerificationError>,
+    ) -> Result<(), chainstate_storage::Error> {
+        let mut pool_ids = BTreeSet::new();
+        self.db_tx.for_each_utxo(&mut |_, utxo| {
+            match utxo.output() {
+                TxOutput::CreateStakePool(pool_id, _)
+                | TxOutput::ProduceBlockFromStake(_, pool_id) => {
+                    pool_ids.insert(*pool_id);
+                }
+                TxOutput::Transfer(..)
+                | TxOutput::LockThenTransfer(..)
+                | TxOutput::Burn(..)
+                | TxOutput::CreateDelegationId(..)
+                | TxOutput::DelegateStaking(..)
+                | TxOutput::IssueFungibleToken(..)
+                | TxOutput::IssueNft(..)
+                | TxOutput::DataDeposit(..)
+                | TxOutput::Htlc(..)
+                | TxOutput::CreateOrder(..) => {}
+            }
+            ControlFlow::Continue(())
+        })?;
+
+        for pool_id in pool_ids {
+            let pool_data =
+                PoSAccountingStorageRead::<TipStorageTag>::get_pool_data(self.db_tx, pool_id)?;
+            let pool_balance =
+                PoSAccountingStorageRead::<TipStorageTag>::get_pool_balance(self.db_tx, pool_id)?;
+
+            let (pool_data, pool_balance) = match (pool_data, pool_balance) {
+                (Some(pool_data), Some(pool_balance)) => (pool_data, pool_balance),
+                (None, _) => {
+                    errors.push(DbVerificationError::PoolDataMissing(pool_id));
+                    continue;
+                }
+                (Some(_), None) => {
+                    errors.push(DbVerificationError::PoolBalanceMissing(pool_id));
+                    continue;
+                }
+            };
+
+            let delegation_shares =
+                PoSAccountingStorageRead::<TipStorageTag>::get_pool_delegations_shares(
+                    self.db_tx, pool_id,
+                )?
+                .unwrap_or_default();
+            let expected = pool_data.staker_balance().ok().and_then(|staker_balance| {
+                delegation_shares.values().try_fold(staker_balance, |acc, share| acc + *share)
+            });
+
+            if expected != Some(pool_balance) {
+                errors.push(DbVerificationError::PoolBalanceMismatch {
+                    pool_id,
+                    balance: pool_balance,
+                    expected,
+                });
+            }
+        }
+
+        Ok(())
     }
 }
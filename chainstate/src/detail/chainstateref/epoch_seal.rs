@@ -21,6 +21,7 @@ use chainstate_types::{
 use common::{
     chain::{
         block::{consensus_data::PoSData, ConsensusData},
+        config::EpochIndex,
         Block, ChainConfig, PoolId, TxOutput,
     },
     primitives::BlockHeight,
@@ -109,21 +110,32 @@ fn rollback_epoch_seal<S: BlockchainStorageWrite>(
         .checked_sub(chain_config.sealed_epoch_distance_from_tip() as u64)
         .expect("always >= 0; because the epoch to unseal cannot be higher than the current epoch");
 
+    unseal_epoch(db_tx, epoch_index_to_unseal)?;
+
+    Ok(())
+}
+
+/// Undo merging of the data of the specified epoch into the sealed state.
+/// Return false if there was nothing to undo, i.e. the epoch had no accounting data.
+#[log_error]
+pub fn unseal_epoch<S: BlockchainStorageWrite>(
+    db_tx: &mut S,
+    epoch_index: EpochIndex,
+) -> Result<bool, EpochSealError> {
     // retrieve delta undo for the epoch to unseal
-    let epoch_undo = db_tx
-        .get_accounting_epoch_undo_delta(epoch_index_to_unseal)
-        .map_err(BlockError::from)
-        .log_err()?;
+    let epoch_undo = db_tx.get_accounting_epoch_undo_delta(epoch_index).log_err()?;
 
     // if no undo found just skip
-    if let Some(epoch_undo) = epoch_undo {
-        let mut db = PoSAccountingDB::<_, SealedStorageTag>::new(&mut *db_tx);
-        db.undo_merge_with_delta(epoch_undo)?;
+    match epoch_undo {
+        Some(epoch_undo) => {
+            let mut db = PoSAccountingDB::<_, SealedStorageTag>::new(&mut *db_tx);
+            db.undo_merge_with_delta(epoch_undo)?;
 
-        db_tx.del_accounting_epoch_undo_delta(epoch_index_to_unseal)?;
+            db_tx.del_accounting_epoch_undo_delta(epoch_index)?;
+            Ok(true)
+        }
+        None => Ok(false),
     }
-
-    Ok(())
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -34,10 +34,10 @@ use tx_verifier::{
     TransactionVerifierStorageError,
 };
 use utils::{ensure, log_error, tap_log::TapLog};
-use utxo::UtxosDB;
+use utxo::{UtxosCacheLimits, UtxosDB};
 
 use crate::{
-    ban_score::BanScore, calculate_median_time_past, BlockProcessingErrorClass,
+    ban_score::BanScore, detail::cache_memory::CacheKind, BlockProcessingErrorClass,
     BlockProcessingErrorClassification, TransactionVerificationStrategy,
 };
 
@@ -78,7 +78,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                 .into();
 
             // The comparison for timelock is done with median_time_past based on BIP-113, i.e., the median time instead of the block timestamp
-            let median_time_past = calculate_median_time_past(self, &new_tip.prev_block_id());
+            let median_time_past = self.median_time_past(&new_tip.prev_block_id());
 
             let connected_txs = self
                 .tx_verification_strategy
@@ -107,6 +107,8 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             )?;
         }
 
+        Self::update_utxo_cache_metrics(&tx_verifier);
+
         let consumed_verifier = tx_verifier.consume()?;
         let consumed_epoch_data = epoch_data_cache.consume();
         Ok((consumed_verifier, consumed_epoch_data))
@@ -138,6 +140,12 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
 
         let cur_tip = self.get_best_block_id()?;
         let mut tx_verifier = TransactionVerifier::new(self, self.chain_config);
+        // The verifier may accumulate the changes of many blocks and can't be flushed to the db,
+        // so only the unmodified utxos can be evicted; the modified ones are not bounded.
+        // The verifier is short-lived, so it gets the memory that is available at the moment.
+        tx_verifier.set_utxo_cache_limits(Some(UtxosCacheLimits {
+            max_memory_usage: self.cache_memory_budget.max_usage(CacheKind::UtxoCache),
+        }));
         let mut epoch_data_cache = EpochDataCache::new(&self.db_tx);
 
         // Disconnect the current chain if it is not a genesis
@@ -194,6 +202,14 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
 
         Ok((tx_verifier, epoch_data_cache))
     }
+
+    /// Export the utxo cache statistics of a verifier used for in-memory block processing.
+    pub fn update_utxo_cache_metrics(tx_verifier: &TxVerifier<'a, '_, S, V>) {
+        let counters = tx_verifier.utxo_cache_counters();
+        metrics::node::UTXO_CACHE_LOADED_ENTRIES.inc_by(counters.loaded_entries);
+        metrics::node::UTXO_CACHE_EVICTED_ENTRIES.inc_by(counters.evicted_entries);
+        metrics::node::UTXO_CACHE_MEMORY_USAGE.set(tx_verifier.utxo_cache_memory_usage() as u64);
+    }
 }
 
 type TxVerifier<'a, 'b, S, V> = TransactionVerifier<
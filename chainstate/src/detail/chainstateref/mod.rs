@@ -17,6 +17,7 @@ mod block_info;
 mod consistency_checker;
 mod epoch_seal;
 mod in_memory_reorg;
+mod startup_recovery;
 mod tx_verifier_storage;
 
 use itertools::Itertools;
@@ -24,13 +25,15 @@ use serialization::{Decode, Encode};
 use std::{
     cmp::max,
     collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
 };
 use thiserror::Error;
 
 use chainstate_storage::{BlockchainStorageRead, BlockchainStorageWrite, TransactionRw};
 use chainstate_types::{
-    block_index_ancestor_getter, get_skip_height, BlockIndex, BlockIndexHandle, BlockStatus,
-    BlockValidationStage, EpochData, EpochDataCache, GenBlockIndex, GetAncestorError,
+    block_index_ancestor_getter, get_skip_height, BlockFilterCommitment, BlockIndex,
+    BlockIndexHandle, BlockStatus, BlockValidationStage, ChainSplitRecord, CompactBlockFilter,
+    EpochData, EpochDataCache, GenBlockIndex, GetAncestorError, PoolBlockRecord,
     PropertyQueryError, TipStorageTag,
 };
 use common::{
@@ -45,7 +48,8 @@ use common::{
         PoolId, Transaction, TxInput, TxOutput, UtxoOutPoint,
     },
     primitives::{
-        id::WithId, time::Time, Amount, BlockCount, BlockDistance, BlockHeight, Id, Idable,
+        id::WithId, time::Time, Amount, BlockCount, BlockDistance, BlockHeight, Fee, Id, Idable,
+        H256,
     },
     time_getter::TimeGetter,
     Uint256,
@@ -54,25 +58,29 @@ use logging::log;
 use pos_accounting::{
     PoSAccountingDB, PoSAccountingDelta, PoSAccountingStorageRead, PoSAccountingView,
 };
-use tx_verifier::transaction_verifier::TransactionVerifier;
+use tx_verifier::transaction_verifier::{issued_ticker, TransactionVerifier};
 use utils::{debug_assert_or_log, ensure, log_error, tap_log::TapLog};
-use utxo::{UtxosCache, UtxosDB, UtxosStorageRead, UtxosView};
+use utxo::{Utxo, UtxosBlockUndo, UtxosCache, UtxosDB, UtxosStorageRead, UtxosView};
 
 use crate::{BlockError, ChainstateConfig};
 
-use self::{
-    block_info::BlockInfo, consistency_checker::ConsistencyChecker,
-    tx_verifier_storage::gen_block_index_getter,
-};
+use self::{block_info::BlockInfo, consistency_checker::ConsistencyChecker};
 
 use super::{
-    median_time::calculate_median_time_past, transaction_verifier::flush::flush_to_storage,
+    block_fee_check::ConnectedBlockFees, block_filter::compact_filter_elements,
+    block_index_cache::BlockIndexCache, cache_memory::CacheMemoryBudget, chain_tips::ChainTip,
+    median_time::MedianTimePastCache, transaction_verifier::flush::flush_to_storage,
     tx_verification_strategy::TransactionVerificationStrategy, BlockSizeError, CheckBlockError,
     CheckBlockTransactionsError,
 };
 
+pub use consistency_checker::{DbVerificationError, DbVerificationLevel};
 pub use epoch_seal::EpochSealError;
 pub use in_memory_reorg::InMemoryReorgError;
+pub use startup_recovery::{StartupRecoveryError, STARTUP_RECOVERY_MAX_DEPTH};
+
+/// The maximum number of blocks whose undo data is pruned when a single block is connected.
+const MAX_UNDO_DATA_PRUNED_PER_BLOCK: u64 = 1000;
 
 pub struct ChainstateRef<'a, S, V> {
     chain_config: &'a ChainConfig,
@@ -80,6 +88,14 @@ pub struct ChainstateRef<'a, S, V> {
     tx_verification_strategy: &'a V,
     db_tx: S,
     time_getter: &'a TimeGetter,
+    median_time_past_cache: &'a MedianTimePastCache,
+    cache_memory_budget: &'a CacheMemoryBudget,
+    block_index_cache: &'a BlockIndexCache,
+    // Block indices written or deleted via this object; they are applied to the cache once
+    // the db transaction is committed.
+    block_index_changes: BTreeMap<Id<Block>, Option<BlockIndex>>,
+    // Fees of the blocks connected via this object
+    connected_block_fees: Vec<ConnectedBlockFees>,
 }
 
 impl<S: BlockchainStorageRead, V: TransactionVerificationStrategy> BlockIndexHandle
@@ -123,7 +139,9 @@ impl<S: BlockchainStorageRead, V: TransactionVerificationStrategy> BlockIndexHan
 impl<S: TransactionRw, V> ChainstateRef<'_, S, V> {
     #[log_error]
     pub fn commit_db_tx(self) -> chainstate_storage::Result<()> {
-        self.db_tx.commit()
+        self.db_tx.commit()?;
+        self.block_index_cache.apply_changes(self.block_index_changes);
+        Ok(())
     }
 
     pub fn check_storage_error(&self) -> chainstate_storage::Result<()> {
@@ -138,6 +156,9 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         tx_verification_strategy: &'a V,
         db_tx: S,
         time_getter: &'a TimeGetter,
+        median_time_past_cache: &'a MedianTimePastCache,
+        cache_memory_budget: &'a CacheMemoryBudget,
+        block_index_cache: &'a BlockIndexCache,
     ) -> Self {
         ChainstateRef {
             chain_config,
@@ -145,6 +166,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             db_tx,
             tx_verification_strategy,
             time_getter,
+            median_time_past_cache,
+            cache_memory_budget,
+            block_index_cache,
+            block_index_changes: BTreeMap::new(),
+            connected_block_fees: Vec::new(),
         }
     }
 
@@ -154,6 +180,9 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         tx_verification_strategy: &'a V,
         db_tx: S,
         time_getter: &'a TimeGetter,
+        median_time_past_cache: &'a MedianTimePastCache,
+        cache_memory_budget: &'a CacheMemoryBudget,
+        block_index_cache: &'a BlockIndexCache,
     ) -> Self {
         ChainstateRef {
             chain_config,
@@ -161,9 +190,19 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             db_tx,
             tx_verification_strategy,
             time_getter,
+            median_time_past_cache,
+            cache_memory_budget,
+            block_index_cache,
+            block_index_changes: BTreeMap::new(),
+            connected_block_fees: Vec::new(),
         }
     }
 
+    /// Take the fees of the blocks that have been connected via this object so far.
+    pub fn take_connected_block_fees(&mut self) -> Vec<ConnectedBlockFees> {
+        std::mem::take(&mut self.connected_block_fees)
+    }
+
     pub fn make_utxo_view(&self) -> impl UtxosView<Error = <S as UtxosStorageRead>::Error> + '_ {
         UtxosDB::new(&self.db_tx)
     }
@@ -183,6 +222,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.time_getter.get_time()
     }
 
+    /// Return the median time past for the given block, using the cached value if possible.
+    pub fn median_time_past(&self, starting_block: &Id<GenBlock>) -> BlockTimestamp {
+        self.median_time_past_cache.get_or_calculate(self, starting_block)
+    }
+
     #[log_error]
     pub fn get_best_block_id(&self) -> Result<Id<GenBlock>, PropertyQueryError> {
         self.db_tx
@@ -197,7 +241,103 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         block_id: &Id<Block>,
     ) -> Result<Option<BlockIndex>, PropertyQueryError> {
         log::trace!("Loading block index of id: {}", block_id);
-        self.db_tx.get_block_index(block_id).map_err(PropertyQueryError::from)
+        self.get_block_index_cached(block_id).map_err(PropertyQueryError::from)
+    }
+
+    /// Return the block index, looking it up in the block index cache first and populating
+    /// the cache on a miss.
+    fn get_block_index_cached(
+        &self,
+        block_id: &Id<Block>,
+    ) -> chainstate_storage::Result<Option<BlockIndex>> {
+        // The cache only contains committed values, so the changes made via this object
+        // take precedence.
+        if let Some(block_index) = self.block_index_changes.get(block_id) {
+            return Ok(block_index.clone());
+        }
+
+        if let Some(block_index) = self.block_index_cache.get(block_id) {
+            return Ok(Some(block_index));
+        }
+
+        let block_index = self.db_tx.get_block_index(block_id)?;
+        if let Some(block_index) = &block_index {
+            self.block_index_cache.insert(block_index.clone());
+        }
+        Ok(block_index)
+    }
+
+    fn get_gen_block_index_cached(
+        &self,
+        block_id: &Id<GenBlock>,
+    ) -> chainstate_storage::Result<Option<GenBlockIndex>> {
+        match block_id.classify(self.chain_config) {
+            GenBlockId::Genesis(_id) => Ok(Some(GenBlockIndex::genesis(self.chain_config))),
+            GenBlockId::Block(id) => {
+                self.get_block_index_cached(&id).map(|b| b.map(GenBlockIndex::Block))
+            }
+        }
+    }
+
+    /// Get the main chain blocks whose compact filters haven't been built yet, e.g. because the
+    /// filter index has just been enabled, in the chain order.
+    ///
+    /// A filter is only built once the filter of the previous block exists, so these are
+    /// the blocks above the last one that has a filter.
+    #[log_error]
+    pub fn get_mainchain_blocks_without_filter(
+        &self,
+    ) -> Result<Vec<Id<Block>>, PropertyQueryError> {
+        let mut block_ids = Vec::new();
+        let mut block_id = self.get_best_block_id()?;
+
+        while let GenBlockId::Block(id) = block_id.classify(self.chain_config) {
+            if self.db_tx.get_block_filter_commitment(&id)?.is_some() {
+                break;
+            }
+
+            block_id = *self.get_existing_block_index(&id)?.prev_block_id();
+            block_ids.push(id);
+        }
+
+        block_ids.reverse();
+        Ok(block_ids)
+    }
+
+    /// Populate the block index cache with the block indices of the most recent mainchain
+    /// blocks, as many as the cache can hold.
+    #[log_error]
+    pub fn load_block_index_cache(&self) -> Result<(), PropertyQueryError> {
+        let mut block_indices = Vec::new();
+        let mut memory_usage = 0;
+        let mut block_id = self.get_best_block_id()?;
+
+        while let GenBlockId::Block(id) = block_id.classify(self.chain_config) {
+            let block_index = self
+                .db_tx
+                .get_block_index(&id)?
+                .ok_or(PropertyQueryError::BlockIndexNotFound(block_id))?;
+
+            memory_usage += BlockIndexCache::entry_size(&block_index);
+            if memory_usage > self.block_index_cache.max_memory_usage() {
+                break;
+            }
+
+            block_id = *block_index.prev_block_id();
+            block_indices.push(block_index);
+        }
+
+        // Insert the older blocks first, so that they are the first to be evicted.
+        for block_index in block_indices.into_iter().rev() {
+            self.block_index_cache.insert(block_index);
+        }
+
+        log::debug!(
+            "Block index cache loaded, memory usage: {} bytes",
+            self.block_index_cache.memory_usage()
+        );
+
+        Ok(())
     }
 
     #[log_error]
@@ -214,8 +354,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         &self,
         block_id: &Id<GenBlock>,
     ) -> Result<Option<GenBlockIndex>, PropertyQueryError> {
-        gen_block_index_getter(&self.db_tx, self.chain_config, block_id)
-            .map_err(PropertyQueryError::from)
+        self.get_gen_block_index_cached(block_id).map_err(PropertyQueryError::from)
     }
 
     #[log_error]
@@ -270,6 +409,30 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.db_tx.get_block(block_id).map_err(PropertyQueryError::from)
     }
 
+    #[log_error]
+    pub fn get_undo_data(
+        &self,
+        block_id: Id<Block>,
+    ) -> Result<Option<UtxosBlockUndo>, PropertyQueryError> {
+        self.db_tx.get_undo_data(block_id).map_err(PropertyQueryError::from)
+    }
+
+    /// Go over the entire utxo set, stopping at the first error returned by the function.
+    pub fn try_for_each_utxo(
+        &self,
+        mut f: impl FnMut(UtxoOutPoint, Utxo) -> Result<(), PropertyQueryError>,
+    ) -> Result<(), PropertyQueryError> {
+        let mut result = Ok(());
+        self.db_tx.for_each_utxo(&mut |outpoint, utxo| match f(outpoint, utxo) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(err) => {
+                result = Err(err);
+                ControlFlow::Break(())
+            }
+        })?;
+        result
+    }
+
     #[log_error]
     pub fn block_exists(&self, block_id: Id<Block>) -> Result<bool, PropertyQueryError> {
         self.db_tx.block_exists(block_id).map_err(PropertyQueryError::from)
@@ -296,6 +459,48 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(self.db_tx.get_min_height_with_allowed_reorg()?.unwrap_or(0.into()))
     }
 
+    /// The lowest main chain height whose undo data hasn't been pruned.
+    #[log_error]
+    pub fn get_min_height_with_undo_data(&self) -> Result<BlockHeight, PropertyQueryError> {
+        Ok(self.db_tx.get_min_height_with_undo_data()?.unwrap_or(0.into()))
+    }
+
+    /// The blocks that have been accepted but whose activation hasn't finished yet.
+    #[log_error]
+    pub fn get_pending_block_activations(&self) -> Result<Vec<Id<Block>>, PropertyQueryError> {
+        Ok(self.db_tx.get_pending_block_activations()?)
+    }
+
+    #[log_error]
+    pub fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, PropertyQueryError> {
+        self.db_tx.get_chain_split_records().map_err(PropertyQueryError::from)
+    }
+
+    #[log_error]
+    pub fn get_block_filter(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<CompactBlockFilter>, PropertyQueryError> {
+        self.db_tx.get_block_filter(block_id).map_err(PropertyQueryError::from)
+    }
+
+    #[log_error]
+    pub fn get_block_filter_commitment(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockFilterCommitment>, PropertyQueryError> {
+        self.db_tx
+            .get_block_filter_commitment(block_id)
+            .map_err(PropertyQueryError::from)
+    }
+
+    pub fn get_pool_blocks(
+        &self,
+        pool_id: PoolId,
+    ) -> Result<Vec<PoolBlockRecord>, PropertyQueryError> {
+        self.db_tx.get_pool_blocks(pool_id).map_err(PropertyQueryError::from)
+    }
+
     #[log_error]
     pub fn get_ancestor(
         &self,
@@ -303,8 +508,10 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         target_height: BlockHeight,
     ) -> Result<GenBlockIndex, GetAncestorError> {
         block_index_ancestor_getter(
-            gen_block_index_getter,
-            &self.db_tx,
+            |this: &Self, _chain_config: &ChainConfig, block_id: &Id<GenBlock>| {
+                this.get_gen_block_index_cached(block_id)
+            },
+            self,
             self.chain_config,
             block_index.into(),
             target_height,
@@ -377,6 +584,19 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.db_tx.get_token_id(tx_id).map_err(PropertyQueryError::from)
     }
 
+    #[log_error]
+    pub fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, PropertyQueryError> {
+        self.db_tx.get_token_ids_by_ticker(ticker).map_err(PropertyQueryError::from)
+    }
+
+    #[log_error]
+    pub fn get_token_tickers_indexed(&self) -> Result<bool, PropertyQueryError> {
+        self.db_tx.get_token_tickers_indexed().map_err(PropertyQueryError::from)
+    }
+
     #[log_error]
     pub fn get_header_from_height(
         &self,
@@ -384,8 +604,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     ) -> Result<Option<SignedBlockHeader>, PropertyQueryError> {
         let id = self.get_existing_block_id_by_height(height)?;
         let id = id
-            .classify(self.chain_config)
-            .chain_block_id()
+            .to_block_id(self.chain_config)
             .ok_or(PropertyQueryError::GenesisHeaderRequested)
             .log_err()?;
         Ok(self.get_block_index(&id)?.map(|block_index| block_index.into_block_header()))
@@ -703,7 +922,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         // This enforces the minimum accepted timestamp for the block. Depending on the consensus algorithm,
         // there might be extra checks. For example, PoS requires the timestamp to be greater the previous
         // block's timestamp.
-        let median_time_past = calculate_median_time_past(self, header.prev_block_id());
+        let median_time_past = self.median_time_past(header.prev_block_id());
         ensure!(
             header.timestamp() >= median_time_past,
             CheckBlockError::BlockTimeOrderInvalid(header.timestamp(), median_time_past),
@@ -802,7 +1021,11 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     }
 
     #[log_error]
-    fn check_block_size(&self, block: &Block) -> Result<(), BlockSizeError> {
+    fn check_block_size(
+        &self,
+        block: &Block,
+        block_height: BlockHeight,
+    ) -> Result<(), BlockSizeError> {
         let block_size = block.block_size();
 
         ensure!(
@@ -813,20 +1036,19 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             )
         );
 
+        let max_size_from_txs = self.chain_config.max_block_size_from_std_scripts(block_height);
         ensure!(
-            block_size.size_from_txs() <= self.chain_config.max_block_size_from_std_scripts(),
-            BlockSizeError::SizeOfTxs(
-                block_size.size_from_txs(),
-                self.chain_config.max_block_size_from_std_scripts()
-            )
+            block_size.size_from_txs() <= max_size_from_txs,
+            BlockSizeError::SizeOfTxs(block_size.size_from_txs(), max_size_from_txs)
         );
 
+        let max_size_from_smart_contracts =
+            self.chain_config.max_block_size_from_smart_contracts(block_height);
         ensure!(
-            block_size.size_from_smart_contracts()
-                <= self.chain_config.max_block_size_from_smart_contracts(),
+            block_size.size_from_smart_contracts() <= max_size_from_smart_contracts,
             BlockSizeError::SizeOfSmartContracts(
                 block_size.size_from_smart_contracts(),
-                self.chain_config.max_block_size_from_smart_contracts()
+                max_size_from_smart_contracts
             )
         );
 
@@ -898,7 +1120,17 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
 
         self.check_block_header_impl(&header_with_id)?;
 
-        self.check_block_size(block).map_err(CheckBlockError::BlockSizeError)?;
+        let block_height = self
+            .get_gen_block_index(&block.prev_block_id())?
+            .ok_or_else(|| PropertyQueryError::PrevBlockIndexNotFound {
+                block_id: block.get_id(),
+                prev_block_id: block.prev_block_id(),
+            })?
+            .block_height()
+            .next_height();
+
+        self.check_block_size(block, block_height)
+            .map_err(CheckBlockError::BlockSizeError)?;
 
         self.check_block_reward_maturity_settings(block)?;
 
@@ -925,15 +1157,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             );
         }
 
-        let prev_block_height = self
-            .get_gen_block_index(&block.prev_block_id())?
-            .ok_or_else(|| PropertyQueryError::PrevBlockIndexNotFound {
-                block_id: block.get_id(),
-                prev_block_id: block.prev_block_id(),
-            })?
-            .block_height();
-
-        self.check_transactions(block, prev_block_height.next_height())
+        self.check_transactions(block, block_height)
             .map_err(CheckBlockError::CheckTransactionFailed)?;
 
         Ok(())
@@ -960,8 +1184,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                 .get_block_id_by_height(&block_height)?
                 .expect("Since block_height is >= best_height, this must exist");
             let block_id = block_id
-                .classify(self.chain_config)
-                .chain_block_id()
+                .to_block_id(self.chain_config)
                 .expect("Since the height is never zero, this cannot be genesis");
             Ok(block_id)
         };
@@ -981,6 +1204,46 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.get_higher_block_ids_sorted_by_height(0.into())
     }
 
+    /// Return the tip of the main chain followed by the tips of all known branches.
+    #[log_error]
+    pub fn get_chain_tips(&self) -> Result<Vec<ChainTip>, PropertyQueryError> {
+        let best_block_index = self.get_best_block_index()?;
+
+        let block_indices = self
+            .get_block_id_tree_as_list()?
+            .iter()
+            .map(|block_id| self.get_existing_block_index(block_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let parent_ids = block_indices
+            .iter()
+            .map(|block_index| *block_index.prev_block_id())
+            .collect::<BTreeSet<_>>();
+
+        let mut result = vec![ChainTip::new_active(&best_block_index)];
+        for block_index in block_indices {
+            let block_id: Id<GenBlock> = (*block_index.block_id()).into();
+            if parent_ids.contains(&block_id) || block_id == best_block_index.block_id() {
+                continue;
+            }
+
+            let branch_length = self.get_branch_length(&block_index)?;
+            result.push(ChainTip::new_fork(&block_index, branch_length));
+        }
+
+        Ok(result)
+    }
+
+    /// Return the number of blocks between the block and the main chain; zero if the block
+    /// is itself on the main chain.
+    #[log_error]
+    pub fn get_branch_length(&self, block_index: &BlockIndex) -> Result<u64, PropertyQueryError> {
+        let fork_point =
+            self.last_common_ancestor_in_main_chain(&block_index.clone().into_gen_block_index())?;
+        let distance = (block_index.block_height() - fork_point.block_height())
+            .expect("the fork point can't be higher than the block");
+        Ok(distance.to_int().unsigned_abs())
+    }
+
     /// Return ids of all blocks with height bigger or equal to the specified one,
     /// sorted by height (lower first).
     // TODO: this function iterates over all block indices in the DB, which is too expensive
@@ -1023,8 +1286,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
 
             let prev_block_id = cur_block_index
                 .prev_block_id()
-                .classify(self.chain_config)
-                .chain_block_id()
+                .to_block_id(self.chain_config)
                 .expect("Genesis at non-zero height");
 
             if seen_block_ids.contains(&prev_block_id) {
@@ -1131,7 +1393,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             .get_existing_block_id_by_height(&min_height)
             .map_err(BlockError::PropertyQueryError)?;
 
-        self.disconnect_tip_in_memory_until(
+        let (tx_verifier, _) = self.disconnect_tip_in_memory_until(
             &lowest_block_id,
             |disconnected_block_index, tx_verifier, _| -> Result<_, BlockError> {
                 let cur_height = disconnected_block_index
@@ -1164,6 +1426,7 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
                 }
             },
         )?;
+        Self::update_utxo_cache_metrics(&tx_verifier);
 
         Ok(height_map)
     }
@@ -1191,7 +1454,26 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
     /// An error is only returned if the checks couldn't be performed for some reason.
     #[log_error]
     pub fn check_consistency(&self) -> Result<(), chainstate_storage::Error> {
-        ConsistencyChecker::new(&self.db_tx, self.chain_config)?.check()
+        let errors = ConsistencyChecker::new(&self.db_tx, self.chain_config)?
+            .check(DbVerificationLevel::BlockIndex, 0)?;
+
+        if let Some(error) = errors.first() {
+            // Certain tests check for this panic message.
+            panic!("Inconsistent chainstate: {error}");
+        }
+
+        Ok(())
+    }
+
+    /// Check the database invariants of the specified level and return all the found
+    /// inconsistencies.
+    #[log_error]
+    pub fn verify_database(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, chainstate_storage::Error> {
+        ConsistencyChecker::new(&self.db_tx, self.chain_config)?.check(level, depth)
     }
 }
 
@@ -1256,14 +1538,15 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         Ok(())
     }
 
+    /// Connect the transactions of the block and return the total fees of the block.
     #[log_error]
     fn connect_transactions(
         &mut self,
         block_index: &BlockIndex,
         block: &WithId<Block>,
-    ) -> Result<(), BlockError> {
+    ) -> Result<Option<Fee>, BlockError> {
         // The comparison for timelock is done with median_time_past based on BIP-113, i.e., the median time instead of the block timestamp
-        let median_time_past = calculate_median_time_past(self, &block.prev_block_id());
+        let median_time_past = self.median_time_past(&block.prev_block_id());
 
         let connected_txs = self
             .tx_verification_strategy
@@ -1277,10 +1560,19 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
             )
             .log_err()?;
 
+        let fees = connected_txs.connected_block_fees();
+        if let Some(fees) = fees {
+            self.connected_block_fees.push(ConnectedBlockFees {
+                block_id: block.get_id(),
+                block_height: block_index.block_height(),
+                fees,
+            });
+        }
+
         let consumed = connected_txs.consume()?;
         flush_to_storage(self, consumed)?;
 
-        Ok(())
+        Ok(fees)
     }
 
     #[log_error]
@@ -1297,6 +1589,23 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         Ok(())
     }
 
+    /// If the block has been produced by a stake pool, record it in the pool's list of blocks.
+    fn record_pool_block(&mut self, block_index: &BlockIndex, fees: Fee) -> Result<(), BlockError> {
+        let pool_id = match block_index.block_header().consensus_data() {
+            ConsensusData::None | ConsensusData::PoW(_) => return Ok(()),
+            ConsensusData::PoS(pos_data) => *pos_data.stake_pool_id(),
+        };
+
+        let block_height = block_index.block_height();
+        let block_subsidy = self.chain_config.block_subsidy_at_height(&block_height);
+        let reward = (block_subsidy + fees.0)
+            .expect("the reward has been checked when the block was connected");
+        let record = PoolBlockRecord::new(*block_index.block_id(), block_height, reward);
+        self.db_tx.set_pool_block(pool_id, &record)?;
+
+        Ok(())
+    }
+
     // Connect new block
     #[log_error]
     fn connect_tip(&mut self, block_index: &BlockIndex) -> Result<(), BlockError> {
@@ -1336,7 +1645,15 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
             BlockError::InvariantErrorInvalidTip(block.get_id().into()),
         );
 
-        self.connect_transactions(block_index, &block)?;
+        let fees = self.connect_transactions(block_index, &block)?;
+
+        if let Some(fees) = fees {
+            self.record_pool_block(block_index, fees)?;
+        }
+
+        if self.chainstate_config.block_filter_index_enabled() {
+            self.build_block_filter(&block)?;
+        }
 
         self.db_tx.set_block_id_at_height(
             &block_index.block_height(),
@@ -1344,6 +1661,10 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         )?;
         self.db_tx.set_best_block_id(&(*block_index.block_id()).into())?;
 
+        if let Some(retention_depth) = self.chainstate_config.undo_data_retention_depth {
+            self.prune_undo_data(block_index.block_height(), retention_depth)?;
+        }
+
         if block_index.status().last_valid_stage() != BlockValidationStage::FullyChecked {
             let mut block_status = block_status;
             block_status.advance_validation_stage_to(BlockValidationStage::FullyChecked);
@@ -1354,6 +1675,53 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         self.post_connect_tip(block_index, block.as_ref())
     }
 
+    /// Delete the undo data of the main chain blocks that are more than `retention_depth` blocks
+    /// below the new tip.
+    ///
+    /// At most [MAX_UNDO_DATA_PRUNED_PER_BLOCK] blocks are pruned at once, so that enabling
+    /// the retention for an existing chain doesn't result in a huge db transaction; the rest
+    /// is pruned when the next blocks are connected.
+    fn prune_undo_data(
+        &mut self,
+        tip_height: BlockHeight,
+        retention_depth: u64,
+    ) -> Result<(), BlockError> {
+        let Some(max_pruned_height) = tip_height.into_int().checked_sub(retention_depth) else {
+            return Ok(());
+        };
+
+        let min_height_with_undo_data =
+            self.get_min_height_with_undo_data().map_err(BlockError::PropertyQueryError)?;
+        // The genesis has no undo data
+        let start_height = std::cmp::max(min_height_with_undo_data, BlockHeight::one());
+        let end_height = std::cmp::min(
+            BlockHeight::new(max_pruned_height + 1),
+            BlockHeight::new(start_height.into_int() + MAX_UNDO_DATA_PRUNED_PER_BLOCK),
+        );
+        if start_height >= end_height {
+            return Ok(());
+        }
+
+        for height in start_height.iter_up_to_including(
+            end_height.prev_height().expect("end height is above the start height"),
+        ) {
+            let block_id = self
+                .db_tx
+                .get_block_id_by_height(&height)?
+                .and_then(|id| id.to_block_id(self.chain_config))
+                .expect("the main chain blocks below the tip must exist");
+
+            self.db_tx.del_undo_data(block_id)?;
+            self.db_tx.del_tokens_accounting_undo_data(block_id)?;
+            self.db_tx.del_orders_accounting_undo_data(block_id)?;
+            self.db_tx.del_pos_accounting_undo_data(block_id)?;
+        }
+
+        self.db_tx.set_min_height_with_undo_data(end_height)?;
+
+        Ok(())
+    }
+
     /// Does a read-modify-write operation on the database and disconnects a block
     /// by unsetting the `next` pointer.
     /// Returns the previous block (the last block in the main-chain)
@@ -1365,8 +1733,7 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         let best_block_id = self
             .get_best_block_id()
             .expect("Best block not initialized")
-            .classify(self.chain_config)
-            .chain_block_id()
+            .to_block_id(self.chain_config)
             .expect("Cannot disconnect genesis");
 
         // Optionally, we can double-check that the tip is what we're disconnecting
@@ -1378,12 +1745,27 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
             .get_block_index(&best_block_id)
             .expect("Database error on retrieving current best block index")
             .expect("Best block index not present in the database");
+        let min_height_with_undo_data =
+            self.get_min_height_with_undo_data().map_err(BlockError::PropertyQueryError)?;
+        ensure!(
+            block_index.block_height() >= min_height_with_undo_data,
+            BlockError::UndoDataPruned {
+                block_id: best_block_id,
+                block_height: block_index.block_height(),
+                min_height_with_undo_data,
+            }
+        );
+
         let block = self.get_block_from_index(&block_index)?.expect("Inconsistent DB");
         // Disconnect transactions
         self.disconnect_transactions(&block.into())?;
+        if let ConsensusData::PoS(_) = block_index.block_header().consensus_data() {
+            self.db_tx.del_pool_block(*block_index.block_id())?;
+        }
         self.db_tx.set_best_block_id(block_index.prev_block_id())?;
         // Disconnect block
         self.db_tx.del_block_id_at_height(&block_index.block_height())?;
+        self.median_time_past_cache.invalidate(&best_block_id.into());
 
         let prev_block_index = self
             .get_previous_block_index(&block_index)
@@ -1423,7 +1805,10 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
 
     #[log_error]
     pub fn set_block_index(&mut self, block_index: &BlockIndex) -> Result<(), BlockError> {
-        self.db_tx.set_block_index(block_index).map_err(BlockError::from)
+        self.db_tx.set_block_index(block_index).map_err(BlockError::from)?;
+        self.block_index_changes
+            .insert(*block_index.block_id(), Some(block_index.clone()));
+        Ok(())
     }
 
     #[log_error]
@@ -1450,6 +1835,7 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
             );
 
             self.db_tx.del_block_index(*block_id)?;
+            self.block_index_changes.insert(*block_id, None);
         }
         Ok(())
     }
@@ -1470,6 +1856,30 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         self.set_block_index(&block_index.with_status(block_status))
     }
 
+    /// Add the block to the persisted queue of the blocks whose activation hasn't finished yet.
+    #[log_error]
+    pub fn add_pending_block_activation(&mut self, block_id: Id<Block>) -> Result<(), BlockError> {
+        let mut block_ids = self.db_tx.get_pending_block_activations()?;
+        if !block_ids.contains(&block_id) {
+            block_ids.push(block_id);
+            self.db_tx.set_pending_block_activations(&block_ids)?;
+        }
+        Ok(())
+    }
+
+    #[log_error]
+    pub fn remove_pending_block_activation(
+        &mut self,
+        block_id: &Id<Block>,
+    ) -> Result<(), BlockError> {
+        let mut block_ids = self.db_tx.get_pending_block_activations()?;
+        if let Some(pos) = block_ids.iter().position(|id| id == block_id) {
+            block_ids.remove(pos);
+            self.db_tx.set_pending_block_activations(&block_ids)?;
+        }
+        Ok(())
+    }
+
     #[log_error]
     pub fn update_min_height_with_allowed_reorg(&mut self) -> Result<(), BlockError> {
         let stored_min_height = self
@@ -1508,6 +1918,71 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRe
         Ok(())
     }
 
+    /// Build the token ticker index from the issued tokens, e.g. if the tokens were issued before
+    /// the index was introduced. The tickers of fungible tokens are taken from the tokens
+    /// accounting data and the tickers of NFTs from their issuance transactions.
+    #[log_error]
+    pub fn build_token_ticker_index(&mut self) -> Result<(), BlockError> {
+        let mut ticker_index = BTreeMap::<Vec<u8>, BTreeSet<TokenId>>::new();
+        for (token_id, token_data) in self.db_tx.get_tokens_data_map()? {
+            match token_data {
+                tokens_accounting::TokenData::FungibleToken(data) => {
+                    ticker_index.entry(data.token_ticker().to_vec()).or_default().insert(token_id);
+                }
+            }
+        }
+        for (token_id, aux_data) in self.db_tx.get_token_aux_data_map()? {
+            if let Some(ticker) = issued_ticker(aux_data.issuance_tx()) {
+                ticker_index.entry(ticker.to_vec()).or_default().insert(token_id);
+            }
+        }
+
+        for (ticker, token_ids) in ticker_index {
+            self.db_tx.set_token_ticker(&ticker, &token_ids)?;
+        }
+        self.db_tx.set_token_tickers_indexed(true)?;
+
+        Ok(())
+    }
+
+    /// Build and store the compact filter of a connected block. The filter header commits to the
+    /// filter header of the previous block, so nothing is done if the latter is missing.
+    /// The filter can't be built if the block has transactions but its undo data is missing.
+    #[log_error]
+    pub fn build_block_filter(&mut self, block: &WithId<Block>) -> Result<(), BlockError> {
+        let prev_filter_header = match block.prev_block_id().classify(self.chain_config) {
+            GenBlockId::Genesis(_) => H256::zero(),
+            GenBlockId::Block(prev_block_id) => {
+                match self.db_tx.get_block_filter_commitment(&prev_block_id)? {
+                    Some(commitment) => *commitment.filter_header(),
+                    None => {
+                        log::warn!(
+                            "Not building the compact filter of block {}, because the filter of the previous block {} is missing",
+                            block.get_id(),
+                            prev_block_id
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        // Without the undo data the filter would lack the spent outputs
+        let undo = self.db_tx.get_undo_data(block.get_id())?;
+        ensure!(
+            undo.is_some() || block.transactions().is_empty(),
+            BlockError::UndoDataMissingForBlockFilter(block.get_id())
+        );
+        let filter = CompactBlockFilter::new(
+            &block.get_id(),
+            &compact_filter_elements(block, undo.as_ref()),
+        );
+        let commitment = BlockFilterCommitment::new(&filter, &prev_filter_header);
+        self.db_tx.set_block_filter(&block.get_id(), &filter, &commitment)?;
+
+        Ok(())
+    }
+
     #[log_error]
     fn post_disconnect_tip(&mut self, tip_height: BlockHeight) -> Result<(), BlockError> {
         epoch_seal::update_epoch_seal(
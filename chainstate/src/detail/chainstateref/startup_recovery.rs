@@ -0,0 +1,205 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_storage::BlockchainStorageWrite;
+use chainstate_types::PropertyQueryError;
+use common::{
+    chain::{Block, GenBlock, GenBlockId, Transaction},
+    primitives::{BlockHeight, Id, Idable},
+};
+use logging::log;
+use thiserror::Error;
+use utils::{ensure, log_error};
+use utxo::{FlushableUtxoView, UtxosCache, UtxosDB, UtxosView};
+
+use crate::TransactionVerificationStrategy;
+
+use super::{epoch_seal, ChainstateRef, EpochSealError};
+
+/// The maximum number of blocks that the utxo set may be ahead of the best block for it
+/// to still be rolled back at startup. The same distance (converted to epochs) is used to limit
+/// the number of epochs that can be unsealed.
+pub const STARTUP_RECOVERY_MAX_DEPTH: usize = 1000;
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum StartupRecoveryError {
+    #[error("Block storage error: `{0}`")]
+    StorageError(#[from] chainstate_storage::Error),
+    #[error("Property query error: `{0}`")]
+    PropertyQueryError(#[from] PropertyQueryError),
+    #[error("Error while unsealing an epoch: {0}")]
+    EpochSealError(#[from] EpochSealError),
+    #[error("Best block for utxos {best_block_for_utxos} is not a descendant of the best block {best_block_id}")]
+    UtxosBestBlockNotDescendant {
+        best_block_for_utxos: Id<GenBlock>,
+        best_block_id: Id<GenBlock>,
+    },
+    #[error("Best block for utxos {best_block_for_utxos} is more than {max_depth} blocks ahead of the best block {best_block_id}")]
+    UtxosTooFarAhead {
+        best_block_for_utxos: Id<GenBlock>,
+        best_block_id: Id<GenBlock>,
+        max_depth: usize,
+    },
+    #[error("Block {0} is missing")]
+    BlockMissing(Id<Block>),
+    #[error("Undo data is missing for block {0}")]
+    UndoDataMissing(Id<Block>),
+    #[error("Undo data is missing for transaction {0}")]
+    TxUndoDataMissing(Id<Transaction>),
+    #[error("Utxo error: {0}")]
+    UtxoError(#[from] utxo::Error),
+}
+
+impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> ChainstateRef<'_, S, V> {
+    /// Check that the utxo set and the sealed accounting state correspond to the best block.
+    /// The components that are ahead of it (which may happen if the node was terminated
+    /// in the middle of a write) are rolled back using their undo data.
+    ///
+    /// Return true if anything has been modified.
+    #[log_error]
+    pub fn recover_inconsistent_state(&mut self) -> Result<bool, StartupRecoveryError> {
+        let best_block_id = match self.db_tx.get_best_block_id()? {
+            Some(id) => id,
+            // Nothing to check before genesis is processed
+            None => return Ok(false),
+        };
+        let best_block_height = self.get_existing_gen_block_index(&best_block_id)?.block_height();
+
+        let utxos_recovered = self.roll_back_utxos_to(&best_block_id, best_block_height)?;
+        let seal_recovered = self.roll_back_epoch_seal_to(best_block_height)?;
+
+        Ok(utxos_recovered || seal_recovered)
+    }
+
+    fn roll_back_utxos_to(
+        &mut self,
+        best_block_id: &Id<GenBlock>,
+        best_block_height: BlockHeight,
+    ) -> Result<bool, StartupRecoveryError> {
+        let best_block_for_utxos = self.db_tx.get_best_block_for_utxos()?;
+        if best_block_for_utxos == *best_block_id {
+            return Ok(false);
+        }
+
+        log::warn!(
+            "Best block for utxos {best_block_for_utxos} doesn't match the best block {best_block_id}, trying to roll back the utxo set"
+        );
+
+        let not_descendant_error = || StartupRecoveryError::UtxosBestBlockNotDescendant {
+            best_block_for_utxos,
+            best_block_id: *best_block_id,
+        };
+
+        // Collect the blocks between the utxo set's best block and the chainstate's best block
+        let mut blocks_to_undo = Vec::new();
+        let mut current_id = best_block_for_utxos;
+        while current_id != *best_block_id {
+            ensure!(
+                blocks_to_undo.len() < STARTUP_RECOVERY_MAX_DEPTH,
+                StartupRecoveryError::UtxosTooFarAhead {
+                    best_block_for_utxos,
+                    best_block_id: *best_block_id,
+                    max_depth: STARTUP_RECOVERY_MAX_DEPTH,
+                }
+            );
+
+            let block_id = match current_id.classify(self.chain_config) {
+                GenBlockId::Block(id) => id,
+                GenBlockId::Genesis(_) => return Err(not_descendant_error()),
+            };
+            let block_index = self.get_existing_block_index(&block_id)?;
+            ensure!(
+                block_index.block_height() > best_block_height,
+                not_descendant_error()
+            );
+
+            current_id = *block_index.prev_block_id();
+            blocks_to_undo.push(block_id);
+        }
+
+        let utxos_db = UtxosDB::new(&self.db_tx);
+        let mut utxos_cache = UtxosCache::new(&utxos_db)?;
+        for block_id in blocks_to_undo {
+            self.undo_block_utxos(&mut utxos_cache, block_id)?;
+        }
+        utxos_cache.set_best_block(*best_block_id);
+        let consumed_utxos = utxos_cache.consume();
+
+        UtxosDB::new(&mut self.db_tx).batch_write(consumed_utxos)?;
+
+        Ok(true)
+    }
+
+    fn undo_block_utxos<P: UtxosView>(
+        &self,
+        utxos_cache: &mut UtxosCache<P>,
+        block_id: Id<Block>,
+    ) -> Result<(), StartupRecoveryError> {
+        let block = self
+            .db_tx
+            .get_block(block_id)?
+            .ok_or(StartupRecoveryError::BlockMissing(block_id))?;
+        let mut undo = self
+            .db_tx
+            .get_undo_data(block_id)?
+            .ok_or(StartupRecoveryError::UndoDataMissing(block_id))?
+            .consume();
+
+        // Transactions are undone in reverse order, so that outputs created and spent within
+        // the block end up removed.
+        for tx in block.transactions().iter().rev() {
+            let tx_id = tx.transaction().get_id();
+            let tx_undo = undo
+                .tx_undos
+                .remove(&tx_id)
+                .ok_or(StartupRecoveryError::TxUndoDataMissing(tx_id))?;
+            utxos_cache.disconnect_transaction(tx.transaction(), tx_undo)?;
+        }
+
+        utxos_cache.disconnect_block_transactable(
+            &block.block_reward_transactable(),
+            &block_id.into(),
+            undo.reward_undo,
+        )?;
+
+        Ok(())
+    }
+
+    fn roll_back_epoch_seal_to(
+        &mut self,
+        best_block_height: BlockHeight,
+    ) -> Result<bool, StartupRecoveryError> {
+        // Epochs up to this one must be sealed, the following ones must not.
+        let first_unsealed_epoch = self
+            .chain_config
+            .sealed_epoch_index(&best_block_height)
+            .map_or(0, |epoch_index| epoch_index + 1);
+        let max_epochs =
+            STARTUP_RECOVERY_MAX_DEPTH as u64 / self.chain_config.epoch_length().get() + 1;
+
+        // Note: epochs without accounting data don't have undo, so there may be gaps.
+        let mut recovered = false;
+        for epoch_index in (first_unsealed_epoch..first_unsealed_epoch + max_epochs).rev() {
+            if epoch_seal::unseal_epoch(&mut self.db_tx, epoch_index)? {
+                log::warn!(
+                    "Epoch {epoch_index} is sealed while the best block height is {best_block_height}, unsealed it"
+                );
+                recovered = true;
+            }
+        }
+
+        Ok(recovered)
+    }
+}
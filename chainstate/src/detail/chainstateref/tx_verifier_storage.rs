@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::detail::{
     chainstateref::ChainstateRef,
@@ -28,8 +28,7 @@ use chainstate_types::{storage_result, GenBlockIndex, TipStorageTag};
 use common::{
     chain::{
         tokens::{TokenAuxiliaryData, TokenId},
-        AccountNonce, AccountType, ChainConfig, DelegationId, GenBlock, GenBlockId, OrderId,
-        PoolId, Transaction,
+        AccountNonce, AccountType, DelegationId, GenBlock, OrderId, PoolId, Transaction,
     },
     primitives::{Amount, Id},
 };
@@ -62,12 +61,22 @@ impl<S: BlockchainStorageRead, V: TransactionVerificationStrategy> TransactionVe
         self.db_tx.get_token_id(&tx_id).map_err(TransactionVerifierStorageError::from)
     }
 
+    #[log_error]
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, TransactionVerifierStorageError> {
+        self.db_tx
+            .get_token_ids_by_ticker(ticker)
+            .map_err(TransactionVerifierStorageError::from)
+    }
+
     #[log_error]
     fn get_gen_block_index(
         &self,
         block_id: &Id<GenBlock>,
     ) -> Result<Option<GenBlockIndex>, storage_result::Error> {
-        gen_block_index_getter(&self.db_tx, self.chain_config, block_id)
+        self.get_gen_block_index_cached(block_id)
     }
 
     #[log_error]
@@ -164,20 +173,6 @@ impl<S: BlockchainStorageRead, V: TransactionVerificationStrategy> TransactionVe
     }
 }
 
-// TODO: this function is a duplicate of one in chainstate-types; the cause for this is that BlockchainStorageRead causes a circular dependencies
-// BlockchainStorageRead should probably be moved out of storage
-#[log_error]
-pub fn gen_block_index_getter<S: BlockchainStorageRead>(
-    db_tx: &S,
-    chain_config: &ChainConfig,
-    block_id: &Id<GenBlock>,
-) -> Result<Option<GenBlockIndex>, storage_result::Error> {
-    match block_id.classify(chain_config) {
-        GenBlockId::Genesis(_id) => Ok(Some(GenBlockIndex::genesis(chain_config))),
-        GenBlockId::Block(id) => db_tx.get_block_index(&id).map(|b| b.map(GenBlockIndex::Block)),
-    }
-}
-
 impl<S: BlockchainStorageRead, V: TransactionVerificationStrategy> UtxosStorageRead
     for ChainstateRef<'_, S, V>
 {
@@ -254,6 +249,24 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> TransactionV
             .map_err(TransactionVerifierStorageError::from)
     }
 
+    #[log_error]
+    fn set_token_ticker(
+        &mut self,
+        ticker: &[u8],
+        token_ids: &BTreeSet<TokenId>,
+    ) -> Result<(), TransactionVerifierStorageError> {
+        self.db_tx
+            .set_token_ticker(ticker, token_ids)
+            .map_err(TransactionVerifierStorageError::from)
+    }
+
+    #[log_error]
+    fn del_token_ticker(&mut self, ticker: &[u8]) -> Result<(), TransactionVerifierStorageError> {
+        self.db_tx
+            .del_token_ticker(ticker)
+            .map_err(TransactionVerifierStorageError::from)
+    }
+
     #[log_error]
     fn set_utxo_undo_data(
         &mut self,
@@ -333,8 +346,7 @@ impl<S: BlockchainStorageWrite, V: TransactionVerificationStrategy> TransactionV
         match tx_source {
             TransactionSource::Chain(id) => {
                 let block_index = self
-                    .db_tx
-                    .get_block_index(&id)
+                    .get_block_index_cached(&id)
                     .map_err(TransactionVerifierStorageError::from)?
                     .ok_or_else(|| {
                         TransactionVerifierStorageError::GenBlockIndexRetrievalFailed(id.into())
@@ -0,0 +1,115 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common::{
+    chain::{output_value::OutputValue, tokens::TokenId, Destination, GenBlock, TxOutput},
+    primitives::{Amount, BlockHeight, Id},
+};
+
+/// The coins and tokens owned by a destination at some mainchain block.
+///
+/// Only transfer and lock-then-transfer outputs (including the ones that are still locked)
+/// are taken into account; htlcs, stake pools and delegations are not considered to be owned
+/// by a single destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestinationBalance {
+    pub block_id: Id<GenBlock>,
+    pub block_height: BlockHeight,
+    pub coins: Amount,
+    /// Non-zero token balances
+    pub tokens: BTreeMap<TokenId, Amount>,
+}
+
+/// Accumulates the balance of a destination one output at a time.
+#[derive(Debug, Clone)]
+pub struct DestinationBalanceBuilder<'a> {
+    destination: &'a Destination,
+    coins: Amount,
+    tokens: BTreeMap<TokenId, Amount>,
+}
+
+impl<'a> DestinationBalanceBuilder<'a> {
+    pub fn new(destination: &'a Destination) -> Self {
+        Self {
+            destination,
+            coins: Amount::ZERO,
+            tokens: BTreeMap::new(),
+        }
+    }
+
+    /// Add the value of the output if it belongs to the destination.
+    /// Returns `None` on overflow.
+    #[must_use]
+    pub fn add(&mut self, output: &TxOutput) -> Option<()> {
+        self.update(output, |balance, amount| balance + amount)
+    }
+
+    /// Subtract the value of the output if it belongs to the destination.
+    /// Returns `None` on underflow.
+    #[must_use]
+    pub fn subtract(&mut self, output: &TxOutput) -> Option<()> {
+        self.update(output, |balance, amount| balance - amount)
+    }
+
+    fn update(
+        &mut self,
+        output: &TxOutput,
+        op: impl Fn(Amount, Amount) -> Option<Amount>,
+    ) -> Option<()> {
+        let value = match output {
+            TxOutput::Transfer(value, destination)
+            | TxOutput::LockThenTransfer(value, destination, _) => {
+                if destination != self.destination {
+                    return Some(());
+                }
+                value
+            }
+            TxOutput::Burn(_)
+            | TxOutput::CreateStakePool(_, _)
+            | TxOutput::ProduceBlockFromStake(_, _)
+            | TxOutput::CreateDelegationId(_, _)
+            | TxOutput::DelegateStaking(_, _)
+            | TxOutput::IssueFungibleToken(_)
+            | TxOutput::IssueNft(_, _, _)
+            | TxOutput::DataDeposit(_)
+            | TxOutput::Htlc(_, _)
+            | TxOutput::CreateOrder(_) => return Some(()),
+        };
+
+        match value {
+            OutputValue::Coin(amount) => {
+                self.coins = op(self.coins, *amount)?;
+            }
+            OutputValue::TokenV1(token_id, amount) => {
+                let balance = self.tokens.entry(*token_id).or_insert(Amount::ZERO);
+                *balance = op(*balance, *amount)?;
+            }
+            OutputValue::TokenV0(_) => {}
+        }
+
+        Some(())
+    }
+
+    pub fn build(self, block_id: Id<GenBlock>, block_height: BlockHeight) -> DestinationBalance {
+        DestinationBalance {
+            block_id,
+            block_height,
+            coins: self.coins,
+            tokens: self.tokens.into_iter().filter(|(_, amount)| *amount != Amount::ZERO).collect(),
+        }
+    }
+}
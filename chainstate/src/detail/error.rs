@@ -18,7 +18,7 @@ use thiserror::Error;
 
 use super::{
     block_invalidation::BestChainCandidatesError,
-    chainstateref::{EpochSealError, InMemoryReorgError},
+    chainstateref::{EpochSealError, InMemoryReorgError, StartupRecoveryError},
     orphan_blocks::OrphanAddError,
     transaction_verifier::{
         error::ConnectTransactionError, storage::TransactionVerifierStorageError,
@@ -36,6 +36,8 @@ use common::{
 };
 use consensus::ConsensusVerificationError;
 
+use crate::ChainstateConfigError;
+
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
 pub enum BlockError {
     #[error("Block storage error: `{0}`")]
@@ -113,6 +115,15 @@ pub enum BlockError {
 
     #[error("Unexpected block height range: first = {0}, second = {1}")]
     UnexpectedHeightRange(BlockHeight, BlockHeight),
+
+    #[error("Cannot disconnect block {block_id} at height {block_height}, because its undo data has been pruned; the lowest height with undo data is {min_height_with_undo_data}")]
+    UndoDataPruned {
+        block_id: Id<Block>,
+        block_height: BlockHeight,
+        min_height_with_undo_data: BlockHeight,
+    },
+    #[error("Cannot build the compact filter of block {0}, because its undo data is missing")]
+    UndoDataMissingForBlockFilter(Id<Block>),
 }
 
 // Note: this enum isn't supposed to represent a complete error; this is why its elements
@@ -235,6 +246,14 @@ pub enum InitializationError {
     StorageCompatibilityCheckError(#[from] StorageCompatibilityCheckError),
     #[error("Error initializing best chain candidates: {0}")]
     BestChainCandidatesError(#[from] BestChainCandidatesError),
+    #[error("Failed to recover inconsistent chainstate: {0}")]
+    StartupRecoveryError(#[from] StartupRecoveryError),
+    #[error("Failed to build compact block filters: {0}")]
+    BlockFilterIndexError(#[from] BlockError),
+    #[error("Failed to build the token ticker index: {0}")]
+    TokenTickerIndexError(BlockError),
+    #[error("Invalid chainstate config: {0}")]
+    InvalidConfig(#[from] ChainstateConfigError),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
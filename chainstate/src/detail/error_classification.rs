@@ -101,6 +101,8 @@ impl BlockProcessingErrorClassification for BlockError {
             | BlockError::InvariantErrorPoolBalancePresentDataMissing(_, _)
             | BlockError::InvariantErrorPoolDataPresentBalanceMissing(_, _)
             | BlockError::UnexpectedHeightRange(_, _)
+            | BlockError::UndoDataPruned { .. }
+            | BlockError::UndoDataMissingForBlockFilter(_)
             | BlockError::DbCommitError(_, _, _)
             | BlockError::BlockAlreadyExists(_)
             | BlockError::BlockIndexAlreadyExists(_)
@@ -449,6 +451,14 @@ impl BlockProcessingErrorClassification for PropertyQueryError {
             | PropertyQueryError::GenesisHeaderRequested
             | PropertyQueryError::InvalidStartingBlockHeightForMainchainBlocks(_)
             | PropertyQueryError::InvalidBlockHeightRange { .. }
+            | PropertyQueryError::UndoDataNotFound(_)
+            | PropertyQueryError::UndoDataPruned { .. }
+            | PropertyQueryError::UtxoSetCoinAmountOverflow
+            | PropertyQueryError::HeightAboveBestBlock { .. }
+            | PropertyQueryError::HeightTooDeepBelowBestBlock { .. }
+            | PropertyQueryError::DestinationBalanceOverflow
+            | PropertyQueryError::DestinationBalanceUnderflow(_)
+            | PropertyQueryError::PoolBlockRewardOverflow(_)
             | PropertyQueryError::UnsupportedTokenV0InOrder(_) => {
                 BlockProcessingErrorClass::General
             }
@@ -493,6 +503,7 @@ impl BlockProcessingErrorClassification for TokensError {
             | TokensError::CoinOrTokenOverflow(_)
             | TokensError::InsufficientTokenFees(_)
             | TokensError::TokenMetadataUriTooLarge(_)
+            | TokensError::TokenTickerAlreadyTaken(_, _, _)
             | TokensError::InvariantBrokenUndoIssuanceOnNonexistentToken(_)
             | TokensError::InvariantBrokenRegisterIssuanceWithDuplicateId(_) => {
                 BlockProcessingErrorClass::BadBlock
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 
 use common::{
     chain::{block::timestamp::BlockTimestamp, GenBlock},
-    primitives::{BlockHeight, Id},
+    primitives::{per_thousand::PerThousand, BlockHeight, Id, H256},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
@@ -28,3 +28,46 @@ pub struct ChainInfo {
     pub median_time: BlockTimestamp,
     pub is_initial_block_download: bool,
 }
+
+/// Progress of the initial block download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct IbdStatus {
+    pub is_initial_block_download: bool,
+    pub best_block_height: BlockHeight,
+    pub best_block_timestamp: BlockTimestamp,
+    /// Estimated height of the best chain in the network, based on the age of the tip and
+    /// the target block spacing; this is not the height of any received block header.
+    /// Equals the best block height once the initial block download is finished.
+    pub estimated_network_height: BlockHeight,
+    /// Estimated fraction of the chain that has been verified, based on the tip timestamp
+    /// relative to the genesis timestamp and the current time.
+    pub verification_progress: PerThousand,
+}
+
+/// Progress of the chain synchronization, based on the chain trust of the best block
+/// relative to the estimated chain trust of the best chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct SyncProgress {
+    pub is_initial_block_download: bool,
+    pub best_block_height: BlockHeight,
+    /// Estimated height of the best chain in the network, same as in `IbdStatus`.
+    pub estimated_network_height: BlockHeight,
+    /// The chain trust of the best block, i.e. the amount of validated work.
+    pub validated_chain_trust: H256,
+    /// Estimated chain trust of the best chain; the trust of the missing blocks is extrapolated
+    /// from the average trust of the recent mainchain blocks.
+    pub estimated_network_chain_trust: H256,
+    /// The fraction of the best chain trust that has been validated.
+    pub verification_progress: PerThousand,
+    /// Estimated time (in seconds) needed to process the missing blocks, based on the recent
+    /// block processing rate; `None` if it can't be estimated yet.
+    pub estimated_seconds_remaining: Option<u64>,
+}
+
+/// The mainchain tip that the utxo queries of the chainstate interface are answered against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestBlockInfo {
+    pub id: Id<GenBlock>,
+    pub height: BlockHeight,
+    pub timestamp: BlockTimestamp,
+}
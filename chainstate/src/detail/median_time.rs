@@ -13,6 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Mutex;
+
 use chainstate_types::BlockIndexHandle;
 use itertools::Itertools;
 
@@ -20,11 +22,15 @@ use common::{
     chain::{block::timestamp::BlockTimestamp, GenBlock},
     primitives::Id,
 };
+use utils::bounded_fifo_map::BoundedFifoMap;
 
 use chainstate_types::BlockIndexHistoryIterator;
 
 pub const MEDIAN_TIME_SPAN: usize = 11;
 
+/// The maximum number of blocks for which the median time past is cached.
+const MEDIAN_TIME_PAST_CACHE_SIZE: usize = 1000;
+
 #[must_use]
 pub fn calculate_median_time_past<H: BlockIndexHandle>(
     block_index_handle: &H,
@@ -43,6 +49,52 @@ pub fn calculate_median_time_past_from_blocktimestamps<I: Iterator<Item = BlockT
     time_values[time_values.len() / 2]
 }
 
+/// Median time past values, keyed by the id of the block the calculation starts from.
+///
+/// The median time past of a block only depends on its ancestors, which are determined by
+/// the block id itself, so a cached value can't become wrong. Still, the entries of the blocks
+/// that are disconnected during a reorg are evicted, so that the cache is mostly populated by
+/// the blocks of the current mainchain, which are the ones that are queried the most.
+pub struct MedianTimePastCache {
+    values: Mutex<BoundedFifoMap<Id<GenBlock>, BlockTimestamp>>,
+}
+
+impl Default for MedianTimePastCache {
+    fn default() -> Self {
+        Self {
+            values: Mutex::new(BoundedFifoMap::new(MEDIAN_TIME_PAST_CACHE_SIZE)),
+        }
+    }
+}
+
+impl MedianTimePastCache {
+    /// Return the cached median time past for the block, calculating it if it's not cached yet.
+    pub fn get_or_calculate<H: BlockIndexHandle>(
+        &self,
+        block_index_handle: &H,
+        starting_block: &Id<GenBlock>,
+    ) -> BlockTimestamp {
+        if let Some(value) = self.lock().get(starting_block) {
+            return *value;
+        }
+
+        let value = calculate_median_time_past(block_index_handle, starting_block);
+
+        self.lock().insert(*starting_block, value);
+
+        value
+    }
+
+    /// Evict the cached value for the block, e.g. because the block has been disconnected.
+    pub fn invalidate(&self, block_id: &Id<GenBlock>) {
+        self.lock().remove(block_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BoundedFifoMap<Id<GenBlock>, BlockTimestamp>> {
+        self.values.lock().expect("poisoned mutex")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
@@ -263,4 +315,47 @@ mod test {
             }
         });
     }
+
+    #[test]
+    fn median_time_past_cache() {
+        utils::concurrency::model(|| {
+            let chain_config = Arc::new(create_unit_test_config());
+            let storage = Store::new_empty().unwrap();
+            let mut chainstate = Chainstate::new(
+                chain_config.clone(),
+                ChainstateConfig::new(),
+                storage,
+                DefaultTransactionVerificationStrategy::new(),
+                None,
+                Default::default(),
+            )
+            .unwrap();
+
+            let block_count = 50;
+            let blocks = chain_blocks(
+                block_count,
+                chainstate.chain_config.genesis_block_id(),
+                chain_config.genesis_block().timestamp().as_int_seconds(),
+            );
+            for block in &blocks {
+                chainstate.process_block(block.clone().into(), BlockSource::Local).unwrap();
+            }
+
+            let chainstate_ref = chainstate.make_db_tx_ro().unwrap();
+            let cache = MedianTimePastCache::default();
+            for block in &blocks {
+                let block_id = block.get_id().into();
+                let expected = calculate_median_time_past(&chainstate_ref, &block_id);
+                assert_eq!(chainstate_ref.median_time_past(&block_id), expected);
+                assert_eq!(cache.get_or_calculate(&chainstate_ref, &block_id), expected);
+                assert_eq!(cache.lock().get(&block_id), Some(&expected));
+            }
+            assert_eq!(cache.lock().len(), block_count);
+
+            let last_block_id = blocks.last().unwrap().get_id().into();
+            cache.invalidate(&last_block_id);
+            assert_eq!(cache.lock().get(&last_block_id), None);
+            assert_eq!(cache.lock().len(), block_count - 1);
+        });
+    }
 }
@@ -13,12 +13,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod block_fee_check;
+mod block_filter;
+mod block_index_cache;
+mod block_rejections;
+mod cache_memory;
+mod chain_split;
+mod chain_tips;
 mod chainstateref;
+mod destination_balance;
 mod error;
 mod error_classification;
 mod info;
 mod median_time;
 mod orphan_blocks;
+mod sync_progress;
+mod utxo_diff;
+mod utxo_statistics;
 
 pub mod ban_score;
 pub mod block_checking;
@@ -27,16 +38,29 @@ pub mod bootstrap;
 pub mod query;
 pub mod tx_verification_strategy;
 
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use itertools::Itertools;
 use thiserror::Error;
 use utils_networking::broadcaster;
 
 use self::{
+    ban_score::BanScore,
+    block_fee_check::{BlockFeeChecks, ConnectedBlockFees},
+    block_filter::FilteredSubscriber,
+    block_index_cache::BlockIndexCache,
     block_invalidation::BlockInvalidator,
+    block_rejections::{BlockRejectionLogAction, RecentBlockRejections},
+    cache_memory::CacheMemoryBudget,
+    chain_split::RecentBlockObservations,
+    median_time::MedianTimePastCache,
     orphan_blocks::{OrphanBlocksMut, OrphansProxy},
     query::ChainstateQuery,
+    sync_progress::{BlockProcessingRate, CHAIN_TRUST_AVERAGING_WINDOW},
     tx_verification_strategy::TransactionVerificationStrategy,
 };
 use crate::{BlockInvalidatorError, ChainstateConfig, ChainstateEvent};
@@ -44,17 +68,19 @@ use chainstate_storage::{
     BlockchainStorage, BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
 };
 use chainstate_types::{
-    pos_randomness::PoSRandomness, BlockIndex, BlockStatus, BlockValidationStage, EpochData,
-    EpochStorageWrite, PropertyQueryError, SealedStorageTag, TipStorageTag,
+    pos_randomness::PoSRandomness, BlockIndex, BlockStatus, BlockValidationStage, ChainSplitRecord,
+    EpochData, EpochStorageWrite, GenBlockIndex, PropertyQueryError, SealedStorageTag,
+    TipStorageTag,
 };
 use chainstateref::{ChainstateRef, ReorgError};
 use common::{
     chain::{block::timestamp::BlockTimestamp, config::ChainConfig, Block, GenBlock, TxOutput},
-    primitives::{id::WithId, BlockHeight, Compact, Id, Idable},
+    primitives::{id::WithId, per_thousand::PerThousand, BlockHeight, Compact, Fee, Id, Idable},
     time_getter::TimeGetter,
     Uint256,
 };
 use logging::log;
+use p2p_types::PeerId;
 use pos_accounting::{
     FlushablePoSAccountingView, PoSAccountingDB, PoSAccountingDelta, PoSAccountingOperations,
     PoSAccountingUndo,
@@ -71,11 +97,22 @@ use utils::{
 use utxo::UtxosDB;
 
 pub use self::{
-    error::*, info::ChainInfo, median_time::calculate_median_time_past,
-    median_time::calculate_median_time_past_from_blocktimestamps, median_time::MEDIAN_TIME_SPAN,
+    error::*,
+    info::{BestBlockInfo, ChainInfo, IbdStatus, SyncProgress},
+    median_time::calculate_median_time_past,
+    median_time::calculate_median_time_past_from_blocktimestamps,
+    median_time::MEDIAN_TIME_SPAN,
 };
+pub use block_fee_check::{BlockFeeCheckStats, BlockFeeDiscrepancy};
+pub use block_filter::{BlockFilter, BlockFilterItem, FilteredBlock, FilteredChainEvent};
+pub use chain_split::{CHAIN_SPLIT_RECORD_MAX_DEPTH, MAX_CHAIN_SPLIT_RECORDS};
+pub use chain_tips::{ChainTip, ChainTipStatus};
 pub use chainstate_types::Locator;
-pub use chainstateref::NonZeroPoolBalances;
+pub use chainstateref::{
+    DbVerificationError, DbVerificationLevel, NonZeroPoolBalances, StartupRecoveryError,
+    STARTUP_RECOVERY_MAX_DEPTH,
+};
+pub use destination_balance::DestinationBalance;
 pub use error::{
     BlockError, CheckBlockError, CheckBlockTransactionsError, DbCommittingContext,
     InitializationError, OrphanCheckError, StorageCompatibilityCheckError,
@@ -87,6 +124,8 @@ pub use transaction_verifier::{
     storage::TransactionVerifierStorageError,
     IOPolicyError,
 };
+pub use utxo_diff::{BlockUtxoDiff, UtxoDiffChunk, UtxoDiffEntry};
+pub use utxo_statistics::{UtxoSetStatistics, UtxoSetStatisticsBuilder};
 
 type TxRw<'a, S> = <S as Transactional<'a>>::TransactionRw;
 type TxRo<'a, S> = <S as Transactional<'a>>::TransactionRo;
@@ -94,6 +133,13 @@ type ChainstateEventHandler = EventHandler<ChainstateEvent>;
 
 pub type OrphanErrorHandler = dyn Fn(&BlockError) + Send + Sync;
 
+/// The number of missing compact block filters built at startup per db transaction.
+const BLOCK_FILTER_BUILD_BATCH_SIZE: usize = 1000;
+
+/// A generous estimate of the storage space taken by the compact filter of a block together with
+/// its commitment, used to make sure the storage can fit a batch of filters before building it.
+const BLOCK_FILTER_SPACE_ESTIMATE: usize = 4 * 1024;
+
 /// A tracing target that either forces full block ids to be printed where they're normally
 /// printed in the abbreviated form, or just makes block ids be printed where normally they won't
 /// be.
@@ -109,13 +155,25 @@ pub struct Chainstate<S, V> {
     custom_orphan_error_hook: Option<Arc<OrphanErrorHandler>>,
     subsystem_events: EventsController<ChainstateEvent>,
     rpc_events: broadcaster::Broadcaster<ChainstateEvent>,
+    filtered_subscribers: Vec<FilteredSubscriber>,
     time_getter: TimeGetter,
     is_initial_block_download_finished: SetFlag,
+    recent_block_observations: RecentBlockObservations,
+    recent_block_rejections: RecentBlockRejections,
+    block_fee_checks: BlockFeeChecks,
+    block_processing_rate: BlockProcessingRate,
+    median_time_past_cache: MedianTimePastCache,
+    cache_memory_budget: Arc<CacheMemoryBudget>,
+    block_index_cache: BlockIndexCache,
+    // The current tip; reset whenever the storage is about to be modified and re-populated on
+    // the next query or new tip event.
+    best_block_info_cache: Mutex<Option<BestBlockInfo>>,
 }
 
 #[derive(Copy, Clone, Eq, Debug, PartialEq)]
 pub enum BlockSource {
-    Peer,
+    /// The block was received from the specified peer.
+    Peer(PeerId),
     Local,
 }
 
@@ -125,20 +183,33 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.subsystem_events.wait_for_all_events();
     }
 
-    #[log_error]
     fn make_db_tx<'a>(
         &'a mut self,
+    ) -> chainstate_storage::Result<ChainstateRef<'a, TxRw<'a, S>, V>> {
+        self.make_db_tx_with_size_estimate(None)
+    }
+
+    /// Start a db transaction that is expected to write about `size_estimate` bytes; the storage
+    /// makes sure in advance that there is enough space for them.
+    #[log_error]
+    fn make_db_tx_with_size_estimate<'a>(
+        &'a mut self,
+        size_estimate: Option<usize>,
     ) -> chainstate_storage::Result<ChainstateRef<'a, TxRw<'a, S>, V>> {
         // Note: this is a workaround for log_error's compilation issues, see log_error docs
         // for details.
         let this = self;
-        let db_tx = this.chainstate_storage.transaction_rw(None)?;
+        this.invalidate_best_block_info_cache();
+        let db_tx = this.chainstate_storage.transaction_rw(size_estimate)?;
         Ok(chainstateref::ChainstateRef::new_rw(
             &this.chain_config,
             &this.chainstate_config,
             &this.tx_verification_strategy,
             db_tx,
             &this.time_getter,
+            &this.median_time_past_cache,
+            &this.cache_memory_budget,
+            &this.block_index_cache,
         ))
     }
 
@@ -153,6 +224,9 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             &self.tx_verification_strategy,
             db_tx,
             &self.time_getter,
+            &self.median_time_past_cache,
+            &self.cache_memory_budget,
+            &self.block_index_cache,
         ))
     }
 
@@ -169,6 +243,24 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.rpc_events.subscribe()
     }
 
+    pub fn subscribe_to_bounded_event_broadcast(
+        &mut self,
+        capacity: usize,
+        overflow_policy: broadcaster::OverflowPolicy,
+    ) -> broadcaster::BoundedReceiver<ChainstateEvent> {
+        self.rpc_events.subscribe_bounded(capacity, overflow_policy)
+    }
+
+    /// Subscribe to the main chain blocks that are relevant to the filter.
+    pub fn subscribe_to_filtered_events(
+        &mut self,
+        filter: BlockFilter,
+    ) -> broadcaster::Receiver<FilteredChainEvent> {
+        let (subscriber, receiver) = FilteredSubscriber::new(filter);
+        self.filtered_subscribers.push(subscriber);
+        receiver
+    }
+
     #[log_error]
     pub fn new(
         chain_config: Arc<ChainConfig>,
@@ -180,6 +272,10 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
     ) -> Result<Self, crate::ChainstateError> {
         use crate::ChainstateError;
 
+        chainstate_config
+            .validate(&chain_config)
+            .map_err(|e| ChainstateError::FailedToInitializeChainstate(e.into()))?;
+
         let best_block_id = {
             let db_tx = chainstate_storage
                 .transaction_ro()
@@ -202,14 +298,21 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             chainstate.process_genesis().map_err(ChainstateError::ProcessBlockError)?;
         } else {
             chainstate.check_genesis().map_err(crate::ChainstateError::from)?;
+            chainstate.recover_inconsistent_state().map_err(crate::ChainstateError::from)?;
+            let _new_tip = chainstate.activate_pending_blocks();
         }
 
+        chainstate.build_missing_token_tickers().map_err(crate::ChainstateError::from)?;
+        chainstate.build_missing_block_filters().map_err(crate::ChainstateError::from)?;
+
         chainstate.update_initial_block_download_flag()?;
 
         chainstate
             .check_consistency()
             .map_err(|e| ChainstateError::FailedToInitializeChainstate(e.into()))?;
 
+        chainstate.load_block_index_cache().map_err(crate::ChainstateError::from)?;
+
         Ok(chainstate)
     }
 
@@ -221,7 +324,21 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         custom_orphan_error_hook: Option<Arc<OrphanErrorHandler>>,
         time_getter: TimeGetter,
     ) -> Self {
-        let orphan_blocks = OrphansProxy::new(*chainstate_config.max_orphan_blocks);
+        let cache_memory_limits = chainstate_config.cache_memory_limits();
+        log::debug!(
+            "Chainstate cache memory limits: {cache_memory_limits:?}, global limit: {:?}",
+            chainstate_config.max_cache_memory
+        );
+        let cache_memory_budget = Arc::new(CacheMemoryBudget::new(
+            cache_memory_limits,
+            chainstate_config.max_cache_memory,
+        ));
+
+        let orphan_blocks = OrphansProxy::new(
+            *chainstate_config.max_orphan_blocks,
+            Arc::clone(&cache_memory_budget),
+        );
+        let block_index_cache = BlockIndexCache::new(Arc::clone(&cache_memory_budget));
         let subsystem_events = EventsController::new();
         let rpc_events = broadcaster::Broadcaster::new();
         Self {
@@ -233,8 +350,40 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             custom_orphan_error_hook,
             subsystem_events,
             rpc_events,
+            filtered_subscribers: Vec::new(),
             time_getter,
             is_initial_block_download_finished: SetFlag::new(),
+            recent_block_observations: RecentBlockObservations::default(),
+            recent_block_rejections: RecentBlockRejections::default(),
+            block_fee_checks: BlockFeeChecks::default(),
+            block_processing_rate: BlockProcessingRate::default(),
+            median_time_past_cache: MedianTimePastCache::default(),
+            cache_memory_budget,
+            block_index_cache,
+            best_block_info_cache: Mutex::new(None),
+        }
+    }
+
+    fn invalidate_best_block_info_cache(&mut self) {
+        *self.best_block_info_cache.get_mut().expect("poisoned mutex") = None;
+    }
+
+    /// Returns the current tip. Unlike the other queries, this normally doesn't touch the storage.
+    #[log_error]
+    pub fn get_best_block_info(&self) -> Result<BestBlockInfo, PropertyQueryError> {
+        let mut cache = self.best_block_info_cache.lock().expect("poisoned mutex");
+        match *cache {
+            Some(info) => Ok(info),
+            None => {
+                let best_block_index = self.query()?.get_best_block_index()?;
+                let info = BestBlockInfo {
+                    id: best_block_index.block_id(),
+                    height: best_block_index.block_height(),
+                    timestamp: best_block_index.block_timestamp(),
+                };
+                *cache = Some(info);
+                Ok(info)
+            }
         }
     }
 
@@ -251,10 +400,9 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         // Look up the parent of block 1 to figure out the genesis ID according to storage
         let block1_id = dbtx
             .get_block_id_by_height(&BlockHeight::new(1))?
+            .and_then(|id| id.to_block_id(&self.chain_config))
             .ok_or(InitializationError::Block1Missing)?;
-        let block1 = dbtx
-            .get_block(Id::new(block1_id.to_hash()))?
-            .ok_or(InitializationError::Block1Missing)?;
+        let block1 = dbtx.get_block(block1_id)?.ok_or(InitializationError::Block1Missing)?;
         let stored_genesis_id = block1.prev_block_id();
 
         // Check storage genesis ID matches chain config genesis ID
@@ -266,15 +414,127 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(())
     }
 
+    /// Make sure the utxo set and the sealed accounting state correspond to the best block,
+    /// rolling back the ones that are ahead of it.
+    #[log_error]
+    fn recover_inconsistent_state(&mut self) -> Result<(), InitializationError> {
+        let mut chainstate_ref = self.make_db_tx()?;
+        if chainstate_ref.recover_inconsistent_state()? {
+            chainstate_ref.commit_db_tx()?;
+            log::info!("Inconsistent chainstate has been recovered");
+        }
+
+        Ok(())
+    }
+
+    /// If the filter index is enabled, build the compact filters of the main chain blocks
+    /// that don't have them yet.
+    fn build_missing_block_filters(&mut self) -> Result<(), InitializationError> {
+        if !self.chainstate_config.block_filter_index_enabled() {
+            return Ok(());
+        }
+
+        let block_ids = self.make_db_tx_ro()?.get_mainchain_blocks_without_filter()?;
+        if block_ids.is_empty() {
+            return Ok(());
+        }
+
+        log::info!("Building compact filters for {} blocks", block_ids.len());
+        for batch in block_ids.chunks(BLOCK_FILTER_BUILD_BATCH_SIZE) {
+            let size_estimate = batch.len() * BLOCK_FILTER_SPACE_ESTIMATE;
+            let mut chainstate_ref = self.make_db_tx_with_size_estimate(Some(size_estimate))?;
+            for block_id in batch {
+                let block = chainstate_ref
+                    .get_block(*block_id)?
+                    .ok_or(BlockError::BlockDataMissingForValidBlockIndex(*block_id))?;
+                chainstate_ref.build_block_filter(&block.into())?;
+            }
+            chainstate_ref.commit_db_tx()?;
+        }
+        log::info!("Compact block filters have been built");
+
+        Ok(())
+    }
+
+    /// Build the token ticker index if it doesn't cover all the issued tokens yet.
+    fn build_missing_token_tickers(&mut self) -> Result<(), InitializationError> {
+        if self.make_db_tx_ro()?.get_token_tickers_indexed()? {
+            return Ok(());
+        }
+
+        log::info!("Building the token ticker index");
+        let mut chainstate_ref = self.make_db_tx()?;
+        chainstate_ref
+            .build_token_ticker_index()
+            .map_err(InitializationError::TokenTickerIndexError)?;
+        chainstate_ref.commit_db_tx()?;
+        log::info!("The token ticker index has been built");
+
+        Ok(())
+    }
+
+    fn load_block_index_cache(&self) -> Result<(), InitializationError> {
+        let chainstate_ref = self.make_db_tx_ro()?;
+        chainstate_ref.load_block_index_cache()?;
+        Ok(())
+    }
+
     fn broadcast_new_tip_event(&mut self, new_block_index: &BlockIndex) {
         let new_height = new_block_index.block_height();
         let new_id = *new_block_index.block_id();
         let event = ChainstateEvent::NewTip(new_id, new_height);
 
+        *self.best_block_info_cache.get_mut().expect("poisoned mutex") = Some(BestBlockInfo {
+            id: new_id.into(),
+            height: new_height,
+            timestamp: new_block_index.block_timestamp(),
+        });
+
         self.rpc_events.broadcast(&event);
         self.subsystem_events.broadcast(event);
     }
 
+    /// Send the blocks connected to the main chain after `old_tip` to the filtered
+    /// subscribers they are relevant to.
+    fn broadcast_filtered_events(
+        &mut self,
+        old_tip: &BestBlockInfo,
+        new_tip_index: &BlockIndex,
+    ) -> Result<(), PropertyQueryError> {
+        self.filtered_subscribers.retain(|subscriber| !subscriber.is_closed());
+        if self.filtered_subscribers.is_empty() {
+            return Ok(());
+        }
+
+        let (fork_height, blocks) = {
+            let query = self.query()?;
+            let fork_height = query.get_fork_height_in_main_chain(&old_tip.id)?;
+            let block_count =
+                new_tip_index.block_height().into_int().saturating_sub(fork_height.into_int());
+            let blocks = query.get_mainchain_blocks_with_undo_data(
+                fork_height.next_height(),
+                block_count.try_into().expect("Block count must fit into usize"),
+            )?;
+            (fork_height, blocks)
+        };
+
+        for subscriber in &mut self.filtered_subscribers {
+            if fork_height < old_tip.height {
+                subscriber.send(&FilteredChainEvent::Reorg(fork_height));
+            }
+
+            for (height, block, undo) in &blocks {
+                if let Some(filtered_block) =
+                    subscriber.filter().filter_block(block, *height, undo.as_ref())
+                {
+                    subscriber.send(&FilteredChainEvent::NewBlock(filtered_block));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Create a read-write transaction, call `main_action` on it and commit.
     ///
     /// If a storage failure occurs during execution or committing fails, repeat the whole process
@@ -323,7 +583,9 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                 }
             };
 
+            let commit_start = Instant::now();
             let db_commit_result = chainstate_ref.commit_db_tx();
+            metrics::node::DB_COMMIT_DURATION.observe(commit_start.elapsed());
 
             match db_commit_result {
                 Ok(()) => return Ok(result),
@@ -336,14 +598,14 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         }
     }
 
-    /// Integrate the block into the blocktree, performing all the necessary checks.
-    /// The returned bool indicates whether a reorg has occurred.
+    /// Check the block and persist it together with its index, adding it to the queue of
+    /// the blocks whose activation hasn't finished yet.
     #[log_error]
-    fn integrate_block(
+    fn accept_block(
         chainstate_ref: &mut ChainstateRef<TxRw<'_, S>, V>,
         block: &WithId<Block>,
         block_index: BlockIndex,
-    ) -> Result<bool, BlockIntegrationError> {
+    ) -> Result<(), BlockIntegrationError> {
         let mut block_status = BlockStatus::new();
 
         chainstate_ref
@@ -354,14 +616,34 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         block_status.advance_validation_stage_to(BlockValidationStage::CheckBlockOk);
         let block_status = block_status;
 
-        // Note: we mark the block as persisted here - if integrate_block eventually
-        // succeeds, we'll save both the index and the block itself via the same db tx;
-        // and if it fails, neither will be saved.
+        // Note: the block is marked as persisted here - if accept_block eventually succeeds,
+        // both the index and the block itself are saved via the same db tx; and if it fails,
+        // neither will be saved.
         let block_index = block_index.with_status(block_status).make_persisted();
         chainstate_ref
             .set_new_block_index(&block_index)
             .and_then(|_| chainstate_ref.persist_block(block))
-            .map_err(|err| BlockIntegrationError::BlockCheckError(err, block_status))?;
+            .and_then(|_| chainstate_ref.add_pending_block_activation(block.get_id()))
+            .map_err(|err| BlockIntegrationError::BlockCheckError(err, block_status))
+    }
+
+    /// Try connecting an accepted block to the best chain and remove it from the pending
+    /// activation queue. The returned bool indicates whether a reorg has occurred; the returned
+    /// fees are those of the blocks that have been connected.
+    #[log_error]
+    fn activate_block(
+        chainstate_ref: &mut ChainstateRef<TxRw<'_, S>, V>,
+        block_id: &Id<Block>,
+    ) -> Result<(bool, Vec<ConnectedBlockFees>), BlockIntegrationError> {
+        chainstate_ref.remove_pending_block_activation(block_id)?;
+
+        // The block may have been invalidated since it was accepted, e.g. together with
+        // its parent.
+        let block_index = match get_block_index(chainstate_ref, block_id)? {
+            Some(block_index) if block_index.status().is_ok() => block_index,
+            Some(_) | None => return Ok((false, Vec::new())),
+        };
+        let block_status = block_index.status();
 
         // Note: we don't advance the stage to FullyChecked if activate_best_chain succeeds even
         // if we know that a reorg has occurred, because during a reorg multiple blocks get
@@ -374,23 +656,25 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             .update_min_height_with_allowed_reorg()
             .map_err(BlockIntegrationError::OtherNonValidationError)?;
 
-        result.map_err(|err| match err {
-            ReorgError::ConnectTipFailed(block_id, block_err) => {
-                BlockIntegrationError::ConnectBlockErrorDuringReorg(
-                    block_err,
-                    block_status,
-                    block_id,
-                )
-            }
-            ReorgError::OtherError(block_err) => {
-                BlockIntegrationError::OtherReorgError(block_err, block_status)
-            }
-        })
+        result
+            .map(|reorg_occurred| (reorg_occurred, chainstate_ref.take_connected_block_fees()))
+            .map_err(|err| match err {
+                ReorgError::ConnectTipFailed(block_id, block_err) => {
+                    BlockIntegrationError::ConnectBlockErrorDuringReorg(
+                        block_err,
+                        block_status,
+                        block_id,
+                    )
+                }
+                ReorgError::OtherError(block_err) => {
+                    BlockIntegrationError::OtherReorgError(block_err, block_status)
+                }
+            })
     }
 
     /// Attempt to process the block. On success, return Some(block_index_of_the_passed_block)
     /// if a reorg has occurred and the passed block is now the best block, otherwise return None.
-    #[log_error]
+    #[log_error(level = "debug")]
     fn attempt_to_process_block(
         &mut self,
         block: WithId<Block>,
@@ -399,66 +683,198 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         let block = self.check_legitimate_orphan(block_source, block)?;
         let block_id = block.get_id();
 
+        self.recent_block_observations.observe(
+            block_id,
+            BlockTimestamp::from_time(self.time_getter.get_time()),
+        );
+
         // Ensure that the block being submitted is new to us. If not, bail out immediately,
-        // otherwise create a new block index and continue.
+        // unless the activation of the block has failed earlier; otherwise create a new block
+        // index and continue.
         let block_index = {
             let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
             let existing_block_index = get_block_index(&chainstate_ref, &block_id)?;
 
-            if let Some(block_index) = existing_block_index {
-                return if block_index.status().is_ok() {
-                    Err(BlockError::BlockAlreadyProcessed(block_id))
-                } else {
-                    Err(BlockError::InvalidBlockAlreadyProcessed(block_id))
-                };
+            match existing_block_index {
+                Some(block_index) => {
+                    if !block_index.status().is_ok() {
+                        return Err(BlockError::InvalidBlockAlreadyProcessed(block_id));
+                    }
+
+                    let is_pending_activation = chainstate_ref
+                        .get_pending_block_activations()
+                        .map_err(BlockError::PropertyQueryError)?
+                        .contains(&block_id);
+                    if !is_pending_activation {
+                        return Err(BlockError::BlockAlreadyProcessed(block_id));
+                    }
+
+                    None
+                }
+                None => Some(
+                    chainstate_ref.create_block_index_for_new_block(&block, BlockStatus::new())?,
+                ),
             }
+        };
 
-            chainstate_ref.create_block_index_for_new_block(&block, BlockStatus::new())?
+        let Some(block_index) = block_index else {
+            log::info!("Retrying the activation of resubmitted block {block_id}");
+            return self.activate_accepted_block(&block_id);
         };
 
-        // Perform block checks; `integrate_block_result` is `Result<bool>`, where the bool
-        // indicates whether a reorg has occurred.
-        let integrate_block_result = self.with_rw_tx(
-            |chainstate_ref| Self::integrate_block(chainstate_ref, &block, block_index.clone()),
+        // The block is accepted and activated in separate db transactions, so that it doesn't
+        // have to be downloaded again if the activation fails for a reason unrelated to
+        // the block itself; such activations are retried when the block is submitted again,
+        // after the next block is processed successfully or on the next start.
+        let accept_block_result = self.with_rw_tx(
+            |chainstate_ref| Self::accept_block(chainstate_ref, &block, block_index.clone()),
             |attempt_number| {
-                log::info!("Processing block {block_id}, attempt #{attempt_number}");
+                log::info!("Accepting block {block_id}, attempt #{attempt_number}");
             },
             |attempts_count, db_err| {
                 BlockIntegrationError::BlockCommitError(block_id, attempts_count, db_err)
             },
         );
 
-        match integrate_block_result {
-            Ok(reorg_occurred) => {
-                // If the above code has succeeded, then the block_index must be present in the DB.
-                // Note that we can't return the initially obtained block_index, because its
-                // block status is outdated.
-                let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
-                let saved_block_index = get_existing_block_index(&chainstate_ref, &block_id)?;
+        if let Err(err) = accept_block_result {
+            return Err(self.handle_block_integration_error(&block_index, err));
+        }
 
-                assert!(saved_block_index.status().is_ok());
-                return Ok(reorg_occurred.then_some(saved_block_index));
+        self.activate_accepted_block(&block_id)
+    }
+
+    /// Activate a block that has already been accepted. On success, return
+    /// Some(block_index_of_the_block) if a reorg has occurred and the block is now the best block,
+    /// otherwise return None.
+    #[log_error(level = "debug")]
+    fn activate_accepted_block(
+        &mut self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockIndex>, BlockError> {
+        let old_best_block_index = self
+            .make_db_tx_ro()
+            .map_err(BlockError::from)?
+            .get_best_block_index()
+            .map_err(BlockError::BestBlockIndexQueryError)?;
+
+        // `activate_block_result` is `Result<(bool, Vec<_>)>`, where the bool indicates
+        // whether a reorg has occurred.
+        let activate_block_result = self.with_rw_tx(
+            |chainstate_ref| Self::activate_block(chainstate_ref, block_id),
+            |attempt_number| {
+                log::info!("Activating block {block_id}, attempt #{attempt_number}");
+            },
+            |attempts_count, db_err| {
+                BlockIntegrationError::BlockCommitError(*block_id, attempts_count, db_err)
+            },
+        );
+
+        let (reorg_occurred, connected_block_fees) = match activate_block_result {
+            Ok(result) => result,
+            Err(err) => {
+                let block_index = {
+                    let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
+                    get_existing_block_index(&chainstate_ref, block_id)?
+                };
+                return Err(self.handle_block_integration_error(&block_index, err));
+            }
+        };
+
+        // Note that the block index must be obtained after the activation, because its
+        // block status may have been updated by it.
+        let saved_block_index = {
+            let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
+            match get_block_index(&chainstate_ref, block_id)? {
+                Some(block_index) if block_index.status().is_ok() => block_index,
+                // The block was invalidated or removed before its activation was retried.
+                Some(_) | None => return Ok(None),
             }
-            Err(BlockIntegrationError::BlockCommitError(block_id, attempts_count, db_err)) => {
-                return Err(BlockError::DbCommitError(
+        };
+
+        if let Some((best_tip, competing_tip)) =
+            find_competing_tips(&old_best_block_index, &saved_block_index, reorg_occurred)
+        {
+            self.record_chain_split_if_any(best_tip, competing_tip);
+            self.alert_if_long_fork(competing_tip);
+        }
+
+        for connected in &connected_block_fees {
+            self.block_fee_checks.check_connected_block(connected);
+        }
+
+        metrics::node::BLOCKS_PROCESSED.inc();
+        self.block_processing_rate.record(self.time_getter.get_time());
+        self.update_sync_progress_metrics();
+        if reorg_occurred && *saved_block_index.prev_block_id() != old_best_block_index.block_id() {
+            metrics::node::REORGS.inc();
+        }
+
+        Ok(reorg_occurred.then_some(saved_block_index))
+    }
+
+    /// Activate the blocks that have been accepted earlier but whose activation hasn't finished,
+    /// e.g. because of a storage failure or because the node was terminated.
+    ///
+    /// The errors are logged but not propagated, so that a single block can't prevent
+    /// the chainstate from starting or another block from being processed. On success, return
+    /// the block index of the new tip if it has been changed.
+    fn activate_pending_blocks(&mut self) -> Option<BlockIndex> {
+        let pending_block_ids = match self
+            .make_db_tx_ro()
+            .map_err(PropertyQueryError::from)
+            .and_then(|chainstate_ref| chainstate_ref.get_pending_block_activations())
+        {
+            Ok(block_ids) => block_ids,
+            Err(err) => {
+                log::error!("Failed to obtain the blocks pending activation: {err}");
+                return None;
+            }
+        };
+
+        let mut new_tip = None;
+        for block_id in pending_block_ids {
+            log::info!("Retrying the activation of block {block_id}");
+            match self.activate_accepted_block(&block_id) {
+                Ok(Some(block_index)) => new_tip = Some(block_index),
+                Ok(None) => {}
+                Err(err) => log::error!("Failed to activate block {block_id}: {err}"),
+            }
+        }
+
+        new_tip
+    }
+
+    /// Handle a failed acceptance or activation of a block, marking the blocks as invalid
+    /// if needed, and return the resulting error.
+    fn handle_block_integration_error(
+        &mut self,
+        block_index: &BlockIndex,
+        err: BlockIntegrationError,
+    ) -> BlockError {
+        let block_id = *block_index.block_id();
+
+        match err {
+            BlockIntegrationError::BlockCommitError(block_id, attempts_count, db_err) => {
+                BlockError::DbCommitError(
                     attempts_count,
                     db_err,
                     DbCommittingContext::Block(block_id),
-                ))
-            }
-            Err(BlockIntegrationError::OtherNonValidationError(err)) => {
-                return Err(err);
+                )
             }
-            Err(BlockIntegrationError::ConnectBlockErrorDuringReorg(
+            BlockIntegrationError::OtherNonValidationError(err) => err,
+            BlockIntegrationError::ConnectBlockErrorDuringReorg(
                 err,
                 status,
                 first_invalid_parent_id,
-            )) => {
-                let is_block_in_main_chain = {
-                    let chainstate_ref = self.make_db_tx_ro().map_err(BlockError::from)?;
-                    is_block_in_main_chain(&chainstate_ref, &first_invalid_parent_id.into())?
-                };
-                assert!(!is_block_in_main_chain);
+            ) => {
+                let is_block_in_main_chain =
+                    self.make_db_tx_ro().map_err(BlockError::from).and_then(|chainstate_ref| {
+                        is_block_in_main_chain(&chainstate_ref, &first_invalid_parent_id.into())
+                    });
+                match is_block_in_main_chain {
+                    Ok(is_block_in_main_chain) => assert!(!is_block_in_main_chain),
+                    Err(err) => return err,
+                }
 
                 let error_class = err.classify();
                 if error_class.block_should_be_invalidated() {
@@ -467,35 +883,32 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                         first_invalid_parent_id
                     );
 
-                    // Since the failure occurred during reorg, the new block itself is ok.
-                    // Update its block status to persist its validation stage.
-                    // (Also, invalidate_block needs the block index to exist in order to be able to
-                    // set the corresponding failure bit.)
+                    // Since the failure occurred during reorg, the block itself has passed
+                    // the checks and has already been persisted with the corresponding status.
                     debug_assert!(status.is_ok());
-                    // Ignore the result, because we already have an error to return.
-                    let _result = self.set_new_block_index(&block_index.with_status(status));
 
-                    // Again, we ignore the result here.
+                    // Ignore the results, because we already have an error to return.
                     let _result = BlockInvalidator::new(self).invalidate_block(
                         &first_invalid_parent_id,
                         block_invalidation::IsExplicit::No,
                     );
+                    let _result = self.remove_pending_block_activation(&block_id);
                 } else {
                     log::warn!(
                         "Error occurred during reorg, but the block ({}) may not be invalid; skipping invalidation",
                         first_invalid_parent_id
                     );
-                    // Don't save an "ok" status for a block that hasn't been persisted.
+                    // The block stays persisted and its activation will be retried later.
                 }
-                return Err(err);
+                err
             }
-            Err(BlockIntegrationError::OtherReorgError(err, _status)) => {
+            BlockIntegrationError::OtherReorgError(err, _status) => {
                 log::warn!("An error occurred during reorg, but none of the blocks can be blamed");
-                // Don't save an "ok" status for a block that hasn't been persisted.
-                return Err(err);
+                // The block stays persisted and its activation will be retried later.
+                err
             }
-            Err(BlockIntegrationError::BlockCheckError(err, status)) => {
-                // The failure occurred during the integration of the new block itself.
+            BlockIntegrationError::BlockCheckError(err, status) => {
+                // The failure occurred during the acceptance of the new block itself.
 
                 let error_class = err.classify();
                 if error_class.block_should_be_invalidated() {
@@ -515,9 +928,9 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
                     );
                     // Don't save an "ok" status for a block that hasn't been persisted.
                 }
-                return Err(err);
+                err
             }
-        };
+        }
     }
 
     /// If heavy checks are enabled, perform block index consistency check; panic if it's violated.
@@ -553,6 +966,138 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         )
     }
 
+    #[log_error]
+    fn remove_pending_block_activation(&mut self, block_id: &Id<Block>) -> Result<(), BlockError> {
+        self.with_rw_tx(
+            |chainstate_ref| chainstate_ref.remove_pending_block_activation(block_id),
+            |attempt_number| {
+                log::info!(
+                    "Removing block {} from pending activations, attempt #{}",
+                    block_id,
+                    attempt_number
+                );
+            },
+            |attempts_count, db_err| {
+                BlockError::DbCommitError(
+                    attempts_count,
+                    db_err,
+                    DbCommittingContext::Block(*block_id),
+                )
+            },
+        )
+    }
+
+    /// If the competing tip is close enough to the best one, persist a chain split record.
+    ///
+    /// Failure to persist the record is logged but not propagated, because it doesn't affect
+    /// the validity of the chain.
+    fn record_chain_split_if_any(&mut self, best_tip: &BlockIndex, competing_tip: &BlockIndex) {
+        let min_chain_trust = match self.chain_split_min_chain_trust(best_tip) {
+            Ok(min_chain_trust) => min_chain_trust,
+            Err(err) => {
+                log::error!("Failed to obtain the chain trust for a chain split check: {err}");
+                return;
+            }
+        };
+
+        let now = BlockTimestamp::from_time(self.time_getter.get_time());
+        let record = match self.recent_block_observations.make_record_if_split(
+            best_tip,
+            competing_tip,
+            min_chain_trust,
+            now,
+        ) {
+            Some(record) => record,
+            None => return,
+        };
+
+        log::warn!(
+            "Chain split observed: best tip {} at height {}, competing tip {} at height {}",
+            best_tip.block_id(),
+            best_tip.block_height(),
+            competing_tip.block_id(),
+            competing_tip.block_height(),
+        );
+
+        if let Err(err) = self.persist_chain_split_record(&record) {
+            log::error!("Failed to persist chain split record: {err}");
+        }
+    }
+
+    /// If the competing tip belongs to a branch that forked off the main chain more than
+    /// `fork_alert_depth` blocks ago, log a warning and broadcast a `LongFork` event.
+    fn alert_if_long_fork(&mut self, competing_tip: &BlockIndex) {
+        let branch_length = match self
+            .make_db_tx_ro()
+            .map_err(PropertyQueryError::from)
+            .and_then(|chainstate_ref| chainstate_ref.get_branch_length(competing_tip))
+        {
+            Ok(branch_length) => branch_length,
+            Err(err) => {
+                log::error!("Failed to calculate the length of a competing branch: {err}");
+                return;
+            }
+        };
+
+        if branch_length <= *self.chainstate_config.fork_alert_depth {
+            return;
+        }
+
+        log::warn!(
+            "Long fork observed: tip {} at height {} is {} blocks away from the main chain",
+            competing_tip.block_id(),
+            competing_tip.block_height(),
+            branch_length,
+        );
+
+        let event = ChainstateEvent::LongFork(
+            *competing_tip.block_id(),
+            competing_tip.block_height(),
+            branch_length,
+        );
+        self.rpc_events.broadcast(&event);
+        self.subsystem_events.broadcast(event);
+    }
+
+    /// The chain trust of the mainchain block `CHAIN_SPLIT_RECORD_MAX_DEPTH` blocks below
+    /// the best tip.
+    fn chain_split_min_chain_trust(
+        &self,
+        best_tip: &BlockIndex,
+    ) -> Result<Uint256, PropertyQueryError> {
+        let height =
+            (best_tip.block_height() - CHAIN_SPLIT_RECORD_MAX_DEPTH).unwrap_or(BlockHeight::zero());
+        let chainstate_ref = self.make_db_tx_ro()?;
+        let ancestor =
+            chainstate_ref.get_ancestor(&GenBlockIndex::Block(best_tip.clone()), height)?;
+        Ok(ancestor.chain_trust())
+    }
+
+    /// Persist the record and remove the oldest records above `MAX_CHAIN_SPLIT_RECORDS`.
+    fn persist_chain_split_record(
+        &mut self,
+        record: &ChainSplitRecord,
+    ) -> Result<(), chainstate_storage::Error> {
+        let mut db_tx = self.chainstate_storage.transaction_rw(None)?;
+        db_tx.set_chain_split_record(record)?;
+
+        let mut records = db_tx.get_chain_split_records()?;
+        if records.len() > MAX_CHAIN_SPLIT_RECORDS {
+            // The new record is the last one to go among the records observed at the same time.
+            records.sort_by_key(|old_record| {
+                (
+                    old_record.observed_at(),
+                    old_record.competing_tip().block_id() == record.competing_tip().block_id(),
+                )
+            });
+            for old_record in &records[..records.len() - MAX_CHAIN_SPLIT_RECORDS] {
+                db_tx.del_chain_split_record(old_record.competing_tip().block_id())?;
+            }
+        }
+
+        db_tx.commit()
+    }
+
     /// process orphan blocks that depend on the given block, recursively
     #[log_error]
     fn process_orphans_of(
@@ -595,7 +1140,8 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         }
     }
 
-    #[log_error]
+    // Note: the errors are logged by `process_block`, which deduplicates them.
+    #[log_error(level = "debug")]
     fn process_block_and_related_orphans(
         &mut self,
         block: WithId<Block>,
@@ -603,6 +1149,13 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
     ) -> Result<Option<BlockIndex>, BlockError> {
         let block_id = block.get_id();
 
+        // The old tip is only needed to find out which blocks have been connected.
+        let old_tip = if self.filtered_subscribers.is_empty() {
+            None
+        } else {
+            Some(self.get_best_block_info().map_err(BlockError::BestBlockIdQueryError)?)
+        };
+
         let result = self.attempt_to_process_block(block, block_source)?;
 
         let new_block_index_after_orphans = self.process_orphans_of(&block_id)?;
@@ -612,7 +1165,21 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
             None => result,
         };
 
+        // The activations that have failed earlier for reasons unrelated to the blocks
+        // themselves may succeed now.
+        let result = match self.activate_pending_blocks() {
+            Some(result_from_pending) => Some(result_from_pending),
+            None => result,
+        };
+
         if let Some(bi) = &result {
+            if let Some(old_tip) = &old_tip {
+                // Note: the block has already been committed at this point, so failing to
+                // notify the filtered subscribers is not a reason to reject it.
+                if let Err(err) = self.broadcast_filtered_events(old_tip, bi) {
+                    log::error!("Failed to notify filtered subscribers: {err}");
+                }
+            }
             self.broadcast_new_tip_event(bi);
 
             let compact_target = match bi.block_header().consensus_data() {
@@ -645,14 +1212,50 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         Ok(result)
     }
 
+    /// Log the error that occurred while processing the block.
+    ///
+    /// If the same block keeps being rejected with the same error (e.g. because a peer keeps
+    /// sending it), the error is only logged once per `BLOCK_REJECTION_SUMMARY_PERIOD` and
+    /// a summary of the suppressed rejections is logged afterwards.
+    fn log_block_rejection(
+        &mut self,
+        block_id: Id<Block>,
+        block_source: BlockSource,
+        error: &BlockError,
+    ) {
+        let now = self.time_getter.get_time();
+        match self.recent_block_rejections.record(block_id, error, block_source, now) {
+            BlockRejectionLogAction::Log => {
+                log::error!("Failed to process block {block_id}: {error}");
+            }
+            BlockRejectionLogAction::Suppress => {
+                log::debug!("Failed to process block {block_id} (repeated error): {error}");
+            }
+            BlockRejectionLogAction::Summarize(summary) => {
+                log::error!(
+                    "Block {} rejected {} times in the last {}s from {} peers (ban score {}): {}",
+                    block_id,
+                    summary.rejection_count,
+                    summary.elapsed.as_secs(),
+                    summary.peer_count,
+                    error.ban_score(),
+                    error,
+                );
+            }
+        }
+    }
+
     /// returns the block index of the new tip
-    #[log_error]
     pub fn process_block(
         &mut self,
         block: WithId<Block>,
         block_source: BlockSource,
     ) -> Result<Option<BlockIndex>, BlockError> {
+        let block_id = block.get_id();
         let result = self.process_block_and_related_orphans(block, block_source);
+        if let Err(err) = &result {
+            self.log_block_rejection(block_id, block_source, err);
+        }
         // Note: we don't ignore the result of check_consistency even though we may already have
         // an error to return (if the checks are enabled but couldn't be done for some reason,
         // we don't want to miss this).
@@ -667,6 +1270,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         let genesis_id = self.chain_config.genesis_block_id();
 
         // Initialize storage with given info
+        self.invalidate_best_block_info_cache();
         let mut db_tx = self.chainstate_storage.transaction_rw(None).map_err(BlockError::from)?;
         db_tx.set_best_block_id(&genesis_id).map_err(BlockError::StorageError)?;
         db_tx
@@ -759,10 +1363,149 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
         self.subsystem_events.subscribers()
     }
 
+    /// Remember the fees predicted for a locally produced block, so that they can be compared
+    /// with the actual fees once the block is connected.
+    pub fn add_block_fee_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee) {
+        self.block_fee_checks.add_prediction(block_id, predicted_fees)
+    }
+
+    /// Return the results of comparing the predicted fees of locally produced blocks with
+    /// the actual ones.
+    pub fn block_fee_check_stats(&self) -> BlockFeeCheckStats {
+        self.block_fee_checks.stats()
+    }
+
     pub fn is_initial_block_download(&self) -> bool {
         !self.is_initial_block_download_finished.test()
     }
 
+    /// Return the progress of the initial block download. The network height and
+    /// the verification progress are estimated from the tip timestamp and the current time.
+    #[log_error]
+    pub fn ibd_status(&self) -> Result<IbdStatus, PropertyQueryError> {
+        let best_block_index = self.query()?.get_best_block_index()?;
+        let best_block_height = best_block_index.block_height();
+        let best_block_timestamp = best_block_index.block_timestamp();
+        let is_initial_block_download = self.is_initial_block_download();
+
+        let (estimated_network_height, verification_progress) = if is_initial_block_download {
+            let now = self.time_getter.get_time().as_duration_since_epoch().as_secs();
+            let tip_time = best_block_timestamp.as_int_seconds();
+            let genesis_time = self.chain_config.genesis_block().timestamp().as_int_seconds();
+            let block_spacing =
+                std::cmp::max(self.chain_config.target_block_spacing().as_secs(), 1);
+
+            let blocks_behind = now.saturating_sub(tip_time) / block_spacing;
+            let estimated_network_height =
+                BlockHeight::new(best_block_height.into_int().saturating_add(blocks_behind));
+
+            let progress = match now.checked_sub(genesis_time) {
+                Some(total) if total > 0 => {
+                    let done = tip_time.saturating_sub(genesis_time);
+                    std::cmp::min(done.saturating_mul(1000) / total, 1000)
+                }
+                Some(_) | None => 1000,
+            };
+            let verification_progress =
+                PerThousand::new(progress as u16).expect("progress is at most 1000");
+
+            (estimated_network_height, verification_progress)
+        } else {
+            let verification_progress = PerThousand::new(1000).expect("valid value");
+            (best_block_height, verification_progress)
+        };
+
+        Ok(IbdStatus {
+            is_initial_block_download,
+            best_block_height,
+            best_block_timestamp,
+            estimated_network_height,
+            verification_progress,
+        })
+    }
+
+    /// Return the sync progress in terms of the chain trust. Since the blocks are only known
+    /// to the chainstate once they are received, the trust of the missing blocks is estimated
+    /// from the average trust of the recent mainchain blocks.
+    #[log_error]
+    pub fn sync_progress(&self) -> Result<SyncProgress, PropertyQueryError> {
+        let IbdStatus {
+            is_initial_block_download,
+            best_block_height,
+            best_block_timestamp: _,
+            estimated_network_height,
+            verification_progress: time_based_progress,
+        } = self.ibd_status()?;
+
+        let query = self.query()?;
+        let validated_chain_trust = query.get_best_block_index()?.chain_trust();
+
+        let remaining_blocks = (estimated_network_height - best_block_height)
+            .and_then(|distance| u64::try_from(distance.to_int()).ok())
+            .unwrap_or(0);
+
+        let window_start = BlockHeight::new(
+            best_block_height.into_int().saturating_sub(CHAIN_TRUST_AVERAGING_WINDOW),
+        );
+        let window_length = best_block_height.into_int() - window_start.into_int();
+        let window_start_trust = query
+            .get_block_id_from_height(&window_start)?
+            .map(|id| query.get_gen_block_index_for_persisted_block(&id))
+            .transpose()?
+            .flatten()
+            .map(|block_index| block_index.chain_trust());
+        let average_block_trust = window_start_trust
+            .filter(|_| window_length > 0)
+            .and_then(|start_trust| validated_chain_trust - start_trust)
+            .and_then(|trust| trust / Uint256::from_u64(window_length));
+
+        let (estimated_network_chain_trust, verification_progress) = match average_block_trust {
+            Some(average_block_trust) if remaining_blocks > 0 => {
+                let missing_chain_trust = (average_block_trust
+                    * Uint256::from_u64(remaining_blocks))
+                .unwrap_or(Uint256::MAX);
+                let estimated_network_chain_trust =
+                    (validated_chain_trust + missing_chain_trust).unwrap_or(Uint256::MAX);
+                let verification_progress = sync_progress::chain_trust_fraction(
+                    validated_chain_trust,
+                    estimated_network_chain_trust,
+                );
+                (estimated_network_chain_trust, verification_progress)
+            }
+            // Nothing is missing or there is nothing to extrapolate from (e.g. the tip is
+            // the genesis), so fall back to the time-based estimate.
+            Some(_) | None => (validated_chain_trust, time_based_progress),
+        };
+
+        let estimated_seconds_remaining = self
+            .block_processing_rate
+            .estimate_remaining_time(remaining_blocks)
+            .map(|duration| duration.as_secs());
+
+        Ok(SyncProgress {
+            is_initial_block_download,
+            best_block_height,
+            estimated_network_height,
+            validated_chain_trust: validated_chain_trust.into(),
+            estimated_network_chain_trust: estimated_network_chain_trust.into(),
+            verification_progress,
+            estimated_seconds_remaining,
+        })
+    }
+
+    fn update_sync_progress_metrics(&self) {
+        match self.sync_progress() {
+            Ok(progress) => {
+                metrics::node::SYNC_PROGRESS.set(progress.verification_progress.value().into());
+                metrics::node::SYNC_SECONDS_REMAINING
+                    .set(progress.estimated_seconds_remaining.unwrap_or(0));
+            }
+            Err(err) => {
+                log::debug!("Failed to update the sync progress metrics: {err}");
+            }
+        }
+    }
+
     /// Returns true if the given block timestamp is newer than `ChainstateConfig::max_tip_age`.
     fn is_fresh_block(&self, time: &BlockTimestamp) -> bool {
         let now = self.time_getter.get_time().as_duration_since_epoch();
@@ -825,7 +1568,7 @@ impl<S: BlockchainStorage, V: TransactionVerificationStrategy> Chainstate<S, V>
     }
 }
 
-/// The error type for integrate_block.
+/// The error type for accept_block and activate_block.
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
 enum BlockIntegrationError {
     #[error("Reorg error during block integration: {0}; resulting block status is {1}; first bad block id is {2}")]
@@ -889,5 +1632,29 @@ where
         .map_err(|err| BlockError::IsBlockInMainChainQueryError(*block_id, err))
 }
 
+/// If the newly processed block competes with the old tip, return the best tip and the
+/// competing one, in this order.
+fn find_competing_tips<'a>(
+    old_best_block_index: &'a GenBlockIndex,
+    new_block_index: &'a BlockIndex,
+    new_block_became_tip: bool,
+) -> Option<(&'a BlockIndex, &'a BlockIndex)> {
+    let old_best_block_index = match old_best_block_index {
+        GenBlockIndex::Block(block_index) => block_index,
+        GenBlockIndex::Genesis(_) => return None,
+    };
+
+    if new_block_became_tip {
+        let old_best_block_id: Id<GenBlock> = (*old_best_block_index.block_id()).into();
+        if *new_block_index.prev_block_id() == old_best_block_id {
+            // The new block simply extends the old tip.
+            return None;
+        }
+        Some((new_block_index, old_best_block_index))
+    } else {
+        Some((old_best_block_index, new_block_index))
+    }
+}
+
 #[cfg(test)]
 mod test;
@@ -13,12 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 
 use logging::log;
 use utils::tap_log::TapLog;
 
 use super::OrphanBlocksPool;
+use crate::detail::cache_memory::CacheMemoryBudget;
 
 type RemoteCall = Option<Box<dyn FnOnce(&mut OrphanBlocksPool) + Send>>;
 
@@ -31,10 +32,10 @@ pub struct OrphansProxy {
 }
 
 impl OrphansProxy {
-    pub fn new(max_orphans: usize) -> Self {
+    pub fn new(max_orphans: usize, memory_budget: Arc<CacheMemoryBudget>) -> Self {
         let (tx, rx) = mpsc::channel();
         let thread_handle = Some(std::thread::spawn(move || {
-            let mut orphans_pool = OrphanBlocksPool::new(max_orphans);
+            let mut orphans_pool = OrphanBlocksPool::new(max_orphans, memory_budget);
             let receiver: mpsc::Receiver<RemoteCall> = rx;
             while let Ok(f) = receiver.recv() {
                 match f {
@@ -88,10 +89,16 @@ mod tests {
     use common::primitives::H256;
 
     use super::*;
+    use crate::CacheMemoryLimits;
 
     #[test]
     fn test_orphans_proxy_control() {
-        let orphans_proxy = OrphansProxy::new(500);
+        let limits = CacheMemoryLimits {
+            block_index_cache: 0,
+            utxo_cache: 0,
+            orphan_blocks: usize::MAX,
+        };
+        let orphans_proxy = OrphansProxy::new(500, Arc::new(CacheMemoryBudget::new(limits, None)));
         assert_eq!(orphans_proxy.call(|o| o.len()).recv().unwrap(), 0);
         assert!(!orphans_proxy
             .call(|o| o.is_already_an_orphan(&H256::zero().into()))
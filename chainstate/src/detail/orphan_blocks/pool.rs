@@ -15,17 +15,23 @@
 
 use std::collections::BTreeMap;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use common::chain::{Block, GenBlock};
 use common::primitives::id::WithId;
 use common::primitives::{Id, Idable};
 use randomness::SliceRandom;
+use serialization::Encode;
+
+use crate::detail::cache_memory::{CacheKind, CacheMemoryBudget};
 
 pub struct OrphanBlocksPool {
     orphan_ids: Vec<Id<Block>>,
     orphan_by_id: BTreeMap<Id<Block>, Rc<WithId<Block>>>,
     orphan_by_prev_id: BTreeMap<Id<GenBlock>, Vec<Rc<WithId<Block>>>>,
     max_orphans: usize,
+    memory_budget: Arc<CacheMemoryBudget>,
+    memory_usage: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,12 +40,14 @@ pub enum OrphanAddError {
 }
 
 impl OrphanBlocksPool {
-    pub fn new(max_orphans: usize) -> Self {
+    pub fn new(max_orphans: usize, memory_budget: Arc<CacheMemoryBudget>) -> Self {
         OrphanBlocksPool {
             orphan_ids: Vec::new(),
             orphan_by_id: BTreeMap::new(),
             orphan_by_prev_id: BTreeMap::new(),
             max_orphans,
+            memory_budget,
+            memory_usage: 0,
         }
     }
 
@@ -47,12 +55,36 @@ impl OrphanBlocksPool {
         self.orphan_ids.len()
     }
 
+    /// The approximate memory usage of the blocks in the pool.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+
+    /// The approximate memory needed to keep the block in the pool.
+    pub fn entry_size(block: &Block) -> usize {
+        std::mem::size_of::<WithId<Block>>()
+            + 2 * std::mem::size_of::<Rc<WithId<Block>>>()
+            + std::mem::size_of::<Id<Block>>()
+            + block.encoded_size()
+    }
+
+    /// The amount of memory the pool may currently use.
+    pub fn max_memory_usage(&self) -> usize {
+        self.memory_budget.max_usage(CacheKind::OrphanBlocks)
+    }
+
+    fn update_memory_usage(&self) {
+        self.memory_budget.set_usage(CacheKind::OrphanBlocks, self.memory_usage);
+        metrics::node::ORPHAN_BLOCKS_MEMORY_USAGE.set(self.memory_usage as u64);
+    }
+
     fn drop_block(&mut self, block_id: &Id<Block>) {
         use std::collections::btree_map::Entry;
 
         // remove from the map
         let block = self.orphan_by_id.remove(block_id).expect("Entry missing from the map");
         let prev_block_id = block.prev_block_id();
+        self.memory_usage -= Self::entry_size(&block);
 
         // remove from the vector
         self.orphan_ids.retain(|id| *id != *block_id);
@@ -67,6 +99,8 @@ impl OrphanBlocksPool {
                 }
             }
         }
+
+        self.update_memory_usage();
     }
 
     // keep digging in the orphans tree until we find a block that has no children, then delete that
@@ -84,18 +118,26 @@ impl OrphanBlocksPool {
         }
     }
 
-    fn prune(&mut self) {
-        if self.len() < self.max_orphans {
-            return;
-        }
-        let id = self.orphan_ids.choose(&mut randomness::make_pseudo_rng());
-        let id = *id.expect("As orphans can never be empty, this should always return");
+    // drop random blocks until there is room for one more block of the specified size
+    fn prune(&mut self, extra_memory: usize, max_memory_usage: usize) {
+        while !self.orphan_ids.is_empty()
+            && (self.len() >= self.max_orphans
+                || self.memory_usage + extra_memory > max_memory_usage)
+        {
+            let id = self.orphan_ids.choose(&mut randomness::make_pseudo_rng());
+            let id = *id.expect("As orphans are not empty, this should always return");
 
-        self.del_one_deepest_child(&id);
+            self.del_one_deepest_child(&id);
+        }
     }
 
+    /// Add the block to the pool, evicting random blocks if either the number of blocks or their
+    /// memory usage exceeds the limit. A block that is bigger than the memory limit by itself
+    /// is not stored.
     pub fn add_block(&mut self, block: WithId<Block>) -> Result<(), Box<OrphanAddError>> {
-        self.prune();
+        let entry_size = Self::entry_size(&block);
+        let max_memory_usage = self.max_memory_usage();
+        self.prune(entry_size, max_memory_usage);
         let block_id = block.get_id();
         if self.orphan_by_id.contains_key(&block_id) {
             return Err(Box::new(OrphanAddError::BlockAlreadyInOrphanList(
@@ -103,6 +145,13 @@ impl OrphanBlocksPool {
             )));
         }
 
+        if entry_size > max_memory_usage {
+            return Ok(());
+        }
+
+        self.memory_usage += entry_size;
+        self.update_memory_usage();
+
         let rc_block = Rc::new(block);
         self.orphan_by_id.insert(block_id, rc_block.clone());
         self.orphan_ids.push(block_id);
@@ -123,6 +172,8 @@ impl OrphanBlocksPool {
         self.orphan_by_id.clear();
         self.orphan_ids.clear();
         self.orphan_by_prev_id.clear();
+        self.memory_usage = 0;
+        self.update_memory_usage();
     }
 
     /// take all the blocks that share the same parent
@@ -161,6 +212,22 @@ mod tests {
 
     const MAX_ORPHAN_BLOCKS: usize = 512;
 
+    fn new_pool(max_orphans: usize, max_memory_usage: usize) -> OrphanBlocksPool {
+        OrphanBlocksPool::new(max_orphans, new_memory_budget(max_memory_usage, None))
+    }
+
+    fn new_memory_budget(
+        max_memory_usage: usize,
+        max_total: Option<usize>,
+    ) -> Arc<CacheMemoryBudget> {
+        let limits = crate::CacheMemoryLimits {
+            block_index_cache: max_memory_usage,
+            utxo_cache: 0,
+            orphan_blocks: max_memory_usage,
+        };
+        Arc::new(CacheMemoryBudget::new(limits, max_total))
+    }
+
     mod helpers {
         use super::*;
         use common::chain::block::timestamp::BlockTimestamp;
@@ -266,7 +333,7 @@ mod tests {
     #[test]
     fn test_pool_custom() {
         let max_orphans = 3;
-        let orphans_pool = OrphanBlocksPool::new(max_orphans);
+        let orphans_pool = new_pool(max_orphans, usize::MAX);
         assert_eq!(orphans_pool.max_orphans, max_orphans);
         check_empty_pool(&orphans_pool);
     }
@@ -275,7 +342,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_add_one_block_and_clear(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
 
         // add a random block
         let mut rng = make_seedable_rng(seed);
@@ -295,7 +362,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_add_blocks_and_clear(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
 
         // add a random block
         let mut rng = make_seedable_rng(seed);
@@ -343,7 +410,7 @@ mod tests {
     #[case(Seed::from_entropy())]
     fn test_add_block_exceeds_max(#[case] seed: Seed) {
         let max_orphans = 3;
-        let mut orphans_pool = OrphanBlocksPool::new(max_orphans);
+        let mut orphans_pool = new_pool(max_orphans, usize::MAX);
         let mut rng = make_seedable_rng(seed);
         let blocks = gen_random_blocks(&mut rng, max_orphans as u32 + 2);
 
@@ -354,11 +421,64 @@ mod tests {
         check_pool_length(&orphans_pool, max_orphans);
     }
 
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn test_add_block_exceeds_max_memory(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let blocks = gen_random_blocks(&mut rng, 5);
+        let entry_size = OrphanBlocksPool::entry_size(&blocks[0]);
+
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, entry_size * 3);
+        blocks.iter().for_each(|block| {
+            assert!(orphans_pool.add_block(block.clone().into()).is_ok());
+            assert!(orphans_pool.memory_usage() <= entry_size * 3);
+        });
+
+        check_pool_length(&orphans_pool, 3);
+        assert_eq!(orphans_pool.memory_usage(), entry_size * 3);
+        check_block_existence(&orphans_pool, &blocks[4].clone().into());
+
+        // A block that doesn't fit into the pool at all is not stored
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, entry_size - 1);
+        assert!(orphans_pool.add_block(blocks[0].clone().into()).is_ok());
+        check_empty_pool(&orphans_pool);
+        assert_eq!(orphans_pool.memory_usage(), 0);
+
+        orphans_pool.clear();
+        assert_eq!(orphans_pool.memory_usage(), 0);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn test_add_block_under_memory_pressure(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let blocks = gen_random_blocks(&mut rng, 5);
+        let entry_size = OrphanBlocksPool::entry_size(&blocks[0]);
+
+        // The pool gets half of the global limit, but may use all of it while it's unused
+        let budget = new_memory_budget(entry_size * 4, Some(entry_size * 4));
+        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS, Arc::clone(&budget));
+        blocks[..4].iter().for_each(|block| {
+            assert!(orphans_pool.add_block(block.clone().into()).is_ok());
+        });
+        check_pool_length(&orphans_pool, 4);
+        assert_eq!(budget.usage(CacheKind::OrphanBlocks), entry_size * 4);
+
+        // Once another cache takes its share, the pool shrinks to its own one
+        budget.set_usage(CacheKind::BlockIndexCache, entry_size * 2);
+        assert!(orphans_pool.add_block(blocks[4].clone().into()).is_ok());
+        check_block_existence_and_pool_length(&orphans_pool, &blocks[4].clone().into(), 2);
+        assert_eq!(budget.usage(CacheKind::OrphanBlocks), entry_size * 2);
+        assert_eq!(budget.total_usage(), entry_size * 4);
+    }
+
     #[rstest]
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_add_block_repeated(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
         let mut rng = make_seedable_rng(seed);
         let blocks = gen_random_blocks(&mut rng, 50);
 
@@ -378,7 +498,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_pool_drop_block(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
         let mut rng = make_seedable_rng(seed);
         let blocks = gen_random_blocks(&mut rng, 5);
 
@@ -401,7 +521,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_deepest_child_in_chain(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
         let mut rng = make_seedable_rng(seed);
 
         // In `orphans_by_prev_id`:
@@ -454,7 +574,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_deepest_child_common_parent(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(MAX_ORPHAN_BLOCKS);
+        let mut orphans_pool = new_pool(MAX_ORPHAN_BLOCKS, usize::MAX);
         let mut rng = make_seedable_rng(seed);
         // In `orphans_by_prev_id`:
         // [
@@ -504,7 +624,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_prune(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(12);
+        let mut orphans_pool = new_pool(12, usize::MAX);
         let mut rng = make_seedable_rng(seed);
         // in `orphans_by_prev_id`:
         // [
@@ -555,11 +675,11 @@ mod tests {
         assert_eq!(orphans_pool.orphan_by_prev_id.len(), 7);
 
         // 1 block is removed; size is 1 less than the set max_orphans
-        orphans_pool.prune();
+        orphans_pool.prune(0);
         check_pool_length(&orphans_pool, orphans_pool.max_orphans - 1);
 
         // for the 2nd prune, nothing should happen.
-        orphans_pool.prune();
+        orphans_pool.prune(0);
         check_pool_length(&orphans_pool, orphans_pool.max_orphans - 1);
 
         // add a random block
@@ -572,7 +692,7 @@ mod tests {
         );
 
         // this will trigger pruning
-        orphans_pool.prune();
+        orphans_pool.prune(0);
         check_pool_length(&orphans_pool, orphans_pool.max_orphans - 1);
     }
 
@@ -580,7 +700,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_simple_take_all_children_of(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(20);
+        let mut orphans_pool = new_pool(20, usize::MAX);
         let mut rng = make_seedable_rng(seed);
 
         let count = 9;
@@ -624,7 +744,7 @@ mod tests {
     #[trace]
     #[case(Seed::from_entropy())]
     fn test_mix_chain_take_all_children_of(#[case] seed: Seed) {
-        let mut orphans_pool = OrphanBlocksPool::new(20);
+        let mut orphans_pool = new_pool(20, usize::MAX);
         let mut rng = make_seedable_rng(seed);
 
         let count = 9;
@@ -13,10 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::num::NonZeroUsize;
+use std::{collections::BTreeSet, num::NonZeroUsize};
 
 use chainstate_storage::BlockchainStorageRead;
-use chainstate_types::{BlockIndex, GenBlockIndex, Locator, PropertyQueryError};
+use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, GenBlockIndex,
+    Locator, LocatorSchedule, PoolBlockRecord, PoolBlockStats, PropertyQueryError,
+};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, BlockReward},
@@ -25,19 +28,26 @@ use common::{
             NftIssuance, RPCFungibleTokenInfo, RPCIsTokenFrozen, RPCNonFungibleTokenInfo,
             RPCTokenInfo, TokenAuxiliaryData, TokenId,
         },
-        AccountType, Block, GenBlock, OrderId, RpcOrderInfo, Transaction, TxOutput,
+        AccountType, Block, Destination, GenBlock, OrderId, PoolId, RpcOrderInfo, Transaction,
+        TxOutput,
     },
     primitives::{Amount, BlockDistance, BlockHeight, Id, Idable},
 };
 use orders_accounting::{OrderData, OrdersAccountingStorageRead};
+use serialization::Encode;
 use tokens_accounting::TokensAccountingStorageRead;
 use utils::ensure;
-
-use super::{chainstateref, tx_verification_strategy::TransactionVerificationStrategy};
-
-pub fn locator_tip_distances() -> impl Iterator<Item = BlockDistance> {
-    itertools::iterate(0, |&i| std::cmp::max(1, i * 2)).map(BlockDistance::new)
-}
+use utxo::UtxosBlockUndo;
+
+use super::{
+    block_filter::{BlockFilter, FilteredBlock},
+    chain_tips::ChainTip,
+    chainstateref::{self, DbVerificationError, DbVerificationLevel},
+    destination_balance::{DestinationBalance, DestinationBalanceBuilder},
+    tx_verification_strategy::TransactionVerificationStrategy,
+    utxo_diff::{BlockUtxoDiff, UtxoDiffChunk},
+    utxo_statistics::{UtxoSetStatistics, UtxoSetStatisticsBuilder},
+};
 
 pub struct ChainstateQuery<'a, S, V> {
     chainstate_ref: chainstateref::ChainstateRef<'a, S, V>,
@@ -90,6 +100,33 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_block(id)?.ok_or(PropertyQueryError::BlockNotFound(id))
     }
 
+    /// Return the blocks with the specified ids in the same order, `None` for the missing ones.
+    ///
+    /// Stop once the total encoded size of the returned blocks would exceed `size_limit`;
+    /// the first block is returned in any case.
+    pub fn get_blocks(
+        &self,
+        ids: &[Id<Block>],
+        size_limit: usize,
+    ) -> Result<Vec<Option<Block>>, PropertyQueryError> {
+        let mut res = Vec::new();
+        let mut total_size = 0;
+
+        for id in ids {
+            let block = self.get_block(*id)?;
+            let block_size = block.as_ref().map_or(0, |block| block.encoded_size());
+
+            if !res.is_empty() && total_size + block_size > size_limit {
+                break;
+            }
+
+            total_size += block_size;
+            res.push(block);
+        }
+
+        Ok(res)
+    }
+
     pub fn get_mainchain_blocks(
         &self,
         mut from: BlockHeight,
@@ -124,6 +161,226 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         Ok(res)
     }
 
+    /// Collect the utxo set changes of mainchain blocks in `from_height..to_height`.
+    ///
+    /// Blocks are added to the result until the number of entries reaches `max_entries`, but
+    /// at least one block is always returned if the range is not empty.
+    /// Same as `get_mainchain_blocks`, but also return the heights and the undo data
+    /// of the blocks.
+    pub fn get_mainchain_blocks_with_undo_data(
+        &self,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<(BlockHeight, Block, Option<UtxosBlockUndo>)>, PropertyQueryError> {
+        let blocks = self.get_mainchain_blocks(from, max_count)?;
+
+        itertools::iterate(from, |height| height.next_height())
+            .zip(blocks)
+            .map(|(height, block)| {
+                let undo = self.chainstate_ref.get_undo_data(block.get_id())?;
+                Ok((height, block, undo))
+            })
+            .collect()
+    }
+
+    /// Return the main chain blocks starting at the specified height (at most `max_count`
+    /// of them), leaving only the parts that are relevant to the filter.
+    pub fn get_filtered_mainchain_blocks(
+        &self,
+        filter: &BlockFilter,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<FilteredBlock>, PropertyQueryError> {
+        let blocks = self.get_mainchain_blocks_with_undo_data(from, max_count)?;
+
+        Ok(blocks
+            .iter()
+            .filter_map(|(height, block, undo)| filter.filter_block(block, *height, undo.as_ref()))
+            .collect())
+    }
+
+    /// Return the height of the last common ancestor of the specified block and the main chain.
+    pub fn get_fork_height_in_main_chain(
+        &self,
+        id: &Id<GenBlock>,
+    ) -> Result<BlockHeight, PropertyQueryError> {
+        let block_index = self.chainstate_ref.get_existing_gen_block_index(id)?;
+        Ok(self
+            .chainstate_ref
+            .last_common_ancestor_in_main_chain(&block_index)?
+            .block_height())
+    }
+
+    /// Make sure the undo data of the main chain blocks starting at the height hasn't been pruned.
+    fn ensure_undo_data_not_pruned(&self, height: BlockHeight) -> Result<(), PropertyQueryError> {
+        let min_height_with_undo_data = self.chainstate_ref.get_min_height_with_undo_data()?;
+        ensure!(
+            height >= min_height_with_undo_data,
+            PropertyQueryError::UndoDataPruned {
+                height,
+                min_height_with_undo_data
+            }
+        );
+        Ok(())
+    }
+
+    pub fn get_utxo_diff(
+        &self,
+        from_height: BlockHeight,
+        to_height: BlockHeight,
+        max_entries: NonZeroUsize,
+    ) -> Result<UtxoDiffChunk, PropertyQueryError> {
+        ensure!(
+            from_height != BlockHeight::zero(),
+            PropertyQueryError::InvalidStartingBlockHeightForMainchainBlocks(from_height)
+        );
+        ensure!(
+            to_height >= from_height,
+            PropertyQueryError::InvalidBlockHeightRange {
+                start: from_height,
+                end: to_height
+            }
+        );
+
+        self.ensure_undo_data_not_pruned(from_height)?;
+
+        let best_height = self.chainstate_ref.get_best_block_index()?.block_height();
+        let to_height = std::cmp::min(to_height, best_height.next_height());
+
+        let mut blocks = Vec::new();
+        let mut entry_count = 0;
+        let mut height = from_height;
+        while height < to_height {
+            if entry_count >= max_entries.get() {
+                return Ok(UtxoDiffChunk {
+                    blocks,
+                    next_height: Some(height),
+                });
+            }
+
+            let block_id = self
+                .chainstate_ref
+                .get_existing_block_id_by_height(&height)?
+                .to_block_id(self.chainstate_ref.chain_config())
+                .ok_or(PropertyQueryError::GenesisHeaderRequested)?;
+            let block = self.get_existing_block(block_id)?;
+            let undo = self
+                .chainstate_ref
+                .get_undo_data(block_id)?
+                .ok_or(PropertyQueryError::UndoDataNotFound(block_id))?;
+
+            let diff = BlockUtxoDiff::from_block_and_undo(&block, height, undo);
+            entry_count += diff.entry_count();
+            blocks.push(diff);
+
+            height = height.next_height();
+        }
+
+        Ok(UtxoDiffChunk {
+            blocks,
+            next_height: None,
+        })
+    }
+
+    /// Go over the whole mainchain utxo set and summarize it.
+    pub fn get_utxo_set_statistics(&self) -> Result<UtxoSetStatistics, PropertyQueryError> {
+        let best_block_index = self.chainstate_ref.get_best_block_index()?;
+
+        let mut builder = UtxoSetStatisticsBuilder::new();
+        self.chainstate_ref.try_for_each_utxo(|outpoint, utxo| {
+            builder.add(&outpoint, &utxo);
+            Ok(())
+        })?;
+
+        builder
+            .build(best_block_index.block_id(), best_block_index.block_height())
+            .ok_or(PropertyQueryError::UtxoSetCoinAmountOverflow)
+    }
+
+    /// Calculate the balance of the destination at the specified mainchain height.
+    ///
+    /// The balance is calculated from the current utxo set, which is then rolled back to
+    /// the specified height using the undo data of the blocks above it. So this goes over
+    /// the entire utxo set and all the blocks above the height; to keep the latter bounded,
+    /// the height can't be deeper than the max reorg depth below the tip.
+    pub fn get_balance_at_height(
+        &self,
+        destination: &Destination,
+        height: BlockHeight,
+    ) -> Result<DestinationBalance, PropertyQueryError> {
+        let best_height = self.chainstate_ref.get_best_block_index()?.block_height();
+        ensure!(
+            height <= best_height,
+            PropertyQueryError::HeightAboveBestBlock {
+                height,
+                best_height
+            }
+        );
+
+        let max_depth = self.chainstate_ref.chain_config().max_depth_for_reorg();
+        let depth = (best_height - height).expect("the height is not above the best block");
+        ensure!(
+            depth <= max_depth,
+            PropertyQueryError::HeightTooDeepBelowBestBlock {
+                height,
+                best_height,
+                max_depth
+            }
+        );
+
+        // The undo data of the blocks above the requested height is needed to roll them back
+        self.ensure_undo_data_not_pruned(height.next_height())?;
+
+        let mut builder = DestinationBalanceBuilder::new(destination);
+        self.chainstate_ref.try_for_each_utxo(|_, utxo| {
+            builder
+                .add(utxo.output())
+                .ok_or(PropertyQueryError::DestinationBalanceOverflow)?;
+            Ok(())
+        })?;
+
+        let mut block_height = best_height;
+        while block_height > height {
+            let block_id = self
+                .chainstate_ref
+                .get_existing_block_id_by_height(&block_height)?
+                .to_block_id(self.chainstate_ref.chain_config())
+                .ok_or(PropertyQueryError::GenesisHeaderRequested)?;
+            let block = self.get_existing_block(block_id)?;
+            let undo = self
+                .chainstate_ref
+                .get_undo_data(block_id)?
+                .ok_or(PropertyQueryError::UndoDataNotFound(block_id))?;
+            let diff = BlockUtxoDiff::from_block_and_undo(&block, block_height, undo);
+
+            // Restore the spent utxos first, because the utxos created and spent in the same
+            // block are present in both lists.
+            for entry in &diff.spent {
+                builder
+                    .add(&entry.output)
+                    .ok_or(PropertyQueryError::DestinationBalanceOverflow)?;
+            }
+            for entry in &diff.created {
+                builder
+                    .subtract(&entry.output)
+                    .ok_or(PropertyQueryError::DestinationBalanceUnderflow(block_id))?;
+            }
+
+            block_height = block_height.prev_height().expect("can't be genesis");
+        }
+
+        let block_id = self.chainstate_ref.get_existing_block_id_by_height(&height)?;
+        Ok(builder.build(block_id, height))
+    }
+
+    pub fn verify_database(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, PropertyQueryError> {
+        Ok(self.chainstate_ref.verify_database(level, depth)?)
+    }
+
     pub fn get_block_index_for_persisted_block(
         &self,
         id: &Id<Block>,
@@ -176,7 +433,8 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         &self,
         height: BlockHeight,
     ) -> Result<Locator, PropertyQueryError> {
-        let headers = locator_tip_distances()
+        let headers = LocatorSchedule::default()
+            .distances()
             .map_while(|dist| height - dist)
             .map(|ht| self.chainstate_ref.get_block_id_by_height(&ht));
 
@@ -184,6 +442,27 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
             .map(Locator::new)
     }
 
+    /// Construct a locator starting from the specified block, which may be on a stale chain,
+    /// using its ancestors at the distances determined by the schedule.
+    pub fn get_locator_from_block(
+        &self,
+        block_id: &Id<GenBlock>,
+        schedule: &LocatorSchedule,
+    ) -> Result<Locator, PropertyQueryError> {
+        let block_index = self.chainstate_ref.get_existing_gen_block_index(block_id)?;
+        let height = block_index.block_height();
+
+        let headers = schedule.distances().map_while(|dist| height - dist).map(|ht| {
+            self.chainstate_ref
+                .get_ancestor(&block_index, ht)
+                .map(|ancestor| ancestor.block_id())
+        });
+
+        itertools::process_results(headers, |iter| iter.collect::<Vec<_>>())
+            .map(Locator::new)
+            .map_err(PropertyQueryError::from)
+    }
+
     pub fn get_block_ids_as_checkpoints(
         &self,
         start_height: BlockHeight,
@@ -229,6 +508,61 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_min_height_with_allowed_reorg()
     }
 
+    pub fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, PropertyQueryError> {
+        self.chainstate_ref.get_chain_split_records()
+    }
+
+    pub fn get_block_filter(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<CompactBlockFilter>, PropertyQueryError> {
+        self.chainstate_ref.get_block_filter(block_id)
+    }
+
+    pub fn get_block_filter_commitment(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockFilterCommitment>, PropertyQueryError> {
+        self.chainstate_ref.get_block_filter_commitment(block_id)
+    }
+
+    /// Return the main chain blocks produced by the pool starting at the specified height
+    /// (at most `max_count` of them), ordered by height.
+    pub fn get_blocks_by_pool(
+        &self,
+        pool_id: PoolId,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<PoolBlockRecord>, PropertyQueryError> {
+        let blocks = self.chainstate_ref.get_pool_blocks(pool_id)?;
+        Ok(blocks
+            .into_iter()
+            .filter(|record| record.block_height() >= from)
+            .take(max_count)
+            .collect())
+    }
+
+    pub fn get_pool_block_stats(
+        &self,
+        pool_id: PoolId,
+    ) -> Result<PoolBlockStats, PropertyQueryError> {
+        let blocks = self.chainstate_ref.get_pool_blocks(pool_id)?;
+        let total_reward = blocks
+            .iter()
+            .map(|record| record.reward())
+            .sum::<Option<Amount>>()
+            .ok_or(PropertyQueryError::PoolBlockRewardOverflow(pool_id))?;
+
+        Ok(PoolBlockStats {
+            block_count: blocks.len() as u64,
+            total_reward,
+        })
+    }
+
+    pub fn get_chain_tips(&self) -> Result<Vec<ChainTip>, PropertyQueryError> {
+        self.chainstate_ref.get_chain_tips()
+    }
+
     pub fn get_block_height_in_main_chain(
         &self,
         id: &Id<GenBlock>,
@@ -381,6 +715,13 @@ impl<'a, S: BlockchainStorageRead, V: TransactionVerificationStrategy> Chainstat
         self.chainstate_ref.get_token_id(tx_id)
     }
 
+    pub fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, PropertyQueryError> {
+        self.chainstate_ref.get_token_ids_by_ticker(ticker)
+    }
+
     pub fn get_mainchain_blocks_list(&self) -> Result<Vec<Id<Block>>, PropertyQueryError> {
         self.chainstate_ref.get_mainchain_blocks_list()
     }
@@ -0,0 +1,131 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::VecDeque, time::Duration};
+
+use common::{
+    primitives::{per_thousand::PerThousand, time::Time},
+    Uint256,
+};
+
+/// The number of recently processed blocks used to estimate the block processing rate.
+const BLOCK_PROCESSING_RATE_WINDOW: usize = 1000;
+
+/// The number of the recent mainchain blocks used to estimate the average chain trust
+/// of the blocks that haven't been received yet.
+pub const CHAIN_TRUST_AVERAGING_WINDOW: u64 = 1000;
+
+/// The times at which the recent blocks were processed, used to estimate how long it will
+/// take to process the rest of the chain.
+#[derive(Default)]
+pub struct BlockProcessingRate {
+    processing_times: VecDeque<Time>,
+}
+
+impl BlockProcessingRate {
+    pub fn record(&mut self, now: Time) {
+        self.processing_times.push_back(now);
+
+        while self.processing_times.len() > BLOCK_PROCESSING_RATE_WINDOW {
+            self.processing_times.pop_front();
+        }
+    }
+
+    /// Estimate the time needed to process the specified number of blocks; `None` if there is
+    /// not enough data yet.
+    pub fn estimate_remaining_time(&self, remaining_blocks: u64) -> Option<Duration> {
+        let first = self.processing_times.front()?;
+        let last = self.processing_times.back()?;
+        let processed_blocks = self.processing_times.len() as u64 - 1;
+        let elapsed = last.saturating_sub(*first);
+
+        if processed_blocks == 0 || elapsed.is_zero() {
+            return None;
+        }
+
+        let remaining_millis = (elapsed.as_millis() as u64)
+            .saturating_mul(remaining_blocks)
+            .checked_div(processed_blocks)?;
+        Some(Duration::from_millis(remaining_millis))
+    }
+}
+
+/// Return `done / total` as a `PerThousand`, capped at 1000.
+pub fn chain_trust_fraction(done: Uint256, total: Uint256) -> PerThousand {
+    if done >= total {
+        return PerThousand::new(1000).expect("valid value");
+    }
+
+    // Make the values fit into u64, so that they can be divided without the risk of overflow
+    let shift = total.bits().saturating_sub(54);
+    let done = (done >> shift).low_u64();
+    let total = (total >> shift).low_u64();
+
+    let value = done.saturating_mul(1000).checked_div(total).unwrap_or(1000).min(1000);
+    PerThousand::new(value as u16).expect("value is at most 1000")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remaining_time() {
+        let mut rate = BlockProcessingRate::default();
+        assert_eq!(rate.estimate_remaining_time(100), None);
+
+        rate.record(Time::from_secs_since_epoch(10));
+        assert_eq!(rate.estimate_remaining_time(100), None);
+
+        // 10 blocks per second
+        for i in 1..=20 {
+            rate.record(Time::from_duration_since_epoch(Duration::from_millis(
+                10_000 + i * 100,
+            )));
+        }
+        assert_eq!(
+            rate.estimate_remaining_time(100),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(rate.estimate_remaining_time(0), Some(Duration::ZERO));
+
+        // Only the recent blocks are taken into account
+        for i in 1..=BLOCK_PROCESSING_RATE_WINDOW as u64 {
+            rate.record(Time::from_secs_since_epoch(100 + i));
+        }
+        assert_eq!(
+            rate.estimate_remaining_time(100),
+            Some(Duration::from_secs(100))
+        );
+    }
+
+    #[test]
+    fn fraction() {
+        let fraction = |done: u64, total: u64| {
+            chain_trust_fraction(Uint256::from_u64(done), Uint256::from_u64(total)).value()
+        };
+
+        assert_eq!(fraction(0, 100), 0);
+        assert_eq!(fraction(25, 100), 250);
+        assert_eq!(fraction(100, 100), 1000);
+        assert_eq!(fraction(200, 100), 1000);
+        assert_eq!(fraction(0, 0), 1000);
+
+        let big = Uint256::MAX >> 1;
+        let half_big = big >> 1;
+        assert_eq!(chain_trust_fraction(half_big, big).value(), 499);
+        assert_eq!(chain_trust_fraction(Uint256::ZERO, big).value(), 0);
+    }
+}
@@ -27,9 +27,8 @@ use common::{
 };
 
 use crate::{
-    detail::query::locator_tip_distances,
     interface::chainstate_interface_impl::ChainstateInterfaceImpl,
-    DefaultTransactionVerificationStrategy,
+    DefaultTransactionVerificationStrategy, LocatorSchedule,
 };
 
 use super::*;
@@ -79,8 +78,22 @@ fn process_genesis_block() {
 
 #[test]
 fn locator_distances() {
-    let distances: Vec<i64> = locator_tip_distances().take(7).map(From::from).collect();
-    assert_eq!(distances, vec![0, 1, 2, 4, 8, 16, 32]);
+    let distances = |schedule: LocatorSchedule| -> Vec<i64> {
+        schedule.distances().take(7).map(From::from).collect()
+    };
+
+    assert_eq!(
+        distances(LocatorSchedule::default()),
+        vec![0, 1, 2, 4, 8, 16, 32]
+    );
+    assert_eq!(
+        distances(LocatorSchedule::new(4, 3)),
+        vec![0, 1, 2, 3, 9, 27, 81]
+    );
+    assert_eq!(
+        distances(LocatorSchedule::new(0, 1)),
+        vec![0, 1, 2, 3, 4, 5, 6]
+    );
 }
 
 #[test]
@@ -0,0 +1,140 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{signature::Signable, Block, OutPointSourceId, TxInput, TxOutput, UtxoOutPoint},
+    primitives::{BlockHeight, Id, Idable},
+};
+use utxo::UtxosBlockUndo;
+
+/// A single utxo that was either created or spent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoDiffEntry {
+    pub outpoint: UtxoOutPoint,
+    pub output: TxOutput,
+}
+
+/// Changes that a mainchain block has made to the utxo set.
+///
+/// Outputs that were created and spent in the same block appear in both lists, so the created
+/// utxos must be applied before the spent ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockUtxoDiff {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    pub created: Vec<UtxoDiffEntry>,
+    pub spent: Vec<UtxoDiffEntry>,
+}
+
+impl BlockUtxoDiff {
+    /// Build the diff from the block itself (for the created utxos) and its undo data
+    /// (for the spent ones).
+    pub fn from_block_and_undo(
+        block: &Block,
+        block_height: BlockHeight,
+        undo: UtxosBlockUndo,
+    ) -> Self {
+        let block_id = block.get_id();
+        let undo = undo.consume();
+
+        let reward_source = OutPointSourceId::BlockReward(block_id.into());
+        let reward_created =
+            block
+                .block_reward()
+                .outputs()
+                .iter()
+                .enumerate()
+                .map(|(idx, output)| UtxoDiffEntry {
+                    outpoint: UtxoOutPoint::new(reward_source.clone(), idx as u32),
+                    output: output.clone(),
+                });
+
+        let tx_created = block.transactions().iter().flat_map(|tx| {
+            let source = OutPointSourceId::Transaction(tx.transaction().get_id());
+            tx.outputs()
+                .iter()
+                .enumerate()
+                .filter(|(_, output)| utxo::should_include_in_utxo_set(output))
+                .map(move |(idx, output)| UtxoDiffEntry {
+                    outpoint: UtxoOutPoint::new(source.clone(), idx as u32),
+                    output: output.clone(),
+                })
+        });
+
+        let created = reward_created.chain(tx_created).collect();
+
+        // The reward undo only contains the utxos spent by utxo inputs, in the input order.
+        let reward_transactable = block.block_reward_transactable();
+        let reward_spent = utxo_outpoints(reward_transactable.inputs().unwrap_or_default())
+            .zip(undo.reward_undo.map(|undo| undo.into_inner()).unwrap_or_default())
+            .map(|(outpoint, utxo)| UtxoDiffEntry {
+                outpoint: outpoint.clone(),
+                output: utxo.take_output(),
+            });
+
+        // Tx undos have an entry for each input, which is `None` for non-utxo inputs.
+        let mut tx_undos = undo.tx_undos;
+        let tx_spent = block.transactions().iter().flat_map(|tx| {
+            let utxos = tx_undos
+                .remove(&tx.transaction().get_id())
+                .map(|undo| undo.into_inner())
+                .unwrap_or_default();
+            tx.inputs()
+                .iter()
+                .zip(utxos)
+                .filter_map(|(input, utxo)| match (input, utxo) {
+                    (TxInput::Utxo(outpoint), Some(utxo)) => Some(UtxoDiffEntry {
+                        outpoint: outpoint.clone(),
+                        output: utxo.take_output(),
+                    }),
+                    (TxInput::Utxo(_), None)
+                    | (TxInput::Account(..), _)
+                    | (TxInput::AccountCommand(..), _)
+                    | (TxInput::OrderAccountCommand(..), _) => None,
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let spent = reward_spent.chain(tx_spent).collect();
+
+        Self {
+            block_id,
+            block_height,
+            created,
+            spent,
+        }
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.created.len() + self.spent.len()
+    }
+}
+
+/// A bounded part of the utxo diff for a range of heights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoDiffChunk {
+    pub blocks: Vec<BlockUtxoDiff>,
+    /// The height to continue from, if the requested range hasn't been fully covered.
+    pub next_height: Option<BlockHeight>,
+}
+
+fn utxo_outpoints(inputs: &[TxInput]) -> impl Iterator<Item = &UtxoOutPoint> {
+    inputs.iter().filter_map(|input| match input {
+        TxInput::Utxo(outpoint) => Some(outpoint),
+        TxInput::Account(..) | TxInput::AccountCommand(..) | TxInput::OrderAccountCommand(..) => {
+            None
+        }
+    })
+}
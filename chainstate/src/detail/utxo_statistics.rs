@@ -0,0 +1,106 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use common::{
+    chain::{output_value::OutputValue, GenBlock, TxOutput, UtxoOutPoint},
+    primitives::{id::hash_encoded, Amount, BlockHeight, Id, H256},
+    Uint256,
+};
+use utxo::Utxo;
+
+/// Summary of the mainchain utxo set, which can be used to compare the state of different nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, rpc::description::HasValueHint)]
+pub struct UtxoSetStatistics {
+    pub best_block_id: Id<GenBlock>,
+    pub best_block_height: BlockHeight,
+    pub utxo_count: u64,
+    /// The total amount of coins in transfer, lock-then-transfer and htlc outputs
+    pub total_coins: Amount,
+    /// A non-cryptographic checksum of the utxo set: the sum (modulo 2^256) of the hashes of
+    /// the individual utxos.
+    ///
+    /// This doesn't depend on the order in which the utxos are visited and can be updated
+    /// incrementally. But anyone can craft a different utxo set with the same checksum, so it
+    /// must only be used to detect accidental state divergence, never as a commitment to
+    /// the utxo set.
+    pub checksum: H256,
+}
+
+/// Accumulates the statistics of a utxo set one utxo at a time.
+#[derive(Debug, Clone)]
+pub struct UtxoSetStatisticsBuilder {
+    utxo_count: u64,
+    total_coins: Option<Amount>,
+    checksum: Uint256,
+}
+
+impl UtxoSetStatisticsBuilder {
+    pub fn new() -> Self {
+        Self {
+            utxo_count: 0,
+            total_coins: Some(Amount::ZERO),
+            checksum: Uint256::ZERO,
+        }
+    }
+
+    pub fn add(&mut self, outpoint: &UtxoOutPoint, utxo: &Utxo) {
+        self.utxo_count += 1;
+        self.total_coins = self.total_coins.and_then(|total| total + coin_amount(utxo.output()));
+        self.checksum = self.checksum.wrapping_add(&hash_encoded(&(outpoint, utxo)).into());
+    }
+
+    /// Returns `None` if the total coin amount has overflowed.
+    pub fn build(
+        self,
+        best_block_id: Id<GenBlock>,
+        best_block_height: BlockHeight,
+    ) -> Option<UtxoSetStatistics> {
+        Some(UtxoSetStatistics {
+            best_block_id,
+            best_block_height,
+            utxo_count: self.utxo_count,
+            total_coins: self.total_coins?,
+            checksum: self.checksum.into(),
+        })
+    }
+}
+
+impl Default for UtxoSetStatisticsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn coin_amount(output: &TxOutput) -> Amount {
+    match output {
+        TxOutput::Transfer(value, _)
+        | TxOutput::LockThenTransfer(value, _, _)
+        | TxOutput::Htlc(value, _) => match value {
+            OutputValue::Coin(amount) => *amount,
+            OutputValue::TokenV0(_) | OutputValue::TokenV1(_, _) => Amount::ZERO,
+        },
+        TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_)
+        | TxOutput::CreateOrder(_) => Amount::ZERO,
+    }
+}
@@ -13,13 +13,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    sync::Arc,
+};
 
 use crate::{
-    detail::BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent,
-    NonZeroPoolBalances,
+    detail::BlockSource, BlockFeeCheckStats, BlockFilter, BootstrapImportProgress, ChainInfo,
+    ChainTip, ChainstateConfig, ChainstateError, ChainstateEvent, DbVerificationError,
+    DbVerificationLevel, DestinationBalance, FilteredBlock, FilteredChainEvent, IbdStatus,
+    NonZeroPoolBalances, SyncProgress, UtxoDiffChunk, UtxoSetStatistics,
+};
+use chainstate_types::{
+    pos_randomness::PoSRandomness, BlockFilterCommitment, BlockIndex, ChainSplitRecord,
+    CompactBlockFilter, EpochData, GenBlockIndex, Locator, LocatorSchedule, PoolBlockRecord,
+    PoolBlockStats,
 };
-use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, Locator};
 use common::{
     chain::{
         block::{
@@ -27,10 +37,10 @@ use common::{
             GenBlock,
         },
         tokens::{RPCTokenInfo, TokenAuxiliaryData, TokenId},
-        AccountNonce, AccountType, ChainConfig, DelegationId, OrderId, PoolId, RpcOrderInfo,
-        Transaction, TxInput, UtxoOutPoint,
+        AccountNonce, AccountType, ChainConfig, DelegationId, Destination, OrderId, PoolId,
+        RpcOrderInfo, Transaction, TxInput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Fee, Id},
 };
 use orders_accounting::OrderData;
 use pos_accounting::{DelegationData, PoolData};
@@ -44,6 +54,19 @@ pub trait ChainstateInterface: Send + Sync {
         handler: Arc<dyn Fn(ChainstateEvent) + Send + Sync>,
     );
     fn subscribe_to_rpc_events(&mut self) -> broadcaster::Receiver<ChainstateEvent>;
+    /// Same as `subscribe_to_rpc_events`, but at most `capacity` events are queued for
+    /// the subscriber, the rest is handled according to `overflow_policy`.
+    fn subscribe_to_bounded_rpc_events(
+        &mut self,
+        capacity: usize,
+        overflow_policy: broadcaster::OverflowPolicy,
+    ) -> broadcaster::BoundedReceiver<ChainstateEvent>;
+    /// Subscribe to the main chain blocks that contain transactions relevant to the filter;
+    /// only the relevant parts of the blocks are delivered.
+    fn subscribe_to_filtered_events(
+        &mut self,
+        filter: BlockFilter,
+    ) -> broadcaster::Receiver<FilteredChainEvent>;
     /// Process the given block. If a reorg occurs, return the block index of the new tip.
     /// Otherwise return None.
     fn process_block(
@@ -72,22 +95,59 @@ pub trait ChainstateInterface: Send + Sync {
         block_id: &Id<GenBlock>,
     ) -> Result<Option<BlockHeight>, ChainstateError>;
     fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError>;
+    /// Returns the height and id of the current tip, both referring to the same tip.
+    /// This is cached and doesn't normally touch the storage, so it's cheap to call.
+    fn get_best_block_height_and_id(&self) -> Result<(BlockHeight, Id<GenBlock>), ChainstateError>;
     fn get_best_block_header(&self) -> Result<SignedBlockHeader, ChainstateError>;
     fn get_block_id_from_height(
         &self,
         height: &BlockHeight,
     ) -> Result<Option<Id<GenBlock>>, ChainstateError>;
     fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError>;
+    /// Return the blocks with the specified ids in the same order (`None` for the missing ones),
+    /// reading all of them in one storage transaction.
+    ///
+    /// The reading stops once the total encoded size of the returned blocks would exceed
+    /// `size_limit`, so fewer blocks than requested may be returned; the first one is returned
+    /// even if it exceeds the limit by itself.
+    fn get_blocks(
+        &self,
+        ids: Vec<Id<Block>>,
+        size_limit: usize,
+    ) -> Result<Vec<Option<Block>>, ChainstateError>;
     fn get_mainchain_blocks(
         &self,
         from: BlockHeight,
         max_count: usize,
     ) -> Result<Vec<Block>, ChainstateError>;
+    /// Same as `get_mainchain_blocks`, but only the blocks relevant to the filter are returned,
+    /// and only their relevant parts. This is the rescan counterpart of
+    /// `subscribe_to_filtered_events`.
+    fn get_filtered_mainchain_blocks(
+        &self,
+        filter: &BlockFilter,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<FilteredBlock>, ChainstateError>;
     fn get_block_header(
         &self,
         block_id: Id<Block>,
     ) -> Result<Option<SignedBlockHeader>, ChainstateError>;
 
+    /// Returns the utxos created and spent by mainchain blocks in `from_height..to_height`,
+    /// built from the blocks' undo data.
+    ///
+    /// The result is bounded by `max_entries` (but always contains at least one block if
+    /// the range is non-empty); if the range hasn't been covered fully, `next_height` of
+    /// the returned chunk specifies where to continue from.
+    /// If the undo data of the blocks has been pruned, an error is returned.
+    fn get_utxo_diff(
+        &self,
+        from_height: BlockHeight,
+        to_height: BlockHeight,
+        max_entries: NonZeroUsize,
+    ) -> Result<UtxoDiffChunk, ChainstateError>;
+
     /// Returns a list of block headers whose heights distances increase exponentially starting
     /// from the current tip.
     ///
@@ -98,6 +158,14 @@ pub trait ChainstateInterface: Send + Sync {
     /// Returns a locator starting from the specified height.
     fn get_locator_from_height(&self, height: BlockHeight) -> Result<Locator, ChainstateError>;
 
+    /// Returns a locator starting from the specified block, which doesn't have to be
+    /// on the main chain; the schedule determines which of its ancestors are included.
+    fn get_locator_from_block(
+        &self,
+        block_id: &Id<GenBlock>,
+        schedule: LocatorSchedule,
+    ) -> Result<Locator, ChainstateError>;
+
     /// Returns mainchain block ids with heights in the range start_height..end_height using
     /// the given step;
     fn get_block_ids_as_checkpoints(
@@ -201,6 +269,10 @@ pub trait ChainstateInterface: Send + Sync {
     /// Returns `None` if no epoch data was found.
     fn get_epoch_data(&self, epoch_index: u64) -> Result<Option<EpochData>, ChainstateError>;
 
+    /// Returns the randomness of the sealed epoch that a block on top of the current tip
+    /// has to use for staking.
+    fn get_sealed_epoch_randomness(&self) -> Result<PoSRandomness, ChainstateError>;
+
     /// Returns token info by `token_id`.
     fn get_token_info_for_rpc(
         &self,
@@ -214,6 +286,11 @@ pub trait ChainstateInterface: Send + Sync {
         &self,
         tx_id: &Id<Transaction>,
     ) -> Result<Option<TokenId>, ChainstateError>;
+    /// Return the id of the first token that was issued with the given ticker.
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, ChainstateError>;
     fn get_token_data(
         &self,
         id: &TokenId,
@@ -247,9 +324,14 @@ pub trait ChainstateInterface: Send + Sync {
     fn get_block_id_tree_as_list(&self) -> Result<Vec<Id<Block>>, ChainstateError>;
 
     /// Imports a bootstrap file exported with `export_bootstrap_stream`.
+    ///
+    /// The progress is reported after each imported chunk of blocks. Blocks that are already
+    /// stored are skipped, so an interrupted import can be resumed by importing the same
+    /// file again.
     fn import_bootstrap_stream<'a>(
         &mut self,
         reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+        progress_callback: Box<dyn FnMut(BootstrapImportProgress) + Send + 'a>,
     ) -> Result<(), ChainstateError>;
 
     /// Writes the blocks of the blockchain into a stream that's meant to go to a file.
@@ -263,9 +345,92 @@ pub trait ChainstateInterface: Send + Sync {
     /// Returns the UTXO for a specified OutPoint.
     fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError>;
 
+    // The following utxo queries look at the mainchain utxo set only; transactions in the mempool
+    // are not taken into account. Each call is answered from a single storage snapshot, i.e.
+    // against one particular tip, but the tip may move between two calls. Callers that combine
+    // results of several calls should compare `get_best_block_id()` before and after.
+
+    /// For each of the specified outpoints, returns whether it's unspent at the current tip.
+    /// All the answers refer to the same tip.
+    fn outpoints_available(&self, outpoints: &[UtxoOutPoint])
+        -> Result<Vec<bool>, ChainstateError>;
+
+    /// Returns the number of utxos, the total amount of coins and the hash of the whole utxo set
+    /// at the current tip. This goes over the entire utxo set, so it's expensive.
+    fn get_utxo_set_statistics(&self) -> Result<UtxoSetStatistics, ChainstateError>;
+
+    /// Returns the coins and tokens owned by the destination at the specified mainchain height.
+    /// This goes over the entire utxo set and then rolls it back block by block to the height,
+    /// so it's expensive. The height can be at most `max_depth_for_reorg` blocks below the tip.
+    fn get_balance_at_height(
+        &self,
+        destination: &Destination,
+        height: BlockHeight,
+    ) -> Result<DestinationBalance, ChainstateError>;
+
+    /// Check the database invariants of the given level and return the found inconsistencies;
+    /// an empty result means that the database is consistent.
+    /// For the utxo-related levels, the utxo changes of the last `depth` mainchain blocks are
+    /// re-derived from their undo data. This reads large parts of the database, so it's expensive.
+    fn verify_database(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, ChainstateError>;
+
+    /// Returns the recorded chain splits, i.e. the competing tips within
+    /// [crate::CHAIN_SPLIT_RECORD_MAX_DEPTH] blocks of each other that this node has observed.
+    fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, ChainstateError>;
+
+    /// Returns the compact filter of the block, if the block filter index has it.
+    fn get_block_filter(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<CompactBlockFilter>, ChainstateError>;
+
+    /// Returns the commitment to the compact filter of the block, if the block filter index
+    /// has it.
+    fn get_block_filter_commitment(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockFilterCommitment>, ChainstateError>;
+
+    /// Returns the main chain blocks produced by the pool starting at the specified height,
+    /// at most `max_count` of them, ordered by height.
+    fn get_blocks_by_pool(
+        &self,
+        pool_id: PoolId,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<PoolBlockRecord>, ChainstateError>;
+
+    /// Returns the number of main chain blocks produced by the pool and their total reward.
+    fn get_pool_block_stats(&self, pool_id: PoolId) -> Result<PoolBlockStats, ChainstateError>;
+
+    /// Returns the tip of the main chain followed by the tips of all known branches.
+    ///
+    /// Note that this iterates over all block indices in the DB, so it may be slow.
+    fn get_chain_tips(&self) -> Result<Vec<ChainTip>, ChainstateError>;
+
     /// Returns true if the initial block download isn't finished yet.
     fn is_initial_block_download(&self) -> bool;
 
+    /// Returns the progress of the initial block download, including the estimated height
+    /// of the best chain and the estimated verification progress.
+    fn ibd_status(&self) -> Result<IbdStatus, ChainstateError>;
+
+    /// Returns the sync progress in terms of the chain trust, together with the estimated time
+    /// needed to process the missing blocks.
+    fn sync_progress(&self) -> Result<SyncProgress, ChainstateError>;
+
+    /// Remember the fees predicted for a locally produced block. Once the block is connected,
+    /// the prediction is compared with the fees computed by the transaction verifier.
+    fn add_block_fee_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee);
+
+    /// Returns the results of comparing the predicted fees of locally produced blocks
+    /// with the actual ones.
+    fn get_block_fee_check_stats(&self) -> BlockFeeCheckStats;
+
     /// Check whether stake pool with given ID exists.
     fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError>;
 
@@ -13,7 +13,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    sync::Arc,
+};
 
 use crate::{
     detail::{
@@ -21,24 +25,29 @@ use crate::{
         block_checking::BlockChecker,
         block_invalidation::BlockInvalidator,
         bootstrap::{export_bootstrap_stream, import_bootstrap_stream},
-        calculate_median_time_past,
         tx_verification_strategy::TransactionVerificationStrategy,
         BlockSource, OrphanBlocksRef, CHAINSTATE_TRACING_TARGET_VERBOSE_BLOCK_IDS,
     },
-    ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, ChainstateInterface, Locator,
-    NonZeroPoolBalances,
+    BlockFeeCheckStats, BlockFilter, BootstrapImportProgress, ChainInfo, ChainTip,
+    ChainstateConfig, ChainstateError, ChainstateEvent, ChainstateInterface, DbVerificationError,
+    DbVerificationLevel, DestinationBalance, FilteredBlock, FilteredChainEvent, IbdStatus, Locator,
+    NonZeroPoolBalances, SyncProgress, UtxoDiffChunk, UtxoSetStatistics,
 };
 use chainstate_storage::BlockchainStorage;
-use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, PropertyQueryError};
+use chainstate_types::{
+    pos_randomness::PoSRandomness, BlockFilterCommitment, BlockIndex, ChainSplitRecord,
+    CompactBlockFilter, EpochData, GenBlockIndex, LocatorSchedule, PoolBlockRecord, PoolBlockStats,
+    PropertyQueryError,
+};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, Block, BlockReward, GenBlock},
         config::ChainConfig,
         tokens::{RPCTokenInfo, TokenAuxiliaryData, TokenId},
-        AccountNonce, AccountType, DelegationId, OrderId, PoolId, RpcOrderInfo, Transaction,
-        TxInput, TxOutput, UtxoOutPoint,
+        AccountNonce, AccountType, DelegationId, Destination, OrderId, PoolId, RpcOrderInfo,
+        Transaction, TxInput, TxOutput, UtxoOutPoint,
     },
-    primitives::{id::WithId, Amount, BlockHeight, Id, Idable},
+    primitives::{id::WithId, Amount, BlockHeight, Fee, Id, Idable},
 };
 use orders_accounting::OrderData;
 use pos_accounting::{DelegationData, PoSAccountingStorageRead, PoolData};
@@ -71,6 +80,23 @@ where
         self.chainstate.subscribe_to_event_broadcast()
     }
 
+    #[tracing::instrument(skip_all)]
+    fn subscribe_to_bounded_rpc_events(
+        &mut self,
+        capacity: usize,
+        overflow_policy: broadcaster::OverflowPolicy,
+    ) -> broadcaster::BoundedReceiver<ChainstateEvent> {
+        self.chainstate.subscribe_to_bounded_event_broadcast(capacity, overflow_policy)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn subscribe_to_filtered_events(
+        &mut self,
+        filter: BlockFilter,
+    ) -> broadcaster::Receiver<FilteredChainEvent> {
+        self.chainstate.subscribe_to_filtered_events(filter)
+    }
+
     // Note: in this and some other functions below (in particular, in those that are called from
     // p2p when processing blocks coming from peers) we add an additional DEBUG span that prints
     // the block via `format!("{:x}")`. This is because the other span prints the id via Display
@@ -148,11 +174,11 @@ where
 
     #[tracing::instrument(skip_all)]
     fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError> {
-        self.chainstate
-            .query()
-            .map_err(ChainstateError::from)?
-            .get_best_block_id()
-            .map_err(ChainstateError::FailedToReadProperty)
+        let info = self
+            .chainstate
+            .get_best_block_info()
+            .map_err(ChainstateError::FailedToReadProperty)?;
+        Ok(info.id)
     }
 
     #[tracing::instrument(skip_all, fields(id = %block_id))]
@@ -206,6 +232,19 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip_all, fields(count = ids.len(), size_limit = size_limit))]
+    fn get_blocks(
+        &self,
+        ids: Vec<Id<Block>>,
+        size_limit: usize,
+    ) -> Result<Vec<Option<Block>>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_blocks(&ids, size_limit)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     #[tracing::instrument(skip_all, fields(from = %from, max_count = max_count))]
     fn get_mainchain_blocks(
         &self,
@@ -219,6 +258,34 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip_all, fields(from = %from, max_count = max_count))]
+    fn get_filtered_mainchain_blocks(
+        &self,
+        filter: &BlockFilter,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<FilteredBlock>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_filtered_mainchain_blocks(filter, from, max_count)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(from = %from_height, to = %to_height))]
+    fn get_utxo_diff(
+        &self,
+        from_height: BlockHeight,
+        to_height: BlockHeight,
+        max_entries: NonZeroUsize,
+    ) -> Result<UtxoDiffChunk, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_utxo_diff(from_height, to_height, max_entries)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     #[tracing::instrument(skip_all, fields(id = %block_id))]
     fn get_block_header(
         &self,
@@ -249,6 +316,19 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip_all, fields(id = %block_id))]
+    fn get_locator_from_block(
+        &self,
+        block_id: &Id<GenBlock>,
+        schedule: LocatorSchedule,
+    ) -> Result<Locator, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_locator_from_block(block_id, &schedule)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     #[tracing::instrument(skip(self))]
     fn get_block_ids_as_checkpoints(
         &self,
@@ -313,13 +393,20 @@ where
 
     #[tracing::instrument(skip_all)]
     fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError> {
-        let best_block_index = self
+        let info = self
             .chainstate
-            .query()
-            .map_err(ChainstateError::from)?
-            .get_best_block_index()
+            .get_best_block_info()
             .map_err(ChainstateError::FailedToReadProperty)?;
-        Ok(best_block_index.block_height())
+        Ok(info.height)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_best_block_height_and_id(&self) -> Result<(BlockHeight, Id<GenBlock>), ChainstateError> {
+        let info = self
+            .chainstate
+            .get_best_block_info()
+            .map_err(ChainstateError::FailedToReadProperty)?;
+        Ok((info.height, info.id))
     }
 
     #[tracing::instrument(skip_all)]
@@ -415,7 +502,7 @@ where
     ) -> Result<common::chain::block::timestamp::BlockTimestamp, ChainstateError> {
         let err_f = |e| ChainstateError::FailedToReadProperty(PropertyQueryError::from(e));
         let dbtx = self.chainstate.make_db_tx_ro().map_err(err_f)?;
-        Ok(calculate_median_time_past(&dbtx, starting_block))
+        Ok(dbtx.median_time_past(starting_block))
     }
 
     #[tracing::instrument(skip_all, fields(id = %block_id))]
@@ -511,6 +598,24 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip(self))]
+    fn get_sealed_epoch_randomness(&self) -> Result<PoSRandomness, ChainstateError> {
+        let chain_config = self.chainstate.chain_config();
+        let next_height = self.get_best_block_height()?.next_height();
+
+        // Same as in consensus, the genesis randomness is used until the first epoch is sealed.
+        let epoch_data = chain_config
+            .sealed_epoch_index(&next_height)
+            .map(|epoch_index| self.get_epoch_data(epoch_index))
+            .transpose()?
+            .flatten();
+
+        Ok(epoch_data.map_or_else(
+            || PoSRandomness::at_genesis(chain_config),
+            |epoch_data| *epoch_data.randomness(),
+        ))
+    }
+
     #[tracing::instrument(skip_all, fields(token_id = %token_id))]
     fn get_token_info_for_rpc(
         &self,
@@ -547,6 +652,18 @@ where
             .map_err(ChainstateError::FailedToReadProperty)
     }
 
+    #[tracing::instrument(skip_all)]
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_token_ids_by_ticker(ticker)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     #[tracing::instrument(skip_all)]
     fn get_inputs_outpoints_coin_amount(
         &self,
@@ -600,21 +717,21 @@ where
     fn import_bootstrap_stream<'a>(
         &mut self,
         reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+        mut progress_callback: Box<dyn FnMut(BootstrapImportProgress) + Send + 'a>,
     ) -> Result<(), ChainstateError> {
         let magic_bytes = *self.chainstate.chain_config().magic_bytes();
 
         let mut reader = reader;
 
-        // We clone because borrowing with the closure below prevents immutable borrows,
-        // and the cost of cloning is small compared to the bootstrapping
+        // We clone because the chainstate is borrowed mutably below, and the cost of cloning
+        // is small compared to the bootstrapping
         let chainstate_config = self.chainstate.chainstate_config().clone();
 
-        let mut block_processor = |block| self.chainstate.process_block(block, BlockSource::Local);
-
         import_bootstrap_stream(
             &magic_bytes.bytes(),
             &mut reader,
-            &mut block_processor,
+            &mut self.chainstate,
+            &mut *progress_callback,
             &chainstate_config,
         )?;
 
@@ -650,10 +767,150 @@ where
             .map_err(|e| ChainstateError::FailedToReadProperty(e.into()))
     }
 
+    #[tracing::instrument(skip_all)]
+    fn outpoints_available(
+        &self,
+        outpoints: &[UtxoOutPoint],
+    ) -> Result<Vec<bool>, ChainstateError> {
+        let chainstate_ref = self
+            .chainstate
+            .make_db_tx_ro()
+            .map_err(|e| ChainstateError::FailedToReadProperty(e.into()))?;
+        let utxo_view = chainstate_ref.make_utxo_view();
+        outpoints
+            .iter()
+            .map(|outpoint| {
+                utxo_view
+                    .has_utxo(outpoint)
+                    .map_err(|e| ChainstateError::FailedToReadProperty(e.into()))
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_utxo_set_statistics(&self) -> Result<UtxoSetStatistics, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_utxo_set_statistics()
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = %height))]
+    fn get_balance_at_height(
+        &self,
+        destination: &Destination,
+        height: BlockHeight,
+    ) -> Result<DestinationBalance, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_balance_at_height(destination, height)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn verify_database(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .verify_database(level, depth)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_chain_split_records()
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(id = %block_id))]
+    fn get_block_filter(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<CompactBlockFilter>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_block_filter(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(id = %block_id))]
+    fn get_block_filter_commitment(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockFilterCommitment>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_block_filter_commitment(block_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(pool_id = %pool_id))]
+    fn get_blocks_by_pool(
+        &self,
+        pool_id: PoolId,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<PoolBlockRecord>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_blocks_by_pool(pool_id, from, max_count)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(pool_id = %pool_id))]
+    fn get_pool_block_stats(&self, pool_id: PoolId) -> Result<PoolBlockStats, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_pool_block_stats(pool_id)
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_chain_tips(&self) -> Result<Vec<ChainTip>, ChainstateError> {
+        self.chainstate
+            .query()
+            .map_err(ChainstateError::from)?
+            .get_chain_tips()
+            .map_err(ChainstateError::FailedToReadProperty)
+    }
+
     fn is_initial_block_download(&self) -> bool {
         self.chainstate.is_initial_block_download()
     }
 
+    #[tracing::instrument(skip_all)]
+    fn ibd_status(&self) -> Result<IbdStatus, ChainstateError> {
+        self.chainstate.ibd_status().map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn sync_progress(&self) -> Result<SyncProgress, ChainstateError> {
+        self.chainstate.sync_progress().map_err(ChainstateError::FailedToReadProperty)
+    }
+
+    #[tracing::instrument(skip_all, fields(id = %block_id))]
+    fn add_block_fee_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee) {
+        self.chainstate.add_block_fee_prediction(block_id, predicted_fees)
+    }
+
+    #[tracing::instrument(skip_all)]
+    fn get_block_fee_check_stats(&self) -> BlockFeeCheckStats {
+        self.chainstate.block_fee_check_stats()
+    }
+
     #[tracing::instrument(skip_all, fields(pool_id = %pool_id))]
     fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError> {
         self.get_stake_pool_data(pool_id).map(|v| v.is_some())
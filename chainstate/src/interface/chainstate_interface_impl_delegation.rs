@@ -14,22 +14,26 @@
 // limitations under the License.
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     num::NonZeroUsize,
     ops::{Deref, DerefMut},
     sync::Arc,
 };
 
-use chainstate_types::{BlockIndex, EpochData, GenBlockIndex, Locator};
+use chainstate_types::{
+    pos_randomness::PoSRandomness, BlockFilterCommitment, BlockIndex, ChainSplitRecord,
+    CompactBlockFilter, EpochData, GenBlockIndex, Locator, LocatorSchedule, PoolBlockRecord,
+    PoolBlockStats,
+};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, timestamp::BlockTimestamp, BlockReward},
         config::ChainConfig,
         tokens::{RPCTokenInfo, TokenAuxiliaryData, TokenId},
-        AccountNonce, AccountType, Block, DelegationId, GenBlock, OrderId, PoolId, RpcOrderInfo,
-        Transaction, TxInput, UtxoOutPoint,
+        AccountNonce, AccountType, Block, DelegationId, Destination, GenBlock, OrderId, PoolId,
+        RpcOrderInfo, Transaction, TxInput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Fee, Id},
 };
 use orders_accounting::OrderData;
 use pos_accounting::{DelegationData, PoolData};
@@ -38,8 +42,11 @@ use utils_networking::broadcaster;
 use utxo::Utxo;
 
 use crate::{
-    chainstate_interface::ChainstateInterface, BlockSource, ChainInfo, ChainstateConfig,
-    ChainstateError, ChainstateEvent, NonZeroPoolBalances,
+    chainstate_interface::ChainstateInterface, BlockFeeCheckStats, BlockFilter, BlockSource,
+    BootstrapImportProgress, ChainInfo, ChainTip, ChainstateConfig, ChainstateError,
+    ChainstateEvent, DbVerificationError, DbVerificationLevel, DestinationBalance, FilteredBlock,
+    FilteredChainEvent, IbdStatus, NonZeroPoolBalances, SyncProgress, UtxoDiffChunk,
+    UtxoSetStatistics,
 };
 
 impl<T: Deref + DerefMut + Send + Sync> ChainstateInterface for T
@@ -57,6 +64,21 @@ where
         self.deref_mut().subscribe_to_rpc_events()
     }
 
+    fn subscribe_to_bounded_rpc_events(
+        &mut self,
+        capacity: usize,
+        overflow_policy: broadcaster::OverflowPolicy,
+    ) -> broadcaster::BoundedReceiver<ChainstateEvent> {
+        self.deref_mut().subscribe_to_bounded_rpc_events(capacity, overflow_policy)
+    }
+
+    fn subscribe_to_filtered_events(
+        &mut self,
+        filter: BlockFilter,
+    ) -> broadcaster::Receiver<FilteredChainEvent> {
+        self.deref_mut().subscribe_to_filtered_events(filter)
+    }
+
     fn process_block(
         &mut self,
         block: Block,
@@ -107,6 +129,10 @@ where
         self.deref().get_best_block_height()
     }
 
+    fn get_best_block_height_and_id(&self) -> Result<(BlockHeight, Id<GenBlock>), ChainstateError> {
+        self.deref().get_best_block_height_and_id()
+    }
+
     fn get_best_block_header(&self) -> Result<SignedBlockHeader, ChainstateError> {
         self.deref().get_best_block_header()
     }
@@ -122,6 +148,14 @@ where
         self.deref().get_block(block_id)
     }
 
+    fn get_blocks(
+        &self,
+        ids: Vec<Id<Block>>,
+        size_limit: usize,
+    ) -> Result<Vec<Option<Block>>, ChainstateError> {
+        self.deref().get_blocks(ids, size_limit)
+    }
+
     fn get_mainchain_blocks(
         &self,
         from: BlockHeight,
@@ -130,6 +164,24 @@ where
         self.deref().get_mainchain_blocks(from, max_count)
     }
 
+    fn get_filtered_mainchain_blocks(
+        &self,
+        filter: &BlockFilter,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<FilteredBlock>, ChainstateError> {
+        self.deref().get_filtered_mainchain_blocks(filter, from, max_count)
+    }
+
+    fn get_utxo_diff(
+        &self,
+        from_height: BlockHeight,
+        to_height: BlockHeight,
+        max_entries: NonZeroUsize,
+    ) -> Result<UtxoDiffChunk, ChainstateError> {
+        self.deref().get_utxo_diff(from_height, to_height, max_entries)
+    }
+
     fn get_locator(&self) -> Result<Locator, ChainstateError> {
         self.deref().get_locator()
     }
@@ -138,6 +190,14 @@ where
         self.deref().get_locator_from_height(height)
     }
 
+    fn get_locator_from_block(
+        &self,
+        block_id: &Id<GenBlock>,
+        schedule: LocatorSchedule,
+    ) -> Result<Locator, ChainstateError> {
+        self.deref().get_locator_from_block(block_id, schedule)
+    }
+
     fn get_block_ids_as_checkpoints(
         &self,
         start_height: BlockHeight,
@@ -269,6 +329,10 @@ where
         self.deref().get_epoch_data(epoch_index)
     }
 
+    fn get_sealed_epoch_randomness(&self) -> Result<PoSRandomness, ChainstateError> {
+        self.deref().get_sealed_epoch_randomness()
+    }
+
     fn get_token_info_for_rpc(
         &self,
         token_id: TokenId,
@@ -290,6 +354,13 @@ where
         self.deref().get_token_id_from_issuance_tx(tx_id)
     }
 
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, ChainstateError> {
+        self.deref().get_token_ids_by_ticker(ticker)
+    }
+
     fn get_inputs_outpoints_coin_amount(
         &self,
         inputs: &[TxInput],
@@ -308,8 +379,9 @@ where
     fn import_bootstrap_stream<'a>(
         &mut self,
         reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+        progress_callback: Box<dyn FnMut(BootstrapImportProgress) + Send + 'a>,
     ) -> Result<(), ChainstateError> {
-        self.deref_mut().import_bootstrap_stream(reader)
+        self.deref_mut().import_bootstrap_stream(reader, progress_callback)
     }
 
     fn export_bootstrap_stream<'a>(
@@ -324,10 +396,88 @@ where
         self.deref().utxo(outpoint)
     }
 
+    fn outpoints_available(
+        &self,
+        outpoints: &[UtxoOutPoint],
+    ) -> Result<Vec<bool>, ChainstateError> {
+        self.deref().outpoints_available(outpoints)
+    }
+
+    fn get_utxo_set_statistics(&self) -> Result<UtxoSetStatistics, ChainstateError> {
+        self.deref().get_utxo_set_statistics()
+    }
+
+    fn get_balance_at_height(
+        &self,
+        destination: &Destination,
+        height: BlockHeight,
+    ) -> Result<DestinationBalance, ChainstateError> {
+        self.deref().get_balance_at_height(destination, height)
+    }
+
+    fn verify_database(
+        &self,
+        level: DbVerificationLevel,
+        depth: usize,
+    ) -> Result<Vec<DbVerificationError>, ChainstateError> {
+        self.deref().verify_database(level, depth)
+    }
+
+    fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, ChainstateError> {
+        self.deref().get_chain_split_records()
+    }
+
+    fn get_block_filter(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<CompactBlockFilter>, ChainstateError> {
+        self.deref().get_block_filter(block_id)
+    }
+
+    fn get_block_filter_commitment(
+        &self,
+        block_id: &Id<Block>,
+    ) -> Result<Option<BlockFilterCommitment>, ChainstateError> {
+        self.deref().get_block_filter_commitment(block_id)
+    }
+
+    fn get_blocks_by_pool(
+        &self,
+        pool_id: PoolId,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> Result<Vec<PoolBlockRecord>, ChainstateError> {
+        self.deref().get_blocks_by_pool(pool_id, from, max_count)
+    }
+
+    fn get_pool_block_stats(&self, pool_id: PoolId) -> Result<PoolBlockStats, ChainstateError> {
+        self.deref().get_pool_block_stats(pool_id)
+    }
+
+    fn get_chain_tips(&self) -> Result<Vec<ChainTip>, ChainstateError> {
+        self.deref().get_chain_tips()
+    }
+
     fn is_initial_block_download(&self) -> bool {
         self.deref().is_initial_block_download()
     }
 
+    fn ibd_status(&self) -> Result<IbdStatus, ChainstateError> {
+        self.deref().ibd_status()
+    }
+
+    fn sync_progress(&self) -> Result<SyncProgress, ChainstateError> {
+        self.deref().sync_progress()
+    }
+
+    fn add_block_fee_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee) {
+        self.deref_mut().add_block_fee_prediction(block_id, predicted_fees)
+    }
+
+    fn get_block_fee_check_stats(&self) -> BlockFeeCheckStats {
+        self.deref().get_block_fee_check_stats()
+    }
+
     fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError> {
         self.deref().stake_pool_exists(pool_id)
     }
@@ -479,8 +629,15 @@ mod tests {
                 max_orphan_blocks: 0.into(),
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Default::default(),
+                fork_alert_depth: Default::default(),
+                max_block_index_cache_memory: Default::default(),
+                max_utxo_cache_memory: Default::default(),
+                max_orphan_blocks_memory: Default::default(),
+                max_cache_memory: Default::default(),
                 enable_heavy_checks: Some(true),
                 allow_checkpoints_mismatch: Default::default(),
+                enable_block_filter_index: Default::default(),
+                undo_data_retention_depth: Default::default(),
             };
             let chainstate_storage = Store::new_empty().unwrap();
 
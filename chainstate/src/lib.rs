@@ -28,22 +28,34 @@ use common::{
     primitives::{BlockHeight, Id},
     time_getter::TimeGetter,
 };
-use detail::{bootstrap::BootstrapError, Chainstate};
+use detail::Chainstate;
 use interface::chainstate_interface_impl;
 
 pub use crate::{
-    config::{ChainstateConfig, MaxTipAge},
+    config::{
+        CacheMemoryLimits, ChainstateConfig, ChainstateConfigError, ForkAlertDepth, MaxTipAge,
+    },
     detail::{
-        ban_score, block_invalidation::BlockInvalidatorError, calculate_median_time_past,
-        calculate_median_time_past_from_blocktimestamps, BlockError, BlockProcessingErrorClass,
-        BlockProcessingErrorClassification, BlockSource, ChainInfo, CheckBlockError,
-        CheckBlockTransactionsError, ConnectTransactionError, IOPolicyError, InitializationError,
-        Locator, NonZeroPoolBalances, OrphanCheckError, SpendStakeError,
-        StorageCompatibilityCheckError, TokenIssuanceError, TokensError,
-        TransactionVerifierStorageError, MEDIAN_TIME_SPAN,
+        ban_score,
+        block_invalidation::BlockInvalidatorError,
+        bootstrap::{BootstrapError, BootstrapImportProgress},
+        calculate_median_time_past, calculate_median_time_past_from_blocktimestamps, BlockError,
+        BlockFeeCheckStats, BlockFeeDiscrepancy, BlockFilter, BlockFilterItem,
+        BlockProcessingErrorClass, BlockProcessingErrorClassification, BlockSource, BlockUtxoDiff,
+        ChainInfo, ChainTip, ChainTipStatus, CheckBlockError, CheckBlockTransactionsError,
+        ConnectTransactionError, DbVerificationError, DbVerificationLevel, DestinationBalance,
+        FilteredBlock, FilteredChainEvent, IOPolicyError, IbdStatus, InitializationError, Locator,
+        NonZeroPoolBalances, OrphanCheckError, SpendStakeError, StartupRecoveryError,
+        StorageCompatibilityCheckError, SyncProgress, TokenIssuanceError, TokensError,
+        TransactionVerifierStorageError, UtxoDiffChunk, UtxoDiffEntry, UtxoSetStatistics,
+        CHAIN_SPLIT_RECORD_MAX_DEPTH, MAX_CHAIN_SPLIT_RECORDS, MEDIAN_TIME_SPAN,
+        STARTUP_RECOVERY_MAX_DEPTH,
     },
 };
-pub use chainstate_types::{BlockIndex, GenBlockIndex, PropertyQueryError};
+pub use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, ChainSplitTip, CompactBlockFilter,
+    GenBlockIndex, LocatorSchedule, PoolBlockRecord, PoolBlockStats, PropertyQueryError,
+};
 pub use constraints_value_accumulator;
 pub use detail::tx_verification_strategy::*;
 pub use interface::{chainstate_interface, chainstate_interface_impl_delegation};
@@ -52,6 +64,10 @@ pub use tx_verifier;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ChainstateEvent {
     NewTip(Id<Block>, BlockHeight),
+    /// A block was added to a branch that forked off the main chain more than
+    /// `ChainstateConfig::fork_alert_depth` blocks ago. The last value is the number of blocks
+    /// between the block and the main chain.
+    LongFork(Id<Block>, BlockHeight, u64),
 }
 
 /// A struct that will be used to print ChainstateEvent when it becomes a part of tracing's span.
@@ -65,6 +81,9 @@ impl std::fmt::Display for ChainstateEventTracingWrapper<'_> {
             ChainstateEvent::NewTip(id, height) => {
                 write!(f, "NewTip({id}, {height})")
             }
+            ChainstateEvent::LongFork(id, height, branch_length) => {
+                write!(f, "LongFork({id}, {height}, {branch_length})")
+            }
         }
     }
 }
@@ -25,8 +25,11 @@ use std::{
 };
 
 use self::types::{block::RpcBlock, event::RpcEvent};
-use crate::{Block, BlockSource, ChainInfo, GenBlock};
-use chainstate_types::BlockIndex;
+use crate::{
+    Block, BlockFeeCheckStats, BlockSource, BootstrapImportProgress, ChainInfo, ChainTip,
+    ChainstateError, ChainstateEvent, GenBlock, IbdStatus, SyncProgress, UtxoSetStatistics,
+};
+use chainstate_types::{BlockIndex, ChainSplitRecord};
 use common::{
     address::{dehexify::to_dehexified_json, Address},
     chain::{
@@ -38,8 +41,13 @@ use common::{
 use rpc::{subscription, RpcResult};
 use serialization::hex_encoded::HexEncoded;
 pub use types::{
+    balance::{RpcDestinationBalance, RpcTokenBalance},
+    chain_split::{RpcChainSplitRecord, RpcChainSplitTip},
+    epoch::{RpcEpochData, RpcSealedEpochRandomness},
     input::RpcUtxoOutpoint,
+    new_block::{NewBlockDetail, RpcNewBlock, RpcOverflowPolicy},
     output::{RpcOutputValueIn, RpcOutputValueOut, RpcTxOutput},
+    pool_block::{RpcPoolBlock, RpcPoolBlockStats},
     signed_transaction::RpcSignedTransaction,
 };
 
@@ -162,6 +170,22 @@ trait ChainstateRpc {
         delegation_address: String,
     ) -> RpcResult<Option<Amount>>;
 
+    /// Get the mainchain blocks produced by the given pool, starting at the given height,
+    /// along with their rewards.
+    ///
+    /// The number of returned blocks can be capped using the `max_count` parameter.
+    #[method(name = "pool_blocks")]
+    async fn pool_blocks(
+        &self,
+        pool_address: String,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> RpcResult<Vec<RpcPoolBlock>>;
+
+    /// Get the number of mainchain blocks produced by the given pool and their total reward.
+    #[method(name = "pool_block_stats")]
+    async fn pool_block_stats(&self, pool_address: String) -> RpcResult<RpcPoolBlockStats>;
+
     /// Get token information, given a token id, in address form.
     #[method(name = "token_info")]
     async fn token_info(&self, token_id: String) -> RpcResult<Option<RPCTokenInfo>>;
@@ -170,6 +194,52 @@ trait ChainstateRpc {
     #[method(name = "order_info")]
     async fn order_info(&self, order_id: String) -> RpcResult<Option<RpcOrderInfo>>;
 
+    /// Get the epoch data (i.e. the randomness) of the given epoch, if it has been sealed.
+    #[method(name = "epoch_data")]
+    async fn epoch_data(&self, epoch_index: u64) -> RpcResult<Option<RpcEpochData>>;
+
+    /// Get the sealed epoch randomness that a block on top of the current tip has to use
+    /// for staking, along with the corresponding epoch indices.
+    #[method(name = "sealed_epoch_randomness")]
+    async fn sealed_epoch_randomness(&self) -> RpcResult<RpcSealedEpochRandomness>;
+
+    /// Get the number of utxos, the total amount of coins in them and a checksum of the utxo set
+    /// at the current tip, which can be used to compare the state of different nodes.
+    ///
+    /// The checksum isn't cryptographic; it only detects accidental differences.
+    ///
+    /// This goes over the entire utxo set, so it may take a while.
+    #[method(name = "utxo_set_statistics")]
+    async fn utxo_set_statistics(&self) -> RpcResult<UtxoSetStatistics>;
+
+    /// Get the coins and tokens owned by the given address at the given mainchain height.
+    ///
+    /// Only transfer and lock-then-transfer outputs (including the locked ones) are counted.
+    ///
+    /// The height can be at most the max reorg depth of the chain below the best block.
+    ///
+    /// Warning: this goes over the entire utxo set and then rolls it back block by block down
+    /// to the given height, so it may take a long time; other chainstate requests have to wait
+    /// until it's done.
+    #[method(name = "balance_at_height")]
+    async fn balance_at_height(
+        &self,
+        address: String,
+        height: BlockHeight,
+    ) -> RpcResult<RpcDestinationBalance>;
+
+    /// Get the recorded chain splits, i.e. pairs of competing tips within a few blocks of each
+    /// other that this node has observed, along with the time they were first seen.
+    #[method(name = "chain_split_records")]
+    async fn chain_split_records(&self) -> RpcResult<Vec<RpcChainSplitRecord>>;
+
+    /// Get the tip of the main chain followed by the tips of all known branches, with their
+    /// heights, chain trust and the number of blocks between each tip and the main chain.
+    ///
+    /// This goes over all block indices in the database, so it may take a while.
+    #[method(name = "chain_tips")]
+    async fn chain_tips(&self) -> RpcResult<Vec<ChainTip>>;
+
     /// Exports a "bootstrap file", which contains all blocks
     #[method(name = "export_bootstrap_file")]
     async fn export_bootstrap_file(
@@ -186,13 +256,56 @@ trait ChainstateRpc {
     #[method(name = "info")]
     async fn info(&self) -> RpcResult<ChainInfo>;
 
+    /// Return the progress of the initial block download.
+    ///
+    /// The network height and the verification progress are estimates based on the timestamp
+    /// of the best block and the current time.
+    #[method(name = "ibd_status")]
+    async fn ibd_status(&self) -> RpcResult<IbdStatus>;
+
+    /// Return the sync progress: the fraction of the estimated best chain trust that has been
+    /// validated and the estimated time needed to process the missing blocks.
+    ///
+    /// The chain trust of the blocks that haven't been received yet is extrapolated from
+    /// the recent mainchain blocks; the remaining time is based on the recent block
+    /// processing rate.
+    #[method(name = "get_sync_progress")]
+    async fn get_sync_progress(&self) -> RpcResult<SyncProgress>;
+
+    /// Return the results of comparing the fees predicted for locally produced blocks
+    /// with the fees computed when the blocks were connected.
+    ///
+    /// A discrepancy means that the mempool and the transaction verifier disagree about
+    /// the fee of some transaction.
+    #[method(name = "block_fee_check_stats")]
+    async fn block_fee_check_stats(&self) -> RpcResult<BlockFeeCheckStats>;
+
     /// Subscribe to chainstate events, such as new tip.
     ///
     /// After a successful subscription, the node will message the subscriber with a message on every event.
     #[subscription(name = "subscribe_to_events", item = RpcEvent)]
     async fn subscribe_to_events(&self) -> rpc::subscription::Reply;
+
+    /// Subscribe to new blocks on the main chain.
+    ///
+    /// The node messages the subscriber every time the chain tip changes; in case of a reorg,
+    /// only the new tip is reported. The `detail` parameter determines whether the block header
+    /// or the whole block is sent in addition to the block id and height.
+    ///
+    /// If the subscriber doesn't keep up, at most 100 messages are queued for it, and
+    /// the `on_overflow` parameter determines what happens to the rest (by default, the oldest
+    /// messages are dropped). Use `best_block_id` to catch up after an overflow.
+    #[subscription(name = "subscribe_new_blocks", item = RpcNewBlock)]
+    async fn subscribe_new_blocks(
+        &self,
+        detail: NewBlockDetail,
+        on_overflow: Option<RpcOverflowPolicy>,
+    ) -> rpc::subscription::Reply;
 }
 
+/// The maximum number of new block notifications queued for a single subscriber
+const NEW_BLOCKS_QUEUE_CAPACITY: usize = 100;
+
 #[async_trait::async_trait]
 impl ChainstateRpcServer for super::ChainstateHandle {
     async fn best_block_id(&self) -> RpcResult<Id<GenBlock>> {
@@ -395,6 +508,47 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         )
     }
 
+    async fn pool_blocks(
+        &self,
+        pool_address: String,
+        from: BlockHeight,
+        max_count: usize,
+    ) -> RpcResult<Vec<RpcPoolBlock>> {
+        rpc::handle_result(
+            self.call(move |this| {
+                let chain_config = this.get_chain_config();
+                let result: Result<Vec<RpcPoolBlock>, _> =
+                    dynamize_err(Address::<PoolId>::from_string(chain_config, pool_address))
+                        .map(|address| address.into_object())
+                        .and_then(|pool_id| {
+                            dynamize_err(this.get_blocks_by_pool(pool_id, from, max_count))
+                        })
+                        .map(|blocks| {
+                            blocks.iter().map(|b| RpcPoolBlock::new(chain_config, b)).collect()
+                        });
+
+                result
+            })
+            .await,
+        )
+    }
+
+    async fn pool_block_stats(&self, pool_address: String) -> RpcResult<RpcPoolBlockStats> {
+        rpc::handle_result(
+            self.call(move |this| {
+                let chain_config = this.get_chain_config();
+                let result: Result<RpcPoolBlockStats, _> =
+                    dynamize_err(Address::<PoolId>::from_string(chain_config, pool_address))
+                        .map(|address| address.into_object())
+                        .and_then(|pool_id| dynamize_err(this.get_pool_block_stats(pool_id)))
+                        .map(|stats| RpcPoolBlockStats::new(chain_config, &stats));
+
+                result
+            })
+            .await,
+        )
+    }
+
     async fn token_info(&self, token_id: String) -> RpcResult<Option<RPCTokenInfo>> {
         rpc::handle_result(
             self.call(move |this| {
@@ -425,6 +579,65 @@ impl ChainstateRpcServer for super::ChainstateHandle {
         )
     }
 
+    async fn epoch_data(&self, epoch_index: u64) -> RpcResult<Option<RpcEpochData>> {
+        rpc::handle_result(
+            self.call(move |this| {
+                this.get_epoch_data(epoch_index)
+                    .map(|epoch_data| epoch_data.as_ref().map(RpcEpochData::from))
+            })
+            .await,
+        )
+    }
+
+    async fn sealed_epoch_randomness(&self) -> RpcResult<RpcSealedEpochRandomness> {
+        rpc::handle_result(
+            self.call(move |this| -> Result<_, ChainstateError> {
+                let block_height = this.get_best_block_height()?.next_height();
+                let randomness = this.get_sealed_epoch_randomness()?;
+                let chain_config = this.get_chain_config();
+                Ok(RpcSealedEpochRandomness {
+                    block_height,
+                    epoch_index: chain_config.epoch_index_from_height(&block_height),
+                    sealed_epoch_index: chain_config.sealed_epoch_index(&block_height),
+                    randomness: randomness.value(),
+                })
+            })
+            .await,
+        )
+    }
+
+    async fn utxo_set_statistics(&self) -> RpcResult<UtxoSetStatistics> {
+        rpc::handle_result(self.call(move |this| this.get_utxo_set_statistics()).await)
+    }
+
+    async fn balance_at_height(
+        &self,
+        address: String,
+        height: BlockHeight,
+    ) -> RpcResult<RpcDestinationBalance> {
+        rpc::handle_result(
+            self.call(move |this| {
+                let chain_config = this.get_chain_config();
+                let destination =
+                    dynamize_err(Address::<Destination>::from_string(chain_config, address))?
+                        .into_object();
+                let balance = dynamize_err(this.get_balance_at_height(&destination, height))?;
+                dynamize_err(RpcDestinationBalance::new(chain_config, balance))
+            })
+            .await,
+        )
+    }
+
+    async fn chain_split_records(&self) -> RpcResult<Vec<RpcChainSplitRecord>> {
+        let records: Vec<ChainSplitRecord> =
+            rpc::handle_result(self.call(move |this| this.get_chain_split_records()).await)?;
+        Ok(records.iter().map(RpcChainSplitRecord::from).collect())
+    }
+
+    async fn chain_tips(&self) -> RpcResult<Vec<ChainTip>> {
+        rpc::handle_result(self.call(move |this| this.get_chain_tips()).await)
+    }
+
     async fn export_bootstrap_file(
         &self,
         file_path: &std::path::Path,
@@ -443,21 +656,92 @@ impl ChainstateRpcServer for super::ChainstateHandle {
 
     async fn import_bootstrap_file(&self, file_path: &std::path::Path) -> RpcResult<()> {
         // TODO: test this function in functional tests
-        let file_obj: std::fs::File = rpc::handle_result(std::fs::File::create(file_path))?;
+        let file_obj: std::fs::File = rpc::handle_result(std::fs::File::open(file_path))?;
         let reader: std::io::BufReader<Box<dyn Read + Send>> =
             std::io::BufReader::new(Box::new(file_obj));
+        let progress_callback = Box::new(|progress: BootstrapImportProgress| {
+            logging::log::info!(
+                "Bootstrap import progress: {} blocks imported, {} skipped, {} bytes read",
+                progress.blocks_imported,
+                progress.blocks_skipped,
+                progress.bytes_read
+            );
+        });
 
-        rpc::handle_result(self.call_mut(move |this| this.import_bootstrap_stream(reader)).await)
+        rpc::handle_result(
+            self.call_mut(move |this| this.import_bootstrap_stream(reader, progress_callback))
+                .await,
+        )
     }
 
     async fn info(&self) -> RpcResult<ChainInfo> {
         rpc::handle_result(self.call(move |this| this.info()).await)
     }
 
+    async fn ibd_status(&self) -> RpcResult<IbdStatus> {
+        rpc::handle_result(self.call(move |this| this.ibd_status()).await)
+    }
+
+    async fn get_sync_progress(&self) -> RpcResult<SyncProgress> {
+        rpc::handle_result(self.call(move |this| this.sync_progress()).await)
+    }
+
+    async fn block_fee_check_stats(&self) -> RpcResult<BlockFeeCheckStats> {
+        rpc::handle_result(self.call(move |this| this.get_block_fee_check_stats()).await)
+    }
+
     async fn subscribe_to_events(&self, pending: subscription::Pending) -> subscription::Reply {
         let event_rx = self.call_mut(move |this| this.subscribe_to_rpc_events()).await?;
         rpc::subscription::connect_broadcast_map(event_rx, pending, RpcEvent::from_event).await
     }
+
+    async fn subscribe_new_blocks(
+        &self,
+        pending: subscription::Pending,
+        detail: NewBlockDetail,
+        on_overflow: Option<RpcOverflowPolicy>,
+    ) -> subscription::Reply {
+        let overflow_policy = on_overflow.unwrap_or(RpcOverflowPolicy::DropOldest).into();
+        let mut event_rx = self
+            .call_mut(move |this| {
+                this.subscribe_to_bounded_rpc_events(NEW_BLOCKS_QUEUE_CAPACITY, overflow_policy)
+            })
+            .await?;
+        let sink = subscription::accept::<RpcNewBlock>(pending).await?;
+
+        while let Some(event) = event_rx.recv().await {
+            let (id, height) = match event {
+                ChainstateEvent::NewTip(id, height) => (id, height),
+                ChainstateEvent::LongFork(_, _, _) => continue,
+            };
+
+            let (header, block) = match detail {
+                NewBlockDetail::Id => (None, None),
+                NewBlockDetail::Header => {
+                    let header = self.call(move |this| this.get_block_header(id)).await??;
+                    (header.map(HexEncoded::new), None)
+                }
+                NewBlockDetail::Full => {
+                    let block = self.call(move |this| this.get_block(id)).await??;
+                    (None, block.map(HexEncoded::new))
+                }
+            };
+
+            sink.send(&RpcNewBlock {
+                id,
+                height,
+                header,
+                block,
+            })
+            .await?;
+        }
+
+        if event_rx.is_overflowed() {
+            return Err("Too many pending new block messages, the subscription is closed".into());
+        }
+
+        Ok(())
+    }
 }
 
 fn dynamize_err<T, E: std::error::Error + Send + Sync>(
@@ -531,6 +815,12 @@ mod test {
 
             let res: RpcCallResult<Value> = rpc.call("chainstate_block_id_at_height", [1u32]).await;
             assert!(matches!(res, Ok(Value::Null)));
+
+            let res: RpcCallResult<Value> =
+                rpc.call("chainstate_sealed_epoch_randomness", [(); 0]).await;
+            let res = res.unwrap();
+            assert_eq!(res["block_height"], 1.into());
+            assert!(res["randomness"].is_string());
         })
         .await
     }
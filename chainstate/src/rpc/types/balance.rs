@@ -0,0 +1,62 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    address::{AddressError, RpcAddress},
+    chain::{tokens::TokenId, ChainConfig, GenBlock},
+    primitives::{amount::RpcAmountOut, Amount, BlockHeight, Id},
+};
+
+use crate::DestinationBalance;
+
+#[derive(Debug, Clone, serde::Serialize, rpc_description::HasValueHint)]
+pub struct RpcTokenBalance {
+    pub token_id: RpcAddress<TokenId>,
+    /// The amount in atoms; the number of decimals can be obtained via `token_info`
+    pub amount: Amount,
+}
+
+#[derive(Debug, Clone, serde::Serialize, rpc_description::HasValueHint)]
+pub struct RpcDestinationBalance {
+    pub block_id: Id<GenBlock>,
+    pub block_height: BlockHeight,
+    pub coins: RpcAmountOut,
+    pub tokens: Vec<RpcTokenBalance>,
+}
+
+impl RpcDestinationBalance {
+    pub fn new(
+        chain_config: &ChainConfig,
+        balance: DestinationBalance,
+    ) -> Result<Self, AddressError> {
+        let tokens = balance
+            .tokens
+            .into_iter()
+            .map(|(token_id, amount)| {
+                Ok(RpcTokenBalance {
+                    token_id: RpcAddress::new(chain_config, token_id)?,
+                    amount,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            block_id: balance.block_id,
+            block_height: balance.block_height,
+            coins: RpcAmountOut::from_amount(balance.coins, chain_config.coin_decimals()),
+            tokens,
+        })
+    }
+}
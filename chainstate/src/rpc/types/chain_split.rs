@@ -0,0 +1,73 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_types::{ChainSplitRecord, ChainSplitTip};
+use common::{
+    chain::{block::timestamp::BlockTimestamp, Block},
+    primitives::{BlockHeight, Id},
+};
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct RpcChainSplitTip {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    /// The time at which the block was first seen by the node, if known
+    pub first_seen: Option<BlockTimestamp>,
+}
+
+impl From<&ChainSplitTip> for RpcChainSplitTip {
+    fn from(tip: &ChainSplitTip) -> Self {
+        Self {
+            block_id: *tip.block_id(),
+            block_height: tip.block_height(),
+            first_seen: tip.first_seen(),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct RpcChainSplitRecord {
+    /// The tip that was the best block after the split was observed
+    pub best_tip: RpcChainSplitTip,
+    /// The tip that lost (or didn't win) the competition
+    pub competing_tip: RpcChainSplitTip,
+    pub observed_at: BlockTimestamp,
+}
+
+impl From<&ChainSplitRecord> for RpcChainSplitRecord {
+    fn from(record: &ChainSplitRecord) -> Self {
+        Self {
+            best_tip: record.best_tip().into(),
+            competing_tip: record.competing_tip().into(),
+            observed_at: record.observed_at(),
+        }
+    }
+}
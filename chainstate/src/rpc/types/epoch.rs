@@ -0,0 +1,63 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_types::EpochData;
+use common::{
+    chain::config::EpochIndex,
+    primitives::{BlockHeight, H256},
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct RpcEpochData {
+    pub randomness: H256,
+}
+
+impl From<&EpochData> for RpcEpochData {
+    fn from(epoch_data: &EpochData) -> Self {
+        Self {
+            randomness: epoch_data.randomness().value(),
+        }
+    }
+}
+
+/// The randomness that the next block has to use for staking
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct RpcSealedEpochRandomness {
+    /// Height of the next block
+    pub block_height: BlockHeight,
+    /// Epoch of the next block
+    pub epoch_index: EpochIndex,
+    /// The sealed epoch, or `None` if no epoch is sealed yet and the genesis randomness is used
+    pub sealed_epoch_index: Option<EpochIndex>,
+    pub randomness: H256,
+}
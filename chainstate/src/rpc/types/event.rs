@@ -23,13 +23,26 @@ use crate::ChainstateEvent;
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, rpc::description::HasValueHint)]
 #[serde(tag = "type", content = "content")]
 pub enum RpcEvent {
-    NewTip { id: Id<Block>, height: BlockHeight },
+    NewTip {
+        id: Id<Block>,
+        height: BlockHeight,
+    },
+    LongFork {
+        id: Id<Block>,
+        height: BlockHeight,
+        branch_length: u64,
+    },
 }
 
 impl RpcEvent {
     pub fn from_event(event: ChainstateEvent) -> Self {
         match event {
             ChainstateEvent::NewTip(id, height) => Self::NewTip { id, height },
+            ChainstateEvent::LongFork(id, height, branch_length) => Self::LongFork {
+                id,
+                height,
+                branch_length,
+            },
         }
     }
 }
@@ -14,11 +14,16 @@
 // limitations under the License.
 
 pub mod account;
+pub mod balance;
 pub mod block;
 pub mod block_reward;
+pub mod chain_split;
 pub mod consensus_data;
+pub mod epoch;
 pub mod event;
 pub mod input;
+pub mod new_block;
 pub mod output;
+pub mod pool_block;
 pub mod signed_transaction;
 pub mod token;
@@ -0,0 +1,62 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{block::signed_block_header::SignedBlockHeader, Block},
+    primitives::{BlockHeight, Id},
+};
+use rpc::description::HasValueHint;
+use serialization::hex_encoded::HexEncoded;
+use utils_networking::broadcaster::OverflowPolicy;
+
+/// How much of a new block is sent to the subscriber
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub enum NewBlockDetail {
+    /// Only the block id and height
+    Id,
+    /// The block header in addition to the id and height
+    Header,
+    /// The whole block in addition to the id and height
+    Full,
+}
+
+/// What happens to the new block notifications that don't fit in the subscriber's queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub enum RpcOverflowPolicy {
+    /// Discard the oldest queued notification
+    DropOldest,
+    /// Discard the new notification
+    DropNewest,
+    /// Close the subscription
+    Disconnect,
+}
+
+impl From<RpcOverflowPolicy> for OverflowPolicy {
+    fn from(policy: RpcOverflowPolicy) -> Self {
+        match policy {
+            RpcOverflowPolicy::DropOldest => OverflowPolicy::DropOldest,
+            RpcOverflowPolicy::DropNewest => OverflowPolicy::DropNewest,
+            RpcOverflowPolicy::Disconnect => OverflowPolicy::Disconnect,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct RpcNewBlock {
+    pub id: Id<Block>,
+    pub height: BlockHeight,
+    pub header: Option<HexEncoded<SignedBlockHeader>>,
+    pub block: Option<HexEncoded<Block>>,
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_types::{PoolBlockRecord, PoolBlockStats};
+use common::{
+    chain::{Block, ChainConfig},
+    primitives::{amount::RpcAmountOut, BlockHeight, Id},
+};
+
+#[derive(Debug, Clone, serde::Serialize, rpc_description::HasValueHint)]
+pub struct RpcPoolBlock {
+    pub block_id: Id<Block>,
+    pub block_height: BlockHeight,
+    /// The total reward of the block, which is distributed among the staker and the delegations
+    pub reward: RpcAmountOut,
+}
+
+impl RpcPoolBlock {
+    pub fn new(chain_config: &ChainConfig, record: &PoolBlockRecord) -> Self {
+        Self {
+            block_id: *record.block_id(),
+            block_height: record.block_height(),
+            reward: RpcAmountOut::from_amount(record.reward(), chain_config.coin_decimals()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, rpc_description::HasValueHint)]
+pub struct RpcPoolBlockStats {
+    pub block_count: u64,
+    pub total_reward: RpcAmountOut,
+}
+
+impl RpcPoolBlockStats {
+    pub fn new(chain_config: &ChainConfig, stats: &PoolBlockStats) -> Self {
+        Self {
+            block_count: stats.block_count,
+            total_reward: RpcAmountOut::from_amount(
+                stats.total_reward,
+                chain_config.coin_decimals(),
+            ),
+        }
+    }
+}
@@ -26,7 +26,7 @@ use crate::{
 };
 
 mod well_known {
-    use common::chain::{self, GenBlock};
+    use common::chain::{self, Block, GenBlock};
 
     use super::{BlockHeight, ChainstateStorageVersion, Codec, Id};
 
@@ -54,6 +54,10 @@ mod well_known {
     declare_entry!(MagicBytes: chain::config::MagicBytes);
     declare_entry!(ChainType: String);
     declare_entry!(MinHeightForReorg: BlockHeight);
+    declare_entry!(LastBootstrapImportedBlockId: Id<Block>);
+    declare_entry!(MinHeightWithUndoData: BlockHeight);
+    declare_entry!(PendingBlockActivations: Vec<Id<Block>>);
+    declare_entry!(TokenTickersIndexed: bool);
 }
 
 /// Read-only chainstate storage transaction
@@ -208,6 +212,11 @@ impl<'st, B: storage::SharedBackend> StoreTxRw<'st, B> {
     {
         self.track_error(|tx| Ok(tx.get_mut::<DbMap, I>().del(key)?))
     }
+
+    // Delete a well-known entry
+    fn del_value<E: well_known::Entry>(&mut self) -> crate::Result<()> {
+        self.del::<db::DBValue, _, _>(E::KEY)
+    }
 }
 
 impl<B: storage::SharedBackend> crate::TransactionRo for StoreTxRo<'_, B> {
@@ -13,10 +13,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+};
 
 use super::db;
-use chainstate_types::{BlockIndex, EpochData, EpochStorageRead, SealedStorageTag, TipStorageTag};
+use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, EpochData,
+    EpochStorageRead, PoolBlockRecord, SealedStorageTag, TipStorageTag,
+};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, BlockReward},
@@ -66,6 +72,19 @@ mod private {
         }
     }
 
+    /// Look up the records of the pool blocks found via `DBPoolBlocksByPool`, sorted by height.
+    pub fn collect_pool_blocks(
+        index_keys: impl Iterator<Item = (PoolId, Id<Block>)>,
+        mut read_record: impl FnMut(Id<Block>) -> crate::Result<Option<(PoolId, PoolBlockRecord)>>,
+    ) -> crate::Result<Vec<PoolBlockRecord>> {
+        let mut records = index_keys
+            .filter_map(|(_, block_id)| read_record(block_id).transpose())
+            .map(|item| item.map(|(_, record)| record))
+            .collect::<crate::Result<Vec<_>>>()?;
+        records.sort_by_key(|record| record.block_height());
+        Ok(records)
+    }
+
     pub fn filter_delegation_shares_for_poolid(
         pool_id: PoolId,
         iter: impl Iterator<Item = ((PoolId, DelegationId), Amount)>,
@@ -141,6 +160,51 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRo<'_, B
         self.read_value::<well_known::MinHeightForReorg>()
     }
 
+    #[log_error]
+    fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>> {
+        self.read_value::<well_known::LastBootstrapImportedBlockId>()
+    }
+
+    #[log_error]
+    fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>> {
+        self.read_value::<well_known::MinHeightWithUndoData>()
+    }
+
+    #[log_error]
+    fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>> {
+        self.read_value::<well_known::PendingBlockActivations>()
+            .map(Option::unwrap_or_default)
+    }
+
+    #[log_error]
+    fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>> {
+        let map = self.0.get::<db::DBChainSplitRecords, _>();
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.map(|(_, record)| record).collect())
+    }
+
+    #[log_error]
+    fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>> {
+        self.read::<db::DBBlockFilters, _, _>(id)
+    }
+
+    #[log_error]
+    fn get_block_filter_commitment(
+        &self,
+        id: &Id<Block>,
+    ) -> crate::Result<Option<BlockFilterCommitment>> {
+        self.read::<db::DBBlockFilterCommitments, _, _>(id)
+    }
+
+    #[log_error]
+    fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>> {
+        let map = self.0.get::<db::DBPoolBlocksByPool, _>();
+        let index_keys = map.prefix_iter_keys(&(pool_id,))?;
+        private::collect_pool_blocks(index_keys, |block_id| {
+            self.read::<db::DBPoolBlocks, _, _>(block_id)
+        })
+    }
+
     #[log_error]
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>> {
         self.read::<db::DBBlockByHeight, _, _>(height)
@@ -161,6 +225,16 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRo<'_, B
         self.read::<db::DBIssuanceTxVsTokenId, _, _>(&issuance_tx_id)
     }
 
+    #[log_error]
+    fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>> {
+        self.read::<db::DBTokenTickers, _, _>(ticker)
+    }
+
+    #[log_error]
+    fn get_token_tickers_indexed(&self) -> crate::Result<bool> {
+        Ok(self.read_value::<well_known::TokenTickersIndexed>()?.unwrap_or(false))
+    }
+
     #[log_error]
     fn get_tokens_accounting_undo(
         &self,
@@ -238,12 +312,42 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRo<'_, B
         Ok(items.collect::<BTreeMap<_, _>>())
     }
 
+    #[log_error]
+    fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>> {
+        let map = self.0.get::<db::DBTokensAuxData, _>();
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
+    #[log_error]
+    fn get_tokens_data_map(
+        &self,
+    ) -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>> {
+        let map = self.0.get::<db::DBTokensData, _>();
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
     #[log_error]
     fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>> {
         let map = self.0.get::<db::DBBlockByHeight, _>();
         let items = map.prefix_iter_decoded(&())?;
         Ok(items.collect::<BTreeMap<_, _>>())
     }
+
+    #[log_error]
+    fn for_each_utxo(
+        &self,
+        f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+    ) -> crate::Result<()> {
+        let map = self.0.get::<db::DBUtxo, _>();
+        for (outpoint, utxo) in map.prefix_iter_decoded(&())? {
+            if f(outpoint, utxo).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<B: storage::SharedBackend> EpochStorageRead for super::StoreTxRo<'_, B> {
@@ -453,6 +557,51 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRw<'_, B
         self.read_value::<well_known::MinHeightForReorg>()
     }
 
+    #[log_error]
+    fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>> {
+        self.read_value::<well_known::LastBootstrapImportedBlockId>()
+    }
+
+    #[log_error]
+    fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>> {
+        self.read_value::<well_known::MinHeightWithUndoData>()
+    }
+
+    #[log_error]
+    fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>> {
+        self.read_value::<well_known::PendingBlockActivations>()
+            .map(Option::unwrap_or_default)
+    }
+
+    #[log_error]
+    fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>> {
+        let map = self.get_map::<db::DBChainSplitRecords, _>()?;
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.map(|(_, record)| record).collect())
+    }
+
+    #[log_error]
+    fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>> {
+        self.read::<db::DBBlockFilters, _, _>(id)
+    }
+
+    #[log_error]
+    fn get_block_filter_commitment(
+        &self,
+        id: &Id<Block>,
+    ) -> crate::Result<Option<BlockFilterCommitment>> {
+        self.read::<db::DBBlockFilterCommitments, _, _>(id)
+    }
+
+    #[log_error]
+    fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>> {
+        let map = self.get_map::<db::DBPoolBlocksByPool, _>()?;
+        let index_keys = map.prefix_iter_keys(&(pool_id,))?;
+        private::collect_pool_blocks(index_keys, |block_id| {
+            self.read::<db::DBPoolBlocks, _, _>(block_id)
+        })
+    }
+
     #[log_error]
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>> {
         self.read::<db::DBBlockByHeight, _, _>(height)
@@ -473,6 +622,16 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRw<'_, B
         self.read::<db::DBIssuanceTxVsTokenId, _, _>(&issuance_tx_id)
     }
 
+    #[log_error]
+    fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>> {
+        self.read::<db::DBTokenTickers, _, _>(ticker)
+    }
+
+    #[log_error]
+    fn get_token_tickers_indexed(&self) -> crate::Result<bool> {
+        Ok(self.read_value::<well_known::TokenTickersIndexed>()?.unwrap_or(false))
+    }
+
     #[log_error]
     fn get_tokens_accounting_undo(
         &self,
@@ -554,6 +713,24 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRw<'_, B
         Ok(items.collect::<BTreeMap<_, _>>())
     }
 
+    // TODO: same as above.
+    #[log_error]
+    fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>> {
+        let map = self.get_map::<db::DBTokensAuxData, _>()?;
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
+    // TODO: same as above.
+    #[log_error]
+    fn get_tokens_data_map(
+        &self,
+    ) -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>> {
+        let map = self.get_map::<db::DBTokensData, _>()?;
+        let items = map.prefix_iter_decoded(&())?;
+        Ok(items.collect::<BTreeMap<_, _>>())
+    }
+
     // TODO: same as above.
     #[log_error]
     fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>> {
@@ -561,6 +738,20 @@ impl<B: storage::SharedBackend> BlockchainStorageRead for super::StoreTxRw<'_, B
         let items = map.prefix_iter_decoded(&())?;
         Ok(items.collect::<BTreeMap<_, _>>())
     }
+
+    #[log_error]
+    fn for_each_utxo(
+        &self,
+        f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+    ) -> crate::Result<()> {
+        let map = self.get_map::<db::DBUtxo, _>()?;
+        for (outpoint, utxo) in map.prefix_iter_decoded(&())? {
+            if f(outpoint, utxo).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<B: storage::SharedBackend> EpochStorageRead for super::StoreTxRw<'_, B> {
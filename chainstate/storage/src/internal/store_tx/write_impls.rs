@@ -13,9 +13,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
+
 use super::{well_known, StoreTxRw};
 use crate::{BlockchainStorageWrite, ChainstateStorageVersion, SealedStorageTag, TipStorageTag};
-use chainstate_types::{BlockIndex, EpochData, EpochStorageWrite};
+use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, EpochData,
+    EpochStorageWrite, PoolBlockRecord,
+};
 use common::{
     chain::{
         config::{EpochIndex, MagicBytes},
@@ -82,6 +87,64 @@ impl<B: storage::SharedBackend> BlockchainStorageWrite for StoreTxRw<'_, B> {
         self.write_value::<well_known::MinHeightForReorg>(&height)
     }
 
+    #[log_error]
+    fn set_last_bootstrap_imported_block_id(&mut self, id: &Id<Block>) -> crate::Result<()> {
+        self.write_value::<well_known::LastBootstrapImportedBlockId>(id)
+    }
+
+    #[log_error]
+    fn del_last_bootstrap_imported_block_id(&mut self) -> crate::Result<()> {
+        self.del_value::<well_known::LastBootstrapImportedBlockId>()
+    }
+
+    #[log_error]
+    fn set_min_height_with_undo_data(&mut self, height: BlockHeight) -> crate::Result<()> {
+        self.write_value::<well_known::MinHeightWithUndoData>(&height)
+    }
+
+    #[log_error]
+    fn set_pending_block_activations(&mut self, block_ids: &[Id<Block>]) -> crate::Result<()> {
+        if block_ids.is_empty() {
+            self.del_value::<well_known::PendingBlockActivations>()
+        } else {
+            self.write_value::<well_known::PendingBlockActivations>(&block_ids.to_vec())
+        }
+    }
+
+    #[log_error]
+    fn set_chain_split_record(&mut self, record: &ChainSplitRecord) -> crate::Result<()> {
+        self.write::<db::DBChainSplitRecords, _, _, _>(record.competing_tip().block_id(), record)
+    }
+
+    #[log_error]
+    fn del_chain_split_record(&mut self, competing_tip_id: &Id<Block>) -> crate::Result<()> {
+        self.del::<db::DBChainSplitRecords, _, _>(competing_tip_id)
+    }
+
+    #[log_error]
+    fn set_block_filter(
+        &mut self,
+        id: &Id<Block>,
+        filter: &CompactBlockFilter,
+        commitment: &BlockFilterCommitment,
+    ) -> crate::Result<()> {
+        self.write::<db::DBBlockFilters, _, _, _>(id, filter)?;
+        self.write::<db::DBBlockFilterCommitments, _, _, _>(id, commitment)
+    }
+
+    #[log_error]
+    fn set_pool_block(&mut self, pool_id: PoolId, record: &PoolBlockRecord) -> crate::Result<()> {
+        let value = (pool_id, record.clone());
+        self.track_error(|tx| {
+            Ok(tx.put_indexed::<db::DBPoolBlocks, _, _>(record.block_id(), &value)?)
+        })
+    }
+
+    #[log_error]
+    fn del_pool_block(&mut self, block_id: Id<Block>) -> crate::Result<()> {
+        self.track_error(|tx| Ok(tx.del_indexed::<db::DBPoolBlocks, _, _>(&block_id)?))
+    }
+
     #[log_error]
     fn set_block_id_at_height(
         &mut self,
@@ -134,6 +197,25 @@ impl<B: storage::SharedBackend> BlockchainStorageWrite for StoreTxRw<'_, B> {
         self.del::<db::DBIssuanceTxVsTokenId, _, _>(issuance_tx_id)
     }
 
+    #[log_error]
+    fn set_token_ticker(
+        &mut self,
+        ticker: &[u8],
+        token_ids: &BTreeSet<TokenId>,
+    ) -> crate::Result<()> {
+        self.write::<db::DBTokenTickers, _, _, _>(ticker, token_ids)
+    }
+
+    #[log_error]
+    fn del_token_ticker(&mut self, ticker: &[u8]) -> crate::Result<()> {
+        self.del::<db::DBTokenTickers, _, _>(ticker)
+    }
+
+    #[log_error]
+    fn set_token_tickers_indexed(&mut self, indexed: bool) -> crate::Result<()> {
+        self.write_value::<well_known::TokenTickersIndexed>(&indexed)
+    }
+
     #[log_error]
     fn set_tokens_accounting_undo_data(
         &mut self,
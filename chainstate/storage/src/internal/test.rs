@@ -14,9 +14,10 @@
 // limitations under the License.
 
 use super::*;
+use chainstate_types::PoolBlockRecord;
 use common::chain::output_value::OutputValue;
 use common::chain::transaction::signed_transaction::SignedTransaction;
-use common::chain::{Block, Destination, OutPointSourceId, TxOutput, UtxoOutPoint};
+use common::chain::{Block, Destination, OutPointSourceId, PoolId, TxOutput, UtxoOutPoint};
 use common::primitives::Id;
 use common::primitives::{Amount, BlockHeight, Idable, H256};
 use crypto::key::{KeyKind, PrivateKey};
@@ -473,3 +474,69 @@ fn utxo_db_impl_test(#[case] seed: Seed) {
     assert!(db_tx.set_best_block_for_utxos(&block_id.into()).is_ok());
     db_tx.commit().expect("commit cannot fail");
 }
+
+#[cfg(not(loom))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pool_blocks_index(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let store = TestStore::new_empty().unwrap();
+
+    let pool_ids = [
+        PoolId::new(H256::random_using(&mut rng)),
+        PoolId::new(H256::random_using(&mut rng)),
+    ];
+    let records = (1..=10)
+        .map(|height| {
+            let pool_id = pool_ids[rng.gen_range(0..pool_ids.len())];
+            let record = PoolBlockRecord::new(
+                Id::new(H256::random_using(&mut rng)),
+                BlockHeight::new(height),
+                Amount::from_atoms(rng.gen_range(0..1000)),
+            );
+            (pool_id, record)
+        })
+        .collect::<Vec<_>>();
+    let expected_pool_blocks = |records: &[(PoolId, PoolBlockRecord)], pool_id: PoolId| {
+        records
+            .iter()
+            .filter(|(id, _)| *id == pool_id)
+            .map(|(_, record)| record.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut db_tx = store.transaction_rw(None).unwrap();
+    // Insert in reverse order, the records are sorted by height on read
+    for (pool_id, record) in records.iter().rev() {
+        db_tx.set_pool_block(*pool_id, record).unwrap();
+    }
+    db_tx.commit().unwrap();
+
+    for pool_id in pool_ids {
+        assert_eq!(
+            store.transaction_ro().unwrap().get_pool_blocks(pool_id),
+            Ok(expected_pool_blocks(&records, pool_id))
+        );
+    }
+
+    // Deleting a record removes it from the index too
+    let (deleted_pool_id, deleted_record) = records.last().unwrap().clone();
+    let mut db_tx = store.transaction_rw(None).unwrap();
+    db_tx.del_pool_block(*deleted_record.block_id()).unwrap();
+    assert_eq!(
+        db_tx.get_pool_blocks(deleted_pool_id),
+        Ok(expected_pool_blocks(
+            &records[..records.len() - 1],
+            deleted_pool_id
+        ))
+    );
+    db_tx.commit().unwrap();
+
+    for pool_id in pool_ids {
+        assert_eq!(
+            store.transaction_ro().unwrap().get_pool_blocks(pool_id),
+            Ok(expected_pool_blocks(&records[..records.len() - 1], pool_id))
+        );
+    }
+}
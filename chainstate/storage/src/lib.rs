@@ -21,10 +21,14 @@ mod is_transaction_seal;
 pub mod mock;
 pub mod schema;
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+};
 
 use chainstate_types::{
-    BlockIndex, EpochStorageRead, EpochStorageWrite, SealedStorageTag, TipStorageTag,
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, EpochStorageRead,
+    EpochStorageWrite, PoolBlockRecord, SealedStorageTag, TipStorageTag,
 };
 use common::{
     chain::{
@@ -32,7 +36,7 @@ use common::{
         config::{EpochIndex, MagicBytes},
         tokens::{TokenAuxiliaryData, TokenId},
         transaction::Transaction,
-        AccountNonce, AccountType, Block, GenBlock,
+        AccountNonce, AccountType, Block, GenBlock, PoolId, UtxoOutPoint,
     },
     primitives::{BlockHeight, Id},
 };
@@ -46,7 +50,7 @@ use pos_accounting::{
 use tokens_accounting::{
     TokenAccountingUndo, TokensAccountingStorageRead, TokensAccountingStorageWrite,
 };
-use utxo::{UtxosBlockUndo, UtxosStorageRead, UtxosStorageWrite};
+use utxo::{Utxo, UtxosBlockUndo, UtxosStorageRead, UtxosStorageWrite};
 
 pub use internal::{ChainstateStorageVersion, Store};
 
@@ -100,6 +104,31 @@ pub trait BlockchainStorageRead:
     /// Get the height below which reorgs should not be allowed.
     fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
 
+    /// Get the id of the last block imported by an unfinished bootstrap import.
+    fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>>;
+
+    /// Get the lowest main chain height whose block undo data hasn't been pruned.
+    fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>>;
+
+    /// Get the ids of the blocks that have been accepted but whose activation hasn't finished yet,
+    /// in the order of their acceptance.
+    fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>>;
+
+    /// Get all recorded chain splits
+    fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>>;
+
+    /// Get the compact filter of the block, if it has been built
+    fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>>;
+
+    /// Get the commitment to the compact filter of the block, if it has been built
+    fn get_block_filter_commitment(
+        &self,
+        id: &Id<Block>,
+    ) -> crate::Result<Option<BlockFilterCommitment>>;
+
+    /// Get the main chain blocks produced by the pool, ordered by height
+    fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>>;
+
     /// Get mainchain block by its height
     fn get_block_id_by_height(&self, height: &BlockHeight) -> crate::Result<Option<Id<GenBlock>>>;
 
@@ -111,6 +140,12 @@ pub trait BlockchainStorageRead:
     /// Get token id by id of the creation tx
     fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
 
+    /// Get the ids of the existing tokens that were issued with the given ticker
+    fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>>;
+
+    /// Check whether the ticker index covers all the issued tokens
+    fn get_token_tickers_indexed(&self) -> crate::Result<bool>;
+
     /// Get block tree as height vs ids
     fn get_block_tree_by_height(
         &self,
@@ -156,9 +191,21 @@ pub trait BlockchainStorageRead:
     /// Get the entire block index map as BTreeMap. This is used in the chainstate's
     /// "heavy" consistency checks.
     fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
+    /// Get the auxiliary data of all the issued tokens. This is used to build the ticker index.
+    fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+    /// Get the tokens accounting data of all the fungible tokens. This is used to build
+    /// the ticker index.
+    fn get_tokens_data_map(&self)
+        -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>>;
     /// Get the entire mainchain-block-by-height map as BTreeMap. This is used in the chainstate's
     /// "heavy" consistency checks.
     fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+    /// Call the function for each entry of the utxo set until it returns `ControlFlow::Break`.
+    /// This is used to go over the entire utxo set without loading it into memory.
+    fn for_each_utxo(
+        &self,
+        f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+    ) -> crate::Result<()>;
 }
 
 /// Modifying operations on persistent blockchain data
@@ -198,6 +245,38 @@ pub trait BlockchainStorageWrite:
     /// Set the height below which reorgs should not be allowed.
     fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
 
+    /// Set the id of the last block imported by an unfinished bootstrap import.
+    fn set_last_bootstrap_imported_block_id(&mut self, id: &Id<Block>) -> crate::Result<()>;
+
+    /// Remove the id of the last block imported by a bootstrap import, once it's finished.
+    fn del_last_bootstrap_imported_block_id(&mut self) -> crate::Result<()>;
+
+    /// Set the lowest main chain height whose block undo data hasn't been pruned.
+    fn set_min_height_with_undo_data(&mut self, height: BlockHeight) -> crate::Result<()>;
+
+    /// Set the ids of the blocks that have been accepted but whose activation hasn't finished yet.
+    fn set_pending_block_activations(&mut self, block_ids: &[Id<Block>]) -> crate::Result<()>;
+
+    /// Record an observed chain split; an existing record for the same competing tip is replaced.
+    fn set_chain_split_record(&mut self, record: &ChainSplitRecord) -> crate::Result<()>;
+
+    /// Remove the chain split record for the given competing tip.
+    fn del_chain_split_record(&mut self, competing_tip_id: &Id<Block>) -> crate::Result<()>;
+
+    /// Set the compact filter of the block together with its commitment.
+    fn set_block_filter(
+        &mut self,
+        id: &Id<Block>,
+        filter: &CompactBlockFilter,
+        commitment: &BlockFilterCommitment,
+    ) -> crate::Result<()>;
+
+    /// Record a main chain block produced by the pool.
+    fn set_pool_block(&mut self, pool_id: PoolId, record: &PoolBlockRecord) -> crate::Result<()>;
+
+    /// Remove the record of a pool's block, once it's disconnected.
+    fn del_pool_block(&mut self, block_id: Id<Block>) -> crate::Result<()>;
+
     /// Set the mainchain block at given height to be given block.
     fn set_block_id_at_height(
         &mut self,
@@ -223,6 +302,15 @@ pub trait BlockchainStorageWrite:
     /// Remove token id
     fn del_token_id(&mut self, issuance_tx_id: &Id<Transaction>) -> Result<()>;
 
+    /// Binding token ticker with the ids of the tokens that were issued with it
+    fn set_token_ticker(&mut self, ticker: &[u8], token_ids: &BTreeSet<TokenId>) -> Result<()>;
+
+    /// Remove token ticker
+    fn del_token_ticker(&mut self, ticker: &[u8]) -> Result<()>;
+
+    /// Set whether the ticker index covers all the issued tokens
+    fn set_token_tickers_indexed(&mut self, indexed: bool) -> Result<()>;
+
     /// Set tokens accounting undo data for specific block
     fn set_tokens_accounting_undo_data(
         &mut self,
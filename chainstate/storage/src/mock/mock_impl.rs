@@ -15,9 +15,15 @@
 
 //! A mock version of the blockchain storage.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::ControlFlow,
+};
 
-use chainstate_types::{BlockIndex, EpochData, EpochStorageRead, EpochStorageWrite};
+use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, EpochData,
+    EpochStorageRead, EpochStorageWrite, PoolBlockRecord,
+};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, BlockReward},
@@ -62,6 +68,16 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>>;
+        fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>>;
+        fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>>;
+        fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>>;
+        fn get_block_filter_commitment(
+            &self,
+            id: &Id<Block>,
+        ) -> crate::Result<Option<BlockFilterCommitment>>;
+        fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>>;
 
         fn get_block_id_by_height(
             &self,
@@ -73,6 +89,8 @@ mockall::mock! {
         fn get_token_aux_data(&self, token_id: &TokenId) -> crate::Result<Option<TokenAuxiliaryData>>;
 
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
+        fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>>;
+        fn get_token_tickers_indexed(&self) -> crate::Result<bool>;
 
         fn get_tokens_accounting_undo(
             &self,
@@ -105,7 +123,13 @@ mockall::mock! {
 
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
+        fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+        fn get_tokens_data_map(&self) -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn for_each_utxo(
+            &self,
+            f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+        ) -> crate::Result<()>;
     }
 
     impl EpochStorageRead for Store {
@@ -186,6 +210,24 @@ mockall::mock! {
         fn del_block(&mut self, id: Id<Block>) -> crate::Result<()>;
 
         fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_last_bootstrap_imported_block_id(&mut self, id: &Id<Block>) -> crate::Result<()>;
+        fn del_last_bootstrap_imported_block_id(&mut self) -> crate::Result<()>;
+        fn set_min_height_with_undo_data(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_pending_block_activations(&mut self, block_ids: &[Id<Block>]) -> crate::Result<()>;
+        fn set_chain_split_record(&mut self, record: &ChainSplitRecord) -> crate::Result<()>;
+        fn del_chain_split_record(&mut self, competing_tip_id: &Id<Block>) -> crate::Result<()>;
+        fn set_block_filter(
+            &mut self,
+            id: &Id<Block>,
+            filter: &CompactBlockFilter,
+            commitment: &BlockFilterCommitment,
+        ) -> crate::Result<()>;
+        fn set_pool_block(
+            &mut self,
+            pool_id: PoolId,
+            record: &PoolBlockRecord,
+        ) -> crate::Result<()>;
+        fn del_pool_block(&mut self, block_id: Id<Block>) -> crate::Result<()>;
 
         fn set_block_id_at_height(
             &mut self,
@@ -202,6 +244,9 @@ mockall::mock! {
         fn del_token_aux_data(&mut self, token_id: &TokenId) -> crate::Result<()>;
         fn set_token_id(&mut self, issuance_tx_id: &Id<Transaction>, token_id: &TokenId) -> crate::Result<()>;
         fn del_token_id(&mut self, issuance_tx_id: &Id<Transaction>) -> crate::Result<()>;
+        fn set_token_ticker(&mut self, ticker: &[u8], token_ids: &BTreeSet<TokenId>) -> crate::Result<()>;
+        fn del_token_ticker(&mut self, ticker: &[u8]) -> crate::Result<()>;
+        fn set_token_tickers_indexed(&mut self, indexed: bool) -> crate::Result<()>;
 
         fn set_tokens_accounting_undo_data(
             &mut self,
@@ -367,6 +412,16 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>>;
+        fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>>;
+        fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>>;
+        fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>>;
+        fn get_block_filter_commitment(
+            &self,
+            id: &Id<Block>,
+        ) -> crate::Result<Option<BlockFilterCommitment>>;
+        fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>>;
 
         fn get_block_id_by_height(
             &self,
@@ -377,6 +432,8 @@ mockall::mock! {
 
         fn get_token_aux_data(&self, token_id: &TokenId) -> crate::Result<Option<TokenAuxiliaryData>>;
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
+        fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>>;
+        fn get_token_tickers_indexed(&self) -> crate::Result<bool>;
         fn get_block_tree_by_height(
             &self,
             start_from: BlockHeight,
@@ -402,7 +459,13 @@ mockall::mock! {
 
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
+        fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+        fn get_tokens_data_map(&self) -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn for_each_utxo(
+            &self,
+            f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+        ) -> crate::Result<()>;
     }
 
     impl EpochStorageRead for StoreTxRo {
@@ -494,6 +557,16 @@ mockall::mock! {
         fn get_block_header(&self, id: Id<Block>) -> crate::Result<Option<SignedBlockHeader>>;
 
         fn get_min_height_with_allowed_reorg(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_last_bootstrap_imported_block_id(&self) -> crate::Result<Option<Id<Block>>>;
+        fn get_min_height_with_undo_data(&self) -> crate::Result<Option<BlockHeight>>;
+        fn get_pending_block_activations(&self) -> crate::Result<Vec<Id<Block>>>;
+        fn get_chain_split_records(&self) -> crate::Result<Vec<ChainSplitRecord>>;
+        fn get_block_filter(&self, id: &Id<Block>) -> crate::Result<Option<CompactBlockFilter>>;
+        fn get_block_filter_commitment(
+            &self,
+            id: &Id<Block>,
+        ) -> crate::Result<Option<BlockFilterCommitment>>;
+        fn get_pool_blocks(&self, pool_id: PoolId) -> crate::Result<Vec<PoolBlockRecord>>;
 
         fn get_block_id_by_height(
             &self,
@@ -504,6 +577,8 @@ mockall::mock! {
 
         fn get_token_aux_data(&self, token_id: &TokenId) -> crate::Result<Option<TokenAuxiliaryData>>;
         fn get_token_id(&self, tx_id: &Id<Transaction>) -> crate::Result<Option<TokenId>>;
+        fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> crate::Result<Option<BTreeSet<TokenId>>>;
+        fn get_token_tickers_indexed(&self) -> crate::Result<bool>;
         fn get_tokens_accounting_undo(&self, id: Id<Block>) -> crate::Result<Option<accounting::BlockUndo<TokenAccountingUndo>>>;
         fn get_block_tree_by_height(
             &self,
@@ -528,7 +603,13 @@ mockall::mock! {
 
         fn get_block_map_keys(&self) -> crate::Result<BTreeSet<Id<Block>>>;
         fn get_block_index_map(&self) -> crate::Result<BTreeMap<Id<Block>, BlockIndex>>;
+        fn get_token_aux_data_map(&self) -> crate::Result<BTreeMap<TokenId, TokenAuxiliaryData>>;
+        fn get_tokens_data_map(&self) -> crate::Result<BTreeMap<TokenId, tokens_accounting::TokenData>>;
         fn get_block_by_height_map(&self) -> crate::Result<BTreeMap<BlockHeight, Id<GenBlock>>>;
+        fn for_each_utxo(
+            &self,
+            f: &mut dyn FnMut(UtxoOutPoint, Utxo) -> ControlFlow<()>,
+        ) -> crate::Result<()>;
     }
 
     impl EpochStorageRead for StoreTxRw {
@@ -609,6 +690,24 @@ mockall::mock! {
         fn del_block(&mut self, id: Id<Block>) -> crate::Result<()>;
 
         fn set_min_height_with_allowed_reorg(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_last_bootstrap_imported_block_id(&mut self, id: &Id<Block>) -> crate::Result<()>;
+        fn del_last_bootstrap_imported_block_id(&mut self) -> crate::Result<()>;
+        fn set_min_height_with_undo_data(&mut self, height: BlockHeight) -> crate::Result<()>;
+        fn set_pending_block_activations(&mut self, block_ids: &[Id<Block>]) -> crate::Result<()>;
+        fn set_chain_split_record(&mut self, record: &ChainSplitRecord) -> crate::Result<()>;
+        fn del_chain_split_record(&mut self, competing_tip_id: &Id<Block>) -> crate::Result<()>;
+        fn set_block_filter(
+            &mut self,
+            id: &Id<Block>,
+            filter: &CompactBlockFilter,
+            commitment: &BlockFilterCommitment,
+        ) -> crate::Result<()>;
+        fn set_pool_block(
+            &mut self,
+            pool_id: PoolId,
+            record: &PoolBlockRecord,
+        ) -> crate::Result<()>;
+        fn del_pool_block(&mut self, block_id: Id<Block>) -> crate::Result<()>;
 
         fn set_block_id_at_height(
             &mut self,
@@ -625,6 +724,9 @@ mockall::mock! {
 
         fn set_token_id(&mut self, issuance_tx_id: &Id<Transaction>, token_id: &TokenId) -> crate::Result<()>;
         fn del_token_id(&mut self, issuance_tx_id: &Id<Transaction>) -> crate::Result<()>;
+        fn set_token_ticker(&mut self, ticker: &[u8], token_ids: &BTreeSet<TokenId>) -> crate::Result<()>;
+        fn del_token_ticker(&mut self, ticker: &[u8]) -> crate::Result<()>;
+        fn set_token_tickers_indexed(&mut self, indexed: bool) -> crate::Result<()>;
 
         fn set_tokens_accounting_undo_data(
             &mut self,
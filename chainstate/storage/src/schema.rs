@@ -15,7 +15,12 @@
 
 //! Chainstate database schema
 
-use chainstate_types::{BlockIndex, EpochData};
+use std::collections::BTreeSet;
+
+use chainstate_types::{
+    BlockFilterCommitment, BlockIndex, ChainSplitRecord, CompactBlockFilter, EpochData,
+    PoolBlockRecord,
+};
 use common::{
     chain::{
         config::EpochIndex,
@@ -29,6 +34,7 @@ use orders_accounting::{OrderData, OrdersAccountingUndo};
 use pos_accounting::{
     DelegationData, DeltaMergeUndo, PoSAccountingDeltaData, PoSAccountingUndo, PoolData,
 };
+use storage::index::{IndexedDbMap, SecondaryIndex};
 use tokens_accounting::TokenAccountingUndo;
 use utxo::{Utxo, UtxosBlockUndo};
 
@@ -53,8 +59,21 @@ storage::decl_schema! {
         pub DBTokensAuxData: Map<TokenId, TokenAuxiliaryData>,
         /// Store of issuance tx id vs token id
         pub DBIssuanceTxVsTokenId: Map<Id<Transaction>, TokenId>,
+        /// Store of token ticker vs the ids of the existing tokens issued with it
+        pub DBTokenTickers: Map<Vec<u8>, BTreeSet<TokenId>>,
         /// Store the number of transactions per account
         pub DBAccountNonceCount: Map<AccountType, AccountNonce>,
+        /// Store for observed chain splits, indexed by the id of the competing tip
+        pub DBChainSplitRecords: Map<Id<Block>, ChainSplitRecord>,
+        /// Store for compact block filters, if the filter index is enabled
+        pub DBBlockFilters: Map<Id<Block>, CompactBlockFilter>,
+        /// Store for the hashes and headers of compact block filters
+        pub DBBlockFilterCommitments: Map<Id<Block>, BlockFilterCommitment>,
+        /// Store for the main chain blocks produced by stake pools, indexed by
+        /// `DBPoolBlocksByPool`
+        pub DBPoolBlocks: Map<Id<Block>, (PoolId, PoolBlockRecord)>,
+        /// Index of the pool blocks by the pool that produced them
+        pub DBPoolBlocksByPool: Map<(PoolId, Id<Block>), ()>,
 
         pub DBTokensData: Map<TokenId, tokens_accounting::TokenData>,
         pub DBTokensCirculatingSupply: Map<TokenId, Amount>,
@@ -99,3 +118,19 @@ storage::decl_schema! {
         pub DBAccountingPoolDelegationSharesSealed: Map<(PoolId, DelegationId), Amount>,
     }
 }
+
+pub struct PoolBlocksByPool;
+
+impl SecondaryIndex for PoolBlocksByPool {
+    type Primary = DBPoolBlocks;
+    type IndexKey = PoolId;
+    type IndexMap = DBPoolBlocksByPool;
+
+    fn index_keys(_block_id: &Id<Block>, (pool_id, _): &(PoolId, PoolBlockRecord)) -> Vec<PoolId> {
+        vec![*pool_id]
+    }
+}
+
+impl IndexedDbMap for DBPoolBlocks {
+    type Indices = (PoolBlocksByPool, ());
+}
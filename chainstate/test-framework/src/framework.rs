@@ -567,7 +567,7 @@ impl TestFramework {
     }
 
     pub fn to_chain_block_id(&self, block_id: &Id<GenBlock>) -> Id<Block> {
-        block_id.classify(self.chainstate.get_chain_config()).chain_block_id().unwrap()
+        block_id.to_block_id(self.chainstate.get_chain_config()).unwrap()
     }
 
     pub fn get_min_height_with_allowed_reorg(&self) -> BlockHeight {
@@ -16,13 +16,17 @@
 use serialization::Encode;
 use std::collections::BTreeSet;
 use std::io::BufWriter;
+use std::sync::{Arc, Mutex};
 
 use chainstate::chainstate_interface::ChainstateInterface;
-use chainstate::ChainstateConfig;
+use chainstate::{BootstrapError, BootstrapImportProgress, ChainstateConfig, ChainstateError};
+use chainstate_storage::{BlockchainStorageRead, Transactional};
 use chainstate_test_framework::TestFramework;
 use common::chain::Block;
 use common::primitives::Id;
 use common::primitives::Idable;
+use itertools::Itertools;
+use randomness::Rng;
 use rstest::rstest;
 use test_utils::random::make_seedable_rng;
 use test_utils::random::Seed;
@@ -190,7 +194,7 @@ fn bootstrap_tests(#[case] seed: Seed) {
             let reader: std::io::BufReader<Box<dyn std::io::Read + Send>> =
                 std::io::BufReader::new(Box::new(bootstrap_with_orphans.as_slice()));
 
-            tf2.chainstate.import_bootstrap_stream(reader).unwrap();
+            tf2.chainstate.import_bootstrap_stream(reader, Box::new(|_| {})).unwrap();
 
             assert_eq!(
                 tf2.chainstate.get_block_id_tree_as_list().unwrap(),
@@ -211,7 +215,7 @@ fn bootstrap_tests(#[case] seed: Seed) {
             let reader: std::io::BufReader<Box<dyn std::io::Read + Send>> =
                 std::io::BufReader::new(Box::new(bootstrap_no_orphans.as_slice()));
 
-            tf3.chainstate.import_bootstrap_stream(reader).unwrap();
+            tf3.chainstate.import_bootstrap_stream(reader, Box::new(|_| {})).unwrap();
 
             //Without the orphans the tf3 chain will only contain the mainchain
             assert_eq!(
@@ -234,7 +238,7 @@ fn bootstrap_tests(#[case] seed: Seed) {
             let reader: std::io::BufReader<Box<dyn std::io::Read + Send>> =
                 std::io::BufReader::new(Box::new(bootstrap_with_orphans.as_slice()));
 
-            tf4.chainstate.import_bootstrap_stream(reader).unwrap();
+            tf4.chainstate.import_bootstrap_stream(reader, Box::new(|_| {})).unwrap();
 
             assert_eq!(
                 tf4.chainstate.get_block_id_tree_as_list().unwrap(),
@@ -255,7 +259,7 @@ fn bootstrap_tests(#[case] seed: Seed) {
             let reader: std::io::BufReader<Box<dyn std::io::Read + Send>> =
                 std::io::BufReader::new(Box::new(bootstrap_with_orphans.as_slice()));
 
-            tf5.chainstate.import_bootstrap_stream(reader).unwrap();
+            tf5.chainstate.import_bootstrap_stream(reader, Box::new(|_| {})).unwrap();
 
             assert_eq!(
                 tf5.chainstate.get_block_id_tree_as_list().unwrap(),
@@ -264,3 +268,153 @@ fn bootstrap_tests(#[case] seed: Seed) {
         }
     });
 }
+
+/// Build a bootstrap file from the given blocks, in the given order.
+fn make_bootstrap_file(tf: &TestFramework, blocks: &[Id<Block>]) -> Vec<u8> {
+    let magic_bytes = tf.chain_config().magic_bytes().bytes();
+    blocks
+        .iter()
+        .flat_map(|id| magic_bytes.iter().copied().chain(tf.block(*id).encode()))
+        .collect()
+}
+
+fn make_reader(data: Vec<u8>) -> std::io::BufReader<Box<dyn std::io::Read + Send>> {
+    std::io::BufReader::new(Box::new(std::io::Cursor::new(data)))
+}
+
+// Interrupt an import by truncating the file in the middle of a block, then import the full file
+// and check that the import is resumed, skipping the already imported blocks.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn bootstrap_import_resume(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf1.genesis().get_id();
+        tf1.create_chain(&genesis_id.into(), rng.gen_range(10..30), &mut rng).unwrap();
+
+        let mainchain = tf1.chainstate.get_mainchain_blocks_list().unwrap();
+        let largest_block_size =
+            mainchain.iter().map(|id| tf1.block(*id).encoded_size()).max().unwrap();
+        let bootstrap_file = make_bootstrap_file(&tf1, &mainchain);
+
+        let mut tf2 = TestFramework::builder(&mut rng)
+            .with_chainstate_config(
+                ChainstateConfig::new()
+                    .with_max_orphan_blocks(0)
+                    .with_bootstrap_buffer_sizes((largest_block_size, 2 * largest_block_size)),
+            )
+            .build();
+
+        // Cut the file somewhere inside the last block.
+        let last_block_size = tf1.block(*mainchain.last().unwrap()).encoded_size();
+        let truncated_file =
+            bootstrap_file[..bootstrap_file.len() - rng.gen_range(1..last_block_size)].to_vec();
+
+        let result = tf2
+            .chainstate
+            .import_bootstrap_stream(make_reader(truncated_file), Box::new(|_| {}));
+        assert!(matches!(
+            result,
+            Err(ChainstateError::BootstrapError(
+                BootstrapError::Deserialization(_)
+            ))
+        ));
+
+        let last_imported_block_id = tf2
+            .storage
+            .transaction_ro()
+            .unwrap()
+            .get_last_bootstrap_imported_block_id()
+            .unwrap()
+            .unwrap();
+        let imported_blocks_count =
+            mainchain.iter().position(|id| *id == last_imported_block_id).unwrap() + 1;
+        assert!(imported_blocks_count < mainchain.len());
+        assert_eq!(tf2.best_block_id(), last_imported_block_id.into());
+
+        // Import the full file.
+        let reported_progress = Arc::new(Mutex::new(Vec::new()));
+        let progress_callback = {
+            let reported_progress = Arc::clone(&reported_progress);
+            Box::new(move |progress: BootstrapImportProgress| {
+                reported_progress.lock().unwrap().push(progress);
+            })
+        };
+        tf2.chainstate
+            .import_bootstrap_stream(make_reader(bootstrap_file.clone()), progress_callback)
+            .unwrap();
+
+        assert_eq!(
+            tf2.chainstate.get_mainchain_blocks_list().unwrap(),
+            mainchain
+        );
+        assert_eq!(
+            tf2.storage
+                .transaction_ro()
+                .unwrap()
+                .get_last_bootstrap_imported_block_id()
+                .unwrap(),
+            None
+        );
+
+        let reported_progress = reported_progress.lock().unwrap();
+        let final_progress = reported_progress.last().unwrap();
+        assert_eq!(
+            *final_progress,
+            BootstrapImportProgress {
+                resumed_after: Some(last_imported_block_id),
+                blocks_imported: (mainchain.len() - imported_blocks_count) as u64,
+                blocks_skipped: imported_blocks_count as u64,
+                bytes_read: bootstrap_file.len() as u64,
+            }
+        );
+        assert!(reported_progress
+            .iter()
+            .tuple_windows()
+            .all(|(prev, next)| prev.bytes_read < next.bytes_read));
+    });
+}
+
+// A block whose parent neither is known nor precedes it in the file must be rejected before any
+// block of its chunk is processed.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn bootstrap_import_unknown_parent(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf1.genesis().get_id();
+        tf1.create_chain(&genesis_id.into(), rng.gen_range(2..20), &mut rng).unwrap();
+
+        let mut blocks = tf1.chainstate.get_mainchain_blocks_list().unwrap();
+        let swap_idx = rng.gen_range(0..blocks.len() - 1);
+        blocks.swap(swap_idx, swap_idx + 1);
+        let bootstrap_file = make_bootstrap_file(&tf1, &blocks);
+
+        // Huge buffers, so that the whole file is a single chunk.
+        let mut tf2 = TestFramework::builder(&mut rng)
+            .with_chainstate_config(
+                ChainstateConfig::new()
+                    .with_max_orphan_blocks(0)
+                    .with_bootstrap_buffer_sizes((usize::MAX / 2, usize::MAX / 2)),
+            )
+            .build();
+
+        let result = tf2
+            .chainstate
+            .import_bootstrap_stream(make_reader(bootstrap_file), Box::new(|_| {}));
+        assert_eq!(
+            result,
+            Err(ChainstateError::BootstrapError(
+                BootstrapError::UnknownParent {
+                    block_id: blocks[swap_idx],
+                    parent_id: blocks[swap_idx + 1].into(),
+                }
+            ))
+        );
+        assert_eq!(tf2.best_block_id(), genesis_id.into());
+    });
+}
@@ -0,0 +1,171 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rstest::rstest;
+
+use chainstate::{BlockSource, CHAIN_SPLIT_RECORD_MAX_DEPTH, MAX_CHAIN_SPLIT_RECORDS};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::{Block, GenBlock},
+    primitives::{BlockHeight, Id, Idable},
+};
+use p2p_types::PeerId;
+use randomness::{CryptoRng, Rng};
+use test_utils::random::{make_seedable_rng, Seed};
+
+fn max_depth() -> usize {
+    CHAIN_SPLIT_RECORD_MAX_DEPTH.to_int() as usize
+}
+
+// Create a mainchain and return its block ids, with the genesis id at index 0.
+fn make_mainchain(
+    tf: &mut TestFramework,
+    blocks_count: usize,
+    rng: &mut (impl Rng + CryptoRng),
+) -> Vec<Id<GenBlock>> {
+    let genesis_id = tf.genesis().get_id().into();
+    std::iter::once(genesis_id)
+        .chain(tf.create_chain_return_ids(&genesis_id, blocks_count, rng).unwrap())
+        .collect()
+}
+
+// Process a block on top of the given parent, as if it was received from the given peer.
+fn process_stale_block_from_peer(
+    tf: &mut TestFramework,
+    parent_id: Id<GenBlock>,
+    peer_id: PeerId,
+    rng: &mut (impl Rng + CryptoRng),
+) -> Id<Block> {
+    let block = tf
+        .make_block_builder()
+        .add_test_transaction_with_parent(parent_id, rng)
+        .with_parent(parent_id)
+        .build(rng);
+    let block_id = block.get_id();
+    let result = tf.process_block(block, BlockSource::Peer(peer_id)).unwrap();
+    assert!(result.is_none());
+    block_id
+}
+
+// A stale block within the allowed depth from the tip produces a record; a subsequent reorg
+// to the competing branch produces another one.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn competing_tips_within_depth_are_recorded(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let main_len = rng.gen_range(1..20);
+        let mainchain = make_mainchain(&mut tf, main_len, &mut rng);
+        let main_tip_id = mainchain.last().unwrap().to_block_id(tf.chain_config());
+        let main_tip_id = main_tip_id.unwrap();
+
+        // The stale block's height is in [main_len - max_depth, main_len].
+        let fork_height = rng.gen_range(main_len.saturating_sub(max_depth() + 1)..main_len);
+        let stale_block_id =
+            process_stale_block_from_peer(&mut tf, mainchain[fork_height], PeerId::new(), &mut rng);
+
+        let records = tf.chainstate.get_chain_split_records().unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.best_tip().block_id(), &main_tip_id);
+        assert_eq!(
+            record.best_tip().block_height(),
+            BlockHeight::new(main_len as u64)
+        );
+        assert!(record.best_tip().first_seen().is_some());
+        assert_eq!(record.competing_tip().block_id(), &stale_block_id);
+        assert_eq!(
+            record.competing_tip().block_height(),
+            BlockHeight::new(fork_height as u64 + 1)
+        );
+        assert!(record.competing_tip().first_seen().is_some());
+
+        // Extend the competing branch so that it becomes the mainchain.
+        let new_tip_id = tf
+            .create_chain(&stale_block_id.into(), main_len - fork_height, &mut rng)
+            .unwrap();
+        assert_eq!(tf.best_block_id(), new_tip_id);
+
+        let records = tf.chainstate.get_chain_split_records().unwrap();
+        let reorg_record = records
+            .iter()
+            .find(|record| record.competing_tip().block_id() == &main_tip_id)
+            .unwrap();
+        assert_eq!(
+            Id::<GenBlock>::from(*reorg_record.best_tip().block_id()),
+            new_tip_id
+        );
+        assert_eq!(
+            reorg_record.best_tip().block_height(),
+            BlockHeight::new(main_len as u64 + 1)
+        );
+    });
+}
+
+// Blocks that just extend the tip and stale blocks that are too far behind it are not recorded.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn deep_or_non_competing_blocks_are_not_recorded(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let main_len = rng.gen_range(max_depth() + 2..max_depth() + 20);
+        let mainchain = make_mainchain(&mut tf, main_len, &mut rng);
+        assert_eq!(tf.chainstate.get_chain_split_records().unwrap(), vec![]);
+
+        // The stale block's height is below main_len - max_depth.
+        let fork_height = rng.gen_range(0..main_len - max_depth() - 1);
+        process_stale_block_from_peer(&mut tf, mainchain[fork_height], PeerId::new(), &mut rng);
+
+        assert_eq!(tf.chainstate.get_chain_split_records().unwrap(), vec![]);
+    });
+}
+
+// Only the latest records are kept.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn number_of_records_is_limited(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let main_len = rng.gen_range(1..10);
+        let mainchain = make_mainchain(&mut tf, main_len, &mut rng);
+
+        let stale_blocks_count = MAX_CHAIN_SPLIT_RECORDS + rng.gen_range(1..10);
+        let mut last_stale_block_id = None;
+        for _ in 0..stale_blocks_count {
+            let parent_id = mainchain[main_len - 1];
+            last_stale_block_id = Some(process_stale_block_from_peer(
+                &mut tf,
+                parent_id,
+                PeerId::new(),
+                &mut rng,
+            ));
+        }
+
+        let records = tf.chainstate.get_chain_split_records().unwrap();
+        assert_eq!(records.len(), MAX_CHAIN_SPLIT_RECORDS);
+        assert!(records
+            .iter()
+            .any(|record| Some(*record.competing_tip().block_id()) == last_stale_block_id));
+    });
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+
+use rstest::rstest;
+
+use chainstate::{ChainTipStatus, ChainstateConfig, ChainstateEvent};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::GenBlock,
+    primitives::{BlockHeight, Id, Idable},
+};
+use randomness::Rng;
+use test_utils::random::{make_seedable_rng, Seed};
+
+// The main chain tip comes first, followed by the tips of the forks with their branch lengths.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn chain_tips(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+
+        let tips = tf.chainstate.get_chain_tips().unwrap();
+        assert_eq!(tips.len(), 1);
+        assert_eq!(tips[0].block_id, genesis_id);
+        assert_eq!(tips[0].status, ChainTipStatus::Active);
+
+        let main_len = rng.gen_range(2..20);
+        let mainchain = tf.create_chain_return_ids(&genesis_id, main_len, &mut rng).unwrap();
+        let main_tip_id = *mainchain.last().unwrap();
+
+        // Fork off the main chain, keeping the fork shorter than the main chain.
+        let fork_height = rng.gen_range(0..main_len - 1);
+        let fork_len = rng.gen_range(1..main_len - fork_height);
+        let fork_parent_id = if fork_height == 0 {
+            genesis_id
+        } else {
+            mainchain[fork_height - 1]
+        };
+        let fork_tip_id = tf.create_chain(&fork_parent_id, fork_len, &mut rng).unwrap();
+        assert_eq!(tf.best_block_id(), main_tip_id);
+
+        let tips = tf.chainstate.get_chain_tips().unwrap();
+        assert_eq!(tips.len(), 2);
+
+        assert_eq!(tips[0].block_id, main_tip_id);
+        assert_eq!(tips[0].block_height, BlockHeight::new(main_len as u64));
+        assert_eq!(tips[0].branch_length, 0);
+        assert_eq!(tips[0].status, ChainTipStatus::Active);
+
+        assert_eq!(tips[1].block_id, fork_tip_id);
+        assert_eq!(
+            tips[1].block_height,
+            BlockHeight::new((fork_height + fork_len) as u64)
+        );
+        assert_eq!(tips[1].branch_length, fork_len as u64);
+        assert_eq!(tips[1].status, ChainTipStatus::ValidFork);
+        assert!(tips[1].chain_trust < tips[0].chain_trust);
+    });
+}
+
+// Extending a fork beyond the configured depth produces a LongFork event for each new block.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn long_fork_events(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let fork_alert_depth = rng.gen_range(1..5);
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(ChainstateConfig::new().with_fork_alert_depth(fork_alert_depth))
+            .build();
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_ = Arc::clone(&events);
+        tf.chainstate
+            .subscribe_to_subsystem_events(Arc::new(move |event: ChainstateEvent| match event {
+                ChainstateEvent::NewTip(_, _) => {}
+                ChainstateEvent::LongFork(block_id, _, branch_length) => {
+                    events_.lock().unwrap().push((block_id, branch_length));
+                }
+            }));
+
+        let main_len = fork_alert_depth as usize + 10;
+        let mainchain = tf.create_chain_return_ids(&genesis_id, main_len, &mut rng).unwrap();
+
+        let fork_len = rng.gen_range(1..main_len - 1);
+        let fork = tf.create_chain_return_ids(&mainchain[0], fork_len, &mut rng).unwrap();
+        tf.chainstate.wait_for_all_events();
+
+        let expected_events = fork
+            .iter()
+            .zip(1..)
+            .filter(|(_, branch_length)| *branch_length > fork_alert_depth)
+            .map(|(block_id, branch_length)| {
+                (
+                    block_id.to_block_id(tf.chain_config()).unwrap(),
+                    branch_length,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(*events.lock().unwrap(), expected_events);
+    });
+}
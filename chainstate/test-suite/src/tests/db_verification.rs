@@ -0,0 +1,148 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rstest::rstest;
+
+use chainstate::{BlockIndex, ChainstateConfig, DbVerificationError, DbVerificationLevel};
+use chainstate_storage::{BlockchainStorageWrite, TransactionRw, Transactional};
+use chainstate_test_framework::TestFramework;
+use chainstate_types::{BlockStatus, BlockValidationStage};
+use common::{
+    chain::{block::timestamp::BlockTimestamp, OutPointSourceId, UtxoOutPoint},
+    primitives::Idable,
+};
+use randomness::Rng;
+use test_utils::random::{make_seedable_rng, Seed};
+
+use super::helpers::block_creation_helpers::build_block;
+
+const ALL_LEVELS: [DbVerificationLevel; 3] = [
+    DbVerificationLevel::BlockIndex,
+    DbVerificationLevel::Utxos,
+    DbVerificationLevel::Accounting,
+];
+
+// A freshly built chain must pass all the checks.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn consistent_chain(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_id = tf.genesis().get_id().into();
+        let blocks_count = rng.gen_range(1..20);
+        tf.create_chain(&genesis_id, blocks_count, &mut rng).unwrap();
+
+        for level in ALL_LEVELS {
+            let depth = rng.gen_range(0..blocks_count * 2);
+            assert_eq!(tf.chainstate.verify_database(level, depth).unwrap(), vec![]);
+        }
+    });
+}
+
+// Remove a utxo created by the tip block; only the utxo-related levels must notice it.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn missing_utxo(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_id = tf.genesis().get_id().into();
+        let blocks_count = rng.gen_range(1..20);
+        let tip_id = tf.create_chain(&genesis_id, blocks_count, &mut rng).unwrap();
+        let tip_id = tip_id.to_block_id(tf.chain_config()).unwrap();
+
+        let tip = tf.block(tip_id);
+        let outpoint = UtxoOutPoint::new(
+            OutPointSourceId::Transaction(tip.transactions()[0].transaction().get_id()),
+            0,
+        );
+
+        let mut tx_rw = tf.storage.transaction_rw(None).unwrap();
+        tx_rw.del_utxo(&outpoint).unwrap();
+        tx_rw.commit().unwrap();
+
+        assert_eq!(
+            tf.chainstate
+                .verify_database(DbVerificationLevel::BlockIndex, blocks_count)
+                .unwrap(),
+            vec![]
+        );
+
+        // Depth 0 means that no blocks are re-derived.
+        assert_eq!(
+            tf.chainstate.verify_database(DbVerificationLevel::Utxos, 0).unwrap(),
+            vec![]
+        );
+
+        let depth = rng.gen_range(1..=blocks_count);
+        for level in [DbVerificationLevel::Utxos, DbVerificationLevel::Accounting] {
+            assert_eq!(
+                tf.chainstate.verify_database(level, depth).unwrap(),
+                vec![DbVerificationError::CreatedUtxoMismatch {
+                    block_id: tip_id,
+                    outpoint: outpoint.clone()
+                }]
+            );
+        }
+    });
+}
+
+// Store a block index with the persistence flag set, but without storing the corresponding block.
+// The problems must be reported instead of causing a panic.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn persisted_block_index_without_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(ChainstateConfig::new().with_heavy_checks_enabled(false))
+            .build();
+
+        let genesis_id = tf.chain_config().genesis_block_id();
+        let block = build_block(&mut tf, &genesis_id, &mut rng);
+        let block_id = block.get_id();
+
+        let block_index = BlockIndex::new(
+            &block,
+            1u64.into(),
+            genesis_id,
+            1.into(),
+            BlockTimestamp::from_time(tf.current_time()),
+            0,
+            BlockStatus::new_at_stage(BlockValidationStage::FullyChecked),
+        )
+        .make_persisted();
+
+        let mut tx_rw = tf.storage.transaction_rw(None).unwrap();
+        tx_rw.set_block_index(&block_index).unwrap();
+        tx_rw.commit().unwrap();
+
+        for level in ALL_LEVELS {
+            assert_eq!(
+                tf.chainstate.verify_database(level, rng.gen_range(0..10)).unwrap(),
+                vec![
+                    DbVerificationError::UnexpectedPersistedFlag(block_id),
+                    DbVerificationError::UnexpectedOkStatus(block_id),
+                ]
+            );
+        }
+    });
+}
@@ -29,6 +29,7 @@ use randomness::Rng;
 use rstest::rstest;
 use test_utils::random::make_seedable_rng;
 use test_utils::random::Seed;
+use utils_networking::broadcaster::OverflowPolicy;
 
 use crate::tests::EventList;
 use chainstate_test_framework::OrphanErrorHandler;
@@ -246,6 +247,7 @@ fn subscribe(chainstate: &mut TestChainstate, n: usize) -> EventList {
             ChainstateEvent::NewTip(block_id, block_height) => {
                 events_.lock().unwrap().push((block_id, block_height));
             }
+            ChainstateEvent::LongFork(_, _, _) => {}
         });
         chainstate.subscribe_to_subsystem_events(handler);
     }
@@ -312,3 +314,50 @@ async fn several_subscribers_several_events_broadcaster(#[case] seed: Seed) {
     // All receivers get the same sequence of events
     assert!(event_traces.into_iter().all(|t| t == expected_events));
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), OverflowPolicy::DropOldest)]
+#[case(Seed::from_entropy(), OverflowPolicy::DropNewest)]
+#[case(Seed::from_entropy(), OverflowPolicy::Disconnect)]
+#[tokio::test]
+async fn bounded_subscriber_overflow(#[case] seed: Seed, #[case] overflow_policy: OverflowPolicy) {
+    let mut rng = make_seedable_rng(seed);
+    let mut tf = TestFramework::builder(&mut rng).build();
+
+    let capacity = rng.gen_range(1..8);
+    let blocks = capacity + rng.gen_range(1..8);
+
+    let mut receiver = tf.chainstate.subscribe_to_bounded_rpc_events(capacity, overflow_policy);
+
+    let mut all_events = Vec::new();
+    for _ in 0..blocks {
+        let block = tf
+            .make_block_builder()
+            .add_test_transaction_from_best_block(&mut rng)
+            .build(&mut rng);
+        let index = tf.process_block(block.clone(), BlockSource::Local).ok().flatten().unwrap();
+        all_events.push(ChainstateEvent::NewTip(
+            *index.block_id(),
+            index.block_height(),
+        ));
+    }
+
+    std::mem::drop(tf);
+
+    let mut received_events = Vec::new();
+    while let Some(event) = receiver.recv().await {
+        received_events.push(event);
+    }
+
+    let expected_events = match overflow_policy {
+        OverflowPolicy::DropOldest => &all_events[blocks - capacity..],
+        OverflowPolicy::DropNewest => &all_events[..capacity],
+        OverflowPolicy::Disconnect => &all_events[..0],
+    };
+    assert_eq!(received_events, expected_events);
+    assert_eq!(
+        receiver.is_overflowed(),
+        overflow_policy == OverflowPolicy::Disconnect
+    );
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate::{BlockFilter, BlockSource, FilteredBlock, FilteredChainEvent};
+use chainstate_test_framework::{TestFramework, TransactionBuilder};
+use common::{
+    chain::{
+        output_value::OutputValue, signature::inputsig::InputWitness, Destination,
+        OutPointSourceId, TxOutput, UtxoOutPoint,
+    },
+    primitives::{Amount, BlockHeight, Idable},
+};
+use crypto::key::{KeyKind, PrivateKey};
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn filtered_events_and_rescan(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id = tf.genesis().get_id();
+
+        let dest =
+            Destination::PublicKey(PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).1);
+        let filter = BlockFilter::new([dest.clone()].into(), [].into());
+        let mut receiver = tf.chainstate.subscribe_to_filtered_events(filter.clone());
+
+        let genesis_outpoint =
+            UtxoOutPoint::new(OutPointSourceId::BlockReward(genesis_id.into()), 0);
+        let make_tx = |atoms: u128| {
+            TransactionBuilder::new()
+                .add_input(
+                    genesis_outpoint.clone().into(),
+                    InputWitness::NoSignature(None),
+                )
+                .add_output(TxOutput::Transfer(
+                    OutputValue::Coin(Amount::from_atoms(atoms)),
+                    dest.clone(),
+                ))
+                .build()
+        };
+
+        // Only the first of the two blocks is relevant.
+        let tx1 = make_tx(1000);
+        let block1 = tf.make_block_builder().with_transactions(vec![tx1.clone()]).build(&mut rng);
+        let expected_block1 = FilteredBlock {
+            block_id: block1.get_id(),
+            block_height: BlockHeight::new(1),
+            reward_matches: false,
+            transactions: vec![tx1],
+        };
+        tf.process_block(block1, BlockSource::Local).unwrap();
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+
+        assert_eq!(
+            receiver.blocking_recv(),
+            Some(FilteredChainEvent::NewBlock(expected_block1.clone()))
+        );
+        assert_eq!(
+            tf.chainstate
+                .get_filtered_mainchain_blocks(&filter, BlockHeight::new(1), 10)
+                .unwrap(),
+            vec![expected_block1]
+        );
+
+        // A longer chain from genesis, whose last block is relevant, triggers a reorg.
+        let fork_tip = tf.create_chain(&genesis_id.into(), 2, &mut rng).unwrap();
+        let tx2 = make_tx(2000);
+        let block2 = tf
+            .make_block_builder()
+            .with_parent(fork_tip)
+            .with_transactions(vec![tx2.clone()])
+            .build(&mut rng);
+        let expected_block2 = FilteredBlock {
+            block_id: block2.get_id(),
+            block_height: BlockHeight::new(3),
+            reward_matches: false,
+            transactions: vec![tx2],
+        };
+        tf.process_block(block2, BlockSource::Local).unwrap();
+
+        assert_eq!(
+            receiver.blocking_recv(),
+            Some(FilteredChainEvent::Reorg(BlockHeight::new(0)))
+        );
+        assert_eq!(
+            receiver.blocking_recv(),
+            Some(FilteredChainEvent::NewBlock(expected_block2.clone()))
+        );
+        assert_eq!(
+            tf.chainstate
+                .get_filtered_mainchain_blocks(&filter, BlockHeight::new(1), 10)
+                .unwrap(),
+            vec![expected_block2]
+        );
+    });
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use ::tx_verifier::transaction_verifier::storage::{
     TransactionVerifierStorageError, TransactionVerifierStorageRef,
@@ -68,6 +68,17 @@ impl TransactionVerifierStorageRef for InMemoryStorageWrapper {
             .map_err(TransactionVerifierStorageError::from)
     }
 
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, TransactionVerifierStorageError> {
+        self.storage
+            .transaction_ro()
+            .unwrap()
+            .get_token_ids_by_ticker(ticker)
+            .map_err(TransactionVerifierStorageError::from)
+    }
+
     fn get_gen_block_index(
         &self,
         block_id: &Id<GenBlock>,
@@ -13,17 +13,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::num::NonZeroU64;
+
 use super::*;
-use chainstate::ChainstateError;
-use chainstate_test_framework::TestFramework;
+use chainstate::{BlockError, BlockFilterCommitment, ChainstateConfig, ChainstateError};
+use chainstate_storage::{
+    BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
+};
+use chainstate_test_framework::{
+    create_stake_pool_data_with_all_reward_to_staker, empty_witness, TestFramework,
+    TransactionBuilder,
+};
 use common::{
     chain::{
         config::{Builder as ChainConfigBuilder, ChainType},
-        NetUpgrades,
+        NetUpgrades, OutPointSourceId, PoolId, TxOutput, UtxoOutPoint,
     },
-    primitives::Id,
+    primitives::{Id, H256},
 };
+use crypto::vrf::{VRFKeyKind, VRFPrivateKey};
 use randomness::CryptoRng;
+use utxo::UtxosStorageRead;
 
 #[cfg(not(loom))]
 fn genesis_check_ok(num_blocks: u64, rng: &mut (impl Rng + CryptoRng)) {
@@ -144,3 +154,301 @@ fn genesis_check_err_nonempty_chain(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     genesis_check_err(rng.gen_range(2..100), &mut rng);
 }
+
+// The utxo set is ahead of the best block, as if the node was terminated after the utxo set
+// was updated but before the rest of the chainstate was; the utxo set must be rolled back
+// when the chainstate is started.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxos_ahead_of_best_block_rolled_back(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let num_blocks: u64 = rng.gen_range(1..10);
+    let num_blocks_ahead: u64 = rng.gen_range(1..10);
+
+    let (storage, best_block_id, expected_utxos) = {
+        let mut tf = TestFramework::builder(&mut rng).build();
+        for _ in 0..num_blocks {
+            tf.make_block_builder()
+                .add_test_transaction_from_best_block(&mut rng)
+                .build_and_process(&mut rng)
+                .unwrap();
+        }
+        let best_block_id = tf.best_block_id();
+        let expected_utxos = tf.storage.transaction_ro().unwrap().read_utxo_set().unwrap();
+
+        for _ in 0..num_blocks_ahead {
+            tf.make_block_builder()
+                .add_test_transaction_from_best_block(&mut rng)
+                .build_and_process(&mut rng)
+                .unwrap();
+        }
+
+        (tf.storage, best_block_id, expected_utxos)
+    };
+
+    // Move the best block back, leaving the utxo set as is
+    {
+        let mut db_tx = storage.transaction_rw(None).unwrap();
+        for height in (num_blocks + 1)..=(num_blocks + num_blocks_ahead) {
+            db_tx.del_block_id_at_height(&BlockHeight::new(height)).unwrap();
+        }
+        db_tx.set_best_block_id(&best_block_id).unwrap();
+        db_tx.commit().unwrap();
+    }
+
+    let tf = TestFramework::builder(&mut rng).with_storage(storage).build();
+    assert_eq!(tf.best_block_id(), best_block_id);
+
+    let db_tx = tf.storage.transaction_ro().unwrap();
+    assert_eq!(db_tx.get_best_block_for_utxos().unwrap(), best_block_id);
+    assert_eq!(db_tx.read_utxo_set().unwrap(), expected_utxos);
+}
+
+// The epoch seal is ahead of the best block, as if the node was terminated after the epoch was
+// sealed but before the best block was updated; the epoch must be unsealed when the chainstate
+// is started.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn epoch_seal_ahead_of_best_block_rolled_back(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    // Seal an epoch every block, one epoch behind the tip
+    let chain_config = ChainConfigBuilder::test_chain()
+        .epoch_length(NonZeroU64::new(1).unwrap())
+        .sealed_epoch_distance_from_tip(1)
+        .build();
+
+    let (storage, best_block_id) = {
+        let mut tf =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+
+        // The stake pool is created in epoch 1, so there is accounting data to seal
+        let (_, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+        let amount_to_stake = chain_config.min_stake_pool_pledge();
+        let (stake_pool_data, _) =
+            create_stake_pool_data_with_all_reward_to_staker(&mut rng, amount_to_stake, vrf_pk);
+        let genesis_outpoint = UtxoOutPoint::new(
+            OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+            0,
+        );
+        let tx = TransactionBuilder::new()
+            .add_input(genesis_outpoint.clone().into(), empty_witness(&mut rng))
+            .add_output(TxOutput::CreateStakePool(
+                PoolId::from_utxo(&genesis_outpoint),
+                Box::new(stake_pool_data),
+            ))
+            .build();
+        tf.make_block_builder().add_transaction(tx).build_and_process(&mut rng).unwrap();
+        let best_block_id = tf.best_block_id();
+
+        let db_tx = tf.storage.transaction_ro().unwrap();
+        assert!(db_tx.read_pos_accounting_data_sealed().unwrap().is_empty());
+        drop(db_tx);
+
+        // Connecting the next block seals epoch 1
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+
+        let db_tx = tf.storage.transaction_ro().unwrap();
+        assert!(!db_tx.read_pos_accounting_data_sealed().unwrap().is_empty());
+        assert!(db_tx.get_accounting_epoch_undo_delta(1).unwrap().is_some());
+        drop(db_tx);
+
+        (tf.storage, best_block_id)
+    };
+
+    // Move the best block back, leaving the epoch seal as is
+    {
+        let mut db_tx = storage.transaction_rw(None).unwrap();
+        db_tx.del_block_id_at_height(&BlockHeight::new(2)).unwrap();
+        db_tx.set_best_block_id(&best_block_id).unwrap();
+        db_tx.commit().unwrap();
+    }
+
+    let tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config)
+        .with_storage(storage)
+        .build();
+    assert_eq!(tf.best_block_id(), best_block_id);
+
+    let db_tx = tf.storage.transaction_ro().unwrap();
+    assert!(db_tx.read_pos_accounting_data_sealed().unwrap().is_empty());
+    assert!(db_tx.get_accounting_epoch_undo_delta(1).unwrap().is_none());
+    assert_eq!(db_tx.get_best_block_for_utxos().unwrap(), best_block_id);
+}
+
+// A block has been accepted but not activated, as if the node was terminated between the two;
+// the block must be activated when the chainstate is started.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pending_block_activation_finished_on_startup(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let num_blocks: usize = rng.gen_range(1..10);
+
+    let mut tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id().into();
+    let block_ids = tf.create_chain_return_ids(&genesis_id, num_blocks + 1, &mut rng).unwrap();
+    let prev_tip_id = block_ids[num_blocks - 1];
+    let tip_id = tf.to_chain_block_id(&block_ids[num_blocks]);
+    assert!(tf
+        .storage
+        .transaction_ro()
+        .unwrap()
+        .get_pending_block_activations()
+        .unwrap()
+        .is_empty());
+
+    // Disconnect the tip, keeping its block and its ok status
+    tf.chainstate.invalidate_block(&tip_id).unwrap();
+    tf.chainstate.reset_block_failure_flags(&tip_id).unwrap();
+    assert_eq!(tf.best_block_id(), prev_tip_id);
+
+    // Without the pending activation, the block stays where it is
+    let tf = tf.reload();
+    assert_eq!(tf.best_block_id(), prev_tip_id);
+
+    {
+        let mut db_tx = tf.storage.transaction_rw(None).unwrap();
+        db_tx.set_pending_block_activations(&[tip_id]).unwrap();
+        db_tx.commit().unwrap();
+    }
+
+    let tf = tf.reload();
+    assert_eq!(tf.best_block_id(), tip_id);
+    assert!(tf
+        .storage
+        .transaction_ro()
+        .unwrap()
+        .get_pending_block_activations()
+        .unwrap()
+        .is_empty());
+}
+
+/// Disconnect the tip, keeping its block and its ok status, and put it into the pending
+/// activation queue, as if its activation has failed for a reason unrelated to the block.
+fn make_tip_pending_activation(tf: &mut TestFramework) -> Id<Block> {
+    let tip_id = tf.to_chain_block_id(&tf.best_block_id());
+    tf.chainstate.invalidate_block(&tip_id).unwrap();
+    tf.chainstate.reset_block_failure_flags(&tip_id).unwrap();
+
+    let mut db_tx = tf.storage.transaction_rw(None).unwrap();
+    db_tx.set_pending_block_activations(&[tip_id]).unwrap();
+    db_tx.commit().unwrap();
+
+    tip_id
+}
+
+// A block whose activation has failed is submitted again; it must be activated instead of being
+// rejected as already processed.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pending_block_activation_retried_on_resubmission(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let mut tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id().into();
+    let prev_tip_id = tf.create_chain(&genesis_id, rng.gen_range(1..10), &mut rng).unwrap();
+    tf.create_chain(&prev_tip_id, 1, &mut rng).unwrap();
+
+    let tip_id = make_tip_pending_activation(&mut tf);
+    assert_eq!(tf.best_block_id(), prev_tip_id);
+
+    let block = tf.block(tip_id);
+    let new_tip_index = tf.process_block(block, BlockSource::Local).unwrap().unwrap();
+    assert_eq!(*new_tip_index.block_id(), tip_id);
+    assert_eq!(tf.best_block_id(), tip_id);
+    assert!(tf
+        .storage
+        .transaction_ro()
+        .unwrap()
+        .get_pending_block_activations()
+        .unwrap()
+        .is_empty());
+
+    // Now the block is really processed
+    let block = tf.block(tip_id);
+    assert_eq!(
+        tf.process_block(block, BlockSource::Local).unwrap_err(),
+        ChainstateError::ProcessBlockError(BlockError::BlockAlreadyProcessed(tip_id))
+    );
+}
+
+// A block whose activation has failed must be activated once another block is processed.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn pending_block_activation_retried_after_next_block(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let mut tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id().into();
+    let prev_tip_id = tf.create_chain(&genesis_id, rng.gen_range(2..10), &mut rng).unwrap();
+    tf.create_chain(&prev_tip_id, 1, &mut rng).unwrap();
+
+    let tip_id = make_tip_pending_activation(&mut tf);
+    assert_eq!(tf.best_block_id(), prev_tip_id);
+
+    // A stale block that doesn't become the tip by itself
+    let new_tip_index = tf
+        .make_block_builder()
+        .with_parent(genesis_id)
+        .build_and_process(&mut rng)
+        .unwrap()
+        .unwrap();
+    assert_eq!(*new_tip_index.block_id(), tip_id);
+    assert_eq!(tf.best_block_id(), tip_id);
+    assert!(tf
+        .storage
+        .transaction_ro()
+        .unwrap()
+        .get_pending_block_activations()
+        .unwrap()
+        .is_empty());
+}
+
+// The block filter index is enabled for an existing chain; the filters of the existing blocks
+// must be built when the chainstate is started.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_filters_built_for_existing_chain(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let num_blocks: u64 = rng.gen_range(1..10);
+
+    let storage = {
+        let mut tf = TestFramework::builder(&mut rng).build();
+        for _ in 0..num_blocks {
+            tf.make_block_builder()
+                .add_test_transaction_from_best_block(&mut rng)
+                .build_and_process(&mut rng)
+                .unwrap();
+        }
+        let tip_id = tf.to_chain_block_id(&tf.best_block_id());
+        assert_eq!(tf.chainstate.get_block_filter(&tip_id).unwrap(), None);
+
+        tf.storage
+    };
+
+    let tf = TestFramework::builder(&mut rng)
+        .with_chainstate_config(ChainstateConfig::new().with_block_filter_index_enabled(true))
+        .with_storage(storage)
+        .build();
+
+    let mut prev_filter_header = H256::zero();
+    for height in 1..=num_blocks {
+        let block_id = tf.to_chain_block_id(&tf.block_id(height));
+        let filter = tf.chainstate.get_block_filter(&block_id).unwrap().unwrap();
+        let commitment = tf.chainstate.get_block_filter_commitment(&block_id).unwrap().unwrap();
+        assert_eq!(
+            commitment,
+            BlockFilterCommitment::new(&filter, &prev_filter_header)
+        );
+        prev_filter_header = *commitment.filter_header();
+    }
+}
@@ -948,7 +948,7 @@ fn check_txs_non_pos(
 }
 
 fn invalidate_tip(tf: &mut TestFramework) {
-    let tip_id = tf.best_block_id().classify(tf.chain_config()).chain_block_id().unwrap();
+    let tip_id = tf.best_block_id().to_block_id(tf.chain_config()).unwrap();
     tf.chainstate.invalidate_block(&tip_id).unwrap();
 }
 
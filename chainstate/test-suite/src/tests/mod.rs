@@ -30,12 +30,16 @@ mod basic_tests;
 mod block_invalidation;
 mod block_status;
 mod bootstrap;
+mod chain_split;
+mod chain_tips;
 mod chainstate_accounting_storage_tests;
 mod chainstate_storage_tests;
 mod data_deposit;
+mod db_verification;
 mod delegation_tests;
 mod double_spend_tests;
 mod events_tests;
+mod filtered_events;
 mod framework_tests;
 mod fungible_tokens;
 mod fungible_tokens_v1;
@@ -52,6 +56,7 @@ mod nft_reorgs;
 mod nft_transfer;
 mod orders_tests;
 mod output_timelock;
+mod pool_blocks;
 mod pos_accounting_reorg;
 mod pos_maturity_settings;
 mod pos_processing_tests;
@@ -61,10 +66,15 @@ mod reorgs_tests;
 mod signature_tests;
 mod stake_pool_tests;
 mod syncing_tests;
+mod tip_queries;
+mod token_tickers;
 mod tx_fee;
 mod tx_verification_simulation;
 mod tx_verifier_among_threads;
 mod tx_verifier_disconnect;
+mod undo_data_pruning;
+mod utxo_diff;
+mod utxo_queries;
 
 mod helpers;
 
@@ -386,8 +386,7 @@ fn nft_reorgs_and_cleanup_data(#[case] seed: Seed) {
         let height = block_index.block_height();
         assert!(
             tf.chainstate.get_block_id_from_height(&height).unwrap().is_some_and(|id| &id
-                .classify(tf.chainstate.get_chain_config())
-                .chain_block_id()
+                .to_block_id(tf.chainstate.get_chain_config())
                 .unwrap()
                 != block_index.block_id())
         );
@@ -0,0 +1,141 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helpers::pos::create_custom_genesis_with_stake_pool;
+
+use chainstate::{PoolBlockRecord, PoolBlockStats};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::{
+        config::Builder as ConfigBuilder, ConsensusUpgrade, NetUpgrades, PoSChainConfigBuilder,
+        PoolId,
+    },
+    primitives::{Amount, BlockHeight, H256},
+    Uint256,
+};
+use crypto::{
+    key::{KeyKind, PrivateKey},
+    vrf::{VRFKeyKind, VRFPrivateKey},
+};
+use randomness::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+// Blocks produced by a pool are listed with their rewards and are removed from the list
+// once disconnected.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn blocks_by_pool(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let (vrf_sk, vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+    let (staking_sk, staking_pk) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+
+    let upgrades = vec![
+        (BlockHeight::new(0), ConsensusUpgrade::IgnoreConsensus),
+        (
+            BlockHeight::new(1),
+            ConsensusUpgrade::PoS {
+                initial_difficulty: Some(Uint256::MAX.into()),
+                config: PoSChainConfigBuilder::new_for_unit_test().build(),
+            },
+        ),
+    ];
+    let net_upgrades = NetUpgrades::initialize(upgrades).expect("valid net-upgrades");
+    let genesis = create_custom_genesis_with_stake_pool(staking_pk, vrf_pk);
+    let chain_config = ConfigBuilder::test_chain()
+        .consensus_upgrades(net_upgrades)
+        .genesis_custom(genesis)
+        .build();
+    let target_block_time = chain_config.target_block_spacing();
+    let genesis_pool_id: PoolId = H256::zero().into();
+    let other_pool_id: PoolId = H256::repeat_byte(1).into();
+
+    let mut tf = TestFramework::builder(&mut rng).with_chain_config(chain_config).build();
+    tf.progress_time_seconds_since_epoch(target_block_time.as_secs());
+
+    let num_blocks = rng.gen_range(2..6);
+    let mut expected_blocks = Vec::new();
+    for height in 1..=num_blocks {
+        tf.make_pos_block_builder()
+            .with_stake_pool_id(genesis_pool_id)
+            .with_stake_spending_key(staking_sk.clone())
+            .with_vrf_key(vrf_sk.clone())
+            .build_and_process(&mut rng)
+            .unwrap();
+        let block_id = tf.to_chain_block_id(&tf.best_block_id());
+
+        let height = BlockHeight::new(height);
+        let reward = tf.chain_config().block_subsidy_at_height(&height);
+        expected_blocks.push(PoolBlockRecord::new(block_id, height, reward));
+    }
+
+    let total_reward = |blocks: &[PoolBlockRecord]| {
+        blocks.iter().map(|b| b.reward()).sum::<Option<Amount>>().unwrap()
+    };
+
+    assert_eq!(
+        tf.chainstate
+            .get_blocks_by_pool(genesis_pool_id, BlockHeight::new(1), usize::MAX)
+            .unwrap(),
+        expected_blocks
+    );
+    assert_eq!(
+        tf.chainstate
+            .get_blocks_by_pool(genesis_pool_id, BlockHeight::new(2), 1)
+            .unwrap(),
+        expected_blocks[1..2]
+    );
+    assert_eq!(
+        tf.chainstate.get_pool_block_stats(genesis_pool_id).unwrap(),
+        PoolBlockStats {
+            block_count: num_blocks,
+            total_reward: total_reward(&expected_blocks),
+        }
+    );
+
+    // Other pools have no blocks
+    assert_eq!(
+        tf.chainstate
+            .get_blocks_by_pool(other_pool_id, BlockHeight::new(1), usize::MAX)
+            .unwrap(),
+        Vec::new()
+    );
+    assert_eq!(
+        tf.chainstate.get_pool_block_stats(other_pool_id).unwrap(),
+        PoolBlockStats {
+            block_count: 0,
+            total_reward: Amount::ZERO,
+        }
+    );
+
+    // Disconnect the tip
+    let tip = expected_blocks.pop().unwrap();
+    tf.chainstate.invalidate_block(tip.block_id()).unwrap();
+
+    assert_eq!(
+        tf.chainstate
+            .get_blocks_by_pool(genesis_pool_id, BlockHeight::new(1), usize::MAX)
+            .unwrap(),
+        expected_blocks
+    );
+    assert_eq!(
+        tf.chainstate.get_pool_block_stats(genesis_pool_id).unwrap(),
+        PoolBlockStats {
+            block_count: num_blocks - 1,
+            total_reward: total_reward(&expected_blocks),
+        }
+    );
+}
@@ -40,6 +40,7 @@ use crypto::{
     key::{KeyKind, PrivateKey},
     vrf::{VRFKeyKind, VRFPrivateKey},
 };
+use p2p_types::PeerId;
 use pos_accounting::PoSAccountingDeltaData;
 use randomness::Rng;
 use rstest::rstest;
@@ -949,13 +950,13 @@ fn pos_reorg_simple(#[case] seed: Seed) {
         .preliminary_headers_check(std::slice::from_ref(block_b.header()))
         .unwrap();
     let block_b = tf1.chainstate.preliminary_block_check(block_b).unwrap();
-    tf1.process_block(block_b, BlockSource::Peer).unwrap();
+    tf1.process_block(block_b, BlockSource::Peer(PeerId::new())).unwrap();
 
     tf1.chainstate
         .preliminary_headers_check(std::slice::from_ref(block_c.header()))
         .unwrap();
     let block_c = tf1.chainstate.preliminary_block_check(block_c).unwrap();
-    tf1.process_block(block_c, BlockSource::Peer).unwrap().unwrap();
+    tf1.process_block(block_c, BlockSource::Peer(PeerId::new())).unwrap().unwrap();
 
     assert_eq!(<Id<GenBlock>>::from(block_c_id), tf1.best_block_id());
 }
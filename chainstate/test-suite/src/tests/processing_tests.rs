@@ -55,6 +55,7 @@ use crypto::{
     key::{KeyKind, PrivateKey},
     vrf::{VRFKeyKind, VRFPrivateKey},
 };
+use p2p_types::PeerId;
 use randomness::Rng;
 use rstest::rstest;
 use test_utils::{
@@ -308,11 +309,8 @@ fn orphans_chains(#[case] seed: Seed) {
             last_block_index.block_height(),
             (MAX_ORPHANS_COUNT_IN_TEST as u64).into()
         );
-        let current_best = tf
-            .best_block_id()
-            .classify(tf.chainstate.get_chain_config())
-            .chain_block_id()
-            .unwrap();
+        let current_best =
+            tf.best_block_id().to_block_id(tf.chainstate.get_chain_config()).unwrap();
         assert_eq!(
             tf.block_index(&current_best).block_height(),
             (MAX_ORPHANS_COUNT_IN_TEST as u64).into()
@@ -456,8 +454,10 @@ fn straight_chain(#[case] seed: Seed) {
                 .with_parent(prev_block_id)
                 .add_test_transaction_with_parent(prev_block_id, &mut rng)
                 .build(&mut rng);
-            let new_block_index =
-                tf.process_block(new_block.clone(), BlockSource::Peer).unwrap().unwrap();
+            let new_block_index = tf
+                .process_block(new_block.clone(), BlockSource::Peer(PeerId::new()))
+                .unwrap()
+                .unwrap();
 
             assert_eq!(new_block_index.prev_block_id(), &prev_block_id);
             assert!(new_block_index.chain_trust() > block_index.chain_trust());
@@ -19,7 +19,7 @@ use rstest::rstest;
 
 use chainstate::{
     BlockError, BlockSource, ChainstateConfig, ChainstateError, CheckBlockError,
-    CheckBlockTransactionsError,
+    CheckBlockTransactionsError, LocatorSchedule,
 };
 use chainstate_test_framework::TestFramework;
 use chainstate_types::{BlockStatus, BlockValidationStage, PropertyQueryError};
@@ -33,7 +33,9 @@ use common::{
     Uint256,
 };
 use logging::log;
+use p2p_types::PeerId;
 use randomness::Rng;
+use serialization::Encode;
 use test_utils::{
     assert_matches,
     random::{make_seedable_rng, Seed},
@@ -172,7 +174,7 @@ fn get_mainchain_headers_by_locator(#[case] seed: Seed) {
                 .build(&mut rng);
             last_block_id = block.get_id().into();
             let header = block.header().clone();
-            tf.process_block(block, BlockSource::Peer).unwrap().unwrap();
+            tf.process_block(block, BlockSource::Peer(PeerId::new())).unwrap().unwrap();
             Some(header)
         })
         .take(headers_count)
@@ -194,6 +196,60 @@ fn get_mainchain_headers_by_locator(#[case] seed: Seed) {
     });
 }
 
+// Check a locator constructed from a block on a stale chain using a custom schedule.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn get_locator_from_block(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut btf = TestFramework::builder(&mut rng).build();
+        let genesis_id: Id<GenBlock> = btf.genesis().get_id().into();
+
+        let main_chain_len = rng.gen_range(100..150);
+        let main_tip_id = btf.create_chain(&genesis_id, main_chain_len, &mut rng).unwrap();
+
+        // The default schedule starting at the tip gives the usual locator.
+        assert_eq!(
+            btf.chainstate
+                .get_locator_from_block(&main_tip_id, LocatorSchedule::default())
+                .unwrap(),
+            btf.chainstate.get_locator().unwrap()
+        );
+
+        let fork_height: u64 = rng.gen_range(10..50);
+        let fork_id =
+            btf.chainstate.get_block_id_from_height(&fork_height.into()).unwrap().unwrap();
+        let stale_chain_len: usize = rng.gen_range(20..40);
+        let stale_chain_ids =
+            btf.create_chain_return_ids(&fork_id, stale_chain_len, &mut rng).unwrap();
+        assert_eq!(btf.best_block_id(), main_tip_id);
+
+        let schedule = LocatorSchedule::new(rng.gen_range(0..10), rng.gen_range(1..5));
+        let stale_tip_id = *stale_chain_ids.last().unwrap();
+        let locator = btf.chainstate.get_locator_from_block(&stale_tip_id, schedule).unwrap();
+
+        let stale_tip_height = fork_height as i64 + stale_chain_len as i64;
+        let expected = schedule
+            .distances()
+            .map(i64::from)
+            .take_while(|dist| *dist <= stale_tip_height)
+            .map(|dist| {
+                let height = stale_tip_height - dist;
+                if height > fork_height as i64 {
+                    stale_chain_ids[(height - fork_height as i64 - 1) as usize]
+                } else {
+                    btf.chainstate
+                        .get_block_id_from_height(&BlockHeight::new(height as u64))
+                        .unwrap()
+                        .unwrap()
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(locator.into_vec(), expected);
+    });
+}
+
 // Create two chains that only share the genesis block and verify that the header is attached to
 // the genesis.
 #[rstest]
@@ -265,7 +321,7 @@ fn get_headers_branching_chains(#[case] seed: Seed) {
 fn get_headers_for_ids(tf: &TestFramework, ids: &[Id<GenBlock>]) -> Vec<SignedBlockHeader> {
     let mut result = Vec::with_capacity(ids.len());
     for id in ids {
-        let id = id.classify(tf.chainstate.get_chain_config()).chain_block_id().unwrap();
+        let id = id.to_block_id(tf.chainstate.get_chain_config()).unwrap();
         let block_index = tf.block_index(&id);
         result.push(block_index.block_header().clone());
     }
@@ -651,8 +707,15 @@ fn initial_block_download(#[case] seed: Seed) {
                 max_orphan_blocks: Default::default(),
                 min_max_bootstrap_import_buffer_sizes: Default::default(),
                 max_tip_age: Duration::from_secs(1).into(),
+                fork_alert_depth: Default::default(),
+                max_block_index_cache_memory: Default::default(),
+                max_utxo_cache_memory: Default::default(),
+                max_orphan_blocks_memory: Default::default(),
+                max_cache_memory: Default::default(),
                 enable_heavy_checks: Some(true),
                 allow_checkpoints_mismatch: Default::default(),
+                enable_block_filter_index: Default::default(),
+                undo_data_retention_depth: Default::default(),
             })
             .with_initial_time_since_genesis(2)
             .build();
@@ -690,6 +753,151 @@ fn initial_block_download(#[case] seed: Seed) {
     });
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn ibd_status(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = chain::config::create_unit_test_config();
+        let block_spacing = chain_config.target_block_spacing().as_secs();
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config)
+            .with_chainstate_config(ChainstateConfig {
+                max_db_commit_attempts: Default::default(),
+                max_orphan_blocks: Default::default(),
+                min_max_bootstrap_import_buffer_sizes: Default::default(),
+                max_tip_age: Duration::from_secs(1).into(),
+                fork_alert_depth: Default::default(),
+                max_block_index_cache_memory: Default::default(),
+                max_utxo_cache_memory: Default::default(),
+                max_orphan_blocks_memory: Default::default(),
+                max_cache_memory: Default::default(),
+                enable_heavy_checks: Some(true),
+                allow_checkpoints_mismatch: Default::default(),
+                enable_block_filter_index: Default::default(),
+                undo_data_retention_depth: Default::default(),
+            })
+            .with_initial_time_since_genesis(100 * block_spacing)
+            .build();
+
+        let status = tf.chainstate.ibd_status().unwrap();
+        assert!(status.is_initial_block_download);
+        assert_eq!(status.best_block_height, BlockHeight::new(0));
+        assert_eq!(status.estimated_network_height, BlockHeight::new(100));
+        assert_eq!(status.verification_progress.value(), 0);
+
+        // A block in the middle between the genesis and the current time
+        let genesis_timestamp = tf.genesis().timestamp();
+        tf.make_block_builder()
+            .with_timestamp(genesis_timestamp.add_int_seconds(50 * block_spacing).unwrap())
+            .build_and_process(&mut rng)
+            .unwrap();
+
+        let status = tf.chainstate.ibd_status().unwrap();
+        assert!(status.is_initial_block_download);
+        assert_eq!(status.best_block_height, BlockHeight::new(1));
+        assert_eq!(status.estimated_network_height, BlockHeight::new(51));
+        assert_eq!(status.verification_progress.value(), 500);
+
+        // A fresh block finishes the initial block download
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+
+        let status = tf.chainstate.ibd_status().unwrap();
+        assert!(!status.is_initial_block_download);
+        assert_eq!(status.best_block_height, BlockHeight::new(2));
+        assert_eq!(status.estimated_network_height, BlockHeight::new(2));
+        assert_eq!(status.verification_progress.value(), 1000);
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn sync_progress(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = chain::config::create_unit_test_config();
+        let block_spacing = chain_config.target_block_spacing().as_secs();
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config)
+            .with_chainstate_config(ChainstateConfig {
+                max_db_commit_attempts: Default::default(),
+                max_orphan_blocks: Default::default(),
+                min_max_bootstrap_import_buffer_sizes: Default::default(),
+                max_tip_age: Duration::from_secs(1).into(),
+                fork_alert_depth: Default::default(),
+                max_block_index_cache_memory: Default::default(),
+                max_utxo_cache_memory: Default::default(),
+                max_orphan_blocks_memory: Default::default(),
+                max_cache_memory: Default::default(),
+                enable_heavy_checks: Some(true),
+                allow_checkpoints_mismatch: Default::default(),
+                enable_block_filter_index: Default::default(),
+                undo_data_retention_depth: Default::default(),
+            })
+            .with_initial_time_since_genesis(100 * block_spacing)
+            .build();
+
+        // Nothing to extrapolate the chain trust from yet
+        let progress = tf.chainstate.sync_progress().unwrap();
+        assert!(progress.is_initial_block_download);
+        assert_eq!(progress.estimated_network_height, BlockHeight::new(100));
+        assert_eq!(progress.validated_chain_trust, Uint256::ZERO.into());
+        assert_eq!(progress.estimated_network_chain_trust, Uint256::ZERO.into());
+        assert_eq!(progress.verification_progress.value(), 0);
+        assert_eq!(progress.estimated_seconds_remaining, None);
+
+        // Each block without consensus data has the chain trust of 1
+        let genesis_timestamp = tf.genesis().timestamp();
+        tf.make_block_builder()
+            .with_timestamp(genesis_timestamp.add_int_seconds(50 * block_spacing).unwrap())
+            .build_and_process(&mut rng)
+            .unwrap();
+
+        let progress = tf.chainstate.sync_progress().unwrap();
+        assert_eq!(progress.best_block_height, BlockHeight::new(1));
+        assert_eq!(progress.estimated_network_height, BlockHeight::new(51));
+        assert_eq!(progress.validated_chain_trust, Uint256::from_u64(1).into());
+        assert_eq!(
+            progress.estimated_network_chain_trust,
+            Uint256::from_u64(51).into()
+        );
+        assert_eq!(progress.verification_progress.value(), 19);
+        assert_eq!(progress.estimated_seconds_remaining, None);
+
+        // The second block is processed a second later, so the rate is 1 block per second
+        tf.progress_time_seconds_since_epoch(1);
+        tf.make_block_builder()
+            .with_timestamp(genesis_timestamp.add_int_seconds(60 * block_spacing).unwrap())
+            .build_and_process(&mut rng)
+            .unwrap();
+
+        let progress = tf.chainstate.sync_progress().unwrap();
+        assert_eq!(progress.best_block_height, BlockHeight::new(2));
+        assert_eq!(progress.estimated_network_height, BlockHeight::new(42));
+        assert_eq!(
+            progress.estimated_network_chain_trust,
+            Uint256::from_u64(42).into()
+        );
+        assert_eq!(progress.verification_progress.value(), 47);
+        assert_eq!(progress.estimated_seconds_remaining, Some(40));
+
+        // A fresh block finishes the sync
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+
+        let progress = tf.chainstate.sync_progress().unwrap();
+        assert!(!progress.is_initial_block_download);
+        assert_eq!(progress.estimated_network_height, BlockHeight::new(3));
+        assert_eq!(
+            progress.validated_chain_trust,
+            progress.estimated_network_chain_trust
+        );
+        assert_eq!(progress.verification_progress.value(), 1000);
+        assert_eq!(progress.estimated_seconds_remaining, Some(0));
+    });
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -727,7 +935,10 @@ fn header_check_for_orphan(#[case] seed: Seed) {
             ))
         );
 
-        let err = tf.chainstate.process_block(block, BlockSource::Peer).unwrap_err();
+        let err = tf
+            .chainstate
+            .process_block(block, BlockSource::Peer(PeerId::new()))
+            .unwrap_err();
         assert_eq!(
             err,
             ChainstateError::ProcessBlockError(BlockError::PrevBlockNotFoundForNewBlock(block_id))
@@ -1223,3 +1434,47 @@ fn preliminary_checks_for_existing_block(#[case] seed: Seed) {
         assert_empty_tx_error(&err);
     });
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn get_blocks(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_id = tf.genesis().get_id().into();
+        let block_ids = tf
+            .create_chain_return_ids(&genesis_id, 10, &mut rng)
+            .unwrap()
+            .into_iter()
+            .map(|id| tf.to_chain_block_id(&id))
+            .collect::<Vec<_>>();
+        let blocks = block_ids.iter().map(|id| tf.block(*id)).collect::<Vec<_>>();
+        let missing_block_id = Id::<Block>::random_using(&mut rng);
+
+        // Without a size limit, all the blocks are returned in the requested order, missing
+        // blocks are reported as None.
+        let ids = vec![block_ids[3], missing_block_id, block_ids[1], block_ids[7]];
+        let result = tf.chainstate.get_blocks(ids, usize::MAX).unwrap();
+        assert_eq!(
+            result,
+            vec![Some(blocks[3].clone()), None, Some(blocks[1].clone()), Some(blocks[7].clone())]
+        );
+
+        // The result is cut at the size limit.
+        let size_limit = blocks[0].encoded_size() + blocks[1].encoded_size();
+        let result = tf.chainstate.get_blocks(block_ids.clone(), size_limit).unwrap();
+        assert_eq!(
+            result,
+            vec![Some(blocks[0].clone()), Some(blocks[1].clone())]
+        );
+
+        // The first block is always returned, even if it's bigger than the limit.
+        let result = tf.chainstate.get_blocks(block_ids.clone(), 1).unwrap();
+        assert_eq!(result, vec![Some(blocks[0].clone())]);
+
+        let result = tf.chainstate.get_blocks(Vec::new(), usize::MAX).unwrap();
+        assert_eq!(result, Vec::new());
+    });
+}
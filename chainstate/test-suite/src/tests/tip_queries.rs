@@ -0,0 +1,62 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate_storage::{BlockchainStorageRead, Transactional};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::GenBlock,
+    primitives::{BlockHeight, Id, Idable},
+};
+use randomness::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+// Check that the cached tip stays in sync with the storage when the tip is moved by new blocks,
+// a reorg and a block invalidation.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn best_block_height_and_id_follows_tip(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+
+        let check_tip = |tf: &TestFramework, expected_height: u64| {
+            let stored_best_block_id =
+                tf.storage.transaction_ro().unwrap().get_best_block_id().unwrap().unwrap();
+            let (height, id) = tf.chainstate.get_best_block_height_and_id().unwrap();
+            assert_eq!(id, stored_best_block_id);
+            assert_eq!(height, BlockHeight::new(expected_height));
+            assert_eq!(tf.chainstate.get_best_block_id().unwrap(), id);
+            assert_eq!(tf.chainstate.get_best_block_height().unwrap(), height);
+        };
+
+        check_tip(&tf, 0);
+
+        let main_len = rng.gen_range(1..5);
+        tf.create_chain(&genesis_id, main_len, &mut rng).unwrap();
+        check_tip(&tf, main_len as u64);
+
+        // Reorg to a longer branch
+        let branch_ids = tf.create_chain_return_ids(&genesis_id, main_len + 1, &mut rng).unwrap();
+        check_tip(&tf, main_len as u64 + 1);
+
+        // Invalidating the branch tip moves the tip back by one block
+        let branch_tip_id = tf.to_chain_block_id(branch_ids.last().unwrap());
+        tf.chainstate.invalidate_block(&branch_tip_id).unwrap();
+        check_tip(&tf, main_len as u64);
+    });
+}
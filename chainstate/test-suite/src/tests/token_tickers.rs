@@ -0,0 +1,239 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use rstest::rstest;
+
+use chainstate::{BlockError, ChainstateError, ConnectTransactionError, TokensError};
+use chainstate_storage::{
+    BlockchainStorageRead, BlockchainStorageWrite, TransactionRw, Transactional,
+};
+use chainstate_test_framework::{
+    helpers::issue_token_from_block, TestFramework, TransactionBuilder,
+};
+use common::{
+    chain::{
+        config::Builder as ChainConfigBuilder,
+        signature::inputsig::InputWitness,
+        tokens::{IsTokenFreezable, TokenIssuance, TokenIssuanceV1, TokenTotalSupply},
+        ChainConfig, ChainstateUpgradeBuilder, Destination, GenBlock, NetUpgrades,
+        TokenTickerUniquenessActivated, TxOutput, UtxoOutPoint,
+    },
+    primitives::{BlockHeight, Id, Idable},
+};
+use randomness::{CryptoRng, Rng};
+use test_utils::{
+    random::{make_seedable_rng, Seed},
+    random_ascii_alphanumeric_string,
+};
+
+fn make_issuance(rng: &mut impl Rng, ticker: &[u8]) -> TokenIssuance {
+    TokenIssuance::V1(TokenIssuanceV1 {
+        token_ticker: ticker.to_vec(),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: random_ascii_alphanumeric_string(rng, 1..1024).as_bytes().to_vec(),
+        total_supply: TokenTotalSupply::Unlimited,
+        authority: Destination::AnyoneCanSpend,
+        is_freezable: IsTokenFreezable::No,
+    })
+}
+
+fn make_chain_config(ticker_uniqueness: TokenTickerUniquenessActivated) -> ChainConfig {
+    ChainConfigBuilder::test_chain()
+        .chainstate_upgrades(
+            NetUpgrades::initialize(vec![(
+                BlockHeight::zero(),
+                ChainstateUpgradeBuilder::latest()
+                    .token_ticker_uniqueness_activated(ticker_uniqueness)
+                    .build(),
+            )])
+            .unwrap(),
+        )
+        .genesis_unittest(Destination::AnyoneCanSpend)
+        .build()
+}
+
+fn make_test_framework(
+    rng: &mut (impl Rng + CryptoRng),
+    ticker_uniqueness: TokenTickerUniquenessActivated,
+) -> TestFramework {
+    TestFramework::builder(rng)
+        .with_chain_config(make_chain_config(ticker_uniqueness))
+        .build()
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn duplicate_ticker_rejected(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = make_test_framework(&mut rng, TokenTickerUniquenessActivated::Yes);
+        let ticker = random_ascii_alphanumeric_string(&mut rng, 1..5).as_bytes().to_vec();
+
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+        let genesis_outpoint = UtxoOutPoint::new(genesis_id.into(), 0);
+        let (token_id, block_id, change_outpoint) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            genesis_id,
+            genesis_outpoint,
+            make_issuance(&mut rng, &ticker),
+        );
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker).unwrap(),
+            Some(BTreeSet::from([token_id]))
+        );
+
+        let issuance = make_issuance(&mut rng, &ticker);
+        // The whole change goes to the issuance fee
+        let tx = TransactionBuilder::new()
+            .add_input(change_outpoint.into(), InputWitness::NoSignature(None))
+            .add_output(TxOutput::IssueFungibleToken(Box::new(issuance)))
+            .build();
+        let tx_id = tx.transaction().get_id();
+        let result = tf
+            .make_block_builder()
+            .add_transaction(tx)
+            .with_parent(block_id.into())
+            .build_and_process(&mut rng);
+
+        assert_eq!(
+            result.unwrap_err(),
+            ChainstateError::ProcessBlockError(BlockError::StateUpdateFailed(
+                ConnectTransactionError::TokensError(TokensError::TokenTickerAlreadyTaken(
+                    ticker.clone(),
+                    token_id,
+                    tx_id
+                ))
+            ))
+        );
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker).unwrap(),
+            Some(BTreeSet::from([token_id]))
+        );
+    });
+}
+
+// Before the upgrade, the ticker is indexed for all the tokens issued with it and is removed from
+// the index when all their issuances are disconnected.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn duplicate_ticker_allowed_before_activation(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = make_test_framework(&mut rng, TokenTickerUniquenessActivated::No);
+        let ticker = random_ascii_alphanumeric_string(&mut rng, 1..5).as_bytes().to_vec();
+
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+        let genesis_outpoint = UtxoOutPoint::new(genesis_id.into(), 0);
+        let (token_id1, block_id1, change_outpoint) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            genesis_id,
+            genesis_outpoint,
+            make_issuance(&mut rng, &ticker),
+        );
+        let (token_id2, _, _) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            block_id1.into(),
+            change_outpoint,
+            make_issuance(&mut rng, &ticker),
+        );
+        assert_ne!(token_id1, token_id2);
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker).unwrap(),
+            Some(BTreeSet::from([token_id1, token_id2]))
+        );
+
+        // Reorg the second issuance out
+        tf.create_chain(&block_id1.into(), 2, &mut rng).unwrap();
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker).unwrap(),
+            Some(BTreeSet::from([token_id1]))
+        );
+
+        // Reorg the first issuance out
+        tf.create_chain(&genesis_id, 4, &mut rng).unwrap();
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker).unwrap(),
+            None
+        );
+    });
+}
+
+// The ticker index is built on startup if it doesn't cover all the issued tokens, e.g. if the tokens
+// were issued before the index was introduced.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn missing_ticker_index_built_on_startup(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = make_test_framework(&mut rng, TokenTickerUniquenessActivated::No);
+        let ticker1 = random_ascii_alphanumeric_string(&mut rng, 1..5).as_bytes().to_vec();
+        let ticker2 = [ticker1.as_slice(), b"X"].concat();
+
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+        let genesis_outpoint = UtxoOutPoint::new(genesis_id.into(), 0);
+        let (token_id1, block_id1, change_outpoint) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            genesis_id,
+            genesis_outpoint,
+            make_issuance(&mut rng, &ticker1),
+        );
+        let (token_id2, block_id2, change_outpoint) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            block_id1.into(),
+            change_outpoint,
+            make_issuance(&mut rng, &ticker1),
+        );
+        let (token_id3, _, _) = issue_token_from_block(
+            &mut rng,
+            &mut tf,
+            block_id2.into(),
+            change_outpoint,
+            make_issuance(&mut rng, &ticker2),
+        );
+
+        let storage = tf.storage;
+        {
+            let mut db_tx = storage.transaction_rw(None).unwrap();
+            db_tx.del_token_ticker(&ticker1).unwrap();
+            db_tx.del_token_ticker(&ticker2).unwrap();
+            db_tx.set_token_tickers_indexed(false).unwrap();
+            db_tx.commit().unwrap();
+        }
+
+        let tf = TestFramework::builder(&mut rng)
+            .with_chain_config(make_chain_config(TokenTickerUniquenessActivated::No))
+            .with_storage(storage)
+            .build();
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker1).unwrap(),
+            Some(BTreeSet::from([token_id1, token_id2]))
+        );
+        assert_eq!(
+            tf.chainstate.get_token_ids_by_ticker(&ticker2).unwrap(),
+            Some(BTreeSet::from([token_id3]))
+        );
+        assert!(tf.storage.transaction_ro().unwrap().get_token_tickers_indexed().unwrap());
+    });
+}
@@ -89,7 +89,8 @@ fn simple_fee_from_coin_transfer(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -161,7 +162,8 @@ fn transfer_lock_and_burn_outputs_fee(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -232,7 +234,8 @@ fn locked_outputs_can_go_to_fee(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(2),
         };
 
@@ -284,7 +287,8 @@ fn create_stake_pool(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -370,7 +374,8 @@ fn delegate_staking(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -450,7 +455,8 @@ fn fee_from_decommissioning_stake_pool(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(2),
         };
 
@@ -540,7 +546,8 @@ fn fee_from_spending_delegation_share(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(2),
         };
 
@@ -610,7 +617,8 @@ fn issue_fungible_token_v0(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, tf.chain_config().as_ref());
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -658,7 +666,8 @@ fn issue_fungible_token_v1(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -752,7 +761,8 @@ fn tokens_cannot_be_used_in_fee(#[case] seed: Seed) {
         let mut verifier = TransactionVerifier::new(&storage, &chain_config);
 
         let tx_source = TransactionSourceForConnect::Mempool {
-            current_best: &tf.best_block_index(),
+            current_best_id: tf.best_block_id(),
+            current_best_height: tf.best_block_height(),
             effective_height: BlockHeight::new(1),
         };
 
@@ -765,3 +775,64 @@ fn tokens_cannot_be_used_in_fee(#[case] seed: Seed) {
         assert_eq!(expected_fee, actual_fee);
     });
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_fee_prediction_check(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let (_chain_config, _storage, mut tf) = setup(&mut rng);
+
+        let transfer_atoms = rng.gen_range(1..100_000);
+        let genesis_amount = chainstate_test_framework::get_output_value(&tf.genesis().utxos()[0])
+            .unwrap()
+            .coin_amount()
+            .unwrap();
+        let actual_fee = Fee((genesis_amount - Amount::from_atoms(transfer_atoms)).unwrap());
+
+        let tx = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(tf.genesis().get_id().into(), 0),
+                empty_witness(&mut rng),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(transfer_atoms)),
+                Destination::AnyoneCanSpend,
+            ))
+            .build();
+
+        // A block without a prediction is not checked
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+        let stats = tf.chainstate.get_block_fee_check_stats();
+        assert_eq!(stats.checked_blocks, 0);
+
+        // A block whose fees match the prediction
+        let block = tf.make_block_builder().build(&mut rng);
+        tf.chainstate.add_block_fee_prediction(block.get_id(), Fee(Amount::ZERO));
+        tf.process_block(block, BlockSource::Local).unwrap();
+        let stats = tf.chainstate.get_block_fee_check_stats();
+        assert_eq!(stats.checked_blocks, 1);
+        assert_eq!(stats.discrepancy_count, 0);
+        assert!(stats.recent_discrepancies.is_empty());
+
+        // A block whose fees differ from the prediction
+        let block = tf.make_block_builder().add_transaction(tx).build(&mut rng);
+        let block_id = block.get_id();
+        let predicted_fee = Fee((actual_fee.0 + Amount::from_atoms(1)).unwrap());
+        tf.chainstate.add_block_fee_prediction(block_id, predicted_fee);
+        tf.process_block(block, BlockSource::Local).unwrap();
+        let stats = tf.chainstate.get_block_fee_check_stats();
+        assert_eq!(stats.checked_blocks, 2);
+        assert_eq!(stats.discrepancy_count, 1);
+        assert_eq!(
+            stats.recent_discrepancies,
+            vec![chainstate::BlockFeeDiscrepancy {
+                block_id,
+                block_height: BlockHeight::new(3),
+                predicted_fees: predicted_fee.0,
+                actual_fees: actual_fee.0,
+            }]
+        );
+    });
+}
@@ -28,6 +28,7 @@ use crypto::{
     key::{KeyKind, PrivateKey},
     vrf::{VRFKeyKind, VRFPrivateKey},
 };
+use p2p_types::PeerId;
 
 #[rstest]
 #[trace]
@@ -144,9 +145,11 @@ fn simulation(#[case] seed: Seed, #[case] max_blocks: usize, #[case] max_tx_per_
 
             // submit common blocks to the alternative chain
             if i <= reorg_at_height {
-                tf2.process_block(block.clone(), BlockSource::Peer).unwrap();
+                tf2.process_block(block.clone(), BlockSource::Peer(PeerId::new())).unwrap();
                 tf2.progress_time_seconds_since_epoch(target_time.as_secs());
-                reference_tf.process_block(block.clone(), BlockSource::Peer).unwrap();
+                reference_tf
+                    .process_block(block.clone(), BlockSource::Peer(PeerId::new()))
+                    .unwrap();
                 reference_tf.progress_time_seconds_since_epoch(target_time.as_secs());
 
                 tf2.staking_pools = tf.staking_pools.clone();
@@ -180,8 +183,8 @@ fn simulation(#[case] seed: Seed, #[case] max_blocks: usize, #[case] max_tx_per_
             tf2.process_block(block.clone(), BlockSource::Local).unwrap();
 
             // submit alternative blocks to the original chain
-            tf.process_block(block.clone(), BlockSource::Peer).unwrap();
-            reference_tf.process_block(block, BlockSource::Peer).unwrap();
+            tf.process_block(block.clone(), BlockSource::Peer(PeerId::new())).unwrap();
+            reference_tf.process_block(block, BlockSource::Peer(PeerId::new())).unwrap();
             reference_tf.progress_time_seconds_since_epoch(target_time.as_secs());
         }
 
@@ -0,0 +1,200 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate::{
+    BlockError, BlockInvalidatorError, ChainstateConfig, ChainstateConfigError, ChainstateError,
+    InitializationError,
+};
+use chainstate_storage::{BlockchainStorageRead, Transactional};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::config::{create_unit_test_config_builder, ChainConfig},
+    primitives::{BlockDistance, BlockHeight, Idable},
+};
+use randomness::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+fn make_chain_config(max_depth_for_reorg: u64) -> ChainConfig {
+    create_unit_test_config_builder()
+        .max_depth_for_reorg(BlockDistance::new(max_depth_for_reorg as i64))
+        .build()
+}
+
+// The undo data is only kept for the last `retention_depth` blocks; the blocks below that
+// can't be disconnected, while the ones inside the window can.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn undo_data_pruned_below_retention_depth(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let retention_depth = rng.gen_range(1..5);
+        let blocks_count = retention_depth as usize + rng.gen_range(2..10);
+
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chainstate_config(
+                ChainstateConfig::new().with_undo_data_retention_depth(retention_depth),
+            )
+            .build();
+
+        let genesis_id = tf.genesis().get_id().into();
+        let block_ids = tf.create_chain_return_ids(&genesis_id, blocks_count, &mut rng).unwrap();
+        let block_ids = block_ids.iter().map(|id| tf.to_chain_block_id(id)).collect::<Vec<_>>();
+
+        // block_ids[i] is at height i + 1
+        let pruned_count = blocks_count - retention_depth as usize;
+        {
+            let db_tx = tf.storage.transaction_ro().unwrap();
+            assert_eq!(
+                db_tx.get_min_height_with_undo_data().unwrap(),
+                Some(BlockHeight::new(pruned_count as u64 + 1))
+            );
+            for (idx, block_id) in block_ids.iter().enumerate() {
+                let undo = db_tx.get_undo_data(*block_id).unwrap();
+                assert_eq!(undo.is_some(), idx >= pruned_count);
+            }
+        }
+
+        // The last pruned block can't be disconnected.
+        let last_pruned_block_id = block_ids[pruned_count - 1];
+        let err = tf.chainstate.invalidate_block(&last_pruned_block_id).unwrap_err();
+        assert_eq!(
+            err,
+            ChainstateError::BlockInvalidatorError(
+                BlockInvalidatorError::BlocksDisconnectionError {
+                    disconnect_until: tf.block_id(pruned_count as u64 - 1),
+                    error: Box::new(BlockError::UndoDataPruned {
+                        block_id: last_pruned_block_id,
+                        block_height: BlockHeight::new(pruned_count as u64),
+                        min_height_with_undo_data: BlockHeight::new(pruned_count as u64 + 1),
+                    }),
+                }
+            )
+        );
+        assert_eq!(tf.best_block_id(), (*block_ids.last().unwrap()).into());
+
+        // The blocks inside the retention window can be disconnected.
+        let first_retained_block_id = block_ids[pruned_count];
+        tf.chainstate.invalidate_block(&first_retained_block_id).unwrap();
+        assert_eq!(tf.best_block_id(), last_pruned_block_id.into());
+    });
+}
+
+// A reorg of the max allowed depth only needs the undo data inside the retention window.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn reorg_at_max_depth(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_depth_for_reorg = rng.gen_range(1..5);
+        let blocks_count = max_depth_for_reorg as usize + rng.gen_range(1..10);
+
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(make_chain_config(max_depth_for_reorg))
+            .with_chainstate_config(
+                ChainstateConfig::new().with_undo_data_retention_depth(max_depth_for_reorg),
+            )
+            .build();
+
+        let genesis_id = tf.genesis().get_id().into();
+        tf.create_chain(&genesis_id, blocks_count, &mut rng).unwrap();
+
+        let fork_height = blocks_count as u64 - max_depth_for_reorg;
+        let fork_point = tf.block_id(fork_height);
+        let fork_tip_id = tf
+            .create_chain(&fork_point, max_depth_for_reorg as usize + 1, &mut rng)
+            .unwrap();
+        assert_eq!(tf.best_block_id(), fork_tip_id);
+    });
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn retention_depth_below_max_reorg_depth(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_depth_for_reorg = rng.gen_range(2..10);
+        let retention_depth = rng.gen_range(0..max_depth_for_reorg);
+
+        let result = TestFramework::builder(&mut rng)
+            .with_chain_config(make_chain_config(max_depth_for_reorg))
+            .with_chainstate_config(
+                ChainstateConfig::new().with_undo_data_retention_depth(retention_depth),
+            )
+            .try_build();
+        assert_eq!(
+            result.err(),
+            Some(ChainstateError::FailedToInitializeChainstate(
+                InitializationError::InvalidConfig(
+                    ChainstateConfigError::UndoDataRetentionDepthTooLow {
+                        retention_depth,
+                        max_depth_for_reorg: BlockDistance::new(max_depth_for_reorg as i64),
+                    }
+                )
+            ))
+        );
+    });
+}
+
+// The compact filters can't be built for the blocks whose undo data has been pruned, so enabling
+// the filter index afterwards fails.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn block_filter_index_after_pruning(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let retention_depth = rng.gen_range(1..5);
+        let blocks_count = retention_depth + rng.gen_range(1..10);
+
+        let (storage, first_block_id) = {
+            let mut tf = TestFramework::builder(&mut rng)
+                .with_chain_config(make_chain_config(retention_depth))
+                .with_chainstate_config(
+                    ChainstateConfig::new().with_undo_data_retention_depth(retention_depth),
+                )
+                .build();
+            for _ in 0..blocks_count {
+                tf.make_block_builder()
+                    .add_test_transaction_from_best_block(&mut rng)
+                    .build_and_process(&mut rng)
+                    .unwrap();
+            }
+            let first_block_id = tf.to_chain_block_id(&tf.block_id(1));
+            (tf.storage, first_block_id)
+        };
+
+        let result = TestFramework::builder(&mut rng)
+            .with_chain_config(make_chain_config(retention_depth))
+            .with_chainstate_config(
+                ChainstateConfig::new()
+                    .with_undo_data_retention_depth(retention_depth)
+                    .with_block_filter_index_enabled(true),
+            )
+            .with_storage(storage)
+            .try_build();
+        assert_eq!(
+            result.err(),
+            Some(ChainstateError::FailedToInitializeChainstate(
+                InitializationError::BlockFilterIndexError(
+                    BlockError::UndoDataMissingForBlockFilter(first_block_id)
+                )
+            ))
+        );
+    });
+}
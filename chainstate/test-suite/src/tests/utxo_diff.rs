@@ -0,0 +1,139 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::num::NonZeroUsize;
+
+use chainstate::{BlockSource, ChainstateError, PropertyQueryError, UtxoDiffEntry};
+use chainstate_test_framework::{TestFramework, TransactionBuilder};
+use common::{
+    chain::{
+        output_value::OutputValue, signature::inputsig::InputWitness, Destination,
+        OutPointSourceId, TxInput, TxOutput, UtxoOutPoint,
+    },
+    primitives::{Amount, BlockHeight, Idable},
+};
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+fn transfer(atoms: u128) -> TxOutput {
+    TxOutput::Transfer(
+        OutputValue::Coin(Amount::from_atoms(atoms)),
+        Destination::AnyoneCanSpend,
+    )
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxo_diff_from_undo_data(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_outpoint = UtxoOutPoint::new(
+            OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+            0,
+        );
+        let genesis_output = tf.genesis().utxos()[0].clone();
+
+        // The second output of tx1 is spent in the same block; the burn isn't a utxo at all.
+        let tx1 = TransactionBuilder::new()
+            .add_input(
+                genesis_outpoint.clone().into(),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(transfer(1000))
+            .add_output(transfer(500))
+            .add_output(TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(1))))
+            .build();
+        let tx1_id = tx1.transaction().get_id();
+        let tx2 = TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(tx1_id.into(), 1),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(transfer(400))
+            .build();
+        let tx2_id = tx2.transaction().get_id();
+
+        let block1 = tf.make_block_builder().with_transactions(vec![tx1, tx2]).build(&mut rng);
+        let block1_id = block1.get_id();
+        tf.process_block(block1, BlockSource::Local).unwrap();
+        tf.make_block_builder().build_and_process(&mut rng).unwrap();
+
+        let entry = |outpoint: UtxoOutPoint, output: TxOutput| UtxoDiffEntry { outpoint, output };
+
+        let chunk = tf
+            .chainstate
+            .get_utxo_diff(
+                BlockHeight::new(1),
+                BlockHeight::new(100),
+                NonZeroUsize::MAX,
+            )
+            .unwrap();
+        assert_eq!(chunk.next_height, None);
+        assert_eq!(chunk.blocks.len(), 2);
+
+        let block1_diff = &chunk.blocks[0];
+        assert_eq!(block1_diff.block_id, block1_id);
+        assert_eq!(block1_diff.block_height, BlockHeight::new(1));
+        assert_eq!(
+            block1_diff.created,
+            vec![
+                entry(UtxoOutPoint::new(tx1_id.into(), 0), transfer(1000)),
+                entry(UtxoOutPoint::new(tx1_id.into(), 1), transfer(500)),
+                entry(UtxoOutPoint::new(tx2_id.into(), 0), transfer(400)),
+            ]
+        );
+        assert_eq!(
+            block1_diff.spent,
+            vec![
+                entry(genesis_outpoint, genesis_output),
+                entry(UtxoOutPoint::new(tx1_id.into(), 1), transfer(500)),
+            ]
+        );
+        assert!(chunk.blocks[1].spent.is_empty());
+
+        // The limit is checked after each block, so the first chunk contains just block1.
+        let chunk = tf
+            .chainstate
+            .get_utxo_diff(BlockHeight::new(1), BlockHeight::new(3), NonZeroUsize::MIN)
+            .unwrap();
+        assert_eq!(chunk.blocks.len(), 1);
+        assert_eq!(chunk.blocks[0].block_id, block1_id);
+        assert_eq!(chunk.next_height, Some(BlockHeight::new(2)));
+
+        let chunk = tf
+            .chainstate
+            .get_utxo_diff(BlockHeight::new(2), BlockHeight::new(3), NonZeroUsize::MIN)
+            .unwrap();
+        assert_eq!(chunk.blocks.len(), 1);
+        assert_eq!(chunk.blocks[0].block_height, BlockHeight::new(2));
+        assert_eq!(chunk.next_height, None);
+
+        assert_eq!(
+            tf.chainstate.get_utxo_diff(
+                BlockHeight::zero(),
+                BlockHeight::new(3),
+                NonZeroUsize::MIN
+            ),
+            Err(ChainstateError::FailedToReadProperty(
+                PropertyQueryError::InvalidStartingBlockHeightForMainchainBlocks(
+                    BlockHeight::zero()
+                )
+            ))
+        );
+    });
+}
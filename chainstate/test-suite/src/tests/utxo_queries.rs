@@ -0,0 +1,269 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chainstate::{BlockSource, ChainstateError};
+use chainstate_storage::Transactional;
+use chainstate_test_framework::{TestFramework, TransactionBuilder};
+use chainstate_types::PropertyQueryError;
+use common::{
+    chain::{
+        config::create_unit_test_config_builder, output_value::OutputValue,
+        signature::inputsig::InputWitness, Destination, GenBlock, OutPointSourceId,
+        SignedTransaction, TxOutput, UtxoOutPoint,
+    },
+    primitives::{Amount, BlockDistance, BlockHeight, Id, Idable},
+};
+use randomness::Rng;
+use rstest::rstest;
+use test_utils::random::{make_seedable_rng, Seed};
+
+// Check that the utxo queries follow the tip, including when it's moved by a reorg.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxo_queries_follow_tip(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+
+        let genesis_id: Id<GenBlock> = tf.genesis().get_id().into();
+        let genesis_outpoint = UtxoOutPoint::new(OutPointSourceId::BlockReward(genesis_id), 0);
+        let output = TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1000))),
+            Destination::AnyoneCanSpend,
+        );
+
+        assert_eq!(
+            tf.chainstate.get_best_block_height_and_id().unwrap(),
+            (BlockHeight::zero(), genesis_id)
+        );
+
+        let tx = TransactionBuilder::new()
+            .add_input(
+                genesis_outpoint.clone().into(),
+                InputWitness::NoSignature(None),
+            )
+            .add_output(output.clone())
+            .build();
+        let tx_outpoint = UtxoOutPoint::new(tx.transaction().get_id().into(), 0);
+        let missing_outpoint = UtxoOutPoint::new(tx.transaction().get_id().into(), 1);
+
+        let block = tf.make_block_builder().add_transaction(tx).build(&mut rng);
+        let block_id = block.get_id();
+        tf.process_block(block, BlockSource::Local).unwrap();
+
+        assert_eq!(
+            tf.chainstate.get_best_block_height_and_id().unwrap(),
+            (BlockHeight::new(1), block_id.into())
+        );
+        assert_eq!(
+            tf.chainstate.utxo(&tx_outpoint).unwrap().map(|utxo| utxo.take_output()),
+            Some(output)
+        );
+        assert_eq!(tf.chainstate.utxo(&genesis_outpoint).unwrap(), None);
+        assert_eq!(
+            tf.chainstate
+                .outpoints_available(&[
+                    genesis_outpoint.clone(),
+                    tx_outpoint.clone(),
+                    missing_outpoint.clone()
+                ])
+                .unwrap(),
+            vec![false, true, false]
+        );
+
+        // Reorg the block out
+        tf.create_chain(&genesis_id, 2, &mut rng).unwrap();
+
+        assert_eq!(
+            tf.chainstate.get_best_block_height_and_id().unwrap(),
+            (BlockHeight::new(2), tf.best_block_id())
+        );
+        assert_eq!(tf.chainstate.utxo(&tx_outpoint).unwrap(), None);
+        assert_eq!(
+            tf.chainstate
+                .outpoints_available(&[genesis_outpoint, tx_outpoint, missing_outpoint])
+                .unwrap(),
+            vec![true, false, false]
+        );
+    });
+}
+
+fn transfer_from_genesis(tf: &TestFramework, atoms: u128) -> SignedTransaction {
+    TransactionBuilder::new()
+        .add_input(
+            UtxoOutPoint::new(
+                OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                0,
+            )
+            .into(),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(atoms)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build()
+}
+
+// The statistics must match the utxo set in the storage and must not depend on the way
+// the node has arrived at its current tip.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxo_set_statistics(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf1 = TestFramework::builder(&mut rng).build();
+        let mut tf2 = TestFramework::builder(&mut rng).build();
+
+        let stats = tf1.chainstate.get_utxo_set_statistics().unwrap();
+        assert_eq!(stats, tf2.chainstate.get_utxo_set_statistics().unwrap());
+        assert_eq!(stats.best_block_height, BlockHeight::zero());
+
+        // tf2 first accepts a different block at height 1, which is reorged out later
+        let alt_tx = transfer_from_genesis(&tf2, rng.gen_range(1..1000));
+        let alt_block = tf2.make_block_builder().add_transaction(alt_tx).build(&mut rng);
+        tf2.process_block(alt_block, BlockSource::Local).unwrap();
+        assert_ne!(
+            stats.checksum,
+            tf2.chainstate.get_utxo_set_statistics().unwrap().checksum
+        );
+
+        let tx = transfer_from_genesis(&tf1, rng.gen_range(1000..2000));
+        let block1 = tf1.make_block_builder().add_transaction(tx).build(&mut rng);
+        tf1.process_block(block1.clone(), BlockSource::Local).unwrap();
+        let block2 = tf1.make_block_builder().build(&mut rng);
+        tf1.process_block(block2.clone(), BlockSource::Local).unwrap();
+
+        tf2.process_block(block1, BlockSource::Local).unwrap();
+        tf2.process_block(block2, BlockSource::Local).unwrap();
+
+        let stats = tf1.chainstate.get_utxo_set_statistics().unwrap();
+        assert_eq!(stats, tf2.chainstate.get_utxo_set_statistics().unwrap());
+        assert_eq!(stats.best_block_id, tf1.best_block_id());
+        assert_eq!(stats.best_block_height, BlockHeight::new(2));
+
+        let utxos = tf1.storage.transaction_ro().unwrap().read_utxo_set().unwrap();
+        assert_eq!(stats.utxo_count, utxos.len() as u64);
+        let total_coins = utxos
+            .values()
+            .filter_map(|utxo| match utxo.output() {
+                TxOutput::Transfer(OutputValue::Coin(amount), _)
+                | TxOutput::LockThenTransfer(OutputValue::Coin(amount), _, _)
+                | TxOutput::Htlc(OutputValue::Coin(amount), _) => Some(*amount),
+                _ => None,
+            })
+            .sum::<Option<Amount>>()
+            .unwrap();
+        assert_eq!(stats.total_coins, total_coins);
+    });
+}
+
+// The balance at each height must match the utxo set that the chainstate had at that height.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn balance_at_height(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng).build();
+        let destination = Destination::AnyoneCanSpend;
+
+        let coins_in_utxo_set = |tf: &TestFramework| {
+            tf.storage
+                .transaction_ro()
+                .unwrap()
+                .read_utxo_set()
+                .unwrap()
+                .values()
+                .filter_map(|utxo| match utxo.output() {
+                    TxOutput::Transfer(OutputValue::Coin(amount), dest)
+                    | TxOutput::LockThenTransfer(OutputValue::Coin(amount), dest, _)
+                        if *dest == destination =>
+                    {
+                        Some(*amount)
+                    }
+                    _ => None,
+                })
+                .sum::<Option<Amount>>()
+                .unwrap()
+        };
+
+        let mut expected = vec![(tf.best_block_id(), coins_in_utxo_set(&tf))];
+        for _ in 0..rng.gen_range(1..10) {
+            tf.create_chain(&tf.best_block_id(), 1, &mut rng).unwrap();
+            expected.push((tf.best_block_id(), coins_in_utxo_set(&tf)));
+        }
+
+        for (height, (block_id, coins)) in expected.iter().enumerate() {
+            let balance = tf
+                .chainstate
+                .get_balance_at_height(&destination, BlockHeight::new(height as u64))
+                .unwrap();
+            assert_eq!(balance.block_id, *block_id);
+            assert_eq!(balance.block_height, BlockHeight::new(height as u64));
+            assert_eq!(balance.coins, *coins);
+            assert!(balance.tokens.is_empty());
+        }
+
+        let best_height = BlockHeight::new(expected.len() as u64 - 1);
+        assert_eq!(
+            tf.chainstate.get_balance_at_height(&destination, best_height.next_height()),
+            Err(ChainstateError::FailedToReadProperty(
+                PropertyQueryError::HeightAboveBestBlock {
+                    height: best_height.next_height(),
+                    best_height,
+                }
+            ))
+        );
+    });
+}
+
+// The balance can't be requested for heights deeper than the max reorg depth below the tip.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn balance_at_height_too_deep(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let max_depth = BlockDistance::new(rng.gen_range(1..5));
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(
+                create_unit_test_config_builder().max_depth_for_reorg(max_depth).build(),
+            )
+            .build();
+        let destination = Destination::AnyoneCanSpend;
+
+        let blocks_count = max_depth.to_int() as usize + rng.gen_range(1..5);
+        tf.create_chain(&tf.best_block_id(), blocks_count, &mut rng).unwrap();
+        let best_height = BlockHeight::new(blocks_count as u64);
+
+        let deepest_height = (best_height - max_depth).unwrap();
+        tf.chainstate.get_balance_at_height(&destination, deepest_height).unwrap();
+
+        let height = deepest_height.prev_height().unwrap();
+        assert_eq!(
+            tf.chainstate.get_balance_at_height(&destination, height),
+            Err(ChainstateError::FailedToReadProperty(
+                PropertyQueryError::HeightTooDeepBelowBestBlock {
+                    height,
+                    best_height,
+                    max_depth,
+                }
+            ))
+        );
+    });
+}
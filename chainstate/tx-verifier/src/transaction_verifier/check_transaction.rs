@@ -78,7 +78,7 @@ pub fn check_transaction(
     block_height: BlockHeight,
     tx: &SignedTransaction,
 ) -> Result<(), CheckTransactionError> {
-    check_size(chain_config, tx)?;
+    check_size(chain_config, block_height, tx)?;
     check_duplicate_inputs(tx)?;
     check_witness_count(tx)?;
     check_tokens_tx(chain_config, block_height, tx)?;
@@ -91,11 +91,12 @@ pub fn check_transaction(
 
 fn check_size(
     chain_config: &ChainConfig,
+    block_height: BlockHeight,
     tx: &SignedTransaction,
 ) -> Result<(), CheckTransactionError> {
     match tx.transaction_data_size() {
         TransactionSize::ScriptedTransaction(size) => {
-            let max_allowed_size = chain_config.max_block_size_from_std_scripts();
+            let max_allowed_size = chain_config.max_block_size_from_std_scripts(block_height);
             ensure!(
                 size <= max_allowed_size,
                 CheckTransactionError::TxSizeTooLarge(
@@ -106,7 +107,7 @@ fn check_size(
             );
         }
         TransactionSize::SmartContractTransaction(size) => {
-            let max_allowed_size = chain_config.max_block_size_from_smart_contracts();
+            let max_allowed_size = chain_config.max_block_size_from_smart_contracts(block_height);
             ensure!(
                 size <= max_allowed_size,
                 CheckTransactionError::TxSizeTooLarge(
@@ -217,6 +217,8 @@ pub enum TokensError {
     InvariantBrokenRegisterIssuanceWithDuplicateId(TokenId),
     #[error("Token {0} metadata uri is to large")]
     TokenMetadataUriTooLarge(TokenId),
+    #[error("Token ticker {ticker:?} is already taken by token {1} in transaction {2}", ticker = String::from_utf8_lossy(.0))]
+    TokenTickerAlreadyTaken(Vec<u8>, TokenId, Id<Transaction>),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
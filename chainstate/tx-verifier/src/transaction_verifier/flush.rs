@@ -15,7 +15,9 @@
 
 use super::{
     storage::{TransactionVerifierStorageMut, TransactionVerifierStorageRef},
-    token_issuance_cache::{CachedAuxDataOp, CachedTokenIndexOp, ConsumedTokenIssuanceCache},
+    token_issuance_cache::{
+        CachedAuxDataOp, CachedTickerIndexOp, CachedTokenIndexOp, ConsumedTokenIssuanceCache,
+    },
     CachedOperation, TransactionVerifierDelta,
 };
 use orders_accounting::FlushableOrdersAccountingView;
@@ -58,6 +60,21 @@ fn flush_tokens<S: TransactionVerifierStorageMut>(
             Ok(())
         },
     )?;
+
+    token_cache.ticker_vs_tokenids.iter().try_for_each(
+        |(ticker, ticker_index_op)| -> Result<(), <S as TransactionVerifierStorageRef>::Error> {
+            match ticker_index_op {
+                CachedTickerIndexOp::Write(token_ids) => {
+                    storage.set_token_ticker(ticker, token_ids)?;
+                }
+                CachedTickerIndexOp::Read(_) => (),
+                CachedTickerIndexOp::Erase => {
+                    storage.del_token_ticker(ticker)?;
+                }
+            };
+            Ok(())
+        },
+    )?;
     Ok(())
 }
 
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use super::{
     accounting_undo_cache::CachedBlockUndo,
@@ -21,7 +21,7 @@ use super::{
         TransactionVerifierStorageError, TransactionVerifierStorageMut,
         TransactionVerifierStorageRef,
     },
-    token_issuance_cache::{CachedAuxDataOp, CachedTokenIndexOp},
+    token_issuance_cache::{CachedAuxDataOp, CachedTickerIndexOp, CachedTokenIndexOp},
     utxos_undo_cache::CachedUtxosBlockUndo,
     CachedOperation, TransactionSource, TransactionVerifier,
 };
@@ -76,6 +76,20 @@ where
         }
     }
 
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, <Self as TransactionVerifierStorageRef>::Error> {
+        match self.token_issuance_cache.ticker_index().get(ticker) {
+            Some(v) => match v {
+                CachedTickerIndexOp::Write(ids) => Ok(Some(ids.clone())),
+                CachedTickerIndexOp::Read(ids) => Ok(Some(ids.clone())),
+                CachedTickerIndexOp::Erase => Ok(None),
+            },
+            None => self.storage.get_token_ids_by_ticker(ticker),
+        }
+    }
+
     fn get_gen_block_index(
         &self,
         block_id: &Id<GenBlock>,
@@ -225,6 +239,25 @@ where
             .map_err(|e| TransactionVerifierStorageError::TokensError(e).into())
     }
 
+    fn set_token_ticker(
+        &mut self,
+        ticker: &[u8],
+        token_ids: &BTreeSet<TokenId>,
+    ) -> Result<(), <Self as TransactionVerifierStorageRef>::Error> {
+        self.token_issuance_cache
+            .set_token_ticker(ticker, token_ids)
+            .map_err(|e| TransactionVerifierStorageError::TokensError(e).into())
+    }
+
+    fn del_token_ticker(
+        &mut self,
+        ticker: &[u8],
+    ) -> Result<(), <Self as TransactionVerifierStorageRef>::Error> {
+        self.token_issuance_cache
+            .del_token_ticker(ticker)
+            .map_err(|e| TransactionVerifierStorageError::TokensError(e).into())
+    }
+
     fn set_utxo_undo_data(
         &mut self,
         tx_source: TransactionSource,
@@ -453,9 +453,10 @@ impl<'a, S> VerifyContextTimelock<'a, S> {
                 (*new_block_index.block_id()).into()
             }
             TransactionSourceForConnect::Mempool {
-                current_best,
+                current_best_id,
+                current_best_height: _,
                 effective_height: _,
-            } => current_best.block_id(),
+            } => *current_best_id,
         };
 
         Self::custom(
@@ -17,6 +17,7 @@ mod input_output_policy;
 mod pos_accounting_delta_adapter;
 mod reward_distribution;
 mod token_issuance_cache;
+pub use token_issuance_cache::issued_ticker;
 
 pub mod check_transaction;
 pub mod error;
@@ -83,7 +84,10 @@ use pos_accounting::{
     PoSAccountingDB, PoSAccountingDelta, PoSAccountingDeltaData, PoSAccountingOperations,
     PoSAccountingUndo, PoSAccountingView,
 };
-use utxo::{ConsumedUtxoCache, UtxosCache, UtxosDB, UtxosStorageRead, UtxosView};
+use utxo::{
+    ConsumedUtxoCache, UtxosCache, UtxosCacheCounters, UtxosCacheLimits, UtxosDB, UtxosStorageRead,
+    UtxosView,
+};
 
 /// The change that a block has caused to the blockchain state
 #[derive(Debug, Eq, PartialEq)]
@@ -130,6 +134,9 @@ pub struct TransactionVerifier<C, S, U, A, T, O> {
     orders_accounting_block_undo: AccountingBlockUndoCache<OrdersAccountingUndo>,
 
     account_nonce: BTreeMap<AccountType, CachedOperation<AccountNonce>>,
+
+    // Total fees of the block whose reward has been connected, if any
+    connected_block_fees: Option<Fee>,
 }
 
 impl<C, S: TransactionVerifierStorageRef + ShallowClone>
@@ -168,6 +175,7 @@ impl<C, S: TransactionVerifierStorageRef + ShallowClone>
             orders_accounting_cache,
             orders_accounting_block_undo: AccountingBlockUndoCache::<OrdersAccountingUndo>::new(),
             account_nonce: BTreeMap::new(),
+            connected_block_fees: None,
         }
     }
 }
@@ -208,6 +216,7 @@ where
             orders_accounting_cache: OrdersAccountingCache::new(orders_accounting),
             orders_accounting_block_undo: AccountingBlockUndoCache::<OrdersAccountingUndo>::new(),
             account_nonce: BTreeMap::new(),
+            connected_block_fees: None,
         }
     }
 }
@@ -248,6 +257,7 @@ where
             orders_accounting_block_undo: AccountingBlockUndoCache::<OrdersAccountingUndo>::new(),
             best_block: self.best_block,
             account_nonce: BTreeMap::new(),
+            connected_block_fees: None,
         }
     }
 
@@ -320,6 +330,9 @@ where
             TxOutput::CreateStakePool(pool_id, _) | TxOutput::ProduceBlockFromStake(_, pool_id) => {
                 // If the input spends `CreateStakePool` or `ProduceBlockFromStake` utxo,
                 // this means the user is decommissioning the pool.
+                // Maturity of the decommissioned amount is enforced by the constraints accumulator
+                // and the signature is checked against the pool's decommission destination
+                // in `input_check`; the returned undo restores the pool data on disconnect.
                 let undo = self
                     .pos_accounting_adapter
                     .operations(tx_source)
@@ -977,6 +990,17 @@ where
             },
         )?;
 
+        self.token_issuance_cache.register_ticker(
+            self.chain_config.as_ref(),
+            tx_source.expected_block_height(),
+            tx.transaction(),
+            |ticker| {
+                self.storage
+                    .get_token_ids_by_ticker(ticker)
+                    .map_err(|_| ConnectTransactionError::TxVerifierStorage)
+            },
+        )?;
+
         // check for attempted money printing and invalid inputs/outputs combinations
         let fee = input_output_policy::check_tx_inputs_outputs_policy(
             tx.transaction(),
@@ -1088,9 +1112,16 @@ where
             }
         };
 
+        self.connected_block_fees = Some(total_fees);
+
         Ok(())
     }
 
+    /// Total fees of the block whose reward has been connected by this verifier, if any
+    pub fn connected_block_fees(&self) -> Option<Fee> {
+        self.connected_block_fees
+    }
+
     pub fn can_disconnect_transaction(
         &self,
         tx_source: &TransactionSource,
@@ -1136,6 +1167,25 @@ where
         }
     }
 
+    // The height at which the transaction was connected.
+    fn tx_source_block_height(
+        &self,
+        tx_source: &TransactionSource,
+    ) -> Result<BlockHeight, ConnectTransactionError> {
+        let (block_id, distance_from_block) = match tx_source {
+            TransactionSource::Chain(block_id) => ((*block_id).into(), 0),
+            TransactionSource::Mempool => (self.best_block, 1),
+        };
+        let block_height = self
+            .storage
+            .get_gen_block_index(&block_id)?
+            .ok_or(ConnectTransactionError::BlockIndexCouldNotBeLoaded(
+                block_id,
+            ))?
+            .block_height();
+        Ok(block_height.checked_add(distance_from_block).expect("Block height overflow"))
+    }
+
     pub fn disconnect_transaction(
         &mut self,
         tx_source: &TransactionSource,
@@ -1163,6 +1213,17 @@ where
                 .map_err(|_| ConnectTransactionError::TxVerifierStorage)
         })?;
 
+        self.token_issuance_cache.unregister_ticker(
+            self.chain_config.as_ref(),
+            || self.tx_source_block_height(tx_source),
+            tx.transaction(),
+            |ticker| {
+                self.storage
+                    .get_token_ids_by_ticker(ticker)
+                    .map_err(|_| ConnectTransactionError::TxVerifierStorage)
+            },
+        )?;
+
         Ok(())
     }
 
@@ -1213,6 +1274,19 @@ where
         self.utxo_cache.set_best_block(id);
     }
 
+    /// Limit the memory used by the utxo cache; see [UtxosCacheLimits].
+    pub fn set_utxo_cache_limits(&mut self, limits: Option<UtxosCacheLimits>) {
+        self.utxo_cache.set_limits(limits);
+    }
+
+    pub fn utxo_cache_memory_usage(&self) -> usize {
+        self.utxo_cache.memory_usage()
+    }
+
+    pub fn utxo_cache_counters(&self) -> UtxosCacheCounters {
+        self.utxo_cache.counters()
+    }
+
     pub fn verify_inputs<Tx>(
         &self,
         tx: &Tx,
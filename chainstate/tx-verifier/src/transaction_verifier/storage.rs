@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::ops::Deref;
+use std::{collections::BTreeSet, ops::Deref};
 
 use chainstate_types::{storage_result, GenBlockIndex, TipStorageTag};
 use common::{
@@ -79,6 +79,11 @@ pub trait TransactionVerifierStorageRef:
         tx_id: Id<Transaction>,
     ) -> Result<Option<TokenId>, <Self as TransactionVerifierStorageRef>::Error>;
 
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, <Self as TransactionVerifierStorageRef>::Error>;
+
     // TODO: Study whether moving this to a closure on tx_verifier construction is helpful.
     //       The issue here is that looking into history prevents testing the tx_verifier independently
     //       where the state of the tx index should be prepared before constructing the tx_verifier
@@ -158,6 +163,17 @@ pub trait TransactionVerifierStorageMut:
         issuance_tx_id: &Id<Transaction>,
     ) -> Result<(), <Self as TransactionVerifierStorageRef>::Error>;
 
+    fn set_token_ticker(
+        &mut self,
+        ticker: &[u8],
+        token_ids: &BTreeSet<TokenId>,
+    ) -> Result<(), <Self as TransactionVerifierStorageRef>::Error>;
+
+    fn del_token_ticker(
+        &mut self,
+        ticker: &[u8],
+    ) -> Result<(), <Self as TransactionVerifierStorageRef>::Error>;
+
     fn set_utxo_undo_data(
         &mut self,
         tx_source: TransactionSource,
@@ -232,6 +248,13 @@ where
         self.deref().get_token_id_from_issuance_tx(tx_id)
     }
 
+    fn get_token_ids_by_ticker(
+        &self,
+        ticker: &[u8],
+    ) -> Result<Option<BTreeSet<TokenId>>, <Self as TransactionVerifierStorageRef>::Error> {
+        self.deref().get_token_ids_by_ticker(ticker)
+    }
+
     fn get_gen_block_index(
         &self,
         block_id: &Id<GenBlock>,
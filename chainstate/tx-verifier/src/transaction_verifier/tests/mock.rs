@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::transaction_verifier::TransactionSource;
 
@@ -61,6 +61,11 @@ mockall::mock! {
             tx_id: Id<Transaction>,
         ) -> Result<Option<TokenId>, TransactionVerifierStorageError>;
 
+        fn get_token_ids_by_ticker(
+            &self,
+            ticker: &[u8],
+        ) -> Result<Option<BTreeSet<TokenId>>, TransactionVerifierStorageError>;
+
         fn get_gen_block_index(
             &self,
             block_id: &Id<GenBlock>,
@@ -115,6 +120,17 @@ mockall::mock! {
             issuance_tx_id: &Id<Transaction>,
         ) -> Result<(), TransactionVerifierStorageError>;
 
+        fn set_token_ticker(
+            &mut self,
+            ticker: &[u8],
+            token_ids: &BTreeSet<TokenId>,
+        ) -> Result<(), TransactionVerifierStorageError>;
+
+        fn del_token_ticker(
+            &mut self,
+            ticker: &[u8],
+        ) -> Result<(), TransactionVerifierStorageError>;
+
         fn set_utxo_undo_data(&mut self, tx_source: TransactionSource, undo: &CachedUtxosBlockUndo) -> Result<(), TransactionVerifierStorageError>;
         fn del_utxo_undo_data(&mut self, tx_source: TransactionSource) -> Result<(), TransactionVerifierStorageError>;
 
@@ -16,6 +16,7 @@
 mod hierarchy_read;
 mod hierarchy_write;
 pub mod mock;
+mod token_tickers;
 
 use super::*;
 use common::{
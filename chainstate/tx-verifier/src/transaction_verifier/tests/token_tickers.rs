@@ -0,0 +1,146 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use crate::transaction_verifier::{
+    error::TokensError,
+    token_issuance_cache::{CachedTickerIndexOp, TokenIssuanceCache},
+};
+
+use super::*;
+use common::{
+    chain::{
+        config::Builder as ConfigBuilder,
+        make_token_id,
+        tokens::{IsTokenFreezable, TokenId, TokenIssuance, TokenIssuanceV1, TokenTotalSupply},
+        ChainstateUpgradeBuilder, NetUpgrades, TokenTickerUniquenessActivated,
+    },
+    primitives::BlockHeight,
+};
+use rstest::rstest;
+use test_utils::random::Seed;
+
+fn make_chain_config(ticker_uniqueness: TokenTickerUniquenessActivated) -> ChainConfig {
+    ConfigBuilder::test_chain()
+        .chainstate_upgrades(
+            NetUpgrades::initialize(vec![(
+                BlockHeight::zero(),
+                ChainstateUpgradeBuilder::latest()
+                    .token_ticker_uniqueness_activated(ticker_uniqueness)
+                    .build(),
+            )])
+            .unwrap(),
+        )
+        .build()
+}
+
+fn make_issuance_tx(rng: &mut (impl Rng + CryptoRng), ticker: &[u8]) -> Transaction {
+    let (outpoint, _) = create_utxo(rng, 100);
+    let issuance = TokenIssuance::V1(TokenIssuanceV1 {
+        token_ticker: ticker.to_vec(),
+        number_of_decimals: rng.gen_range(1..18),
+        metadata_uri: Vec::new(),
+        total_supply: TokenTotalSupply::Unlimited,
+        authority: Destination::AnyoneCanSpend,
+        is_freezable: IsTokenFreezable::No,
+    });
+    Transaction::new(
+        0,
+        vec![outpoint.into()],
+        vec![TxOutput::IssueFungibleToken(Box::new(issuance))],
+    )
+    .unwrap()
+}
+
+fn no_indexed_tokens(_: &[u8]) -> Result<Option<BTreeSet<TokenId>>, ConnectTransactionError> {
+    Ok(None)
+}
+
+// Before the upgrade, a ticker is indexed for every token issued with it and stays indexed
+// until all of them are disconnected, in any order.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn duplicate_ticker_unregistered_in_any_order(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+    let chain_config = make_chain_config(TokenTickerUniquenessActivated::No);
+    let height = BlockHeight::new(rng.gen_range(1..1000));
+    let ticker = b"TKN".to_vec();
+
+    let tx1 = make_issuance_tx(&mut rng, &ticker);
+    let tx2 = make_issuance_tx(&mut rng, &ticker);
+    let token_id1 = make_token_id(&chain_config, height, tx1.inputs()).unwrap();
+    let token_id2 = make_token_id(&chain_config, height, tx2.inputs()).unwrap();
+
+    let mut cache = TokenIssuanceCache::new();
+    cache.register_ticker(&chain_config, height, &tx1, no_indexed_tokens).unwrap();
+    cache.register_ticker(&chain_config, height, &tx2, no_indexed_tokens).unwrap();
+    assert_eq!(
+        cache.ticker_index().get(&ticker),
+        Some(&CachedTickerIndexOp::Write(BTreeSet::from([
+            token_id1, token_id2
+        ])))
+    );
+
+    // The ticker is still taken by the second token after the first one is gone
+    cache
+        .unregister_ticker(&chain_config, || Ok(height), &tx1, no_indexed_tokens)
+        .unwrap();
+    assert_eq!(
+        cache.ticker_index().get(&ticker),
+        Some(&CachedTickerIndexOp::Write(BTreeSet::from([token_id2])))
+    );
+
+    cache
+        .unregister_ticker(&chain_config, || Ok(height), &tx2, no_indexed_tokens)
+        .unwrap();
+    assert_eq!(
+        cache.ticker_index().get(&ticker),
+        Some(&CachedTickerIndexOp::Erase)
+    );
+}
+
+// Once the upgrade is activated, a ticker can't be reused while any token issued with it exists.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn ticker_of_remaining_token_stays_taken(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+    let chain_config = make_chain_config(TokenTickerUniquenessActivated::Yes);
+    let height = BlockHeight::new(rng.gen_range(1..1000));
+    let ticker = b"TKN".to_vec();
+
+    let tx1 = make_issuance_tx(&mut rng, &ticker);
+    let tx2 = make_issuance_tx(&mut rng, &ticker);
+    let token_id1 = make_token_id(&chain_config, height, tx1.inputs()).unwrap();
+    let token_id2 = make_token_id(&chain_config, height, tx2.inputs()).unwrap();
+    let indexed_tokens = |_: &[u8]| -> Result<_, ConnectTransactionError> {
+        Ok(Some(BTreeSet::from([token_id1, token_id2])))
+    };
+
+    let mut cache = TokenIssuanceCache::new();
+    cache
+        .unregister_ticker(&chain_config, || Ok(height), &tx1, indexed_tokens)
+        .unwrap();
+
+    let tx3 = make_issuance_tx(&mut rng, &ticker);
+    assert_eq!(
+        cache.register_ticker(&chain_config, height, &tx3, indexed_tokens),
+        Err(ConnectTransactionError::TokensError(
+            TokensError::TokenTickerAlreadyTaken(ticker.clone(), token_id2, tx3.get_id())
+        ))
+    );
+}
@@ -13,13 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{btree_map::Entry, BTreeMap};
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
 
 use common::{
     chain::{
         make_token_id,
-        tokens::{TokenAuxiliaryData, TokenId},
-        Block, ChainConfig, Transaction, TxOutput,
+        tokens::{NftIssuance, TokenAuxiliaryData, TokenId, TokenIssuance},
+        Block, ChainConfig, TokenTickerUniquenessActivated, Transaction, TxOutput,
     },
     primitives::{BlockHeight, Id, Idable, H256},
 };
@@ -34,16 +34,19 @@ use super::{
 
 pub type CachedAuxDataOp = CachedOperation<TokenAuxiliaryData>;
 pub type CachedTokenIndexOp = CachedOperation<TokenId>;
+pub type CachedTickerIndexOp = CachedOperation<BTreeSet<TokenId>>;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct ConsumedTokenIssuanceCache {
     pub data: BTreeMap<TokenId, CachedAuxDataOp>,
     pub txid_vs_tokenid: BTreeMap<Id<Transaction>, CachedTokenIndexOp>,
+    pub ticker_vs_tokenids: BTreeMap<Vec<u8>, CachedTickerIndexOp>,
 }
 
 pub struct TokenIssuanceCache {
     data: BTreeMap<TokenId, CachedAuxDataOp>,
     txid_vs_tokenid: BTreeMap<Id<Transaction>, CachedTokenIndexOp>,
+    ticker_vs_tokenids: BTreeMap<Vec<u8>, CachedTickerIndexOp>,
 }
 
 impl TokenIssuanceCache {
@@ -51,6 +54,7 @@ impl TokenIssuanceCache {
         Self {
             data: BTreeMap::new(),
             txid_vs_tokenid: BTreeMap::new(),
+            ticker_vs_tokenids: BTreeMap::new(),
         }
     }
 
@@ -62,6 +66,7 @@ impl TokenIssuanceCache {
         Self {
             data,
             txid_vs_tokenid,
+            ticker_vs_tokenids: BTreeMap::new(),
         }
     }
 
@@ -116,6 +121,103 @@ impl TokenIssuanceCache {
         Ok(())
     }
 
+    // The ticker index maps a ticker to the ids of all the existing tokens that were issued with it.
+    // Before the uniqueness upgrade is activated, issuing another token with the same ticker is
+    // allowed, so a ticker may be indexed for several tokens.
+    pub fn register_ticker<E>(
+        &mut self,
+        chain_config: &ChainConfig,
+        block_height: BlockHeight,
+        tx: &Transaction,
+        ticker_getter: impl Fn(&[u8]) -> Result<Option<BTreeSet<TokenId>>, E>,
+    ) -> Result<(), ConnectTransactionError>
+    where
+        ConnectTransactionError: From<E>,
+    {
+        let block_height_getter = || Ok(block_height);
+        if let Some((token_id, ticker)) =
+            issued_token_ticker(chain_config, block_height_getter, tx)?
+        {
+            let mut token_ids = self.indexed_token_ids(ticker, ticker_getter)?;
+            if let Some(existing_token_id) = token_ids.first() {
+                match chain_config
+                    .chainstate_upgrades()
+                    .version_at_height(block_height)
+                    .1
+                    .token_ticker_uniqueness_activated()
+                {
+                    TokenTickerUniquenessActivated::Yes => {
+                        return Err(ConnectTransactionError::TokensError(
+                            TokensError::TokenTickerAlreadyTaken(
+                                ticker.to_vec(),
+                                *existing_token_id,
+                                tx.get_id(),
+                            ),
+                        ));
+                    }
+                    TokenTickerUniquenessActivated::No => {}
+                }
+            }
+            token_ids.insert(token_id);
+            self.ticker_vs_tokenids
+                .insert(ticker.to_vec(), CachedTickerIndexOp::Write(token_ids));
+        }
+        Ok(())
+    }
+
+    pub fn unregister_ticker<E>(
+        &mut self,
+        chain_config: &ChainConfig,
+        block_height_getter: impl FnOnce() -> Result<BlockHeight, ConnectTransactionError>,
+        tx: &Transaction,
+        ticker_getter: impl Fn(&[u8]) -> Result<Option<BTreeSet<TokenId>>, E>,
+    ) -> Result<(), ConnectTransactionError>
+    where
+        ConnectTransactionError: From<E>,
+    {
+        if let Some((token_id, ticker)) =
+            issued_token_ticker(chain_config, block_height_getter, tx)?
+        {
+            let mut token_ids = self.indexed_token_ids(ticker, ticker_getter)?;
+            if token_ids.remove(&token_id) {
+                // The ticker stays taken while other tokens issued with it still exist
+                let op = if token_ids.is_empty() {
+                    CachedTickerIndexOp::Erase
+                } else {
+                    CachedTickerIndexOp::Write(token_ids)
+                };
+                self.ticker_vs_tokenids.insert(ticker.to_vec(), op);
+            }
+        }
+        Ok(())
+    }
+
+    fn indexed_token_ids<E>(
+        &mut self,
+        ticker: &[u8],
+        ticker_getter: impl Fn(&[u8]) -> Result<Option<BTreeSet<TokenId>>, E>,
+    ) -> Result<BTreeSet<TokenId>, ConnectTransactionError>
+    where
+        ConnectTransactionError: From<E>,
+    {
+        match self.ticker_vs_tokenids.get(ticker) {
+            Some(CachedTickerIndexOp::Write(ids) | CachedTickerIndexOp::Read(ids)) => {
+                Ok(ids.clone())
+            }
+            Some(CachedTickerIndexOp::Erase) => Ok(BTreeSet::new()),
+            None => {
+                let token_ids = ticker_getter(ticker)?;
+                if let Some(token_ids) = &token_ids {
+                    self.ticker_vs_tokenids.insert(
+                        ticker.to_vec(),
+                        CachedTickerIndexOp::Read(token_ids.clone()),
+                    );
+                }
+                Ok(token_ids.unwrap_or_default())
+            }
+        }
+    }
+
     fn write_issuance(
         &mut self,
         block_id: &Id<Block>,
@@ -222,6 +324,23 @@ impl TokenIssuanceCache {
         Ok(())
     }
 
+    pub fn set_token_ticker(
+        &mut self,
+        ticker: &[u8],
+        token_ids: &BTreeSet<TokenId>,
+    ) -> Result<(), TokensError> {
+        self.ticker_vs_tokenids.insert(
+            ticker.to_vec(),
+            CachedTickerIndexOp::Write(token_ids.clone()),
+        );
+        Ok(())
+    }
+
+    pub fn del_token_ticker(&mut self, ticker: &[u8]) -> Result<(), TokensError> {
+        self.ticker_vs_tokenids.insert(ticker.to_vec(), CachedTickerIndexOp::Erase);
+        Ok(())
+    }
+
     pub fn data(&self) -> &BTreeMap<TokenId, CachedAuxDataOp> {
         &self.data
     }
@@ -230,10 +349,15 @@ impl TokenIssuanceCache {
         &self.txid_vs_tokenid
     }
 
+    pub fn ticker_index(&self) -> &BTreeMap<Vec<u8>, CachedTickerIndexOp> {
+        &self.ticker_vs_tokenids
+    }
+
     pub fn consume(self) -> ConsumedTokenIssuanceCache {
         ConsumedTokenIssuanceCache {
             data: self.data,
             txid_vs_tokenid: self.txid_vs_tokenid,
+            ticker_vs_tokenids: self.ticker_vs_tokenids,
         }
     }
 }
@@ -254,4 +378,49 @@ fn has_tokens_issuance_to_cache(outputs: &[TxOutput]) -> Option<TokenId> {
         TxOutput::IssueNft(id, _, _) => Some(*id),
     })
 }
+/// Return the ticker of the token issued by the transaction, if any.
+pub fn issued_ticker(tx: &Transaction) -> Option<&[u8]> {
+    find_issuance(tx).map(|(_, ticker)| ticker)
+}
+
+/// Return the id and the ticker of the token issued by the transaction, if any.
+///
+/// The block height is only needed to calculate the id of a fungible token.
+fn issued_token_ticker<'a>(
+    chain_config: &ChainConfig,
+    block_height_getter: impl FnOnce() -> Result<BlockHeight, ConnectTransactionError>,
+    tx: &'a Transaction,
+) -> Result<Option<(TokenId, &'a [u8])>, ConnectTransactionError> {
+    match find_issuance(tx) {
+        Some((Some(token_id), ticker)) => Ok(Some((token_id, ticker))),
+        Some((None, ticker)) => {
+            let token_id = make_token_id(chain_config, block_height_getter()?, tx.inputs())?;
+            Ok(Some((token_id, ticker)))
+        }
+        None => Ok(None),
+    }
+}
+
+// Find the token issuance output of the transaction; the token id is only known for NFTs
+fn find_issuance(tx: &Transaction) -> Option<(Option<TokenId>, &[u8])> {
+    tx.outputs().iter().find_map(|output| match output {
+        TxOutput::Transfer(_, _)
+        | TxOutput::LockThenTransfer(_, _, _)
+        | TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::DataDeposit(_)
+        | TxOutput::Htlc(_, _)
+        | TxOutput::CreateOrder(_) => None,
+        TxOutput::IssueFungibleToken(issuance) => match issuance.as_ref() {
+            TokenIssuance::V1(data) => Some((None, data.token_ticker.as_slice())),
+        },
+        TxOutput::IssueNft(id, issuance, _) => match issuance.as_ref() {
+            NftIssuance::V0(data) => Some((Some(*id), data.metadata.ticker().as_slice())),
+        },
+    })
+}
+
 // TODO: write tests for operations
@@ -15,7 +15,7 @@
 
 use chainstate_types::{BlockIndex, GenBlockIndex};
 use common::{
-    chain::Block,
+    chain::{Block, GenBlock},
     primitives::{BlockHeight, Id},
 };
 use utxo::UtxoSource;
@@ -33,7 +33,8 @@ impl From<&TransactionSourceForConnect<'_>> for TransactionSource {
                 TransactionSource::Chain(*new_block_index.block_id())
             }
             TransactionSourceForConnect::Mempool {
-                current_best: _,
+                current_best_id: _,
+                current_best_height: _,
                 effective_height: _,
             } => TransactionSource::Mempool,
         }
@@ -51,7 +52,9 @@ pub enum TransactionSourceForConnect<'a> {
     },
     Mempool {
         /// Blockchain tip according to mempool
-        current_best: &'a GenBlockIndex,
+        current_best_id: Id<GenBlock>,
+        /// Height of the blockchain tip according to mempool
+        current_best_height: BlockHeight,
         /// Effective block height used for mempool transaction validation (e.g. timelocks)
         effective_height: BlockHeight,
     },
@@ -64,9 +67,13 @@ impl<'a> TransactionSourceForConnect<'a> {
 
     /// Used for the transaction accumulator for block production,
     /// where the height of the new block is the current best + 1
-    pub fn for_mempool(current_best: &'a GenBlockIndex) -> Self {
-        let effective_height = current_best.block_height().next_height();
-        Self::for_mempool_with_height(current_best, effective_height)
+    pub fn for_mempool(current_best: &GenBlockIndex) -> Self {
+        let current_best_height = current_best.block_height();
+        Self::for_mempool_with_height(
+            current_best.block_id(),
+            current_best_height,
+            current_best_height.next_height(),
+        )
     }
 
     /// Source is mempool with given declared block height
@@ -76,12 +83,14 @@ impl<'a> TransactionSourceForConnect<'a> {
     /// This is needed when accepting new transactions to the mempool, where a certain
     /// tolerance to timelocks is needed due to fluctuations of block timestamps.
     pub fn for_mempool_with_height(
-        current_best: &'a GenBlockIndex,
+        current_best_id: Id<GenBlock>,
+        current_best_height: BlockHeight,
         effective_height: BlockHeight,
     ) -> Self {
-        assert!(current_best.block_height() < effective_height);
+        assert!(current_best_height < effective_height);
         Self::Mempool {
-            current_best,
+            current_best_id,
+            current_best_height,
             effective_height,
         }
     }
@@ -96,7 +105,8 @@ impl<'a> TransactionSourceForConnect<'a> {
                 new_block_index.block_height()
             }
             TransactionSourceForConnect::Mempool {
-                current_best: _,
+                current_best_id: _,
+                current_best_height: _,
                 effective_height,
             } => *effective_height,
         }
@@ -106,7 +116,8 @@ impl<'a> TransactionSourceForConnect<'a> {
         match self {
             TransactionSourceForConnect::Chain { new_block_index } => Some(new_block_index),
             TransactionSourceForConnect::Mempool {
-                current_best: _,
+                current_best_id: _,
+                current_best_height: _,
                 effective_height: _,
             } => None,
         }
@@ -118,7 +129,8 @@ impl<'a> TransactionSourceForConnect<'a> {
                 new_block_index: idx,
             } => UtxoSource::Blockchain(idx.block_height()),
             Self::Mempool {
-                current_best: _,
+                current_best_id: _,
+                current_best_height: _,
                 effective_height: _,
             } => UtxoSource::Mempool,
         }
@@ -0,0 +1,92 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{block::timestamp::BlockTimestamp, Block},
+    primitives::{BlockHeight, Id},
+};
+use serialization::{Decode, Encode};
+
+/// One of the two tips involved in an observed chain split.
+#[derive(PartialEq, Eq, Clone, Debug, Encode, Decode)]
+pub struct ChainSplitTip {
+    block_id: Id<Block>,
+    block_height: BlockHeight,
+    /// The time at which the block was first seen by this node, if known.
+    first_seen: Option<BlockTimestamp>,
+}
+
+impl ChainSplitTip {
+    pub fn new(
+        block_id: Id<Block>,
+        block_height: BlockHeight,
+        first_seen: Option<BlockTimestamp>,
+    ) -> Self {
+        Self {
+            block_id,
+            block_height,
+            first_seen,
+        }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn block_height(&self) -> BlockHeight {
+        self.block_height
+    }
+
+    pub fn first_seen(&self) -> Option<BlockTimestamp> {
+        self.first_seen
+    }
+}
+
+/// A forensic record of two competing tips that appeared within a small distance of each other.
+#[derive(PartialEq, Eq, Clone, Debug, Encode, Decode)]
+pub struct ChainSplitRecord {
+    /// The tip that was the best block after the split was observed.
+    best_tip: ChainSplitTip,
+    /// The tip that lost (or didn't win) the competition.
+    competing_tip: ChainSplitTip,
+    /// The time at which the split was observed.
+    observed_at: BlockTimestamp,
+}
+
+impl ChainSplitRecord {
+    pub fn new(
+        best_tip: ChainSplitTip,
+        competing_tip: ChainSplitTip,
+        observed_at: BlockTimestamp,
+    ) -> Self {
+        Self {
+            best_tip,
+            competing_tip,
+            observed_at,
+        }
+    }
+
+    pub fn best_tip(&self) -> &ChainSplitTip {
+        &self.best_tip
+    }
+
+    pub fn competing_tip(&self) -> &ChainSplitTip {
+        &self.competing_tip
+    }
+
+    pub fn observed_at(&self) -> BlockTimestamp {
+        self.observed_at
+    }
+}
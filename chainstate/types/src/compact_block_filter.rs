@@ -0,0 +1,311 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact block filters, which allow light clients to find out whether a block may be relevant
+//! to them without downloading it.
+//!
+//! The filters are Golomb-coded sets with the same parameters as the basic filters of BIP-158.
+//! The filter headers form a chain like in BIP-157, so that a client can check that the filters
+//! served by different peers are consistent.
+
+use std::{collections::BTreeSet, hash::Hasher};
+
+use common::{
+    chain::Block,
+    primitives::{id, Id, H256},
+};
+use serialization::{Decode, Encode};
+use siphasher::sip::SipHasher;
+
+/// The number of bits in the remainder of the Golomb-Rice coded values.
+const FILTER_P: u8 = 19;
+/// The inverse of the false positive rate.
+const FILTER_M: u64 = 784_931;
+
+/// A Golomb-coded set of the items of a block, keyed by the block id.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CompactBlockFilter {
+    #[codec(compact)]
+    element_count: u32,
+    data: Vec<u8>,
+}
+
+impl CompactBlockFilter {
+    pub fn new(block_id: &Id<Block>, elements: &BTreeSet<Vec<u8>>) -> Self {
+        let element_count =
+            u32::try_from(elements.len()).expect("the number of block items fits into u32");
+        let hasher = ElementHasher::new(block_id, element_count);
+
+        let mut values = elements.iter().map(|element| hasher.hash(element)).collect::<Vec<_>>();
+        values.sort_unstable();
+
+        let mut writer = BitWriter::default();
+        let mut last_value = 0;
+        for value in values {
+            writer.write_golomb_rice(value - last_value);
+            last_value = value;
+        }
+
+        Self {
+            element_count,
+            data: writer.into_bytes(),
+        }
+    }
+
+    pub fn element_count(&self) -> u32 {
+        self.element_count
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Check whether any of the queried items may belong to the filter. False positives are
+    /// possible with the probability of about 1/784931 per item, false negatives are not.
+    ///
+    /// `block_id` must be the id of the block the filter was built for; the result is
+    /// meaningless otherwise.
+    pub fn matches_any<'a>(
+        &self,
+        block_id: &Id<Block>,
+        queries: impl IntoIterator<Item = &'a [u8]>,
+    ) -> bool {
+        let hasher = ElementHasher::new(block_id, self.element_count);
+        let mut queries = queries.into_iter().map(|query| hasher.hash(query)).collect::<Vec<_>>();
+        queries.sort_unstable();
+        let mut queries = queries.into_iter().peekable();
+
+        let mut reader = BitReader::new(&self.data);
+        let mut value: u64 = 0;
+        for _ in 0..self.element_count {
+            let Some(next_value) =
+                reader.read_golomb_rice().and_then(|delta| value.checked_add(delta))
+            else {
+                return false;
+            };
+            value = next_value;
+
+            while queries.next_if(|query| *query < value).is_some() {}
+            match queries.peek() {
+                Some(query) if *query == value => return true,
+                Some(_) => {}
+                None => return false,
+            }
+        }
+
+        false
+    }
+
+    /// The hash of the filter, which is committed to by the filter header.
+    pub fn filter_hash(&self) -> H256 {
+        id::hash_encoded(self)
+    }
+}
+
+/// The hash of the compact filter of a block and the filter header, which commits to the filter
+/// and to the filter headers of all the previous blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct BlockFilterCommitment {
+    filter_hash: H256,
+    filter_header: H256,
+}
+
+impl BlockFilterCommitment {
+    /// Make the commitment of the block's filter; `prev_filter_header` is the filter header
+    /// of the previous block, or zero if the previous block is the genesis.
+    pub fn new(filter: &CompactBlockFilter, prev_filter_header: &H256) -> Self {
+        let filter_hash = filter.filter_hash();
+        let filter_header = id::hash_encoded(&(filter_hash, *prev_filter_header));
+        Self {
+            filter_hash,
+            filter_header,
+        }
+    }
+
+    pub fn filter_hash(&self) -> &H256 {
+        &self.filter_hash
+    }
+
+    pub fn filter_header(&self) -> &H256 {
+        &self.filter_header
+    }
+}
+
+/// Maps the elements uniformly onto the range `[0, element_count * FILTER_M)`.
+struct ElementHasher {
+    k0: u64,
+    k1: u64,
+    range: u64,
+}
+
+impl ElementHasher {
+    fn new(block_id: &Id<Block>, element_count: u32) -> Self {
+        let key = block_id.to_hash();
+        let key = key.as_bytes();
+        let k0 = u64::from_le_bytes(key[0..8].try_into().expect("8 bytes"));
+        let k1 = u64::from_le_bytes(key[8..16].try_into().expect("8 bytes"));
+        Self {
+            k0,
+            k1,
+            range: u64::from(element_count) * FILTER_M,
+        }
+    }
+
+    fn hash(&self, element: &[u8]) -> u64 {
+        let mut hasher = SipHasher::new_with_keys(self.k0, self.k1);
+        hasher.write(element);
+        ((u128::from(hasher.finish()) * u128::from(self.range)) >> 64) as u64
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_count: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.bit_count % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.last_mut().expect("a byte has been pushed");
+            *last |= 0x80 >> (self.bit_count % 8);
+        }
+        self.bit_count += 1;
+    }
+
+    fn write_bits(&mut self, value: u64, count: u8) {
+        for i in (0..count).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn write_golomb_rice(&mut self, value: u64) {
+        for _ in 0..(value >> FILTER_P) {
+            self.write_bit(true);
+        }
+        self.write_bit(false);
+        self.write_bits(value, FILTER_P);
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = byte & (0x80 >> (self.position % 8)) != 0;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn read_bits(&mut self, count: u8) -> Option<u64> {
+        (0..count).try_fold(0, |value, _| {
+            Some((value << 1) | u64::from(self.read_bit()?))
+        })
+    }
+
+    fn read_golomb_rice(&mut self) -> Option<u64> {
+        let mut quotient: u64 = 0;
+        while self.read_bit()? {
+            quotient = quotient.checked_add(1)?;
+        }
+        let remainder = self.read_bits(FILTER_P)?;
+        quotient.checked_mul(1 << FILTER_P)?.checked_add(remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_elements(prefix: &str, count: usize) -> BTreeSet<Vec<u8>> {
+        (0..count).map(|i| format!("{prefix}{i}").into_bytes()).collect()
+    }
+
+    #[test]
+    fn golomb_rice_roundtrip() {
+        let values = [0, 1, (1 << FILTER_P) - 1, 1 << FILTER_P, 5 << FILTER_P, 123_456_789];
+
+        let mut writer = BitWriter::default();
+        for value in values {
+            writer.write_golomb_rice(value);
+        }
+        let bytes = writer.into_bytes();
+
+        let mut reader = BitReader::new(&bytes);
+        for value in values {
+            assert_eq!(reader.read_golomb_rice(), Some(value));
+        }
+    }
+
+    #[test]
+    fn filter_matching() {
+        let block_id = Id::new(H256::from_low_u64_be(1));
+        let other_block_id = Id::new(H256::from_low_u64_be(2));
+        let elements = make_elements("element", 1000);
+        let filter = CompactBlockFilter::new(&block_id, &elements);
+        assert_eq!(filter.element_count(), 1000);
+
+        // All the elements match, individually and together with unrelated queries
+        for element in &elements {
+            assert!(filter.matches_any(&block_id, [element.as_slice()]));
+            assert!(filter.matches_any(&block_id, [b"unrelated".as_slice(), element.as_slice()]));
+        }
+
+        // With the false positive rate of about 1/784931, none of these is expected to match
+        let unrelated = make_elements("unrelated", 1000);
+        assert!(!filter.matches_any(&block_id, unrelated.iter().map(|e| e.as_slice())));
+        assert!(!filter.matches_any(&block_id, std::iter::empty()));
+
+        // The filter is keyed by the block id
+        assert_ne!(CompactBlockFilter::new(&other_block_id, &elements), filter);
+
+        let empty_filter = CompactBlockFilter::new(&block_id, &BTreeSet::new());
+        assert_eq!(empty_filter.element_count(), 0);
+        assert!(empty_filter.data().is_empty());
+        assert!(!empty_filter.matches_any(&block_id, elements.iter().map(|e| e.as_slice())));
+    }
+
+    #[test]
+    fn filter_commitment() {
+        let block_id = Id::new(H256::from_low_u64_be(1));
+        let filter = CompactBlockFilter::new(&block_id, &make_elements("element", 10));
+        let other_filter = CompactBlockFilter::new(&block_id, &make_elements("element", 11));
+
+        let commitment = BlockFilterCommitment::new(&filter, &H256::zero());
+        assert_eq!(*commitment.filter_hash(), filter.filter_hash());
+
+        // The header commits both to the filter and to the previous header
+        let other_commitment = BlockFilterCommitment::new(&other_filter, &H256::zero());
+        assert_ne!(commitment.filter_header(), other_commitment.filter_header());
+        let next_commitment = BlockFilterCommitment::new(&filter, commitment.filter_header());
+        assert_eq!(next_commitment.filter_hash(), commitment.filter_hash());
+        assert_ne!(next_commitment.filter_header(), commitment.filter_header());
+    }
+}
@@ -17,7 +17,7 @@ use thiserror::Error;
 
 use common::{
     chain::{Block, GenBlock, OrderId, PoolId},
-    primitives::{BlockHeight, Id},
+    primitives::{BlockDistance, BlockHeight, Id},
 };
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -60,6 +60,32 @@ pub enum PropertyQueryError {
         start: BlockHeight,
         end: BlockHeight,
     },
+    #[error("Undo data not found for block {0}")]
+    UndoDataNotFound(Id<Block>),
+    #[error("Undo data for height {height} has been pruned, the min height with undo data is {min_height_with_undo_data}")]
+    UndoDataPruned {
+        height: BlockHeight,
+        min_height_with_undo_data: BlockHeight,
+    },
+    #[error("Total coin amount in the utxo set overflow")]
+    UtxoSetCoinAmountOverflow,
+    #[error("Requested height {height} is above the best block height {best_height}")]
+    HeightAboveBestBlock {
+        height: BlockHeight,
+        best_height: BlockHeight,
+    },
+    #[error("Requested height {height} is more than {max_depth} blocks below the best block height {best_height}")]
+    HeightTooDeepBelowBestBlock {
+        height: BlockHeight,
+        best_height: BlockHeight,
+        max_depth: BlockDistance,
+    },
+    #[error("Destination balance overflow")]
+    DestinationBalanceOverflow,
+    #[error("Destination balance underflow while rolling back block {0}")]
+    DestinationBalanceUnderflow(Id<Block>),
+    #[error("Total reward of the blocks produced by pool {0} overflow")]
+    PoolBlockRewardOverflow(PoolId),
 }
 
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
@@ -24,6 +24,8 @@ pub use crate::{
     block_index_handle::BlockIndexHandle,
     block_index_history_iter::BlockIndexHistoryIterator,
     block_status::{BlockStatus, BlockValidationStage},
+    chain_split::{ChainSplitRecord, ChainSplitTip},
+    compact_block_filter::{BlockFilterCommitment, CompactBlockFilter},
     epoch_data::EpochData,
     epoch_data_cache::{
         ConsumedEpochDataCache, EpochDataCache, EpochStorageRead, EpochStorageWrite,
@@ -32,7 +34,8 @@ pub use crate::{
     error::PropertyQueryError,
     gen_block_index::GenBlockIndex,
     height_skip::get_skip_height,
-    locator::Locator,
+    locator::{Locator, LocatorSchedule},
+    pool_block::{PoolBlockRecord, PoolBlockStats},
 };
 
 mod ancestor;
@@ -40,12 +43,15 @@ mod block_index;
 mod block_index_handle;
 mod block_index_history_iter;
 mod block_status;
+mod chain_split;
+mod compact_block_filter;
 mod epoch_data;
 mod epoch_data_cache;
 mod error;
 mod gen_block_index;
 mod height_skip;
 mod locator;
+mod pool_block;
 
 pub struct TipStorageTag;
 impl pos_accounting::StorageTag for TipStorageTag {}
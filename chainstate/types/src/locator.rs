@@ -14,7 +14,7 @@
 // limitations under the License.
 
 use common::chain::GenBlock;
-use common::primitives::Id;
+use common::primitives::{BlockDistance, Id};
 use serialization::{Decode, Encode};
 
 /// Locator is a list of block IDs at exponentially increasing distance from the tip
@@ -50,3 +50,55 @@ impl std::ops::Index<usize> for Locator {
         &self.0[i]
     }
 }
+
+/// Determines the distances from the starting block of the blocks included into a locator.
+///
+/// The first `dense_len` entries are consecutive blocks going back from the starting block.
+/// After that, the distance from the starting block is multiplied by `growth_factor` for each
+/// subsequent entry.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct LocatorSchedule {
+    dense_len: u32,
+    growth_factor: u32,
+}
+
+impl LocatorSchedule {
+    pub fn new(dense_len: u32, growth_factor: u32) -> Self {
+        Self {
+            dense_len,
+            growth_factor,
+        }
+    }
+
+    pub fn dense_len(&self) -> u32 {
+        self.dense_len
+    }
+
+    pub fn growth_factor(&self) -> u32 {
+        self.growth_factor
+    }
+
+    /// The distances of the locator entries from the starting block, in increasing order
+    pub fn distances(&self) -> impl Iterator<Item = BlockDistance> {
+        let dense_len = i64::from(self.dense_len);
+        let growth_factor = i64::from(self.growth_factor);
+
+        std::iter::successors(Some(0i64), move |&dist| {
+            let next = if dist + 1 < dense_len {
+                dist + 1
+            } else {
+                std::cmp::max(dist + 1, dist.saturating_mul(growth_factor))
+            };
+            // Stop once the distance is too large to be meaningful
+            (next != i64::MAX).then_some(next)
+        })
+        .map(BlockDistance::new)
+    }
+}
+
+/// The distances are 0, 1, 2, 4, 8 and so on.
+impl Default for LocatorSchedule {
+    fn default() -> Self {
+        Self::new(2, 2)
+    }
+}
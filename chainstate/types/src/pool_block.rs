@@ -0,0 +1,59 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::Block,
+    primitives::{Amount, BlockHeight, Id},
+};
+use serialization::{Decode, Encode};
+
+/// A main chain block produced by a stake pool.
+#[derive(PartialEq, Eq, Clone, Debug, Encode, Decode)]
+pub struct PoolBlockRecord {
+    block_id: Id<Block>,
+    block_height: BlockHeight,
+    /// The total reward of the block (the subsidy plus the fees), which is distributed among
+    /// the staker and the delegations.
+    reward: Amount,
+}
+
+impl PoolBlockRecord {
+    pub fn new(block_id: Id<Block>, block_height: BlockHeight, reward: Amount) -> Self {
+        Self {
+            block_id,
+            block_height,
+            reward,
+        }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn block_height(&self) -> BlockHeight {
+        self.block_height
+    }
+
+    pub fn reward(&self) -> Amount {
+        self.reward
+    }
+}
+
+/// The number of main chain blocks produced by a stake pool and their total reward.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct PoolBlockStats {
+    pub block_count: u64,
+    pub total_reward: Amount,
+}
@@ -222,7 +222,10 @@ mod tests {
             hexified::HexifiedAddress, pubkeyhash::PublicKeyHash, traits::Addressable, Address,
             AddressError,
         },
-        chain::{config::create_regtest, ChainConfig, Destination},
+        chain::{
+            config::{create_regtest, ChainType},
+            ChainConfig, Destination,
+        },
         primitives::H256,
     };
 
@@ -397,7 +400,7 @@ mod tests {
     impl Addressable for Foo {
         type Error = AddressError;
 
-        fn address_prefix(&self, _chain_config: &ChainConfig) -> &str {
+        fn address_prefix(&self, _chain_type: ChainType) -> &'static str {
             "Foo"
         }
 
@@ -15,17 +15,16 @@
 
 pub mod dehexify;
 pub mod hexified;
+pub mod payment_uri;
 pub mod pubkeyhash;
 pub mod rpc;
 pub mod traits;
 
-use crate::chain::ChainConfig;
+use crate::chain::{config::ChainType, ChainConfig};
 use crate::primitives::{bech32_encoding, Bech32Error};
 use std::fmt::Display;
-use utils::{
-    ensure,
-    qrcode::{qrcode_from_str, QrCode, QrCodeError},
-};
+use strum::IntoEnumIterator as _;
+use utils::qrcode::{qrcode_from_str, QrCode, QrCodeError};
 
 use self::traits::Addressable;
 pub use rpc::RpcAddress;
@@ -38,6 +37,11 @@ pub enum AddressError {
     DecodingError(String),
     #[error("Invalid prefix: {0}")]
     InvalidPrefix(String),
+    #[error("The address is for {}, but {} is expected", .actual.name(), .expected.name())]
+    WrongNetwork {
+        expected: ChainType,
+        actual: ChainType,
+    },
     #[error("QR Code error: {0}")]
     QrCodeError(#[from] QrCodeError),
 }
@@ -51,7 +55,7 @@ pub struct Address<T> {
 
 impl<T: Addressable> Address<T> {
     pub fn new(cfg: &ChainConfig, object: T) -> Result<Self, AddressError> {
-        let hrp = object.address_prefix(cfg);
+        let hrp = object.address_prefix(*cfg.chain_type());
         let address = bech32_encoding::bech32m_encode(hrp, object.encode_to_bytes_for_address())?;
         Ok(Self { address, object })
     }
@@ -65,8 +69,19 @@ impl<T: Addressable> Address<T> {
         let object = T::decode_from_bytes_from_address(data.data())
             .map_err(|e| AddressError::DecodingError(e.to_string()))?;
 
-        let hrp_ok = data.hrp() == object.address_prefix(cfg);
-        ensure!(hrp_ok, AddressError::InvalidPrefix(data.hrp().to_owned()));
+        let expected_chain_type = *cfg.chain_type();
+        if data.hrp() != object.address_prefix(expected_chain_type) {
+            // Check whether the address is valid, but for a different network.
+            let actual_chain_type = ChainType::iter()
+                .find(|chain_type| data.hrp() == object.address_prefix(*chain_type));
+            return Err(match actual_chain_type {
+                Some(actual) => AddressError::WrongNetwork {
+                    expected: expected_chain_type,
+                    actual,
+                },
+                None => AddressError::InvalidPrefix(data.hrp().to_owned()),
+            });
+        }
 
         Ok(Self { address, object })
     }
@@ -116,7 +131,7 @@ impl<T> Display for Address<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::chain::config::{create_mainnet, create_regtest};
+    use crate::chain::config::{create_mainnet, create_regtest, create_testnet};
     use crate::chain::{DelegationId, Destination, PoolId};
     use crypto::{
         key::{KeyKind, PrivateKey},
@@ -124,6 +139,7 @@ mod tests {
     };
     use pubkeyhash::PublicKeyHash;
     use rstest::rstest;
+    use serialization::hex::HexDecode;
     use test_utils::random::Seed;
 
     #[rstest]
@@ -141,6 +157,66 @@ mod tests {
         assert_eq!(public_key_hash_restored_dest, public_key_hash_dest);
     }
 
+    #[test]
+    fn fixed_vrf_public_key_addresses() {
+        let pk = VRFPublicKey::hex_decode_all(
+            "006a9602eaae527451eed95667ecd4756324084e46b52fe908283c8b6b69095c09",
+        )
+        .unwrap();
+
+        for (cfg, expected) in [
+            (
+                create_mainnet(),
+                "mvrfpk1qp4fvqh24ef8g50wm9tx0mx5w43jgzzwg66jl6gg9q7gk6mfp9wqjejjtsj",
+            ),
+            (
+                create_testnet(),
+                "tvrfpk1qp4fvqh24ef8g50wm9tx0mx5w43jgzzwg66jl6gg9q7gk6mfp9wqju2t2k0",
+            ),
+        ] {
+            let address = Address::new(&cfg, pk.clone()).unwrap();
+            assert_eq!(address.as_str(), expected);
+
+            let decoded = Address::<VRFPublicKey>::from_string(&cfg, expected).unwrap();
+            assert_eq!(decoded.into_object(), pk);
+        }
+
+        // An address for one chain can't be used on another
+        assert_eq!(
+            Address::<VRFPublicKey>::from_string(
+                &create_testnet(),
+                "mvrfpk1qp4fvqh24ef8g50wm9tx0mx5w43jgzzwg66jl6gg9q7gk6mfp9wqjejjtsj",
+            ),
+            Err(AddressError::WrongNetwork {
+                expected: ChainType::Testnet,
+                actual: ChainType::Mainnet
+            })
+        );
+    }
+
+    #[test]
+    fn wrong_network() {
+        let regtest_address = "rmt1qyyra5j3qduhyd43wa50lpn2ddpg9ql0u50ceu68";
+        Address::<Destination>::from_string(&create_regtest(), regtest_address).unwrap();
+
+        assert_eq!(
+            Address::<Destination>::from_string(&create_mainnet(), regtest_address),
+            Err(AddressError::WrongNetwork {
+                expected: ChainType::Mainnet,
+                actual: ChainType::Regtest
+            })
+        );
+
+        // A pool id address can't be decoded as a destination at all.
+        assert!(matches!(
+            Address::<Destination>::from_string(
+                &create_regtest(),
+                "rpool1zg7yccqqjlz38cyghxlxyp5lp36vwecu2g7gudrf58plzjm75tzq99fr6v",
+            ),
+            Err(AddressError::DecodingError(_) | AddressError::InvalidPrefix(_))
+        ));
+    }
+
     #[test]
     fn to_short_string() {
         let cfg = create_regtest();
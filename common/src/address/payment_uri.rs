@@ -0,0 +1,412 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Payment URIs
+//!
+//! A payment URI describes a payment request in a form that can be passed between applications,
+//! e.g. as a link or a QR code:
+//!
+//! `<scheme>:<address>?amount=<amount>&token=<token id>&label=<label>&message=<message>`
+//!
+//! The scheme depends on the chain (see [ChainConfig::payment_uri_scheme]); the address and
+//! the token id must be bech32m addresses of the same chain. The amount is a decimal number
+//! in the units of the coin or, if the token id is specified, of the token. The label (e.g.
+//! the name of the recipient) and the message (e.g. the purpose of the payment) are
+//! percent-encoded UTF-8 strings.
+//!
+//! All the parameters are optional and may appear at most once. Unknown parameters are
+//! ignored, unless their names start with `req-`, in which case the URI must be rejected,
+//! because the payment can't be made correctly without understanding them.
+
+use std::fmt::Display;
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use utils::ensure;
+
+use crate::{
+    chain::{tokens::TokenId, ChainConfig, Destination},
+    primitives::DecimalAmount,
+};
+
+use super::{Address, AddressError};
+
+const AMOUNT_PARAM: &str = "amount";
+const TOKEN_PARAM: &str = "token";
+const LABEL_PARAM: &str = "label";
+const MESSAGE_PARAM: &str = "message";
+const REQUIRED_PARAM_PREFIX: &str = "req-";
+
+/// The characters that are percent-encoded in parameter values; everything except
+/// the RFC 3986 "unreserved" characters.
+const VALUE_ENCODE_SET: &AsciiSet =
+    &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PaymentUriError {
+    #[error("The URI scheme is missing")]
+    MissingScheme,
+    #[error("Wrong URI scheme '{actual}', '{expected}' is expected")]
+    WrongScheme {
+        expected: &'static str,
+        actual: String,
+    },
+    #[error("Invalid address: {0}")]
+    InvalidAddress(AddressError),
+    #[error("Invalid token id: {0}")]
+    InvalidTokenId(AddressError),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Malformed parameter: {0}")]
+    MalformedParameter(String),
+    #[error("Duplicate parameter: {0}")]
+    DuplicateParameter(String),
+    #[error("Invalid encoding of parameter: {0}")]
+    InvalidParameterEncoding(String),
+    #[error("Unsupported required parameter: {0}")]
+    UnsupportedRequiredParameter(String),
+}
+
+/// A payment request that can be converted to and from a payment URI
+#[derive(Debug, Clone)]
+pub struct PaymentUri {
+    scheme: &'static str,
+    destination: Address<Destination>,
+    amount: Option<DecimalAmount>,
+    token_id: Option<Address<TokenId>>,
+    label: Option<String>,
+    message: Option<String>,
+}
+
+impl PaymentUri {
+    pub fn new(chain_config: &ChainConfig, destination: Address<Destination>) -> Self {
+        Self {
+            scheme: chain_config.payment_uri_scheme(),
+            destination,
+            amount: None,
+            token_id: None,
+            label: None,
+            message: None,
+        }
+    }
+
+    pub fn with_amount(mut self, amount: DecimalAmount) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_token_id(mut self, token_id: Address<TokenId>) -> Self {
+        self.token_id = Some(token_id);
+        self
+    }
+
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn with_message(mut self, message: String) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Parse a payment URI, which must belong to the specified chain.
+    pub fn from_string(chain_config: &ChainConfig, uri: &str) -> Result<Self, PaymentUriError> {
+        let (scheme, rest) = uri.split_once(':').ok_or(PaymentUriError::MissingScheme)?;
+        // Schemes are case-insensitive per RFC 3986
+        ensure!(
+            scheme.eq_ignore_ascii_case(chain_config.payment_uri_scheme()),
+            PaymentUriError::WrongScheme {
+                expected: chain_config.payment_uri_scheme(),
+                actual: scheme.to_owned(),
+            }
+        );
+
+        let (address, query) = match rest.split_once('?') {
+            Some((address, query)) => (address, Some(query)),
+            None => (rest, None),
+        };
+        let destination =
+            Address::from_string(chain_config, address).map_err(PaymentUriError::InvalidAddress)?;
+        let mut result = Self::new(chain_config, destination);
+
+        for param in query.into_iter().flat_map(|query| query.split('&')) {
+            let (name, value) = param
+                .split_once('=')
+                .ok_or_else(|| PaymentUriError::MalformedParameter(param.to_owned()))?;
+            let value = percent_decode_str(value)
+                .decode_utf8()
+                .map_err(|_| PaymentUriError::InvalidParameterEncoding(name.to_owned()))?;
+
+            match name {
+                AMOUNT_PARAM => {
+                    let amount = parse_amount(&value)?;
+                    set_param(&mut result.amount, name, amount)?;
+                }
+                TOKEN_PARAM => {
+                    let token_id = Address::from_string(chain_config, value)
+                        .map_err(PaymentUriError::InvalidTokenId)?;
+                    set_param(&mut result.token_id, name, token_id)?;
+                }
+                LABEL_PARAM => set_param(&mut result.label, name, value.into_owned())?,
+                MESSAGE_PARAM => set_param(&mut result.message, name, value.into_owned())?,
+                _ => ensure!(
+                    !name.starts_with(REQUIRED_PARAM_PREFIX),
+                    PaymentUriError::UnsupportedRequiredParameter(name.to_owned())
+                ),
+            }
+        }
+
+        // The number of decimals of a token is not known here, but for coins it can be checked
+        if let (Some(amount), None) = (&result.amount, &result.token_id) {
+            ensure!(
+                amount.to_amount(chain_config.coin_decimals()).is_some(),
+                PaymentUriError::InvalidAmount(amount.to_string())
+            );
+        }
+
+        Ok(result)
+    }
+
+    pub fn destination(&self) -> &Address<Destination> {
+        &self.destination
+    }
+
+    pub fn amount(&self) -> Option<DecimalAmount> {
+        self.amount
+    }
+
+    /// The token to be paid with; `None` means coins
+    pub fn token_id(&self) -> Option<&Address<TokenId>> {
+        self.token_id.as_ref()
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+fn set_param<T>(param: &mut Option<T>, name: &str, value: T) -> Result<(), PaymentUriError> {
+    ensure!(
+        param.is_none(),
+        PaymentUriError::DuplicateParameter(name.to_owned())
+    );
+    *param = Some(value);
+    Ok(())
+}
+
+/// Parse the amount, which must be a plain decimal number, e.g. no separators are allowed.
+fn parse_amount(value: &str) -> Result<DecimalAmount, PaymentUriError> {
+    let is_plain_number = value.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && value.chars().filter(|c| *c == '.').count() <= 1;
+    ensure!(
+        is_plain_number,
+        PaymentUriError::InvalidAmount(value.to_owned())
+    );
+
+    value.parse().map_err(|_| PaymentUriError::InvalidAmount(value.to_owned()))
+}
+
+impl Display for PaymentUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.scheme, self.destination)?;
+
+        let params = [
+            (AMOUNT_PARAM, self.amount.map(|amount| amount.to_string())),
+            (TOKEN_PARAM, self.token_id.as_ref().map(|id| id.to_string())),
+            (LABEL_PARAM, self.label.clone()),
+            (MESSAGE_PARAM, self.message.clone()),
+        ];
+        let mut separator = '?';
+        for (name, value) in params {
+            if let Some(value) = value {
+                write!(
+                    f,
+                    "{separator}{name}={}",
+                    utf8_percent_encode(&value, VALUE_ENCODE_SET)
+                )?;
+                separator = '&';
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        address::pubkeyhash::PublicKeyHash,
+        chain::config::{create_mainnet, create_testnet},
+        primitives::H256,
+    };
+    use crypto::key::{KeyKind, PrivateKey};
+    use randomness::{CryptoRng, Rng};
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    fn random_destination(
+        chain_config: &ChainConfig,
+        rng: &mut (impl Rng + CryptoRng),
+    ) -> Address<Destination> {
+        let (_, public_key) = PrivateKey::new_from_rng(rng, KeyKind::Secp256k1Schnorr);
+        let destination = Destination::PublicKeyHash(PublicKeyHash::from(&public_key));
+        Address::new(chain_config, destination).unwrap()
+    }
+
+    fn random_token_id(chain_config: &ChainConfig, rng: &mut impl Rng) -> Address<TokenId> {
+        Address::new(chain_config, TokenId::new(H256::random_using(rng))).unwrap()
+    }
+
+    fn assert_same(uri1: &PaymentUri, uri2: &PaymentUri) {
+        assert_eq!(uri1.destination(), uri2.destination());
+        assert_eq!(
+            uri1.amount().map(|amount| amount.to_string()),
+            uri2.amount().map(|amount| amount.to_string())
+        );
+        assert_eq!(uri1.token_id(), uri2.token_id());
+        assert_eq!(uri1.label(), uri2.label());
+        assert_eq!(uri1.message(), uri2.message());
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn round_trip(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+
+        let destination = random_destination(&chain_config, &mut rng);
+        let token_id = random_token_id(&chain_config, &mut rng);
+        let amount = DecimalAmount::from_uint_decimal(rng.gen_range(0..1_000_000_000), 5);
+
+        let uris = [
+            PaymentUri::new(&chain_config, destination.clone()),
+            PaymentUri::new(&chain_config, destination.clone()).with_amount(amount),
+            PaymentUri::new(&chain_config, destination.clone())
+                .with_amount(amount)
+                .with_token_id(token_id.clone())
+                .with_label("Alice & Bob's café".to_owned())
+                .with_message("Invoice #42: 100% paid?=no/yes".to_owned()),
+            PaymentUri::new(&chain_config, destination)
+                .with_token_id(token_id)
+                .with_message(String::new()),
+        ];
+
+        for uri in uris {
+            let uri_str = uri.to_string();
+            let parsed = PaymentUri::from_string(&chain_config, &uri_str).unwrap();
+            assert_same(&uri, &parsed);
+            assert_eq!(parsed.to_string(), uri_str);
+        }
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn encoding(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+        let address = random_destination(&chain_config, &mut rng);
+
+        let uri = PaymentUri::new(&chain_config, address.clone())
+            .with_amount("12.5".parse().unwrap())
+            .with_label("Shop".to_owned())
+            .with_message("Order 7, thanks!".to_owned());
+        assert_eq!(
+            uri.to_string(),
+            format!("mintlayer:{address}?amount=12.5&label=Shop&message=Order%207%2C%20thanks%21")
+        );
+
+        // The scheme is case-insensitive and unknown optional parameters are ignored
+        let parsed = PaymentUri::from_string(
+            &chain_config,
+            &format!("MintLayer:{address}?foo=bar&amount=12.5&label=Shop&message=Order%207%2C%20thanks%21"),
+        )
+        .unwrap();
+        assert_same(&uri, &parsed);
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn invalid_uris(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_mainnet();
+        let address = random_destination(&chain_config, &mut rng);
+        let testnet_address = random_destination(&create_testnet(), &mut rng);
+        let testnet_token_id = random_token_id(&create_testnet(), &mut rng);
+
+        let check = |uri: String, expected: PaymentUriError| {
+            assert_eq!(
+                PaymentUri::from_string(&chain_config, &uri).unwrap_err(),
+                expected
+            );
+        };
+
+        check(address.to_string(), PaymentUriError::MissingScheme);
+        check(
+            format!("mintlayer-testnet:{testnet_address}"),
+            PaymentUriError::WrongScheme {
+                expected: "mintlayer",
+                actual: "mintlayer-testnet".to_owned(),
+            },
+        );
+        assert!(matches!(
+            PaymentUri::from_string(&chain_config, &format!("mintlayer:{testnet_address}")),
+            Err(PaymentUriError::InvalidAddress(_))
+        ));
+        assert!(matches!(
+            PaymentUri::from_string(
+                &chain_config,
+                &format!("mintlayer:{address}?token={testnet_token_id}")
+            ),
+            Err(PaymentUriError::InvalidTokenId(_))
+        ));
+
+        for amount in ["", "1,5", "1_000", "-1", "1.2.3", " 1", "1e5"] {
+            check(
+                format!("mintlayer:{address}?amount={amount}"),
+                PaymentUriError::InvalidAmount(amount.to_owned()),
+            );
+        }
+        // Too many decimals for coins
+        check(
+            format!("mintlayer:{address}?amount=0.000000000001"),
+            PaymentUriError::InvalidAmount("0.000000000001".to_owned()),
+        );
+
+        check(
+            format!("mintlayer:{address}?amount=1&amount=2"),
+            PaymentUriError::DuplicateParameter("amount".to_owned()),
+        );
+        check(
+            format!("mintlayer:{address}?label"),
+            PaymentUriError::MalformedParameter("label".to_owned()),
+        );
+        check(
+            format!("mintlayer:{address}?label=%FF"),
+            PaymentUriError::InvalidParameterEncoding("label".to_owned()),
+        );
+        check(
+            format!("mintlayer:{address}?req-expiry=100"),
+            PaymentUriError::UnsupportedRequiredParameter("req-expiry".to_owned()),
+        );
+    }
+}
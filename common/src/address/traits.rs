@@ -16,15 +16,16 @@
 use crypto::vrf::VRFPublicKey;
 use serialization::{DecodeAll, Encode};
 
-use crate::chain::ChainConfig;
+use crate::chain::config::ChainType;
 
 use super::AddressError;
 
 pub trait Addressable {
     type Error: std::error::Error;
 
+    /// The human-readable part of the bech32m address on a chain of the given type.
     #[must_use]
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str;
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str;
 
     #[must_use]
     fn encode_to_bytes_for_address(&self) -> Vec<u8>;
@@ -42,8 +43,8 @@ pub trait Addressable {
 impl Addressable for VRFPublicKey {
     type Error = AddressError;
 
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str {
-        chain_config.vrf_public_key_address_prefix()
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str {
+        chain_type.vrf_public_key_address_prefix()
     }
 
     fn encode_to_bytes_for_address(&self) -> Vec<u8> {
@@ -19,7 +19,8 @@ use crate::{
     chain::{
         config::{
             create_mainnet_genesis, create_testnet_genesis, create_unit_test_genesis,
-            emission_schedule, ChainConfig, ChainType, EmissionScheduleTabular,
+            emission_schedule, ChainConfig, ChainParametersError, ChainType,
+            EmissionScheduleTabular, EpochParams, MaturityParams, TokenParams,
         },
         get_initial_randomness,
         pos::{
@@ -28,12 +29,13 @@ use crate::{
         },
         pos_initial_difficulty,
         pow::PoWChainConfigBuilder,
-        ChainstateUpgrade, ChainstateUpgradesBuilder, ChangeTokenMetadataUriActivated, CoinUnit,
-        ConsensusUpgrade, DataDepositFeeVersion, Destination, FrozenTokensValidationVersion,
-        GenBlock, Genesis, HtlcActivated, NetUpgrades, OrdersActivated, OrdersVersion,
-        PoSChainConfig, PoSConsensusVersion, PoWChainConfig, RewardDistributionVersion,
-        SighashInputCommitmentVersion, StakerDestinationUpdateForbidden, TokenIdGenerationVersion,
-        TokenIssuanceVersion, TokensFeeVersion,
+        BlockSizeLimits, ChainstateUpgrade, ChainstateUpgradesBuilder,
+        ChangeTokenMetadataUriActivated, CoinUnit, ConsensusUpgrade, DataDepositFeeVersion,
+        Destination, FrozenTokensValidationVersion, GenBlock, Genesis, HtlcActivated, NetUpgrades,
+        OrdersActivated, OrdersVersion, PoSChainConfig, PoSConsensusVersion, PoWChainConfig,
+        PoWDifficultyAdjustment, RewardDistributionVersion, SighashInputCommitmentVersion,
+        StakerDestinationUpdateForbidden, TokenIdGenerationVersion, TokenIssuanceVersion,
+        TokenTickerUniquenessActivated, TokensFeeVersion,
     },
     primitives::{
         id::WithId, per_thousand::PerThousand, semver::SemVer, Amount, BlockCount, BlockDistance,
@@ -201,6 +203,30 @@ impl ChainType {
         }
     }
 
+    fn default_block_size_limits(&self) -> NetUpgrades<BlockSizeLimits> {
+        match self {
+            ChainType::Mainnet | ChainType::Testnet | ChainType::Regtest | ChainType::Signet => {
+                NetUpgrades::initialize(vec![(
+                    BlockHeight::zero(),
+                    BlockSizeLimits::new(1_048_576, 1_048_576),
+                )])
+                .expect("net upgrades")
+            }
+        }
+    }
+
+    fn default_pow_difficulty_adjustments(&self) -> NetUpgrades<PoWDifficultyAdjustment> {
+        match self {
+            ChainType::Mainnet | ChainType::Testnet | ChainType::Regtest | ChainType::Signet => {
+                NetUpgrades::initialize(vec![(
+                    BlockHeight::zero(),
+                    PoWDifficultyAdjustment::Retargeting,
+                )])
+                .expect("net upgrades")
+            }
+        }
+    }
+
     fn default_chainstate_upgrades(&self) -> NetUpgrades<ChainstateUpgrade> {
         match self {
             ChainType::Mainnet => ChainstateUpgradesBuilder::new(ChainstateUpgrade::new(
@@ -216,6 +242,7 @@ impl ChainType {
                 StakerDestinationUpdateForbidden::No,
                 TokenIdGenerationVersion::V0,
                 SighashInputCommitmentVersion::V0,
+                TokenTickerUniquenessActivated::No,
             ))
             .then(MAINNET_FORK_HEIGHT_1_HTLC_AND_ORDERS, |builder| {
                 builder
@@ -253,6 +280,7 @@ impl ChainType {
                 StakerDestinationUpdateForbidden::No,
                 TokenIdGenerationVersion::V0,
                 SighashInputCommitmentVersion::V0,
+                TokenTickerUniquenessActivated::No,
             ))
             .then(TESTNET_FORK_HEIGHT_1_TOKENS_V1, |builder| {
                 builder.token_issuance_version(TokenIssuanceVersion::V1)
@@ -302,6 +330,7 @@ pub fn default_regtest_chainstate_upgrade_at_genesis() -> ChainstateUpgrade {
         StakerDestinationUpdateForbidden::Yes,
         TokenIdGenerationVersion::V1,
         SighashInputCommitmentVersion::V1,
+        TokenTickerUniquenessActivated::No,
     )
 }
 
@@ -344,8 +373,7 @@ pub struct Builder {
     coin_decimals: u8,
     coin_ticker: &'static str,
     max_block_header_size: usize,
-    max_block_size_with_standard_txs: usize,
-    max_block_size_with_smart_contracts: usize,
+    block_size_limits: NetUpgrades<BlockSizeLimits>,
     data_in_no_signature_witness_allowed: bool,
     data_in_no_signature_witness_max_size: usize,
     max_depth_for_reorg: BlockDistance,
@@ -354,18 +382,23 @@ pub struct Builder {
     initial_randomness: H256,
     consensus_upgrades: NetUpgrades<ConsensusUpgrade>,
     chainstate_upgrades: NetUpgrades<ChainstateUpgrade>,
+    pow_difficulty_adjustments: NetUpgrades<PoWDifficultyAdjustment>,
     genesis_block: GenesisBlockInit,
     emission_schedule: EmissionScheduleInit,
     data_deposit_max_size: Option<usize>,
     token_max_uri_len: usize,
     token_max_dec_count: u8,
+    token_max_ticker_len: usize,
     token_max_name_len: usize,
     token_max_description_len: usize,
     token_min_hash_len: usize,
     token_max_hash_len: usize,
+    fungible_token_issuance_fee: Amount,
+    token_change_metadata_uri_fee: Amount,
     empty_consensus_reward_maturity_block_count: BlockCount,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
+    mempool_dust_threshold: Amount,
 }
 
 impl Builder {
@@ -388,8 +421,7 @@ impl Builder {
             software_version: SemVer::try_from(env!("CARGO_PKG_VERSION"))
                 .expect("invalid CARGO_PKG_VERSION value"),
             max_block_header_size: super::MAX_BLOCK_HEADER_SIZE,
-            max_block_size_with_standard_txs: super::MAX_BLOCK_TXS_SIZE,
-            max_block_size_with_smart_contracts: super::MAX_BLOCK_CONTRACTS_SIZE,
+            block_size_limits: chain_type.default_block_size_limits(),
             data_in_no_signature_witness_allowed: chain_type
                 .default_data_in_no_signature_witness_allowed(),
             data_in_no_signature_witness_max_size: super::TX_DATA_IN_NO_SIG_WITNESS_MAX_SIZE,
@@ -403,16 +435,21 @@ impl Builder {
             emission_schedule: EmissionScheduleInit::Mainnet,
             consensus_upgrades,
             chainstate_upgrades: chain_type.default_chainstate_upgrades(),
+            pow_difficulty_adjustments: chain_type.default_pow_difficulty_adjustments(),
             data_deposit_max_size: None,
             token_max_uri_len: super::TOKEN_MAX_URI_LEN,
             token_max_dec_count: super::TOKEN_MAX_DEC_COUNT,
+            token_max_ticker_len: super::TOKEN_MAX_TICKER_LEN,
             token_max_name_len: super::TOKEN_MAX_NAME_LEN,
             token_max_description_len: super::TOKEN_MAX_DESCRIPTION_LEN,
             token_min_hash_len: super::TOKEN_MIN_HASH_LEN,
             token_max_hash_len: super::TOKEN_MAX_HASH_LEN,
+            fungible_token_issuance_fee: super::FUNGIBLE_TOKEN_ISSUANCE_FEE,
+            token_change_metadata_uri_fee: super::TOKEN_CHANGE_METADATA_URI_FEE,
             empty_consensus_reward_maturity_block_count: BlockCount::new(0),
             max_classic_multisig_public_keys_count: super::MAX_CLASSIC_MULTISIG_PUBLIC_KEYS_COUNT,
             min_stake_pool_pledge: super::MIN_STAKE_POOL_PLEDGE,
+            mempool_dust_threshold: Amount::ZERO,
         }
     }
 
@@ -441,8 +478,7 @@ impl Builder {
             default_rpc_port,
             software_version,
             max_block_header_size,
-            max_block_size_with_standard_txs,
-            max_block_size_with_smart_contracts,
+            block_size_limits,
             max_future_block_time_offset,
             data_in_no_signature_witness_allowed,
             data_in_no_signature_witness_max_size,
@@ -455,16 +491,21 @@ impl Builder {
             emission_schedule,
             consensus_upgrades,
             chainstate_upgrades,
+            pow_difficulty_adjustments,
             data_deposit_max_size,
             token_max_uri_len,
             token_max_dec_count,
+            token_max_ticker_len,
             token_max_name_len,
             token_max_description_len,
             token_min_hash_len,
             token_max_hash_len,
+            fungible_token_issuance_fee,
+            token_change_metadata_uri_fee,
             empty_consensus_reward_maturity_block_count,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
+            mempool_dust_threshold,
         } = self;
 
         let emission_table = match emission_schedule {
@@ -536,8 +577,7 @@ impl Builder {
             default_rpc_port,
             software_version,
             max_block_header_size,
-            max_block_size_with_standard_txs,
-            max_block_size_with_smart_contracts,
+            block_size_limits,
             max_future_block_time_offset,
             data_in_no_signature_witness_allowed,
             data_in_no_signature_witness_max_size,
@@ -553,18 +593,30 @@ impl Builder {
             final_supply,
             consensus_upgrades,
             chainstate_upgrades,
+            pow_difficulty_adjustments,
             data_deposit_max_size,
             token_max_uri_len,
             token_max_dec_count,
+            token_max_ticker_len,
             empty_consensus_reward_maturity_block_count,
             token_max_name_len,
             token_max_description_len,
             token_min_hash_len,
             token_max_hash_len,
+            fungible_token_issuance_fee,
+            token_change_metadata_uri_fee,
             max_classic_multisig_public_keys_count,
             min_stake_pool_pledge,
+            mempool_dust_threshold,
         }
     }
+
+    /// Build the chain config and check the cross-parameter invariants
+    pub fn try_build(self) -> Result<ChainConfig, ChainParametersError> {
+        let chain_config = self.build();
+        chain_config.validate_parameters()?;
+        Ok(chain_config)
+    }
 }
 
 macro_rules! builder_method {
@@ -592,16 +644,94 @@ impl Builder {
     builder_method!(data_in_no_signature_witness_allowed: bool);
     builder_method!(data_in_no_signature_witness_max_size: usize);
     builder_method!(max_block_header_size: usize);
-    builder_method!(max_block_size_with_standard_txs: usize);
-    builder_method!(max_block_size_with_smart_contracts: usize);
+    builder_method!(block_size_limits: NetUpgrades<BlockSizeLimits>);
     builder_method!(max_depth_for_reorg: BlockDistance);
     builder_method!(consensus_upgrades: NetUpgrades<ConsensusUpgrade>);
     builder_method!(chainstate_upgrades: NetUpgrades<ChainstateUpgrade>);
+    builder_method!(pow_difficulty_adjustments: NetUpgrades<PoWDifficultyAdjustment>);
     builder_method!(empty_consensus_reward_maturity_block_count: BlockCount);
     builder_method!(epoch_length: NonZeroU64);
     builder_method!(sealed_epoch_distance_from_tip: usize);
     builder_method!(data_deposit_max_size: Option<usize>);
     builder_method!(min_stake_pool_pledge: Amount);
+    builder_method!(mempool_dust_threshold: Amount);
+
+    /// Set the maximum size of a block that uses standard transactions at all heights
+    #[must_use = "chain::config::Builder dropped prematurely"]
+    pub fn max_block_size_with_standard_txs(self, size: usize) -> Self {
+        self.map_block_size_limits(|limits| limits.with_max_size_with_standard_txs(size))
+    }
+
+    /// Set the maximum size of a block that uses smart contracts at all heights
+    #[must_use = "chain::config::Builder dropped prematurely"]
+    pub fn max_block_size_with_smart_contracts(self, size: usize) -> Self {
+        self.map_block_size_limits(|limits| limits.with_max_size_with_smart_contracts(size))
+    }
+
+    fn map_block_size_limits(mut self, func: impl Fn(BlockSizeLimits) -> BlockSizeLimits) -> Self {
+        let upgrades = self
+            .block_size_limits
+            .all_upgrades()
+            .iter()
+            .map(|(height, limits)| (*height, func(*limits)))
+            .collect();
+        self.block_size_limits =
+            NetUpgrades::initialize(upgrades).expect("the upgrades are known to be valid");
+        self
+    }
+
+    /// Override all token parameters at once
+    pub fn token_params(mut self, params: TokenParams) -> Self {
+        let TokenParams {
+            max_dec_count,
+            max_ticker_len,
+            max_name_len,
+            max_description_len,
+            max_uri_len,
+            min_hash_len,
+            max_hash_len,
+            fungible_issuance_fee,
+            change_metadata_uri_fee,
+        } = params;
+
+        self.token_max_dec_count = max_dec_count;
+        self.token_max_ticker_len = max_ticker_len;
+        self.token_max_name_len = max_name_len;
+        self.token_max_description_len = max_description_len;
+        self.token_max_uri_len = max_uri_len;
+        self.token_min_hash_len = min_hash_len;
+        self.token_max_hash_len = max_hash_len;
+        self.fungible_token_issuance_fee = fungible_issuance_fee;
+        self.token_change_metadata_uri_fee = change_metadata_uri_fee;
+        self
+    }
+
+    /// Override all epoch parameters at once
+    pub fn epoch_params(mut self, params: EpochParams) -> Self {
+        let EpochParams {
+            epoch_length,
+            sealed_epoch_distance_from_tip,
+        } = params;
+
+        self.epoch_length = epoch_length;
+        self.sealed_epoch_distance_from_tip = sealed_epoch_distance_from_tip;
+        self
+    }
+
+    /// Override all maturity parameters at once
+    pub fn maturity_params(mut self, params: MaturityParams) -> Self {
+        let MaturityParams {
+            max_depth_for_reorg,
+            empty_consensus_reward_maturity_block_count,
+            min_stake_pool_pledge,
+        } = params;
+
+        self.max_depth_for_reorg = max_depth_for_reorg;
+        self.empty_consensus_reward_maturity_block_count =
+            empty_consensus_reward_maturity_block_count;
+        self.min_stake_pool_pledge = min_stake_pool_pledge;
+        self
+    }
 
     pub fn checkpoints(mut self, checkpoints: BTreeMap<BlockHeight, Id<GenBlock>>) -> Self {
         self.checkpoints = Some(checkpoints);
@@ -760,7 +890,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -777,7 +908,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -794,7 +926,8 @@ mod tests {
                             OrdersVersion::V1,
                             StakerDestinationUpdateForbidden::Yes,
                             TokenIdGenerationVersion::V1,
-                            SighashInputCommitmentVersion::V1
+                            SighashInputCommitmentVersion::V1,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                 ])
@@ -823,7 +956,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -840,7 +974,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -857,7 +992,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -874,7 +1010,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -891,7 +1028,8 @@ mod tests {
                             OrdersVersion::V0,
                             StakerDestinationUpdateForbidden::No,
                             TokenIdGenerationVersion::V0,
-                            SighashInputCommitmentVersion::V0
+                            SighashInputCommitmentVersion::V0,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                     (
@@ -908,7 +1046,8 @@ mod tests {
                             OrdersVersion::V1,
                             StakerDestinationUpdateForbidden::Yes,
                             TokenIdGenerationVersion::V1,
-                            SighashInputCommitmentVersion::V1
+                            SighashInputCommitmentVersion::V1,
+                            TokenTickerUniquenessActivated::No
                         ),
                     ),
                 ])
@@ -916,4 +1055,48 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn block_size_limits() {
+        let fork_height = BlockHeight::new(100);
+        let config = Builder::new(ChainType::Regtest)
+            .block_size_limits(
+                NetUpgrades::initialize(vec![
+                    (BlockHeight::zero(), BlockSizeLimits::new(10_000, 5_000)),
+                    (fork_height, BlockSizeLimits::new(20_000, 7_000)),
+                ])
+                .unwrap(),
+            )
+            .build();
+
+        for height in [BlockHeight::zero(), BlockHeight::new(99)] {
+            assert_eq!(config.max_block_size_from_std_scripts(height), 10_000);
+            assert_eq!(config.max_block_size_from_smart_contracts(height), 5_000);
+            assert_eq!(config.max_tx_size_for_mempool(height), 4_000);
+        }
+        for height in [fork_height, BlockHeight::new(1000)] {
+            assert_eq!(config.max_block_size_from_std_scripts(height), 20_000);
+            assert_eq!(config.max_block_size_from_smart_contracts(height), 7_000);
+            assert_eq!(config.max_tx_size_for_mempool(height), 6_000);
+        }
+
+        // Setting a single limit replaces it at all heights and keeps the other one
+        let config = Builder::new(ChainType::Regtest)
+            .block_size_limits(config.block_size_limits().clone())
+            .max_block_size_with_standard_txs(30_000)
+            .build();
+        assert_eq!(
+            config.max_block_size_from_std_scripts(BlockHeight::zero()),
+            30_000
+        );
+        assert_eq!(config.max_block_size_from_std_scripts(fork_height), 30_000);
+        assert_eq!(
+            config.max_block_size_from_smart_contracts(BlockHeight::zero()),
+            5_000
+        );
+        assert_eq!(
+            config.max_block_size_from_smart_contracts(fork_height),
+            7_000
+        );
+    }
 }
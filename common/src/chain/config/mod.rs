@@ -17,6 +17,7 @@ mod builder;
 mod checkpoints;
 pub mod checkpoints_data;
 pub mod emission_schedule;
+mod params;
 pub mod regtest;
 pub mod regtest_options;
 
@@ -55,12 +56,12 @@ use crate::{
 };
 
 use super::{
-    output_value::OutputValue, stakelock::StakePoolData, ChainstateUpgrade,
+    output_value::OutputValue, stakelock::StakePoolData, BlockSizeLimits, ChainstateUpgrade,
     ChangeTokenMetadataUriActivated, ConsensusUpgrade, DataDepositFeeVersion, DestinationTag,
     FrozenTokensValidationVersion, HtlcActivated, OrdersActivated, OrdersVersion,
-    RequiredConsensus, RewardDistributionVersion, SighashInputCommitmentVersion,
-    StakerDestinationUpdateForbidden, TokenIdGenerationVersion, TokenIssuanceVersion,
-    TokensFeeVersion,
+    PoWDifficultyAdjustment, RequiredConsensus, RewardDistributionVersion,
+    SighashInputCommitmentVersion, StakerDestinationUpdateForbidden, TokenIdGenerationVersion,
+    TokenIssuanceVersion, TokenTickerUniquenessActivated, TokensFeeVersion,
 };
 
 use self::emission_schedule::{CoinUnit, DEFAULT_INITIAL_MINT};
@@ -68,6 +69,9 @@ use self::emission_schedule::{CoinUnit, DEFAULT_INITIAL_MINT};
 pub use builder::Builder;
 pub use checkpoints::Checkpoints;
 pub use emission_schedule::{EmissionSchedule, EmissionScheduleFn, EmissionScheduleTabular};
+pub use params::{
+    validate_chain_parameters, ChainParametersError, EpochParams, MaturityParams, TokenParams,
+};
 
 const DEFAULT_MAX_FUTURE_BLOCK_TIME_OFFSET_V1: Duration = Duration::from_secs(120);
 const DEFAULT_MAX_FUTURE_BLOCK_TIME_OFFSET_V2: Duration = Duration::from_secs(30);
@@ -173,6 +177,105 @@ impl ChainType {
         }
     }
 
+    /// Bech32m addresses of this chain type will use this prefix
+    #[must_use]
+    pub const fn destination_address_prefix(
+        &self,
+        destination_tag: DestinationTag,
+    ) -> &'static str {
+        match self {
+            ChainType::Mainnet => match destination_tag {
+                DestinationTag::AnyoneCanSpend => "mxanyonecanspend",
+                DestinationTag::PublicKeyHash => "mtc",
+                DestinationTag::PublicKey => "mptc",
+                DestinationTag::ScriptHash => "mstc",
+                DestinationTag::ClassicMultisig => "mmtc",
+            },
+            ChainType::Testnet => match destination_tag {
+                DestinationTag::AnyoneCanSpend => "txanyonecanspend",
+                DestinationTag::PublicKeyHash => "tmt",
+                DestinationTag::PublicKey => "tpmt",
+                DestinationTag::ScriptHash => "tstc",
+                DestinationTag::ClassicMultisig => "tmtc",
+            },
+            ChainType::Regtest => match destination_tag {
+                DestinationTag::AnyoneCanSpend => "rxanyonecanspend",
+                DestinationTag::PublicKeyHash => "rmt",
+                DestinationTag::PublicKey => "rpmt",
+                DestinationTag::ScriptHash => "rstc",
+                DestinationTag::ClassicMultisig => "rmtc",
+            },
+            ChainType::Signet => match destination_tag {
+                DestinationTag::AnyoneCanSpend => "sxanyonecanspend",
+                DestinationTag::PublicKeyHash => "smt",
+                DestinationTag::PublicKey => "spmt",
+                DestinationTag::ScriptHash => "sstc",
+                DestinationTag::ClassicMultisig => "smtc",
+            },
+        }
+    }
+
+    #[must_use]
+    pub const fn pool_id_address_prefix(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mpool",
+            ChainType::Testnet => "tpool",
+            ChainType::Regtest => "rpool",
+            ChainType::Signet => "spool",
+        }
+    }
+
+    #[must_use]
+    pub const fn delegation_id_address_prefix(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mdelg",
+            ChainType::Testnet => "tdelg",
+            ChainType::Regtest => "rdelg",
+            ChainType::Signet => "sdelg",
+        }
+    }
+
+    #[must_use]
+    pub const fn token_id_address_prefix(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mmltk",
+            ChainType::Testnet => "tmltk",
+            ChainType::Regtest => "rmltk",
+            ChainType::Signet => "smltk",
+        }
+    }
+
+    #[must_use]
+    pub const fn order_id_address_prefix(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mordr",
+            ChainType::Testnet => "tordr",
+            ChainType::Regtest => "rordr",
+            ChainType::Signet => "sordr",
+        }
+    }
+
+    #[must_use]
+    pub const fn vrf_public_key_address_prefix(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mvrfpk",
+            ChainType::Testnet => "tvrfpk",
+            ChainType::Regtest => "rvrfpk",
+            ChainType::Signet => "svrfpk",
+        }
+    }
+
+    /// The scheme of the payment URIs of this chain type
+    #[must_use]
+    pub const fn payment_uri_scheme(&self) -> &'static str {
+        match self {
+            ChainType::Mainnet => "mintlayer",
+            ChainType::Testnet => "mintlayer-testnet",
+            ChainType::Regtest => "mintlayer-regtest",
+            ChainType::Signet => "mintlayer-signet",
+        }
+    }
+
     const fn default_p2p_port(&self) -> u16 {
         match self {
             ChainType::Mainnet => 3031,
@@ -230,39 +333,6 @@ impl ChainType {
     }
 }
 
-fn address_prefix(chain_type: ChainType, destination_tag: DestinationTag) -> &'static str {
-    match chain_type {
-        ChainType::Mainnet => match destination_tag {
-            DestinationTag::AnyoneCanSpend => "mxanyonecanspend",
-            DestinationTag::PublicKeyHash => "mtc",
-            DestinationTag::PublicKey => "mptc",
-            DestinationTag::ScriptHash => "mstc",
-            DestinationTag::ClassicMultisig => "mmtc",
-        },
-        ChainType::Testnet => match destination_tag {
-            DestinationTag::AnyoneCanSpend => "txanyonecanspend",
-            DestinationTag::PublicKeyHash => "tmt",
-            DestinationTag::PublicKey => "tpmt",
-            DestinationTag::ScriptHash => "tstc",
-            DestinationTag::ClassicMultisig => "tmtc",
-        },
-        ChainType::Regtest => match destination_tag {
-            DestinationTag::AnyoneCanSpend => "rxanyonecanspend",
-            DestinationTag::PublicKeyHash => "rmt",
-            DestinationTag::PublicKey => "rpmt",
-            DestinationTag::ScriptHash => "rstc",
-            DestinationTag::ClassicMultisig => "rmtc",
-        },
-        ChainType::Signet => match destination_tag {
-            DestinationTag::AnyoneCanSpend => "sxanyonecanspend",
-            DestinationTag::PublicKeyHash => "smt",
-            DestinationTag::PublicKey => "spmt",
-            DestinationTag::ScriptHash => "sstc",
-            DestinationTag::ClassicMultisig => "smtc",
-        },
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct ChainConfig {
     chain_type: ChainType,
@@ -270,6 +340,7 @@ pub struct ChainConfig {
     height_checkpoint_data: Checkpoints,
     consensus_upgrades: NetUpgrades<ConsensusUpgrade>,
     chainstate_upgrades: NetUpgrades<ChainstateUpgrade>,
+    pow_difficulty_adjustments: NetUpgrades<PoWDifficultyAdjustment>,
     magic_bytes: MagicBytes,
     p2p_port: u16,
     dns_seeds: Vec<&'static str>,
@@ -284,8 +355,7 @@ pub struct ChainConfig {
     emission_schedule: EmissionSchedule,
     final_supply: Option<CoinUnit>, // `None` if the supply increases indefinitely
     max_block_header_size: usize,
-    max_block_size_with_standard_txs: usize,
-    max_block_size_with_smart_contracts: usize,
+    block_size_limits: NetUpgrades<BlockSizeLimits>,
     data_in_no_signature_witness_max_size: usize,
     data_in_no_signature_witness_allowed: bool,
     max_depth_for_reorg: BlockDistance,
@@ -296,70 +366,55 @@ pub struct ChainConfig {
     data_deposit_max_size: Option<usize>,
     token_max_uri_len: usize,
     token_max_dec_count: u8,
+    token_max_ticker_len: usize,
     token_max_name_len: usize,
     token_max_description_len: usize,
     token_min_hash_len: usize,
     token_max_hash_len: usize,
+    fungible_token_issuance_fee: Amount,
+    token_change_metadata_uri_fee: Amount,
     empty_consensus_reward_maturity_block_count: BlockCount,
     max_classic_multisig_public_keys_count: usize,
     min_stake_pool_pledge: Amount,
+    mempool_dust_threshold: Amount,
 }
 
 impl ChainConfig {
     /// Bech32m addresses in this chain will use this prefix
     #[must_use]
     pub fn destination_address_prefix(&self, destination_tag: DestinationTag) -> &'static str {
-        address_prefix(self.chain_type, destination_tag)
+        self.chain_type.destination_address_prefix(destination_tag)
     }
 
     #[must_use]
     pub fn pool_id_address_prefix(&self) -> &'static str {
-        match self.chain_type {
-            ChainType::Mainnet => "mpool",
-            ChainType::Testnet => "tpool",
-            ChainType::Regtest => "rpool",
-            ChainType::Signet => "spool",
-        }
+        self.chain_type.pool_id_address_prefix()
     }
 
     #[must_use]
     pub fn delegation_id_address_prefix(&self) -> &'static str {
-        match self.chain_type {
-            ChainType::Mainnet => "mdelg",
-            ChainType::Testnet => "tdelg",
-            ChainType::Regtest => "rdelg",
-            ChainType::Signet => "sdelg",
-        }
+        self.chain_type.delegation_id_address_prefix()
     }
 
     #[must_use]
     pub fn token_id_address_prefix(&self) -> &'static str {
-        match self.chain_type {
-            ChainType::Mainnet => "mmltk",
-            ChainType::Testnet => "tmltk",
-            ChainType::Regtest => "rmltk",
-            ChainType::Signet => "smltk",
-        }
+        self.chain_type.token_id_address_prefix()
     }
 
     #[must_use]
     pub fn order_id_address_prefix(&self) -> &'static str {
-        match self.chain_type {
-            ChainType::Mainnet => "mordr",
-            ChainType::Testnet => "tordr",
-            ChainType::Regtest => "rordr",
-            ChainType::Signet => "sordr",
-        }
+        self.chain_type.order_id_address_prefix()
     }
 
     #[must_use]
     pub fn vrf_public_key_address_prefix(&self) -> &'static str {
-        match self.chain_type {
-            ChainType::Mainnet => "mvrfpk",
-            ChainType::Testnet => "tvrfpk",
-            ChainType::Regtest => "rvrfpk",
-            ChainType::Signet => "svrfpk",
-        }
+        self.chain_type.vrf_public_key_address_prefix()
+    }
+
+    /// The scheme of the payment URIs in this chain
+    #[must_use]
+    pub fn payment_uri_scheme(&self) -> &'static str {
+        self.chain_type.payment_uri_scheme()
     }
 
     /// The BIP44 coin type for this chain
@@ -511,27 +566,37 @@ impl ChainConfig {
         self.max_block_header_size
     }
 
-    /// The maximum size of a block that uses standard transactions
+    /// The block size limits at all heights
     #[must_use]
-    pub fn max_block_size_from_std_scripts(&self) -> usize {
-        self.max_block_size_with_standard_txs
+    pub fn block_size_limits(&self) -> &NetUpgrades<BlockSizeLimits> {
+        &self.block_size_limits
     }
 
-    /// The maximum size of a block that uses smart contracts
+    /// The maximum size of a block at the given height that uses standard transactions
     #[must_use]
-    pub fn max_block_size_from_smart_contracts(&self) -> usize {
-        self.max_block_size_with_smart_contracts
+    pub fn max_block_size_from_std_scripts(&self, height: BlockHeight) -> usize {
+        self.block_size_limits.version_at_height(height).1.max_size_with_standard_txs()
     }
 
-    /// The maximum size of any transaction submitted to the node for the mempool
-    pub fn max_tx_size_for_mempool(&self) -> usize {
+    /// The maximum size of a block at the given height that uses smart contracts
+    #[must_use]
+    pub fn max_block_size_from_smart_contracts(&self, height: BlockHeight) -> usize {
+        self.block_size_limits
+            .version_at_height(height)
+            .1
+            .max_size_with_smart_contracts()
+    }
+
+    /// The maximum size of any transaction submitted to the node for the mempool, when it's
+    /// going to be included into a block at the given height
+    pub fn max_tx_size_for_mempool(&self, height: BlockHeight) -> usize {
         // Reserve some space in the block for the data it needs to store beyond the transaction
         // data itself, namely the transaction count due to how sequences of elements are encoded.
         const BLOCK_DATA_OVERHEAD: usize = 1000;
 
         let max_block_size = std::cmp::min(
-            self.max_block_size_from_std_scripts(),
-            self.max_block_size_from_smart_contracts(),
+            self.max_block_size_from_std_scripts(height),
+            self.max_block_size_from_smart_contracts(height),
         );
 
         max_block_size.saturating_sub(BLOCK_DATA_OVERHEAD)
@@ -599,7 +664,7 @@ impl ChainConfig {
 
     /// The fee for issuing a fungible token
     pub fn fungible_token_issuance_fee(&self) -> Amount {
-        FUNGIBLE_TOKEN_ISSUANCE_FEE
+        self.fungible_token_issuance_fee
     }
 
     /// The fee for issuing a NFT
@@ -640,7 +705,7 @@ impl ChainConfig {
 
     /// The fee for changing token metadata uri
     pub fn token_change_metadata_uri_fee(&self) -> Amount {
-        TOKEN_CHANGE_METADATA_URI_FEE
+        self.token_change_metadata_uri_fee
     }
 
     /// The maximum length of a URI contained in a token
@@ -658,7 +723,7 @@ impl ChainConfig {
     /// The maximum length of a ticker of a token
     #[must_use]
     pub fn token_max_ticker_len(&self) -> usize {
-        TOKEN_MAX_TICKER_LEN
+        self.token_max_ticker_len
     }
 
     /// The maximum length of a description of a token
@@ -702,6 +767,12 @@ impl ChainConfig {
         &self.pow_chain_config
     }
 
+    /// The PoW difficulty adjustment algorithms and the heights at which they become active
+    #[must_use]
+    pub fn pow_difficulty_adjustments(&self) -> &NetUpgrades<PoWDifficultyAdjustment> {
+        &self.pow_difficulty_adjustments
+    }
+
     /// The minimum number of blocks required to be able to spend a utxo coming from a decommissioned pool
     #[must_use]
     pub fn staking_pool_spend_maturity_block_count(&self, block_height: BlockHeight) -> BlockCount {
@@ -726,9 +797,64 @@ impl ChainConfig {
         self.min_stake_pool_pledge
     }
 
+    /// Coin outputs below this amount are considered dust and not relayed by default.
+    /// This is a mempool policy, not a consensus rule. It is zero on all the networks, because
+    /// the wallet doesn't avoid creating small change outputs; node operators may set a
+    /// threshold in the mempool config.
+    pub fn mempool_dust_threshold(&self) -> Amount {
+        self.mempool_dust_threshold
+    }
+
     pub fn final_supply(&self) -> Option<CoinUnit> {
         self.final_supply
     }
+
+    /// Token issuance limits and fees that don't depend on the block height
+    #[must_use]
+    pub fn token_params(&self) -> TokenParams {
+        TokenParams {
+            max_dec_count: self.token_max_dec_count,
+            max_ticker_len: self.token_max_ticker_len,
+            max_name_len: self.token_max_name_len,
+            max_description_len: self.token_max_description_len,
+            max_uri_len: self.token_max_uri_len,
+            min_hash_len: self.token_min_hash_len,
+            max_hash_len: self.token_max_hash_len,
+            fungible_issuance_fee: self.fungible_token_issuance_fee,
+            change_metadata_uri_fee: self.token_change_metadata_uri_fee,
+        }
+    }
+
+    /// PoS epoch settings
+    #[must_use]
+    pub fn epoch_params(&self) -> EpochParams {
+        EpochParams {
+            epoch_length: self.epoch_length,
+            sealed_epoch_distance_from_tip: self.sealed_epoch_distance_from_tip,
+        }
+    }
+
+    /// Maturity distances and reorg limits
+    #[must_use]
+    pub fn maturity_params(&self) -> MaturityParams {
+        MaturityParams {
+            max_depth_for_reorg: self.max_depth_for_reorg,
+            empty_consensus_reward_maturity_block_count: self
+                .empty_consensus_reward_maturity_block_count,
+            min_stake_pool_pledge: self.min_stake_pool_pledge,
+        }
+    }
+
+    /// Check the cross-parameter invariants of this config, see [validate_chain_parameters]
+    pub fn validate_parameters(&self) -> Result<(), ChainParametersError> {
+        validate_chain_parameters(
+            &self.token_params(),
+            &self.epoch_params(),
+            &self.maturity_params(),
+            &self.pow_chain_config,
+            &self.consensus_upgrades,
+        )
+    }
 }
 
 impl AsRef<ChainConfig> for ChainConfig {
@@ -738,8 +864,6 @@ impl AsRef<ChainConfig> for ChainConfig {
 }
 
 const MAX_BLOCK_HEADER_SIZE: usize = 1024;
-const MAX_BLOCK_TXS_SIZE: usize = 1_048_576;
-const MAX_BLOCK_CONTRACTS_SIZE: usize = 1_048_576;
 const TX_DATA_IN_NO_SIG_WITNESS_MAX_SIZE: usize = 128;
 
 const FUNGIBLE_TOKEN_ISSUANCE_FEE: Amount = CoinUnit::from_coins(100).to_amount_atoms();
@@ -919,6 +1043,7 @@ pub fn create_unit_test_config_builder() -> Builder {
                     StakerDestinationUpdateForbidden::Yes,
                     TokenIdGenerationVersion::V1,
                     SighashInputCommitmentVersion::V1,
+                    TokenTickerUniquenessActivated::No,
                 ),
             )])
             .expect("cannot fail"),
@@ -1010,6 +1135,115 @@ mod tests {
         assert_eq!(config.chain_type(), &ChainType::Testnet);
     }
 
+    #[rstest]
+    #[case(ChainType::Mainnet)]
+    #[case(ChainType::Testnet)]
+    #[case(ChainType::Regtest)]
+    fn default_parameters_valid(#[case] chain_type: ChainType) {
+        let config = Builder::new(chain_type).try_build().unwrap();
+        assert_eq!(config.validate_parameters(), Ok(()));
+    }
+
+    #[test]
+    fn parameter_groups_roundtrip() {
+        let config = create_mainnet();
+        let token_params = TokenParams {
+            max_ticker_len: 5,
+            fungible_issuance_fee: Amount::from_atoms(1),
+            ..config.token_params()
+        };
+        let epoch_params = EpochParams {
+            epoch_length: NonZeroU64::new(100).unwrap(),
+            sealed_epoch_distance_from_tip: 20,
+        };
+        let maturity_params = MaturityParams {
+            max_depth_for_reorg: BlockDistance::new(500),
+            ..config.maturity_params()
+        };
+
+        let config = Builder::new(ChainType::Mainnet)
+            .token_params(token_params)
+            .epoch_params(epoch_params)
+            .maturity_params(maturity_params)
+            .try_build()
+            .unwrap();
+
+        assert_eq!(config.token_params(), token_params);
+        assert_eq!(config.token_max_ticker_len(), 5);
+        assert_eq!(config.fungible_token_issuance_fee(), Amount::from_atoms(1));
+        assert_eq!(config.epoch_params(), epoch_params);
+        assert_eq!(config.maturity_params(), maturity_params);
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let token_params = TokenParams {
+            min_hash_len: 10,
+            max_hash_len: 5,
+            ..create_mainnet().token_params()
+        };
+        assert_eq!(
+            Builder::new(ChainType::Mainnet)
+                .token_params(token_params)
+                .try_build()
+                .unwrap_err(),
+            ChainParametersError::TokenHashLenRange { min: 10, max: 5 }
+        );
+
+        assert_eq!(
+            Builder::new(ChainType::Mainnet)
+                .epoch_length(NonZeroU64::new(10).unwrap())
+                .try_build()
+                .unwrap_err(),
+            ChainParametersError::SealedDistanceBelowReorgDepth {
+                sealed_distance: 20,
+                max_reorg_depth: DEFAULT_MAX_DEPTH_FOR_REORG,
+            }
+        );
+
+        assert_eq!(
+            Builder::new(ChainType::Mainnet)
+                .max_depth_for_reorg(BlockDistance::new(10_000))
+                .epoch_length(NonZeroU64::new(10_000).unwrap())
+                .try_build()
+                .unwrap_err(),
+            ChainParametersError::PoolMaturityBelowReorgDepth {
+                height: BlockHeight::new(1),
+                maturity: 7200,
+                max_reorg_depth: BlockDistance::new(10_000),
+            }
+        );
+
+        assert_eq!(
+            Builder::new(ChainType::Regtest)
+                .epoch_length(NonZeroU64::new(1).unwrap())
+                .try_build()
+                .unwrap_err(),
+            ChainParametersError::SealedDistanceBelowReorgDepth {
+                sealed_distance: 2,
+                max_reorg_depth: DEFAULT_MAX_DEPTH_FOR_REORG,
+            }
+        );
+
+        let consensus_upgrades = NetUpgrades::initialize(vec![
+            (BlockHeight::zero(), ConsensusUpgrade::IgnoreConsensus),
+            (BlockHeight::new(10), ConsensusUpgrade::IgnoreConsensus),
+        ])
+        .unwrap();
+        assert_eq!(
+            Builder::new(ChainType::Regtest)
+                .consensus_upgrades(consensus_upgrades.clone())
+                .try_build()
+                .unwrap_err(),
+            ChainParametersError::ZeroBlockRewardMaturity(BlockHeight::new(10))
+        );
+        Builder::new(ChainType::Regtest)
+            .consensus_upgrades(consensus_upgrades)
+            .empty_consensus_reward_maturity_block_count(BlockCount::new(1))
+            .try_build()
+            .unwrap();
+    }
+
     #[test]
     fn different_magic_bytes() {
         let config1 = Builder::new(ChainType::Regtest).build();
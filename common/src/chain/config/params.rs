@@ -0,0 +1,198 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cohesive groups of chain parameters.
+//!
+//! [ChainConfig](super::ChainConfig) stores a large number of loosely related values. The structs
+//! in this module group the ones that have to be consistent with each other, so that custom
+//! networks can override them together and have them checked by [validate_chain_parameters].
+
+use std::num::NonZeroU64;
+
+use crate::{
+    chain::{ConsensusUpgrade, NetUpgrades, PoWChainConfig},
+    primitives::{Amount, BlockCount, BlockDistance, BlockHeight},
+};
+
+/// Limits applied to token issuance data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenParams {
+    /// The maximum number of decimals in a token
+    pub max_dec_count: u8,
+
+    /// The maximum length of a token ticker
+    pub max_ticker_len: usize,
+
+    /// The maximum length of a token name
+    pub max_name_len: usize,
+
+    /// The maximum length of a token description
+    pub max_description_len: usize,
+
+    /// The maximum length of a token metadata URI
+    pub max_uri_len: usize,
+
+    /// The minimum length of a token metadata hash
+    pub min_hash_len: usize,
+
+    /// The maximum length of a token metadata hash
+    pub max_hash_len: usize,
+
+    /// The fee for issuing a fungible token
+    pub fungible_issuance_fee: Amount,
+
+    /// The fee for changing the metadata URI of a token
+    pub change_metadata_uri_fee: Amount,
+}
+
+/// PoS epoch settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochParams {
+    /// Length of an epoch in blocks
+    pub epoch_length: NonZeroU64,
+
+    /// Distance from the tip of the chain to the sealed state in epochs
+    pub sealed_epoch_distance_from_tip: usize,
+}
+
+impl EpochParams {
+    /// Distance from the tip to the sealed state in blocks, `None` on overflow
+    pub fn sealed_distance_in_blocks(&self) -> Option<u64> {
+        (self.sealed_epoch_distance_from_tip as u64).checked_mul(self.epoch_length.get())
+    }
+}
+
+/// Maturity distances and reorg limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaturityParams {
+    /// The maximum depth of a reorg the node accepts
+    pub max_depth_for_reorg: BlockDistance,
+
+    /// The maturity of block rewards for blocks without consensus data
+    pub empty_consensus_reward_maturity_block_count: BlockCount,
+
+    /// Min pledge required to create a stake pool
+    pub min_stake_pool_pledge: Amount,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ChainParametersError {
+    #[error("Token min hash length {min} is greater than max hash length {max}")]
+    TokenHashLenRange { min: usize, max: usize },
+    #[error("Token {0} length limit cannot be zero")]
+    ZeroTokenLengthLimit(&'static str),
+    #[error("Sealed epoch distance from tip cannot be zero")]
+    ZeroSealedEpochDistance,
+    #[error("Sealed epoch distance in blocks overflows")]
+    SealedDistanceOverflow,
+    #[error("Sealed state distance of {sealed_distance} blocks is shallower than the max reorg depth {max_reorg_depth}")]
+    SealedDistanceBelowReorgDepth {
+        sealed_distance: u64,
+        max_reorg_depth: BlockDistance,
+    },
+    #[error("Staking pool maturity of {maturity} blocks at height {height} is shorter than the max reorg depth {max_reorg_depth}")]
+    PoolMaturityBelowReorgDepth {
+        height: BlockHeight,
+        maturity: u64,
+        max_reorg_depth: BlockDistance,
+    },
+    #[error("Block reward maturity cannot be zero for the consensus starting at height {0}")]
+    ZeroBlockRewardMaturity(BlockHeight),
+}
+
+/// Check the invariants that span several parameter groups.
+///
+/// Sealed epoch data and matured pool outputs are assumed to never be affected by a reorg, so both
+/// distances have to be at least as deep as the max reorg depth. Block rewards produced after
+/// genesis must not be spendable right away, otherwise a shallow reorg could invalidate
+/// the transactions spending them.
+pub fn validate_chain_parameters(
+    tokens: &TokenParams,
+    epochs: &EpochParams,
+    maturity: &MaturityParams,
+    pow_config: &PoWChainConfig,
+    consensus_upgrades: &NetUpgrades<ConsensusUpgrade>,
+) -> Result<(), ChainParametersError> {
+    utils::ensure!(
+        tokens.min_hash_len <= tokens.max_hash_len,
+        ChainParametersError::TokenHashLenRange {
+            min: tokens.min_hash_len,
+            max: tokens.max_hash_len,
+        }
+    );
+    for (name, len) in [
+        ("ticker", tokens.max_ticker_len),
+        ("name", tokens.max_name_len),
+        ("hash", tokens.max_hash_len),
+    ] {
+        utils::ensure!(len > 0, ChainParametersError::ZeroTokenLengthLimit(name));
+    }
+
+    utils::ensure!(
+        epochs.sealed_epoch_distance_from_tip > 0,
+        ChainParametersError::ZeroSealedEpochDistance
+    );
+    let sealed_distance = epochs
+        .sealed_distance_in_blocks()
+        .ok_or(ChainParametersError::SealedDistanceOverflow)?;
+
+    let max_reorg_depth = maturity.max_depth_for_reorg;
+    let max_reorg_depth_int = u64::try_from(max_reorg_depth.to_int()).unwrap_or(0);
+
+    utils::ensure!(
+        sealed_distance >= max_reorg_depth_int,
+        ChainParametersError::SealedDistanceBelowReorgDepth {
+            sealed_distance,
+            max_reorg_depth,
+        }
+    );
+
+    for (height, upgrade) in consensus_upgrades.all_upgrades() {
+        match upgrade {
+            ConsensusUpgrade::PoS {
+                initial_difficulty: _,
+                config,
+            } => {
+                let maturity = config.staking_pool_spend_maturity_block_count().to_int();
+                utils::ensure!(
+                    maturity >= max_reorg_depth_int,
+                    ChainParametersError::PoolMaturityBelowReorgDepth {
+                        height: *height,
+                        maturity,
+                        max_reorg_depth,
+                    }
+                );
+            }
+            ConsensusUpgrade::PoW {
+                initial_difficulty: _,
+            } => {
+                utils::ensure!(
+                    pow_config.reward_maturity_distance().to_int() > 0,
+                    ChainParametersError::ZeroBlockRewardMaturity(*height)
+                );
+            }
+            ConsensusUpgrade::IgnoreConsensus => {
+                // Only the genesis block is allowed to have no reward maturity
+                utils::ensure!(
+                    *height == BlockHeight::zero()
+                        || maturity.empty_consensus_reward_maturity_block_count.to_int() > 0,
+                    ChainParametersError::ZeroBlockRewardMaturity(*height)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{str::FromStr, time::Duration};
+use std::{num::NonZeroU64, str::FromStr, time::Duration};
 
 use clap::Args;
 
@@ -75,6 +75,14 @@ pub struct ChainConfigOptions {
     #[clap(long)]
     pub chain_max_block_size_with_smart_contracts: Option<usize>,
 
+    /// Length of an epoch in blocks.
+    #[clap(long)]
+    pub chain_epoch_length: Option<NonZeroU64>,
+
+    /// Distance from the tip of the chain to the sealed state in epochs.
+    #[clap(long)]
+    pub chain_sealed_epoch_distance_from_tip: Option<usize>,
+
     /// Initial difficulty for the chain in Compact representation.
     #[clap(long)]
     pub chain_initial_difficulty: Option<u32>,
@@ -113,6 +121,8 @@ pub fn regtest_chain_config_builder(options: &ChainConfigOptions) -> Result<Buil
         chain_max_block_header_size,
         chain_max_block_size_with_standard_txs,
         chain_max_block_size_with_smart_contracts,
+        chain_epoch_length,
+        chain_sealed_epoch_distance_from_tip,
         chain_pos_netupgrades,
         chain_pos_netupgrades_v0_to_v1,
         chain_initial_difficulty,
@@ -167,6 +177,8 @@ pub fn regtest_chain_config_builder(options: &ChainConfigOptions) -> Result<Buil
     update_builder!(max_block_header_size);
     update_builder!(max_block_size_with_standard_txs);
     update_builder!(max_block_size_with_smart_contracts);
+    update_builder!(epoch_length);
+    update_builder!(sealed_epoch_distance_from_tip);
 
     let chain_initial_difficulty = chain_initial_difficulty
         .map(primitives::Compact)
@@ -256,5 +268,5 @@ pub fn regtest_chain_config_builder(options: &ChainConfigOptions) -> Result<Buil
 }
 
 pub fn regtest_chain_config(options: &ChainConfigOptions) -> Result<ChainConfig> {
-    regtest_chain_config_builder(options).map(Builder::build)
+    Ok(regtest_chain_config_builder(options)?.try_build()?)
 }
@@ -97,6 +97,30 @@ impl Id<GenBlock> {
             GenBlockId::Block(Id::new(self.to_hash()))
         }
     }
+
+    /// Convert to the [Id] of a proper [Block], or `None` if this refers to [Genesis].
+    pub fn to_block_id(&self, c: &crate::chain::config::ChainConfig) -> Option<Id<Block>> {
+        self.classify(c).chain_block_id()
+    }
+
+    /// Convert to the [Id] of a proper [Block] when the caller already knows it cannot refer to
+    /// [Genesis] (e.g. it was obtained for a non-zero height).
+    ///
+    /// The assumption is verified in debug builds only.
+    pub fn to_block_id_unchecked(&self, c: &crate::chain::config::ChainConfig) -> Id<Block> {
+        debug_assert!(
+            !self.classify(c).is_genesis(),
+            "Genesis id {self} converted to a block id"
+        );
+        Id::new(self.to_hash())
+    }
+}
+
+impl Id<Block> {
+    /// Convert to a generalized block [Id]. Always succeeds.
+    pub fn to_gen_block_id(&self) -> Id<GenBlock> {
+        (*self).into()
+    }
 }
 
 /// Classified generalized block
@@ -118,3 +142,39 @@ impl GenBlockId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use test_utils::random::Seed;
+
+    use crate::chain::config::create_unit_test_config;
+
+    use super::*;
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn block_id_conversions(#[case] seed: Seed) {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+
+        let genesis_id = chain_config.genesis_block_id();
+        assert_eq!(genesis_id.to_block_id(&chain_config), None);
+
+        let block_id = Id::<Block>::random_using(&mut rng);
+        let gen_block_id = block_id.to_gen_block_id();
+        assert_eq!(gen_block_id, block_id);
+        assert_eq!(gen_block_id.to_block_id(&chain_config), Some(block_id));
+        assert_eq!(gen_block_id.to_block_id_unchecked(&chain_config), block_id);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn genesis_to_block_id_unchecked_panics_in_debug() {
+        let chain_config = create_unit_test_config();
+        let _ = chain_config.genesis_block_id().to_block_id_unchecked(&chain_config);
+    }
+}
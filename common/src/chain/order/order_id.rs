@@ -18,7 +18,7 @@ use typename::TypeName;
 
 use crate::{
     address::{hexified::HexifiedAddress, traits::Addressable, AddressError},
-    chain::ChainConfig,
+    chain::config::ChainType,
     primitives::Id,
 };
 
@@ -29,8 +29,8 @@ pub type OrderId = Id<Order>;
 impl Addressable for OrderId {
     type Error = AddressError;
 
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str {
-        chain_config.order_id_address_prefix()
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str {
+        chain_type.order_id_address_prefix()
     }
 
     fn encode_to_bytes_for_address(&self) -> Vec<u8> {
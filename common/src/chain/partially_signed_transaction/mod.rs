@@ -22,7 +22,14 @@ use crate::{
     chain::{
         htlc::HtlcSecret,
         signature::{
-            inputsig::InputWitness,
+            inputsig::{
+                classical_multisig::{
+                    authorize_classical_multisig::combine_classical_multisig_spends,
+                    encode_decode_multisig_spend::{decode_multisig_spend, encode_multisig_spend},
+                },
+                standard_signature::StandardInputSignature,
+                InputWitness,
+            },
             sighash::{
                 self,
                 input_commitments::{
@@ -30,8 +37,10 @@ use crate::{
                     make_sighash_input_commitments_for_transaction_inputs_at_height,
                     SighashInputCommitment,
                 },
+                sighashtype::SigHashType,
+                signature_hash,
             },
-            Signable, Transactable,
+            DestinationSigError, Signable, Transactable,
         },
         tokens::TokenId,
         AccountCommand, ChainConfig, Destination, OrderAccountCommand, OrderId, PoolId,
@@ -335,6 +344,113 @@ impl PartiallySignedTransaction {
         &self.additional_info
     }
 
+    /// Combine two partially signed versions of the same transaction, e.g. the ones signed by
+    /// different cosigners in parallel, into one.
+    ///
+    /// A witness that is present in only one of the transactions is taken as is; classical
+    /// multisig witnesses that are present in both are merged. Any other pair of differing
+    /// witnesses is a conflict.
+    pub fn combine(
+        self,
+        other: Self,
+        chain_config: &ChainConfig,
+        block_height: BlockHeight,
+    ) -> Result<Self, PartiallySignedTransactionError> {
+        ensure!(
+            self.tx == other.tx
+                && self.input_utxos == other.input_utxos
+                && self.destinations == other.destinations,
+            PartiallySignedTransactionError::CombiningDifferentTransactions
+        );
+
+        let witnesses = {
+            let input_commitments =
+                self.make_sighash_input_commitments_at_height(chain_config, block_height)?;
+
+            self.witnesses
+                .iter()
+                .zip(other.witnesses)
+                .enumerate()
+                .map(|(input_index, witnesses)| match witnesses {
+                    (None, other_witness) => Ok(other_witness),
+                    (Some(witness), None) => Ok(Some(witness.clone())),
+                    (Some(witness), Some(other_witness)) if *witness == other_witness => {
+                        Ok(Some(other_witness))
+                    }
+                    (
+                        Some(InputWitness::Standard(sig)),
+                        Some(InputWitness::Standard(other_sig)),
+                    ) => match &self.destinations[input_index] {
+                        Some(Destination::ClassicMultisig(_)) => self
+                            .combine_multisig_signatures(
+                                chain_config,
+                                &input_commitments,
+                                input_index,
+                                sig,
+                                &other_sig,
+                            )
+                            .map(|sig| Some(InputWitness::Standard(sig))),
+                        _ => Err(PartiallySignedTransactionError::ConflictingWitnesses {
+                            input_index,
+                        }),
+                    },
+                    _ => Err(PartiallySignedTransactionError::ConflictingWitnesses { input_index }),
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let htlc_secrets = self
+            .htlc_secrets
+            .into_iter()
+            .zip(other.htlc_secrets)
+            .map(|(secret, other_secret)| secret.or(other_secret))
+            .collect();
+
+        Ok(Self {
+            tx: self.tx,
+            witnesses,
+            input_utxos: self.input_utxos,
+            destinations: self.destinations,
+            htlc_secrets,
+            additional_info: self.additional_info.join(other.additional_info),
+        })
+    }
+
+    fn combine_multisig_signatures(
+        &self,
+        chain_config: &ChainConfig,
+        input_commitments: &[SighashInputCommitment],
+        input_index: usize,
+        sig: &StandardInputSignature,
+        other_sig: &StandardInputSignature,
+    ) -> Result<StandardInputSignature, PartiallySignedTransactionError> {
+        // Multisig witnesses are always produced for SigHashType::all
+        ensure!(
+            sig.sighash_type() == SigHashType::all()
+                && other_sig.sighash_type() == SigHashType::all(),
+            PartiallySignedTransactionError::ConflictingWitnesses { input_index }
+        );
+
+        let input_utxo = self.input_utxos[input_index].as_ref();
+        let to_combining_error =
+            |error| PartiallySignedTransactionError::MultisigCombiningError { input_index, error };
+
+        let sighash = signature_hash(SigHashType::all(), &self.tx, input_commitments, input_index)
+            .map_err(to_combining_error)?;
+        let signatures = decode_multisig_spend(sig, input_utxo).map_err(to_combining_error)?;
+        let other_signatures =
+            decode_multisig_spend(other_sig, input_utxo).map_err(to_combining_error)?;
+
+        let combined =
+            combine_classical_multisig_spends(chain_config, &sighash, signatures, other_signatures)
+                .map_err(|err| {
+                    to_combining_error(DestinationSigError::ClassicalMultisigSigningFailed(err))
+                })?
+                .take();
+
+        Ok(encode_multisig_spend(&combined, input_utxo))
+    }
+
     pub fn make_sighash_input_commitments(
         &self,
         version: SighashInputCommitmentVersion,
@@ -431,6 +547,18 @@ pub enum PartiallySignedTransactionError {
 
     #[error("Error creating sighash input commitment: {0}")]
     SighashInputCommitmentCreationError(#[from] SighashInputCommitmentCreationError),
+
+    #[error("Cannot combine partially signed transactions that differ in anything but witnesses")]
+    CombiningDifferentTransactions,
+
+    #[error("Conflicting witnesses for input #{input_index}")]
+    ConflictingWitnesses { input_index: usize },
+
+    #[error("Failed to combine multisig signatures for input #{input_index}: {error}")]
+    MultisigCombiningError {
+        input_index: usize,
+        error: DestinationSigError,
+    },
 }
 
 pub type SighashInputCommitmentCreationError =
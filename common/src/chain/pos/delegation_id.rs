@@ -18,11 +18,10 @@ use typename::TypeName;
 
 use crate::{
     address::{hexified::HexifiedAddress, traits::Addressable, AddressError},
+    chain::config::ChainType,
     primitives::Id,
 };
 
-use super::ChainConfig;
-
 #[derive(Eq, PartialEq, TypeName)]
 pub enum Delegation {}
 pub type DelegationId = Id<Delegation>;
@@ -30,8 +29,8 @@ pub type DelegationId = Id<Delegation>;
 impl Addressable for DelegationId {
     type Error = AddressError;
 
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str {
-        chain_config.delegation_id_address_prefix()
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str {
+        chain_type.delegation_id_address_prefix()
     }
 
     fn encode_to_bytes_for_address(&self) -> Vec<u8> {
@@ -18,11 +18,10 @@ use typename::TypeName;
 
 use crate::{
     address::{hexified::HexifiedAddress, traits::Addressable, AddressError},
+    chain::config::ChainType,
     primitives::Id,
 };
 
-use super::ChainConfig;
-
 #[derive(Eq, PartialEq, TypeName)]
 pub enum Pool {}
 pub type PoolId = Id<Pool>;
@@ -42,8 +41,8 @@ impl<'de> serde::Deserialize<'de> for PoolId {
 impl Addressable for PoolId {
     type Error = AddressError;
 
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str {
-        chain_config.pool_id_address_prefix()
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str {
+        chain_type.pool_id_address_prefix()
     }
 
     fn encode_to_bytes_for_address(&self) -> Vec<u8> {
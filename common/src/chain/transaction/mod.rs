@@ -43,6 +43,9 @@ pub use output::*;
 
 pub mod signature;
 
+pub mod tx_size;
+pub use tx_size::TxSize;
+
 mod printout;
 
 use self::signature::inputsig::InputWitness;
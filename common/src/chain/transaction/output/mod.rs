@@ -22,6 +22,7 @@ use crate::{
         AddressError,
     },
     chain::{
+        config::ChainType,
         order::OrderData,
         output_value::OutputValue,
         tokens::{IsTokenFreezable, NftIssuance, TokenId, TokenIssuance, TokenTotalSupply},
@@ -79,8 +80,8 @@ impl rpc_description::HasValueHint for Destination {
 impl Addressable for Destination {
     type Error = AddressError;
 
-    fn address_prefix(&self, chain_config: &ChainConfig) -> &str {
-        chain_config.destination_address_prefix(self.into())
+    fn address_prefix(&self, chain_type: ChainType) -> &'static str {
+        chain_type.destination_address_prefix(self.into())
     }
 
     fn encode_to_bytes_for_address(&self) -> Vec<u8> {
@@ -165,6 +165,10 @@ pub enum ClassicalMultisigSigningError {
     ProducingSignatureFailed(crypto::key::SignatureError),
     #[error("Invalid classical multisig authorization: {0}")]
     InvalidClassicalMultisig(#[from] PartiallySignedMultisigStructureError),
+    #[error("Attempted to combine classical multisig authorizations for different challenges")]
+    CombiningClassicalMultisigsWithDifferentChallenges,
+    #[error("Attempted to combine classical multisig authorizations with invalid signature(s)")]
+    CombiningClassicalMultisigsWithInvalidSignature,
 }
 
 /// Given a challenge, a private key, a sighash, and a set of current signatures, sign the challenge
@@ -265,6 +269,64 @@ pub fn sign_classical_multisig_spending<AuxP: SigAuxDataProvider + ?Sized>(
     }
 }
 
+/// Combine two partial authorizations of the same challenge, e.g. the ones produced by different
+/// cosigners in parallel, into one. The signatures of both authorizations must be valid.
+/// The signatures in `first` take precedence; the ones from `second` are only added until
+/// the required number of signatures is reached, so that the result is never overconstrained.
+pub fn combine_classical_multisig_spends(
+    chain_config: &ChainConfig,
+    sighash: &H256,
+    first: AuthorizedClassicalMultisigSpend,
+    second: AuthorizedClassicalMultisigSpend,
+) -> Result<ClassicalMultisigCompletionStatus, ClassicalMultisigSigningError> {
+    if first.challenge() != second.challenge() {
+        return Err(
+            ClassicalMultisigSigningError::CombiningClassicalMultisigsWithDifferentChallenges,
+        );
+    }
+
+    let msg = sighash.encode();
+
+    for signatures in [&first, &second] {
+        let verifier =
+            PartiallySignedMultisigChallenge::from_partial(chain_config, &msg, signatures)?;
+
+        match verifier.verify_signatures(chain_config)? {
+            super::multisig_partial_signature::SigsVerifyResult::CompleteAndValid
+            | super::multisig_partial_signature::SigsVerifyResult::Incomplete => (),
+            super::multisig_partial_signature::SigsVerifyResult::Invalid => {
+                return Err(
+                    ClassicalMultisigSigningError::CombiningClassicalMultisigsWithInvalidSignature,
+                )
+            }
+        }
+    }
+
+    let min_required_signatures = first.challenge().min_required_signatures() as usize;
+    let mut combined = first;
+
+    for (key_index, signature) in second.take() {
+        if combined.available_signatures_count() >= min_required_signatures {
+            break;
+        }
+        combined.signatures.entry(key_index).or_insert(signature);
+    }
+
+    let verifier = PartiallySignedMultisigChallenge::from_partial(chain_config, &msg, &combined)?;
+
+    match verifier.verify_signatures(chain_config)? {
+        super::multisig_partial_signature::SigsVerifyResult::CompleteAndValid => {
+            Ok(ClassicalMultisigCompletionStatus::Complete(combined))
+        }
+        super::multisig_partial_signature::SigsVerifyResult::Incomplete => {
+            Ok(ClassicalMultisigCompletionStatus::Incomplete(combined))
+        }
+        super::multisig_partial_signature::SigsVerifyResult::Invalid => {
+            unreachable!("Both sets of signatures were checked before combining them")
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU8;
@@ -955,4 +1017,119 @@ mod tests {
             );
         }
     }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn combine_parallel_signatures(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let chain_config = create_mainnet();
+        let min_required_signatures = (rng.gen::<u8>() % 10) + 2;
+        let min_required_signatures: NonZeroU8 = min_required_signatures.try_into().unwrap();
+        let total_parties = (rng.gen::<u8>() % 5) + min_required_signatures.get();
+        let (priv_keys, pub_keys): (Vec<_>, Vec<_>) = (0..total_parties)
+            .map(|_| PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr))
+            .unzip();
+        let challenge =
+            ClassicMultisigChallenge::new(&chain_config, min_required_signatures, pub_keys)
+                .unwrap();
+        let challenge_hash: PublicKeyHash = (&challenge).into();
+
+        let sighash = H256::random_using(&mut rng);
+
+        let sign_with = |indices: &[u8], rng: &mut _| {
+            indices.iter().fold(
+                AuthorizedClassicalMultisigSpend::new_empty(challenge.clone()),
+                |signatures, key_index| {
+                    sign_classical_multisig_spending(
+                        &chain_config,
+                        *key_index,
+                        &priv_keys[*key_index as usize],
+                        &challenge,
+                        &sighash,
+                        signatures,
+                        rng,
+                    )
+                    .unwrap()
+                    .take()
+                },
+            )
+        };
+
+        // Two cosigners sign independently, each one below the threshold.
+        let mut indices: Vec<_> = (0..total_parties).collect();
+        indices.shuffle(&mut rng);
+        let first_count = rng.gen_range(1..min_required_signatures.get()) as usize;
+        let first = sign_with(&indices[..first_count], &mut rng);
+        let second = sign_with(
+            &indices[first_count..min_required_signatures.get() as usize],
+            &mut rng,
+        );
+
+        let combined =
+            combine_classical_multisig_spends(&chain_config, &sighash, first.clone(), second)
+                .unwrap();
+        assert!(combined.is_complete());
+        let combined = combined.take();
+        verify_classical_multisig_spending(&chain_config, &challenge_hash, &combined, &sighash)
+            .unwrap();
+
+        // Combining with an authorization that has more signatures doesn't overconstrain it.
+        let other = sign_with(
+            &indices[..min_required_signatures.get() as usize - 1],
+            &mut rng,
+        );
+        let combined =
+            combine_classical_multisig_spends(&chain_config, &sighash, combined, other).unwrap();
+        assert!(combined.is_complete());
+        assert_eq!(
+            combined.take().available_signatures_count(),
+            min_required_signatures.get() as usize
+        );
+
+        // Combining with itself adds nothing.
+        let combined = combine_classical_multisig_spends(
+            &chain_config,
+            &sighash,
+            first.clone(),
+            first.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            combined,
+            ClassicalMultisigCompletionStatus::Incomplete(first.clone())
+        );
+
+        // Signatures for a different sighash are rejected.
+        let other_sighash = H256::random_using(&mut rng);
+        assert_eq!(
+            combine_classical_multisig_spends(
+                &chain_config,
+                &other_sighash,
+                AuthorizedClassicalMultisigSpend::new_empty(challenge.clone()),
+                first.clone(),
+            )
+            .unwrap_err(),
+            ClassicalMultisigSigningError::CombiningClassicalMultisigsWithInvalidSignature
+        );
+
+        // Authorizations for different challenges cannot be combined.
+        let (_, other_pub_key) = PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr);
+        let mut other_pub_keys = challenge.public_keys().to_vec();
+        other_pub_keys[0] = other_pub_key;
+        let other_challenge =
+            ClassicMultisigChallenge::new(&chain_config, min_required_signatures, other_pub_keys)
+                .unwrap();
+        assert_eq!(
+            combine_classical_multisig_spends(
+                &chain_config,
+                &sighash,
+                first,
+                AuthorizedClassicalMultisigSpend::new_empty(other_challenge),
+            )
+            .unwrap_err(),
+            ClassicalMultisigSigningError::CombiningClassicalMultisigsWithDifferentChallenges
+        );
+    }
 }
@@ -15,16 +15,20 @@
 
 use super::{
     signature::{inputsig::InputWitness, Signable, Transactable},
-    Transaction, TransactionSize, TxOutput,
+    Transaction, TransactionSize, TxOutput, TxSize,
 };
 use crate::{
     chain::{TransactionCreationError, TxInput},
-    primitives::id::{self, H256},
+    primitives::{
+        id::{self, H256},
+        Id,
+    },
 };
 use serialization::{Decode, Encode};
+use typename::TypeName;
 use utils::ensure;
 
-#[derive(Debug, Clone, PartialEq, Eq, Encode, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, TypeName, serde::Serialize)]
 pub struct SignedTransaction {
     transaction: Transaction,
     signatures: Vec<InputWitness>,
@@ -77,11 +81,16 @@ impl SignedTransaction {
         self.transaction.has_smart_contracts()
     }
 
+    pub fn tx_size(&self) -> TxSize {
+        TxSize::new(self)
+    }
+
     pub fn transaction_data_size(&self) -> TransactionSize {
+        let weight = self.tx_size().weight();
         if self.has_smart_contracts() {
-            TransactionSize::SmartContractTransaction(self.encoded_size())
+            TransactionSize::SmartContractTransaction(weight)
         } else {
-            TransactionSize::ScriptedTransaction(self.encoded_size())
+            TransactionSize::ScriptedTransaction(weight)
         }
     }
 
@@ -93,6 +102,14 @@ impl SignedTransaction {
     pub fn serialized_hash(&self) -> H256 {
         id::hash_encoded(self)
     }
+
+    /// The witness transaction id, i.e. the id that commits to the signatures as well.
+    ///
+    /// Unlike the transaction id, it changes if the signatures are altered, so it distinguishes
+    /// different (possibly invalid) versions of the same transaction.
+    pub fn wtxid(&self) -> Id<SignedTransaction> {
+        Id::new(self.serialized_hash())
+    }
 }
 
 impl Signable for SignedTransaction {
@@ -148,7 +165,7 @@ mod tests {
     use crate::chain::output_value::OutputValue;
     use crate::chain::TxInput;
     use crate::primitives::id::Id;
-    use crate::primitives::H256;
+    use crate::primitives::{Idable, H256};
 
     #[test]
     fn require_inputs_witnesses_same_size() {
@@ -274,4 +291,41 @@ mod tests {
             SignedTransaction::decode(&mut encoded.as_slice()).unwrap_err();
         }
     }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn wtxid_commits_to_witness(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let tx = Transaction::new(
+            0,
+            vec![TxInput::from_utxo(
+                Id::<Transaction>::new(H256::random_using(&mut rng)).into(),
+                0,
+            )],
+            vec![],
+        )
+        .unwrap();
+        let witness1 = InputWitness::NoSignature(Some(vec![1]));
+        let witness2 = InputWitness::NoSignature(Some(vec![2]));
+
+        let signed_tx1 = SignedTransaction::new(tx.clone(), vec![witness1.clone()]).unwrap();
+        let signed_tx2 = SignedTransaction::new(tx, vec![witness2]).unwrap();
+
+        // Same transaction, different witnesses
+        assert_eq!(
+            signed_tx1.transaction().get_id(),
+            signed_tx2.transaction().get_id()
+        );
+        assert_ne!(signed_tx1.wtxid(), signed_tx2.wtxid());
+        assert_ne!(
+            signed_tx1.wtxid().to_hash(),
+            signed_tx1.transaction().get_id().to_hash()
+        );
+
+        let signed_tx1_copy =
+            SignedTransaction::new(signed_tx1.transaction().clone(), vec![witness1]).unwrap();
+        assert_eq!(signed_tx1.wtxid(), signed_tx1_copy.wtxid());
+    }
 }
@@ -0,0 +1,122 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serialization::Encode;
+
+use super::{signature::inputsig::InputWitness, signed_transaction::SignedTransaction};
+
+/// The different measures of the size of a transaction.
+///
+/// The code that limits transactions by their size, charges fees for it or budgets network
+/// messages should use the appropriate measure from here instead of the encoded size, so that
+/// the definitions (e.g. a discount for the witness data) can be changed in one place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxSize {
+    serialized: usize,
+    weight: usize,
+    sigop_cost: usize,
+}
+
+impl TxSize {
+    pub fn new(tx: &SignedTransaction) -> Self {
+        let serialized = tx.encoded_size();
+        let sigop_cost = tx
+            .signatures()
+            .iter()
+            .filter(|witness| match witness {
+                InputWitness::NoSignature(_) => false,
+                InputWitness::Standard(_) => true,
+            })
+            .count();
+
+        Self {
+            serialized,
+            weight: serialized,
+            sigop_cost,
+        }
+    }
+
+    /// The size of the transaction on the wire and on disk.
+    pub fn serialized(&self) -> usize {
+        self.serialized
+    }
+
+    /// The size that counts towards the block size limits and that the fees are paid for.
+    ///
+    /// Currently it's the same as the serialized size.
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    /// The number of signature checks the transaction requires.
+    ///
+    /// Each signed input counts as a single operation. Multisig inputs are not expanded because
+    /// that would require looking up the destination of the spent output.
+    pub fn sigop_cost(&self) -> usize {
+        self.sigop_cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chain::{
+            output_value::OutputValue,
+            signature::{
+                inputsig::standard_signature::StandardInputSignature,
+                sighash::sighashtype::SigHashType,
+            },
+            Destination, OutPointSourceId, Transaction, TxInput, TxOutput,
+        },
+        primitives::{Amount, Id, H256},
+    };
+
+    use super::*;
+
+    #[test]
+    fn tx_size() {
+        let inputs = (0..3)
+            .map(|i| {
+                TxInput::from_utxo(
+                    OutPointSourceId::Transaction(Id::new(H256::from_low_u64_be(i))),
+                    0,
+                )
+            })
+            .collect();
+        let outputs = vec![TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(1)),
+            Destination::AnyoneCanSpend,
+        )];
+        let witnesses = vec![
+            InputWitness::NoSignature(None),
+            InputWitness::Standard(StandardInputSignature::new(
+                SigHashType::all(),
+                vec![1; 100],
+            )),
+            InputWitness::Standard(StandardInputSignature::new(
+                SigHashType::all(),
+                vec![2; 100],
+            )),
+        ];
+        let tx = SignedTransaction::new(Transaction::new(0, inputs, outputs).unwrap(), witnesses)
+            .unwrap();
+
+        let size = tx.tx_size();
+        assert_eq!(size, TxSize::new(&tx));
+        assert_eq!(size.serialized(), tx.encoded_size());
+        assert_eq!(size.weight(), tx.encoded_size());
+        assert_eq!(size.sigop_cost(), 2);
+    }
+}
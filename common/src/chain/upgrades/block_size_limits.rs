@@ -0,0 +1,58 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// The limits on the size of the block body, as measured by the weight of its transactions
+/// (see [crate::chain::TxSize::weight]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub struct BlockSizeLimits {
+    max_size_with_standard_txs: usize,
+    max_size_with_smart_contracts: usize,
+}
+
+impl BlockSizeLimits {
+    pub const fn new(
+        max_size_with_standard_txs: usize,
+        max_size_with_smart_contracts: usize,
+    ) -> Self {
+        Self {
+            max_size_with_standard_txs,
+            max_size_with_smart_contracts,
+        }
+    }
+
+    /// The maximum total size of the standard transactions in a block
+    pub fn max_size_with_standard_txs(&self) -> usize {
+        self.max_size_with_standard_txs
+    }
+
+    /// The maximum total size of the smart contract transactions in a block
+    pub fn max_size_with_smart_contracts(&self) -> usize {
+        self.max_size_with_smart_contracts
+    }
+
+    pub fn with_max_size_with_standard_txs(self, max_size_with_standard_txs: usize) -> Self {
+        Self {
+            max_size_with_standard_txs,
+            ..self
+        }
+    }
+
+    pub fn with_max_size_with_smart_contracts(self, max_size_with_smart_contracts: usize) -> Self {
+        Self {
+            max_size_with_smart_contracts,
+            ..self
+        }
+    }
+}
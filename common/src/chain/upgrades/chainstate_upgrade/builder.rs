@@ -17,7 +17,8 @@ use crate::chain::{
     ChainstateUpgrade, ChangeTokenMetadataUriActivated, DataDepositFeeVersion,
     FrozenTokensValidationVersion, HtlcActivated, OrdersActivated, OrdersVersion,
     RewardDistributionVersion, SighashInputCommitmentVersion, StakerDestinationUpdateForbidden,
-    TokenIdGenerationVersion, TokenIssuanceVersion, TokensFeeVersion,
+    TokenIdGenerationVersion, TokenIssuanceVersion, TokenTickerUniquenessActivated,
+    TokensFeeVersion,
 };
 
 /// A builder for `ChainstateUpgrade`.
@@ -74,6 +75,8 @@ impl ChainstateUpgradeBuilder {
             staker_destination_update_forbidden: StakerDestinationUpdateForbidden::Yes,
             token_id_generation_version: TokenIdGenerationVersion::V1,
             sighash_input_commitment_version: SighashInputCommitmentVersion::V1,
+            // Not scheduled on any network yet, so tests have to opt in explicitly.
+            token_ticker_uniqueness_activated: TokenTickerUniquenessActivated::No,
         })
     }
 
@@ -93,4 +96,5 @@ impl ChainstateUpgradeBuilder {
     builder_method!(staker_destination_update_forbidden: StakerDestinationUpdateForbidden);
     builder_method!(token_id_generation_version: TokenIdGenerationVersion);
     builder_method!(sighash_input_commitment_version: SighashInputCommitmentVersion);
+    builder_method!(token_ticker_uniqueness_activated: TokenTickerUniquenessActivated);
 }
@@ -116,6 +116,16 @@ pub enum SighashInputCommitmentVersion {
     V1,
 }
 
+/// Whether a token ticker can only be used by the first token that was issued with it.
+///
+/// Tickers are indexed regardless of this upgrade; it only controls whether issuing a token
+/// with an already indexed ticker is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum TokenTickerUniquenessActivated {
+    Yes,
+    No,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
 pub struct ChainstateUpgrade {
     token_issuance_version: TokenIssuanceVersion,
@@ -130,6 +140,7 @@ pub struct ChainstateUpgrade {
     staker_destination_update_forbidden: StakerDestinationUpdateForbidden,
     token_id_generation_version: TokenIdGenerationVersion,
     sighash_input_commitment_version: SighashInputCommitmentVersion,
+    token_ticker_uniqueness_activated: TokenTickerUniquenessActivated,
 }
 
 impl ChainstateUpgrade {
@@ -147,6 +158,7 @@ impl ChainstateUpgrade {
         staker_destination_update_forbidden: StakerDestinationUpdateForbidden,
         token_id_generation_version: TokenIdGenerationVersion,
         sighash_input_commitment_version: SighashInputCommitmentVersion,
+        token_ticker_uniqueness_activated: TokenTickerUniquenessActivated,
     ) -> Self {
         Self {
             token_issuance_version,
@@ -161,6 +173,7 @@ impl ChainstateUpgrade {
             staker_destination_update_forbidden,
             token_id_generation_version,
             sighash_input_commitment_version,
+            token_ticker_uniqueness_activated,
         }
     }
 
@@ -211,4 +224,8 @@ impl ChainstateUpgrade {
     pub fn sighash_input_commitment_version(&self) -> SighashInputCommitmentVersion {
         self.sighash_input_commitment_version
     }
+
+    pub fn token_ticker_uniqueness_activated(&self) -> TokenTickerUniquenessActivated {
+        self.token_ticker_uniqueness_activated
+    }
 }
@@ -55,7 +55,7 @@ mod tests {
         ChangeTokenMetadataUriActivated, DataDepositFeeVersion, FrozenTokensValidationVersion,
         HtlcActivated, OrdersActivated, OrdersVersion, RewardDistributionVersion,
         SighashInputCommitmentVersion, StakerDestinationUpdateForbidden, TokenIdGenerationVersion,
-        TokenIssuanceVersion, TokensFeeVersion,
+        TokenIssuanceVersion, TokenTickerUniquenessActivated, TokensFeeVersion,
     };
 
     use super::*;
@@ -75,6 +75,7 @@ mod tests {
             StakerDestinationUpdateForbidden::No,
             TokenIdGenerationVersion::V0,
             SighashInputCommitmentVersion::V0,
+            TokenTickerUniquenessActivated::No,
         ))
         .then(BlockHeight::new(1), |builder| {
             builder.token_issuance_version(TokenIssuanceVersion::V1)
@@ -130,6 +131,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -147,6 +149,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -164,6 +167,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -181,6 +185,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -198,6 +203,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -215,6 +221,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -232,6 +239,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -249,6 +257,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -266,6 +275,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -283,6 +293,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::No,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -300,6 +311,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::Yes,
                     TokenIdGenerationVersion::V0,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -317,6 +329,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::Yes,
                     TokenIdGenerationVersion::V1,
                     SighashInputCommitmentVersion::V0,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
             (
@@ -334,6 +347,7 @@ mod tests {
                     StakerDestinationUpdateForbidden::Yes,
                     TokenIdGenerationVersion::V1,
                     SighashInputCommitmentVersion::V1,
+                    TokenTickerUniquenessActivated::No,
                 ),
             ),
         ])
@@ -358,6 +372,7 @@ mod tests {
             StakerDestinationUpdateForbidden::No,
             TokenIdGenerationVersion::V0,
             SighashInputCommitmentVersion::V0,
+            TokenTickerUniquenessActivated::No,
         ))
         .then(BlockHeight::new(2), |builder| {
             builder.token_issuance_version(TokenIssuanceVersion::V1)
@@ -384,6 +399,7 @@ mod tests {
             StakerDestinationUpdateForbidden::No,
             TokenIdGenerationVersion::V0,
             SighashInputCommitmentVersion::V0,
+            TokenTickerUniquenessActivated::No,
         ))
         .then(BlockHeight::new(1), |builder| {
             builder.token_issuance_version(TokenIssuanceVersion::V1)
@@ -410,6 +426,7 @@ mod tests {
             StakerDestinationUpdateForbidden::No,
             TokenIdGenerationVersion::V0,
             SighashInputCommitmentVersion::V0,
+            TokenTickerUniquenessActivated::No,
         ))
         .then(BlockHeight::new(0), |builder| {
             builder.token_issuance_version(TokenIssuanceVersion::V1)
@@ -433,6 +450,7 @@ mod tests {
             StakerDestinationUpdateForbidden::No,
             TokenIdGenerationVersion::V0,
             SighashInputCommitmentVersion::V0,
+            TokenTickerUniquenessActivated::No,
         ))
         .then(BlockHeight::new(1), |builder| {
             builder.token_issuance_version(TokenIssuanceVersion::V0)
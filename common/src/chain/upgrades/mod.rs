@@ -13,21 +13,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod block_size_limits;
 mod chainstate_upgrade;
 mod chainstate_upgrades_builder;
 mod consensus_upgrade;
 mod netupgrade;
+mod pow_difficulty_adjustment;
 
+pub use block_size_limits::BlockSizeLimits;
 pub use chainstate_upgrade::{
     ChainstateUpgrade, ChainstateUpgradeBuilder, ChangeTokenMetadataUriActivated,
     DataDepositFeeVersion, FrozenTokensValidationVersion, HtlcActivated, OrdersActivated,
     OrdersVersion, RewardDistributionVersion, SighashInputCommitmentVersion,
     StakerDestinationUpdateForbidden, TokenIdGenerationVersion, TokenIssuanceVersion,
-    TokensFeeVersion,
+    TokenTickerUniquenessActivated, TokensFeeVersion,
 };
 pub use chainstate_upgrades_builder::ChainstateUpgradesBuilder;
 pub use consensus_upgrade::{ConsensusUpgrade, PoSStatus, PoWStatus, RequiredConsensus};
 pub use netupgrade::NetUpgrades;
+pub use pow_difficulty_adjustment::PoWDifficultyAdjustment;
 
 pub enum NetUpgradeError {
     GenerateConfigFailed,
@@ -0,0 +1,30 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::num::NonZeroU64;
+
+/// The algorithm used to adjust the difficulty of PoW blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd)]
+pub enum PoWDifficultyAdjustment {
+    /// Bitcoin-style retargeting once per difficulty adjustment interval
+    /// (see [crate::chain::PoWChainConfig::target_timespan]).
+    Retargeting,
+
+    /// ASERT (absolutely scheduled exponentially rising targets), which adjusts the target of
+    /// every block based on how far the chain is ahead of or behind the schedule that starts at
+    /// the last block before the activation height. The target doubles (or halves) for every
+    /// `half_life_secs` seconds that the chain is behind (or ahead of) the schedule.
+    Asert { half_life_secs: NonZeroU64 },
+}
@@ -259,6 +259,10 @@ macro_rules! construct_uint {
                 (!carry).then_some(result)
             }
 
+            pub fn wrapping_add(&self, other: &Self) -> Self {
+                self.overflowing_add(other).0
+            }
+
             pub fn checked_sub(&self, other: &Self) -> Option<Self> {
                 (*self >= *other).then(|| self.unchecked_sub(other))
             }
@@ -910,6 +914,11 @@ mod tests {
 
         let add = (init + copy).unwrap();
         assert_eq!(add, Uint256([0xBD5B7DDFBD5B7DDEu64, 1, 0, 0]));
+        assert_eq!(init.wrapping_add(&copy), add);
+        assert_eq!(
+            Uint256::MAX.wrapping_add(&init),
+            (init - Uint256::from_u64(1)).unwrap()
+        );
         // Bitshifts
         let shl = add << 88;
         assert_eq!(shl, Uint256([0u64, 0xDFBD5B7DDE000000, 0x1BD5B7D, 0]));
@@ -19,7 +19,7 @@ use chainstate_types::{BlockIndex, GenBlockIndex};
 use common::{
     chain::block::timestamp::BlockTimestamp,
     primitives::{BlockHeight, Compact},
-    Uint256,
+    Uint256, Uint512,
 };
 
 use crate::pow::error::ConsensusPoWError;
@@ -88,6 +88,64 @@ pub fn calculate_new_target(
     Ok(Compact::from(new_target))
 }
 
+/// Returns the ASERT target for a block.
+///
+/// This is the integer approximation used by Bitcoin Cash's aserti3-2d, so its test vectors
+/// apply here too, provided that the deltas are measured from the same reference point.
+/// # Arguments
+/// `anchor_target` - the target of the reference block.
+/// `time_delta` - the time between the reference block and the parent of the new block, in seconds.
+/// `height_delta` - the number of blocks between the reference block and the parent of the new block.
+/// `target_spacing` - the ideal time between blocks, in seconds.
+/// `half_life` - the time by which the chain has to fall behind the schedule for the target to
+/// double, in seconds.
+/// `difficulty_limit` - found in the PoWChainConfig, as `limit`
+pub fn calculate_asert_target(
+    anchor_target: Uint256,
+    time_delta: i64,
+    height_delta: u64,
+    target_spacing: u64,
+    half_life: NonZeroU64,
+    difficulty_limit: Uint256,
+) -> Uint256 {
+    let schedule_delta = time_delta as i128 - target_spacing as i128 * height_delta as i128;
+    // The exponent is a 16.16 fixed point number of half-lives; the division truncates
+    // towards zero.
+    let exponent = schedule_delta * 65536 / half_life.get() as i128;
+
+    // The integer part of the exponent is applied as a shift; the arithmetic shift rounds it
+    // down, so the fractional part is always positive.
+    let shifts = (exponent >> 16) - 16;
+    let frac = (exponent & 0xffff) as u128;
+
+    // 2^frac, approximated by a cubic polynomial, in 16-bit fixed point
+    let factor = 65536
+        + ((195_766_423_245_049 * frac
+            + 971_821_376 * frac * frac
+            + 5_127 * frac * frac * frac
+            + (1 << 47))
+            >> 48);
+    let factor = u32::try_from(factor).expect("the factor is below 2^17");
+
+    let new_target = Uint512::from(anchor_target).mul_u32(factor);
+    let new_target = if shifts <= 0 {
+        let shifts = usize::try_from(-shifts).unwrap_or(usize::MAX);
+        Some(new_target >> shifts)
+    } else {
+        usize::try_from(shifts)
+            .ok()
+            .map(|shifts| (new_target << shifts, shifts))
+            .filter(|(shifted, shifts)| (*shifted >> *shifts) == new_target)
+            .map(|(shifted, _)| shifted)
+    };
+
+    match new_target.and_then(|target| Uint256::try_from(target).ok()) {
+        Some(target) if target == Uint256::ZERO => Uint256::ONE,
+        Some(target) if target <= difficulty_limit => target,
+        Some(_) | None => difficulty_limit,
+    }
+}
+
 pub mod special_rules {
 
     /// Checks if it took > 20 minutes to find a block
@@ -104,6 +162,7 @@ pub mod special_rules {
 mod tests {
     use super::*;
     use common::primitives::BlockHeight;
+    use rstest::rstest;
 
     #[test]
     fn due_for_retarget_test() {
@@ -120,4 +179,49 @@ mod tests {
         assert!(!test(BlockHeight::new((interval * 10) + 1)));
         assert!(!test(BlockHeight::new((interval * 10) - 1)));
     }
+
+    const SPACING: i64 = 600;
+    const HALF_LIFE: i64 = 2 * 24 * 60 * 60;
+
+    fn target(mantissa: u64, shift: usize) -> Uint256 {
+        Uint256::from_u64(mantissa) << shift
+    }
+
+    fn limit() -> Uint256 {
+        (Uint256::ONE << 224).checked_sub(&Uint256::ONE).unwrap()
+    }
+
+    #[rstest]
+    // On schedule
+    #[case(target(1, 200), SPACING * 10, 10, target(1, 200))]
+    // One half-life behind and ahead of the schedule
+    #[case(target(1, 200), SPACING * 10 + HALF_LIFE, 10, target(2, 200))]
+    #[case(target(1, 200), SPACING * 10 - HALF_LIFE, 10, target(1, 199))]
+    // Three half-lives behind the schedule
+    #[case(target(1, 200), SPACING * 10 + 3 * HALF_LIFE, 10, target(8, 200))]
+    // Half a half-life behind and ahead of the schedule, which exercises the approximation
+    #[case(target(1, 200), SPACING * 10 + HALF_LIFE / 2, 10, target(0x16a02, 184))]
+    #[case(target(1, 200), SPACING * 10 - HALF_LIFE / 2, 10, target(0xb501, 184))]
+    // Far ahead of the schedule
+    #[case(target(1, 200), 0, 10_000, target(0x26cb8, 152))]
+    #[case(target(1, 0), 0, 10_000, Uint256::ONE)]
+    // Far behind the schedule, the target is capped by the limit
+    #[case(target(1, 200), 1_000_000_000, 1, limit())]
+    #[case(target(1, 200), i64::MAX, 0, limit())]
+    fn asert_target(
+        #[case] anchor_target: Uint256,
+        #[case] time_delta: i64,
+        #[case] height_delta: u64,
+        #[case] expected: Uint256,
+    ) {
+        let new_target = calculate_asert_target(
+            anchor_target,
+            time_delta,
+            height_delta,
+            SPACING as u64,
+            NonZeroU64::new(HALF_LIFE as u64).unwrap(),
+            limit(),
+        );
+        assert_eq!(new_target, expected);
+    }
 }
@@ -15,7 +15,7 @@
 
 #![allow(dead_code)]
 
-use std::sync::Arc;
+use std::{num::NonZeroU64, sync::Arc};
 
 use chainstate_types::{BlockIndex, BlockIndexHandle, GenBlockIndex};
 use common::{
@@ -23,7 +23,7 @@ use common::{
         block::consensus_data::PoWData,
         block::{timestamp::BlockTimestamp, BlockHeader, ConsensusData},
         config::ChainConfig,
-        GenBlockId, PoWStatus,
+        GenBlockId, NetUpgrades, PoWDifficultyAdjustment, PoWStatus,
     },
     primitives::{BlockHeight, Compact, Idable, H256},
     Uint256,
@@ -34,7 +34,10 @@ use crate::{
     get_ancestor_from_block_index_handle,
     pow::{
         error::ConsensusPoWError,
-        helpers::{calculate_new_target, due_for_retarget, get_starting_block_time, special_rules},
+        helpers::{
+            calculate_asert_target, calculate_new_target, due_for_retarget,
+            get_starting_block_time, special_rules,
+        },
         PoW,
     },
 };
@@ -76,6 +79,7 @@ pub fn check_pow_consensus<H: BlockIndexHandle>(
                 PoW::new(chain_config).get_work_required(
                     &prev_block_index,
                     header.timestamp(),
+                    chain_config.pow_difficulty_adjustments(),
                     get_ancestor,
                 )?
             }
@@ -126,6 +130,7 @@ where
             GenBlockIndex::Block(prev_block_index) => PoW::new(chain_config).get_work_required(
                 prev_block_index,
                 block_timestamp,
+                chain_config.pow_difficulty_adjustments(),
                 get_ancestor,
             ),
         },
@@ -149,6 +154,7 @@ impl PoW {
         &self,
         prev_block_index: &BlockIndex,
         new_block_time: BlockTimestamp,
+        difficulty_adjustments: &NetUpgrades<PoWDifficultyAdjustment>,
         get_ancestor: F,
     ) -> Result<Compact, ConsensusPoWError>
     where
@@ -175,6 +181,18 @@ impl PoW {
             .checked_add(1)
             .expect("max block height has been reached.");
 
+        match difficulty_adjustments.version_at_height(current_height) {
+            (_, PoWDifficultyAdjustment::Retargeting) => {}
+            (activation_height, PoWDifficultyAdjustment::Asert { half_life_secs }) => {
+                return self.next_asert_work_required(
+                    *activation_height,
+                    *half_life_secs,
+                    prev_block_index,
+                    get_ancestor,
+                );
+            }
+        }
+
         let adjustment_interval = self.difficulty_adjustment_interval();
 
         // Only change once per difficulty adjustment interval
@@ -217,6 +235,54 @@ impl PoW {
         )
     }
 
+    /// ASERT proof of work; the schedule starts at the last block before the activation height.
+    /// If that's the genesis or a block that is not PoW, the schedule starts at the difficulty
+    /// limit.
+    fn next_asert_work_required<F>(
+        &self,
+        activation_height: BlockHeight,
+        half_life_secs: NonZeroU64,
+        prev_block_index: &BlockIndex,
+        get_ancestor: F,
+    ) -> Result<Compact, ConsensusPoWError>
+    where
+        F: Fn(&BlockIndex, BlockHeight) -> Result<GenBlockIndex, crate::ChainstateError>,
+    {
+        let anchor_height = activation_height.prev_height().unwrap_or(BlockHeight::zero());
+        let anchor_block_index = get_ancestor(prev_block_index, anchor_height)?;
+
+        let anchor_target = match &anchor_block_index {
+            GenBlockIndex::Block(block_index) => {
+                match block_index.block_header().consensus_data() {
+                    ConsensusData::PoW(pow_data) => {
+                        Uint256::try_from(pow_data.bits()).map_err(|_| {
+                            ConsensusPoWError::PreviousBitsDecodingFailed(pow_data.bits())
+                        })?
+                    }
+                    ConsensusData::None | ConsensusData::PoS(_) => self.difficulty_limit(),
+                }
+            }
+            GenBlockIndex::Genesis(_) => self.difficulty_limit(),
+        };
+
+        let time_delta = prev_block_index.block_timestamp().as_int_seconds() as i64
+            - anchor_block_index.block_timestamp().as_int_seconds() as i64;
+        let height_delta = (prev_block_index.block_height() - anchor_block_index.block_height())
+            .and_then(|distance| u64::try_from(distance.to_int()).ok())
+            .expect("the anchor block is an ancestor");
+
+        let new_target = calculate_asert_target(
+            anchor_target,
+            time_delta,
+            height_delta,
+            self.target_spacing().as_secs(),
+            half_life_secs,
+            self.difficulty_limit(),
+        );
+
+        Ok(Compact::from(new_target))
+    }
+
     fn next_work_required_for_min_difficulty(
         &self,
         new_block_time: u64,
@@ -263,12 +329,16 @@ pub fn mine(
 
 #[cfg(test)]
 mod tests {
-    use crate::pow::work::check_proof_of_work;
-    use common::chain::config::create_mainnet;
+    use crate::pow::{helpers::calculate_asert_target, work::check_proof_of_work};
+    use chainstate_types::BlockStatus;
+    use common::chain::config::{create_mainnet, Builder, ChainType};
+    use common::chain::{block::BlockReward, Block, ConsensusUpgrade};
     use common::primitives::{Compact, H256};
     use rstest::rstest;
     use std::str::FromStr;
 
+    use super::*;
+
     //TODO: add `CalculateNextWorkRequired` test cases from Bitcoin
 
     #[rstest]
@@ -312,4 +382,117 @@ mod tests {
 
         assert!(!check_proof_of_work(hash, bits).unwrap());
     }
+
+    #[test]
+    fn asert_fork_activation() {
+        let activation_height = 10;
+        let half_life = 2 * 24 * 60 * 60;
+        let pow_limit = create_mainnet().get_proof_of_work_config().limit();
+
+        let make_chain_config = |difficulty_adjustments| {
+            Builder::new(ChainType::Mainnet)
+                .consensus_upgrades(
+                    NetUpgrades::initialize(vec![(
+                        BlockHeight::zero(),
+                        ConsensusUpgrade::PoW {
+                            initial_difficulty: pow_limit.into(),
+                        },
+                    )])
+                    .unwrap(),
+                )
+                .pow_difficulty_adjustments(
+                    NetUpgrades::initialize(difficulty_adjustments).unwrap(),
+                )
+                .build()
+        };
+        let retargeting_config = make_chain_config(vec![(
+            BlockHeight::zero(),
+            PoWDifficultyAdjustment::Retargeting,
+        )]);
+        let asert_config = make_chain_config(vec![
+            (BlockHeight::zero(), PoWDifficultyAdjustment::Retargeting),
+            (
+                BlockHeight::new(activation_height),
+                PoWDifficultyAdjustment::Asert {
+                    half_life_secs: NonZeroU64::new(half_life).unwrap(),
+                },
+            ),
+        ]);
+
+        // The blocks are on schedule until the activation, after which each one is late by
+        // half of the half-life
+        let bits_target = Uint256::ONE << 200;
+        let bits = Compact::from(bits_target);
+        let spacing = asert_config.get_proof_of_work_config().target_spacing().as_secs();
+        let genesis_time = asert_config.genesis_block().timestamp().as_int_seconds();
+        let mut block_indices = vec![GenBlockIndex::genesis(&asert_config)];
+        let mut block_time = genesis_time;
+        for height in 1..activation_height + 3 {
+            block_time += if height < activation_height {
+                spacing
+            } else {
+                spacing + half_life / 2
+            };
+            let prev_block_index = block_indices.last().unwrap();
+            let block = Block::new(
+                vec![],
+                prev_block_index.block_id(),
+                BlockTimestamp::from_int_seconds(block_time),
+                ConsensusData::PoW(Box::new(PoWData::new(bits, 0))),
+                BlockReward::new(Vec::new()),
+            )
+            .unwrap();
+            let block_index = BlockIndex::new(
+                &block,
+                Uint256::ZERO,
+                prev_block_index.block_id(),
+                BlockHeight::new(height),
+                block.timestamp(),
+                0,
+                BlockStatus::new(),
+            );
+            block_indices.push(GenBlockIndex::Block(block_index));
+        }
+
+        let get_ancestor = |_: &BlockIndex, height: BlockHeight| {
+            Ok::<_, crate::ChainstateError>(block_indices[height.into_int() as usize].clone())
+        };
+        let work_required = |chain_config: &ChainConfig, height: u64| {
+            let prev_block_index = &block_indices[height as usize - 1];
+            calculate_work_required(
+                chain_config,
+                prev_block_index,
+                BlockTimestamp::from_int_seconds(block_time + spacing),
+                &PoWStatus::Ongoing,
+                get_ancestor,
+            )
+            .unwrap()
+        };
+
+        // Before the activation, both configs keep the target until the next retarget
+        for height in 2..=activation_height {
+            assert_eq!(work_required(&retargeting_config, height), bits);
+            assert_eq!(work_required(&asert_config, height), bits);
+        }
+
+        // After the activation, ASERT adjusts the target of every block
+        for height in activation_height + 1..activation_height + 3 {
+            assert_eq!(work_required(&retargeting_config, height), bits);
+
+            let blocks_since_anchor = height - activation_height;
+            let expected = calculate_asert_target(
+                bits_target,
+                ((spacing + half_life / 2) * blocks_since_anchor) as i64,
+                blocks_since_anchor,
+                spacing,
+                NonZeroU64::new(half_life).unwrap(),
+                pow_limit,
+            );
+            assert!(expected > bits_target);
+            assert_eq!(
+                work_required(&asert_config, height),
+                Compact::from(expected)
+            );
+        }
+    }
 }
@@ -322,7 +322,10 @@ fn to_key_and_chain_code(
 mod tests {
     use hex::FromHex;
     use rstest::rstest;
-    use serialization::DecodeAll;
+    use serialization::{
+        hex::{HexDecode, HexEncode},
+        DecodeAll,
+    };
     use test_utils::random::make_seedable_rng;
     use test_utils::random::Seed;
 
@@ -366,6 +369,53 @@ mod tests {
         assert_eq!(decoded_pk, VRFPublicKey::from_private_key(&decoded_sk))
     }
 
+    #[test]
+    fn fixed_public_key_hex() {
+        // The leading byte is the key kind, so the encoding of existing keys must never change
+        let pk_hex = "006a9602eaae527451eed95667ecd4756324084e46b52fe908283c8b6b69095c09";
+
+        let pk = VRFPublicKey::hex_decode_all(pk_hex).unwrap();
+        assert_eq!(pk.hex_encode(), pk_hex);
+
+        let sk = VRFPrivateKey::hex_decode_all("00c4546c0ad4d86dff34ff0459737e3bab90a0e3452c6deea6e8701a215ed45b0565d5ecaa1e36e81e5ad2b0fe884ed5d25f1de033d15cfb3ae8fb634ea130f93c").unwrap();
+        assert_eq!(sk.to_public_key().hex_encode(), pk_hex);
+
+        // Unknown key kinds and truncated keys are rejected
+        VRFPublicKey::hex_decode_all(
+            "016a9602eaae527451eed95667ecd4756324084e46b52fe908283c8b6b69095c09",
+        )
+        .unwrap_err();
+        VRFPublicKey::hex_decode_all(&pk_hex[..pk_hex.len() - 2]).unwrap_err();
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn vrf_return_serialization(#[case] seed: Seed) {
+        let transcript = make_arbitrary_transcript();
+
+        let mut rng = make_seedable_rng(seed);
+        let (sk, pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+        let vrf_data = sk.produce_vrf_data(transcript.clone());
+
+        // One byte for the kind, followed by the preout and the proof
+        let encoded = vrf_data.encode();
+        assert_eq!(encoded.len(), 1 + 32 + 64);
+        assert_eq!(encoded[0], 0);
+        match &vrf_data {
+            VRFReturn::Schnorrkel(d) => {
+                assert_eq!(encoded[1..33], d.vrf_preout());
+                assert_eq!(encoded[33..], d.vrf_proof());
+            }
+        }
+
+        let decoded = VRFReturn::hex_decode_all(vrf_data.hex_encode()).unwrap();
+        assert_eq!(decoded, vrf_data);
+        pk.verify_vrf_data(transcript, &decoded).expect("Valid VRF check failed");
+
+        VRFReturn::hex_decode_all(hex::encode(&encoded[..encoded.len() - 1])).unwrap_err();
+    }
+
     fn make_arbitrary_transcript() -> VRFTranscript {
         VRFTranscript::new(b"some context")
             .attach_raw_data(b"some label", b"Data to commit")
@@ -44,6 +44,15 @@ impl serde::Serialize for SchnorrkelVRFReturn {
     }
 }
 
+impl<'d> serde::Deserialize<'d> for SchnorrkelVRFReturn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        HexEncoded::<SchnorrkelVRFReturn>::deserialize(deserializer).map(|hex| hex.take())
+    }
+}
+
 impl Encode for SchnorrkelVRFReturn {
     fn size_hint(&self) -> usize {
         SCHNORKEL_RETURN_SIZE
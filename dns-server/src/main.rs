@@ -70,6 +70,7 @@ async fn run(options: DnsServerRunOptions) -> anyhow::Result<Never> {
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         // Note: this ban config (as well as any other settings related to the peer or sync manager)
         // won't have any effect on the dns server.
         ban_config: Default::default(),
@@ -82,6 +83,9 @@ async fn run(options: DnsServerRunOptions) -> anyhow::Result<Never> {
         allow_discover_private_ips: Default::default(),
         user_agent,
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod log_levels;
 mod log_style;
 mod tracing_utils;
 mod utils;
@@ -24,13 +25,18 @@ use std::{
 
 use tracing::{level_filters::LevelFilter, Subscriber};
 use tracing_subscriber::{
-    fmt::MakeWriter, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer, Registry,
+    fmt::MakeWriter,
+    layer::{Filter, SubscriberExt},
+    reload,
+    util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
 };
 
-use log_style::{get_log_style_from_env, LogStyleParseError};
+use log_style::get_log_style_from_env;
 
 pub use log;
-pub use log_style::{LogStyle, TextColoring};
+pub use log_levels::{set_log_level, SetLogLevelError};
+pub use log_style::{LogStyle, LogStyleParseError, TextColoring};
 pub use tracing_utils::{spawn_in_current_span, spawn_in_span};
 pub use utils::{get_from_env, GetFromEnvError, ValueOrEnvVar};
 
@@ -89,6 +95,8 @@ pub struct WriterSettings<MW> {
 
 /// Generic version of init_logging that allows to have an auxiliary writer with its own settings
 /// for filtering and log style.
+///
+/// The log levels of the main writer can be adjusted later via [set_log_level].
 pub fn init_logging_generic<MW1, MW2>(
     main_writer_settings: WriterSettings<MW1>,
     aux_writer_settings: Option<WriterSettings<MW2>>,
@@ -98,7 +106,7 @@ pub fn init_logging_generic<MW1, MW2>(
 {
     INITIALIZE_LOGGER_ONCE_FLAG.call_once(move || {
         let mut errors = Vec::new();
-        let main_layer = make_layer(main_writer_settings, &mut errors);
+        let main_layer = make_reloadable_layer(main_writer_settings, &mut errors);
         let aux_layer = aux_writer_settings.map(|settings| make_layer(settings, &mut errors));
 
         Registry::default()
@@ -128,7 +136,7 @@ where
     MW: for<'a> MakeWriter<'a> + Send + Sync + 'static,
     S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
 {
-    let filter = make_env_filter(writer_settings.filter, errors);
+    let (filter, _) = make_env_filter(writer_settings.filter, errors);
     let log_style = get_log_style(&writer_settings.log_style, errors);
 
     make_layer_impl(
@@ -139,14 +147,41 @@ where
     )
 }
 
-fn make_layer_impl<MW, S>(
+/// Same as `make_layer`, but the filter of the layer can be replaced later via [set_log_level].
+fn make_reloadable_layer<MW, S>(
+    writer_settings: WriterSettings<MW>,
+    errors: &mut Vec<InternalLogInitError>,
+) -> Box<dyn Layer<S> + Send + Sync>
+where
+    MW: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a> + 'static,
+{
+    let (filter, filter_directives) = make_env_filter(writer_settings.filter, errors);
+    let log_style = get_log_style(&writer_settings.log_style, errors);
+
+    let (filter, reload_handle) = reload::Layer::new(filter);
+    log_levels::set_main_filter_reload(
+        filter_directives,
+        Box::new(move |filter| reload_handle.reload(filter).map_err(|err| err.to_string())),
+    );
+
+    make_layer_impl(
+        writer_settings.make_writer,
+        writer_settings.is_terminal,
+        filter,
+        log_style,
+    )
+}
+
+fn make_layer_impl<MW, F, S>(
     make_writer: MW,
     is_terminal: bool,
-    filter: EnvFilter,
+    filter: F,
     log_style: LogStyle,
 ) -> Box<dyn Layer<S> + Send + Sync>
 where
     MW: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    F: Filter<S> + Send + Sync + 'static,
     S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
 {
     match log_style {
@@ -191,12 +226,13 @@ fn get_log_style_impl(
     }
 }
 
+/// Return the filter and the directives it was created from.
 fn make_env_filter(
     filter_str: ValueOrEnvVar<String>,
     errors: &mut Vec<InternalLogInitError>,
-) -> EnvFilter {
+) -> (EnvFilter, String) {
     let result_opt = match make_env_filter_impl(filter_str) {
-        Ok(filter) => Some(filter),
+        Ok(filter_and_directives) => Some(filter_and_directives),
         Err(err) => {
             errors.push(err);
             None
@@ -204,13 +240,16 @@ fn make_env_filter(
     };
 
     result_opt.unwrap_or_else(|| {
-        EnvFilter::builder()
+        let filter = EnvFilter::builder()
             .with_default_directive(default_filter_directive())
-            .parse_lossy("")
+            .parse_lossy("");
+        (filter, String::new())
     })
 }
 
-fn make_env_filter_impl(filter: ValueOrEnvVar<String>) -> Result<EnvFilter, InternalLogInitError> {
+fn make_env_filter_impl(
+    filter: ValueOrEnvVar<String>,
+) -> Result<(EnvFilter, String), InternalLogInitError> {
     let filter_directives = match filter {
         ValueOrEnvVar::Value(val) => Some(val),
         ValueOrEnvVar::EnvVar(var_name) => get_from_env(var_name.as_ref())?,
@@ -227,11 +266,11 @@ fn make_env_filter_impl(filter: ValueOrEnvVar<String>) -> Result<EnvFilter, Inte
         .with_default_directive(default_filter_directive())
         .parse(&filter_directives)
         .map_err(|err| InternalLogInitError::FilterDirectivesParseError {
-            directives: filter_directives,
+            directives: filter_directives.clone(),
             error: err,
         })?;
 
-    Ok(filter)
+    Ok((filter, filter_directives))
 }
 
 // Note: EnvFilter::from_env also uses ERROR as the default.
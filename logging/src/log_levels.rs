@@ -0,0 +1,152 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime adjustment of the log levels of the main writer.
+//!
+//! The filter of the main writer is wrapped in a reload layer, so that the log level of individual
+//! targets (e.g. "chainstate" or "p2p::sync") can be changed after the logging has been initialized.
+//! The per-target levels are appended to the initial filter directives, so they take precedence over
+//! the directives for the same targets obtained from the env var.
+
+use std::{collections::BTreeMap, str::FromStr, sync::Mutex};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+type ReloadFn = Box<dyn Fn(EnvFilter) -> Result<(), String> + Send + Sync>;
+
+struct ReloadableFilter {
+    /// The directives the filter was initially created from.
+    base_directives: String,
+    /// The levels that have been set via `set_log_level`.
+    target_levels: BTreeMap<String, LevelFilter>,
+    reload: ReloadFn,
+}
+
+static MAIN_FILTER: Mutex<Option<ReloadableFilter>> = Mutex::new(None);
+
+pub(crate) fn set_main_filter_reload(base_directives: String, reload: ReloadFn) {
+    *MAIN_FILTER.lock().expect("poisoned mutex") = Some(ReloadableFilter {
+        base_directives,
+        target_levels: BTreeMap::new(),
+        reload,
+    });
+}
+
+/// Set the log level for the specified target of the main log writer.
+///
+/// The target is a module path prefix, e.g. "chainstate" or "p2p::peer_manager"; the level is one of
+/// "off", "error", "warn", "info", "debug" or "trace".
+pub fn set_log_level(target: &str, level: &str) -> Result<(), SetLogLevelError> {
+    if !is_target_valid(target) {
+        return Err(SetLogLevelError::InvalidTarget(target.to_owned()));
+    }
+    let level = LevelFilter::from_str(level)
+        .map_err(|_| SetLogLevelError::InvalidLevel(level.to_owned()))?;
+
+    let mut main_filter = MAIN_FILTER.lock().expect("poisoned mutex");
+    let main_filter = main_filter.as_mut().ok_or(SetLogLevelError::NotInitialized)?;
+
+    let mut target_levels = main_filter.target_levels.clone();
+    target_levels.insert(target.to_owned(), level);
+
+    let directives = make_directives(&main_filter.base_directives, &target_levels);
+    let filter = EnvFilter::builder()
+        .with_default_directive(crate::default_filter_directive())
+        // Note: the base directives have already been validated during the initialization.
+        .parse_lossy(directives);
+    (main_filter.reload)(filter).map_err(SetLogLevelError::ReloadFailed)?;
+
+    main_filter.target_levels = target_levels;
+    Ok(())
+}
+
+fn is_target_valid(target: &str) -> bool {
+    !target.is_empty()
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-')
+}
+
+fn make_directives(base_directives: &str, target_levels: &BTreeMap<String, LevelFilter>) -> String {
+    std::iter::once(base_directives.to_owned())
+        .chain(
+            target_levels
+                .iter()
+                .map(|(target, level)| format!("{target}={}", level.to_string().to_lowercase())),
+        )
+        .filter(|directive| !directive.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetLogLevelError {
+    #[error("Logging is not initialized")]
+    NotInitialized,
+    #[error("Invalid log target: {0}")]
+    InvalidTarget(String),
+    #[error("Invalid log level: {0}")]
+    InvalidLevel(String),
+    #[error("Failed to reload the log filter: {0}")]
+    ReloadFailed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directives() {
+        assert_eq!(make_directives("", &BTreeMap::new()), "");
+        assert_eq!(make_directives("info", &BTreeMap::new()), "info");
+
+        let target_levels = BTreeMap::from([
+            ("p2p".to_owned(), LevelFilter::INFO),
+            ("chainstate".to_owned(), LevelFilter::DEBUG),
+        ]);
+        assert_eq!(
+            make_directives("", &target_levels),
+            "chainstate=debug,p2p=info"
+        );
+        assert_eq!(
+            make_directives("warn,p2p=trace", &target_levels),
+            "warn,p2p=trace,chainstate=debug,p2p=info"
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        crate::init_logging();
+
+        assert_eq!(
+            set_log_level("", "debug"),
+            Err(SetLogLevelError::InvalidTarget("".to_owned()))
+        );
+        assert_eq!(
+            set_log_level("p2p=info,chainstate", "debug"),
+            Err(SetLogLevelError::InvalidTarget(
+                "p2p=info,chainstate".to_owned()
+            ))
+        );
+        assert_eq!(
+            set_log_level("p2p", "verbose"),
+            Err(SetLogLevelError::InvalidLevel("verbose".to_owned()))
+        );
+
+        set_log_level("p2p::sync", "debug").unwrap();
+        set_log_level("p2p::sync", "off").unwrap();
+    }
+}
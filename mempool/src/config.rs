@@ -111,6 +111,9 @@ pub const ROLLING_FEE_BASE_HALFLIFE: Duration = Duration::new(60 * 60 * 12, 1);
 
 pub const MAX_BIP125_REPLACEMENT_CANDIDATES: usize = 100;
 
+/// The maximum number of in-mempool ancestors a new transaction may have.
+pub const MAX_UNCONFIRMED_ANCESTORS: usize = 1000;
+
 pub const MAX_MEMPOOL_SIZE_BYTES: usize = 300_000_000;
 
 pub const DEFAULT_MEMPOOL_EXPIRY: Duration = Duration::new(336 * 60 * 60, 0);
@@ -125,10 +128,15 @@ pub const MAX_ORPHAN_TX_SIZE: usize = 20_000;
 
 pub const MAX_ORPHAN_ACCOUNT_GAP: u64 = 2;
 
+/// How far ahead of the current time time-based locks are considered satisfied on mempool entry
 pub const FUTURE_TIMELOCK_TOLERANCE: Duration = Duration::from_secs(5 * 60);
 
+/// How far ahead of the tip height-based locks are considered satisfied on mempool entry
 pub const FUTURE_TIMELOCK_TOLERANCE_BLOCKS: BlockDistance = BlockDistance::new(5);
 
+/// Transaction flags that the mempool accepts; currently only the "replaceable" flag.
+pub const KNOWN_TRANSACTION_FLAGS: u128 = 0b1;
+
 pub const INCREMENTAL_RELAY_FEE_RATE: FeeRate =
     FeeRate::from_amount_per_kb(Amount::from_atoms(1000));
 
@@ -141,9 +149,33 @@ make_config_setting!(
     FeeRate::from_amount_per_kb(Amount::from_atoms(100_000_000_000))
 );
 
+make_config_setting!(MaxStandardSigops, usize, 1000);
+
+make_config_setting!(MaxProtectedTxsSize, usize, 1_000_000);
+
 #[derive(Debug, Clone, Default)]
 pub struct MempoolConfig {
     pub min_tx_relay_fee_rate: MinTxRelayFeeRate,
+
+    /// Coin outputs below this amount are rejected.
+    /// If not set, the chain's default is used, see [common::chain::ChainConfig::mempool_dust_threshold].
+    pub dust_threshold: Option<Amount>,
+
+    /// The maximum size of a transaction accepted into the mempool.
+    /// It's always capped by the chain's limit, see [common::chain::ChainConfig::max_tx_size_for_mempool].
+    pub max_tx_size: Option<usize>,
+
+    /// The maximum number of signature checks a transaction may require.
+    pub max_standard_sigops: MaxStandardSigops,
+
+    /// The maximum total size of transactions that are protected from eviction when
+    /// the mempool is full, see [crate::tx_options::TxEvictionPolicy::Protected].
+    pub max_protected_txs_size: MaxProtectedTxsSize,
+
+    /// If true, transactions are accepted regardless of the fee they pay, i.e. the relay fee
+    /// and the rolling fee checks are skipped (the consensus checks still apply).
+    /// Only honored on regtest, which is what private networks are based on.
+    pub accept_zero_fee_txs: Option<bool>,
 }
 
 impl MempoolConfig {
@@ -66,6 +66,12 @@ impl MempoolBanScore for MempoolPolicyError {
             MempoolPolicyError::ReplacementFeeLowerThanOriginal { .. } => 0,
             MempoolPolicyError::AdditionalFeesUnderflow => 0,
 
+            // Local relay policy may differ between nodes, don't punish the peer.
+            MempoolPolicyError::ExceedsMaxStandardTxSize { .. } => 0,
+            MempoolPolicyError::TooManySigops { .. } => 0,
+            MempoolPolicyError::DustOutput { .. } => 0,
+            MempoolPolicyError::ProtectedTxsSizeExceeded { .. } => 0,
+
             // Sending transactions with a fee below the minimum should not be punished.
             MempoolPolicyError::InsufficientFeesToRelay { .. } => 0,
             MempoolPolicyError::InsufficientFeesToRelayRBF => 0,
@@ -79,7 +85,13 @@ impl MempoolBanScore for MempoolPolicyError {
             MempoolPolicyError::AncestorFeeUpdateOverflow => 0,
             MempoolPolicyError::FeeOverflow => 0,
             MempoolPolicyError::GetParentError => 0,
+            MempoolPolicyError::TooManyUnconfirmedAncestors { .. } => 0,
             MempoolPolicyError::DescendantOfExpiredTransaction => 0,
+
+            // The transaction may become valid after a network upgrade (or may already be valid
+            // for a peer that knows about a newer one), so it's only rejected by policy.
+            MempoolPolicyError::UnknownTransactionFlags(_) => 0,
+            MempoolPolicyError::FeatureNotActivatedAtNextHeight { .. } => 0,
         }
     }
 }
@@ -302,9 +314,15 @@ impl MempoolBanScore for TransactionVerifierStorageError {
 
 impl MempoolBanScore for TokensError {
     fn mempool_ban_score(&self) -> u32 {
-        // TokensError only involves state-independent transaction validity.
-        // We can reuse the ban logic from chainstate here.
-        chainstate::ban_score::BanScore::ban_score(self)
+        match self {
+            // Depends on the tokens issued so far; two transactions issuing a token with the same
+            // ticker may be relayed concurrently.
+            TokensError::TokenTickerAlreadyTaken(_, _, _) => 0,
+
+            // The rest of TokensError only involves state-independent transaction validity.
+            // We can reuse the ban logic from chainstate here.
+            err => chainstate::ban_score::BanScore::ban_score(err),
+        }
     }
 }
 
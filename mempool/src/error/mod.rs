@@ -16,13 +16,16 @@
 mod ban_score;
 
 pub use ban_score::MempoolBanScore;
-use chainstate::{tx_verifier::error::ConnectTransactionError, ChainstateError};
+use chainstate::{
+    tx_verifier::{error::ConnectTransactionError, CheckTransactionError},
+    ChainstateError,
+};
 use subsystem::error::CallError;
 use thiserror::Error;
 
 use common::{
     chain::{Block, GenBlock, Transaction},
-    primitives::{amount::DisplayAmount, Id, H256},
+    primitives::{amount::DisplayAmount, BlockHeight, Id, H256},
 };
 
 use crate::pool::fee::Fee;
@@ -64,6 +67,18 @@ pub enum MempoolPolicyError {
     NoOutputs,
     #[error("Transaction exceeds the maximum block size.")]
     ExceedsMaxBlockSize,
+    #[error("Transaction size {size} exceeds the maximum standard transaction size {max_size}")]
+    ExceedsMaxStandardTxSize { size: usize, max_size: usize },
+    #[error("Transaction requires {sigops} signature checks, the maximum is {max_sigops}")]
+    TooManySigops { sigops: usize, max_sigops: usize },
+    #[error("Output {output_index} amount {amount} is below the dust threshold {threshold}")]
+    DustOutput {
+        output_index: usize,
+        amount: DisplayAmount,
+        threshold: DisplayAmount,
+    },
+    #[error("Protecting the transaction from eviction would bring the total size of protected transactions to {size}, the maximum is {max_size}")]
+    ProtectedTxsSizeExceeded { size: usize, max_size: usize },
     #[error("Replacement transaction has fee lower than the original. Replacement fee is {replacement_fee:?}, original fee {original_fee:?}")]
     ReplacementFeeLowerThanOriginal {
         replacement_tx: H256,
@@ -97,10 +112,19 @@ pub enum MempoolPolicyError {
     FeeOverflow,
     #[error("Get parent error")]
     GetParentError,
+    #[error("Transaction has more than {max} unconfirmed ancestors")]
+    TooManyUnconfirmedAncestors { max: usize },
     #[error("Transaction is a descendant of expired transaction.")]
     DescendantOfExpiredTransaction,
     #[error("Relay fee overflow error")]
     RelayFeeOverflow,
+    #[error("Transaction flags {0:#x} contain bits that are not known")]
+    UnknownTransactionFlags(u128),
+    #[error("Transaction uses a feature that is not activated at the next block height {height}: {error}")]
+    FeatureNotActivatedAtNextHeight {
+        height: BlockHeight,
+        error: CheckTransactionError,
+    },
 }
 
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
@@ -14,7 +14,7 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, Transaction},
+    chain::{Block, SignedTransaction, Transaction},
     primitives::{BlockHeight, Id},
 };
 
@@ -28,6 +28,7 @@ use crate::{
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TransactionProcessed {
     tx_id: Id<Transaction>,
+    wtxid: Id<SignedTransaction>,
     origin: TxOrigin,
     relay_policy: TxRelayPolicy,
     result: crate::Result<()>,
@@ -36,24 +37,36 @@ pub struct TransactionProcessed {
 impl TransactionProcessed {
     fn new(
         tx_id: Id<Transaction>,
+        wtxid: Id<SignedTransaction>,
         origin: TxOrigin,
         relay_policy: TxRelayPolicy,
         result: crate::Result<()>,
     ) -> Self {
         Self {
             tx_id,
+            wtxid,
             origin,
             relay_policy,
             result,
         }
     }
 
-    pub fn accepted(tx_id: Id<Transaction>, relay_policy: TxRelayPolicy, origin: TxOrigin) -> Self {
-        Self::new(tx_id, origin, relay_policy, Ok(()))
+    pub fn accepted(
+        tx_id: Id<Transaction>,
+        wtxid: Id<SignedTransaction>,
+        relay_policy: TxRelayPolicy,
+        origin: TxOrigin,
+    ) -> Self {
+        Self::new(tx_id, wtxid, origin, relay_policy, Ok(()))
     }
 
-    pub fn rejected(tx_id: Id<Transaction>, err: Error, origin: TxOrigin) -> Self {
-        Self::new(tx_id, origin, TxRelayPolicy::DontRelay, Err(err))
+    pub fn rejected(
+        tx_id: Id<Transaction>,
+        wtxid: Id<SignedTransaction>,
+        err: Error,
+        origin: TxOrigin,
+    ) -> Self {
+        Self::new(tx_id, wtxid, origin, TxRelayPolicy::DontRelay, Err(err))
     }
 
     pub fn result(&self) -> &crate::Result<()> {
@@ -72,6 +85,10 @@ impl TransactionProcessed {
         &self.tx_id
     }
 
+    pub fn wtxid(&self) -> &Id<SignedTransaction> {
+        &self.wtxid
+    }
+
     pub fn origin(&self) -> TxOrigin {
         self.origin
     }
@@ -102,11 +119,72 @@ impl NewTip {
     }
 }
 
+/// Event triggered when a transaction has replaced other transactions in the mempool
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransactionReplaced {
+    replaced: Vec<Id<Transaction>>,
+    replacement: Id<Transaction>,
+}
+
+impl TransactionReplaced {
+    pub fn new(replaced: Vec<Id<Transaction>>, replacement: Id<Transaction>) -> Self {
+        Self {
+            replaced,
+            replacement,
+        }
+    }
+
+    /// The evicted transactions, including the descendants of the conflicting ones
+    pub fn replaced(&self) -> &[Id<Transaction>] {
+        &self.replaced
+    }
+
+    pub fn replacement(&self) -> &Id<Transaction> {
+        &self.replacement
+    }
+}
+
+/// The reason a transaction has been removed from the mempool
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TxRemovalReason {
+    /// The transaction has been replaced by a conflicting one
+    Replaced,
+    /// The transaction has stayed in the mempool for longer than allowed
+    Expired,
+    /// The transaction has been included in a new block or has been invalidated by one
+    Block,
+    /// The transaction has been evicted to keep the mempool within its size limit
+    SizeLimit,
+}
+
+/// Event triggered when a transaction has been removed from the mempool
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransactionRemoved {
+    tx_id: Id<Transaction>,
+    reason: TxRemovalReason,
+}
+
+impl TransactionRemoved {
+    pub fn new(tx_id: Id<Transaction>, reason: TxRemovalReason) -> Self {
+        Self { tx_id, reason }
+    }
+
+    pub fn tx_id(&self) -> &Id<Transaction> {
+        &self.tx_id
+    }
+
+    pub fn reason(&self) -> TxRemovalReason {
+        self.reason
+    }
+}
+
 /// Events emitted by mempool
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum MempoolEvent {
     NewTip(NewTip),
     TransactionProcessed(TransactionProcessed),
+    TransactionReplaced(TransactionReplaced),
+    TransactionRemoved(TransactionRemoved),
 }
 
 impl From<TransactionProcessed> for MempoolEvent {
@@ -120,3 +198,15 @@ impl From<NewTip> for MempoolEvent {
         Self::NewTip(event)
     }
 }
+
+impl From<TransactionReplaced> for MempoolEvent {
+    fn from(event: TransactionReplaced) -> Self {
+        Self::TransactionReplaced(event)
+    }
+}
+
+impl From<TransactionRemoved> for MempoolEvent {
+    fn from(event: TransactionRemoved) -> Self {
+        Self::TransactionRemoved(event)
+    }
+}
@@ -18,13 +18,14 @@ use crate::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolMaxSize, MempoolTxAncestry, MempoolTxInfo, SeenTransactions, TxOptions,
+    TxStatus,
 };
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
     primitives::Id,
 };
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 pub trait MempoolInterface: Send + Sync {
     /// Add a transaction from remote peer to mempool
@@ -49,6 +50,19 @@ pub trait MempoolInterface: Send + Sync {
     /// Get a specific transaction from the main mempool (non-orphan)
     fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
 
+    /// Get fee information about a transaction in the main mempool (non-orphan)
+    fn transaction_info(&self, id: &Id<Transaction>) -> Option<MempoolTxInfo>;
+
+    /// Get the in-mempool ancestors and descendants of a transaction in the main mempool
+    fn transaction_ancestry(&self, id: &Id<Transaction>) -> Option<MempoolTxAncestry>;
+
+    /// Get the ids of the local transactions that should be relayed and have stayed in the main
+    /// mempool for at least `min_age`, so that they can be announced to peers again
+    fn local_transactions_to_rebroadcast(
+        &self,
+        min_age: Duration,
+    ) -> Vec<(Id<Transaction>, Id<SignedTransaction>)>;
+
     /// Get a specific transaction from the orphan pool
     fn orphan_transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
 
@@ -78,6 +92,10 @@ pub trait MempoolInterface: Send + Sync {
     /// Subscribe to broadcast mempool events
     fn subscribe_to_rpc_events(&mut self) -> utils_networking::broadcaster::Receiver<MempoolEvent>;
 
+    /// Get the shared filter of recently processed transactions, which can be queried directly,
+    /// without calling into the mempool subsystem
+    fn seen_transactions(&self) -> SeenTransactions;
+
     /// Get current memory usage
     fn memory_usage(&self) -> usize;
 
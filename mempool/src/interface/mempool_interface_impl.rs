@@ -20,7 +20,8 @@ use crate::{
     pool::memory_usage_estimator::StoreMemoryUsageEstimator,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolInterface, MempoolMaxSize, MempoolTxAncestry, MempoolTxInfo, SeenTransactions,
+    TxOptions, TxStatus,
 };
 use chainstate::ChainstateEventTracingWrapper;
 use common::{
@@ -29,7 +30,7 @@ use common::{
     time_getter::TimeGetter,
 };
 use logging::log;
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 use utils::{const_value::ConstValue, tap_log::TapLog};
 
 type Mempool = crate::pool::Mempool<StoreMemoryUsageEstimator>;
@@ -128,6 +129,21 @@ impl MempoolInterface for Mempool {
         self.transaction(id).cloned()
     }
 
+    fn transaction_info(&self, id: &Id<Transaction>) -> Option<MempoolTxInfo> {
+        self.transaction_info(id)
+    }
+
+    fn transaction_ancestry(&self, id: &Id<Transaction>) -> Option<MempoolTxAncestry> {
+        self.transaction_ancestry(id)
+    }
+
+    fn local_transactions_to_rebroadcast(
+        &self,
+        min_age: Duration,
+    ) -> Vec<(Id<Transaction>, Id<SignedTransaction>)> {
+        self.local_transactions_to_rebroadcast(min_age)
+    }
+
     fn contains_orphan_transaction(&self, tx: &Id<Transaction>) -> bool {
         self.contains_orphan_transaction(tx)
     }
@@ -158,6 +174,10 @@ impl MempoolInterface for Mempool {
         self.subscribe_to_event_broadcast()
     }
 
+    fn seen_transactions(&self) -> SeenTransactions {
+        self.seen_transactions().clone()
+    }
+
     fn memory_usage(&self) -> usize {
         self.memory_usage()
     }
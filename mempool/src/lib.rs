@@ -26,9 +26,17 @@ mod interface;
 mod pool;
 pub mod rpc;
 pub mod rpc_event;
+mod seen_transactions;
 pub mod tx_accumulator;
 
-pub use {config::MempoolConfig, pool::feerate_points::find_interpolated_value, pool::FeeRate};
+pub use {
+    config::MempoolConfig,
+    pool::feerate_points::find_interpolated_value,
+    pool::FeeRate,
+    pool::MempoolTxAncestry,
+    pool::MempoolTxInfo,
+    seen_transactions::{SeenTransactions, SeenTxOutcome},
+};
 
 pub type MempoolHandle = subsystem::Handle<dyn MempoolInterface>;
 
@@ -126,9 +126,10 @@ impl TxDependency {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TxEntry<O = TxOrigin> {
     tx_id: Id<Transaction>,
+    wtxid: Id<SignedTransaction>,
     transaction: SignedTransaction,
     creation_time: Time,
-    encoded_size: NonZeroUsize,
+    size: NonZeroUsize,
     origin: O,
     options: TxOptions,
 }
@@ -142,13 +143,14 @@ impl<O: IsOrigin> TxEntry<O> {
         options: TxOptions,
     ) -> Self {
         let tx_id = transaction.transaction().get_id();
-        let encoded_size = serialization::Encode::encoded_size(&transaction);
-        let encoded_size = NonZeroUsize::new(encoded_size).expect("Encoded tx size is non-zero");
+        let wtxid = transaction.wtxid();
+        let size = NonZeroUsize::new(transaction.tx_size().weight()).expect("Tx size is non-zero");
         Self {
             tx_id,
+            wtxid,
             transaction,
             creation_time,
-            encoded_size,
+            size,
             origin,
             options,
         }
@@ -169,9 +171,14 @@ impl<O: IsOrigin> TxEntry<O> {
         &self.tx_id
     }
 
-    /// Encoded size of this entry
+    /// Witness transaction ID
+    pub fn wtxid(&self) -> &Id<SignedTransaction> {
+        &self.wtxid
+    }
+
+    /// Size of this entry that the fee rate and the mempool limits are based on
     pub fn size(&self) -> NonZeroUsize {
-        self.encoded_size
+        self.size
     }
 
     /// Where we got this transaction
@@ -214,18 +221,20 @@ impl<O: IsOrigin> TxEntry<O> {
             Ok(origin) => {
                 let TxEntry {
                     tx_id,
+                    wtxid,
                     transaction,
                     creation_time,
-                    encoded_size,
+                    size,
                     origin: _,
                     options,
                 } = self;
 
                 Ok(TxEntry {
                     tx_id,
+                    wtxid,
                     transaction,
                     creation_time,
-                    encoded_size,
+                    size,
                     origin,
                     options,
                 })
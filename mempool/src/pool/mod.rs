@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use chainstate::ChainstateEvent;
 use common::{
@@ -25,7 +25,11 @@ use logging::log;
 use utils::{const_value::ConstValue, ensure, eventhandler::EventsController};
 use utils_networking::broadcaster;
 
-pub use self::{feerate::FeeRate, tx_pool::feerate_points};
+pub use self::{
+    feerate::FeeRate,
+    tx_info::{MempoolTxAncestry, MempoolTxInfo},
+    tx_pool::feerate_points,
+};
 
 use self::{
     entry::{TxDependency, TxEntry},
@@ -44,13 +48,14 @@ use crate::{
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_options::{TxOptions, TxTrustPolicy},
     tx_origin::{RemoteTxOrigin, TxOrigin},
-    MempoolMaxSize, TxStatus,
+    MempoolMaxSize, SeenTransactions, SeenTxOutcome, TxStatus,
 };
 
 mod entry;
 pub mod fee;
 mod feerate;
 mod orphans;
+mod tx_info;
 mod tx_pool;
 mod work_queue;
 
@@ -68,6 +73,7 @@ pub struct Mempool<M> {
     orphans: TxOrphanPool,
     work_queue: WorkQueue,
     events_broadcast: EventsBroadcast,
+    seen_transactions: SeenTransactions,
     clock: TimeGetter,
 }
 
@@ -91,6 +97,7 @@ impl<M> Mempool<M> {
             orphans: orphans::TxOrphanPool::new(),
             work_queue: WorkQueue::new(),
             events_broadcast: EventsBroadcast::new(),
+            seen_transactions: SeenTransactions::new(),
             clock,
         }
     }
@@ -120,6 +127,21 @@ impl<M> Mempool<M> {
         self.tx_pool.transaction(id)
     }
 
+    pub fn transaction_info(&self, id: &Id<Transaction>) -> Option<MempoolTxInfo> {
+        self.tx_pool.transaction_info(id)
+    }
+
+    pub fn transaction_ancestry(&self, id: &Id<Transaction>) -> Option<MempoolTxAncestry> {
+        self.tx_pool.transaction_ancestry(id)
+    }
+
+    pub fn local_transactions_to_rebroadcast(
+        &self,
+        min_age: Duration,
+    ) -> Vec<(Id<Transaction>, Id<SignedTransaction>)> {
+        self.tx_pool.local_transactions_to_rebroadcast(min_age)
+    }
+
     pub fn contains_orphan_transaction(&self, id: &Id<Transaction>) -> bool {
         self.orphans.contains(id)
     }
@@ -139,6 +161,10 @@ impl<M> Mempool<M> {
     pub fn has_work(&self) -> bool {
         !self.work_queue.is_empty()
     }
+
+    pub fn seen_transactions(&self) -> &SeenTransactions {
+        &self.seen_transactions
+    }
 }
 
 // Mempool Interface and Event Reactions
@@ -155,6 +181,9 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             TxTrustPolicy::Untrusted => (),
         }
 
+        let tx_id = *transaction.tx_id();
+        let wtxid = *transaction.wtxid();
+
         let mut finalizer = TxFinalizer::new(
             &mut self.orphans,
             &self.clock,
@@ -162,9 +191,24 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             TxFinalizerEventsMode::Broadcast(&mut self.events_broadcast),
         );
 
-        self.tx_pool.add_transaction(transaction, |outcome, tx_pool| {
-            finalizer.finalize_tx(tx_pool, outcome)
-        })?
+        let result = self
+            .tx_pool
+            .add_transaction(transaction, |outcome, tx_pool| {
+                finalizer.finalize_tx(tx_pool, outcome)
+            })
+            .and_then(|result| result);
+
+        self.broadcast_removed_txs();
+
+        match &result {
+            Ok(TxStatus::InMempool | TxStatus::InMempoolDuplicate) => {
+                self.seen_transactions.record(&tx_id, &wtxid, SeenTxOutcome::Accepted)
+            }
+            Ok(TxStatus::InOrphanPool | TxStatus::InOrphanPoolDuplicate) => {}
+            Err(_) => self.seen_transactions.record(&tx_id, &wtxid, SeenTxOutcome::Rejected),
+        }
+
+        result
     }
 
     /// Make transaction entry out of a signed transaction.
@@ -223,6 +267,7 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
         log::debug!("Processing chainstate event {evt:?}");
         match evt {
             ChainstateEvent::NewTip(block_id, height) => self.on_new_tip(block_id, height)?,
+            ChainstateEvent::LongFork(_, _, _) => {}
         };
         Ok(())
     }
@@ -244,6 +289,9 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
             }
         })?;
 
+        self.broadcast_removed_txs();
+        self.seen_transactions.clear_rejected();
+
         let new_tip = event::NewTip::new(block_id, height);
         let event = new_tip.into();
         self.events_broadcast.broadcast(event);
@@ -256,7 +304,20 @@ impl<M: MemoryUsageEstimator> Mempool<M> {
     }
 
     pub fn set_size_limit(&mut self, max_size: MempoolMaxSize) -> Result<(), Error> {
-        self.tx_pool.set_max_size(max_size)
+        let result = self.tx_pool.set_max_size(max_size);
+        self.broadcast_removed_txs();
+        result
+    }
+
+    fn broadcast_removed_txs(&mut self) {
+        for event in self.tx_pool.take_removed_txs() {
+            log::trace!(
+                "Transaction {} removed: {:?}",
+                event.tx_id(),
+                event.reason()
+            );
+            self.events_broadcast.broadcast(event.into());
+        }
     }
 
     pub fn memory_usage(&self) -> usize {
@@ -353,6 +414,7 @@ impl<'a> TxFinalizer<'a> {
         match outcome {
             TxAdditionOutcome::Added { transaction } => {
                 let tx_id = *transaction.tx_id();
+                let wtxid = *transaction.tx_entry().wtxid();
                 let relay_policy = transaction.tx_entry().options().relay_policy();
                 let origin = transaction.tx_entry().origin();
                 log::trace!("Added transaction {tx_id}");
@@ -362,10 +424,22 @@ impl<'a> TxFinalizer<'a> {
                 match &mut self.events_mode {
                     TxFinalizerEventsMode::Silent => {}
                     TxFinalizerEventsMode::Broadcast(events_broadcast) => {
-                        let event =
-                            event::TransactionProcessed::accepted(tx_id, relay_policy, origin);
+                        let event = event::TransactionProcessed::accepted(
+                            tx_id,
+                            wtxid,
+                            relay_policy,
+                            origin,
+                        );
                         let event = event.into();
                         events_broadcast.broadcast(event);
+
+                        if !transaction.replaced().is_empty() {
+                            let event = event::TransactionReplaced::new(
+                                transaction.replaced().to_vec(),
+                                tx_id,
+                            );
+                            events_broadcast.broadcast(event.into());
+                        }
                     }
                 }
 
@@ -377,6 +451,7 @@ impl<'a> TxFinalizer<'a> {
             }
             TxAdditionOutcome::Rejected { transaction, error } => {
                 let tx_id = *transaction.tx_id();
+                let wtxid = *transaction.wtxid();
                 let origin = transaction.origin();
                 log::trace!(
                     "Rejected transaction {tx_id} with error {error}. Checking orphan status"
@@ -386,8 +461,12 @@ impl<'a> TxFinalizer<'a> {
                     .inspect_err(|err| match &mut self.events_mode {
                         TxFinalizerEventsMode::Silent => {}
                         TxFinalizerEventsMode::Broadcast(events_broadcast) => {
-                            let event =
-                                event::TransactionProcessed::rejected(tx_id, err.clone(), origin);
+                            let event = event::TransactionProcessed::rejected(
+                                tx_id,
+                                wtxid,
+                                err.clone(),
+                                origin,
+                            );
                             let event = event.into();
                             events_broadcast.broadcast(event);
                         }
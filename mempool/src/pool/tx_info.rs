@@ -0,0 +1,73 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::Transaction,
+    primitives::{Amount, Id},
+};
+use rpc::description::HasValueHint;
+
+use super::FeeRate;
+
+/// Fee related information about a transaction in the mempool
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct MempoolTxInfo {
+    /// Fee paid by the transaction
+    pub fee: Amount,
+
+    /// Encoded size of the transaction in bytes
+    pub size: usize,
+
+    /// Fee rate of the transaction alone
+    pub fee_rate: FeeRate,
+
+    /// Fee rate of the transaction together with its unconfirmed ancestors.
+    /// This is the rate the transaction is prioritized by when blocks are assembled.
+    pub package_fee_rate: FeeRate,
+
+    /// Number of unconfirmed transactions that have to be included in a block along with this
+    /// one, including the transaction itself
+    pub count_with_ancestors: usize,
+
+    /// Transactions that have been evicted from the mempool by this one via replace-by-fee,
+    /// including the descendants of the conflicting transactions
+    pub replaced: Vec<Id<Transaction>>,
+
+    /// Whether the transaction or one of its unconfirmed ancestors signals that it may be
+    /// replaced via replace-by-fee
+    pub rbf_signalled: bool,
+}
+
+/// In-mempool relatives of a transaction together with package totals
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct MempoolTxAncestry {
+    /// Unconfirmed transactions the transaction spends from, directly or indirectly
+    pub ancestors: Vec<Id<Transaction>>,
+
+    /// Mempool transactions spending from the transaction, directly or indirectly
+    pub descendants: Vec<Id<Transaction>>,
+
+    /// Total fee of the transaction together with its ancestors
+    pub fee_with_ancestors: Amount,
+
+    /// Total size of the transaction together with its ancestors
+    pub size_with_ancestors: usize,
+
+    /// Total fee of the transaction together with its descendants
+    pub fee_with_descendants: Amount,
+
+    /// Total size of the transaction together with its descendants
+    pub size_with_descendants: usize,
+}
@@ -15,8 +15,9 @@
 
 use crate::{
     error::{BlockConstructionError, TxValidationError},
-    pool::tx_pool::{tx_verifier, TxMempoolEntry, TxPool},
+    pool::tx_pool::{store::AncestorScore, tx_verifier, TxMempoolEntry, TxPool},
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
+    tx_origin::TxOrigin,
 };
 
 use std::{
@@ -77,6 +78,7 @@ pub fn collect_txs<M>(
 ) -> Result<Option<Box<dyn TransactionAccumulator>>, BlockConstructionError> {
     let mempool_tip = mempool.best_block_id();
     let unlock_timestamp = tx_accumulator.unlock_timestamp();
+    let template_policy = tx_accumulator.template_policy();
 
     if tx_accumulator.expected_tip() != mempool_tip {
         return Ok(None);
@@ -111,6 +113,10 @@ pub fn collect_txs<M>(
         })
         .collect::<Result<Vec<_>, TxValidationError>>()?;
 
+    // Total size of the transactions placed into the accumulator
+    let mut emitted_size: usize =
+        tx_accumulator.transactions().iter().map(|tx| tx.tx_size().weight()).sum();
+
     // Set of transactions already placed into the accumulator
     let mut emitted: BTreeSet<_> = accum_ids.iter().collect();
     // Set of already processed transactions, for de-duplication
@@ -128,12 +134,30 @@ pub fn collect_txs<M>(
         graph_traversals::dag_depth_postorder_multiroot(&transaction_ids, |tx_id| {
             mempool.store.get_entry(tx_id).expect("already checked").parents()
         })
+        .collect::<Vec<_>>()
+    };
+
+    // Transactions that may use the space reserved by the template policy
+    let given_txids_set: BTreeSet<_> = given_txids.iter().copied().collect();
+    let is_priority = |entry: &TxMempoolEntry| {
+        let is_local = match entry.tx_entry().origin() {
+            TxOrigin::Local(_) => true,
+            TxOrigin::Remote(_) => false,
+        };
+        is_local || given_txids_set.contains(entry.tx_id())
     };
 
     // Transaction IDs taken from mempool to fill in the rest of the block
     let mempool_txids = {
-        // Get transactions from mempool by score
-        let txids = mempool.store.txs_by_ancestor_score.iter().map(|x| &x.1).rev();
+        let min_score = template_policy.min_fee_rate.map(AncestorScore::from);
+        // Get transactions from mempool by score, skipping the ones below the fee rate floor
+        let txids = mempool
+            .store
+            .txs_by_ancestor_score
+            .iter()
+            .rev()
+            .take_while(move |(score, _)| min_score.as_ref().is_none_or(|min| score >= min))
+            .map(|x| &x.1);
         // Take the appropriate amount of them as determined by the packing strategy
         txids.take(match packing_strategy {
             PackingStrategy::FillSpaceFromMempool => usize::MAX,
@@ -143,6 +167,8 @@ pub fn collect_txs<M>(
 
     // Put all the transaction IDs together
     let mut tx_iter = given_txids
+        .iter()
+        .copied()
         .chain(mempool_txids)
         .filter_map(|tx_id| {
             // If the transaction with this ID has already been processed, skip it
@@ -194,6 +220,14 @@ pub fn collect_txs<M>(
             (None, None) => break,
         };
 
+        let tx_size = next_tx.size().get();
+        if !is_priority(next_tx)
+            && emitted_size.saturating_add(tx_size) > template_policy.non_priority_size_limit
+        {
+            // The remaining space is reserved for priority transactions
+            continue;
+        }
+
         let verification_result =
             tx_verifier.connect_transaction(&tx_source, next_tx.transaction(), &unlock_timestamp);
 
@@ -216,6 +250,7 @@ pub fn collect_txs<M>(
         }
 
         emitted.insert(next_tx.tx_id());
+        emitted_size += tx_size;
 
         // Release newly ready transactions
         for child in next_tx.children() {
@@ -16,6 +16,7 @@
 mod collect_txs;
 pub mod feerate_points;
 pub mod memory_usage_estimator;
+mod policy;
 mod reorg;
 mod rolling_fee_rate;
 mod store;
@@ -51,6 +52,7 @@ use utils::{const_value::ConstValue, ensure, shallow_clone::ShallowClone};
 
 use self::{
     memory_usage_estimator::MemoryUsageEstimator,
+    policy::MempoolPolicy,
     rolling_fee_rate::RollingFeeRate,
     store::{Conflicts, DescendantScore, MempoolRemovalReason, MempoolStore, TxMempoolEntry},
 };
@@ -60,18 +62,21 @@ use crate::{
         BlockConstructionError, Error, MempoolConflictError, MempoolPolicyError, OrphanPoolError,
         ReorgError, TxValidationError,
     },
+    event::TransactionRemoved,
     pool::{
         entry::{TxEntry, TxEntryWithFee},
         fee::Fee,
         feerate::FeeRate,
+        tx_info::{MempoolTxAncestry, MempoolTxInfo},
     },
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
-    tx_origin::RemoteTxOrigin,
+    tx_options::{TxEvictionPolicy, TxRelayPolicy},
+    tx_origin::{RemoteTxOrigin, TxOrigin},
 };
 
 pub struct TxPool<M> {
     chain_config: Arc<ChainConfig>,
-    mempool_config: ConstValue<MempoolConfig>,
+    policy: MempoolPolicy,
     store: MempoolStore,
     rolling_fee_rate: RwLock<RollingFeeRate>,
     max_size: config::MempoolMaxSize,
@@ -102,10 +107,13 @@ impl<M> TxPool<M> {
             chainstate_handle.shallow_clone(),
         );
 
+        let policy = MempoolPolicy::new(&chain_config, &mempool_config);
+        log::debug!("Mempool policy: {policy:?}");
+
         log::trace!("Creating mempool object");
         Self {
             chain_config,
-            mempool_config,
+            policy,
             store: MempoolStore::new(),
             chainstate_handle,
             max_size: config::MempoolMaxSize::default(),
@@ -144,7 +152,21 @@ impl<M> TxPool<M> {
             self.chainstate_handle.shallow_clone(),
         );
 
-        std::mem::replace(&mut self.store, MempoolStore::new()).into_transactions()
+        let mut old_store = std::mem::replace(&mut self.store, MempoolStore::new());
+        // Keep the removals that haven't been reported yet
+        for (tx_id, reason) in old_store.take_removed_txs() {
+            self.store.record_removal(tx_id, reason);
+        }
+        old_store.into_transactions()
+    }
+
+    /// Take the transactions removed from the mempool since the last call
+    pub fn take_removed_txs(&mut self) -> Vec<TransactionRemoved> {
+        self.store
+            .take_removed_txs()
+            .into_iter()
+            .map(|(tx_id, reason)| TransactionRemoved::new(tx_id, reason.into()))
+            .collect()
     }
 
     pub fn is_ibd(&self) -> bool {
@@ -244,6 +266,35 @@ impl<M> TxPool<M> {
     pub fn transaction(&self, id: &Id<Transaction>) -> Option<&SignedTransaction> {
         self.store.get_entry(id).map(TxMempoolEntry::transaction)
     }
+
+    pub fn transaction_info(&self, id: &Id<Transaction>) -> Option<MempoolTxInfo> {
+        self.store.get_entry(id).map(|entry| entry.info(&self.store))
+    }
+
+    pub fn transaction_ancestry(&self, id: &Id<Transaction>) -> Option<MempoolTxAncestry> {
+        self.store.get_entry(id).map(|entry| entry.ancestry(&self.store))
+    }
+
+    /// Local transactions that should be relayed and have stayed in the mempool for at least
+    /// `min_age`, oldest first.
+    pub fn local_transactions_to_rebroadcast(
+        &self,
+        min_age: Duration,
+    ) -> Vec<(Id<Transaction>, Id<SignedTransaction>)> {
+        let now = self.clock.get_time();
+        self.store
+            .txs_by_creation_time
+            .iter()
+            .map(|(_time, id)| self.store.txs_by_id.get(id).expect("entry should exist"))
+            .filter(|entry| {
+                let tx_entry = entry.tx_entry();
+                matches!(tx_entry.origin(), TxOrigin::Local(_))
+                    && tx_entry.options().relay_policy() == TxRelayPolicy::DoRelay
+                    && now.saturating_sub(entry.creation_time()) >= min_age
+            })
+            .map(|entry| (*entry.tx_id(), *entry.tx_entry().wtxid()))
+            .collect()
+    }
 }
 
 // Transaction Validation
@@ -259,19 +310,75 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
         ensure!(has_outputs, MempoolPolicyError::NoOutputs);
 
         let size = entry.size().get();
-        let max_size = self.chain_config.max_tx_size_for_mempool();
-        ensure!(size <= max_size, MempoolPolicyError::ExceedsMaxBlockSize);
+        self.policy.check_transaction(&self.chain_config, tx, size)?;
+
+        if entry.options().eviction_policy() == TxEvictionPolicy::Protected {
+            let protected_size = self.store.protected_txs_size() + size;
+            let max_protected_size = self.policy.max_protected_txs_size();
+            ensure!(
+                protected_size <= max_protected_size,
+                MempoolPolicyError::ProtectedTxsSizeExceeded {
+                    size: protected_size,
+                    max_size: max_protected_size,
+                }
+            );
+        }
+
+        let flags = tx.transaction().flags();
+        ensure!(
+            flags & !config::KNOWN_TRANSACTION_FLAGS == 0,
+            MempoolPolicyError::UnknownTransactionFlags(flags)
+        );
 
         Ok(())
     }
 
+    // The block size limits may change with network upgrades, so the transaction has to fit
+    // into the block at the next height.
+    fn check_block_size_limit_at_next_height(
+        &self,
+        entry: &TxEntry,
+        next_height: BlockHeight,
+    ) -> Result<(), MempoolPolicyError> {
+        let max_size = self.chain_config.max_tx_size_for_mempool(next_height);
+        ensure!(
+            entry.size().get() <= max_size,
+            MempoolPolicyError::ExceedsMaxBlockSize
+        );
+        Ok(())
+    }
+
+    // The consensus checks are done at a height that is a few blocks ahead of the tip, to
+    // tolerate timelock fluctuations. Features gated by network upgrades must be active already
+    // at the next height though, otherwise the transaction would be accepted and relayed before
+    // it can be included in a block.
+    fn check_features_activated_at_next_height(
+        &self,
+        entry: &TxEntry,
+        next_height: BlockHeight,
+    ) -> Result<(), MempoolPolicyError> {
+        chainstate::tx_verifier::check_transaction(
+            &self.chain_config,
+            next_height,
+            entry.transaction(),
+        )
+        .map_err(
+            |error| MempoolPolicyError::FeatureNotActivatedAtNextHeight {
+                height: next_height,
+                error,
+            },
+        )
+    }
+
     // Check the transaction against the mempool inclusion policy
     fn check_mempool_policy(
         &self,
         entry: &TxEntryWithFee,
     ) -> Result<Conflicts, MempoolPolicyError> {
-        self.pays_minimum_relay_fees(entry)?;
-        self.pays_minimum_mempool_fee(entry)?;
+        if !self.policy.accept_zero_fee_txs() {
+            self.pays_minimum_relay_fees(entry)?;
+            self.pays_minimum_mempool_fee(entry)?;
+        }
 
         if config::ENABLE_RBF {
             self.rbf_checks(entry)
@@ -309,7 +416,7 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
     }
 
     fn get_minimum_relay_fee(&self, tx: &TxEntry) -> Result<Fee, MempoolPolicyError> {
-        self.mempool_config.min_tx_relay_fee_rate.compute_fee(tx.size().into())
+        self.policy.min_tx_relay_fee_rate().compute_fee(tx.size().into())
     }
 
     fn pays_minimum_relay_fees(&self, tx: &TxEntryWithFee) -> Result<(), MempoolPolicyError> {
@@ -608,23 +715,33 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
     fn trim(&mut self) -> Result<Vec<FeeRate>, MempoolPolicyError> {
         let mut removed_fees = Vec::new();
         while !self.store.is_empty() && self.memory_usage() > self.max_size.as_bytes() {
-            // TODO sort by descendant score, not by fee
-            let removed_id = self
+            // Evict the package (i.e. the transaction together with all its descendants) with
+            // the lowest descendant score. Evicting a transaction also evicts its descendants,
+            // so skip the ones that would take a protected transaction with them.
+            let removed_id = match self
                 .store
                 .txs_by_descendant_score
                 .iter()
                 .map(|(_score, entry)| *entry)
-                .next()
-                .expect("pool not empty");
+                .find(|id| !self.store.is_eviction_protected(id))
+            {
+                Some(id) => id,
+                None => {
+                    log::debug!("Mempool trim: only eviction-protected transactions are left");
+                    break;
+                }
+            };
             let removed = self.store.txs_by_id.get(&removed_id).expect("tx with id should exist");
 
             log::debug!(
-                "Mempool trim: Evicting tx {} which has a descendant score of {:?} and has size {}",
+                "Mempool trim: Evicting tx {} which has a descendant score of {:?} and has size {}, {} descendants included",
                 removed_id,
                 removed.descendant_score(),
-                removed.size()
+                removed.size(),
+                removed.count_with_descendants() - 1,
             );
-            removed_fees.push(FeeRate::from_total_tx_fee(removed.fee(), removed.size())?);
+            // The whole package is gone, so its fee rate is what new transactions have to beat
+            removed_fees.push(removed.package_fee_rate_with_descendants());
             self.remove_tx_and_descendants(&removed_id, MempoolRemovalReason::SizeLimit);
         }
         Ok(removed_fees)
@@ -706,10 +823,16 @@ enum TxValidationOutcome {
     Valid {
         fee: Fee,
         delta: TransactionVerifierDelta,
+        /// The height of the block that would include the transaction
+        next_height: BlockHeight,
     },
     Rejected {
         error: ConnectTransactionError,
     },
+    /// The transaction was rejected by the mempool policy checks that depend on the tip
+    PolicyViolation {
+        error: MempoolPolicyError,
+    },
     TipMoved {
         start_tip: Id<GenBlock>,
         current_tip: Id<GenBlock>,
@@ -767,11 +890,16 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
     ) -> Result<TxAdditionAttemptOutcome, Error> {
         debug_assert!(!self.is_ibd());
 
-        let (fee, delta) = match self.validate_transaction(&transaction)? {
-            TxValidationOutcome::Valid { fee, delta } => (fee, delta),
+        let (fee, delta, next_height) = match self.validate_transaction(&transaction)? {
+            TxValidationOutcome::Valid {
+                fee,
+                delta,
+                next_height,
+            } => (fee, delta, next_height),
             TxValidationOutcome::Rejected { error } => {
                 return Ok(TxAdditionAttemptOutcome::Rejected { transaction, error })
             }
+            TxValidationOutcome::PolicyViolation { error } => return Err(error.into()),
             TxValidationOutcome::TipMoved {
                 start_tip,
                 current_tip,
@@ -784,14 +912,22 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
             }
         };
 
+        self.check_features_activated_at_next_height(&transaction, next_height)?;
+
         let tx = TxEntryWithFee::new(transaction, fee);
         let conflicts = self.check_mempool_policy(&tx)?;
 
-        if config::ENABLE_RBF {
-            self.store.drop_conflicts(conflicts);
-        }
+        let tx_id = *tx.tx_id();
+        let replaced = if config::ENABLE_RBF {
+            self.store.drop_conflicts(conflicts)
+        } else {
+            Vec::new()
+        };
         tx_verifier::flush_to_storage(&mut self.tx_verifier, delta)?;
         self.finalize_tx(tx)?;
+        if !replaced.is_empty() {
+            self.store.set_replaced(&tx_id, replaced);
+        }
         self.store.assert_valid();
 
         Ok(TxAdditionAttemptOutcome::Added)
@@ -804,13 +940,15 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
         let tx_id = *transaction.tx_id();
         let chainstate_handle = self.blocking_chainstate_handle();
 
-        let (start_tip, current_best) = chainstate_handle.call(|chainstate| {
-            let tip = chainstate.get_best_block_id()?;
-            let tip_index = chainstate
-                .get_gen_block_index_for_persisted_block(&tip)?
-                .expect("tip block index to exist");
-            Ok::<_, chainstate::ChainstateError>((tip, tip_index))
-        })??;
+        let (start_tip_height, start_tip) =
+            chainstate_handle.call(|c| c.get_best_block_height_and_id())??;
+        let next_height = start_tip_height.next_height();
+
+        // Checked before the full validation, which is much more expensive. Since the check
+        // depends on the tip, it's re-done for all the transactions when a new block arrives.
+        if let Err(error) = self.check_block_size_limit_at_next_height(transaction, next_height) {
+            return Ok(TxValidationOutcome::PolicyViolation { error });
+        }
 
         let mut tx_verifier = self.tx_verifier.derive_child();
 
@@ -819,19 +957,26 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
             tx_verifier.get_best_block_for_utxos()?
         );
 
+        // The full check includes timelocks of the spent outputs. They are checked against a point
+        // slightly in the future so that transactions becoming spendable within a few blocks are
+        // not rejected and re-requested repeatedly. Block production re-checks the timelocks
+        // against the actual next block, see `collect_txs`.
         let verifier_time =
             self.clock.get_time().saturating_duration_add(config::FUTURE_TIMELOCK_TOLERANCE);
-        let effective_height = (current_best.block_height()
-            + config::FUTURE_TIMELOCK_TOLERANCE_BLOCKS)
+        let effective_height = (start_tip_height + config::FUTURE_TIMELOCK_TOLERANCE_BLOCKS)
             .expect("Block height overflow");
 
         let connect_result = tx_verifier.connect_transaction(
-            &TransactionSourceForConnect::for_mempool_with_height(&current_best, effective_height),
+            &TransactionSourceForConnect::for_mempool_with_height(
+                start_tip,
+                start_tip_height,
+                effective_height,
+            ),
             transaction.transaction(),
             &BlockTimestamp::from_time(verifier_time),
         );
 
-        let current_tip = chainstate_handle.call(|c| c.get_best_block_id())??;
+        let (_, current_tip) = chainstate_handle.call(|c| c.get_best_block_height_and_id())??;
         if start_tip != current_tip {
             return Ok(TxValidationOutcome::TipMoved {
                 start_tip,
@@ -842,14 +987,18 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
         let result = connect_result
             .and_then(|fee| {
                 let fee = fee
-                    .map_into_block_fees(self.chain_config.as_ref(), current_best.block_height())
+                    .map_into_block_fees(self.chain_config.as_ref(), start_tip_height)
                     .map_err(|e| {
                         let outpt = tx_id.into();
                         ConnectTransactionError::ConstrainedValueAccumulatorError(e, outpt)
                     })?
                     .into();
                 let delta = tx_verifier.consume()?;
-                Ok(TxValidationOutcome::Valid { fee, delta })
+                Ok(TxValidationOutcome::Valid {
+                    fee,
+                    delta,
+                    next_height,
+                })
             })
             .unwrap_or_else(|error| TxValidationOutcome::Rejected { error });
 
@@ -877,7 +1026,7 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
     pub fn get_fee_rate(&self, in_top_x_mb: usize) -> FeeRate {
         let min_feerate = std::cmp::max(
             self.rolling_fee_rate.read().rolling_minimum_fee_rate(),
-            *self.mempool_config.min_tx_relay_fee_rate,
+            self.policy.min_tx_relay_fee_rate(),
         );
         let mut total_size = 0;
         self.store
@@ -897,7 +1046,7 @@ impl<M: MemoryUsageEstimator> TxPool<M> {
     ) -> Result<Vec<(usize, FeeRate)>, MempoolPolicyError> {
         let min_feerate = std::cmp::max(
             self.rolling_fee_rate.read().rolling_minimum_fee_rate(),
-            *self.mempool_config.min_tx_relay_fee_rate,
+            self.policy.min_tx_relay_fee_rate(),
         );
         let min_score = DescendantScore::new(min_feerate);
 
@@ -0,0 +1,152 @@
+// Copyright (c) 2022 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local transaction relay policy, i.e. the rules beyond consensus that decide whether a
+//! transaction is accepted into this node's mempool.
+
+use common::{
+    chain::{
+        config::ChainType, output_value::OutputValue, ChainConfig, SignedTransaction, TxOutput,
+    },
+    primitives::{amount::DisplayAmount, Amount},
+};
+use logging::log;
+use utils::ensure;
+
+use crate::{config::MempoolConfig, error::MempoolPolicyError, pool::feerate::FeeRate};
+
+/// Mempool policy with the configured values resolved against the chain defaults
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolPolicy {
+    min_tx_relay_fee_rate: FeeRate,
+    dust_threshold: Amount,
+    max_tx_size: Option<usize>,
+    max_standard_sigops: usize,
+    max_protected_txs_size: usize,
+    accept_zero_fee_txs: bool,
+}
+
+impl MempoolPolicy {
+    pub fn new(chain_config: &ChainConfig, mempool_config: &MempoolConfig) -> Self {
+        let accept_zero_fee_txs = match (
+            mempool_config.accept_zero_fee_txs,
+            chain_config.chain_type(),
+        ) {
+            (Some(true), ChainType::Regtest) => true,
+            (Some(true), ChainType::Mainnet | ChainType::Testnet | ChainType::Signet) => {
+                log::warn!(
+                    "Accepting zero fee transactions is only allowed on regtest, ignoring the setting"
+                );
+                false
+            }
+            (Some(false) | None, _) => false,
+        };
+
+        // The relay fee rate is also the floor for the fee rate estimates, so it's reset to zero
+        // if there is no floor.
+        let min_tx_relay_fee_rate = if accept_zero_fee_txs {
+            FeeRate::from_amount_per_kb(Amount::ZERO)
+        } else {
+            *mempool_config.min_tx_relay_fee_rate
+        };
+
+        Self {
+            min_tx_relay_fee_rate,
+            dust_threshold: mempool_config
+                .dust_threshold
+                .unwrap_or_else(|| chain_config.mempool_dust_threshold()),
+            max_tx_size: mempool_config.max_tx_size,
+            max_standard_sigops: *mempool_config.max_standard_sigops,
+            max_protected_txs_size: *mempool_config.max_protected_txs_size,
+            accept_zero_fee_txs,
+        }
+    }
+
+    /// Whether the transactions are accepted regardless of the fee they pay.
+    pub fn accept_zero_fee_txs(&self) -> bool {
+        self.accept_zero_fee_txs
+    }
+
+    pub fn min_tx_relay_fee_rate(&self) -> FeeRate {
+        self.min_tx_relay_fee_rate
+    }
+
+    pub fn max_protected_txs_size(&self) -> usize {
+        self.max_protected_txs_size
+    }
+
+    /// Check the transaction against the size, sigops and dust limits
+    pub fn check_transaction(
+        &self,
+        chain_config: &ChainConfig,
+        tx: &SignedTransaction,
+        tx_size: usize,
+    ) -> Result<(), MempoolPolicyError> {
+        // The chain's limit, which depends on the block height, is checked separately
+        if let Some(max_tx_size) = self.max_tx_size {
+            ensure!(
+                tx_size <= max_tx_size,
+                MempoolPolicyError::ExceedsMaxStandardTxSize {
+                    size: tx_size,
+                    max_size: max_tx_size,
+                }
+            );
+        }
+
+        let sigops = tx.tx_size().sigop_cost();
+        ensure!(
+            sigops <= self.max_standard_sigops,
+            MempoolPolicyError::TooManySigops {
+                sigops,
+                max_sigops: self.max_standard_sigops,
+            }
+        );
+
+        for (output_index, output) in tx.transaction().outputs().iter().enumerate() {
+            if let Some(amount) = dust_amount(output, self.dust_threshold) {
+                let decimals = chain_config.coin_decimals();
+                return Err(MempoolPolicyError::DustOutput {
+                    output_index,
+                    amount: DisplayAmount::from_amount_full(amount, decimals),
+                    threshold: DisplayAmount::from_amount_full(self.dust_threshold, decimals),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the coin amount of a spendable output if it is below the dust threshold
+fn dust_amount(output: &TxOutput, dust_threshold: Amount) -> Option<Amount> {
+    match output {
+        TxOutput::Transfer(OutputValue::Coin(amount), _)
+        | TxOutput::LockThenTransfer(OutputValue::Coin(amount), _, _) => {
+            (*amount < dust_threshold).then_some(*amount)
+        }
+        TxOutput::Transfer(OutputValue::TokenV0(_) | OutputValue::TokenV1(_, _), _)
+        | TxOutput::LockThenTransfer(OutputValue::TokenV0(_) | OutputValue::TokenV1(_, _), _, _)
+        | TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_)
+        | TxOutput::Htlc(_, _)
+        | TxOutput::CreateOrder(_) => None,
+    }
+}
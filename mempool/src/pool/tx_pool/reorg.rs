@@ -26,7 +26,7 @@ use logging::log;
 use utils::ensure;
 use utxo::UtxosStorageRead;
 
-use super::{MemoryUsageEstimator, TxAdditionOutcome, TxEntry, TxPool};
+use super::{MemoryUsageEstimator, MempoolRemovalReason, TxAdditionOutcome, TxEntry, TxPool};
 use crate::error::ReorgError;
 
 /// Collect blocks between the given two points
@@ -39,8 +39,7 @@ fn collect_blocks<C: ChainstateInterface + ?Sized>(
     let mut result = Vec::new();
     while curr_id != stop_id {
         let curr_block_id = curr_id
-            .classify(chain_config)
-            .chain_block_id()
+            .to_block_id(chain_config)
             .expect("Reached genesis before the stopping block");
         let block = chainstate
             .get_block(curr_block_id)?
@@ -136,7 +135,7 @@ pub fn handle_new_tip<M: MemoryUsageEstimator>(
 
     let (is_ibd, actual_tip) = tx_pool.blocking_chainstate_handle().call(|cs| {
         let is_ibd = cs.is_initial_block_download();
-        let actual_tip = cs.get_best_block_id()?;
+        let (_, actual_tip) = cs.get_best_block_height_and_id()?;
         Ok::<_, chainstate::ChainstateError>((is_ibd, actual_tip))
     })??;
 
@@ -212,6 +211,12 @@ fn reorg_mempool_transactions<M: MemoryUsageEstimator>(
         if let Err(e) = tx_pool.add_transaction(tx, &mut finalizer) {
             log::debug!("Evicting {tx_id:?} from mempool: {e:?}")
         }
+
+        // The transactions that didn't make it back have been either included in the new blocks
+        // or invalidated by them, unless they've been evicted for another reason already.
+        if !tx_pool.store.contains(&tx_id) {
+            tx_pool.store.record_removal(tx_id, MempoolRemovalReason::Block);
+        }
     }
 
     Ok(())
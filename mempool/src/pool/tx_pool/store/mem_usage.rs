@@ -322,7 +322,8 @@ impl MemoryUsage for TxMempoolEntry {
         let transaction = self.transaction().indirect_memory_usage();
         let parents = self.parents.indirect_memory_usage();
         let children = self.children.indirect_memory_usage();
-        transaction + parents + children
+        let replaced = self.replaced.indirect_memory_usage();
+        transaction + parents + children + replaced
     }
 }
 
@@ -27,10 +27,14 @@ use common::{
     primitives::Id,
 };
 use logging::log;
-use utils::newtype;
+use utils::{ensure, newtype};
 
 use super::{Fee, Time, TxEntry, TxEntryWithFee};
-use crate::{error::MempoolPolicyError, pool::entry::TxDependency, FeeRate};
+use crate::{
+    config::MAX_UNCONFIRMED_ANCESTORS, error::MempoolPolicyError, event::TxRemovalReason,
+    pool::entry::TxDependency, tx_options::TxEvictionPolicy, FeeRate, MempoolTxAncestry,
+    MempoolTxInfo,
+};
 use mem_usage::Tracked;
 
 newtype! {
@@ -122,6 +126,13 @@ pub struct MempoolStore {
     seq_nos_by_tx: Tracked<BTreeMap<Id<Transaction>, usize>>,
     next_seq_no: usize,
 
+    // Total size of the entries that are protected from eviction when the mempool is full.
+    protected_txs_size: usize,
+
+    // The transactions removed from the mempool that haven't been reported yet, in the order
+    // of removal.
+    removed_txs: Vec<(Id<Transaction>, MempoolRemovalReason)>,
+
     /// Memory usage accumulator
     mem_tracker: mem_usage::MemUsageTracker,
 }
@@ -141,6 +152,17 @@ pub enum MempoolRemovalReason {
     Replaced,
 }
 
+impl From<MempoolRemovalReason> for TxRemovalReason {
+    fn from(reason: MempoolRemovalReason) -> Self {
+        match reason {
+            MempoolRemovalReason::Block => TxRemovalReason::Block,
+            MempoolRemovalReason::Expiry => TxRemovalReason::Expired,
+            MempoolRemovalReason::SizeLimit => TxRemovalReason::SizeLimit,
+            MempoolRemovalReason::Replaced => TxRemovalReason::Replaced,
+        }
+    }
+}
+
 impl MempoolStore {
     pub fn new() -> Self {
         Self {
@@ -152,6 +174,8 @@ impl MempoolStore {
             txs_by_seq_no: Tracked::default(),
             seq_nos_by_tx: Tracked::default(),
             next_seq_no: 0,
+            protected_txs_size: 0,
+            removed_txs: Vec::new(),
             mem_tracker: mem_usage::MemUsageTracker::new(),
         }
     }
@@ -172,6 +196,22 @@ impl MempoolStore {
         self.mem_tracker.get_usage()
     }
 
+    /// Total size of the entries that are protected from eviction
+    pub fn protected_txs_size(&self) -> usize {
+        self.protected_txs_size
+    }
+
+    /// Whether evicting the entry would remove a protected transaction, i.e. the entry itself
+    /// or any of its descendants is protected from eviction
+    pub fn is_eviction_protected(&self, id: &Id<Transaction>) -> bool {
+        self.protected_txs_size > 0
+            && self.get_entry(id).is_some_and(|entry| {
+                entry
+                    .depth_postorder_descendants(self)
+                    .any(TxMempoolEntry::is_eviction_protected)
+            })
+    }
+
     pub fn assert_valid(&self) {
         #[cfg(test)]
         self.assert_valid_inner()
@@ -352,9 +392,14 @@ impl MempoolStore {
         self.mem_tracker.modify(&mut self.txs_by_seq_no, |m, _| m.insert(seq_no, tx_id));
         self.mem_tracker.modify(&mut self.seq_nos_by_tx, |m, _| m.insert(tx_id, seq_no));
 
+        if entry.is_eviction_protected() {
+            self.protected_txs_size += entry.size().get();
+        }
+
         let entry = self.mem_tracker.track(entry);
         let prev = self.mem_tracker.modify(&mut self.txs_by_id, |m, _| m.insert(tx_id, entry));
         assert!(prev.is_none(), "Entry already in store");
+        self.update_metrics();
         Ok(())
     }
 
@@ -441,6 +486,11 @@ impl MempoolStore {
                 self.update_descendant_state_for_drop(&entry)
             }
             self.drop_tx(&entry);
+            if entry.is_eviction_protected() {
+                self.protected_txs_size -= entry.size().get();
+            }
+            self.removed_txs.push((*tx_id, reason));
+            self.update_metrics();
             Some(entry)
         } else {
             assert!(!self.txs_by_descendant_score.iter().any(|(_, id)| id == tx_id));
@@ -449,6 +499,25 @@ impl MempoolStore {
         }
     }
 
+    /// Record the removal of a transaction that has left the mempool without going through
+    /// `remove_tx`, e.g. because it has been dropped during a reorg. Does nothing if the removal
+    /// of the transaction has already been recorded.
+    pub fn record_removal(&mut self, tx_id: Id<Transaction>, reason: MempoolRemovalReason) {
+        if !self.removed_txs.iter().any(|(removed_id, _)| *removed_id == tx_id) {
+            self.removed_txs.push((tx_id, reason));
+        }
+    }
+
+    /// Take the transactions removed since the last call, in the order of removal
+    pub fn take_removed_txs(&mut self) -> Vec<(Id<Transaction>, MempoolRemovalReason)> {
+        std::mem::take(&mut self.removed_txs)
+    }
+
+    fn update_metrics(&self) {
+        metrics::node::MEMPOOL_TRANSACTIONS.set(self.txs_by_id.len() as u64);
+        metrics::node::MEMPOOL_MEMORY_USAGE.set(self.memory_usage() as u64);
+    }
+
     fn update_for_drop(&mut self, entry: &TxMempoolEntry) {
         self.remove_from_parents(entry);
         self.remove_from_children(entry);
@@ -491,10 +560,23 @@ impl MempoolStore {
         assert_eq!(tx_id_seq, Some(*tx_id), "Inconsistent transaction seq nos");
     }
 
-    pub fn drop_conflicts(&mut self, conflicts: Conflicts) {
-        for conflict in conflicts.0 {
-            self.remove_tx(&conflict, MempoolRemovalReason::Replaced);
-        }
+    /// Remove the conflicting transactions, returning the ids of the removed ones
+    pub fn drop_conflicts(&mut self, conflicts: Conflicts) -> Vec<Id<Transaction>> {
+        conflicts
+            .0
+            .into_iter()
+            .filter(|conflict| self.remove_tx(conflict, MempoolRemovalReason::Replaced).is_some())
+            .collect()
+    }
+
+    /// Record the transactions the given one has replaced
+    pub fn set_replaced(&mut self, tx_id: &Id<Transaction>, replaced: Vec<Id<Transaction>>) {
+        self.mem_tracker.modify(&mut self.txs_by_id, |txs_by_id, tracker| {
+            tracker.modify(
+                txs_by_id.get_mut(tx_id).expect("set_replaced"),
+                |entry, _| entry.replaced = replaced,
+            );
+        })
     }
 
     // Remove given transaction and its descendants. Return the IDs of the removed transactions
@@ -551,6 +633,7 @@ pub struct TxMempoolEntry {
     fees_with_ancestors: Fee,
     size_with_descendants: NonZeroUsize,
     size_with_ancestors: NonZeroUsize,
+    replaced: Vec<Id<Transaction>>,
 }
 
 impl TxMempoolEntry {
@@ -583,6 +666,7 @@ impl TxMempoolEntry {
             count_with_descendants: 1,
             fees_with_descendants: fee,
             fees_with_ancestors,
+            replaced: Vec::new(),
         })
     }
 
@@ -613,6 +697,11 @@ impl TxMempoolEntry {
         self.count_with_descendants
     }
 
+    /// The transactions this one has evicted from the mempool by replace-by-fee
+    pub fn replaced(&self) -> &[Id<Transaction>] {
+        &self.replaced
+    }
+
     #[cfg(test)]
     pub fn fees_with_descendants(&self) -> Fee {
         self.fees_with_descendants
@@ -623,14 +712,47 @@ impl TxMempoolEntry {
         self.fees_with_ancestors
     }
 
+    /// The fee rate of the transaction together with all its descendants.
+    pub fn package_fee_rate_with_descendants(&self) -> FeeRate {
+        FeeRate::from_total_tx_fee(self.fees_with_descendants, self.size_with_descendants)
+            .expect("cannot overflow due to max supply")
+    }
+
     pub fn descendant_score(&self) -> DescendantScore {
-        let a = FeeRate::from_total_tx_fee(self.fees_with_descendants, self.size_with_descendants)
-            .expect("cannot overflow due to max supply");
+        let a = self.package_fee_rate_with_descendants();
         let b = FeeRate::from_total_tx_fee(self.fee, self.size())
             .expect("cannot overflow due to max supply");
         std::cmp::max(a, b).into()
     }
 
+    pub fn info(&self, store: &MempoolStore) -> MempoolTxInfo {
+        MempoolTxInfo {
+            fee: *self.fee,
+            size: self.size().get(),
+            fee_rate: FeeRate::from_total_tx_fee(self.fee, self.size())
+                .expect("cannot overflow due to max supply"),
+            package_fee_rate: FeeRate::from_total_tx_fee(
+                self.fees_with_ancestors,
+                self.size_with_ancestors,
+            )
+            .expect("cannot overflow due to max supply"),
+            count_with_ancestors: self.count_with_ancestors,
+            replaced: self.replaced.clone(),
+            rbf_signalled: self.is_replaceable(store),
+        }
+    }
+
+    pub fn ancestry(&self, store: &MempoolStore) -> MempoolTxAncestry {
+        MempoolTxAncestry {
+            ancestors: self.unconfirmed_ancestors(store).0.into_iter().collect(),
+            descendants: self.unconfirmed_descendants(store).0.into_iter().collect(),
+            fee_with_ancestors: *self.fees_with_ancestors,
+            size_with_ancestors: self.size_with_ancestors.get(),
+            fee_with_descendants: *self.fees_with_descendants,
+            size_with_descendants: self.size_with_descendants.get(),
+        }
+    }
+
     pub fn ancestor_score(&self) -> AncestorScore {
         log::debug!("ancestor score for {:?}", self.tx_id());
         log::debug!(
@@ -664,6 +786,10 @@ impl TxMempoolEntry {
         self.entry.creation_time()
     }
 
+    pub fn is_eviction_protected(&self) -> bool {
+        self.entry.options().eviction_policy() == TxEvictionPolicy::Protected
+    }
+
     // Note: only the parents that are currently in the mempool are included here.
     pub fn parents(&self) -> impl Iterator<Item = &Id<Transaction>> {
         self.parents.iter()
@@ -689,33 +815,41 @@ impl TxMempoolEntry {
     }
 
     pub fn unconfirmed_ancestors(&self, store: &MempoolStore) -> Ancestors {
-        let mut visited = Ancestors(BTreeSet::new());
-        self.unconfirmed_ancestors_inner(&mut visited, store);
-        visited
+        // The limit is only enforced when a transaction enters the mempool; the transactions
+        // that are already there may end up with more ancestors, e.g. after a reorg.
+        Self::collect_unconfirmed_ancestors(self.parents.iter(), store, usize::MAX)
+            .expect("ancestors of a mempool entry are in the mempool")
     }
 
     pub fn unconfirmed_ancestors_from_parents(
         parents: &BTreeSet<Id<Transaction>>,
         store: &MempoolStore,
     ) -> Result<Ancestors, MempoolPolicyError> {
-        let mut ancestors = parents.clone().into();
-        for parent in parents {
-            let parent = store.get_entry(parent).ok_or(MempoolPolicyError::GetParentError)?;
-            parent.unconfirmed_ancestors_inner(&mut ancestors, store);
-        }
-        Ok(ancestors)
+        Self::collect_unconfirmed_ancestors(parents, store, MAX_UNCONFIRMED_ANCESTORS)
     }
 
-    fn unconfirmed_ancestors_inner(&self, visited: &mut Ancestors, store: &MempoolStore) {
-        // TODO: change this from recursive to iterative
-        for parent in self.parents.iter() {
-            if visited.insert(*parent) {
-                store
-                    .get_entry(parent)
-                    .expect("entry")
-                    .unconfirmed_ancestors_inner(visited, store);
+    fn collect_unconfirmed_ancestors<'a>(
+        parents: impl IntoIterator<Item = &'a Id<Transaction>>,
+        store: &MempoolStore,
+        max_count: usize,
+    ) -> Result<Ancestors, MempoolPolicyError> {
+        let mut ancestors = Ancestors(BTreeSet::new());
+        let mut to_visit: Vec<Id<Transaction>> = parents.into_iter().copied().collect();
+
+        while let Some(tx_id) = to_visit.pop() {
+            if !ancestors.insert(tx_id) {
+                continue;
             }
+            ensure!(
+                ancestors.len() <= max_count,
+                MempoolPolicyError::TooManyUnconfirmedAncestors { max: max_count }
+            );
+
+            let entry = store.get_entry(&tx_id).ok_or(MempoolPolicyError::GetParentError)?;
+            to_visit.extend(entry.parents.iter().filter(|id| !ancestors.contains(id)));
         }
+
+        Ok(ancestors)
     }
 
     pub fn depth_postorder_descendants<'a>(
@@ -729,20 +863,17 @@ impl TxMempoolEntry {
     }
 
     pub fn unconfirmed_descendants(&self, store: &MempoolStore) -> Descendants {
-        let mut visited = Descendants(BTreeSet::new());
-        self.unconfirmed_descendants_inner(&mut visited, store);
-        visited
-    }
-
-    fn unconfirmed_descendants_inner(&self, visited: &mut Descendants, store: &MempoolStore) {
-        for child in self.children.iter() {
-            if visited.insert(*child) {
-                store
-                    .get_entry(child)
-                    .expect("entry")
-                    .unconfirmed_descendants_inner(visited, store);
+        let mut descendants = Descendants(BTreeSet::new());
+        let mut to_visit: Vec<Id<Transaction>> = self.children.iter().copied().collect();
+
+        while let Some(tx_id) = to_visit.pop() {
+            if descendants.insert(tx_id) {
+                let entry = store.get_entry(&tx_id).expect("entry");
+                to_visit.extend(entry.children.iter().filter(|id| !descendants.contains(id)));
             }
         }
+
+        descendants
     }
 }
 
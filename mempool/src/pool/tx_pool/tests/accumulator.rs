@@ -36,7 +36,7 @@ use crypto::key::{KeyKind, PrivateKey};
 use serialization::Compact;
 
 use super::*;
-use crate::tx_accumulator::DefaultTxAccumulator;
+use crate::tx_accumulator::{DefaultTxAccumulator, TxTemplatePolicy};
 
 // Useful for testing cases where timestamp is irrelevant.
 const DUMMY_TIMESTAMP: BlockTimestamp = BlockTimestamp::from_int_seconds(0u64);
@@ -193,6 +193,73 @@ async fn transaction_graph_respects_deps(#[case] seed: Seed) {
     }
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn template_policy(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis_id = tf.genesis().get_id();
+
+    let tx0 = make_tx(
+        &mut rng,
+        &[(genesis_id.into(), 0)],
+        &[400_000_000_000, 400_000_000_000],
+    );
+    let tx0_id = tx0.transaction().get_id();
+    let tx1 = make_tx(&mut rng, &[(tx0_id.into(), 0)], &[300_000_000_000]);
+    let tx1_id = tx1.transaction().get_id();
+    let tx2 = make_tx(&mut rng, &[(tx0_id.into(), 1)], &[300_000_000_000]);
+    let tx2_id = tx2.transaction().get_id();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    for tx in [tx0, tx1, tx2] {
+        assert_eq!(mempool.add_transaction_test(tx), Ok(TxStatus::InMempool));
+    }
+
+    let collect = |policy: TxTemplatePolicy, tx_ids: Vec<Id<Transaction>>| {
+        let accumulator = Box::new(
+            DefaultTxAccumulator::new(1_000_000, genesis_id.into(), DUMMY_TIMESTAMP)
+                .with_template_policy(policy),
+        );
+        let tx_ids: BTreeSet<_> = mempool
+            .collect_txs(accumulator, tx_ids, PackingStrategy::FillSpaceFromMempool)
+            .unwrap()
+            .unwrap()
+            .transactions()
+            .iter()
+            .map(|tx| tx.transaction().get_id())
+            .collect();
+        tx_ids
+    };
+
+    let all_txs = BTreeSet::from([tx0_id, tx1_id, tx2_id]);
+    assert_eq!(collect(TxTemplatePolicy::default(), vec![]), all_txs);
+
+    // Remote transactions below the fee rate floor are left out, unless requested explicitly
+    let fee_rate_floor = TxTemplatePolicy {
+        min_fee_rate: Some(FeeRate::from_amount_per_kb(Amount::MAX)),
+        ..TxTemplatePolicy::default()
+    };
+    assert_eq!(collect(fee_rate_floor, vec![]), BTreeSet::new());
+    assert_eq!(
+        collect(fee_rate_floor, vec![tx1_id]),
+        BTreeSet::from([tx0_id, tx1_id])
+    );
+
+    // All the space is reserved for priority transactions
+    let reserved_space = TxTemplatePolicy {
+        non_priority_size_limit: 0,
+        ..TxTemplatePolicy::default()
+    };
+    assert_eq!(collect(reserved_space, vec![]), BTreeSet::new());
+    assert_eq!(
+        collect(reserved_space, vec![tx2_id]),
+        BTreeSet::from([tx0_id, tx2_id])
+    );
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -125,6 +125,87 @@ async fn add_tx_with_fee_rate_below_minimum() {
     assert_eq!(tx_status, TxStatus::InMempool);
 }
 
+#[rstest]
+#[case(Some(true), true)]
+#[case(Some(false), false)]
+#[case(None, false)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn zero_fee_txs(#[case] accept_zero_fee_txs: Option<bool>, #[case] accepted: bool) {
+    let mut mempool = setup_with_mempool_config(MempoolConfig {
+        min_tx_relay_fee_rate: TEST_MIN_TX_RELAY_FEE_RATE.into(),
+        accept_zero_fee_txs,
+        ..MempoolConfig::new()
+    });
+    let genesis_id = mempool.chain_config.genesis_block_id();
+
+    let tx = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(genesis_id.into(), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        Fee::new(Amount::ZERO),
+        0,
+    )
+    .await
+    .unwrap();
+    let tx_id = tx.transaction().get_id();
+
+    let result = mempool.add_transaction_test(tx);
+    if !accepted {
+        assert!(matches!(
+            result,
+            Err(Error::Policy(
+                MempoolPolicyError::InsufficientFeesToRelay { .. }
+            ))
+        ));
+        return;
+    }
+    assert_eq!(result.unwrap(), TxStatus::InMempool);
+
+    // The transaction is included into block templates
+    let accumulator = Box::new(crate::tx_accumulator::DefaultTxAccumulator::new(
+        1_000_000,
+        genesis_id,
+        BlockTimestamp::from_int_seconds(0),
+    ));
+    let collected_tx_ids: Vec<_> = mempool
+        .collect_txs(accumulator, vec![], PackingStrategy::FillSpaceFromMempool)
+        .unwrap()
+        .unwrap()
+        .transactions()
+        .iter()
+        .map(|tx| tx.transaction().get_id())
+        .collect();
+    assert_eq!(collected_tx_ids, vec![tx_id]);
+}
+
+#[test]
+fn zero_fee_txs_only_on_regtest() {
+    let mempool_config = MempoolConfig {
+        accept_zero_fee_txs: Some(true),
+        ..MempoolConfig::new()
+    };
+
+    let regtest_config = common::chain::config::create_unit_test_config();
+    assert_eq!(
+        regtest_config.chain_type(),
+        &common::chain::config::ChainType::Regtest
+    );
+    let policy = MempoolPolicy::new(&regtest_config, &mempool_config);
+    assert!(policy.accept_zero_fee_txs());
+    assert_eq!(
+        policy.min_tx_relay_fee_rate(),
+        FeeRate::from_amount_per_kb(Amount::ZERO)
+    );
+
+    let testnet_config = common::chain::config::Builder::test_chain().build();
+    let policy = MempoolPolicy::new(&testnet_config, &mempool_config);
+    assert!(!policy.accept_zero_fee_txs());
+    assert_eq!(
+        policy.min_tx_relay_fee_rate(),
+        *mempool_config.min_tx_relay_fee_rate
+    );
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -211,6 +292,106 @@ async fn tx_no_outputs(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tx_unknown_flags(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+    let flags = rng.gen_range(2..=u128::MAX);
+    let tx = TransactionBuilder::new()
+        .with_flags(flags)
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(100)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    assert_eq!(
+        mempool.add_transaction_test(tx),
+        Err(MempoolPolicyError::UnknownTransactionFlags(flags).into())
+    );
+    mempool.store.assert_valid();
+    Ok(())
+}
+
+// The transaction is valid at the height used for the timelock checks, but the feature it uses
+// is activated only after the next block, so the transaction must be rejected for now.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tx_feature_not_activated_at_next_height(#[case] seed: Seed) {
+    use chainstate::tx_verifier::CheckTransactionError;
+    use common::chain::{
+        config,
+        htlc::{HashedTimelockContract, HtlcSecretHash},
+        timelock::OutputTimeLock,
+        ChainstateUpgradeBuilder, HtlcActivated, NetUpgrades,
+    };
+
+    let mut rng = make_seedable_rng(seed);
+    let tf = {
+        // Activate htlc at height 2, i.e. after the next block
+        let chainstate_upgrades = NetUpgrades::initialize(vec![
+            (
+                BlockHeight::zero(),
+                ChainstateUpgradeBuilder::latest().htlc_activated(HtlcActivated::No).build(),
+            ),
+            (
+                BlockHeight::new(2),
+                ChainstateUpgradeBuilder::latest().htlc_activated(HtlcActivated::Yes).build(),
+            ),
+        ])
+        .unwrap();
+        let chain_config = config::Builder::test_chain()
+            .chainstate_upgrades(chainstate_upgrades)
+            .genesis_unittest(Destination::AnyoneCanSpend)
+            .build();
+        TestFramework::builder(&mut rng).with_chain_config(chain_config).build()
+    };
+    assert!(
+        BlockHeight::new(2) <= (BlockHeight::zero() + FUTURE_TIMELOCK_TOLERANCE_BLOCKS).unwrap()
+    );
+
+    let htlc = HashedTimelockContract {
+        secret_hash: HtlcSecretHash::zero(),
+        spend_key: Destination::AnyoneCanSpend,
+        refund_timelock: OutputTimeLock::ForSeconds(200),
+        refund_key: Destination::AnyoneCanSpend,
+    };
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(
+                OutPointSourceId::BlockReward(tf.genesis().get_id().into()),
+                0,
+            ),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Htlc(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1_000_000))),
+            Box::new(htlc),
+        ))
+        .build();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    assert_eq!(
+        mempool.add_transaction_test(tx),
+        Err(MempoolPolicyError::FeatureNotActivatedAtNextHeight {
+            height: BlockHeight::new(1),
+            error: CheckTransactionError::HtlcsAreNotActivated,
+        }
+        .into())
+    );
+    mempool.store.assert_valid();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn tx_duplicate_inputs() -> anyhow::Result<()> {
     let mut mempool = setup();
@@ -336,7 +517,8 @@ async fn tx_too_big(#[case] seed: Seed) -> anyhow::Result<()> {
     )
     .encoded_size();
     let too_many_outputs =
-        tf.chainstate.get_chain_config().max_tx_size_for_mempool() / single_output_size;
+        tf.chainstate.get_chain_config().max_tx_size_for_mempool(BlockHeight::one())
+            / single_output_size;
     let mut tx_builder = TransactionBuilder::new().add_input(
         TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
         empty_witness(&mut rng),
@@ -867,6 +1049,151 @@ async fn rolling_fee(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+// A low-fee parent with a high-fee child vs a standalone tx. Trimming evicts whichever package
+// (a tx together with its descendants) has the lowest fee rate, so a parent is kept if its child
+// pays for it, while evicting only by the own fee rate of each tx would always evict the parent.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy(), 10, 2, false)]
+#[case(Seed::from_entropy(), 3, 4, true)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn trim_evicts_lowest_package(
+    #[case] seed: Seed,
+    #[case] child_fee_multiplier: u128,
+    #[case] standalone_fee_multiplier: u128,
+    #[case] package_evicted: bool,
+) -> anyhow::Result<()> {
+    let mut mock_usage = MockMemoryUsageEstimator::new();
+    // Add root, parent, child and standalone
+    mock_usage.expect_estimate_memory_usage().times(4).return_const(0usize);
+    // Add the high-fee tx, triggering the trimming process
+    mock_usage
+        .expect_estimate_memory_usage()
+        .times(1)
+        .return_const(MAX_MEMPOOL_SIZE_BYTES + 1);
+    // After removing one package, cause the code to exit the loop by showing a small usage
+    mock_usage.expect_estimate_memory_usage().return_const(0usize);
+
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+    let mut tx_builder = TransactionBuilder::new().add_input(
+        TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+        empty_witness(&mut rng),
+    );
+    for _ in 0..3 {
+        tx_builder = tx_builder.add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(999_999_999_000)),
+            anyonecanspend_address(),
+        ));
+    }
+    let root = tx_builder.build();
+    let root_id = root.transaction().get_id();
+
+    let chainstate = tf.chainstate();
+    let chain_config = Arc::clone(chainstate.get_chain_config());
+    let mut tx_pool = TxPool::new(
+        chain_config,
+        create_mempool_config(),
+        start_chainstate(chainstate),
+        Default::default(),
+        mock_usage,
+    );
+    tx_pool.add_transaction_test(root)?.assert_in_mempool();
+
+    let relay_fee = get_relay_fee_from_tx_size(estimate_tx_size(1, 2));
+    let fee_times = |multiplier: u128| -> Fee { (relay_fee * multiplier).unwrap().into() };
+    let witness = || InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec()));
+    let flags = 0;
+
+    let parent_fee = fee_times(1);
+    let parent = tx_spend_input(
+        &tx_pool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(root_id), 0),
+        witness(),
+        parent_fee,
+        flags,
+    )
+    .await?;
+    let parent_id = parent.transaction().get_id();
+    tx_pool.add_transaction_test(parent.clone())?.assert_in_mempool();
+
+    let child_fee = fee_times(child_fee_multiplier);
+    let child = tx_spend_input(
+        &tx_pool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(parent_id), 0),
+        witness(),
+        child_fee,
+        flags,
+    )
+    .await?;
+    let child_id = child.transaction().get_id();
+    tx_pool.add_transaction_test(child.clone())?.assert_in_mempool();
+
+    let standalone_fee = fee_times(standalone_fee_multiplier);
+    let standalone = tx_spend_input(
+        &tx_pool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(root_id), 1),
+        witness(),
+        standalone_fee,
+        flags,
+    )
+    .await?;
+    let standalone_id = standalone.transaction().get_id();
+    tx_pool.add_transaction_test(standalone.clone())?.assert_in_mempool();
+
+    let parent_rate = FeeRate::from_total_tx_fee(
+        parent_fee,
+        NonZeroUsize::new(parent.encoded_size()).unwrap(),
+    )?;
+    let package_rate = FeeRate::from_total_tx_fee(
+        (parent_fee + child_fee).unwrap(),
+        NonZeroUsize::new(parent.encoded_size() + child.encoded_size()).unwrap(),
+    )?;
+    let standalone_rate = FeeRate::from_total_tx_fee(
+        standalone_fee,
+        NonZeroUsize::new(standalone.encoded_size()).unwrap(),
+    )?;
+    // The parent alone always has the lowest fee rate
+    assert!(parent_rate < standalone_rate);
+    assert_eq!(package_evicted, package_rate < standalone_rate);
+
+    let trigger = tx_spend_input(
+        &tx_pool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(root_id), 2),
+        witness(),
+        fee_times(20),
+        flags,
+    )
+    .await?;
+    let trigger_id = trigger.transaction().get_id();
+    tx_pool.add_transaction_test(trigger)?.assert_in_mempool();
+
+    assert!(tx_pool.contains_transaction(&root_id));
+    assert!(tx_pool.contains_transaction(&trigger_id));
+    assert_eq!(tx_pool.contains_transaction(&parent_id), !package_evicted);
+    assert_eq!(tx_pool.contains_transaction(&child_id), !package_evicted);
+    assert_eq!(
+        tx_pool.contains_transaction(&standalone_id),
+        package_evicted
+    );
+
+    let rolling_fee = tx_pool.get_minimum_rolling_fee();
+    let evicted_rate = if package_evicted {
+        package_rate
+    } else {
+        standalone_rate
+    };
+    assert_eq!(
+        rolling_fee,
+        (evicted_rate + INCREMENTAL_RELAY_FEE_RATE).unwrap()
+    );
+    assert!(rolling_fee > (parent_rate + INCREMENTAL_RELAY_FEE_RATE).unwrap());
+
+    tx_pool.store.assert_valid();
+    Ok(())
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1075,6 +1402,100 @@ async fn ancestor_score(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn transaction_ancestry(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(10_000)),
+            Destination::AnyoneCanSpend,
+        ))
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(10_000)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_id = tx.transaction().get_id();
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    mempool.add_transaction_test(tx)?.assert_in_mempool();
+
+    let fee: Fee = get_relay_fee_from_tx_size(estimate_tx_size(1, 2)).into();
+    let tx_a = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(tx_id), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        fee,
+        0,
+    )
+    .await?;
+    let tx_a_id = tx_a.transaction().get_id();
+    mempool.add_transaction_test(tx_a)?.assert_in_mempool();
+
+    let tx_b = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(tx_a_id), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        fee,
+        0,
+    )
+    .await?;
+    let tx_b_id = tx_b.transaction().get_id();
+    mempool.add_transaction_test(tx_b)?.assert_in_mempool();
+
+    let entry_tx = mempool.store.get_entry(&tx_id).expect("tx");
+    let entry_a = mempool.store.get_entry(&tx_a_id).expect("tx_a");
+    let entry_b = mempool.store.get_entry(&tx_b_id).expect("tx_b");
+
+    let ancestry = mempool.transaction_ancestry(&tx_a_id).expect("tx_a ancestry");
+    assert_eq!(ancestry.ancestors, vec![tx_id]);
+    assert_eq!(ancestry.descendants, vec![tx_b_id]);
+    assert_eq!(
+        ancestry.fee_with_ancestors,
+        *(entry_a.fee() + entry_tx.fee()).unwrap()
+    );
+    assert_eq!(
+        ancestry.size_with_ancestors,
+        entry_a.size().get() + entry_tx.size().get()
+    );
+    assert_eq!(
+        ancestry.fee_with_descendants,
+        *(entry_a.fee() + entry_b.fee()).unwrap()
+    );
+    assert_eq!(
+        ancestry.size_with_descendants,
+        entry_a.size().get() + entry_b.size().get()
+    );
+
+    let ancestry = mempool.transaction_ancestry(&tx_id).expect("tx ancestry");
+    assert!(ancestry.ancestors.is_empty());
+    assert_eq!(
+        ancestry.descendants.into_iter().collect::<BTreeSet<_>>(),
+        BTreeSet::from([tx_a_id, tx_b_id])
+    );
+
+    let ancestry = mempool.transaction_ancestry(&tx_b_id).expect("tx_b ancestry");
+    assert_eq!(
+        ancestry.ancestors.into_iter().collect::<BTreeSet<_>>(),
+        BTreeSet::from([tx_id, tx_a_id])
+    );
+    assert!(ancestry.descendants.is_empty());
+
+    mempool.store.assert_valid();
+
+    Ok(())
+}
+
 fn check_txs_sorted_by_ancestor_score<E>(tx_pool: &TxPool<E>) {
     let txs_by_ancestor_score = tx_pool
         .store
@@ -1261,6 +1682,106 @@ async fn mempool_full_mock(#[case] seed: Seed) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mempool_full_protected_tx(#[case] seed: Seed) -> anyhow::Result<()> {
+    use crate::tx_options::{TxEvictionPolicy, TxOptionsOverrides};
+
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+
+    let mut mock_usage = MockMemoryUsageEstimator::new();
+    mock_usage
+        .expect_estimate_memory_usage()
+        .times(1)
+        .return_const(MAX_MEMPOOL_SIZE_BYTES + 1);
+
+    let chainstate = tf.chainstate();
+    let chain_config = Arc::clone(chainstate.get_chain_config());
+    let chainstate_handle = start_chainstate(chainstate);
+
+    let mut tx_pool = TxPool::new(
+        chain_config,
+        create_mempool_config(),
+        chainstate_handle,
+        Default::default(),
+        mock_usage,
+    );
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(100)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_id = tx.transaction().get_id();
+
+    let origin = TxOrigin::Remote(RemoteTxOrigin::new(p2p_types::PeerId::from_u64(1)));
+    let options = TxOptions::default_for(origin).with_overrides(
+        TxOptionsOverrides::default().with_eviction_policy(TxEvictionPolicy::Protected),
+    );
+    let entry = TxEntry::new(tx, tx_pool.clock.get_time(), origin, options);
+
+    // The pool is over its size limit but the only candidate for eviction is protected
+    let res = tx_pool.add_transaction_bare(entry);
+    assert_eq!(res, Ok(TxStatus::InMempool));
+    assert!(tx_pool.contains_transaction(&tx_id));
+    assert!(tx_pool.store.is_eviction_protected(&tx_id));
+    tx_pool.store.assert_valid();
+    Ok(())
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn protected_txs_size_exceeded(#[case] seed: Seed) {
+    use crate::tx_options::{TxEvictionPolicy, TxOptionsOverrides};
+
+    let mut rng = make_seedable_rng(seed);
+    let mut mempool = setup_with_mempool_config(MempoolConfig {
+        max_protected_txs_size: 10.into(),
+        ..MempoolConfig::new()
+    });
+    let genesis_id = mempool.chain_config.genesis_block_id();
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis_id), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(100)),
+            anyonecanspend_address(),
+        ))
+        .build();
+
+    let origin = TxOrigin::Remote(RemoteTxOrigin::new(p2p_types::PeerId::from_u64(1)));
+    let options = TxOptions::default_for(origin).with_overrides(
+        TxOptionsOverrides::default().with_eviction_policy(TxEvictionPolicy::Protected),
+    );
+    let entry = TxEntry::new(tx.clone(), mempool.clock.get_time(), origin, options);
+
+    let err = mempool.add_transaction_bare(entry).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Policy(MempoolPolicyError::ProtectedTxsSizeExceeded {
+            size: _,
+            max_size: 10,
+        })
+    ));
+
+    // The same transaction is accepted without the protection
+    assert_eq!(mempool.add_transaction_test(tx), Ok(TxStatus::InMempool));
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1332,6 +1853,59 @@ async fn mempool_full_real(#[case] seed: Seed) {
     assert_eq!(mempool.store.txs_by_id.len(), num_txs, "Some txs missing");
 }
 
+// A long chain of transactions is walked without recursion, and a transaction with too many
+// ancestors is rejected.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn unconfirmed_ancestors_limit(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let time = TimeGetter::default().get_time();
+    let origin: TxOrigin = RemoteTxOrigin::new(p2p_types::PeerId::from_u64(1)).into();
+
+    let mut store = MempoolStore::new();
+    let mut input = TxInput::from_utxo(tf.genesis().get_id().into(), 0);
+    let mut tx_ids = Vec::new();
+    for _ in 0..=MAX_UNCONFIRMED_ANCESTORS + 1 {
+        let tx = TransactionBuilder::new()
+            .add_input(input, empty_witness(&mut rng))
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(Amount::from_atoms(1000)),
+                Destination::AnyoneCanSpend,
+            ))
+            .build();
+        let tx_id = tx.transaction().get_id();
+        input = TxInput::from_utxo(tx_id.into(), 0);
+        let entry = TxEntry::new(tx, time, origin, TxOptions::default_for(origin));
+
+        let result = store.add_transaction(TxEntryWithFee::new(entry, Fee::new(Amount::ZERO)));
+        if tx_ids.len() <= MAX_UNCONFIRMED_ANCESTORS {
+            assert_eq!(result, Ok(()));
+            tx_ids.push(tx_id);
+        } else {
+            assert_eq!(
+                result,
+                Err(MempoolPolicyError::TooManyUnconfirmedAncestors {
+                    max: MAX_UNCONFIRMED_ANCESTORS
+                })
+            );
+        }
+    }
+
+    let first = store.get_entry(&tx_ids[0]).unwrap();
+    assert_eq!(
+        first.unconfirmed_descendants(&store).len(),
+        MAX_UNCONFIRMED_ANCESTORS
+    );
+    let last = store.get_entry(tx_ids.last().unwrap()).unwrap();
+    assert_eq!(
+        last.unconfirmed_ancestors(&store).len(),
+        MAX_UNCONFIRMED_ANCESTORS
+    );
+    store.assert_valid();
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -1443,7 +2017,7 @@ async fn accepted_tx_size(#[case] seed: Seed, #[case] tx_size: usize, #[case] ac
         transaction
     };
 
-    let max_tx_size = tf.chain_config().max_tx_size_for_mempool();
+    let max_tx_size = tf.chain_config().max_tx_size_for_mempool(BlockHeight::one());
     let mut mempool = setup_with_chainstate(tf.chainstate());
     let result = mempool.add_transaction_test(transaction);
 
@@ -1457,3 +2031,190 @@ async fn accepted_tx_size(#[case] seed: Seed, #[case] tx_size: usize, #[case] ac
         "tx_size: {tx_size}, max tx size: {max_tx_size}"
     );
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn dust_output_rejected(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let dust_threshold = Amount::from_atoms(rng.gen_range(2..1_000_000));
+    let mut mempool = setup_with_mempool_config(MempoolConfig {
+        min_tx_relay_fee_rate: FeeRate::from_amount_per_kb(Amount::ZERO).into(),
+        dust_threshold: Some(dust_threshold),
+        ..MempoolConfig::new()
+    });
+    let genesis_id = mempool.chain_config.genesis_block_id();
+    let witness = empty_witness(&mut rng);
+
+    let make_tx = |amount| {
+        TransactionBuilder::new()
+            .add_input(
+                TxInput::from_utxo(OutPointSourceId::BlockReward(genesis_id), 0),
+                witness.clone(),
+            )
+            .add_output(TxOutput::Transfer(
+                OutputValue::Coin(amount),
+                anyonecanspend_address(),
+            ))
+            .build()
+    };
+
+    let below_threshold = (dust_threshold - Amount::from_atoms(1)).unwrap();
+    let err = mempool.add_transaction_test(make_tx(below_threshold)).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Policy(MempoolPolicyError::DustOutput {
+            output_index: 0,
+            amount: _,
+            threshold: _,
+        })
+    ));
+
+    let tx_status = mempool.add_transaction_test(make_tx(dust_threshold)).unwrap();
+    assert_eq!(tx_status, TxStatus::InMempool);
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn non_standard_tx_rejected(#[case] seed: Seed) {
+    use common::chain::signature::{
+        inputsig::standard_signature::StandardInputSignature, sighash::sighashtype::SigHashType,
+    };
+
+    let mut rng = make_seedable_rng(seed);
+    let genesis_id = common::chain::config::create_unit_test_config().genesis_block_id();
+
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis_id), 0),
+            InputWitness::Standard(StandardInputSignature::new(SigHashType::all(), Vec::new())),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen_range(1..1_000_000))),
+            anyonecanspend_address(),
+        ))
+        .build();
+    let tx_size = tx.encoded_size();
+
+    let mut mempool = setup_with_mempool_config(MempoolConfig {
+        max_tx_size: Some(tx_size - 1),
+        ..MempoolConfig::new()
+    });
+    let err = mempool.add_transaction_test(tx.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Policy(MempoolPolicyError::ExceedsMaxStandardTxSize {
+            size: tx_size,
+            max_size: tx_size - 1,
+        })
+    );
+
+    let mut mempool = setup_with_mempool_config(MempoolConfig {
+        max_standard_sigops: MaxStandardSigops::new(0),
+        ..MempoolConfig::new()
+    });
+    let err = mempool.add_transaction_test(tx).unwrap_err();
+    assert_eq!(
+        err,
+        Error::Policy(MempoolPolicyError::TooManySigops {
+            sigops: 1,
+            max_sigops: 0,
+        })
+    );
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn local_transactions_to_rebroadcast(#[case] seed: Seed) -> anyhow::Result<()> {
+    use crate::tx_origin::LocalTxOrigin;
+
+    let mock_time = Arc::new(SeqCstAtomicU64::new(0));
+    let mock_clock = mocked_time_getter_seconds(Arc::clone(&mock_time));
+
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+    let chainstate = tf.chainstate();
+    let mut mempool = TxPool::new(
+        Arc::clone(chainstate.get_chain_config()),
+        create_mempool_config(),
+        start_chainstate(chainstate),
+        mock_clock,
+        StoreMemoryUsageEstimator,
+    );
+
+    let parent = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(1_000)),
+            Destination::AnyoneCanSpend,
+        ))
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(1_000)),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let parent_id = parent.transaction().get_id();
+    let parent_wtxid = parent.wtxid();
+
+    let origin: TxOrigin = LocalTxOrigin::P2p.into();
+    let entry = TxEntry::new(
+        parent,
+        mempool.clock.get_time(),
+        origin,
+        TxOptions::default_for(origin),
+    );
+    mempool.add_transaction_bare(entry)?.assert_in_mempool();
+
+    // Transactions received from peers are not rebroadcast
+    let outpoint_source_id = OutPointSourceId::Transaction(parent_id);
+    let remote_child = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(outpoint_source_id.clone(), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        None,
+        0,
+    )
+    .await?;
+    mempool.add_transaction_test(remote_child)?.assert_in_mempool();
+
+    // Neither are the local transactions that should not be relayed
+    let local_child = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(outpoint_source_id, 1),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        None,
+        0,
+    )
+    .await?;
+    let origin: TxOrigin = LocalTxOrigin::Mempool.into();
+    let entry = TxEntry::new(
+        local_child,
+        mempool.clock.get_time(),
+        origin,
+        TxOptions::default_for(origin),
+    );
+    mempool.add_transaction_bare(entry)?.assert_in_mempool();
+
+    let min_age = Duration::from_secs(100);
+    assert_eq!(
+        mempool.local_transactions_to_rebroadcast(min_age),
+        Vec::new()
+    );
+
+    mock_time.store(min_age.as_secs());
+    assert_eq!(
+        mempool.local_transactions_to_rebroadcast(min_age),
+        vec![(parent_id, parent_wtxid)]
+    );
+    mempool.store.assert_valid();
+    Ok(())
+}
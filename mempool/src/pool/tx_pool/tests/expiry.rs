@@ -16,6 +16,7 @@
 use common::chain::output_value::OutputValue;
 
 use super::*;
+use crate::event::{TransactionRemoved, TxRemovalReason};
 use ::utils::atomics::SeqCstAtomicU64;
 
 #[rstest]
@@ -77,6 +78,13 @@ async fn descendant_of_expired_entry(#[case] seed: Seed) -> anyhow::Result<()> {
 
     assert!(!mempool.contains_transaction(&parent_id));
     assert!(!mempool.contains_transaction(&child_id));
+    assert_eq!(
+        mempool.take_removed_txs(),
+        vec![
+            TransactionRemoved::new(child_id, TxRemovalReason::Expired),
+            TransactionRemoved::new(parent_id, TxRemovalReason::Expired),
+        ]
+    );
     mempool.store.assert_valid();
     Ok(())
 }
@@ -39,7 +39,11 @@ use common::{
 };
 use serialization::Encode;
 
-use std::{collections::BTreeMap, ops::Deref, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Deref,
+    sync::Arc,
+};
 
 mod accumulator;
 mod basic;
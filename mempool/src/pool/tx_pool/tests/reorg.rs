@@ -26,7 +26,15 @@ async fn basic_reorg(#[case] seed: Seed) {
     let mut rng = make_seedable_rng(seed);
     let tf = TestFramework::builder(&mut rng).build();
     let genesis = tf.genesis();
-    let mut tx_pool = setup_with_chainstate(tf.chainstate());
+    let mock_time = Arc::new(SeqCstAtomicU64::new(0));
+    let chainstate = tf.chainstate();
+    let mut tx_pool = TxPool::new(
+        Arc::clone(chainstate.get_chain_config()),
+        create_mempool_config(),
+        start_chainstate(chainstate),
+        mocked_time_getter_seconds(Arc::clone(&mock_time)),
+        StoreMemoryUsageEstimator,
+    );
     let chainstate = tx_pool.chainstate_handle().shallow_clone();
 
     // Add the first transaction
@@ -63,8 +71,7 @@ async fn basic_reorg(#[case] seed: Seed) {
 
     // Make sure adding a block does not reset mempool entry timestamp.
     let tx2_time = tx_pool.store.get_entry(&tx2_id).unwrap().creation_time();
-    // TODO: Use proper time mocking here instead of sleep.
-    tokio::time::sleep(Duration::from_millis(1100)).await;
+    mock_time.fetch_add(2);
 
     // Submit a block with tx1 and check the corresponding tx has been removed from mempool
     let block1 = make_test_block(vec![tx1], genesis.get_id(), DUMMY_TIME);
@@ -240,3 +247,59 @@ async fn reject_txs_during_ibd(#[case] seed: Seed) {
     assert_eq!(res, Ok(TxStatus::InMempool));
     assert!(tx_pool.contains_transaction(&tx1_id));
 }
+
+// The block size limit is checked at the height of the next block, so the transactions that
+// no longer fit into a block after a network upgrade are evicted once the upgrade is near.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn tx_evicted_when_block_size_limit_lowered(#[case] seed: Seed) {
+    use common::chain::{config, BlockSizeLimits, NetUpgrades};
+
+    let mut rng = make_seedable_rng(seed);
+    let tf = {
+        let block_size_limits = NetUpgrades::initialize(vec![
+            (BlockHeight::zero(), BlockSizeLimits::new(100_000, 100_000)),
+            (BlockHeight::new(2), BlockSizeLimits::new(20_000, 20_000)),
+        ])
+        .unwrap();
+        let chain_config = config::Builder::new(config::ChainType::Regtest)
+            .block_size_limits(block_size_limits)
+            .data_deposit_max_size(Some(100_000))
+            .build();
+        TestFramework::builder(&mut rng).with_chain_config(chain_config).build()
+    };
+    let genesis = tf.genesis();
+
+    // The transaction fits into the block at height 1 but not at height 2
+    let data_size = rng.gen_range(30_000..60_000);
+    let tx = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(OutPointSourceId::BlockReward(genesis.get_id().into()), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::DataDeposit(
+            (0..data_size).map(|_| rng.gen()).collect(),
+        ))
+        .build();
+    let tx_id = tx.transaction().get_id();
+    assert!(tx.encoded_size() <= tf.chain_config().max_tx_size_for_mempool(BlockHeight::new(1)));
+    assert!(tx.encoded_size() > tf.chain_config().max_tx_size_for_mempool(BlockHeight::new(2)));
+
+    let mut tx_pool = setup_with_chainstate(tf.chainstate());
+    let chainstate = tx_pool.chainstate_handle().shallow_clone();
+
+    tx_pool.add_transaction_test(tx).expect("adding tx").assert_in_mempool();
+
+    let block1 = make_test_block(Vec::new(), genesis.get_id(), DUMMY_TIME);
+    let block1_id = block1.get_id();
+    chainstate
+        .call_mut(move |c| c.process_block(block1, BlockSource::Local))
+        .await
+        .unwrap()
+        .expect("block1");
+    tx_pool.on_new_tip(block1_id, BlockHeight::new(1)).unwrap();
+    assert!(!tx_pool.contains_transaction(&tx_id));
+    tx_pool.store.assert_valid();
+}
@@ -17,6 +17,7 @@ use common::chain::output_value::OutputValue;
 use randomness::{CryptoRng, Rng};
 
 use super::*;
+use crate::event::{TransactionRemoved, TxRemovalReason};
 
 async fn test_replace_tx(
     rng: &mut (impl Rng + CryptoRng),
@@ -67,13 +68,72 @@ async fn test_replace_tx(
         "created a replacement with fee {:?}",
         try_get_fee(&mempool, &replacement).await
     );
+    let replacement_id = replacement.transaction().get_id();
     mempool.add_transaction_test(replacement)?.assert_in_mempool();
     assert!(!mempool.contains_transaction(&original_id));
+    assert_eq!(
+        mempool.transaction_info(&replacement_id).unwrap().replaced,
+        vec![original_id]
+    );
+    assert_eq!(
+        mempool.take_removed_txs(),
+        vec![TransactionRemoved::new(original_id, TxRemovalReason::Replaced)]
+    );
     mempool.store.assert_valid();
 
     Ok(())
 }
 
+// The replace-by-fee signal of a transaction is reported for its in-mempool descendants too
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rbf_signal_inherited_by_descendants(#[case] seed: Seed) -> anyhow::Result<()> {
+    let mut rng = make_seedable_rng(seed);
+    let tf = TestFramework::builder(&mut rng).build();
+    let genesis = tf.genesis();
+    let outpoint_source_id = OutPointSourceId::BlockReward(genesis.get_id().into());
+
+    let mut mempool = setup_with_chainstate(tf.chainstate());
+    let parent_flags = rng.gen_range(0..=1);
+    let parent = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(outpoint_source_id, 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        None,
+        parent_flags,
+    )
+    .await
+    .expect("should be able to spend here");
+    let parent_id = parent.transaction().get_id();
+    mempool.add_transaction_test(parent)?.assert_in_mempool();
+
+    let child = tx_spend_input(
+        &mempool,
+        TxInput::from_utxo(OutPointSourceId::Transaction(parent_id), 0),
+        InputWitness::NoSignature(Some(DUMMY_WITNESS_MSG.to_vec())),
+        None,
+        0,
+    )
+    .await
+    .expect("should be able to spend here");
+    let child_id = child.transaction().get_id();
+    mempool.add_transaction_test(child)?.assert_in_mempool();
+
+    let signalled = parent_flags == 1;
+    assert_eq!(
+        mempool.transaction_info(&parent_id).unwrap().rbf_signalled,
+        signalled
+    );
+    assert_eq!(
+        mempool.transaction_info(&child_id).unwrap().rbf_signalled,
+        signalled
+    );
+
+    Ok(())
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
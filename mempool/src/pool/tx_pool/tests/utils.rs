@@ -44,6 +44,7 @@ pub const TEST_MIN_TX_RELAY_FEE_RATE: FeeRate =
 pub fn create_mempool_config() -> ConstValue<MempoolConfig> {
     ConstValue::new(MempoolConfig {
         min_tx_relay_fee_rate: TEST_MIN_TX_RELAY_FEE_RATE.into(),
+        ..MempoolConfig::new()
     })
 }
 
@@ -333,10 +334,16 @@ pub fn setup() -> TxPool<StoreMemoryUsageEstimator> {
 }
 
 pub fn setup_with_min_tx_relay_fee_rate(fee_rate: FeeRate) -> TxPool<StoreMemoryUsageEstimator> {
-    let chain_config = Arc::new(common::chain::config::create_unit_test_config());
-    let mempool_config = MempoolConfig {
+    setup_with_mempool_config(MempoolConfig {
         min_tx_relay_fee_rate: fee_rate.into(),
-    };
+        ..MempoolConfig::new()
+    })
+}
+
+pub fn setup_with_mempool_config(
+    mempool_config: MempoolConfig,
+) -> TxPool<StoreMemoryUsageEstimator> {
+    let chain_config = Arc::new(common::chain::config::create_unit_test_config());
     let chainstate_interface = start_chainstate_with_config(Arc::clone(&chain_config));
     TxPool::new(
         chain_config,
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use chainstate::{
     chainstate_interface::ChainstateInterface,
@@ -237,6 +237,11 @@ impl TransactionVerifierStorageRef for ChainstateHandle {
         self.call(move |c| c.get_token_id_from_issuance_tx(&tx_id))
     }
 
+    fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> Result<Option<BTreeSet<TokenId>>, Error> {
+        let ticker = ticker.to_vec();
+        self.call(move |c| c.get_token_ids_by_ticker(&ticker))
+    }
+
     fn get_undo_data(
         &self,
         _source: TransactionSource,
@@ -309,7 +314,9 @@ impl UtxosView for ChainstateHandle {
     }
 
     fn has_utxo(&self, outpoint: &UtxoOutPoint) -> Result<bool, Error> {
-        self.utxo(outpoint).map(|outpt| outpt.is_some())
+        let outpoint = outpoint.clone();
+        self.call(move |c| c.outpoints_available(&[outpoint]))
+            .map(|available| available == [true])
     }
 
     fn best_block_hash(&self) -> Result<Id<GenBlock>, Error> {
@@ -19,13 +19,13 @@ use std::num::NonZeroUsize;
 
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
-    primitives::Id,
+    primitives::{Amount, Id},
 };
 use mempool_types::{tx_options::TxOptionsOverrides, tx_origin::LocalTxOrigin, TxOptions};
 use serialization::hex_encoded::HexEncoded;
 use utils::tap_log::TapLog;
 
-use crate::{rpc_event::RpcEvent, FeeRate, MempoolMaxSize, TxStatus};
+use crate::{rpc_event::RpcEvent, FeeRate, MempoolMaxSize, MempoolTxInfo, TxStatus};
 
 use rpc::RpcResult;
 
@@ -36,6 +36,24 @@ pub struct GetTxResponse {
     transaction: HexEncoded<SignedTransaction>,
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, rpc::description::HasValueHint)]
+pub struct RelatedTx {
+    id: Id<Transaction>,
+    info: Option<MempoolTxInfo>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, rpc::description::HasValueHint)]
+pub struct GetTxAncestryResponse {
+    id: Id<Transaction>,
+    ancestors: Vec<RelatedTx>,
+    descendants: Vec<RelatedTx>,
+    fee_with_ancestors: Amount,
+    size_with_ancestors: usize,
+    fee_with_descendants: Amount,
+    size_with_descendants: usize,
+    eligible_at_fee_rate: Option<bool>,
+}
+
 #[rpc::describe]
 #[rpc::rpc(server, client, namespace = "mempool")]
 trait MempoolRpc {
@@ -56,6 +74,21 @@ trait MempoolRpc {
     #[method(name = "get_transaction")]
     async fn get_transaction(&self, tx_id: Id<Transaction>) -> RpcResult<Option<GetTxResponse>>;
 
+    /// Returns the in-mempool ancestors and descendants of a transaction, along with
+    /// the total fee and size of the transaction together with each of those sets.
+    ///
+    /// If `verbose` is true, fee information is included for each related transaction.
+    /// If `fee_rate` is provided, the result tells whether the transaction, together with its
+    /// ancestors, pays enough to be selected for a block at that fee rate.
+    /// Returns `None` (null) if the transaction is not found in the main mempool.
+    #[method(name = "get_transaction_ancestry")]
+    async fn get_transaction_ancestry(
+        &self,
+        tx_id: Id<Transaction>,
+        verbose: bool,
+        fee_rate: Option<FeeRate>,
+    ) -> RpcResult<Option<GetTxAncestryResponse>>;
+
     /// Get all mempool transactions in a Vec/List, with hex-encoding.
     ///
     /// Notice that this call may be expensive. Use it with caution.
@@ -149,6 +182,47 @@ impl MempoolRpcServer for super::MempoolHandle {
         }))
     }
 
+    async fn get_transaction_ancestry(
+        &self,
+        tx_id: Id<Transaction>,
+        verbose: bool,
+        fee_rate: Option<FeeRate>,
+    ) -> rpc::RpcResult<Option<GetTxAncestryResponse>> {
+        rpc::handle_result(
+            self.call(move |this| {
+                let info = this.transaction_info(&tx_id)?;
+                let ancestry = this.transaction_ancestry(&tx_id)?;
+
+                let related = |ids: Vec<Id<Transaction>>| -> Vec<RelatedTx> {
+                    ids.into_iter()
+                        .map(|id| RelatedTx {
+                            id,
+                            info: verbose.then(|| this.transaction_info(&id)).flatten(),
+                        })
+                        .collect()
+                };
+
+                // Block assembly picks transactions by their ancestor score, which is the
+                // lower of the transaction's own fee rate and its package fee rate.
+                let eligible_at_fee_rate = fee_rate.map(|fee_rate| {
+                    std::cmp::min(info.fee_rate, info.package_fee_rate) >= fee_rate
+                });
+
+                Some(GetTxAncestryResponse {
+                    id: tx_id,
+                    ancestors: related(ancestry.ancestors),
+                    descendants: related(ancestry.descendants),
+                    fee_with_ancestors: ancestry.fee_with_ancestors,
+                    size_with_ancestors: ancestry.size_with_ancestors,
+                    fee_with_descendants: ancestry.fee_with_descendants,
+                    size_with_descendants: ancestry.size_with_descendants,
+                    eligible_at_fee_rate,
+                })
+            })
+            .await,
+        )
+    }
+
     async fn submit_transaction(
         &self,
         tx: HexEncoded<SignedTransaction>,
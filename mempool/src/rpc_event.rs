@@ -14,13 +14,13 @@
 // limitations under the License.
 
 use common::{
-    chain::{Block, Transaction},
+    chain::{Block, SignedTransaction, Transaction},
     primitives::{BlockHeight, Id},
 };
 use mempool_types::{tx_options::TxRelayPolicy, tx_origin::LocalTxOrigin};
 use p2p_types::PeerId;
 
-use crate::event::MempoolEvent;
+use crate::event::{MempoolEvent, TxRemovalReason};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, rpc::description::HasValueHint)]
 #[serde(tag = "type", content = "content")]
@@ -31,10 +31,19 @@ pub enum RpcEvent {
     },
     TransactionProcessed {
         tx_id: Id<Transaction>,
+        wtxid: Id<SignedTransaction>,
         origin: RpcTxOrigin,
         relay: RpcTxRelayPolicy,
         successful: bool,
     },
+    TransactionReplaced {
+        replaced: Vec<Id<Transaction>>,
+        replacement: Id<Transaction>,
+    },
+    TransactionRemoved {
+        tx_id: Id<Transaction>,
+        reason: RpcTxRemovalReason,
+    },
 }
 
 impl RpcEvent {
@@ -46,6 +55,7 @@ impl RpcEvent {
             },
             MempoolEvent::TransactionProcessed(e) => RpcEvent::TransactionProcessed {
                 tx_id: *e.tx_id(),
+                wtxid: *e.wtxid(),
                 origin: match e.origin() {
                     mempool_types::tx_origin::TxOrigin::Local(local_origin) => RpcTxOrigin::Local {
                         origin: match local_origin {
@@ -64,6 +74,19 @@ impl RpcEvent {
                 },
                 successful: e.result().is_ok(),
             },
+            MempoolEvent::TransactionReplaced(e) => RpcEvent::TransactionReplaced {
+                replaced: e.replaced().to_vec(),
+                replacement: *e.replacement(),
+            },
+            MempoolEvent::TransactionRemoved(e) => RpcEvent::TransactionRemoved {
+                tx_id: *e.tx_id(),
+                reason: match e.reason() {
+                    TxRemovalReason::Replaced => RpcTxRemovalReason::Replaced,
+                    TxRemovalReason::Expired => RpcTxRemovalReason::Expired,
+                    TxRemovalReason::Block => RpcTxRemovalReason::Block,
+                    TxRemovalReason::SizeLimit => RpcTxRemovalReason::SizeLimit,
+                },
+            },
         }
     }
 }
@@ -95,3 +118,14 @@ pub enum RpcTxRelayPolicy {
     DoRelay,
     DontRelay,
 }
+
+#[derive(
+    Debug, Clone, Copy, serde::Serialize, serde::Deserialize, rpc_description::HasValueHint,
+)]
+#[serde(tag = "type", content = "content")]
+pub enum RpcTxRemovalReason {
+    Replaced,
+    Expired,
+    Block,
+    SizeLimit,
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::{
+    chain::{SignedTransaction, Transaction},
+    primitives::{Id, H256},
+};
+use randomness::make_pseudo_rng;
+use utils::{
+    bloom_filters::rolling_bloom_filter::RollingBloomFilter,
+    sync::{Arc, Mutex},
+};
+
+const ROLLING_BLOOM_FILTER_SIZE: usize = 50000;
+const ROLLING_BLOOM_FPP: f64 = 0.000001;
+
+/// The outcome of the last attempt to add a transaction to the mempool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeenTxOutcome {
+    /// The transaction was accepted (it may have been mined or evicted since then).
+    Accepted,
+    /// The transaction was rejected since the last tip change.
+    Rejected,
+}
+
+/// Helper to use with `RollingBloomFilter` because `H256` does not implement `Hash`
+struct HashWrapper(H256);
+
+impl std::hash::Hash for HashWrapper {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.as_ref().hash(state);
+    }
+}
+
+struct Filters {
+    /// Ids of the accepted transactions
+    accepted: RollingBloomFilter<HashWrapper>,
+    /// Both ids and witness ids of the rejected transactions
+    rejected: RollingBloomFilter<HashWrapper>,
+}
+
+fn new_filter() -> RollingBloomFilter<HashWrapper> {
+    RollingBloomFilter::new(
+        ROLLING_BLOOM_FILTER_SIZE,
+        ROLLING_BLOOM_FPP,
+        &mut make_pseudo_rng(),
+    )
+}
+
+/// A rolling filter of the transactions recently processed by the mempool, together with the
+/// outcome of the processing.
+///
+/// The mempool updates it and other subsystems (p2p) may query it directly, without going
+/// through the mempool subsystem, e.g. to drop duplicate transaction announcements early.
+/// Orphans are not recorded, because they may become valid once their parents arrive.
+/// Being a bloom filter, it may report false positives (with a negligible probability).
+///
+/// Acceptance is tracked by the transaction id, because once a transaction is accepted, any
+/// other version of it is redundant. Rejection is tracked by the witness id as well, because
+/// a transaction may be rejected due to malleated signatures, in which case its other versions
+/// may still be valid; so if the witness id is known, [SeenTransactions::outcome_by_wtxid]
+/// should be used.
+#[derive(Clone)]
+pub struct SeenTransactions {
+    filters: Arc<Mutex<Filters>>,
+}
+
+impl SeenTransactions {
+    pub fn new() -> Self {
+        Self {
+            filters: Arc::new(Mutex::new(Filters {
+                accepted: new_filter(),
+                rejected: new_filter(),
+            })),
+        }
+    }
+
+    pub fn record(
+        &self,
+        tx_id: &Id<Transaction>,
+        wtxid: &Id<SignedTransaction>,
+        outcome: SeenTxOutcome,
+    ) {
+        let mut filters = self.filters.lock().expect("poisoned mutex");
+        match outcome {
+            SeenTxOutcome::Accepted => {
+                filters.accepted.insert(&HashWrapper(tx_id.to_hash()), &mut make_pseudo_rng());
+            }
+            SeenTxOutcome::Rejected => {
+                filters.rejected.insert(&HashWrapper(tx_id.to_hash()), &mut make_pseudo_rng());
+                filters.rejected.insert(&HashWrapper(wtxid.to_hash()), &mut make_pseudo_rng());
+            }
+        }
+    }
+
+    /// The outcome for the transaction that is only known by its id.
+    ///
+    /// Note that if some version of the transaction was rejected, e.g. due to malleated
+    /// signatures, the transaction is reported as rejected.
+    pub fn outcome(&self, tx_id: &Id<Transaction>) -> Option<SeenTxOutcome> {
+        self.outcome_impl(tx_id, &tx_id.to_hash())
+    }
+
+    /// The outcome for the specific version of the transaction.
+    pub fn outcome_by_wtxid(
+        &self,
+        tx_id: &Id<Transaction>,
+        wtxid: &Id<SignedTransaction>,
+    ) -> Option<SeenTxOutcome> {
+        self.outcome_impl(tx_id, &wtxid.to_hash())
+    }
+
+    fn outcome_impl(&self, tx_id: &Id<Transaction>, rejected_key: &H256) -> Option<SeenTxOutcome> {
+        let filters = self.filters.lock().expect("poisoned mutex");
+
+        if filters.accepted.contains(&HashWrapper(tx_id.to_hash())) {
+            Some(SeenTxOutcome::Accepted)
+        } else if filters.rejected.contains(&HashWrapper(*rejected_key)) {
+            Some(SeenTxOutcome::Rejected)
+        } else {
+            None
+        }
+    }
+
+    /// Forget the rejected transactions; this should be done when the tip changes, because
+    /// a transaction that was invalid before may become valid after that.
+    pub fn clear_rejected(&self) {
+        self.filters.lock().expect("poisoned mutex").rejected = new_filter();
+    }
+}
+
+impl Default for SeenTransactions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wtxid(n: u64) -> Id<SignedTransaction> {
+        Id::new(H256::from_low_u64_be(n))
+    }
+
+    #[test]
+    fn record_and_clear() {
+        let seen = SeenTransactions::new();
+        let tx1 = Id::<Transaction>::new(H256::from_low_u64_be(1));
+        let tx2 = Id::<Transaction>::new(H256::from_low_u64_be(2));
+        let tx3 = Id::<Transaction>::new(H256::from_low_u64_be(3));
+
+        seen.record(&tx1, &wtxid(11), SeenTxOutcome::Accepted);
+        seen.clone().record(&tx2, &wtxid(12), SeenTxOutcome::Rejected);
+
+        assert_eq!(seen.outcome(&tx1), Some(SeenTxOutcome::Accepted));
+        assert_eq!(seen.outcome(&tx2), Some(SeenTxOutcome::Rejected));
+        assert_eq!(seen.outcome(&tx3), None);
+
+        seen.clear_rejected();
+        assert_eq!(seen.outcome(&tx1), Some(SeenTxOutcome::Accepted));
+        assert_eq!(seen.outcome(&tx2), None);
+    }
+
+    #[test]
+    fn malleated_versions() {
+        let seen = SeenTransactions::new();
+        let tx1 = Id::<Transaction>::new(H256::from_low_u64_be(1));
+        let tx2 = Id::<Transaction>::new(H256::from_low_u64_be(2));
+
+        // A version of tx1 with bad signatures was rejected
+        seen.record(&tx1, &wtxid(11), SeenTxOutcome::Rejected);
+        assert_eq!(seen.outcome(&tx1), Some(SeenTxOutcome::Rejected));
+        assert_eq!(
+            seen.outcome_by_wtxid(&tx1, &wtxid(11)),
+            Some(SeenTxOutcome::Rejected)
+        );
+        // but other versions are not affected
+        assert_eq!(seen.outcome_by_wtxid(&tx1, &wtxid(12)), None);
+
+        // Once some version is accepted, all the others are redundant
+        seen.record(&tx2, &wtxid(21), SeenTxOutcome::Accepted);
+        assert_eq!(
+            seen.outcome_by_wtxid(&tx2, &wtxid(22)),
+            Some(SeenTxOutcome::Accepted)
+        );
+    }
+}
@@ -20,7 +20,7 @@ use common::{
 use rpc::description::HasValueHint;
 use serialization::{Compact, Encode};
 
-use crate::pool::fee::Fee;
+use crate::pool::{fee::Fee, feerate::FeeRate};
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum TxAccumulatorError {
@@ -34,6 +34,32 @@ pub enum PackingStrategy {
     LeaveEmptySpace,
 }
 
+/// Rules for selecting mempool transactions into a block template
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxTemplatePolicy {
+    /// Transactions taken from the mempool with a lower ancestor fee rate are not included.
+    /// Explicitly requested transactions are not subject to this limit.
+    pub min_fee_rate: Option<FeeRate>,
+
+    /// The total transaction size up to which non-priority transactions may be included.
+    /// Priority transactions are the explicitly requested ones, their ancestors and the ones
+    /// that originate locally; the space beyond this limit is reserved for them.
+    pub non_priority_size_limit: usize,
+}
+
+impl TxTemplatePolicy {
+    pub const DEFAULT: Self = Self {
+        min_fee_rate: None,
+        non_priority_size_limit: usize::MAX,
+    };
+}
+
+impl Default for TxTemplatePolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 pub trait TransactionAccumulator: Send {
     /// Add a transaction to the accumulator and its fee
     /// This method should not mutate self unless it's successful
@@ -54,6 +80,11 @@ pub trait TransactionAccumulator: Send {
     /// must be the so-called "median time past" calculated from "expected_tip".
     /// If a later timestamp is specified, the produced block may not be correct.
     fn unlock_timestamp(&self) -> BlockTimestamp;
+
+    /// The rules for choosing which mempool transactions go into the block.
+    fn template_policy(&self) -> TxTemplatePolicy {
+        TxTemplatePolicy::DEFAULT
+    }
 }
 
 pub struct DefaultTxAccumulator {
@@ -64,6 +95,7 @@ pub struct DefaultTxAccumulator {
     total_fees: Fee,
     expected_tip: Id<GenBlock>,
     unlock_timestamp: BlockTimestamp,
+    template_policy: TxTemplatePolicy,
 }
 
 impl DefaultTxAccumulator {
@@ -80,9 +112,15 @@ impl DefaultTxAccumulator {
             total_fees: Amount::ZERO.into(),
             expected_tip,
             unlock_timestamp,
+            template_policy: TxTemplatePolicy::DEFAULT,
         }
     }
 
+    pub fn with_template_policy(mut self, template_policy: TxTemplatePolicy) -> Self {
+        self.template_policy = template_policy;
+        self
+    }
+
     pub fn total_size(&self) -> usize {
         Compact(self.transactions().len() as u64).encoded_size() + self.txs_size
     }
@@ -95,7 +133,7 @@ impl DefaultTxAccumulator {
 
 impl TransactionAccumulator for DefaultTxAccumulator {
     fn add_tx(&mut self, tx: SignedTransaction, tx_fee: Fee) -> Result<(), TxAccumulatorError> {
-        let tx_size = tx.encoded_size();
+        let tx_size = tx.tx_size().weight();
         let total_size_with_tx = self.total_size_with(tx_size);
 
         if total_size_with_tx <= self.target_size {
@@ -113,7 +151,11 @@ impl TransactionAccumulator for DefaultTxAccumulator {
 
         // Sanity check the size tracking is accurate
         #[cfg(test)]
-        assert_eq!(self.total_size(), self.transactions().encoded_size());
+        assert_eq!(
+            self.total_size(),
+            Compact(self.transactions().len() as u64).encoded_size()
+                + self.transactions().iter().map(|tx| tx.tx_size().weight()).sum::<usize>()
+        );
 
         Ok(())
     }
@@ -137,4 +179,8 @@ impl TransactionAccumulator for DefaultTxAccumulator {
     fn unlock_timestamp(&self) -> BlockTimestamp {
         self.unlock_timestamp
     }
+
+    fn template_policy(&self) -> TxTemplatePolicy {
+        self.template_policy
+    }
 }
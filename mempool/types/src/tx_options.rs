@@ -26,6 +26,17 @@ pub enum TxTrustPolicy {
     Untrusted,
 }
 
+#[derive(Clone, Copy, Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize, Default)]
+pub enum TxEvictionPolicy {
+    /// Transaction may be evicted when the mempool is full.
+    #[default]
+    Evictable,
+
+    /// Transaction is not evicted when the mempool is full, as long as the total size of the
+    /// protected transactions fits the configured budget. It still expires normally.
+    Protected,
+}
+
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub enum TxRelayPolicy {
     /// Transaction should be relayed by p2p if checks pass
@@ -44,6 +55,9 @@ pub struct TxOptions {
 
     /// Whether the transaction should be relayed
     relay_policy: TxRelayPolicy,
+
+    /// Whether the transaction can be evicted when the mempool is full
+    eviction_policy: TxEvictionPolicy,
 }
 
 impl TxOptions {
@@ -63,6 +77,7 @@ impl TxOptions {
         TxOptions {
             trust_policy,
             relay_policy,
+            eviction_policy: TxEvictionPolicy::Evictable,
         }
     }
 
@@ -72,6 +87,10 @@ impl TxOptions {
             self.trust_policy = trust_policy;
         }
 
+        if let Some(eviction_policy) = overrides.eviction_policy {
+            self.eviction_policy = eviction_policy;
+        }
+
         self
     }
 
@@ -82,6 +101,10 @@ impl TxOptions {
     pub fn relay_policy(&self) -> TxRelayPolicy {
         self.relay_policy
     }
+
+    pub fn eviction_policy(&self) -> TxEvictionPolicy {
+        self.eviction_policy
+    }
 }
 
 /// Mechanism to apply user-specified overrides to [TxOptions].
@@ -90,11 +113,29 @@ impl TxOptions {
 pub struct TxOptionsOverrides {
     /// Override transaction trust policy.
     trust_policy: Option<TxTrustPolicy>,
+
+    /// Override transaction eviction policy.
+    eviction_policy: Option<TxEvictionPolicy>,
+}
+
+impl TxOptionsOverrides {
+    pub fn with_eviction_policy(self, eviction_policy: TxEvictionPolicy) -> Self {
+        Self {
+            eviction_policy: Some(eviction_policy),
+            ..self
+        }
+    }
 }
 
 impl rpc_description::HasValueHint for TxOptionsOverrides {
-    const HINT_SER: VH = VH::Object(&[(
-        "trust_policy",
-        &VH::Choice(&[&VH::StrLit("Trusted"), &VH::StrLit("Untrusted")]),
-    )]);
+    const HINT_SER: VH = VH::Object(&[
+        (
+            "trust_policy",
+            &VH::Choice(&[&VH::StrLit("Trusted"), &VH::StrLit("Untrusted")]),
+        ),
+        (
+            "eviction_policy",
+            &VH::Choice(&[&VH::StrLit("Evictable"), &VH::StrLit("Protected")]),
+        ),
+    ]);
 }
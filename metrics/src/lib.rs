@@ -0,0 +1,30 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Node metrics, exported in the Prometheus text format.
+//!
+//! Metrics are process-wide statics (see [node]) updated directly by the subsystems that own the
+//! measured state. The [MetricsServer] subsystem serves their current values over HTTP at
+//! `/metrics`.
+
+mod metric;
+mod server;
+
+pub mod node;
+
+pub use crate::{
+    metric::{render, Counter, Gauge, Metric, Summary},
+    server::{MetricsServer, METRICS_PATH},
+};
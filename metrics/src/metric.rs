@@ -0,0 +1,220 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// A metric that can be rendered in the Prometheus text format.
+pub trait Metric: Sync {
+    fn name(&self) -> &'static str;
+
+    fn help(&self) -> &'static str;
+
+    fn metric_type(&self) -> &'static str;
+
+    /// Write the sample lines of this metric.
+    fn write_samples(&self, out: &mut String);
+}
+
+/// A monotonically increasing value.
+pub struct Counter {
+    name: &'static str,
+    help: &'static str,
+    value: AtomicU64,
+}
+
+impl Counter {
+    pub const fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            value: AtomicU64::new(0),
+        }
+    }
+
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    pub fn inc_by(&self, amount: u64) {
+        self.value.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+impl Metric for Counter {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn metric_type(&self) -> &'static str {
+        "counter"
+    }
+
+    fn write_samples(&self, out: &mut String) {
+        let _ = writeln!(out, "{} {}", self.name, self.get());
+    }
+}
+
+/// A value that can go up and down.
+pub struct Gauge {
+    name: &'static str,
+    help: &'static str,
+    value: AtomicU64,
+}
+
+impl Gauge {
+    pub const fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            value: AtomicU64::new(0),
+        }
+    }
+
+    pub fn set(&self, value: u64) {
+        self.value.store(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+impl Metric for Gauge {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn metric_type(&self) -> &'static str {
+        "gauge"
+    }
+
+    fn write_samples(&self, out: &mut String) {
+        let _ = writeln!(out, "{} {}", self.name, self.get());
+    }
+}
+
+/// Running total and count of observed durations, exported in seconds.
+pub struct Summary {
+    name: &'static str,
+    help: &'static str,
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Summary {
+    pub const fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+impl Metric for Summary {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn metric_type(&self) -> &'static str {
+        "summary"
+    }
+
+    fn write_samples(&self, out: &mut String) {
+        let sum_micros = self.sum_micros.load(Ordering::Relaxed);
+        let _ = writeln!(
+            out,
+            "{}_sum {}.{:06}",
+            self.name,
+            sum_micros / 1_000_000,
+            sum_micros % 1_000_000
+        );
+        let _ = writeln!(out, "{}_count {}", self.name, self.count());
+    }
+}
+
+/// Render the given metrics in the Prometheus text exposition format.
+pub fn render<'a>(metrics: impl IntoIterator<Item = &'a dyn Metric>) -> String {
+    let mut out = String::new();
+    for metric in metrics {
+        let _ = writeln!(out, "# HELP {} {}", metric.name(), metric.help());
+        let _ = writeln!(out, "# TYPE {} {}", metric.name(), metric.metric_type());
+        metric.write_samples(&mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_text_format() {
+        let counter = Counter::new("test_counter", "A counter");
+        let gauge = Gauge::new("test_gauge", "A gauge");
+        let summary = Summary::new("test_summary_seconds", "A summary");
+
+        counter.inc();
+        counter.inc_by(2);
+        gauge.set(10);
+        gauge.set(7);
+        summary.observe(Duration::from_millis(1500));
+        summary.observe(Duration::from_micros(25));
+
+        let metrics: [&dyn Metric; 3] = [&counter, &gauge, &summary];
+        let expected = "# HELP test_counter A counter\n\
+                        # TYPE test_counter counter\n\
+                        test_counter 3\n\
+                        # HELP test_gauge A gauge\n\
+                        # TYPE test_gauge gauge\n\
+                        test_gauge 7\n\
+                        # HELP test_summary_seconds A summary\n\
+                        # TYPE test_summary_seconds summary\n\
+                        test_summary_seconds_sum 1.500025\n\
+                        test_summary_seconds_count 2\n";
+        assert_eq!(render(metrics), expected);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics collected by the node subsystems.
+
+use crate::{Counter, Gauge, Metric, Summary};
+
+pub static BLOCKS_PROCESSED: Counter = Counter::new(
+    "mintlayer_chainstate_blocks_processed_total",
+    "Number of blocks successfully processed by the chainstate",
+);
+
+pub static REORGS: Counter = Counter::new(
+    "mintlayer_chainstate_reorgs_total",
+    "Number of reorgs of the mainchain",
+);
+
+pub static DB_COMMIT_DURATION: Summary = Summary::new(
+    "mintlayer_chainstate_db_commit_duration_seconds",
+    "Time spent committing chainstate database transactions",
+);
+
+pub static LMDB_MAP_RESIZES: Counter = Counter::new(
+    "mintlayer_chainstate_lmdb_map_resizes_total",
+    "Number of times the chainstate LMDB memory map was resized",
+);
+
+pub static LMDB_MAP_SIZE: Gauge = Gauge::new(
+    "mintlayer_chainstate_lmdb_map_size_bytes",
+    "Size of the chainstate LMDB memory map",
+);
+
+pub static LMDB_MAP_USED_SIZE: Gauge = Gauge::new(
+    "mintlayer_chainstate_lmdb_map_used_bytes",
+    "Part of the chainstate LMDB memory map occupied by data, as of the last bulk write",
+);
+
+pub static LMDB_ESTIMATED_WRITE_SIZE: Gauge = Gauge::new(
+    "mintlayer_chainstate_lmdb_estimated_write_bytes",
+    "Space estimated to be needed by the last bulk write to the chainstate LMDB database",
+);
+
+pub static SYNC_PROGRESS: Gauge = Gauge::new(
+    "mintlayer_chainstate_sync_progress_per_thousand",
+    "Estimated fraction of the best chain trust that has been validated, in per thousand",
+);
+
+pub static SYNC_SECONDS_REMAINING: Gauge = Gauge::new(
+    "mintlayer_chainstate_sync_remaining_seconds",
+    "Estimated time needed to process the missing blocks; zero if unknown",
+);
+
+pub static BLOCK_INDEX_CACHE_HITS: Counter = Counter::new(
+    "mintlayer_chainstate_block_index_cache_hits_total",
+    "Number of block index lookups served by the chainstate block index cache",
+);
+
+pub static BLOCK_INDEX_CACHE_MISSES: Counter = Counter::new(
+    "mintlayer_chainstate_block_index_cache_misses_total",
+    "Number of block index lookups that had to be served by the chainstate database",
+);
+
+pub static BLOCK_INDEX_CACHE_MEMORY_USAGE: Gauge = Gauge::new(
+    "mintlayer_chainstate_block_index_cache_memory_usage_bytes",
+    "Estimated memory usage of the chainstate block index cache",
+);
+
+pub static UTXO_CACHE_LOADED_ENTRIES: Counter = Counter::new(
+    "mintlayer_chainstate_utxo_cache_loaded_entries_total",
+    "Number of utxos loaded into the utxo cache during in-memory block verification",
+);
+
+pub static UTXO_CACHE_EVICTED_ENTRIES: Counter = Counter::new(
+    "mintlayer_chainstate_utxo_cache_evicted_entries_total",
+    "Number of unmodified utxos evicted from the utxo cache due to its memory limit",
+);
+
+pub static UTXO_CACHE_MEMORY_USAGE: Gauge = Gauge::new(
+    "mintlayer_chainstate_utxo_cache_memory_usage_bytes",
+    "Estimated memory usage of the utxo cache used by the last in-memory block verification",
+);
+
+pub static ORPHAN_BLOCKS_MEMORY_USAGE: Gauge = Gauge::new(
+    "mintlayer_chainstate_orphan_blocks_memory_usage_bytes",
+    "Estimated memory usage of the chainstate orphan blocks pool",
+);
+
+pub static CACHE_MEMORY_LIMIT: Gauge = Gauge::new(
+    "mintlayer_chainstate_cache_memory_limit_bytes",
+    "Limit of the total memory usage of the chainstate caches",
+);
+
+pub static CACHE_MEMORY_USAGE: Gauge = Gauge::new(
+    "mintlayer_chainstate_cache_memory_usage_bytes",
+    "Estimated total memory usage of the chainstate caches",
+);
+
+pub static MEMPOOL_TRANSACTIONS: Gauge = Gauge::new(
+    "mintlayer_mempool_transactions",
+    "Number of transactions in the mempool",
+);
+
+pub static MEMPOOL_MEMORY_USAGE: Gauge = Gauge::new(
+    "mintlayer_mempool_memory_usage_bytes",
+    "Estimated memory usage of the mempool",
+);
+
+pub static PEERS: Gauge = Gauge::new("mintlayer_p2p_peers", "Number of connected peers");
+
+/// All the node metrics, in the order they are exported.
+pub fn all() -> [&'static dyn Metric; 21] {
+    [
+        &BLOCKS_PROCESSED,
+        &REORGS,
+        &DB_COMMIT_DURATION,
+        &LMDB_MAP_RESIZES,
+        &LMDB_MAP_SIZE,
+        &LMDB_MAP_USED_SIZE,
+        &LMDB_ESTIMATED_WRITE_SIZE,
+        &SYNC_PROGRESS,
+        &SYNC_SECONDS_REMAINING,
+        &BLOCK_INDEX_CACHE_HITS,
+        &BLOCK_INDEX_CACHE_MISSES,
+        &BLOCK_INDEX_CACHE_MEMORY_USAGE,
+        &UTXO_CACHE_LOADED_ENTRIES,
+        &UTXO_CACHE_EVICTED_ENTRIES,
+        &UTXO_CACHE_MEMORY_USAGE,
+        &ORPHAN_BLOCKS_MEMORY_USAGE,
+        &CACHE_MEMORY_LIMIT,
+        &CACHE_MEMORY_USAGE,
+        &MEMPOOL_TRANSACTIONS,
+        &MEMPOOL_MEMORY_USAGE,
+        &PEERS,
+    ]
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::SocketAddr;
+
+use axum::{http::header, routing::get, Router};
+use tokio::{net::TcpListener, sync::oneshot, task::JoinHandle};
+
+use logging::log;
+
+/// The HTTP path the metrics are served at.
+pub const METRICS_PATH: &str = "/metrics";
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Subsystem serving the node metrics over HTTP.
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    shutdown_sender: oneshot::Sender<()>,
+    server_task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// Bind to the given address and start serving the metrics.
+    pub async fn start(bind_address: SocketAddr) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(bind_address).await?;
+        let local_addr = listener.local_addr()?;
+
+        let router = Router::new().route(
+            METRICS_PATH,
+            get(|| async {
+                (
+                    [(header::CONTENT_TYPE, CONTENT_TYPE)],
+                    crate::render(crate::node::all()),
+                )
+            }),
+        );
+
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+        let server_task = tokio::spawn(async move {
+            let result = axum::serve(listener, router)
+                .with_graceful_shutdown(async move {
+                    let _ = shutdown_receiver.await;
+                })
+                .await;
+            if let Err(e) = result {
+                log::error!("Metrics server failed: {e}");
+            }
+        });
+
+        log::info!("Metrics server listening on {local_addr}");
+
+        Ok(Self {
+            local_addr,
+            shutdown_sender,
+            server_task,
+        })
+    }
+
+    pub fn local_addr(&self) -> &SocketAddr {
+        &self.local_addr
+    }
+
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_sender.send(());
+        if let Err(e) = self.server_task.await {
+            log::error!("Metrics server task join failed: {e}");
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl subsystem::Subsystem for MetricsServer {
+    type Interface = Self;
+
+    fn interface_ref(&self) -> &Self {
+        self
+    }
+
+    fn interface_mut(&mut self) -> &mut Self {
+        self
+    }
+
+    async fn shutdown(self) {
+        self.shutdown().await
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use metrics::{node, Metric, MetricsServer, METRICS_PATH};
+
+#[tokio::test]
+async fn serves_node_metrics() {
+    let server = MetricsServer::start("127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+    node::BLOCKS_PROCESSED.inc();
+
+    let mut stream = TcpStream::connect(server.local_addr()).await.unwrap();
+    let request =
+        format!("GET {METRICS_PATH} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("content-type: text/plain; version=0.0.4"));
+    for metric in node::all() {
+        assert!(response.contains(&format!("# TYPE {} ", metric.name())));
+    }
+    assert!(response.contains("mintlayer_chainstate_blocks_processed_total "));
+
+    server.shutdown().await;
+}
@@ -13,12 +13,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    sync::Arc,
+};
 
 use chainstate::{
-    BlockSource, ChainInfo, ChainstateConfig, ChainstateError, ChainstateEvent, Locator,
+    BestBlockInfo, BlockFeeCheckStats, BlockFilter, BlockSource, BootstrapImportProgress,
+    ChainInfo, ChainTip, ChainstateConfig, ChainstateError, ChainstateEvent, DbVerificationError,
+    DbVerificationLevel, DestinationBalance, FilteredBlock, FilteredChainEvent, IbdStatus, Locator,
+    SyncProgress, UtxoDiffChunk, UtxoSetStatistics,
+};
+use chainstate_types::{
+    pos_randomness::PoSRandomness, BlockFilterCommitment, BlockIndex, ChainSplitRecord,
+    CompactBlockFilter, EpochData, GenBlockIndex, LocatorSchedule, PoolBlockRecord, PoolBlockStats,
 };
-use chainstate_types::{BlockIndex, EpochData, GenBlockIndex};
 use common::{
     chain::{
         block::{
@@ -26,10 +36,10 @@ use common::{
             GenBlock,
         },
         tokens::{RPCTokenInfo, TokenAuxiliaryData, TokenId},
-        AccountNonce, AccountType, ChainConfig, DelegationId, OrderId, PoolId, RpcOrderInfo,
-        TxInput, UtxoOutPoint,
+        AccountNonce, AccountType, ChainConfig, DelegationId, Destination, OrderId, PoolId,
+        RpcOrderInfo, TxInput, UtxoOutPoint,
     },
-    primitives::{Amount, BlockHeight, Id},
+    primitives::{Amount, BlockHeight, Fee, Id},
 };
 use orders_accounting::OrderData;
 use pos_accounting::PoolData;
@@ -44,6 +54,15 @@ mockall::mock! {
     impl ChainstateInterface for ChainstateInterface {
         fn subscribe_to_subsystem_events(&mut self, handler: Arc<dyn Fn(ChainstateEvent) + Send + Sync>);
         fn subscribe_to_rpc_events(&mut self) -> utils_networking::broadcaster::Receiver<ChainstateEvent>;
+        fn subscribe_to_bounded_rpc_events(
+            &mut self,
+            capacity: usize,
+            overflow_policy: utils_networking::broadcaster::OverflowPolicy,
+        ) -> utils_networking::broadcaster::BoundedReceiver<ChainstateEvent>;
+        fn subscribe_to_filtered_events(
+            &mut self,
+            filter: BlockFilter,
+        ) -> utils_networking::broadcaster::Receiver<FilteredChainEvent>;
         fn process_block(&mut self, block: Block, source: BlockSource) -> Result<Option<BlockIndex>, ChainstateError>;
         fn invalidate_block(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
         fn reset_block_failure_flags(&mut self, block_id: &Id<Block>) -> Result<(), ChainstateError>;
@@ -54,6 +73,7 @@ mockall::mock! {
         )-> Result<(), ChainstateError>;
         fn get_best_block_id(&self) -> Result<Id<GenBlock>, ChainstateError>;
         fn get_best_block_height(&self) -> Result<BlockHeight, ChainstateError>;
+        fn get_best_block_height_and_id(&self) -> Result<(BlockHeight, Id<GenBlock>), ChainstateError>;
         fn get_best_block_header(&self) -> Result<SignedBlockHeader, ChainstateError>;
         fn is_block_in_main_chain(&self, block_id: &Id<GenBlock>) -> Result<bool, ChainstateError>;
         fn get_min_height_with_allowed_reorg(&self) -> Result<BlockHeight, ChainstateError>;
@@ -66,14 +86,36 @@ mockall::mock! {
             height: &BlockHeight,
         ) -> Result<Option<Id<GenBlock>>, ChainstateError>;
         fn get_block(&self, block_id: Id<Block>) -> Result<Option<Block>, ChainstateError>;
+        fn get_blocks(
+            &self,
+            ids: Vec<Id<Block>>,
+            size_limit: usize,
+        ) -> Result<Vec<Option<Block>>, ChainstateError>;
         fn get_mainchain_blocks(
             &self,
             start_block_height: BlockHeight,
             max_count: usize,
         ) -> Result<Vec<Block>, ChainstateError>;
+        fn get_filtered_mainchain_blocks(
+            &self,
+            filter: &BlockFilter,
+            from: BlockHeight,
+            max_count: usize,
+        ) -> Result<Vec<FilteredBlock>, ChainstateError>;
         fn get_block_header(&self, block_id: Id<Block>) -> Result<Option<SignedBlockHeader>, ChainstateError>;
+        fn get_utxo_diff(
+            &self,
+            from_height: BlockHeight,
+            to_height: BlockHeight,
+            max_entries: NonZeroUsize,
+        ) -> Result<UtxoDiffChunk, ChainstateError>;
         fn get_locator(&self) -> Result<Locator, ChainstateError>;
         fn get_locator_from_height(&self, height: BlockHeight) -> Result<Locator, ChainstateError>;
+        fn get_locator_from_block(
+            &self,
+            block_id: &Id<GenBlock>,
+            schedule: LocatorSchedule,
+        ) -> Result<Locator, ChainstateError>;
         fn get_block_ids_as_checkpoints(
             &self,
             start_height: BlockHeight,
@@ -138,6 +180,7 @@ mockall::mock! {
             block_index: &BlockIndex,
         ) -> Result<Option<BlockReward>, ChainstateError>;
         fn get_epoch_data(&self, epoch_index: u64) -> Result<Option<EpochData>, ChainstateError>;
+        fn get_sealed_epoch_randomness(&self) -> Result<PoSRandomness, ChainstateError>;
         fn get_token_info_for_rpc(&self, token_id: TokenId) -> Result<Option<RPCTokenInfo>, ChainstateError>;
         fn get_token_aux_data(
             &self,
@@ -147,6 +190,7 @@ mockall::mock! {
             &self,
             tx_id: &Id<common::chain::Transaction>,
         ) -> Result<Option<TokenId>, ChainstateError>;
+        fn get_token_ids_by_ticker(&self, ticker: &[u8]) -> Result<Option<BTreeSet<TokenId>>, ChainstateError>;
         fn get_token_data(
             &self,
             id: &TokenId,
@@ -161,6 +205,7 @@ mockall::mock! {
         fn import_bootstrap_stream<'a>(
             &'a mut self,
             reader: std::io::BufReader<Box<dyn std::io::Read + Send + 'a>>,
+            progress_callback: Box<dyn FnMut(BootstrapImportProgress) + Send + 'a>,
         ) -> Result<(), ChainstateError>;
         fn export_bootstrap_stream<'a>(
             &'a self,
@@ -168,7 +213,40 @@ mockall::mock! {
             include_stale_blocks: bool,
         ) -> Result<(), ChainstateError>;
         fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, ChainstateError>;
+        fn outpoints_available(&self, outpoints: &[UtxoOutPoint]) -> Result<Vec<bool>, ChainstateError>;
+        fn get_utxo_set_statistics(&self) -> Result<UtxoSetStatistics, ChainstateError>;
+        fn get_balance_at_height(
+            &self,
+            destination: &Destination,
+            height: BlockHeight,
+        ) -> Result<DestinationBalance, ChainstateError>;
+        fn verify_database(
+            &self,
+            level: DbVerificationLevel,
+            depth: usize,
+        ) -> Result<Vec<DbVerificationError>, ChainstateError>;
+        fn get_chain_split_records(&self) -> Result<Vec<ChainSplitRecord>, ChainstateError>;
+        fn get_block_filter(
+            &self,
+            block_id: &Id<Block>,
+        ) -> Result<Option<CompactBlockFilter>, ChainstateError>;
+        fn get_block_filter_commitment(
+            &self,
+            block_id: &Id<Block>,
+        ) -> Result<Option<BlockFilterCommitment>, ChainstateError>;
+        fn get_blocks_by_pool(
+            &self,
+            pool_id: PoolId,
+            from: BlockHeight,
+            max_count: usize,
+        ) -> Result<Vec<PoolBlockRecord>, ChainstateError>;
+        fn get_pool_block_stats(&self, pool_id: PoolId) -> Result<PoolBlockStats, ChainstateError>;
+        fn get_chain_tips(&self) -> Result<Vec<ChainTip>, ChainstateError>;
         fn is_initial_block_download(&self) -> bool;
+        fn ibd_status(&self) -> Result<IbdStatus, ChainstateError>;
+        fn sync_progress(&self) -> Result<SyncProgress, ChainstateError>;
+        fn add_block_fee_prediction(&mut self, block_id: Id<Block>, predicted_fees: Fee);
+        fn get_block_fee_check_stats(&self) -> BlockFeeCheckStats;
         fn stake_pool_exists(&self, pool_id: PoolId) -> Result<bool, ChainstateError>;
         fn get_stake_pool_balance(&self, pool_id: PoolId) -> Result<Option<Amount>, ChainstateError>;
         fn get_stake_pool_balances_at_heights(
@@ -15,7 +15,7 @@
 
 #![allow(clippy::unwrap_used)]
 
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use common::{
     chain::{GenBlock, SignedTransaction, Transaction},
@@ -26,7 +26,8 @@ use mempool::{
     event::MempoolEvent,
     tx_accumulator::{PackingStrategy, TransactionAccumulator},
     tx_origin::{LocalTxOrigin, RemoteTxOrigin},
-    FeeRate, MempoolInterface, MempoolMaxSize, TxOptions, TxStatus,
+    FeeRate, MempoolInterface, MempoolMaxSize, MempoolTxAncestry, MempoolTxInfo, SeenTransactions,
+    TxOptions, TxStatus,
 };
 
 mockall::mock! {
@@ -49,6 +50,12 @@ mockall::mock! {
 
         fn get_all(&self) -> Vec<SignedTransaction>;
         fn transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
+        fn transaction_info(&self, id: &Id<Transaction>) -> Option<MempoolTxInfo>;
+        fn transaction_ancestry(&self, id: &Id<Transaction>) -> Option<MempoolTxAncestry>;
+        fn local_transactions_to_rebroadcast(
+            &self,
+            min_age: Duration,
+        ) -> Vec<(Id<Transaction>, Id<SignedTransaction>)>;
         fn orphan_transaction(&self, id: &Id<Transaction>) -> Option<SignedTransaction>;
         fn contains_transaction(&self, tx: &Id<Transaction>) -> bool;
         fn contains_orphan_transaction(&self, tx: &Id<Transaction>) -> bool;
@@ -63,6 +70,7 @@ mockall::mock! {
 
         fn subscribe_to_subsystem_events(&mut self, handler: Arc<dyn Fn(MempoolEvent) + Send + Sync>);
         fn subscribe_to_rpc_events(&mut self) -> utils_networking::broadcaster::Receiver<MempoolEvent>;
+        fn seen_transactions(&self) -> SeenTransactions;
 
         fn memory_usage(&self) -> usize;
         fn get_size_limit(&self) -> MempoolMaxSize;
@@ -423,6 +423,7 @@ impl Backend {
             None,
             false,
             vec![],
+            Default::default(),
             handles_client,
         )
         .await
@@ -644,6 +645,7 @@ impl Backend {
             None,
             false,
             vec![],
+            Default::default(),
             handles_client,
         )
         .await
@@ -78,6 +78,7 @@ impl ChainstateEventHandler {
                     ChainstateEvent::NewTip(_, _) => {
                         self.chain_info_updated = true;
                     }
+                    ChainstateEvent::LongFork(_, _, _) => {}
                 },
                 None => {
                     // Node is stopped
@@ -0,0 +1,302 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chain specification files, which describe a custom (regtest-like) chain.
+
+use std::{
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+    str::FromStr as _,
+};
+
+use serde::{Deserialize, Serialize};
+
+use common::chain::config::{regtest::GenesisStakingSettings, regtest_options::ChainConfigOptions};
+
+/// The contents of a chain spec file.
+///
+/// All the fields are optional; the missing ones take their regtest defaults. The values
+/// have the same meaning and format as the corresponding `--chain-*` command line options.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainSpec {
+    /// Magic bytes (a string of 4 ASCII characters).
+    pub magic_bytes: Option<String>,
+
+    /// The maximum future block offset in seconds.
+    pub max_future_block_time_offset: Option<u64>,
+
+    /// The software version (major.minor.path).
+    pub software_version: Option<String>,
+
+    /// Target block spacing in seconds.
+    pub target_block_spacing: Option<u64>,
+
+    /// Coin decimals.
+    pub coin_decimals: Option<u8>,
+
+    /// Emission schedule (`<initial_supply>+<initial_subsidy>[, <height>:+<subsidy>]`).
+    pub emission_schedule: Option<String>,
+
+    /// The maximum block header size in bytes.
+    pub max_block_header_size: Option<usize>,
+
+    /// The maximum transactions size in block in bytes.
+    pub max_block_size_with_standard_txs: Option<usize>,
+
+    /// The maximum smart contracts size in block in bytes.
+    pub max_block_size_with_smart_contracts: Option<usize>,
+
+    /// Length of an epoch in blocks.
+    pub epoch_length: Option<NonZeroU64>,
+
+    /// Distance from the tip of the chain to the sealed state in epochs.
+    pub sealed_epoch_distance_from_tip: Option<usize>,
+
+    /// Initial difficulty for the chain in Compact representation.
+    pub initial_difficulty: Option<u32>,
+
+    /// If set, the consensus type will be switched to PoS at the specified height.
+    pub pos_netupgrades: Option<u64>,
+
+    /// If set, the PoS consensus version will be upgraded from V0 to V1 at the specified height.
+    pub pos_netupgrades_v0_to_v1: Option<u64>,
+
+    /// Genesis block timestamp in seconds since UNIX epoch.
+    pub genesis_block_timestamp: Option<u64>,
+
+    /// PoS genesis staking settings (`pool_id:<hex>,stake_private_key:<hex>,vrf_private_key:<hex>`).
+    pub genesis_staking_settings: Option<String>,
+
+    /// If set, chainstate will upgrade from orders v0 to v1 at the specified height.
+    pub chainstate_orders_v1_upgrade_height: Option<u64>,
+}
+
+impl ChainSpec {
+    /// Combine the chain spec with the chain options specified on the command line;
+    /// the latter take precedence.
+    pub fn merge_with_options(
+        self,
+        options: &ChainConfigOptions,
+    ) -> Result<ChainConfigOptions, ChainSpecReadError> {
+        let ChainSpec {
+            magic_bytes,
+            max_future_block_time_offset,
+            software_version,
+            target_block_spacing,
+            coin_decimals,
+            emission_schedule,
+            max_block_header_size,
+            max_block_size_with_standard_txs,
+            max_block_size_with_smart_contracts,
+            epoch_length,
+            sealed_epoch_distance_from_tip,
+            initial_difficulty,
+            pos_netupgrades,
+            pos_netupgrades_v0_to_v1,
+            genesis_block_timestamp,
+            genesis_staking_settings,
+            chainstate_orders_v1_upgrade_height,
+        } = self;
+
+        let chain_genesis_staking_settings =
+            if options.chain_genesis_staking_settings != GenesisStakingSettings::default() {
+                options.chain_genesis_staking_settings.clone()
+            } else if let Some(settings) = genesis_staking_settings {
+                GenesisStakingSettings::from_str(&settings)
+                    .map_err(|err| ChainSpecReadError::BadGenesisStakingSettings(err.to_string()))?
+            } else {
+                GenesisStakingSettings::default()
+            };
+
+        // Note: only one kind of PoS net upgrades can be specified, so if the command line
+        // specifies one of them, both values from the chain spec are ignored.
+        let (pos_netupgrades, pos_netupgrades_v0_to_v1) = if options.chain_pos_netupgrades.is_some()
+            || options.chain_pos_netupgrades_v0_to_v1.is_some()
+        {
+            (
+                options.chain_pos_netupgrades,
+                options.chain_pos_netupgrades_v0_to_v1,
+            )
+        } else {
+            (pos_netupgrades, pos_netupgrades_v0_to_v1)
+        };
+
+        Ok(ChainConfigOptions {
+            chain_magic_bytes: options.chain_magic_bytes.clone().or(magic_bytes),
+            chain_max_future_block_time_offset: options
+                .chain_max_future_block_time_offset
+                .or(max_future_block_time_offset),
+            software_version: options.software_version.clone().or(software_version),
+            chain_target_block_spacing: options.chain_target_block_spacing.or(target_block_spacing),
+            chain_coin_decimals: options.chain_coin_decimals.or(coin_decimals),
+            chain_emission_schedule: options.chain_emission_schedule.clone().or(emission_schedule),
+            chain_max_block_header_size: options
+                .chain_max_block_header_size
+                .or(max_block_header_size),
+            chain_max_block_size_with_standard_txs: options
+                .chain_max_block_size_with_standard_txs
+                .or(max_block_size_with_standard_txs),
+            chain_max_block_size_with_smart_contracts: options
+                .chain_max_block_size_with_smart_contracts
+                .or(max_block_size_with_smart_contracts),
+            chain_epoch_length: options.chain_epoch_length.or(epoch_length),
+            chain_sealed_epoch_distance_from_tip: options
+                .chain_sealed_epoch_distance_from_tip
+                .or(sealed_epoch_distance_from_tip),
+            chain_initial_difficulty: options.chain_initial_difficulty.or(initial_difficulty),
+            chain_pos_netupgrades: pos_netupgrades,
+            chain_pos_netupgrades_v0_to_v1: pos_netupgrades_v0_to_v1,
+            chain_genesis_block_timestamp: options
+                .chain_genesis_block_timestamp
+                .or(genesis_block_timestamp),
+            chain_genesis_staking_settings,
+            chain_chainstate_orders_v1_upgrade_height: options
+                .chain_chainstate_orders_v1_upgrade_height
+                .or(chainstate_orders_v1_upgrade_height),
+        })
+    }
+}
+
+/// Read a chain spec from a file; files with the "json" extension are parsed as JSON,
+/// all others as TOML.
+pub fn read_chain_spec_file(file: &Path) -> Result<ChainSpec, ChainSpecReadError> {
+    let contents =
+        std::fs::read_to_string(file).map_err(|err| ChainSpecReadError::FileReadError {
+            file: file.to_owned(),
+            error: err.to_string(),
+        })?;
+
+    let is_json = file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        read_chain_spec_json(&contents)
+    } else {
+        read_chain_spec_toml(&contents)
+    }
+}
+
+pub fn read_chain_spec_toml(contents: &str) -> Result<ChainSpec, ChainSpecReadError> {
+    toml::from_str(contents).map_err(|err| ChainSpecReadError::ParseError {
+        error: err.to_string(),
+    })
+}
+
+pub fn read_chain_spec_json(contents: &str) -> Result<ChainSpec, ChainSpecReadError> {
+    serde_json::from_str(contents).map_err(|err| ChainSpecReadError::ParseError {
+        error: err.to_string(),
+    })
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum ChainSpecReadError {
+    #[error("Cannot read file '{file}': {error}")]
+    FileReadError { file: PathBuf, error: String },
+
+    #[error("Error parsing chain spec: {error}")]
+    ParseError { error: String },
+
+    #[error("Bad genesis staking settings: {0}")]
+    BadGenesisStakingSettings(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use common::chain::config::regtest_options::regtest_chain_config;
+    use utils::concatln;
+
+    use super::*;
+
+    #[test]
+    fn toml_and_json_are_equivalent() {
+        let toml_spec = concatln!(
+            r#"magic_bytes = "abcd""#,
+            "target_block_spacing = 30",
+            "coin_decimals = 8",
+            "epoch_length = 500",
+            "pos_netupgrades = 5",
+            "genesis_block_timestamp = 1700000000"
+        );
+        let json_spec = r#"{
+            "magic_bytes": "abcd",
+            "target_block_spacing": 30,
+            "coin_decimals": 8,
+            "epoch_length": 500,
+            "pos_netupgrades": 5,
+            "genesis_block_timestamp": 1700000000
+        }"#;
+
+        let spec = read_chain_spec_toml(toml_spec).unwrap();
+        assert_eq!(spec, read_chain_spec_json(json_spec).unwrap());
+
+        let chain_config =
+            regtest_chain_config(&spec.merge_with_options(&Default::default()).unwrap()).unwrap();
+        assert_eq!(chain_config.magic_bytes().bytes(), *b"abcd");
+        assert_eq!(chain_config.target_block_spacing().as_secs(), 30);
+        assert_eq!(chain_config.coin_decimals(), 8);
+        assert_eq!(chain_config.epoch_length().get(), 500);
+        assert_eq!(
+            chain_config.genesis_block().timestamp().as_int_seconds(),
+            1700000000
+        );
+    }
+
+    #[test]
+    fn command_line_options_take_precedence() {
+        let spec = read_chain_spec_toml(concatln!(
+            "coin_decimals = 8",
+            "epoch_length = 20",
+            "pos_netupgrades = 5"
+        ))
+        .unwrap();
+        let options = ChainConfigOptions {
+            chain_coin_decimals: Some(6),
+            chain_pos_netupgrades_v0_to_v1: Some(10),
+            ..Default::default()
+        };
+
+        let merged = spec.merge_with_options(&options).unwrap();
+        assert_eq!(merged.chain_coin_decimals, Some(6));
+        assert_eq!(merged.chain_epoch_length, NonZeroU64::new(20));
+        assert_eq!(merged.chain_pos_netupgrades, None);
+        assert_eq!(merged.chain_pos_netupgrades_v0_to_v1, Some(10));
+    }
+
+    #[test]
+    fn invalid_chain_parameters() {
+        // The sealed state would be shallower than the max reorg depth
+        let spec = read_chain_spec_toml("epoch_length = 20").unwrap();
+        let err = regtest_chain_config(&spec.merge_with_options(&Default::default()).unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("max reorg depth"));
+    }
+
+    #[test]
+    fn bad_spec() {
+        assert!(matches!(
+            read_chain_spec_toml("unknown_field = 1"),
+            Err(ChainSpecReadError::ParseError { .. })
+        ));
+        assert!(matches!(
+            read_chain_spec_toml("coin_decimals = \"eight\""),
+            Err(ChainSpecReadError::ParseError { .. })
+        ));
+
+        let spec = read_chain_spec_toml(r#"genesis_staking_settings = "foo:bar""#).unwrap();
+        assert!(matches!(
+            spec.merge_with_options(&Default::default()),
+            Err(ChainSpecReadError::BadGenesisStakingSettings(_))
+        ));
+    }
+}
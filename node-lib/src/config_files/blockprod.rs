@@ -14,6 +14,8 @@
 // limitations under the License.
 
 use blockprod::config::BlockProdConfig;
+use common::primitives::Amount;
+use mempool::FeeRate;
 use serde::{Deserialize, Serialize};
 
 /// The rpc subsystem configuration.
@@ -28,6 +30,12 @@ pub struct BlockProdConfigFile {
     /// If true, blocks with non-PoS consensus types will always be created with timestamps
     /// bigger than or equal to the current time.
     pub use_current_time_if_non_pos: Option<bool>,
+    /// Mempool transactions with a lower fee rate (in atoms per 1000 bytes) are not included
+    /// into produced blocks.
+    pub min_tx_fee_rate_for_inclusion: Option<u64>,
+    /// Block space (in bytes) reserved for explicitly requested and locally originating
+    /// transactions.
+    pub reserved_block_space_for_priority_txs: Option<usize>,
 }
 
 impl From<BlockProdConfigFile> for BlockProdConfig {
@@ -36,12 +44,18 @@ impl From<BlockProdConfigFile> for BlockProdConfig {
             min_peers_to_produce_blocks,
             skip_ibd_check,
             use_current_time_if_non_pos,
+            min_tx_fee_rate_for_inclusion,
+            reserved_block_space_for_priority_txs,
         } = config_file;
 
         Self {
             min_peers_to_produce_blocks: min_peers_to_produce_blocks.unwrap_or_default(),
             skip_ibd_check: skip_ibd_check.unwrap_or_default(),
             use_current_time_if_non_pos: use_current_time_if_non_pos.unwrap_or_default(),
+            min_tx_fee_rate_for_inclusion: min_tx_fee_rate_for_inclusion
+                .map(|val| FeeRate::from_amount_per_kb(Amount::from_atoms(val.into()))),
+            reserved_block_space_for_priority_txs: reserved_block_space_for_priority_txs
+                .unwrap_or_default(),
         }
     }
 }
@@ -38,11 +38,37 @@ pub struct ChainstateConfigFile {
     /// tip time is less than this value.
     pub max_tip_age: Option<u64>,
 
+    /// A warning is emitted when a block is added to a branch that forked off the main chain
+    /// more than this number of blocks ago.
+    pub fork_alert_depth: Option<u64>,
+
+    /// The maximum amount of memory, in bytes, used to cache block indices.
+    pub max_block_index_cache_memory: Option<usize>,
+
+    /// The maximum amount of memory, in bytes, used to cache utxos when a chain of blocks is
+    /// verified in memory (e.g. a reorg); it doesn't apply to the initial block download.
+    pub max_utxo_cache_memory: Option<usize>,
+
+    /// The maximum amount of memory, in bytes, used by the orphan blocks pool.
+    pub max_orphan_blocks_memory: Option<usize>,
+
+    /// The global limit of the memory, in bytes, used by all the chainstate caches; when it is
+    /// reached, the caches shrink to their shares of it, proportional to their own limits.
+    pub max_cache_memory: Option<usize>,
+
     /// If true, additional computationally-expensive consistency checks will be performed by the chainstate.
     pub enable_heavy_checks: Option<bool>,
 
     /// If true, blocks and block headers will not be rejected if checkpoints mismatch is detected.
     pub allow_checkpoints_mismatch: Option<bool>,
+
+    /// If true, compact block filters will be built for the main chain blocks and served to
+    /// light clients.
+    pub enable_block_filter_index: Option<bool>,
+
+    /// If set, the undo data is only kept for this number of the most recent main chain blocks;
+    /// blocks below that depth can no longer be disconnected.
+    pub undo_data_retention_depth: Option<u64>,
 }
 
 impl From<ChainstateConfigFile> for ChainstateConfig {
@@ -52,8 +78,15 @@ impl From<ChainstateConfigFile> for ChainstateConfig {
             max_orphan_blocks,
             min_max_bootstrap_import_buffer_sizes,
             max_tip_age,
+            fork_alert_depth,
+            max_block_index_cache_memory,
+            max_utxo_cache_memory,
+            max_orphan_blocks_memory,
+            max_cache_memory,
             enable_heavy_checks,
             allow_checkpoints_mismatch,
+            enable_block_filter_index,
+            undo_data_retention_depth,
         } = config_file;
 
         ChainstateConfig {
@@ -61,8 +94,15 @@ impl From<ChainstateConfigFile> for ChainstateConfig {
             max_orphan_blocks: max_orphan_blocks.into(),
             min_max_bootstrap_import_buffer_sizes: min_max_bootstrap_import_buffer_sizes.into(),
             max_tip_age: max_tip_age.map(Duration::from_secs).into(),
+            fork_alert_depth: fork_alert_depth.into(),
+            max_block_index_cache_memory: max_block_index_cache_memory.into(),
+            max_utxo_cache_memory: max_utxo_cache_memory.into(),
+            max_orphan_blocks_memory: max_orphan_blocks_memory.into(),
+            max_cache_memory,
             enable_heavy_checks,
             allow_checkpoints_mismatch,
+            enable_block_filter_index,
+            undo_data_retention_depth,
         }
     }
 }
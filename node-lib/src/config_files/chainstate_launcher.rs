@@ -15,7 +15,9 @@
 
 //! Chainstate launcher configuration
 
-use chainstate_launcher::{ChainstateLauncherConfig, StorageBackendConfig};
+use chainstate_launcher::{
+    ChainstateLauncherConfig, LmdbConfig, MemSize, StorageBackendConfig, SyncMode,
+};
 use serde::{Deserialize, Serialize};
 
 use super::chainstate::ChainstateConfigFile;
@@ -26,19 +28,12 @@ pub enum StorageBackendConfigFile {
     #[serde(rename = "lmdb")]
     #[default]
     Lmdb,
+    #[serde(rename = "sqlite")]
+    Sqlite,
     #[serde(rename = "inmemory", alias = "in-memory")]
     InMemory,
 }
 
-impl From<StorageBackendConfigFile> for StorageBackendConfig {
-    fn from(c: StorageBackendConfigFile) -> Self {
-        match c {
-            StorageBackendConfigFile::Lmdb => StorageBackendConfig::Lmdb,
-            StorageBackendConfigFile::InMemory => StorageBackendConfig::InMemory,
-        }
-    }
-}
-
 impl std::str::FromStr for StorageBackendConfigFile {
     type Err = serde::de::value::Error;
 
@@ -48,6 +43,63 @@ impl std::str::FromStr for StorageBackendConfigFile {
     }
 }
 
+/// LMDB commit durability
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LmdbSyncModeConfigFile {
+    #[serde(rename = "full")]
+    Full,
+    #[serde(rename = "no-meta-sync")]
+    NoMetaSync,
+    #[serde(rename = "no-sync")]
+    NoSync,
+}
+
+impl From<LmdbSyncModeConfigFile> for SyncMode {
+    fn from(c: LmdbSyncModeConfigFile) -> Self {
+        match c {
+            LmdbSyncModeConfigFile::Full => SyncMode::Full,
+            LmdbSyncModeConfigFile::NoMetaSync => SyncMode::NoMetaSync,
+            LmdbSyncModeConfigFile::NoSync => SyncMode::NoSync,
+        }
+    }
+}
+
+/// LMDB tuning options; the sizes are in megabytes
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LmdbConfigFile {
+    /// The initial size of the memory map
+    pub initial_map_size_mb: Option<u64>,
+    /// The minimum amount by which the memory map grows when it's resized
+    pub min_resize_step_mb: Option<u64>,
+    /// The maximum amount by which the memory map grows when it's resized
+    pub max_resize_step_mb: Option<u64>,
+    /// The maximum number of simultaneous read transactions
+    pub max_readers: Option<u32>,
+    /// How durable the commits are
+    pub sync_mode: Option<LmdbSyncModeConfigFile>,
+}
+
+impl From<LmdbConfigFile> for LmdbConfig {
+    fn from(c: LmdbConfigFile) -> Self {
+        let LmdbConfigFile {
+            initial_map_size_mb,
+            min_resize_step_mb,
+            max_resize_step_mb,
+            max_readers,
+            sync_mode,
+        } = c;
+
+        LmdbConfig {
+            initial_map_size: initial_map_size_mb.map(MemSize::from_megabytes),
+            min_resize_step: min_resize_step_mb.map(MemSize::from_megabytes),
+            max_resize_step: max_resize_step_mb.map(MemSize::from_megabytes),
+            max_readers,
+            sync_mode: sync_mode.map(Into::into).unwrap_or_default(),
+        }
+    }
+}
+
 /// Storage configuration
 #[must_use]
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
@@ -62,6 +114,10 @@ pub struct ChainstateLauncherConfigFile {
     #[serde(default)]
     pub storage_backend: StorageBackendConfigFile,
 
+    /// LMDB tuning options, used if the storage backend is LMDB
+    #[serde(default)]
+    pub lmdb: LmdbConfigFile,
+
     /// Chainstate configuration
     #[serde(flatten)]
     pub chainstate_config: ChainstateConfigFile,
@@ -77,11 +133,18 @@ impl From<ChainstateLauncherConfigFile> for ChainstateLauncherConfig {
     fn from(config_file: ChainstateLauncherConfigFile) -> Self {
         let ChainstateLauncherConfigFile {
             storage_backend,
+            lmdb,
             chainstate_config,
         } = config_file;
 
+        let storage_backend = match storage_backend {
+            StorageBackendConfigFile::Lmdb => StorageBackendConfig::Lmdb(lmdb.into()),
+            StorageBackendConfigFile::Sqlite => StorageBackendConfig::Sqlite,
+            StorageBackendConfigFile::InMemory => StorageBackendConfig::InMemory,
+        };
+
         ChainstateLauncherConfig {
-            storage_backend: storage_backend.into(),
+            storage_backend,
             chainstate_config: chainstate_config.into(),
         }
     }
@@ -94,9 +157,44 @@ mod test {
     #[test]
     fn backend_from_str() {
         assert_eq!("lmdb".parse(), Ok(StorageBackendConfigFile::Lmdb));
+        assert_eq!("sqlite".parse(), Ok(StorageBackendConfigFile::Sqlite));
         assert_eq!("in-memory".parse(), Ok(StorageBackendConfigFile::InMemory));
         assert_eq!("inmemory".parse(), Ok(StorageBackendConfigFile::InMemory));
         assert!("meh".parse::<StorageBackendConfigFile>().is_err());
         assert!("".parse::<StorageBackendConfigFile>().is_err());
     }
+
+    #[test]
+    fn lmdb_config_from_toml() {
+        let config: ChainstateLauncherConfigFile = toml::from_str(
+            r#"
+            storage_backend = "lmdb"
+
+            [lmdb]
+            initial_map_size_mb = 1024
+            max_resize_step_mb = 512
+            max_readers = 256
+            sync_mode = "no-meta-sync"
+            "#,
+        )
+        .unwrap();
+
+        let config = ChainstateLauncherConfig::from(config);
+        assert_eq!(
+            config.storage_backend,
+            StorageBackendConfig::Lmdb(LmdbConfig {
+                initial_map_size: Some(MemSize::from_megabytes(1024)),
+                min_resize_step: None,
+                max_resize_step: Some(MemSize::from_megabytes(512)),
+                max_readers: Some(256),
+                sync_mode: SyncMode::NoMetaSync,
+            })
+        );
+
+        let err = toml::from_str::<ChainstateLauncherConfigFile>(
+            "[lmdb]
+map_size = 1",
+        );
+        assert!(err.is_err());
+    }
 }
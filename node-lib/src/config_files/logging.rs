@@ -0,0 +1,38 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The logging configuration.
+#[must_use]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfigFile {
+    /// The style of the log output: "text", "text-colored", "text-uncolored" or "json".
+    /// If not set, the style is taken from the ML_LOG_STYLE env var.
+    pub log_style: Option<String>,
+
+    /// Log levels of individual subsystems (log targets), e.g. `chainstate = "debug"`.
+    /// They take precedence over the levels specified for the same targets in RUST_LOG.
+    pub log_levels: Option<BTreeMap<String, String>>,
+}
+
+impl LoggingConfigFile {
+    pub fn log_style(&self) -> Result<Option<logging::LogStyle>, logging::LogStyleParseError> {
+        self.log_style.as_deref().map(logging::LogStyle::parse).transpose()
+    }
+}
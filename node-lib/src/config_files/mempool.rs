@@ -27,6 +27,16 @@ use crate::RunOptions;
 pub struct MempoolConfigFile {
     /// Minimum transaction relay fee rate (in atoms per 1000 bytes).
     pub min_tx_relay_fee_rate: Option<u64>,
+    /// Coin outputs below this amount (in atoms) are not accepted; the chain's default if not set.
+    pub dust_threshold: Option<u64>,
+    /// The maximum size of a transaction accepted into the mempool, in bytes.
+    pub max_tx_size: Option<usize>,
+    /// The maximum number of signature checks a transaction may require.
+    pub max_standard_sigops: Option<usize>,
+    /// The maximum total size of transactions protected from eviction, in bytes.
+    pub max_protected_txs_size: Option<usize>,
+    /// Accept transactions regardless of the fee they pay; only honored on regtest.
+    pub accept_zero_fee_txs: Option<bool>,
 }
 
 impl MempoolConfigFile {
@@ -37,12 +47,28 @@ impl MempoolConfigFile {
     pub fn with_run_options(config: MempoolConfigFile, options: &RunOptions) -> MempoolConfigFile {
         let MempoolConfigFile {
             min_tx_relay_fee_rate,
+            dust_threshold,
+            max_tx_size,
+            max_standard_sigops,
+            max_protected_txs_size,
+            accept_zero_fee_txs,
         } = config;
 
         let min_tx_relay_fee_rate = min_tx_relay_fee_rate.or(options.min_tx_relay_fee_rate);
+        let dust_threshold = dust_threshold.or(options.mempool_dust_threshold);
+        let max_tx_size = max_tx_size.or(options.mempool_max_tx_size);
+        let max_standard_sigops = max_standard_sigops.or(options.mempool_max_standard_sigops);
+        let max_protected_txs_size =
+            max_protected_txs_size.or(options.mempool_max_protected_txs_size);
+        let accept_zero_fee_txs = accept_zero_fee_txs.or(options.mempool_accept_zero_fee_txs);
 
         MempoolConfigFile {
             min_tx_relay_fee_rate,
+            dust_threshold,
+            max_tx_size,
+            max_standard_sigops,
+            max_protected_txs_size,
+            accept_zero_fee_txs,
         }
     }
 }
@@ -51,12 +77,22 @@ impl From<MempoolConfigFile> for MempoolConfig {
     fn from(config_file: MempoolConfigFile) -> Self {
         let MempoolConfigFile {
             min_tx_relay_fee_rate,
+            dust_threshold,
+            max_tx_size,
+            max_standard_sigops,
+            max_protected_txs_size,
+            accept_zero_fee_txs,
         } = config_file;
 
         Self {
             min_tx_relay_fee_rate: min_tx_relay_fee_rate
                 .map(|val| FeeRate::from_amount_per_kb(Amount::from_atoms(val.into())))
                 .into(),
+            dust_threshold: dust_threshold.map(|val| Amount::from_atoms(val.into())),
+            max_tx_size,
+            max_standard_sigops: max_standard_sigops.into(),
+            max_protected_txs_size: max_protected_txs_size.into(),
+            accept_zero_fee_txs,
         }
     }
 }
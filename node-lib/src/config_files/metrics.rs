@@ -0,0 +1,61 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::RunOptions;
+
+use super::DEFAULT_METRICS_ENABLED;
+
+/// The metrics exporter configuration.
+#[must_use]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfigFile {
+    /// Whether the Prometheus metrics endpoint is enabled
+    pub metrics_enabled: Option<bool>,
+
+    /// Address to bind the metrics endpoint to
+    pub bind_address: Option<SocketAddr>,
+}
+
+impl MetricsConfigFile {
+    pub const DEFAULT_PORT: u16 = 9531;
+
+    pub fn default_bind_address() -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, Self::DEFAULT_PORT))
+    }
+
+    pub fn with_run_options(config_file: MetricsConfigFile, options: &RunOptions) -> Self {
+        let MetricsConfigFile {
+            metrics_enabled,
+            bind_address,
+        } = config_file;
+
+        let metrics_enabled = options
+            .metrics_enabled
+            .unwrap_or_else(|| metrics_enabled.unwrap_or(DEFAULT_METRICS_ENABLED));
+        let bind_address = options
+            .metrics_bind_address
+            .unwrap_or_else(|| bind_address.unwrap_or_else(Self::default_bind_address));
+
+        MetricsConfigFile {
+            metrics_enabled: Some(metrics_enabled),
+            bind_address: Some(bind_address),
+        }
+    }
+}
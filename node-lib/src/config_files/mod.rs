@@ -17,15 +17,19 @@
 
 pub const DEFAULT_RPC_ENABLED: bool = true;
 pub const DEFAULT_P2P_NETWORKING_ENABLED: bool = true;
+pub const DEFAULT_METRICS_ENABLED: bool = false;
 
 pub use self::{
-    chainstate_launcher::StorageBackendConfigFile, p2p::NodeTypeConfigFile, rpc::RpcConfigFile,
+    chainstate_launcher::StorageBackendConfigFile, logging::LoggingConfigFile,
+    metrics::MetricsConfigFile, p2p::NodeTypeConfigFile, rpc::RpcConfigFile,
 };
 
 mod blockprod;
 mod chainstate;
 mod chainstate_launcher;
+mod logging;
 mod mempool;
+mod metrics;
 mod p2p;
 mod rpc;
 
@@ -54,6 +58,8 @@ pub struct NodeConfigFile {
     pub mempool: Option<MempoolConfigFile>,
     pub p2p: Option<P2pConfigFile>,
     pub rpc: Option<RpcConfigFile>,
+    pub metrics: Option<MetricsConfigFile>,
+    pub logging: Option<LoggingConfigFile>,
 }
 
 impl NodeConfigFile {
@@ -64,6 +70,8 @@ impl NodeConfigFile {
             mempool: None,
             p2p: None,
             rpc: None,
+            metrics: None,
+            logging: None,
         })
     }
 
@@ -93,6 +101,8 @@ impl NodeConfigFile {
             mempool,
             p2p,
             rpc,
+            metrics,
+            logging,
         } = toml::from_str(&config_as_str).context("Failed to parse config")?;
 
         let blockprod = blockprod_config(blockprod.unwrap_or_default(), options);
@@ -100,6 +110,9 @@ impl NodeConfigFile {
         let mempool = MempoolConfigFile::with_run_options(mempool.unwrap_or_default(), options);
         let p2p = p2p_config(p2p.unwrap_or_default(), options);
         let rpc = RpcConfigFile::with_run_options(chain_config, rpc.unwrap_or_default(), options);
+        let metrics = MetricsConfigFile::with_run_options(metrics.unwrap_or_default(), options);
+        let logging = logging.unwrap_or_default();
+        logging.log_style().context("Invalid log style")?;
 
         Ok(Self {
             blockprod: Some(blockprod),
@@ -107,6 +120,8 @@ impl NodeConfigFile {
             mempool: Some(mempool),
             p2p: Some(p2p),
             rpc: Some(rpc),
+            metrics: Some(metrics),
+            logging: Some(logging),
         })
     }
 }
@@ -118,6 +133,8 @@ fn blockprod_config(config: BlockProdConfigFile, options: &RunOptions) -> BlockP
         min_peers_to_produce_blocks,
         skip_ibd_check,
         use_current_time_if_non_pos,
+        min_tx_fee_rate_for_inclusion,
+        reserved_block_space_for_priority_txs,
     } = config;
 
     let min_peers_to_produce_blocks = options
@@ -129,11 +146,19 @@ fn blockprod_config(config: BlockProdConfigFile, options: &RunOptions) -> BlockP
         .blockprod_use_current_time_if_non_pos
         .or(use_current_time_if_non_pos)
         .unwrap_or(false);
+    let min_tx_fee_rate_for_inclusion = options
+        .blockprod_min_tx_fee_rate_for_inclusion
+        .or(min_tx_fee_rate_for_inclusion);
+    let reserved_block_space_for_priority_txs = options
+        .blockprod_reserved_block_space_for_priority_txs
+        .or(reserved_block_space_for_priority_txs);
 
     BlockProdConfigFile {
         min_peers_to_produce_blocks,
         skip_ibd_check: Some(skip_ibd_check),
         use_current_time_if_non_pos: Some(use_current_time_if_non_pos),
+        min_tx_fee_rate_for_inclusion,
+        reserved_block_space_for_priority_txs,
     }
 }
 
@@ -143,6 +168,7 @@ fn chainstate_config(
 ) -> ChainstateLauncherConfigFile {
     let ChainstateLauncherConfigFile {
         storage_backend,
+        lmdb,
         chainstate_config,
     } = config;
 
@@ -151,28 +177,51 @@ fn chainstate_config(
         max_orphan_blocks,
         min_max_bootstrap_import_buffer_sizes,
         max_tip_age,
+        fork_alert_depth,
+        max_block_index_cache_memory,
+        max_utxo_cache_memory,
+        max_orphan_blocks_memory,
+        max_cache_memory,
         enable_heavy_checks,
         allow_checkpoints_mismatch,
+        enable_block_filter_index,
+        undo_data_retention_depth,
     } = chainstate_config;
 
     let storage_backend = options.storage_backend.clone().unwrap_or(storage_backend);
     let max_db_commit_attempts = options.max_db_commit_attempts.or(max_db_commit_attempts);
     let max_orphan_blocks = options.max_orphan_blocks.or(max_orphan_blocks);
     let max_tip_age = options.max_tip_age.or(max_tip_age);
+    let fork_alert_depth = options.fork_alert_depth.or(fork_alert_depth);
+    let max_block_index_cache_memory =
+        options.max_block_index_cache_memory.or(max_block_index_cache_memory);
+    let max_utxo_cache_memory = options.max_utxo_cache_memory.or(max_utxo_cache_memory);
+    let max_orphan_blocks_memory = options.max_orphan_blocks_memory.or(max_orphan_blocks_memory);
+    let max_cache_memory = options.max_cache_memory.or(max_cache_memory);
     let enable_heavy_checks = options.enable_chainstate_heavy_checks.or(enable_heavy_checks);
     let allow_checkpoints_mismatch =
         options.allow_checkpoints_mismatch.or(allow_checkpoints_mismatch);
+    let enable_block_filter_index = options.enable_block_filter_index.or(enable_block_filter_index);
+    let undo_data_retention_depth = options.undo_data_retention_depth.or(undo_data_retention_depth);
 
     let chainstate_config = ChainstateConfigFile {
         max_db_commit_attempts,
         max_orphan_blocks,
         min_max_bootstrap_import_buffer_sizes,
         max_tip_age,
+        fork_alert_depth,
+        max_block_index_cache_memory,
+        max_utxo_cache_memory,
+        max_orphan_blocks_memory,
+        max_cache_memory,
         enable_heavy_checks,
         allow_checkpoints_mismatch,
+        enable_block_filter_index,
+        undo_data_retention_depth,
     };
     ChainstateLauncherConfigFile {
         storage_backend,
+        lmdb,
         chainstate_config,
     }
 }
@@ -186,6 +235,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         boot_nodes,
         reserved_nodes,
         whitelisted_addresses,
+        external_addresses,
         max_inbound_connections,
         discouragement_threshold,
         discouragement_duration,
@@ -194,6 +244,9 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         ping_check_period,
         ping_timeout,
         sync_stalling_timeout,
+        tx_relay_trickle_interval,
+        disable_tx_trickle_for_whitelisted_peers,
+        tx_rebroadcast_interval,
         node_type,
         force_dns_query_if_no_global_addresses_known,
     } = config;
@@ -205,6 +258,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
     let boot_nodes = options.p2p_boot_nodes.clone().or(boot_nodes);
     let reserved_nodes = options.p2p_reserved_nodes.clone().or(reserved_nodes);
     let whitelisted_addresses = options.p2p_whitelist_addr.clone().or(whitelisted_addresses);
+    let external_addresses = options.p2p_external_addresses.clone().or(external_addresses);
     let max_inbound_connections = options.p2p_max_inbound_connections.or(max_inbound_connections);
     let discouragement_threshold =
         options.p2p_discouragement_threshold.or(discouragement_threshold);
@@ -215,6 +269,12 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
     let outbound_connection_timeout =
         options.p2p_outbound_connection_timeout.or(outbound_connection_timeout);
     let sync_stalling_timeout = options.p2p_sync_stalling_timeout.or(sync_stalling_timeout);
+    let tx_relay_trickle_interval =
+        options.p2p_tx_relay_trickle_interval.or(tx_relay_trickle_interval);
+    let disable_tx_trickle_for_whitelisted_peers = options
+        .p2p_disable_tx_trickle_for_whitelisted_peers
+        .or(disable_tx_trickle_for_whitelisted_peers);
+    let tx_rebroadcast_interval = options.p2p_tx_rebroadcast_interval.or(tx_rebroadcast_interval);
     let node_type = options.node_type.or(node_type);
     let force_dns_query_if_no_global_addresses_known = options
         .p2p_force_dns_query_if_no_global_addresses_known
@@ -228,6 +288,7 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         boot_nodes,
         reserved_nodes,
         whitelisted_addresses,
+        external_addresses,
         max_inbound_connections,
         discouragement_threshold,
         discouragement_duration,
@@ -236,6 +297,9 @@ fn p2p_config(config: P2pConfigFile, options: &RunOptions) -> P2pConfigFile {
         ping_check_period,
         ping_timeout,
         sync_stalling_timeout,
+        tx_relay_trickle_interval,
+        disable_tx_trickle_for_whitelisted_peers,
+        tx_rebroadcast_interval,
         node_type,
         force_dns_query_if_no_global_addresses_known,
     }
@@ -257,6 +321,41 @@ mod tests {
         let _config: ChainstateConfigFile = toml::from_str("").unwrap();
         let _config: P2pConfigFile = toml::from_str("").unwrap();
         let _config: RpcConfigFile = toml::from_str("").unwrap();
+        let _config: MetricsConfigFile = toml::from_str("").unwrap();
+        let _config: LoggingConfigFile = toml::from_str("").unwrap();
+    }
+
+    #[test]
+    fn logging_config() {
+        let config: NodeConfigFile = toml::from_str(
+            r#"
+            [logging]
+            log_style = "json"
+
+            [logging.log_levels]
+            chainstate = "debug"
+            p2p = "info"
+            "#,
+        )
+        .unwrap();
+
+        let logging_config = config.logging.unwrap();
+        assert_eq!(
+            logging_config.log_style(),
+            Ok(Some(::logging::LogStyle::Json))
+        );
+        assert_eq!(
+            logging_config.log_levels,
+            Some(
+                [("chainstate", "debug"), ("p2p", "info")]
+                    .into_iter()
+                    .map(|(target, level)| (target.to_owned(), level.to_owned()))
+                    .collect()
+            )
+        );
+
+        let config: LoggingConfigFile = toml::from_str(r#"log_style = "xml""#).unwrap();
+        assert!(config.log_style().is_err());
     }
 
     #[test]
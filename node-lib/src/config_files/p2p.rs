@@ -78,6 +78,8 @@ pub struct P2pConfigFile {
     pub reserved_nodes: Option<Vec<IpOrSocketAddress>>,
     /// Optional list of whitelisted addresses.
     pub whitelisted_addresses: Option<Vec<IpAddr>>,
+    /// Optional list of external addresses of this node to announce to peers.
+    pub external_addresses: Option<Vec<SocketAddr>>,
     /// Maximum allowed number of inbound connections.
     pub max_inbound_connections: Option<usize>,
     /// The score threshold after which a peer becomes discouraged.
@@ -95,6 +97,13 @@ pub struct P2pConfigFile {
     pub ping_timeout: Option<NonZeroU64>,
     /// A timeout after which a peer is disconnected.
     pub sync_stalling_timeout: Option<NonZeroU64>,
+    /// The average interval between transaction announcements sent to a peer (in seconds).
+    pub tx_relay_trickle_interval: Option<u64>,
+    /// If true, transactions are announced to whitelisted peers immediately.
+    pub disable_tx_trickle_for_whitelisted_peers: Option<bool>,
+    /// The average interval between rebroadcasts of the local unconfirmed transactions
+    /// (in seconds); zero disables the rebroadcasts.
+    pub tx_rebroadcast_interval: Option<u64>,
     /// A node type.
     pub node_type: Option<NodeTypeConfigFile>,
     /// If true, the node will perform an early dns query if the peer db doesn't contain
@@ -112,6 +121,7 @@ impl From<P2pConfigFile> for P2pConfig {
             boot_nodes,
             reserved_nodes,
             whitelisted_addresses,
+            external_addresses,
             max_inbound_connections,
             discouragement_threshold,
             discouragement_duration,
@@ -120,6 +130,9 @@ impl From<P2pConfigFile> for P2pConfig {
             ping_check_period,
             ping_timeout,
             sync_stalling_timeout,
+            tx_relay_trickle_interval,
+            disable_tx_trickle_for_whitelisted_peers,
+            tx_rebroadcast_interval,
             node_type,
             force_dns_query_if_no_global_addresses_known,
         } = config_file;
@@ -131,6 +144,7 @@ impl From<P2pConfigFile> for P2pConfig {
             boot_nodes: boot_nodes.unwrap_or_default(),
             reserved_nodes: reserved_nodes.unwrap_or_default(),
             whitelisted_addresses: whitelisted_addresses.unwrap_or_default(),
+            external_addresses: external_addresses.unwrap_or_default(),
             ban_config: BanConfig {
                 discouragement_threshold: discouragement_threshold.into(),
                 discouragement_duration: discouragement_duration.map(Duration::from_secs).into(),
@@ -148,6 +162,10 @@ impl From<P2pConfigFile> for P2pConfig {
             sync_stalling_timeout: sync_stalling_timeout
                 .map(|t| Duration::from_secs(t.into()))
                 .into(),
+            tx_relay_trickle_interval: tx_relay_trickle_interval.map(Duration::from_secs).into(),
+            disable_tx_trickle_for_whitelisted_peers: disable_tx_trickle_for_whitelisted_peers
+                .into(),
+            tx_rebroadcast_interval: tx_rebroadcast_interval.map(Duration::from_secs).into(),
             peer_manager_config: PeerManagerConfig {
                 max_inbound_connections: max_inbound_connections.into(),
 
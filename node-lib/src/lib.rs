@@ -15,6 +15,7 @@
 
 //! Top-level node runner as a library
 
+mod chain_spec;
 mod checkpoints_from_file;
 mod config_files;
 mod mock_time;
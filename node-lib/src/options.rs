@@ -38,6 +38,7 @@ use utils::{
 use utils_networking::IpOrSocketAddress;
 
 use crate::{
+    chain_spec::read_chain_spec_file,
     checkpoints_from_file::read_checkpoints_from_csv_file,
     config_files::{NodeTypeConfigFile, StorageBackendConfigFile},
 };
@@ -160,10 +161,16 @@ impl Command {
             Command::Testnet(run_options) => {
                 (chain::config::Builder::new(ChainType::Testnet), run_options)
             }
-            Command::Regtest(regtest_options) => (
-                regtest_chain_config_builder(&regtest_options.chain_config)?,
-                &regtest_options.run_options,
-            ),
+            Command::Regtest(regtest_options) => {
+                let chain_config_builder = match &regtest_options.chain_spec {
+                    Some(chain_spec_file) => regtest_chain_config_builder(
+                        &read_chain_spec_file(chain_spec_file)?
+                            .merge_with_options(&regtest_options.chain_config)?,
+                    )?,
+                    None => regtest_chain_config_builder(&regtest_options.chain_config)?,
+                };
+                (chain_config_builder, &regtest_options.run_options)
+            }
         };
 
         if let Some(csv_file) = &run_options.custom_checkpoints_csv_file {
@@ -171,7 +178,7 @@ impl Command {
             chain_config_builder = chain_config_builder.checkpoints(checkpoints);
         }
 
-        Ok(chain_config_builder.build())
+        Ok(chain_config_builder.try_build()?)
     }
 
     pub fn chain_type(&self) -> ChainType {
@@ -187,6 +194,13 @@ impl Command {
 pub struct RegtestOptions {
     #[clap(flatten)]
     pub run_options: RunOptions,
+
+    /// Path to a chain spec file (TOML or JSON) describing a custom chain.
+    ///
+    /// The chain options specified on the command line override the ones from the file.
+    #[clap(long)]
+    pub chain_spec: Option<PathBuf>,
+
     #[clap(flatten)]
     pub chain_config: ChainConfigOptions,
 }
@@ -222,6 +236,16 @@ pub struct RunOptions {
     #[arg(hide = true)]
     pub blockprod_use_current_time_if_non_pos: Option<bool>,
 
+    /// Mempool transactions with a lower fee rate (in atoms per 1000 bytes) are not included
+    /// into produced blocks.
+    #[clap(long, value_name = "VAL")]
+    pub blockprod_min_tx_fee_rate_for_inclusion: Option<u64>,
+
+    /// Block space (in bytes) reserved for explicitly requested and locally originating
+    /// transactions.
+    #[clap(long, value_name = "BYTES")]
+    pub blockprod_reserved_block_space_for_priority_txs: Option<usize>,
+
     /// Storage backend to use.
     #[clap(long)]
     pub storage_backend: Option<StorageBackendConfigFile>,
@@ -276,6 +300,12 @@ pub struct RunOptions {
     #[clap(long, value_name = "ADDR", value_delimiter(','))]
     pub p2p_whitelist_addr: Option<Vec<IpAddr>>,
 
+    /// Optional list of external addresses of this node to announce to peers.
+    /// If not specified, the addresses are discovered automatically via outbound connections.
+    /// Can be specified multiple times and/or be a comma-separated list.
+    #[clap(long, value_name = "ADDR", value_delimiter(','))]
+    pub p2p_external_addresses: Option<Vec<SocketAddr>>,
+
     /// Maximum allowed number of inbound connections.
     #[clap(long, value_name = "COUNT")]
     pub p2p_max_inbound_connections: Option<usize>,
@@ -305,6 +335,19 @@ pub struct RunOptions {
     #[clap(long, value_name = "TIMEOUT")]
     pub p2p_sync_stalling_timeout: Option<NonZeroU64>,
 
+    /// The average interval between transaction announcements sent to a peer (in seconds).
+    #[clap(long, value_name = "INTERVAL")]
+    pub p2p_tx_relay_trickle_interval: Option<u64>,
+
+    /// If true, transactions are announced to whitelisted peers immediately.
+    #[clap(long, action = clap::ArgAction::SetTrue)]
+    pub p2p_disable_tx_trickle_for_whitelisted_peers: Option<bool>,
+
+    /// The average interval between rebroadcasts of the local unconfirmed transactions
+    /// (in seconds); zero disables the rebroadcasts.
+    #[clap(long, value_name = "INTERVAL")]
+    pub p2p_tx_rebroadcast_interval: Option<u64>,
+
     /// Maximum acceptable time difference between this node and the remote peer (in seconds).
     /// If a large difference is detected, the peer will be disconnected.
     #[clap(long, value_name = "DIFF")]
@@ -325,6 +368,29 @@ pub struct RunOptions {
     #[clap(long, value_name = "AGE")]
     pub max_tip_age: Option<u64>,
 
+    /// Warn when a block is added to a branch that forked off the main chain more than
+    /// this number of blocks ago.
+    #[clap(long, value_name = "DEPTH")]
+    pub fork_alert_depth: Option<u64>,
+
+    /// The maximum amount of memory, in bytes, used to cache block indices.
+    #[clap(long, value_name = "SIZE")]
+    pub max_block_index_cache_memory: Option<usize>,
+
+    /// The maximum amount of memory, in bytes, used to cache utxos when a chain of blocks is
+    /// verified in memory (e.g. a reorg); it doesn't apply to the initial block download.
+    #[clap(long, value_name = "SIZE")]
+    pub max_utxo_cache_memory: Option<usize>,
+
+    /// The maximum amount of memory, in bytes, used by the orphan blocks pool.
+    #[clap(long, value_name = "SIZE")]
+    pub max_orphan_blocks_memory: Option<usize>,
+
+    /// The global limit of the memory, in bytes, used by all the chainstate caches; when it is
+    /// reached, the caches shrink to their shares of it, proportional to their own limits.
+    #[clap(long, value_name = "SIZE")]
+    pub max_cache_memory: Option<usize>,
+
     /// Address to bind RPC to.
     #[clap(long, value_name = "ADDR")]
     pub rpc_bind_address: Option<SocketAddr>,
@@ -348,10 +414,42 @@ pub struct RunOptions {
     #[clap(long, value_name = "PATH")]
     pub rpc_cookie_file: Option<String>,
 
+    /// Address to bind the Prometheus metrics endpoint to.
+    #[clap(long, value_name = "ADDR")]
+    pub metrics_bind_address: Option<SocketAddr>,
+
+    /// Enable/Disable the Prometheus metrics endpoint (disabled by default).
+    #[clap(long, value_name = "VAL")]
+    pub metrics_enabled: Option<bool>,
+
     /// Minimum transaction relay fee rate (in atoms per 1000 bytes).
     #[clap(long, value_name = "VAL")]
     pub min_tx_relay_fee_rate: Option<u64>,
 
+    /// Coin outputs below this amount (in atoms) are not accepted into the mempool.
+    /// If not set, no outputs are considered dust.
+    #[clap(long, value_name = "VAL")]
+    pub mempool_dust_threshold: Option<u64>,
+
+    /// The maximum size of a transaction accepted into the mempool, in bytes.
+    /// The chain's limit is never exceeded.
+    #[clap(long, value_name = "VAL")]
+    pub mempool_max_tx_size: Option<usize>,
+
+    /// The maximum number of signature checks a transaction accepted into the mempool may require.
+    #[clap(long, value_name = "VAL")]
+    pub mempool_max_standard_sigops: Option<usize>,
+
+    /// The maximum total size of mempool transactions submitted with the "Protected" eviction
+    /// policy, in bytes. Such transactions are not evicted when the mempool is full.
+    #[clap(long, value_name = "VAL")]
+    pub mempool_max_protected_txs_size: Option<usize>,
+
+    /// Accept transactions into the mempool regardless of the fee they pay.
+    /// Only honored on regtest.
+    #[clap(long, action = clap::ArgAction::SetTrue)]
+    pub mempool_accept_zero_fee_txs: Option<bool>,
+
     #[clap(flatten)]
     pub force_allow_run_as_root_outer: ForceRunAsRootOptions,
 
@@ -364,9 +462,24 @@ pub struct RunOptions {
     #[clap(long, action = clap::ArgAction::SetTrue, hide = true)]
     pub allow_checkpoints_mismatch: Option<bool>,
 
+    /// Build compact block filters for the main chain blocks and serve them to light clients.
+    #[clap(long, action = clap::ArgAction::SetTrue)]
+    pub enable_block_filter_index: Option<bool>,
+
+    /// Only keep the undo data for this number of the most recent main chain blocks.
+    /// Blocks deeper than that can no longer be disconnected during a reorg.
+    #[clap(long, value_name = "DEPTH")]
+    pub undo_data_retention_depth: Option<u64>,
+
     /// Path to a CSV file with custom checkpoints that must be used instead of the predefined ones.
     #[clap(long, hide = true)]
     pub custom_checkpoints_csv_file: Option<PathBuf>,
+
+    /// Path to a bootstrap file (as produced by `chainstate_export_bootstrap_file` on another
+    /// node) whose blocks will be imported before the node is started.
+    /// An import that was interrupted previously is resumed.
+    #[clap(long, value_name = "FILE")]
+    pub import_blocks: Option<PathBuf>,
 }
 
 pub fn default_data_dir(chain_type: ChainType) -> PathBuf {
@@ -405,6 +518,8 @@ mod tests {
             blockprod_min_peers_to_produce_blocks: Default::default(),
             blockprod_skip_ibd_check: Default::default(),
             blockprod_use_current_time_if_non_pos: Default::default(),
+            blockprod_min_tx_fee_rate_for_inclusion: Default::default(),
+            blockprod_reserved_block_space_for_priority_txs: Default::default(),
             storage_backend: Default::default(),
             node_type: Default::default(),
             mock_time: Default::default(),
@@ -417,6 +532,7 @@ mod tests {
             p2p_boot_nodes: Default::default(),
             p2p_reserved_nodes: Default::default(),
             p2p_whitelist_addr: Default::default(),
+            p2p_external_addresses: Default::default(),
             p2p_max_inbound_connections: Default::default(),
             p2p_discouragement_threshold: Default::default(),
             p2p_discouragement_duration: Default::default(),
@@ -424,25 +540,44 @@ mod tests {
             p2p_ping_check_period: Default::default(),
             p2p_ping_timeout: Default::default(),
             p2p_sync_stalling_timeout: Default::default(),
+            p2p_tx_relay_trickle_interval: Default::default(),
+            p2p_disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            p2p_tx_rebroadcast_interval: Default::default(),
             p2p_max_clock_diff: Default::default(),
             p2p_force_dns_query_if_no_global_addresses_known: Default::default(),
             max_tip_age: Default::default(),
+            fork_alert_depth: Default::default(),
+            max_block_index_cache_memory: Default::default(),
+            max_utxo_cache_memory: Default::default(),
+            max_orphan_blocks_memory: Default::default(),
+            max_cache_memory: Default::default(),
             rpc_bind_address: Default::default(),
             rpc_enabled: Default::default(),
             rpc_username: Default::default(),
             rpc_password: Default::default(),
             rpc_cookie_file: Default::default(),
+            metrics_bind_address: Default::default(),
+            metrics_enabled: Default::default(),
             min_tx_relay_fee_rate: Default::default(),
+            mempool_dust_threshold: Default::default(),
+            mempool_max_tx_size: Default::default(),
+            mempool_max_standard_sigops: Default::default(),
+            mempool_max_protected_txs_size: Default::default(),
+            mempool_accept_zero_fee_txs: Default::default(),
             force_allow_run_as_root_outer: Default::default(),
             enable_chainstate_heavy_checks: Default::default(),
             allow_checkpoints_mismatch: Default::default(),
+            enable_block_filter_index: Default::default(),
+            undo_data_retention_depth: Default::default(),
             custom_checkpoints_csv_file,
+            import_blocks: Default::default(),
         };
         let make_cmd = |run_options| match chain_type {
             ChainType::Mainnet => Command::Mainnet(run_options),
             ChainType::Testnet => Command::Testnet(run_options),
             ChainType::Regtest => Command::Regtest(Box::new(RegtestOptions {
                 run_options,
+                chain_spec: None,
                 chain_config: Default::default(),
             })),
             ChainType::Signet => panic!("Signet is not among possible chain types for this test"),
@@ -17,9 +17,105 @@
 
 use std::{sync::Arc, time::Duration};
 
+use chainstate::{ChainstateError, ChainstateHandle, ChainstateInterface, PropertyQueryError};
 use chainstate_launcher::ChainConfig;
+use common::{
+    chain::{Block, GenBlock, Transaction},
+    primitives::{BlockHeight, Id, Idable},
+};
+use mempool::{MempoolHandle, MempoolTxInfo};
+use p2p::P2pHandle;
 use rpc::{description::Described, handle_result, RpcResult};
-use subsystem::ShutdownTrigger;
+use subsystem::{HealthMonitor, ShutdownTrigger, SubsystemHealth};
+
+/// Status of a transaction as seen by the mempool and the chainstate together
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+#[serde(tag = "type", content = "content")]
+pub enum TransactionStatus {
+    /// The transaction is waiting in the mempool to be included in a block
+    InMempool { info: MempoolTxInfo },
+
+    /// The transaction is in the orphan pool because some of its inputs are unknown
+    InOrphanPool {},
+
+    /// The transaction is included in a block on the main chain
+    Confirmed {
+        block_id: Id<Block>,
+        block_height: BlockHeight,
+        confirmations: u64,
+        /// The block is below the height where reorgs are allowed, so the transaction cannot
+        /// be reverted by a reorg anymore
+        is_final: bool,
+    },
+
+    /// The transaction is neither in the mempool nor in the most recent main chain blocks.
+    /// It may have been confirmed earlier than that, or it may be unknown.
+    NotFound {},
+}
+
+/// Health of a single subsystem as reported by the subsystem manager
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct SubsystemHealthInfo {
+    pub name: String,
+    /// Whether the subsystem is still running
+    pub alive: bool,
+    /// Time of the last health report of the subsystem, in seconds since the Unix epoch
+    pub last_activity: Option<u64>,
+    /// Number of calls waiting to be processed by the subsystem
+    pub queue_depth: usize,
+}
+
+impl From<SubsystemHealth> for SubsystemHealthInfo {
+    fn from(health: SubsystemHealth) -> Self {
+        let SubsystemHealth {
+            name,
+            alive,
+            last_activity,
+            queue_depth,
+        } = health;
+        Self {
+            name,
+            alive,
+            last_activity,
+            queue_depth,
+        }
+    }
+}
+
+/// Overall status of the node
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    rpc::description::HasValueHint,
+)]
+pub struct NodeStatus {
+    pub best_block_id: Id<GenBlock>,
+    pub best_block_height: BlockHeight,
+    /// Total memory usage of the transactions in the mempool, in bytes
+    pub mempool_memory_usage: usize,
+    pub peer_count: usize,
+    pub subsystems: Vec<SubsystemHealthInfo>,
+}
 
 /// RPC methods controlling the node.
 #[rpc::describe]
@@ -39,22 +135,100 @@ pub trait NodeRpc {
     /// WARNING: This function is strictly used for testing purposes. Using it will obstruct normal node functionality.
     #[method(name = "set_mock_time")]
     fn set_mock_time(&self, time: u64) -> RpcResult<()>;
+
+    /// Get the status of a transaction, combining the mempool and the chainstate views.
+    ///
+    /// The mempool is queried first, then the 10 most recent main chain blocks are searched.
+    /// Transactions confirmed deeper than that are reported as not found since the node
+    /// doesn't keep a transaction index.
+    #[method(name = "get_transaction_status")]
+    async fn get_transaction_status(&self, tx_id: Id<Transaction>) -> RpcResult<TransactionStatus>;
+
+    /// Get the overall status of the node in one call.
+    ///
+    /// This includes the chainstate tip, the mempool size, the number of connected peers
+    /// and the health of each subsystem.
+    #[method(name = "status")]
+    async fn node_status(&self) -> RpcResult<NodeStatus>;
+
+    /// Set the log level for the specified target, e.g. "chainstate" or "p2p::sync".
+    ///
+    /// The level is one of "off", "error", "warn", "info", "debug" or "trace".
+    /// The change only affects the terminal output (not the log file) and is not persisted.
+    #[method(name = "set_log_level")]
+    fn set_log_level(&self, target: String, level: String) -> RpcResult<()>;
 }
 
 struct NodeRpc {
     shutdown_trigger: ShutdownTrigger,
     chain_config: Arc<ChainConfig>,
+    chainstate: ChainstateHandle,
+    mempool: MempoolHandle,
+    p2p: P2pHandle,
+    health_monitor: HealthMonitor,
 }
 
 impl NodeRpc {
-    fn new(shutdown_trigger: ShutdownTrigger, chain_config: Arc<ChainConfig>) -> Self {
+    fn new(
+        shutdown_trigger: ShutdownTrigger,
+        chain_config: Arc<ChainConfig>,
+        chainstate: ChainstateHandle,
+        mempool: MempoolHandle,
+        p2p: P2pHandle,
+        health_monitor: HealthMonitor,
+    ) -> Self {
         Self {
             shutdown_trigger,
             chain_config,
+            chainstate,
+            mempool,
+            p2p,
+            health_monitor,
         }
     }
 }
 
+/// The number of the most recent main chain blocks that are searched for a transaction
+/// whose status is requested
+const TX_STATUS_SEARCH_DEPTH: u64 = 10;
+
+/// Search the most recent main chain blocks for the transaction, going down from the tip one
+/// block at a time
+fn find_recent_confirmation(
+    chainstate: &dyn ChainstateInterface,
+    tx_id: Id<Transaction>,
+) -> Result<TransactionStatus, ChainstateError> {
+    let chain_config = chainstate.get_chain_config();
+    let tip_height = chainstate.get_best_block_height()?;
+    let start_height = tip_height.into_int().saturating_sub(TX_STATUS_SEARCH_DEPTH - 1).max(1);
+    // The blocks at this height and below can't be disconnected by a reorg
+    let min_height_with_allowed_reorg = chainstate.get_min_height_with_allowed_reorg()?;
+
+    for height in (start_height..=tip_height.into_int()).rev() {
+        let block_height = BlockHeight::new(height);
+        let block_id = chainstate
+            .get_block_id_from_height(&block_height)?
+            .and_then(|id| id.to_block_id(chain_config))
+            .ok_or(PropertyQueryError::BlockForHeightNotFound(block_height))?;
+        let block = chainstate
+            .get_block(block_id)?
+            .ok_or(PropertyQueryError::BlockNotFound(block_id))?;
+
+        let contains_tx = block.transactions().iter().any(|tx| tx.transaction().get_id() == tx_id);
+        if contains_tx {
+            return Ok(TransactionStatus::Confirmed {
+                block_id,
+                block_height,
+                confirmations: tip_height.into_int() - height + 1,
+                is_final: block_height <= min_height_with_allowed_reorg,
+            });
+        }
+    }
+
+    Ok(TransactionStatus::NotFound {})
+}
+
+#[async_trait::async_trait]
 impl NodeRpcServer for NodeRpc {
     fn shutdown(&self) -> RpcResult<()> {
         // There is no easy way to gracefully shut down the jsonrpsee server to make it finish existing RPC requests first.
@@ -80,10 +254,72 @@ impl NodeRpcServer for NodeRpc {
             time,
         ))
     }
+
+    async fn get_transaction_status(&self, tx_id: Id<Transaction>) -> RpcResult<TransactionStatus> {
+        // The mempool has to be checked first. If the transaction gets included in a block in
+        // the meantime, it will still be found in the chainstate.
+        let mempool_status = handle_result(
+            self.mempool
+                .call(move |m| {
+                    m.transaction_info(&tx_id)
+                        .map(|info| TransactionStatus::InMempool { info })
+                        .or_else(|| {
+                            m.contains_orphan_transaction(&tx_id)
+                                .then_some(TransactionStatus::InOrphanPool {})
+                        })
+                })
+                .await,
+        )?;
+
+        if let Some(status) = mempool_status {
+            return Ok(status);
+        }
+
+        handle_result(self.chainstate.call(move |c| find_recent_confirmation(c, tx_id)).await)
+    }
+
+    async fn node_status(&self) -> RpcResult<NodeStatus> {
+        // Collect the subsystem health first so that it's not affected by the calls below
+        let subsystems =
+            self.health_monitor.subsystem_health().into_iter().map(Into::into).collect();
+
+        let (best_block_height, best_block_id) =
+            handle_result(self.chainstate.call(|c| c.get_best_block_height_and_id()).await)?;
+        let mempool_memory_usage = handle_result(self.mempool.call(|m| m.memory_usage()).await)?;
+        let peer_count = handle_result(self.p2p.call_async(|p| p.get_peer_count()).await)?;
+
+        Ok(NodeStatus {
+            best_block_id,
+            best_block_height,
+            mempool_memory_usage,
+            peer_count,
+            subsystems,
+        })
+    }
+
+    fn set_log_level(&self, target: String, level: String) -> RpcResult<()> {
+        handle_result(logging::set_log_level(&target, &level))
+    }
 }
 
-pub fn init(shutdown_trigger: ShutdownTrigger, chain_config: Arc<ChainConfig>) -> rpc::Methods {
-    NodeRpc::new(shutdown_trigger, chain_config).into_rpc().into()
+pub fn init(
+    shutdown_trigger: ShutdownTrigger,
+    chain_config: Arc<ChainConfig>,
+    chainstate: ChainstateHandle,
+    mempool: MempoolHandle,
+    p2p: P2pHandle,
+    health_monitor: HealthMonitor,
+) -> rpc::Methods {
+    NodeRpc::new(
+        shutdown_trigger,
+        chain_config,
+        chainstate,
+        mempool,
+        p2p,
+        health_monitor,
+    )
+    .into_rpc()
+    .into()
 }
 
 pub fn interface_description() -> rpc::description::Interface {
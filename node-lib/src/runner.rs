@@ -21,12 +21,15 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
 use file_rotate::{compression::Compression, suffix::AppendCount, ContentLimit, FileRotate};
 
 use blockprod::rpc::BlockProductionRpcServer;
-use chainstate::{rpc::ChainstateRpcServer, ChainstateError, InitializationError};
-use chainstate_launcher::{ChainConfig, StorageBackendConfig};
+use chainstate::{
+    rpc::ChainstateRpcServer, BootstrapImportProgress, ChainstateError, DbVerificationLevel,
+    InitializationError,
+};
+use chainstate_launcher::{ChainConfig, ChainstateLauncherConfig};
 use common::chain::config::{assert_no_ignore_consensus_in_chain_config, ChainType};
 use logging::log;
 use mempool::rpc::MempoolRpcServer;
@@ -37,7 +40,10 @@ use test_rpc_functions::{
 };
 
 use crate::{
-    config_files::{NodeConfigFile, DEFAULT_P2P_NETWORKING_ENABLED, DEFAULT_RPC_ENABLED},
+    config_files::{
+        MetricsConfigFile, NodeConfigFile, DEFAULT_METRICS_ENABLED, DEFAULT_P2P_NETWORKING_ENABLED,
+        DEFAULT_RPC_ENABLED,
+    },
     mock_time::set_mock_time,
     node_controller::NodeController,
     options::{default_data_dir, OptionsWithResolvedCommand, RunOptions},
@@ -47,6 +53,10 @@ use crate::{
 const LOCK_FILE_NAME: &str = ".lock";
 const DEFAULT_LOG_FILE_NAME: &str = "mintlayer.log";
 
+/// The number of the last mainchain blocks whose utxo changes are re-derived when verifying
+/// the database after a block import.
+const IMPORT_BLOCKS_VERIFICATION_DEPTH: usize = 100;
+
 pub enum NodeSetupResult {
     Node(Node),
     DataDirCleanedUp,
@@ -187,6 +197,18 @@ async fn initialize(
         manager.add_direct_subsystem("rpc_test_functions", make_empty_rpc_test_functions())
     };
 
+    // Metrics exporter subsystem
+    let metrics_config = node_config.metrics.unwrap_or_default();
+    if metrics_config.metrics_enabled.unwrap_or(DEFAULT_METRICS_ENABLED) {
+        let metrics_server = metrics::MetricsServer::start(
+            metrics_config
+                .bind_address
+                .unwrap_or_else(MetricsConfigFile::default_bind_address),
+        )
+        .await?;
+        let _metrics_server = manager.add_subsystem("metrics", metrics_server);
+    }
+
     // RPC subsystem
     let rpc_config = node_config.rpc.unwrap_or_default();
     if rpc_config.rpc_enabled.unwrap_or(DEFAULT_RPC_ENABLED) {
@@ -207,6 +229,10 @@ async fn initialize(
         .register(crate::rpc::init(
             manager.make_shutdown_trigger(),
             chain_config,
+            chainstate.clone(),
+            mempool.clone(),
+            p2p.clone(),
+            manager.make_health_monitor(),
         ))
         .register(block_prod.clone().into_rpc())
         .register(chainstate.clone().into_rpc())
@@ -255,7 +281,20 @@ pub async fn setup(options: OptionsWithResolvedCommand) -> Result<NodeSetupResul
         return Ok(NodeSetupResult::DataDirCleanedUp);
     }
 
-    let main_log_writer_settings = logging::default_writer_settings();
+    // The config has to be read before the logging is initialized, because it contains
+    // the logging settings.
+    let node_config = NodeConfigFile::read(
+        &chain_config,
+        &options.top_level.config_path(*chain_config.chain_type()),
+        run_options,
+    )
+    .context("Failed to initialize config")?;
+    let logging_config = node_config.logging.clone().unwrap_or_default();
+
+    let mut main_log_writer_settings = logging::default_writer_settings();
+    if let Some(log_style) = logging_config.log_style()? {
+        main_log_writer_settings.log_style = logging::ValueOrEnvVar::Value(log_style);
+    }
 
     // Init logging
     if options.log_to_file_option_set() {
@@ -291,15 +330,15 @@ pub async fn setup(options: OptionsWithResolvedCommand) -> Result<NodeSetupResul
         logging::init_logging_generic(main_log_writer_settings, logging::no_writer_settings());
     }
 
+    for (target, level) in logging_config.log_levels.iter().flatten() {
+        if let Err(err) = logging::set_log_level(target, level) {
+            log::error!("Failed to set log level {level} for {target}: {err}");
+        }
+    }
+
     logging::log::info!("Command line options: {options:?}");
 
-    let (manager, controller) = start(
-        &options.top_level.config_path(*chain_config.chain_type()),
-        &data_dir,
-        run_options,
-        chain_config,
-    )
-    .await?;
+    let (manager, controller) = start(node_config, &data_dir, run_options, chain_config).await?;
 
     Ok(NodeSetupResult::Node(Node {
         manager,
@@ -338,7 +377,7 @@ fn clean_data_dir(data_dir: &Path, exclude: &[&Path]) -> Result<()> {
 }
 
 async fn start(
-    config_path: &Path,
+    node_config: NodeConfigFile,
     datadir_path: &Path,
     run_options: &RunOptions,
     chain_config: ChainConfig,
@@ -349,9 +388,6 @@ async fn start(
         set_mock_time(*chain_config.chain_type(), mock_time)?;
     }
 
-    let node_config = NodeConfigFile::read(&chain_config, config_path, run_options)
-        .context("Failed to initialize config")?;
-
     log::info!(
         "Starting mintlayer-core version {}",
         chain_config.software_version()
@@ -359,6 +395,10 @@ async fn start(
 
     log::info!("Starting with the following config:\n {node_config:#?}");
 
+    if let Some(bootstrap_file) = &run_options.import_blocks {
+        import_blocks(&chain_config, datadir_path, &node_config, bootstrap_file)?;
+    }
+
     let (manager, controller) = match initialize(
         chain_config.clone(),
         datadir_path,
@@ -373,11 +413,13 @@ async fn start(
             )) => {
                 log::warn!("Failed to init chainstate: {e} \n Cleaning up current db and trying from scratch.");
 
-                let storage_config: StorageBackendConfig =
-                    node_config.chainstate.clone().unwrap_or_default().storage_backend.into();
+                let storage_config: ChainstateLauncherConfig =
+                    node_config.chainstate.clone().unwrap_or_default().into();
 
                 // cleanup storage directory and retry initialization
-                if let Some(storage_subdir_name) = storage_config.subdirectory_name() {
+                if let Some(storage_subdir_name) =
+                    storage_config.storage_backend.subdirectory_name()
+                {
                     let path = datadir_path.join(storage_subdir_name);
                     if path.exists() {
                         std::fs::remove_dir_all(path)
@@ -393,3 +435,57 @@ async fn start(
 
     Ok((manager, controller))
 }
+
+/// Import the blocks from the bootstrap file and verify the resulting database.
+fn import_blocks(
+    chain_config: &ChainConfig,
+    datadir_path: &Path,
+    node_config: &NodeConfigFile,
+    bootstrap_file: &Path,
+) -> Result<()> {
+    let file = File::open(bootstrap_file)
+        .map_err(|e| anyhow!("Cannot open bootstrap file {bootstrap_file:?}: {e}"))?;
+    let file_size = file.metadata()?.len();
+
+    let mut chainstate = chainstate_launcher::make_chainstate(
+        datadir_path,
+        Arc::new(chain_config.clone()),
+        node_config.chainstate.clone().unwrap_or_default().into(),
+    )?;
+
+    log::info!("Importing blocks from {bootstrap_file:?}");
+
+    let reader = std::io::BufReader::new(Box::new(file) as Box<dyn std::io::Read + Send>);
+    let progress_callback = Box::new(move |progress: BootstrapImportProgress| {
+        log::info!(
+            "Block import progress: {} blocks imported, {} skipped, {} of {} bytes read ({}%)",
+            progress.blocks_imported,
+            progress.blocks_skipped,
+            progress.bytes_read,
+            file_size,
+            progress.bytes_read * 100 / std::cmp::max(file_size, 1),
+        );
+    });
+    chainstate
+        .import_bootstrap_stream(reader, progress_callback)
+        .context("Block import failed")?;
+
+    log::info!("Verifying the database after block import");
+
+    let errors = chainstate
+        .verify_database(DbVerificationLevel::Utxos, IMPORT_BLOCKS_VERIFICATION_DEPTH)
+        .context("Database verification after block import failed")?;
+    ensure!(
+        errors.is_empty(),
+        "Database is inconsistent after block import: {errors:?}"
+    );
+
+    let best_block = chainstate.get_best_block_index()?;
+    log::info!(
+        "Block import finished, best block: {} at height {}",
+        best_block.block_id(),
+        best_block.block_height()
+    );
+
+    Ok(())
+}
@@ -97,6 +97,8 @@ fn read_config_override_values() {
     let blockprod_min_peers_to_produce_blocks = 10;
     let blockprod_skip_ibd_check = true;
     let blockprod_use_current_time_if_non_pos = true;
+    let blockprod_min_tx_fee_rate_for_inclusion = 5000;
+    let blockprod_reserved_block_space_for_priority_txs = 10_000;
     let max_db_commit_attempts = 1;
     let max_orphan_blocks = 2;
     let p2p_networking_enabled = false;
@@ -105,6 +107,7 @@ fn read_config_override_values() {
     let p2p_disable_noise = false;
     let p2p_boot_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
     let p2p_reserved_node: IpOrSocketAddress = "127.0.0.1".parse().unwrap();
+    let p2p_external_addr = "1.2.3.4:3031".parse::<SocketAddr>().unwrap();
     let p2p_max_inbound_connections = 123;
     let p2p_discouragement_threshold = 3;
     let p2p_discouragement_duration = 234;
@@ -112,23 +115,43 @@ fn read_config_override_values() {
     let p2p_ping_check_period = 30;
     let p2p_ping_timeout = NonZeroU64::new(60).unwrap();
     let p2p_sync_stalling_timeout = NonZeroU64::new(37).unwrap();
+    let p2p_tx_relay_trickle_interval = 3;
+    let p2p_disable_tx_trickle_for_whitelisted_peers = true;
+    let p2p_tx_rebroadcast_interval = 600;
     let p2p_max_clock_diff = 15;
     let p2p_force_dns_query_if_no_global_addresses_known = true;
     let rpc_bind_address = "127.0.0.1:5432".parse().unwrap();
+    let metrics_bind_address = "127.0.0.1:5433".parse().unwrap();
     let backend_type = StorageBackendConfigFile::InMemory;
     let node_type = NodeTypeConfigFile::FullNode;
     let max_tip_age = 1000;
+    let fork_alert_depth = 10;
+    let max_block_index_cache_memory = 1 << 20;
+    let max_utxo_cache_memory = 1 << 21;
+    let max_orphan_blocks_memory = 1 << 22;
+    let max_cache_memory = 1 << 23;
     let rpc_username = "username";
     let rpc_password = "password";
     let rpc_cookie_file = "cookie_file";
     let min_tx_relay_fee_rate = 321;
+    let mempool_dust_threshold = 654;
+    let mempool_max_tx_size = 50_000;
+    let mempool_max_standard_sigops = 20;
+    let mempool_max_protected_txs_size = 100_000;
+    let mempool_accept_zero_fee_txs = true;
     let enable_chainstate_heavy_checks = true;
     let allow_checkpoints_mismatch = true;
+    let enable_block_filter_index = true;
+    let undo_data_retention_depth = 5000;
 
     let options = RunOptions {
         blockprod_min_peers_to_produce_blocks: Some(blockprod_min_peers_to_produce_blocks),
         blockprod_skip_ibd_check: Some(blockprod_skip_ibd_check),
         blockprod_use_current_time_if_non_pos: Some(blockprod_use_current_time_if_non_pos),
+        blockprod_min_tx_fee_rate_for_inclusion: Some(blockprod_min_tx_fee_rate_for_inclusion),
+        blockprod_reserved_block_space_for_priority_txs: Some(
+            blockprod_reserved_block_space_for_priority_txs,
+        ),
         storage_backend: Some(backend_type.clone()),
         node_type: Some(node_type),
         mock_time: None,
@@ -147,25 +170,47 @@ fn read_config_override_values() {
         p2p_ping_check_period: Some(p2p_ping_check_period),
         p2p_ping_timeout: Some(p2p_ping_timeout),
         p2p_sync_stalling_timeout: Some(p2p_sync_stalling_timeout),
+        p2p_tx_relay_trickle_interval: Some(p2p_tx_relay_trickle_interval),
+        p2p_disable_tx_trickle_for_whitelisted_peers: Some(
+            p2p_disable_tx_trickle_for_whitelisted_peers,
+        ),
+        p2p_tx_rebroadcast_interval: Some(p2p_tx_rebroadcast_interval),
         p2p_max_clock_diff: Some(p2p_max_clock_diff),
         p2p_whitelist_addr: None,
+        p2p_external_addresses: Some(vec![p2p_external_addr]),
         p2p_force_dns_query_if_no_global_addresses_known: Some(
             p2p_force_dns_query_if_no_global_addresses_known,
         ),
         max_tip_age: Some(max_tip_age),
+        fork_alert_depth: Some(fork_alert_depth),
+        max_block_index_cache_memory: Some(max_block_index_cache_memory),
+        max_utxo_cache_memory: Some(max_utxo_cache_memory),
+        max_orphan_blocks_memory: Some(max_orphan_blocks_memory),
+        max_cache_memory: Some(max_cache_memory),
         rpc_bind_address: Some(rpc_bind_address),
         rpc_enabled: Some(true),
         rpc_username: Some(rpc_username.to_owned()),
         rpc_password: Some(rpc_password.to_owned()),
         rpc_cookie_file: Some(rpc_cookie_file.to_owned()),
+        metrics_bind_address: Some(metrics_bind_address),
+        metrics_enabled: Some(true),
         clean_data: Some(false),
         min_tx_relay_fee_rate: Some(min_tx_relay_fee_rate),
+        mempool_dust_threshold: Some(mempool_dust_threshold),
+        mempool_max_tx_size: Some(mempool_max_tx_size),
+        mempool_max_standard_sigops: Some(mempool_max_standard_sigops),
+        mempool_max_protected_txs_size: Some(mempool_max_protected_txs_size),
+        mempool_accept_zero_fee_txs: Some(mempool_accept_zero_fee_txs),
         force_allow_run_as_root_outer: Default::default(),
         enable_chainstate_heavy_checks: Some(enable_chainstate_heavy_checks),
         allow_checkpoints_mismatch: Some(allow_checkpoints_mismatch),
+        enable_block_filter_index: Some(enable_block_filter_index),
+        undo_data_retention_depth: Some(undo_data_retention_depth),
         // Note: there is no correspondence to this option inside NodeConfigFile;
         // the contents of the csv file will become part of ChainConfig.
         custom_checkpoints_csv_file: Some("foo.csv".to_owned().into()),
+        // Note: this option doesn't have a correspondence inside NodeConfigFile either.
+        import_blocks: Some("bootstrap.bin".to_owned().into()),
     };
     let config = NodeConfigFile::read(&chain_config, &config_path, &options).unwrap();
 
@@ -184,6 +229,16 @@ fn read_config_override_values() {
         Some(blockprod_use_current_time_if_non_pos)
     );
 
+    assert_eq!(
+        config.blockprod.as_ref().unwrap().min_tx_fee_rate_for_inclusion,
+        Some(blockprod_min_tx_fee_rate_for_inclusion)
+    );
+
+    assert_eq!(
+        config.blockprod.as_ref().unwrap().reserved_block_space_for_priority_txs,
+        Some(blockprod_reserved_block_space_for_priority_txs)
+    );
+
     assert_eq!(
         config.chainstate.as_ref().unwrap().chainstate_config.max_db_commit_attempts,
         Some(max_db_commit_attempts)
@@ -196,11 +251,56 @@ fn read_config_override_values() {
         config.chainstate.as_ref().unwrap().chainstate_config.max_tip_age,
         Some(max_tip_age)
     );
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.fork_alert_depth,
+        Some(fork_alert_depth)
+    );
+    assert_eq!(
+        config
+            .chainstate
+            .as_ref()
+            .unwrap()
+            .chainstate_config
+            .max_block_index_cache_memory,
+        Some(max_block_index_cache_memory)
+    );
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.max_utxo_cache_memory,
+        Some(max_utxo_cache_memory)
+    );
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.max_orphan_blocks_memory,
+        Some(max_orphan_blocks_memory)
+    );
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.max_cache_memory,
+        Some(max_cache_memory)
+    );
 
     assert_eq!(
-        config.mempool.unwrap().min_tx_relay_fee_rate,
+        config.mempool.as_ref().unwrap().min_tx_relay_fee_rate,
         Some(min_tx_relay_fee_rate)
     );
+    assert_eq!(
+        config.mempool.as_ref().unwrap().dust_threshold,
+        Some(mempool_dust_threshold)
+    );
+    assert_eq!(
+        config.mempool.as_ref().unwrap().max_tx_size,
+        Some(mempool_max_tx_size)
+    );
+    assert_eq!(
+        config.mempool.as_ref().unwrap().max_standard_sigops,
+        Some(mempool_max_standard_sigops)
+    );
+    assert_eq!(
+        config.mempool.as_ref().unwrap().max_protected_txs_size,
+        Some(mempool_max_protected_txs_size)
+    );
+    assert_eq!(
+        config.mempool.as_ref().unwrap().accept_zero_fee_txs,
+        Some(mempool_accept_zero_fee_txs)
+    );
 
     assert_eq!(
         config.chainstate.as_ref().unwrap().chainstate_config.enable_heavy_checks,
@@ -212,6 +312,16 @@ fn read_config_override_values() {
         Some(allow_checkpoints_mismatch)
     );
 
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.enable_block_filter_index,
+        Some(enable_block_filter_index)
+    );
+
+    assert_eq!(
+        config.chainstate.as_ref().unwrap().chainstate_config.undo_data_retention_depth,
+        Some(undo_data_retention_depth)
+    );
+
     assert_eq!(
         config.p2p.as_ref().unwrap().networking_enabled,
         Some(p2p_networking_enabled)
@@ -236,6 +346,10 @@ fn read_config_override_values() {
         config.p2p.as_ref().unwrap().reserved_nodes,
         Some(vec!(p2p_reserved_node))
     );
+    assert_eq!(
+        config.p2p.as_ref().unwrap().external_addresses,
+        Some(vec!(p2p_external_addr))
+    );
     assert_eq!(
         config.p2p.as_ref().unwrap().max_inbound_connections,
         Some(p2p_max_inbound_connections)
@@ -264,6 +378,18 @@ fn read_config_override_values() {
         config.p2p.as_ref().unwrap().sync_stalling_timeout,
         Some(p2p_sync_stalling_timeout)
     );
+    assert_eq!(
+        config.p2p.as_ref().unwrap().tx_relay_trickle_interval,
+        Some(p2p_tx_relay_trickle_interval)
+    );
+    assert_eq!(
+        config.p2p.as_ref().unwrap().disable_tx_trickle_for_whitelisted_peers,
+        Some(p2p_disable_tx_trickle_for_whitelisted_peers)
+    );
+    assert_eq!(
+        config.p2p.as_ref().unwrap().tx_rebroadcast_interval,
+        Some(p2p_tx_rebroadcast_interval)
+    );
     assert_eq!(
         config.p2p.as_ref().unwrap().max_clock_diff,
         Some(p2p_max_clock_diff)
@@ -293,5 +419,11 @@ fn read_config_override_values() {
         Some(rpc_cookie_file)
     );
 
+    assert_eq!(
+        config.metrics.as_ref().unwrap().bind_address,
+        Some(metrics_bind_address)
+    );
+    assert!(config.metrics.as_ref().unwrap().metrics_enabled.unwrap());
+
     assert_eq!(config.chainstate.unwrap().storage_backend, backend_type);
 }
@@ -126,6 +126,7 @@ where
                 peer_id,
                 common_services: _,
                 protocol_version: _,
+                is_whitelisted: _,
                 block_sync_msg_receiver,
                 transaction_sync_msg_receiver: _,
             } => (peer_id, block_sync_msg_receiver),
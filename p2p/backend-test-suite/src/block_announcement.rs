@@ -104,6 +104,7 @@ where
             peer_id: _,
             common_services: _,
             protocol_version: _,
+            is_whitelisted: _,
             block_sync_msg_receiver,
             transaction_sync_msg_receiver: _,
         } => block_sync_msg_receiver,
@@ -141,6 +142,7 @@ where
             peer_id: _,
             common_services: _,
             protocol_version: _,
+            is_whitelisted: _,
             block_sync_msg_receiver,
             transaction_sync_msg_receiver: _,
         } => block_sync_msg_receiver,
@@ -182,6 +184,7 @@ where
         boot_nodes: Vec::new(),
         reserved_nodes: Vec::new(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -191,6 +194,9 @@ where
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
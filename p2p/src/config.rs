@@ -35,6 +35,13 @@ make_config_setting!(PingTimeout, Duration, Duration::from_secs(150));
 make_config_setting!(MaxClockDiff, Duration, Duration::from_secs(10));
 make_config_setting!(SyncStallingTimeout, Duration, Duration::from_secs(25));
 make_config_setting!(PeerHandshakeTimeout, Duration, Duration::from_secs(10));
+make_config_setting!(TxRelayTrickleInterval, Duration, Duration::from_secs(5));
+make_config_setting!(DisableTxTrickleForWhitelistedPeers, bool, false);
+make_config_setting!(
+    TxRebroadcastInterval,
+    Duration,
+    Duration::from_secs(30 * 60)
+);
 
 /// A node type.
 #[derive(Debug, Copy, Clone)]
@@ -82,6 +89,10 @@ pub struct P2pConfig {
     pub reserved_nodes: Vec<IpOrSocketAddress>,
     /// Optional list of whitelisted addresses. Such addresses cannot be automatically banned.
     pub whitelisted_addresses: Vec<IpAddr>,
+    /// Optional list of external addresses of this node.
+    /// If set, these addresses are announced to peers instead of the ones discovered via
+    /// outbound connections (useful when the node is behind NAT with port forwarding).
+    pub external_addresses: Vec<SocketAddr>,
     /// Settings related to banning and discouragement.
     pub ban_config: BanConfig,
     /// The outbound connection timeout value in seconds.
@@ -103,6 +114,16 @@ pub struct P2pConfig {
     pub user_agent: UserAgent,
     /// A timeout after which a peer is disconnected.
     pub sync_stalling_timeout: SyncStallingTimeout,
+    /// The average interval between transaction announcements sent to a peer.
+    /// New transactions are accumulated and announced in batches at random times, so that
+    /// it's harder to find out which node a transaction originates from.
+    pub tx_relay_trickle_interval: TxRelayTrickleInterval,
+    /// If true, transactions are announced to whitelisted peers immediately.
+    pub disable_tx_trickle_for_whitelisted_peers: DisableTxTrickleForWhitelistedPeers,
+    /// The average interval between rebroadcasts of the local transactions that are still
+    /// in the mempool; only the transactions that are older than the interval are announced
+    /// again. Zero disables the rebroadcasts.
+    pub tx_rebroadcast_interval: TxRebroadcastInterval,
     /// Various settings used internally by the peer manager.
     pub peer_manager_config: PeerManagerConfig,
     /// Various limits related to the protocol; these should only be overridden in tests.
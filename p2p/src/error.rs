@@ -25,7 +25,10 @@ use networking::error::NetworkingError;
 use p2p_types::{services::Services, socket_address::SocketAddress, PeerId};
 use utils::try_as::TryAsRef;
 
-use crate::{net::types::PeerRole, peer_manager::peerdb_common, protocol::ProtocolVersion};
+use crate::{
+    net::types::PeerRole, peer_manager::peerdb_common, protocol::ProtocolVersion,
+    sync::HeaderCheckError,
+};
 
 /// Errors related to invalid data/peer information that results in connection getting closed
 /// and the peer getting banned.
@@ -64,6 +67,22 @@ pub enum ProtocolError {
     DuplicatedTransactionAnnouncement(Id<Transaction>),
     #[error("Announced too many transactions (limit is {0})")]
     TransactionAnnouncementLimitExceeded(usize),
+    #[error("Header of block {0} is invalid: {1}")]
+    InvalidHeader(Id<Block>, HeaderCheckError),
+    #[error("Chunk manifest of block {0} is invalid")]
+    InvalidBlockChunkManifest(Id<Block>),
+    #[error("Chunk {1} of block {0} doesn't match the manifest")]
+    InvalidBlockChunk(Id<Block>, u32),
+    #[error("Peer sent chunk {1} of block {0} that wasn't requested")]
+    UnsolicitedBlockChunkReceived(Id<Block>, u32),
+    #[error("Requested {0} block chunks with limit of {1}")]
+    BlockChunksRequestLimitExceeded(usize, usize),
+    #[error("Invalid chunk request for block {0}")]
+    InvalidBlockChunkRequest(Id<Block>),
+    #[error("Requested block filters for {0} blocks with limit of {1}")]
+    BlockFiltersRequestLimitExceeded(usize, usize),
+    #[error("Invalid block filter request with stop block {0}")]
+    InvalidBlockFilterRequest(Id<Block>),
 }
 
 /// Peer state errors (Errors either for an individual peer or for the [`PeerManager`](crate::peer_manager::PeerManager))
@@ -263,6 +282,14 @@ impl BanScore for ProtocolError {
             ProtocolError::AddressListLimitExceeded => 100,
             ProtocolError::DuplicatedTransactionAnnouncement(_) => 20,
             ProtocolError::TransactionAnnouncementLimitExceeded(_) => 20,
+            ProtocolError::InvalidHeader(_, _) => 100,
+            ProtocolError::InvalidBlockChunkManifest(_) => 100,
+            ProtocolError::InvalidBlockChunk(_, _) => 100,
+            ProtocolError::UnsolicitedBlockChunkReceived(_, _) => 20,
+            ProtocolError::BlockChunksRequestLimitExceeded(_, _) => 20,
+            ProtocolError::InvalidBlockChunkRequest(_) => 20,
+            ProtocolError::BlockFiltersRequestLimitExceeded(_, _) => 20,
+            ProtocolError::InvalidBlockFilterRequest(_) => 20,
         }
     }
 }
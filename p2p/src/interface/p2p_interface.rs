@@ -22,7 +22,10 @@ use p2p_types::{
 };
 use utils_networking::IpOrSocketAddress;
 
-use crate::{interface::types::ConnectedPeer, types::peer_id::PeerId};
+use crate::{
+    interface::types::{ConnectedPeer, ReachabilityInfo},
+    types::peer_id::PeerId,
+};
 
 #[async_trait::async_trait]
 pub trait P2pInterface: Send + Sync {
@@ -41,6 +44,7 @@ pub trait P2pInterface: Send + Sync {
     async fn get_peer_count(&self) -> crate::Result<usize>;
     async fn get_bind_addresses(&self) -> crate::Result<Vec<SocketAddress>>;
     async fn get_connected_peers(&self) -> crate::Result<Vec<ConnectedPeer>>;
+    async fn get_reachability(&self) -> crate::Result<ReachabilityInfo>;
 
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>>;
     async fn add_reserved_node(&mut self, addr: IpOrSocketAddress) -> crate::Result<()>;
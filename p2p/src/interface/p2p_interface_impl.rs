@@ -26,7 +26,10 @@ use utils_networking::IpOrSocketAddress;
 use crate::{
     disconnection_reason::DisconnectionReason,
     error::P2pError,
-    interface::{p2p_interface::P2pInterface, types::ConnectedPeer},
+    interface::{
+        p2p_interface::P2pInterface,
+        types::{ConnectedPeer, ReachabilityInfo},
+    },
     net::NetworkingService,
     peer_manager_event::PeerDisconnectionDbAction,
     types::peer_id::PeerId,
@@ -135,6 +138,13 @@ where
         Ok(response_receiver.await?)
     }
 
+    async fn get_reachability(&self) -> crate::Result<ReachabilityInfo> {
+        let (response_sender, response_receiver) = oneshot_nofail::channel();
+        self.peer_mgr_event_sender
+            .send(PeerManagerEvent::GetReachability(response_sender))?;
+        Ok(response_receiver.await?)
+    }
+
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>> {
         let (response_sender, response_receiver) = oneshot_nofail::channel();
         self.peer_mgr_event_sender
@@ -26,7 +26,10 @@ use utils_networking::IpOrSocketAddress;
 
 use crate::{types::peer_id::PeerId, P2pEvent};
 
-use super::{p2p_interface::P2pInterface, types::ConnectedPeer};
+use super::{
+    p2p_interface::P2pInterface,
+    types::{ConnectedPeer, ReachabilityInfo},
+};
 
 #[async_trait::async_trait]
 impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> + Send + Sync>
@@ -76,6 +79,10 @@ impl<T: Deref<Target = dyn P2pInterface> + DerefMut<Target = dyn P2pInterface> +
         self.deref().get_connected_peers().await
     }
 
+    async fn get_reachability(&self) -> crate::Result<ReachabilityInfo> {
+        self.deref().get_reachability().await
+    }
+
     async fn get_reserved_nodes(&self) -> crate::Result<Vec<SocketAddress>> {
         self.deref().get_reserved_nodes().await
     }
@@ -47,4 +47,27 @@ pub struct ConnectedPeer {
 
     /// Last time the peer has sent us a block that became our tip, in seconds since UNIX epoch
     pub last_tip_block_time: Option<u64>,
+
+    /// Number of blocks the peer has sent us while the same block, received first from another
+    /// peer, was still being processed
+    pub duplicate_blocks_received: u64,
+}
+
+/// Helper type used to report whether this node appears to be reachable from the internet.
+#[derive(Clone, Debug, Serialize, Deserialize, rpc_description::HasValueHint)]
+pub struct ReachabilityInfo {
+    /// Addresses that this node announces to its peers, either configured explicitly
+    /// or discovered via outbound connections.
+    pub announced_addresses: Vec<SocketAddress>,
+
+    /// Number of currently connected inbound peers with a publicly routable address
+    pub public_inbound_peer_count: usize,
+
+    /// Last time an inbound connection from a publicly routable address was accepted,
+    /// in seconds since UNIX epoch
+    pub last_public_inbound_connection_time: Option<u64>,
+
+    /// Whether the node appears to be reachable, i.e. at least one inbound connection
+    /// from a publicly routable address has been accepted since startup
+    pub reachable: bool,
 }
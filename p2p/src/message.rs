@@ -13,13 +13,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use chainstate::Locator;
+use chainstate::{CompactBlockFilter, Locator};
 use common::{
     chain::{
         block::{signed_block_header::SignedBlockHeader, Block},
         SignedTransaction, Transaction,
     },
-    primitives::Id,
+    primitives::{BlockHeight, Id, Idable, H256},
 };
 use serialization::{Decode, Encode};
 
@@ -31,6 +31,14 @@ pub enum BlockSyncMessage {
     BlockListRequest(BlockListRequest),
     HeaderList(HeaderList),
     BlockResponse(BlockResponse),
+    BlockChunkManifest(BlockChunkManifest),
+    BlockChunkRequest(BlockChunkRequest),
+    BlockChunk(BlockChunk),
+    BlockChunksReceived(Id<Block>),
+    BlockFilterHeadersRequest(BlockFilterRequest),
+    BlockFilterHeaders(BlockFilterHeaders),
+    BlockFiltersRequest(BlockFilterRequest),
+    BlockFilter(BlockFilterResponse),
 
     // A "sentinel" message for testing purposes that allows to ensure that all block sync messages
     // that were sent into a channel have been processed by the receiver.
@@ -43,6 +51,7 @@ pub enum BlockSyncMessage {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TransactionSyncMessage {
     NewTransaction(Id<Transaction>),
+    NewTransactionWithWtxid(TransactionAnnouncement),
     TransactionRequest(Id<Transaction>),
     TransactionResponse(TransactionResponse),
 }
@@ -152,6 +161,231 @@ impl BlockResponse {
     }
 }
 
+/// The way a block is split into chunks.
+///
+/// Only plain chunks are supported at this moment; erasure-coded ones can be added later
+/// as new variants.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum BlockChunkEncoding {
+    #[codec(index = 0)]
+    Plain,
+}
+
+/// The description of a block that is going to be transferred in chunks.
+///
+/// This message is sent instead of `BlockResponse` when the requested block is too big.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockChunkManifest {
+    block_id: Id<Block>,
+    encoding: BlockChunkEncoding,
+    block_size: u32,
+    chunk_size: u32,
+    chunk_hashes: Vec<H256>,
+}
+
+impl BlockChunkManifest {
+    pub fn new(
+        block_id: Id<Block>,
+        encoding: BlockChunkEncoding,
+        block_size: u32,
+        chunk_size: u32,
+        chunk_hashes: Vec<H256>,
+    ) -> Self {
+        Self {
+            block_id,
+            encoding,
+            block_size,
+            chunk_size,
+            chunk_hashes,
+        }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn encoding(&self) -> BlockChunkEncoding {
+        self.encoding
+    }
+
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    pub fn chunk_hashes(&self) -> &[H256] {
+        &self.chunk_hashes
+    }
+}
+
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockChunkRequest {
+    block_id: Id<Block>,
+    chunk_indices: Vec<u32>,
+}
+
+impl BlockChunkRequest {
+    pub fn new(block_id: Id<Block>, chunk_indices: Vec<u32>) -> Self {
+        Self {
+            block_id,
+            chunk_indices,
+        }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn chunk_indices(&self) -> &[u32] {
+        &self.chunk_indices
+    }
+}
+
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockChunk {
+    block_id: Id<Block>,
+    chunk_index: u32,
+    data: Vec<u8>,
+}
+
+impl BlockChunk {
+    pub fn new(block_id: Id<Block>, chunk_index: u32, data: Vec<u8>) -> Self {
+        Self {
+            block_id,
+            chunk_index,
+            data,
+        }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn chunk_index(&self) -> u32 {
+        self.chunk_index
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// A request for the compact filters (or their hashes) of the main chain blocks starting at
+/// `start_height` and ending at `stop_block_id`.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockFilterRequest {
+    start_height: BlockHeight,
+    stop_block_id: Id<Block>,
+}
+
+impl BlockFilterRequest {
+    pub fn new(start_height: BlockHeight, stop_block_id: Id<Block>) -> Self {
+        Self {
+            start_height,
+            stop_block_id,
+        }
+    }
+
+    pub fn start_height(&self) -> BlockHeight {
+        self.start_height
+    }
+
+    pub fn stop_block_id(&self) -> &Id<Block> {
+        &self.stop_block_id
+    }
+}
+
+/// The filter hashes of the requested blocks, in the chain order, and the filter header of the
+/// block preceding them, from which the filter headers of the requested blocks can be computed.
+///
+/// The list is empty if the filters of the requested blocks are not available.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockFilterHeaders {
+    stop_block_id: Id<Block>,
+    prev_filter_header: H256,
+    filter_hashes: Vec<H256>,
+}
+
+impl BlockFilterHeaders {
+    pub fn new(
+        stop_block_id: Id<Block>,
+        prev_filter_header: H256,
+        filter_hashes: Vec<H256>,
+    ) -> Self {
+        Self {
+            stop_block_id,
+            prev_filter_header,
+            filter_hashes,
+        }
+    }
+
+    pub fn stop_block_id(&self) -> &Id<Block> {
+        &self.stop_block_id
+    }
+
+    pub fn prev_filter_header(&self) -> &H256 {
+        &self.prev_filter_header
+    }
+
+    pub fn filter_hashes(&self) -> &[H256] {
+        &self.filter_hashes
+    }
+}
+
+/// The compact filter of a single requested block.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+pub struct BlockFilterResponse {
+    block_id: Id<Block>,
+    filter: CompactBlockFilter,
+}
+
+impl BlockFilterResponse {
+    pub fn new(block_id: Id<Block>, filter: CompactBlockFilter) -> Self {
+        Self { block_id, filter }
+    }
+
+    pub fn block_id(&self) -> &Id<Block> {
+        &self.block_id
+    }
+
+    pub fn filter(&self) -> &CompactBlockFilter {
+        &self.filter
+    }
+}
+
+/// A transaction announcement that identifies the exact version of the transaction, i.e.
+/// including its signatures.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransactionAnnouncement {
+    tx_id: Id<Transaction>,
+    wtxid: Id<SignedTransaction>,
+}
+
+impl TransactionAnnouncement {
+    pub fn new(tx_id: Id<Transaction>, wtxid: Id<SignedTransaction>) -> Self {
+        Self { tx_id, wtxid }
+    }
+
+    pub fn from_tx(tx: &SignedTransaction) -> Self {
+        Self::new(tx.transaction().get_id(), tx.wtxid())
+    }
+
+    pub fn tx_id(&self) -> &Id<Transaction> {
+        &self.tx_id
+    }
+
+    pub fn wtxid(&self) -> &Id<SignedTransaction> {
+        &self.wtxid
+    }
+}
+
 #[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
 pub enum TransactionResponse {
     #[codec(index = 0)]
@@ -266,12 +266,16 @@ where
         let old_value = peer.was_accepted.test_and_set();
         assert!(!old_value);
 
+        let is_whitelisted =
+            self.p2p_config.whitelisted_addresses.contains(&peer.peer_address.ip_addr());
+
         Self::send_syncing_event(
             &self.syncing_event_sender,
             SyncingEvent::Connected {
                 peer_id,
                 common_services: peer.common_services,
                 protocol_version: peer.protocol_version,
+                is_whitelisted,
                 block_sync_msg_receiver,
                 transaction_sync_msg_receiver,
             },
@@ -18,7 +18,7 @@ use std::time::Duration;
 use tokio::sync::{mpsc::Sender, oneshot};
 
 use common::{
-    chain::{config::MagicBytes, Transaction},
+    chain::{config::MagicBytes, Block, Transaction},
     primitives::{semver::SemVer, time::Time, user_agent::UserAgent, Id},
 };
 use p2p_types::socket_address::SocketAddress;
@@ -28,9 +28,11 @@ use crate::{
     disconnection_reason::DisconnectionReason,
     error::P2pError,
     message::{
-        AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockListRequest, BlockResponse,
-        BlockSyncMessage, HeaderList, HeaderListRequest, PeerManagerMessage, PingRequest,
-        PingResponse, TransactionResponse, TransactionSyncMessage, WillDisconnectMessage,
+        AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockChunk, BlockChunkManifest,
+        BlockChunkRequest, BlockFilterHeaders, BlockFilterRequest, BlockFilterResponse,
+        BlockListRequest, BlockResponse, BlockSyncMessage, HeaderList, HeaderListRequest,
+        PeerManagerMessage, PingRequest, PingResponse, TransactionAnnouncement,
+        TransactionResponse, TransactionSyncMessage, WillDisconnectMessage,
     },
     net::types::services::Services,
     protocol::{ProtocolVersion, SupportedProtocolVersion},
@@ -218,6 +220,30 @@ pub enum Message {
     #[codec(index = 13)]
     WillDisconnect(WillDisconnectMessage),
 
+    /// Messages for transferring big blocks in chunks. Available since protocol V4.
+    #[codec(index = 14)]
+    BlockChunkManifest(BlockChunkManifest),
+    #[codec(index = 15)]
+    BlockChunkRequest(BlockChunkRequest),
+    #[codec(index = 16)]
+    BlockChunk(BlockChunk),
+    #[codec(index = 17)]
+    BlockChunksReceived(Id<Block>),
+
+    /// Transaction announcement that includes the witness id. Available since protocol V5.
+    #[codec(index = 18)]
+    NewTransactionWithWtxid(TransactionAnnouncement),
+
+    /// Messages for serving compact block filters to light clients. Available since protocol V6.
+    #[codec(index = 19)]
+    BlockFilterHeadersRequest(BlockFilterRequest),
+    #[codec(index = 20)]
+    BlockFilterHeaders(BlockFilterHeaders),
+    #[codec(index = 21)]
+    BlockFiltersRequest(BlockFilterRequest),
+    #[codec(index = 22)]
+    BlockFilter(BlockFilterResponse),
+
     // A message that corresponds to BlockSyncMessage::TestSentinel.
     #[cfg(test)]
     #[codec(index = 255)]
@@ -244,6 +270,14 @@ impl From<BlockSyncMessage> for Message {
             BlockSyncMessage::BlockListRequest(r) => Message::BlockListRequest(r),
             BlockSyncMessage::HeaderList(r) => Message::HeaderList(r),
             BlockSyncMessage::BlockResponse(r) => Message::BlockResponse(r),
+            BlockSyncMessage::BlockChunkManifest(m) => Message::BlockChunkManifest(m),
+            BlockSyncMessage::BlockChunkRequest(r) => Message::BlockChunkRequest(r),
+            BlockSyncMessage::BlockChunk(c) => Message::BlockChunk(c),
+            BlockSyncMessage::BlockChunksReceived(id) => Message::BlockChunksReceived(id),
+            BlockSyncMessage::BlockFilterHeadersRequest(r) => Message::BlockFilterHeadersRequest(r),
+            BlockSyncMessage::BlockFilterHeaders(h) => Message::BlockFilterHeaders(h),
+            BlockSyncMessage::BlockFiltersRequest(r) => Message::BlockFiltersRequest(r),
+            BlockSyncMessage::BlockFilter(f) => Message::BlockFilter(f),
             #[cfg(test)]
             BlockSyncMessage::TestSentinel(id) => Message::TestBlockSyncMsgSentinel(id),
         }
@@ -254,6 +288,9 @@ impl From<TransactionSyncMessage> for Message {
     fn from(message: TransactionSyncMessage) -> Self {
         match message {
             TransactionSyncMessage::NewTransaction(id) => Message::NewTransaction(id),
+            TransactionSyncMessage::NewTransactionWithWtxid(announcement) => {
+                Message::NewTransactionWithWtxid(announcement)
+            }
             TransactionSyncMessage::TransactionRequest(id) => Message::TransactionRequest(id),
             TransactionSyncMessage::TransactionResponse(tx) => Message::TransactionResponse(tx),
         }
@@ -306,6 +343,30 @@ impl Message {
             Message::BlockResponse(msg) => {
                 CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockResponse(msg))
             }
+            Message::BlockChunkManifest(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockChunkManifest(msg))
+            }
+            Message::BlockChunkRequest(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockChunkRequest(msg))
+            }
+            Message::BlockChunk(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockChunk(msg))
+            }
+            Message::BlockChunksReceived(id) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockChunksReceived(id))
+            }
+            Message::BlockFilterHeadersRequest(msg) => CategorizedMessage::BlockSyncMessage(
+                BlockSyncMessage::BlockFilterHeadersRequest(msg),
+            ),
+            Message::BlockFilterHeaders(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockFilterHeaders(msg))
+            }
+            Message::BlockFiltersRequest(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockFiltersRequest(msg))
+            }
+            Message::BlockFilter(msg) => {
+                CategorizedMessage::BlockSyncMessage(BlockSyncMessage::BlockFilter(msg))
+            }
             #[cfg(test)]
             Message::TestBlockSyncMsgSentinel(id) => {
                 CategorizedMessage::BlockSyncMessage(BlockSyncMessage::TestSentinel(id))
@@ -314,6 +375,9 @@ impl Message {
             Message::NewTransaction(msg) => CategorizedMessage::TransactionSyncMessage(
                 TransactionSyncMessage::NewTransaction(msg),
             ),
+            Message::NewTransactionWithWtxid(msg) => CategorizedMessage::TransactionSyncMessage(
+                TransactionSyncMessage::NewTransactionWithWtxid(msg),
+            ),
             Message::TransactionRequest(msg) => CategorizedMessage::TransactionSyncMessage(
                 TransactionSyncMessage::TransactionRequest(msg),
             ),
@@ -334,11 +398,11 @@ pub fn can_send_will_disconnect(peer_protocol_version: ProtocolVersion) -> bool
 mod tests {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-    use chainstate::Locator;
+    use chainstate::{CompactBlockFilter, Locator};
     use chainstate_test_framework::TestFramework;
     use common::{
         chain::config::MagicBytes,
-        primitives::{semver::SemVer, Id},
+        primitives::{semver::SemVer, BlockHeight, Id, Idable, H256},
     };
     use networking::test_helpers::{get_two_connected_sockets, TestTransportChannel};
     use networking::transport::{BufferedTranscoder, MpscChannelTransport};
@@ -348,9 +412,10 @@ mod tests {
 
     use crate::{
         message::{
-            AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockListRequest,
-            BlockResponse, HeaderList, HeaderListRequest, PingRequest, PingResponse,
-            TransactionResponse,
+            AddrListRequest, AddrListResponse, AnnounceAddrRequest, BlockChunk, BlockChunkEncoding,
+            BlockChunkManifest, BlockChunkRequest, BlockFilterHeaders, BlockFilterRequest,
+            BlockFilterResponse, BlockListRequest, BlockResponse, HeaderList, HeaderListRequest,
+            PingRequest, PingResponse, TransactionAnnouncement, TransactionResponse,
         },
         net::default_backend::types::{HandshakeMessage, P2pTimestamp},
         protocol::ProtocolVersion,
@@ -418,6 +483,10 @@ mod tests {
             Message::PingRequest(PingRequest { nonce: rng.gen() }),
             Message::PingResponse(PingResponse { nonce: rng.gen() }),
             Message::NewTransaction(Id::new(rng.gen())),
+            Message::NewTransactionWithWtxid(TransactionAnnouncement::new(
+                Id::new(rng.gen()),
+                Id::new(rng.gen()),
+            )),
             Message::HeaderListRequest(HeaderListRequest::new(Locator::new(vec![
                 Id::new(rng.gen()),
                 Id::new(rng.gen()),
@@ -428,6 +497,43 @@ mod tests {
                 Id::new(rng.gen()),
             ])),
             Message::BlockResponse(BlockResponse::new(block.clone())),
+            Message::BlockChunkManifest(BlockChunkManifest::new(
+                block.get_id(),
+                BlockChunkEncoding::Plain,
+                rng.gen(),
+                rng.gen(),
+                vec![H256::random_using(&mut rng), H256::random_using(&mut rng)],
+            )),
+            Message::BlockChunkRequest(BlockChunkRequest::new(
+                block.get_id(),
+                vec![rng.gen(), rng.gen()],
+            )),
+            Message::BlockChunk(BlockChunk::new(
+                block.get_id(),
+                rng.gen(),
+                vec![rng.gen(), rng.gen(), rng.gen()],
+            )),
+            Message::BlockChunksReceived(block.get_id()),
+            Message::BlockFilterHeadersRequest(BlockFilterRequest::new(
+                BlockHeight::new(rng.gen()),
+                block.get_id(),
+            )),
+            Message::BlockFilterHeaders(BlockFilterHeaders::new(
+                block.get_id(),
+                H256::random_using(&mut rng),
+                vec![H256::random_using(&mut rng), H256::random_using(&mut rng)],
+            )),
+            Message::BlockFiltersRequest(BlockFilterRequest::new(
+                BlockHeight::new(rng.gen()),
+                block.get_id(),
+            )),
+            Message::BlockFilter(BlockFilterResponse::new(
+                block.get_id(),
+                CompactBlockFilter::new(
+                    &block.get_id(),
+                    &[vec![rng.gen()], vec![rng.gen(), rng.gen()]].into(),
+                ),
+            )),
             Message::TransactionRequest(Id::new(rng.gen())),
             Message::TransactionResponse(TransactionResponse::NotFound(Id::new(rng.gen()))),
             Message::TransactionResponse(TransactionResponse::Found(
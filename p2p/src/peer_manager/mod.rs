@@ -51,7 +51,7 @@ use crate::{
     config::P2pConfig,
     disconnection_reason::DisconnectionReason,
     error::{ConnectionValidationError, P2pError, PeerError, ProtocolError},
-    interface::types::ConnectedPeer,
+    interface::types::{ConnectedPeer, ReachabilityInfo},
     message::{
         AddrListRequest, AddrListResponse, AnnounceAddrRequest, PeerManagerMessage, PingRequest,
         PingResponse, WillDisconnectMessage,
@@ -207,6 +207,8 @@ where
     init_time: Time,
     /// Last time when a new tip was added to the chainstate.
     last_chainstate_tip_block_time: Option<Time>,
+    /// Last time when an inbound connection from a publicly routable address was accepted.
+    last_public_inbound_connection_time: Option<Time>,
     /// Last heartbeat time.
     last_heartbeat_time: Option<Time>,
     /// Last time dns seed was queried.
@@ -297,6 +299,7 @@ where
             dns_seed,
             init_time: now,
             last_chainstate_tip_block_time: None,
+            last_public_inbound_connection_time: None,
             last_heartbeat_time: None,
             last_dns_query_time: None,
             last_ping_check_time: None,
@@ -319,11 +322,22 @@ where
             .is_some()
     }
 
+    /// External addresses of this node from the config that can be announced to peers.
+    fn configured_external_addresses(&self) -> impl Iterator<Item = SocketAddress> + '_ {
+        self.p2p_config.external_addresses.iter().filter_map(|address| {
+            SocketAddress::new(*address)
+                .as_peer_address()
+                .as_discoverable_socket_address(*self.p2p_config.allow_discover_private_ips)
+        })
+    }
+
     /// Discover public addresses for this node after a new outbound connection is made
     ///
     /// `node_address_as_seen_by_peer` is this host socket address as seen and reported by remote peer.
     /// This should work for hosts with public IPs and for hosts behind NAT with port forwarding (same port is assumed).
     /// This won't work for majority of nodes but that should be accepted.
+    ///
+    /// If external addresses are set in the config, they are used instead.
     fn discover_own_address(
         &mut self,
         peer_id: PeerId,
@@ -341,11 +355,34 @@ where
             return None;
         }
 
-        let node_address_as_seen_by_peer = node_address_as_seen_by_peer?;
+        let discovered_own_addresses = if self.p2p_config.external_addresses.is_empty() {
+            let node_address_as_seen_by_peer = node_address_as_seen_by_peer?;
+            self.discover_own_addresses_from_peer(node_address_as_seen_by_peer)
+        } else {
+            self.configured_external_addresses().collect::<Vec<_>>()
+        };
+
+        // Send only one address because of the rate limiter (see `ADDR_RATE_INITIAL_SIZE`).
+        // Select a random address to give all addresses a chance to be discovered by the network.
+        let chosen_discovered_address =
+            discovered_own_addresses.iter().choose(&mut make_pseudo_rng()).cloned();
 
-        // Take IP and use port numbers from all listening sockets (with same IP version)
-        let discovered_own_addresses = self
-            .peer_connectivity_handle
+        log::debug!(
+            "Own addresses discovered for peer {peer_id}: {:?}, chosen address: {:?}",
+            discovered_own_addresses,
+            chosen_discovered_address
+        );
+
+        chosen_discovered_address
+    }
+
+    /// Take IP as seen by the remote peer and use port numbers from all listening sockets
+    /// (with same IP version)
+    fn discover_own_addresses_from_peer(
+        &self,
+        node_address_as_seen_by_peer: PeerAddress,
+    ) -> Vec<SocketAddress> {
+        self.peer_connectivity_handle
             .local_addresses()
             .iter()
             .map(SocketAddress::as_peer_address)
@@ -369,20 +406,7 @@ where
             .filter_map(|address| {
                 address.as_discoverable_socket_address(*self.p2p_config.allow_discover_private_ips)
             })
-            .collect::<Vec<_>>();
-
-        // Send only one address because of the rate limiter (see `ADDR_RATE_INITIAL_SIZE`).
-        // Select a random address to give all addresses a chance to be discovered by the network.
-        let chosen_discovered_address =
-            discovered_own_addresses.iter().choose(&mut make_pseudo_rng()).cloned();
-
-        log::debug!(
-            "Own addresses discovered for peer {peer_id}: {:?}, chosen address: {:?}",
-            discovered_own_addresses,
-            chosen_discovered_address
-        );
-
-        chosen_discovered_address
+            .collect()
     }
 
     /// Send address announcement to the selected peer (if the address is new)
@@ -1035,6 +1059,7 @@ where
             discovered_own_address,
             last_tip_block_time: None,
             last_tx_time: None,
+            duplicate_blocks_received: 0,
             block_sync_status: PeerBlockSyncStatus::new(),
         };
 
@@ -1042,11 +1067,16 @@ where
 
         let old_value = self.peers.insert(peer_id, peer);
         assert!(old_value.is_none());
+        metrics::node::PEERS.set(self.peers.len() as u64);
 
         if peer_role.is_outbound() {
             self.peerdb.outbound_peer_connected(peer_address);
         }
 
+        if peer_role == PeerRole::Inbound && peer_address.ip_addr().is_global_unicast_ip() {
+            self.last_public_inbound_connection_time = Some(self.time_getter.get_time());
+        }
+
         if peer_role == PeerRole::OutboundBlockRelay {
             let anchor_addresses = self
                 .peers
@@ -1185,6 +1215,7 @@ where
     fn connection_closed(&mut self, peer_id: PeerId) {
         // The peer will not be in `peers` for rejected connections
         if let Some(peer) = self.peers.remove(&peer_id) {
+            metrics::node::PEERS.set(self.peers.len() as u64);
             log::info!(
                 "Peer disconnected, peer_id: {}, address: {:?}",
                 peer.info.peer_id,
@@ -1657,6 +1688,12 @@ where
                     peer.last_tip_block_time = Some(self.time_getter.get_time());
                 }
             }
+            PeerManagerEvent::DuplicateBlockReceived { peer_id, block_id } => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    log::debug!("Duplicate block {block_id} received from peer {peer_id}");
+                    peer.duplicate_blocks_received += 1;
+                }
+            }
             PeerManagerEvent::NewChainstateTip(block_id) => {
                 log::debug!("New tip {block_id} added to chainstate");
                 self.last_chainstate_tip_block_time = Some(self.time_getter.get_time());
@@ -1687,6 +1724,9 @@ where
                 let peers = self.get_connected_peers();
                 response_sender.send(peers);
             }
+            PeerManagerEvent::GetReachability(response_sender) => {
+                response_sender.send(self.get_reachability());
+            }
             PeerManagerEvent::GetReserved(response_sender) => {
                 response_sender.send(self.peerdb.get_reserved_nodes().collect())
             }
@@ -1824,10 +1864,43 @@ where
                 last_tip_block_time: context
                     .last_tip_block_time
                     .map(|time| time.as_secs_since_epoch()),
+                duplicate_blocks_received: context.duplicate_blocks_received,
             })
             .collect()
     }
 
+    /// Returns info about whether this node appears to be reachable from the internet
+    fn get_reachability(&self) -> ReachabilityInfo {
+        let announced_addresses = if self.p2p_config.external_addresses.is_empty() {
+            self.peers
+                .values()
+                .filter_map(|peer| peer.discovered_own_address)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        } else {
+            self.configured_external_addresses().collect()
+        };
+
+        let public_inbound_peer_count = self
+            .peers
+            .values()
+            .filter(|peer| {
+                peer.peer_role == PeerRole::Inbound
+                    && peer.peer_address.ip_addr().is_global_unicast_ip()
+            })
+            .count();
+
+        ReachabilityInfo {
+            announced_addresses,
+            public_inbound_peer_count,
+            last_public_inbound_connection_time: self
+                .last_public_inbound_connection_time
+                .map(|time| time.as_secs_since_epoch()),
+            reachable: self.last_public_inbound_connection_time.is_some(),
+        }
+    }
+
     /// Checks if the peer is in active state
     fn is_peer_connected(&self, peer_id: PeerId) -> bool {
         self.peers.contains_key(&peer_id)
@@ -78,6 +78,10 @@ pub struct PeerContext {
 
     pub last_tx_time: Option<Time>,
 
+    /// The number of blocks the peer has sent us while the same block, received first from
+    /// another peer, was still being processed.
+    pub duplicate_blocks_received: u64,
+
     /// Certain information from the block sync manager that the peer manager may be interested in.
     pub block_sync_status: PeerBlockSyncStatus,
 }
@@ -243,6 +243,12 @@ fn make_p2p_config() -> P2pConfig {
             max_request_blocks_count: Default::default(),
             msg_max_locator_count: Default::default(),
             max_message_size: Default::default(),
+            max_block_chunks_per_request: Default::default(),
+            block_chunking_threshold: Default::default(),
+            block_chunk_size: Default::default(),
+            max_chunked_block_size: Default::default(),
+            max_block_filter_headers_per_request: Default::default(),
+            max_block_filters_per_request: Default::default(),
             max_peer_tx_announcements: Default::default(),
         },
 
@@ -252,6 +258,7 @@ fn make_p2p_config() -> P2pConfig {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -262,6 +269,9 @@ fn make_p2p_config() -> P2pConfig {
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
     }
 }
@@ -467,6 +467,105 @@ async fn resend_own_addresses(#[case] seed: Seed) {
     }
 }
 
+// Verify that the configured external address is announced instead of the discovered one
+// and that an inbound connection from a public address makes the node reachable.
+#[tracing::instrument(skip(seed))]
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn external_address_and_reachability(#[case] seed: Seed) {
+    type TestNetworkingService = DefaultNetworkingService<TcpTransportSocket>;
+
+    let mut rng = make_seedable_rng(seed);
+
+    let listening_address: SocketAddress = "1.2.3.4:3031".parse().unwrap();
+    let outbound_address: SocketAddress = "1.2.3.4:12345".parse().unwrap();
+    let external_address: SocketAddress = "5.6.7.8:4444".parse().unwrap();
+
+    let chain_config = Arc::new(config::create_unit_test_config());
+    let p2p_config = Arc::new(P2pConfig {
+        external_addresses: vec![external_address.socket_addr()],
+        ..test_p2p_config()
+    });
+    let (cmd_sender, mut cmd_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (_conn_event_sender, conn_event_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let (_peer_mgr_event_sender, peer_mgr_event_receiver) =
+        tokio::sync::mpsc::unbounded_channel::<PeerManagerEvent>();
+    let time_getter = BasicTestTimeGetter::new();
+    let connectivity_handle = ConnectivityHandle::<TestNetworkingService>::new(
+        vec![listening_address],
+        cmd_sender,
+        conn_event_receiver,
+    );
+
+    let mut pm = PeerManager::<TestNetworkingService, _>::new(
+        true,
+        Arc::clone(&chain_config),
+        Arc::clone(&p2p_config),
+        connectivity_handle,
+        peer_mgr_event_receiver,
+        time_getter.get_time_getter(),
+        peerdb_inmemory_store(),
+    )
+    .unwrap();
+
+    let reachability = pm.get_reachability();
+    assert_eq!(reachability.announced_addresses, vec![external_address]);
+    assert_eq!(reachability.public_inbound_peer_count, 0);
+    assert_eq!(reachability.last_public_inbound_connection_time, None);
+    assert!(!reachability.reachable);
+
+    // The configured address is announced to a new outbound peer
+    let outbound_peer_address = get_new_discoverable_address(&mut rng);
+    let outbound_peer_address =
+        outbound_peer_address.as_discoverable_socket_address(false).unwrap();
+    pm.connect(outbound_peer_address, OutboundConnectType::Reserved);
+    expect_cmd_connect_to(&cmd_receiver.try_recv().unwrap(), &outbound_peer_address);
+    let outbound_peer_id = PeerId::new();
+    pm.accept_connection(
+        outbound_peer_address,
+        listening_address,
+        ConnectionDirection::Outbound,
+        make_full_relay_peer_info(outbound_peer_id, &chain_config),
+        Some(outbound_address.as_peer_address()),
+    );
+
+    let mut announced_addresses = Vec::new();
+    while let Ok(cmd) = cmd_receiver.try_recv() {
+        if let Command::SendMessage { peer_id, message } = cmd {
+            if let CategorizedMessage::PeerManagerMessage(
+                PeerManagerMessage::AnnounceAddrRequest(AnnounceAddrRequest { address }),
+            ) = message.categorize()
+            {
+                assert_eq!(peer_id, outbound_peer_id);
+                announced_addresses.push(address.as_discoverable_socket_address(false).unwrap());
+            }
+        }
+    }
+    assert_eq!(announced_addresses, vec![external_address]);
+
+    // An inbound connection from a public address makes the node reachable
+    let inbound_peer_address = get_new_discoverable_address(&mut rng);
+    let inbound_peer_address = inbound_peer_address.as_discoverable_socket_address(false).unwrap();
+    pm.accept_connection(
+        inbound_peer_address,
+        listening_address,
+        ConnectionDirection::Inbound,
+        make_full_relay_peer_info(PeerId::new(), &chain_config),
+        None,
+    );
+    assert_eq!(pm.peers.len(), 2);
+
+    let reachability = pm.get_reachability();
+    assert_eq!(reachability.announced_addresses, vec![external_address]);
+    assert_eq!(reachability.public_inbound_peer_count, 1);
+    assert_eq!(
+        reachability.last_public_inbound_connection_time,
+        Some(time_getter.get_time_getter().get_time().as_secs_since_epoch())
+    );
+    assert!(reachability.reachable);
+}
+
 // Configure the peer manager with an empty dns seed and a predefined peer address.
 // Check that it attempts to connect to the predefined address.
 #[tracing::instrument(skip(seed))]
@@ -710,6 +809,7 @@ async fn dont_use_dns_seed_if_connections_exist(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_timeout: Default::default(),
@@ -719,6 +819,9 @@ async fn dont_use_dns_seed_if_connections_exist(#[case] seed: Seed) {
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -391,6 +391,7 @@ async fn banned_address_is_not_announced(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -400,6 +401,9 @@ async fn banned_address_is_not_announced(#[case] seed: Seed) {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -512,6 +516,7 @@ async fn banned_address_not_in_addr_response(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -521,6 +526,9 @@ async fn banned_address_not_in_addr_response(#[case] seed: Seed) {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
 
@@ -845,6 +845,7 @@ async fn connection_timeout_rpc_notified<T>(
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         ping_check_period: Default::default(),
         ping_timeout: Default::default(),
@@ -854,6 +855,9 @@ async fn connection_timeout_rpc_notified<T>(
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -959,6 +963,7 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -969,6 +974,9 @@ where
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -1003,6 +1011,7 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1013,6 +1022,9 @@ where
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     });
@@ -1114,6 +1126,7 @@ where
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1123,6 +1136,9 @@ where
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender1, _shutdown_sender, _subscribers_sender) = run_peer_manager::<T>(
@@ -1158,6 +1174,7 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1167,6 +1184,9 @@ where
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender2, _shutdown_sender, _subscribers_sender) = run_peer_manager::<T>(
@@ -1189,6 +1209,7 @@ where
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1198,6 +1219,9 @@ where
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender3, _shutdown_sender, _subscribers_sender) = run_peer_manager::<T>(
@@ -1318,6 +1342,7 @@ async fn discovered_node_2_groups() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1327,6 +1352,9 @@ async fn discovered_node_2_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender1, _shutdown_sender, _subscribers_sender) =
@@ -1363,6 +1391,7 @@ async fn discovered_node_2_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1372,6 +1401,9 @@ async fn discovered_node_2_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender2, _shutdown_sender, _subscribers_sender) =
@@ -1395,6 +1427,7 @@ async fn discovered_node_2_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1404,6 +1437,9 @@ async fn discovered_node_2_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender3, _shutdown_sender, _subscribers_sender) =
@@ -1485,6 +1521,7 @@ async fn discovered_node_separate_groups() {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1494,6 +1531,9 @@ async fn discovered_node_separate_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender1, _shutdown_sender, _subscribers_sender) =
@@ -1530,6 +1570,7 @@ async fn discovered_node_separate_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1539,6 +1580,9 @@ async fn discovered_node_separate_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender2, _shutdown_sender, _subscribers_sender) =
@@ -1562,6 +1606,7 @@ async fn discovered_node_separate_groups() {
         disable_noise: Default::default(),
         boot_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -1571,6 +1616,9 @@ async fn discovered_node_separate_groups() {
         node_type: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
     let (peer_mgr_event_sender3, _shutdown_sender, _subscribers_sender) =
@@ -1876,6 +1924,7 @@ mod feeler_connections_test_utils {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_timeout: Default::default(),
@@ -1885,6 +1934,9 @@ mod feeler_connections_test_utils {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             protocol_config: Default::default(),
         }
     }
@@ -1962,6 +2014,7 @@ async fn reject_connection_to_existing_ip(#[case] seed: Seed) {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_timeout: Default::default(),
@@ -1971,6 +2024,9 @@ async fn reject_connection_to_existing_ip(#[case] seed: Seed) {
         allow_discover_private_ips: Default::default(),
         user_agent: mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         protocol_config: Default::default(),
     });
 
@@ -130,6 +130,7 @@ mod dont_evict_if_blocks_in_flight {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_timeout: Default::default(),
@@ -139,6 +140,9 @@ mod dont_evict_if_blocks_in_flight {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             protocol_config: Default::default(),
         });
 
@@ -51,6 +51,7 @@ fn validate_services() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -60,6 +61,9 @@ fn validate_services() {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
             protocol_config: Default::default(),
         });
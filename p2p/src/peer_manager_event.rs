@@ -23,9 +23,12 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use utils_networking::IpOrSocketAddress;
 
 use crate::{
-    disconnection_reason::DisconnectionReason, interface::types::ConnectedPeer,
-    peer_manager::PeerManagerInterface, sync::sync_status::PeerBlockSyncStatus,
-    types::peer_id::PeerId, utils::oneshot_nofail,
+    disconnection_reason::DisconnectionReason,
+    interface::types::{ConnectedPeer, ReachabilityInfo},
+    peer_manager::PeerManagerInterface,
+    sync::sync_status::PeerBlockSyncStatus,
+    types::peer_id::PeerId,
+    utils::oneshot_nofail,
 };
 
 #[derive(Debug)]
@@ -60,6 +63,9 @@ pub enum PeerManagerEvent {
     /// Get peer IDs and addresses of connected peers
     GetConnectedPeers(oneshot_nofail::Sender<Vec<ConnectedPeer>>),
 
+    /// Get information about whether the local node is reachable from the internet
+    GetReachability(oneshot_nofail::Sender<ReachabilityInfo>),
+
     /// Increases the ban score of a peer by the given amount.
     ///
     /// The peer is discouraged if the new score exceeds the corresponding threshold.
@@ -80,6 +86,13 @@ pub enum PeerManagerEvent {
         block_id: Id<Block>,
     },
 
+    /// A block has been received from the peer while the same block, received from another peer,
+    /// was still being processed; the copy has been dropped without being processed.
+    DuplicateBlockReceived {
+        peer_id: PeerId,
+        block_id: Id<Block>,
+    },
+
     /// A new tip block has been added to the chainstate
     ///
     /// Note: normally, NewTipReceived and NewChainstateTip are dependent in the sense
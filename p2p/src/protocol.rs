@@ -43,6 +43,9 @@ impl ProtocolVersion {
 pub enum SupportedProtocolVersion {
     V2 = 2,
     V3 = 3,
+    V4 = 4,
+    V5 = 5,
+    V6 = 6,
 }
 
 lazy_static::lazy_static! {
@@ -88,6 +91,12 @@ make_config_setting!(MaxMessageSize, usize, 10 * 1024 * 1024);
 make_config_setting!(MaxPeerTxAnnouncements, usize, 5000);
 make_config_setting!(MaxUnconnectedHeaders, usize, 10);
 make_config_setting!(MaxAddrListResponseAddressCount, usize, 1000);
+make_config_setting!(MaxBlockChunksPerRequest, usize, 16);
+make_config_setting!(BlockChunkingThreshold, usize, 4 * 1024 * 1024);
+make_config_setting!(BlockChunkSize, usize, 512 * 1024);
+make_config_setting!(MaxChunkedBlockSize, usize, 64 * 1024 * 1024);
+make_config_setting!(MaxBlockFilterHeadersPerRequest, usize, 2000);
+make_config_setting!(MaxBlockFiltersPerRequest, usize, 1000);
 
 /// Protocol configuration. These values are supposed to be modified in tests only.
 ///
@@ -107,6 +116,12 @@ pub struct ProtocolConfig {
     pub max_request_blocks_count: RequestedBlocksLimit,
     /// The maximum number of addresses that a single AddrListResponse may contain.
     pub max_addr_list_response_address_count: MaxAddrListResponseAddressCount,
+    /// The maximum number of block chunks that can be requested in one message.
+    pub max_block_chunks_per_request: MaxBlockChunksPerRequest,
+    /// The maximum number of blocks whose filter hashes can be requested in one message.
+    pub max_block_filter_headers_per_request: MaxBlockFilterHeadersPerRequest,
+    /// The maximum number of blocks whose compact filters can be requested in one message.
+    pub max_block_filters_per_request: MaxBlockFiltersPerRequest,
 
     // "Soft" limits:
     /// The maximum number of elements in a locator.
@@ -115,4 +130,10 @@ pub struct ProtocolConfig {
     pub max_message_size: MaxMessageSize,
     /// The maximum number of announcements (hashes) for which we haven't receive transactions.
     pub max_peer_tx_announcements: MaxPeerTxAnnouncements,
+    /// Blocks whose encoded size exceeds this value are sent in chunks to peers that support it.
+    pub block_chunking_threshold: BlockChunkingThreshold,
+    /// The size of the chunks that big blocks are split into when sent to peers.
+    pub block_chunk_size: BlockChunkSize,
+    /// The maximum size of a block that we agree to download in chunks.
+    pub max_chunked_block_size: MaxChunkedBlockSize,
 }
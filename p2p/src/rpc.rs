@@ -21,7 +21,10 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use serialization::hex_encoded::HexEncoded;
 use utils_networking::IpOrSocketAddress;
 
-use crate::{interface::types::ConnectedPeer, types::peer_id::PeerId};
+use crate::{
+    interface::types::{ConnectedPeer, ReachabilityInfo},
+    types::peer_id::PeerId,
+};
 use rpc::RpcResult;
 
 #[rpc::describe]
@@ -79,6 +82,14 @@ trait P2pRpc {
     #[method(name = "get_connected_peers")]
     async fn get_connected_peers(&self) -> RpcResult<Vec<ConnectedPeer>>;
 
+    /// Check whether this node appears to be reachable from the internet.
+    ///
+    /// The node is considered reachable if at least one inbound connection from a publicly
+    /// routable address has been accepted since startup. This can be used to verify that
+    /// port forwarding is set up correctly.
+    #[method(name = "check_reachability")]
+    async fn check_reachability(&self) -> RpcResult<ReachabilityInfo>;
+
     /// Get addresses of reserved nodes.
     #[method(name = "get_reserved_nodes")]
     async fn get_reserved_nodes(&self) -> RpcResult<Vec<SocketAddress>>;
@@ -162,6 +173,11 @@ impl P2pRpcServer for super::P2pHandle {
         rpc::handle_result(res)
     }
 
+    async fn check_reachability(&self) -> RpcResult<ReachabilityInfo> {
+        let res = self.call_async(|this| this.get_reachability()).await;
+        rpc::handle_result(res)
+    }
+
     async fn get_reserved_nodes(&self) -> RpcResult<Vec<SocketAddress>> {
         let res = self.call_async(|this| this.get_reserved_nodes()).await;
         rpc::handle_result(res)
@@ -0,0 +1,266 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transfer of big blocks in chunks.
+//!
+//! A block whose encoded size exceeds [ProtocolConfig::block_chunking_threshold] is announced
+//! to the requesting peer via [BlockChunkManifest] instead of being sent as a whole; the peer
+//! then requests the individual chunks. Partially downloaded blocks are kept in
+//! [BlockChunkDownloads], which is shared by all peer tasks. This way, all peers that have sent
+//! the same manifest can serve chunks of the same block simultaneously, and a download that was
+//! interrupted by a disconnect is resumed by another peer.
+
+use std::collections::BTreeMap;
+
+use common::{
+    chain::Block,
+    primitives::{
+        id::{default_hash, hash_encoded},
+        Id, H256,
+    },
+};
+use utils::sync::Mutex;
+
+use crate::{
+    message::{BlockChunkEncoding, BlockChunkManifest},
+    protocol::ProtocolConfig,
+    types::peer_id::PeerId,
+};
+
+/// The maximum number of partially downloaded blocks that are kept simultaneously.
+const MAX_DOWNLOADS: usize = 8;
+
+/// Create the manifest for the given encoded block.
+pub fn make_manifest(
+    block_id: Id<Block>,
+    encoded_block: &[u8],
+    chunk_size: usize,
+) -> BlockChunkManifest {
+    let chunk_hashes = encoded_block.chunks(chunk_size).map(default_hash).collect();
+    BlockChunkManifest::new(
+        block_id,
+        BlockChunkEncoding::Plain,
+        u32::try_from(encoded_block.len()).expect("block size must fit into u32"),
+        u32::try_from(chunk_size).expect("chunk size must fit into u32"),
+        chunk_hashes,
+    )
+}
+
+/// Return the chunk with the specified index from the encoded block, if it exists.
+pub fn get_chunk(encoded_block: &[u8], chunk_size: usize, chunk_index: u32) -> Option<&[u8]> {
+    encoded_block.chunks(chunk_size).nth(chunk_index as usize)
+}
+
+/// Check that the manifest received from a peer is consistent and acceptable for us.
+pub fn is_manifest_valid(manifest: &BlockChunkManifest, protocol_config: &ProtocolConfig) -> bool {
+    let block_size = manifest.block_size() as usize;
+    let chunk_size = manifest.chunk_size() as usize;
+
+    match manifest.encoding() {
+        BlockChunkEncoding::Plain => {}
+    }
+
+    chunk_size != 0
+        && chunk_size < *protocol_config.max_message_size
+        && block_size != 0
+        && block_size <= *protocol_config.max_chunked_block_size
+        && manifest.chunk_hashes().len() == block_size.div_ceil(chunk_size)
+}
+
+/// Check that the chunk data corresponds to the manifest.
+pub fn is_chunk_valid(manifest: &BlockChunkManifest, chunk_index: u32, data: &[u8]) -> bool {
+    let chunk_count = manifest.chunk_hashes().len();
+    let expected_size = if chunk_index as usize + 1 == chunk_count {
+        manifest.block_size() as usize - (chunk_count - 1) * manifest.chunk_size() as usize
+    } else {
+        manifest.chunk_size() as usize
+    };
+
+    manifest
+        .chunk_hashes()
+        .get(chunk_index as usize)
+        .is_some_and(|hash| data.len() == expected_size && *hash == default_hash(data))
+}
+
+/// Downloads are identified by the block id and the hash of the manifest, so that peers that
+/// disagree on the chunking (or lie about it) don't interfere with each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DownloadKey {
+    block_id: Id<Block>,
+    manifest_hash: H256,
+}
+
+impl DownloadKey {
+    pub fn new(manifest: &BlockChunkManifest) -> Self {
+        Self {
+            block_id: *manifest.block_id(),
+            manifest_hash: hash_encoded(manifest),
+        }
+    }
+}
+
+struct Download {
+    chunks: Vec<Option<Vec<u8>>>,
+    received_count: usize,
+    /// Chunks that have been requested but not received yet and the peers they were requested from.
+    in_flight: BTreeMap<u32, PeerId>,
+    /// Used to evict the oldest download when there are too many of them.
+    seq_no: u64,
+}
+
+impl Download {
+    fn new(manifest: &BlockChunkManifest, seq_no: u64) -> Self {
+        Self {
+            chunks: vec![None; manifest.chunk_hashes().len()],
+            received_count: 0,
+            in_flight: BTreeMap::new(),
+            seq_no,
+        }
+    }
+
+    fn missing_chunks(&self) -> impl Iterator<Item = u32> + '_ {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(idx, _)| idx as u32)
+    }
+}
+
+pub enum AddChunkResult {
+    /// The chunk has been stored; more chunks are needed.
+    Added,
+    /// This was the last missing chunk; the download is finished and the encoded block
+    /// is returned.
+    Completed(Vec<u8>),
+}
+
+struct DownloadsState {
+    downloads: BTreeMap<DownloadKey, Download>,
+    next_seq_no: u64,
+}
+
+impl DownloadsState {
+    fn get_or_create(&mut self, key: DownloadKey, manifest: &BlockChunkManifest) -> &mut Download {
+        if !self.downloads.contains_key(&key) {
+            if self.downloads.len() >= MAX_DOWNLOADS {
+                let oldest_key = self
+                    .downloads
+                    .iter()
+                    .min_by_key(|(_, download)| download.seq_no)
+                    .map(|(key, _)| *key);
+                if let Some(oldest_key) = oldest_key {
+                    self.downloads.remove(&oldest_key);
+                }
+            }
+
+            let seq_no = self.next_seq_no;
+            self.next_seq_no += 1;
+            self.downloads.insert(key, Download::new(manifest, seq_no));
+        }
+
+        self.downloads.get_mut(&key).expect("the download must exist")
+    }
+}
+
+/// Partially downloaded chunked blocks, shared by all peers.
+pub struct BlockChunkDownloads {
+    state: Mutex<DownloadsState>,
+}
+
+impl BlockChunkDownloads {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(DownloadsState {
+                downloads: BTreeMap::new(),
+                next_seq_no: 0,
+            }),
+        }
+    }
+
+    /// Choose up to `max_count` chunks to request from the peer.
+    ///
+    /// Chunks that haven't been requested from anyone are preferred; once there are no such chunks,
+    /// the ones that are in flight from other peers are chosen, so that a slow peer can't delay
+    /// the completion of the download.
+    pub fn assign_chunks(
+        &self,
+        manifest: &BlockChunkManifest,
+        peer_id: PeerId,
+        max_count: usize,
+    ) -> Vec<u32> {
+        let mut state = self.state.lock().expect("poisoned mutex");
+        let download = state.get_or_create(DownloadKey::new(manifest), manifest);
+
+        let (unassigned, assigned_to_others): (Vec<_>, Vec<_>) = download
+            .missing_chunks()
+            .filter(|idx| download.in_flight.get(idx) != Some(&peer_id))
+            .partition(|idx| !download.in_flight.contains_key(idx));
+
+        let chunks: Vec<_> =
+            unassigned.into_iter().chain(assigned_to_others).take(max_count).collect();
+        for idx in &chunks {
+            download.in_flight.insert(*idx, peer_id);
+        }
+
+        chunks
+    }
+
+    /// Store a chunk that has already been checked against the manifest.
+    pub fn add_chunk(
+        &self,
+        manifest: &BlockChunkManifest,
+        chunk_index: u32,
+        data: Vec<u8>,
+    ) -> AddChunkResult {
+        let key = DownloadKey::new(manifest);
+        let mut state = self.state.lock().expect("poisoned mutex");
+        let download = state.get_or_create(key, manifest);
+
+        download.in_flight.remove(&chunk_index);
+        let chunk = &mut download.chunks[chunk_index as usize];
+        if chunk.is_none() {
+            *chunk = Some(data);
+            download.received_count += 1;
+        }
+
+        if download.received_count < download.chunks.len() {
+            return AddChunkResult::Added;
+        }
+
+        let download = state.downloads.remove(&key).expect("the download must exist");
+        AddChunkResult::Completed(download.chunks.into_iter().flatten().flatten().collect())
+    }
+
+    /// Forget the download, e.g. because the block has been obtained by other means.
+    pub fn remove(&self, manifest: &BlockChunkManifest) {
+        let mut state = self.state.lock().expect("poisoned mutex");
+        state.downloads.remove(&DownloadKey::new(manifest));
+    }
+
+    /// Make the chunks that were requested from a disconnected peer available for other peers.
+    pub fn release_peer(&self, peer_id: PeerId) {
+        let mut state = self.state.lock().expect("poisoned mutex");
+        for download in state.downloads.values_mut() {
+            download.in_flight.retain(|_, id| *id != peer_id);
+        }
+    }
+}
+
+impl Default for BlockChunkDownloads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
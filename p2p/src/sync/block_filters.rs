@@ -0,0 +1,141 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Serving compact block filters to light clients.
+//!
+//! A client requests the filter hashes of a range of main chain blocks and checks them against
+//! the filter headers obtained from other peers; then it requests the filters themselves.
+//! Filters are only available if the block filter index is enabled in the chainstate config.
+
+use chainstate::chainstate_interface::ChainstateInterface;
+use common::{
+    chain::{Block, GenBlockId},
+    primitives::{BlockHeight, Id, H256},
+};
+
+use crate::{
+    error::{P2pError, ProtocolError},
+    message::{BlockFilterHeaders, BlockFilterRequest, BlockFilterResponse},
+};
+
+/// Get the ids of the requested main chain blocks in the chain order; `None` is returned
+/// if the stop block is not in our main chain.
+fn get_requested_block_ids(
+    chainstate: &dyn ChainstateInterface,
+    request: &BlockFilterRequest,
+    max_block_count: usize,
+) -> crate::Result<Option<Vec<Id<Block>>>> {
+    let start_height = request.start_height();
+    let stop_block_id = *request.stop_block_id();
+
+    let Some(stop_height) = chainstate.get_block_height_in_main_chain(&stop_block_id.into())?
+    else {
+        return Ok(None);
+    };
+
+    utils::ensure!(
+        start_height > BlockHeight::zero() && start_height <= stop_height,
+        P2pError::ProtocolError(ProtocolError::InvalidBlockFilterRequest(stop_block_id))
+    );
+
+    let block_count = stop_height.into_int() - start_height.into_int() + 1;
+    let block_count = usize::try_from(block_count).unwrap_or(usize::MAX);
+    utils::ensure!(
+        block_count <= max_block_count,
+        P2pError::ProtocolError(ProtocolError::BlockFiltersRequestLimitExceeded(
+            block_count,
+            max_block_count
+        ))
+    );
+
+    let chain_config = chainstate.get_chain_config();
+    let block_ids = start_height
+        .iter_up_to_including(stop_height)
+        .map(|height| {
+            let block_id = chainstate
+                .get_block_id_from_height(&height)?
+                .and_then(|id| id.to_block_id(chain_config))
+                .expect("the main chain blocks below the stop block must exist");
+            Ok(block_id)
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    Ok(Some(block_ids))
+}
+
+/// Make the response to a filter headers request.
+pub fn make_block_filter_headers(
+    chainstate: &dyn ChainstateInterface,
+    request: &BlockFilterRequest,
+    max_block_count: usize,
+) -> crate::Result<BlockFilterHeaders> {
+    let stop_block_id = *request.stop_block_id();
+    let unavailable = BlockFilterHeaders::new(stop_block_id, H256::zero(), Vec::new());
+
+    let Some(block_ids) = get_requested_block_ids(chainstate, request, max_block_count)? else {
+        return Ok(unavailable);
+    };
+
+    let prev_block_id = chainstate
+        .get_block_id_from_height(
+            &request.start_height().prev_height().expect("start height is positive"),
+        )?
+        .expect("the main chain blocks below the stop block must exist");
+    let prev_filter_header = match prev_block_id.classify(chainstate.get_chain_config()) {
+        GenBlockId::Genesis(_) => H256::zero(),
+        GenBlockId::Block(prev_block_id) => {
+            match chainstate.get_block_filter_commitment(&prev_block_id)? {
+                Some(commitment) => *commitment.filter_header(),
+                None => return Ok(unavailable),
+            }
+        }
+    };
+
+    let mut filter_hashes = Vec::with_capacity(block_ids.len());
+    for block_id in block_ids {
+        match chainstate.get_block_filter_commitment(&block_id)? {
+            Some(commitment) => filter_hashes.push(*commitment.filter_hash()),
+            None => return Ok(unavailable),
+        }
+    }
+
+    Ok(BlockFilterHeaders::new(
+        stop_block_id,
+        prev_filter_header,
+        filter_hashes,
+    ))
+}
+
+/// Get the filters of the requested blocks. Nothing is returned if the stop block is not in our
+/// main chain; if some of the filters are not available, only the preceding ones are returned.
+pub fn get_block_filters(
+    chainstate: &dyn ChainstateInterface,
+    request: &BlockFilterRequest,
+    max_block_count: usize,
+) -> crate::Result<Vec<BlockFilterResponse>> {
+    let Some(block_ids) = get_requested_block_ids(chainstate, request, max_block_count)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut filters = Vec::with_capacity(block_ids.len());
+    for block_id in block_ids {
+        match chainstate.get_block_filter(&block_id)? {
+            Some(filter) => filters.push(BlockFilterResponse::new(block_id, filter)),
+            None => break,
+        }
+    }
+
+    Ok(filters)
+}
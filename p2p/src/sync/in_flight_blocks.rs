@@ -0,0 +1,121 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deduplication of the blocks received from multiple peers simultaneously.
+//!
+//! The same block may be delivered by several peers at nearly the same time. Each peer task
+//! registers the received block in [InFlightBlocks], which is shared by all peer tasks, before
+//! submitting it to the chainstate, and the block stays registered until the chainstate has
+//! finished processing it. A copy of the block that arrives while it is registered is not
+//! submitted again.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use common::{chain::Block, primitives::Id};
+use utils::sync::Mutex;
+
+use crate::types::peer_id::PeerId;
+
+/// Blocks received from peers that are being processed by the chainstate, shared by all peers.
+pub struct InFlightBlocks {
+    blocks: Mutex<BTreeMap<Id<Block>, PeerId>>,
+}
+
+impl InFlightBlocks {
+    pub fn new() -> Self {
+        Self {
+            blocks: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Register the block received from the specified peer.
+    ///
+    /// If the block is already being processed, the id of the peer that has sent the first copy
+    /// is returned as the error. Otherwise, the block stays registered until the returned guard
+    /// is dropped.
+    pub fn try_start(
+        self: &Arc<Self>,
+        block_id: Id<Block>,
+        peer_id: PeerId,
+    ) -> Result<InFlightBlockGuard, PeerId> {
+        let mut blocks = self.blocks.lock().expect("poisoned mutex");
+
+        if let Some(first_peer_id) = blocks.get(&block_id) {
+            return Err(*first_peer_id);
+        }
+
+        blocks.insert(block_id, peer_id);
+        Ok(InFlightBlockGuard {
+            in_flight_blocks: Arc::clone(self),
+            block_id,
+        })
+    }
+}
+
+impl Default for InFlightBlocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes the block from [InFlightBlocks] when dropped.
+#[must_use]
+pub struct InFlightBlockGuard {
+    in_flight_blocks: Arc<InFlightBlocks>,
+    block_id: Id<Block>,
+}
+
+impl Drop for InFlightBlockGuard {
+    fn drop(&mut self) {
+        self.in_flight_blocks
+            .blocks
+            .lock()
+            .expect("poisoned mutex")
+            .remove(&self.block_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::primitives::H256;
+
+    use super::*;
+
+    #[test]
+    fn dedupe() {
+        let in_flight = Arc::new(InFlightBlocks::new());
+        let block1 = Id::<Block>::new(H256::from_low_u64_be(1));
+        let block2 = Id::<Block>::new(H256::from_low_u64_be(2));
+        let peer1 = PeerId::new();
+        let peer2 = PeerId::new();
+
+        let guard1 = in_flight.try_start(block1, peer1).unwrap();
+
+        // The second copy is rejected, no matter which peer has sent it
+        assert_eq!(in_flight.try_start(block1, peer2).err(), Some(peer1));
+        assert_eq!(in_flight.try_start(block1, peer1).err(), Some(peer1));
+
+        // Other blocks are not affected
+        let guard2 = in_flight.try_start(block2, peer2).unwrap();
+
+        // Once the first copy has been processed, the block can be submitted again
+        drop(guard1);
+        let _guard1 = in_flight.try_start(block1, peer2).unwrap();
+        assert_eq!(in_flight.try_start(block2, peer1).err(), Some(peer2));
+
+        drop(guard2);
+        let _guard2 = in_flight.try_start(block2, peer1).unwrap();
+    }
+}
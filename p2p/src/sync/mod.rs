@@ -16,12 +16,17 @@
 //! This module is responsible for both initial syncing and further blocks processing (the reaction
 //! to block announcement from peers and the announcement of blocks produced by this node).
 
+mod block_chunks;
+mod block_filters;
 mod chainstate_handle;
+mod in_flight_blocks;
 mod peer;
 mod peer_activity;
 mod peer_common;
 pub mod sync_status;
 
+pub use peer::header_verification::HeaderCheckError;
+
 use std::collections::HashMap;
 
 use dyn_clone::DynClone;
@@ -29,22 +34,24 @@ use futures::never::Never;
 use tokio::{
     sync::mpsc::{self, Receiver, UnboundedReceiver, UnboundedSender},
     task::JoinSet,
+    time::Instant,
 };
 
 use common::{
-    chain::{config::ChainConfig, Block, Transaction},
+    chain::{config::ChainConfig, Block},
     primitives::Id,
     time_getter::TimeGetter,
 };
 use logging::log;
-use mempool::{event::TransactionProcessed, tx_origin::TxOrigin, MempoolHandle};
+use mempool::{event::TransactionProcessed, tx_origin::TxOrigin, MempoolHandle, SeenTransactions};
+use randomness::{make_pseudo_rng, Rng};
 use tracing::Instrument;
 use utils::{sync::Arc, tap_log::TapLog};
 
 use crate::{
     config::P2pConfig,
     error::P2pError,
-    message::{BlockSyncMessage, TransactionSyncMessage},
+    message::{BlockSyncMessage, TransactionAnnouncement, TransactionSyncMessage},
     net::{
         types::{services::Services, SyncingEvent},
         MessagingService, NetworkingService, SyncingEventReceiver,
@@ -54,12 +61,17 @@ use crate::{
     PeerManagerEvent, Result,
 };
 
-use self::chainstate_handle::ChainstateHandle;
+use self::{
+    block_chunks::BlockChunkDownloads, chainstate_handle::ChainstateHandle,
+    in_flight_blocks::InFlightBlocks,
+};
 
 #[derive(Debug, Clone)]
 pub enum LocalEvent {
     ChainstateNewTip(Id<Block>),
-    MempoolNewTx(Id<Transaction>),
+    MempoolNewTx(TransactionAnnouncement),
+    /// A local transaction that has stayed in the mempool for a long time is announced again.
+    MempoolTxRebroadcast(TransactionAnnouncement),
 }
 
 pub struct PeerContext {
@@ -85,6 +97,12 @@ pub struct SyncManager<T: NetworkingService> {
     chainstate_handle: ChainstateHandle,
     mempool_handle: MempoolHandle,
 
+    /// Partially downloaded chunked blocks, shared by all peers.
+    block_chunk_downloads: Arc<BlockChunkDownloads>,
+
+    /// Blocks received from peers that are being processed by the chainstate, shared by all peers.
+    in_flight_blocks: Arc<InFlightBlocks>,
+
     /// The list of connected peers
     peers: HashMap<PeerId, PeerContext>,
 
@@ -146,6 +164,8 @@ where
             peer_mgr_event_sender,
             chainstate_handle: ChainstateHandle::new(chainstate_handle),
             mempool_handle,
+            block_chunk_downloads: Arc::new(BlockChunkDownloads::new()),
+            in_flight_blocks: Arc::new(InFlightBlocks::new()),
             peers: Default::default(),
             time_getter,
             observer,
@@ -158,6 +178,8 @@ where
 
         let mut new_tip_receiver = subscribe_to_new_tip(&self.chainstate_handle).await?;
         let mut tx_processed_receiver = subscribe_to_tx_processed(&self.mempool_handle).await?;
+        let seen_transactions = self.mempool_handle.call(|m| m.seen_transactions()).await?;
+        let mut next_tx_rebroadcast_time = self.next_tx_rebroadcast_time();
 
         loop {
             tokio::select! {
@@ -173,20 +195,68 @@ where
                 },
 
                 event = self.syncing_event_receiver.poll_next() => {
-                    self.handle_peer_event(event?).await;
+                    self.handle_peer_event(event?, &seen_transactions).await;
+                },
+
+                _ = Self::wait_until(next_tx_rebroadcast_time) => {
+                    self.rebroadcast_local_transactions().await?;
+                    next_tx_rebroadcast_time = self.next_tx_rebroadcast_time();
                 },
             }
         }
     }
 
+    /// The time of the next rebroadcast of the local transactions, or `None` if rebroadcasts
+    /// are disabled. The interval is randomized by up to a half of its value in either direction.
+    fn next_tx_rebroadcast_time(&self) -> Option<Instant> {
+        let interval = *self.p2p_config.tx_rebroadcast_interval;
+        if interval.is_zero() {
+            return None;
+        }
+
+        let delay = interval.mul_f64(make_pseudo_rng().gen_range(0.5..1.5));
+        Some(Instant::now() + delay)
+    }
+
+    async fn wait_until(time: Option<Instant>) {
+        match time {
+            Some(time) => tokio::time::sleep_until(time).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Announce the local transactions that have been in the mempool for longer than the
+    /// rebroadcast interval to all peers again, in case they were lost on the way to the block
+    /// producers.
+    async fn rebroadcast_local_transactions(&mut self) -> Result<()> {
+        let min_age = *self.p2p_config.tx_rebroadcast_interval;
+        let txs = self
+            .mempool_handle
+            .call(move |m| m.local_transactions_to_rebroadcast(min_age))
+            .await?;
+
+        if !txs.is_empty() {
+            log::debug!("Rebroadcasting {} local transactions", txs.len());
+        }
+
+        for (tx_id, wtxid) in txs {
+            let announcement = TransactionAnnouncement::new(tx_id, wtxid);
+            self.send_local_event(&LocalEvent::MempoolTxRebroadcast(announcement));
+        }
+
+        Ok(())
+    }
+
     /// Starts a task for the new peer.
     pub fn register_peer(
         &mut self,
         peer_id: PeerId,
         common_services: Services,
-        _protocol_version: SupportedProtocolVersion,
+        protocol_version: SupportedProtocolVersion,
+        is_whitelisted: bool,
         block_sync_msg_receiver: Receiver<BlockSyncMessage>,
         transaction_sync_msg_receiver: Receiver<TransactionSyncMessage>,
+        seen_transactions: SeenTransactions,
     ) {
         log::debug!("Register peer {peer_id} to sync manager");
 
@@ -197,9 +267,12 @@ where
         let mut mgr = peer::block_manager::PeerBlockSyncManager::<T>::new(
             peer_id,
             common_services,
+            protocol_version,
             Arc::clone(&self.chain_config),
             Arc::clone(&self.p2p_config),
             self.chainstate_handle.clone(),
+            Arc::clone(&self.block_chunk_downloads),
+            Arc::clone(&self.in_flight_blocks),
             self.peer_mgr_event_sender.clone(),
             block_sync_msg_receiver,
             self.messaging_handle.clone(),
@@ -220,9 +293,12 @@ where
         let mut mgr = peer::transaction_manager::PeerTransactionSyncManager::<T>::new(
             peer_id,
             common_services,
+            protocol_version,
+            is_whitelisted,
             Arc::clone(&self.p2p_config),
             self.chainstate_handle.clone(),
             self.mempool_handle.clone(),
+            seen_transactions,
             self.peer_mgr_event_sender.clone(),
             transaction_sync_msg_receiver,
             self.messaging_handle.clone(),
@@ -258,6 +334,7 @@ where
             .unwrap_or_else(|| panic!("Unregistering unknown peer: {peer_id}"));
         // Call `abort` because the peer tasks may be sleeping for a long time in the `sync_clock` function
         peer.tasks.abort_all();
+        self.block_chunk_downloads.release_peer(peer_id);
     }
 
     fn send_local_event(&mut self, event: &LocalEvent) {
@@ -294,7 +371,9 @@ where
                 match tx_proc_event.relay_policy() {
                     TxRelayPolicy::DoRelay => {
                         log::info!("Broadcasting transaction {tx_id} originating in {origin}");
-                        self.send_local_event(&LocalEvent::MempoolNewTx(tx_id));
+                        let announcement =
+                            TransactionAnnouncement::new(tx_id, *tx_proc_event.wtxid());
+                        self.send_local_event(&LocalEvent::MempoolNewTx(announcement));
                     }
                     TxRelayPolicy::DontRelay => {
                         log::trace!("Not propagating transaction {tx_id} originating in {origin}");
@@ -340,20 +419,27 @@ where
     }
 
     /// Sends an event to the corresponding peer.
-    async fn handle_peer_event(&mut self, event: SyncingEvent) {
+    async fn handle_peer_event(
+        &mut self,
+        event: SyncingEvent,
+        seen_transactions: &SeenTransactions,
+    ) {
         match event {
             SyncingEvent::Connected {
                 peer_id,
                 common_services,
                 protocol_version,
+                is_whitelisted,
                 block_sync_msg_receiver,
                 transaction_sync_msg_receiver,
             } => self.register_peer(
                 peer_id,
                 common_services,
                 protocol_version,
+                is_whitelisted,
                 block_sync_msg_receiver,
                 transaction_sync_msg_receiver,
+                seen_transactions.clone(),
             ),
             SyncingEvent::Disconnected { peer_id } => {
                 Self::notify_mempool_peer_disconnected(&self.mempool_handle, peer_id).await;
@@ -388,6 +474,7 @@ pub async fn subscribe_to_new_tip(
                 chainstate::ChainstateEvent::NewTip(block_id, _) => {
                     let _ = sender.send(block_id).log_err_pfx("The new tip receiver closed");
                 }
+                chainstate::ChainstateEvent::LongFork(_, _, _) => {}
             },
         );
 
@@ -411,7 +498,9 @@ pub async fn subscribe_to_tx_processed(
         mempool::event::MempoolEvent::TransactionProcessed(tpe) => {
             let _ = sender.send(tpe).log_err_pfx("The tx processed receiver closed");
         }
-        mempool::event::MempoolEvent::NewTip(_) => (),
+        mempool::event::MempoolEvent::NewTip(_)
+        | mempool::event::MempoolEvent::TransactionReplaced(_)
+        | mempool::event::MempoolEvent::TransactionRemoved(_) => (),
     };
     let subscribe_func = Arc::new(subscribe_func);
 
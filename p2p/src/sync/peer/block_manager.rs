@@ -16,6 +16,7 @@
 use std::{
     collections::{BTreeSet, VecDeque},
     mem,
+    num::NonZeroUsize,
 };
 
 use itertools::Itertools;
@@ -31,6 +32,7 @@ use common::{
     time_getter::TimeGetter,
 };
 use logging::log;
+use serialization::{DecodeAll, Encode};
 use utils::const_value::ConstValue;
 use utils::sync::Arc;
 
@@ -38,14 +40,22 @@ use crate::{
     config::P2pConfig,
     disconnection_reason::DisconnectionReason,
     error::{P2pError, PeerError, ProtocolError, SyncError},
-    message::{BlockListRequest, BlockResponse, BlockSyncMessage, HeaderList, HeaderListRequest},
+    message::{
+        BlockChunk, BlockChunkManifest, BlockChunkRequest, BlockFilterRequest, BlockListRequest,
+        BlockResponse, BlockSyncMessage, HeaderList, HeaderListRequest,
+    },
     net::{
         types::services::{Service, Services},
         NetworkingService,
     },
     peer_manager_event::PeerDisconnectionDbAction,
+    protocol::SupportedProtocolVersion,
     sync::{
+        block_chunks::{self, AddChunkResult, BlockChunkDownloads},
+        block_filters,
         chainstate_handle::ChainstateHandle,
+        in_flight_blocks::InFlightBlocks,
+        peer::header_verification,
         peer_activity::PeerActivity,
         peer_common::{choose_peers_best_block, handle_message_processing_result},
         sync_status::PeerBlockSyncStatus,
@@ -56,6 +66,10 @@ use crate::{
     MessagingService, PeerManagerEvent, Result,
 };
 
+/// The maximum total size of the blocks that are fetched from the chainstate at once when
+/// sending them to the peer.
+const SEND_BLOCKS_BATCH_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+
 // TODO: Take into account the chain work when syncing.
 /// Block syncing manager.
 ///
@@ -65,7 +79,12 @@ pub struct PeerBlockSyncManager<T: NetworkingService> {
     chain_config: Arc<ChainConfig>,
     p2p_config: Arc<P2pConfig>,
     common_services: Services,
+    protocol_version: SupportedProtocolVersion,
     chainstate_handle: ChainstateHandle,
+    /// Partially downloaded chunked blocks, shared with other peers.
+    block_chunk_downloads: Arc<BlockChunkDownloads>,
+    /// Blocks that are being processed by the chainstate, shared with other peers.
+    in_flight_blocks: Arc<InFlightBlocks>,
     peer_mgr_event_sender: UnboundedSender<PeerManagerEvent>,
     messaging_handle: T::MessagingHandle,
     sync_msg_receiver: Receiver<BlockSyncMessage>,
@@ -93,11 +112,25 @@ struct IncomingDataState {
     /// This includes headers received by any means, e.g. via HeaderList messages, as part
     /// of a locator during peer's header requests, via block responses.
     peers_best_block_that_we_have: Option<Id<GenBlock>>,
+    /// The chunked transfer of the first requested block, if the peer has sent us its manifest.
+    chunked_block: Option<IncomingChunkedBlock>,
+    /// The last block whose chunked transfer has been finished. Chunks of this block that
+    /// were requested from this peer may still arrive after the block has been obtained.
+    finished_chunked_block: Option<Id<Block>>,
+}
+
+struct IncomingChunkedBlock {
+    manifest: BlockChunkManifest,
+    /// Chunks that we requested from this peer and haven't received yet.
+    requested_chunks: BTreeSet<u32>,
 }
 
 struct OutgoingDataState {
     /// A queue of the blocks requested by this peer.
     blocks_queue: VecDeque<Id<Block>>,
+    /// The block that is being sent to the peer in chunks. Sending the blocks from the queue
+    /// is suspended until the peer tells us that it has obtained this block.
+    chunked_block: Option<OutgoingChunkedBlock>,
     /// The index of the best block that we've sent to the peer.
     best_sent_block: Option<BlockIndex>,
     /// The id of the best block header that we've sent to the peer.
@@ -105,6 +138,12 @@ struct OutgoingDataState {
     best_sent_block_header: Option<Id<GenBlock>>,
 }
 
+struct OutgoingChunkedBlock {
+    block_id: Id<Block>,
+    encoded_block: Vec<u8>,
+    chunk_size: usize,
+}
+
 impl<T> PeerBlockSyncManager<T>
 where
     T: NetworkingService,
@@ -114,9 +153,12 @@ where
     pub fn new(
         id: PeerId,
         common_services: Services,
+        protocol_version: SupportedProtocolVersion,
         chain_config: Arc<ChainConfig>,
         p2p_config: Arc<P2pConfig>,
         chainstate_handle: ChainstateHandle,
+        block_chunk_downloads: Arc<BlockChunkDownloads>,
+        in_flight_blocks: Arc<InFlightBlocks>,
         peer_mgr_event_sender: UnboundedSender<PeerManagerEvent>,
         sync_msg_receiver: Receiver<BlockSyncMessage>,
         messaging_handle: T::MessagingHandle,
@@ -128,7 +170,10 @@ where
             chain_config,
             p2p_config,
             common_services,
+            protocol_version,
             chainstate_handle,
+            block_chunk_downloads,
+            in_flight_blocks,
             peer_mgr_event_sender,
             messaging_handle,
             sync_msg_receiver,
@@ -138,9 +183,12 @@ where
                 pending_headers: Vec::new(),
                 requested_blocks: VecDeque::new(),
                 peers_best_block_that_we_have: None,
+                chunked_block: None,
+                finished_chunked_block: None,
             },
             outgoing: OutgoingDataState {
                 blocks_queue: VecDeque::new(),
+                chunked_block: None,
                 best_sent_block: None,
                 best_sent_block_header: None,
             },
@@ -183,10 +231,8 @@ where
                     self.handle_message(message).await?;
                 }
 
-                block_to_send_to_peer = async {
-                    self.outgoing.blocks_queue.pop_front().expect("The block queue is empty")
-                }, if !self.outgoing.blocks_queue.is_empty() => {
-                    self.send_block(block_to_send_to_peer).await?;
+                _ = async {}, if !self.outgoing.blocks_queue.is_empty() && self.outgoing.chunked_block.is_none() => {
+                    self.send_blocks().await?;
                 }
 
                 event = self.local_event_receiver.recv() => {
@@ -279,7 +325,7 @@ where
                 let (headers, best_block_id) = self
                     .chainstate_handle
                     .call(move |c| {
-                        let best_block_id = c.get_best_block_id()?;
+                        let (_, best_block_id) = c.get_best_block_height_and_id()?;
 
                         let headers =
                             c.get_mainchain_headers_since_latest_fork_point(&block_ids, limit)?;
@@ -321,7 +367,7 @@ where
 
         match event {
             LocalEvent::ChainstateNewTip(new_tip_id) => self.handle_new_tip(&new_tip_id).await,
-            LocalEvent::MempoolNewTx(_) => Ok(()),
+            LocalEvent::MempoolNewTx(_) | LocalEvent::MempoolTxRebroadcast(_) => Ok(()),
         }
     }
 
@@ -358,6 +404,20 @@ where
             }
             BlockSyncMessage::HeaderList(l) => self.handle_header_list(l.into_headers()).await,
             BlockSyncMessage::BlockResponse(r) => self.handle_block_response(r.into_block()).await,
+            BlockSyncMessage::BlockChunkManifest(m) => self.handle_block_chunk_manifest(m).await,
+            BlockSyncMessage::BlockChunkRequest(r) => self.handle_block_chunk_request(r),
+            BlockSyncMessage::BlockChunk(c) => self.handle_block_chunk(c).await,
+            BlockSyncMessage::BlockChunksReceived(id) => self.handle_block_chunks_received(id),
+            BlockSyncMessage::BlockFilterHeadersRequest(r) => {
+                self.handle_block_filter_headers_request(r).await
+            }
+            BlockSyncMessage::BlockFiltersRequest(r) => self.handle_block_filters_request(r).await,
+            BlockSyncMessage::BlockFilterHeaders(_) | BlockSyncMessage::BlockFilter(_) => {
+                // We never request filters from peers.
+                Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
+                    "Block filter data is received, but it wasn't requested".to_owned(),
+                )))
+            }
 
             #[cfg(test)]
             BlockSyncMessage::TestSentinel(id) => {
@@ -411,7 +471,7 @@ where
                     )?
                 } else {
                     // If headers are empty, the peer already has our best block.
-                    Some(c.get_best_block_id()?)
+                    Some(c.get_best_block_height_and_id()?.1)
                 };
 
                 Ok((headers, peers_best_block_that_we_have))
@@ -486,7 +546,7 @@ where
                     // following check will fail without peer's fault. (But this situation should be rare, so we
                     // probably won't care about it anyway, because its impact - erroneously discourage/or be discouraged
                     // by a peer - is low.)
-                    // Also see a similar note in send_block.
+                    // Also see a similar note in send_blocks.
                     let index = c.get_block_index_for_persisted_block(&id)?.ok_or(
                         P2pError::ProtocolError(ProtocolError::UnknownBlockRequested(id)),
                     )?;
@@ -631,8 +691,9 @@ where
             .expect("cannot overflow");
         self.wait_for_clock_diff(last_header.timestamp(), last_header_height).await;
 
+        let headers_count = headers.len();
         let peer_may_have_more_headers =
-            headers.len() == *self.p2p_config.protocol_config.msg_header_count_limit;
+            headers_count == *self.p2p_config.protocol_config.msg_header_count_limit;
 
         // Filter out any existing headers from "headers" and determine the new value for
         // peers_best_block_that_we_have.
@@ -670,6 +731,14 @@ where
             return Ok(());
         }
 
+        // First do the checks that don't require the chainstate, in parallel.
+        let first_new_header_height = first_header_prev_block_height
+            .checked_add((headers_count - new_block_headers.len() + 1) as u64)
+            .expect("cannot overflow");
+        let new_block_headers = self
+            .verify_headers_in_segments(first_new_header_height, new_block_headers)
+            .await?;
+
         // Now use preliminary_headers_check; this can be done because the first header
         // is known to be connected to the chainstate.
         {
@@ -682,12 +751,61 @@ where
         self.request_blocks(new_block_headers)
     }
 
+    /// Perform the context-free checks of the headers on multiple threads.
+    ///
+    /// The headers are returned back, so that they can be passed to the chainstate afterwards.
+    async fn verify_headers_in_segments(
+        &self,
+        first_header_height: BlockHeight,
+        headers: Vec<SignedBlockHeader>,
+    ) -> Result<Vec<SignedBlockHeader>> {
+        let chain_config = Arc::clone(&self.chain_config);
+        let parallelism = std::thread::available_parallelism()
+            .unwrap_or(NonZeroUsize::MIN)
+            .min(header_verification::MAX_VERIFICATION_THREADS);
+
+        let (headers, result) = tokio::task::spawn_blocking(move || {
+            let segments = header_verification::plan_segments(
+                &chain_config,
+                first_header_height,
+                &headers,
+                parallelism,
+            );
+            let result = header_verification::verify_segments_in_parallel(
+                &chain_config,
+                &headers,
+                &segments,
+            );
+            (headers, result)
+        })
+        .await
+        .expect("Header verification task panicked");
+
+        result.map_err(|(block_id, err)| {
+            P2pError::ProtocolError(ProtocolError::InvalidHeader(block_id, err))
+        })?;
+
+        Ok(headers)
+    }
+
     async fn handle_block_response(&mut self, block: Block) -> Result<()> {
         let block_id = block.get_id();
         log::debug!("Handling block response, block id = {block_id}");
 
-        if self.incoming.requested_blocks.front() != Some(&block.get_id()) {
-            let idx = self.incoming.requested_blocks.iter().position(|id| id == &block.get_id());
+        if self.incoming.chunked_block.is_some() {
+            return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
+                "A block response is received during a chunked block transfer".to_owned(),
+            )));
+        }
+
+        self.check_next_requested_block(&block_id)?;
+        self.process_requested_block(block_id, Some(block)).await
+    }
+
+    /// Check that the block is the one that the peer is expected to send next.
+    fn check_next_requested_block(&self, block_id: &Id<Block>) -> Result<()> {
+        if self.incoming.requested_blocks.front() != Some(block_id) {
+            let idx = self.incoming.requested_blocks.iter().position(|id| id == block_id);
             // Note: we treat wrongly ordered blocks in the same way as unsolicited ones, i.e.
             // we don't remove their ids from the list.
             if idx.is_some() {
@@ -698,16 +816,28 @@ where
                             .requested_blocks
                             .front()
                             .expect("The deque is known to be non-empty"),
-                        actual_block_id: block.get_id(),
+                        actual_block_id: *block_id,
                     },
                 ));
             } else {
                 return Err(P2pError::ProtocolError(
-                    ProtocolError::UnsolicitedBlockReceived(block.get_id()),
+                    ProtocolError::UnsolicitedBlockReceived(*block_id),
                 ));
             }
         }
 
+        Ok(())
+    }
+
+    /// Process the first requested block.
+    ///
+    /// `block` is `None` if the block has already been obtained by other means (e.g. its chunked
+    /// transfer has been completed by another peer).
+    async fn process_requested_block(
+        &mut self,
+        block_id: Id<Block>,
+        block: Option<Block>,
+    ) -> Result<()> {
         self.incoming.requested_blocks.pop_front();
 
         if self.incoming.requested_blocks.is_empty() {
@@ -716,22 +846,44 @@ where
             self.peer_activity.set_expecting_blocks_since(Some(self.time_getter.get_time()));
         }
 
+        // If the same block received from another peer is still being processed, don't submit
+        // it again. The guard is held until the chainstate has processed the block.
+        let peer_id = self.id();
+        let (block, in_flight_guard) = match block {
+            Some(block) => match self.in_flight_blocks.try_start(block_id, peer_id) {
+                Ok(guard) => (Some(block), Some(guard)),
+                Err(first_peer_id) => {
+                    log::debug!(
+                        "Block {block_id} is already being processed, received first from peer {first_peer_id}"
+                    );
+                    self.peer_mgr_event_sender
+                        .send(PeerManagerEvent::DuplicateBlockReceived { peer_id, block_id })?;
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+
         // Process the block and also determine the new value for peers_best_block_that_we_have.
         let old_peers_best_block_that_we_have = self.incoming.peers_best_block_that_we_have;
         let (best_block, new_tip_received) = self
             .chainstate_handle
             .call_mut(move |c| {
-                let block = c.preliminary_block_check(block)?;
-
-                // If the block already exists in the block tree, skip it.
-                let new_tip_received =
-                    if c.get_block_index_for_persisted_block(&block.get_id())?.is_some() {
-                        log::debug!("The peer sent a block that already exists ({block_id})");
-                        false
-                    } else {
-                        let block_index = c.process_block(block, BlockSource::Peer)?;
-                        block_index.is_some()
-                    };
+                let new_tip_received = match block {
+                    Some(block) => {
+                        let block = c.preliminary_block_check(block)?;
+
+                        // If the block already exists in the block tree, skip it.
+                        if c.get_block_index_for_persisted_block(&block.get_id())?.is_some() {
+                            log::debug!("The peer sent a block that already exists ({block_id})");
+                            false
+                        } else {
+                            let block_index = c.process_block(block, BlockSource::Peer(peer_id))?;
+                            block_index.is_some()
+                        }
+                    }
+                    None => false,
+                };
 
                 let best_block = choose_peers_best_block(
                     c,
@@ -742,6 +894,7 @@ where
                 Ok((best_block, new_tip_received))
             })
             .await?;
+        drop(in_flight_guard);
         self.incoming.peers_best_block_that_we_have = best_block;
 
         if new_tip_received {
@@ -778,6 +931,293 @@ where
         Ok(())
     }
 
+    fn check_block_chunks_supported(&self, message_desc: &str) -> Result<()> {
+        utils::ensure!(
+            self.protocol_version >= SupportedProtocolVersion::V4,
+            P2pError::ProtocolError(ProtocolError::UnexpectedMessage(format!(
+                "{message_desc} is received, but the peer's protocol version doesn't support it"
+            )))
+        );
+        Ok(())
+    }
+
+    async fn handle_block_chunk_manifest(&mut self, manifest: BlockChunkManifest) -> Result<()> {
+        let block_id = *manifest.block_id();
+        log::debug!(
+            "Handling block chunk manifest, block id = {block_id}, chunk count = {}",
+            manifest.chunk_hashes().len()
+        );
+
+        self.check_block_chunks_supported("A block chunk manifest")?;
+
+        if self.incoming.chunked_block.is_some() {
+            return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
+                "A block chunk manifest is received during another chunked block transfer"
+                    .to_owned(),
+            )));
+        }
+
+        self.check_next_requested_block(&block_id)?;
+
+        utils::ensure!(
+            block_chunks::is_manifest_valid(&manifest, &self.p2p_config.protocol_config),
+            P2pError::ProtocolError(ProtocolError::InvalidBlockChunkManifest(block_id))
+        );
+
+        self.incoming.chunked_block = Some(IncomingChunkedBlock {
+            manifest,
+            requested_chunks: BTreeSet::new(),
+        });
+        self.peer_activity.set_expecting_blocks_since(Some(self.time_getter.get_time()));
+
+        self.request_block_chunks().await
+    }
+
+    /// Request the next portion of chunks of the block that is being transferred in chunks.
+    async fn request_block_chunks(&mut self) -> Result<()> {
+        let block_id = *self.incoming_chunked_block().manifest.block_id();
+
+        let block_exists = self
+            .chainstate_handle
+            .call(move |c| Ok(c.get_block_index_for_persisted_block(&block_id)?.is_some()))
+            .await?;
+        if block_exists {
+            log::debug!(
+                "Block {block_id} has already been obtained, finishing its chunked transfer"
+            );
+            return self.finish_chunked_block(None).await;
+        }
+
+        let peer_id = self.id();
+        let max_chunk_count = *self.p2p_config.protocol_config.max_block_chunks_per_request;
+        let chunked_block =
+            self.incoming.chunked_block.as_mut().expect("The chunked block must be present");
+        let chunk_indices = self.block_chunk_downloads.assign_chunks(
+            &chunked_block.manifest,
+            peer_id,
+            max_chunk_count,
+        );
+
+        if chunk_indices.is_empty() {
+            // Note: this shouldn't happen, because finished downloads are removed and missing ones
+            // are re-created.
+            log::warn!("No chunks of block {block_id} to request from the peer");
+            return Ok(());
+        }
+
+        log::debug!("Requesting chunks {chunk_indices:?} of block {block_id} from the peer");
+        chunked_block.requested_chunks.extend(chunk_indices.iter().copied());
+
+        self.send_message(BlockSyncMessage::BlockChunkRequest(BlockChunkRequest::new(
+            block_id,
+            chunk_indices,
+        )))
+    }
+
+    async fn handle_block_chunk(&mut self, chunk: BlockChunk) -> Result<()> {
+        let block_id = *chunk.block_id();
+        let chunk_index = chunk.chunk_index();
+        log::trace!("Handling chunk {chunk_index} of block {block_id}");
+
+        self.check_block_chunks_supported("A block chunk")?;
+
+        if self.incoming.finished_chunked_block == Some(block_id) {
+            log::debug!("Ignoring chunk {chunk_index} of the already obtained block {block_id}");
+            return Ok(());
+        }
+
+        let is_requested = self.incoming.chunked_block.as_mut().is_some_and(|chunked_block| {
+            *chunked_block.manifest.block_id() == block_id
+                && chunked_block.requested_chunks.remove(&chunk_index)
+        });
+        utils::ensure!(
+            is_requested,
+            P2pError::ProtocolError(ProtocolError::UnsolicitedBlockChunkReceived(
+                block_id,
+                chunk_index
+            ))
+        );
+
+        let chunked_block = self.incoming_chunked_block();
+        utils::ensure!(
+            block_chunks::is_chunk_valid(&chunked_block.manifest, chunk_index, chunk.data()),
+            P2pError::ProtocolError(ProtocolError::InvalidBlockChunk(block_id, chunk_index))
+        );
+
+        let add_chunk_result = self.block_chunk_downloads.add_chunk(
+            &chunked_block.manifest,
+            chunk_index,
+            chunk.into_data(),
+        );
+        let all_requested_chunks_received = chunked_block.requested_chunks.is_empty();
+
+        self.peer_activity.set_expecting_blocks_since(Some(self.time_getter.get_time()));
+
+        match add_chunk_result {
+            AddChunkResult::Added => {
+                if all_requested_chunks_received {
+                    self.request_block_chunks().await?;
+                }
+                Ok(())
+            }
+            AddChunkResult::Completed(encoded_block) => {
+                // All chunks match the manifest, so if the block is malformed, the manifest
+                // is to blame.
+                let block = Block::decode_all(&mut encoded_block.as_slice())
+                    .ok()
+                    .filter(|block| block.get_id() == block_id)
+                    .ok_or(P2pError::ProtocolError(
+                        ProtocolError::InvalidBlockChunkManifest(block_id),
+                    ))?;
+                self.finish_chunked_block(Some(block)).await
+            }
+        }
+    }
+
+    fn incoming_chunked_block(&self) -> &IncomingChunkedBlock {
+        self.incoming.chunked_block.as_ref().expect("The chunked block must be present")
+    }
+
+    /// Finish the chunked transfer of the first requested block and process the block.
+    async fn finish_chunked_block(&mut self, block: Option<Block>) -> Result<()> {
+        let chunked_block =
+            self.incoming.chunked_block.take().expect("The chunked block must be present");
+        let block_id = *chunked_block.manifest.block_id();
+
+        self.block_chunk_downloads.remove(&chunked_block.manifest);
+        self.incoming.finished_chunked_block = Some(block_id);
+
+        // Let the peer know that it can continue sending the remaining requested blocks.
+        self.send_message(BlockSyncMessage::BlockChunksReceived(block_id))?;
+
+        self.process_requested_block(block_id, block).await
+    }
+
+    fn handle_block_chunk_request(&mut self, request: BlockChunkRequest) -> Result<()> {
+        let block_id = *request.block_id();
+        log::debug!(
+            "Handling block chunk request, block id = {block_id}, chunks = {:?}",
+            request.chunk_indices()
+        );
+
+        self.check_block_chunks_supported("A block chunk request")?;
+
+        let max_chunk_count = *self.p2p_config.protocol_config.max_block_chunks_per_request;
+        utils::ensure!(
+            request.chunk_indices().len() <= max_chunk_count,
+            P2pError::ProtocolError(ProtocolError::BlockChunksRequestLimitExceeded(
+                request.chunk_indices().len(),
+                max_chunk_count,
+            ))
+        );
+
+        let chunked_block = self
+            .outgoing
+            .chunked_block
+            .as_ref()
+            .filter(|chunked_block| chunked_block.block_id == block_id)
+            .ok_or(P2pError::ProtocolError(
+                ProtocolError::InvalidBlockChunkRequest(block_id),
+            ))?;
+
+        let chunks = request
+            .chunk_indices()
+            .iter()
+            .map(|chunk_index| {
+                block_chunks::get_chunk(
+                    &chunked_block.encoded_block,
+                    chunked_block.chunk_size,
+                    *chunk_index,
+                )
+                .map(|data| BlockChunk::new(block_id, *chunk_index, data.to_vec()))
+                .ok_or(P2pError::ProtocolError(
+                    ProtocolError::InvalidBlockChunkRequest(block_id),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        utils::ensure!(
+            !chunks.is_empty(),
+            P2pError::ProtocolError(ProtocolError::InvalidBlockChunkRequest(block_id))
+        );
+
+        for chunk in chunks {
+            self.send_message(BlockSyncMessage::BlockChunk(chunk))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_block_filters_supported(&self, message_desc: &str) -> Result<()> {
+        utils::ensure!(
+            self.protocol_version >= SupportedProtocolVersion::V6,
+            P2pError::ProtocolError(ProtocolError::UnexpectedMessage(format!(
+                "{message_desc} is received, but the peer's protocol version doesn't support it"
+            )))
+        );
+        Ok(())
+    }
+
+    async fn handle_block_filter_headers_request(
+        &mut self,
+        request: BlockFilterRequest,
+    ) -> Result<()> {
+        log::debug!(
+            "Handling block filter headers request, start height = {}, stop block id = {}",
+            request.start_height(),
+            request.stop_block_id()
+        );
+
+        self.check_block_filters_supported("A block filter headers request")?;
+
+        let max_block_count = *self.p2p_config.protocol_config.max_block_filter_headers_per_request;
+        let headers = self
+            .chainstate_handle
+            .call(move |c| block_filters::make_block_filter_headers(c, &request, max_block_count))
+            .await?;
+
+        self.send_message(BlockSyncMessage::BlockFilterHeaders(headers))
+    }
+
+    async fn handle_block_filters_request(&mut self, request: BlockFilterRequest) -> Result<()> {
+        log::debug!(
+            "Handling block filters request, start height = {}, stop block id = {}",
+            request.start_height(),
+            request.stop_block_id()
+        );
+
+        self.check_block_filters_supported("A block filters request")?;
+
+        let max_block_count = *self.p2p_config.protocol_config.max_block_filters_per_request;
+        let filters = self
+            .chainstate_handle
+            .call(move |c| block_filters::get_block_filters(c, &request, max_block_count))
+            .await?;
+
+        for filter in filters {
+            self.send_message(BlockSyncMessage::BlockFilter(filter))?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_block_chunks_received(&mut self, block_id: Id<Block>) -> Result<()> {
+        log::debug!("The peer has obtained the chunked block {block_id}");
+
+        self.check_block_chunks_supported("A block chunks received notification")?;
+
+        if self.outgoing.chunked_block.as_ref().map(|chunked_block| chunked_block.block_id)
+            != Some(block_id)
+        {
+            return Err(P2pError::ProtocolError(ProtocolError::UnexpectedMessage(format!(
+                "A block chunks received notification for block {block_id}, which is not being transferred"
+            ))));
+        }
+
+        self.outgoing.chunked_block = None;
+        Ok(())
+    }
+
     /// Sends a block list request.
     ///
     /// The number of blocks requested equals `ProtocolConfig::max_request_blocks_count`,
@@ -812,38 +1252,84 @@ where
         Ok(())
     }
 
-    async fn send_block(&mut self, id: Id<Block>) -> Result<()> {
-        let (block, block_index) = self
+    /// Send the blocks from the queue, fetching as many of them as fit into
+    /// `SEND_BLOCKS_BATCH_SIZE_LIMIT` from the chainstate at once.
+    async fn send_blocks(&mut self) -> Result<()> {
+        let ids = self.outgoing.blocks_queue.iter().copied().collect::<Vec<_>>();
+        let blocks = self
             .chainstate_handle
             .call(move |c| {
-                let index = c.get_block_index_for_persisted_block(&id);
-                let block = c.get_block(id);
-                Ok((block, index))
+                let blocks = c.get_blocks(ids.clone(), SEND_BLOCKS_BATCH_SIZE_LIMIT)?;
+                // Note: all requested blocks have already been checked for existence in handle_block_request.
+                // But in the future, when/if we implement block purging, it will still be possible for a
+                // block to become missing by this point. This should be a rare and low impact situation,
+                // but at least we should fail gracefully here and not panic.
+                // Also see a similar note in handle_block_request.
+                blocks
+                    .into_iter()
+                    .zip(ids)
+                    .map(|(block, id)| {
+                        let block = block.ok_or(SyncError::BlockDataMissingInSendBlock(id))?;
+                        let block_index = c
+                            .get_block_index_for_persisted_block(&id)?
+                            .ok_or(SyncError::BlockIndexMissingInSendBlock(id))?;
+                        Ok((block, block_index))
+                    })
+                    .collect::<Result<Vec<_>>>()
             })
             .await?;
-        // Note: all requested blocks have already been checked for existence in handle_block_request.
-        // But in the future, when/if we implement block purging, it will still be possible for a
-        // block to become missing by this point. This should be a rare and low impact situation,
-        // but at least we should fail gracefully here and not panic.
-        // Also see a similar note in handle_block_request.
-        let block = block?.ok_or(SyncError::BlockDataMissingInSendBlock(id))?;
-        let block_index = block_index?.ok_or(SyncError::BlockIndexMissingInSendBlock(id))?;
-
-        let old_best_sent_block_id = self.outgoing.best_sent_block.as_ref().map(|idx| {
-            let id: Id<GenBlock> = (*idx.block_id()).into();
-            id
-        });
-        let new_best_sent_block_id = self
-            .chainstate_handle
-            .call(move |c| choose_peers_best_block(c, old_best_sent_block_id, Some(id.into())))
-            .await?;
 
-        if new_best_sent_block_id == Some(id.into()) {
-            self.outgoing.best_sent_block = Some(block_index);
+        for (block, block_index) in blocks {
+            let id = self.outgoing.blocks_queue.pop_front().expect("The block queue is empty");
+            debug_assert_eq!(id, block.get_id());
+
+            let is_better = self
+                .outgoing
+                .best_sent_block
+                .as_ref()
+                .is_none_or(|best| block_index.chain_trust() >= best.chain_trust());
+            if is_better {
+                self.outgoing.best_sent_block = Some(block_index);
+            }
+
+            // The rest of the queue has to wait until the peer has received all the chunks.
+            if self.send_block(block)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the block to the peer, either as a whole or in chunks; return true if the block
+    /// is sent in chunks.
+    fn send_block(&mut self, block: Block) -> Result<bool> {
+        let id = block.get_id();
+        let chunking_threshold = *self.p2p_config.protocol_config.block_chunking_threshold;
+        if self.protocol_version >= SupportedProtocolVersion::V4
+            && block.encoded_size() > chunking_threshold
+        {
+            let encoded_block = block.encode();
+            let chunk_size = *self.p2p_config.protocol_config.block_chunk_size;
+            let manifest = block_chunks::make_manifest(id, &encoded_block, chunk_size);
+
+            log::debug!(
+                "Sending chunk manifest of block with id = {} to the peer, chunk count = {}",
+                id,
+                manifest.chunk_hashes().len()
+            );
+            self.outgoing.chunked_block = Some(OutgoingChunkedBlock {
+                block_id: id,
+                encoded_block,
+                chunk_size,
+            });
+            self.send_message(BlockSyncMessage::BlockChunkManifest(manifest))?;
+            return Ok(true);
         }
 
-        log::debug!("Sending block with id = {} to the peer", block.get_id());
-        self.send_message(BlockSyncMessage::BlockResponse(BlockResponse::new(block)))
+        log::debug!("Sending block with id = {} to the peer", id);
+        self.send_message(BlockSyncMessage::BlockResponse(BlockResponse::new(block)))?;
+        Ok(false)
     }
 
     async fn disconnect_if_stalling(&mut self) -> Result<()> {
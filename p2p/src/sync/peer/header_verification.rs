@@ -0,0 +1,335 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Context-free verification of header lists received during block sync.
+//!
+//! Checking a header against the chainstate is inherently sequential, but some of the checks
+//! only depend on the header itself and on its immediate predecessor. Those are done here, before
+//! the headers are passed to the chainstate: the list is split into segments that are verified
+//! on separate threads.
+//!
+//! These checks are only a cheap filter for obviously invalid headers. In particular, a PoW
+//! header is only checked against the target it specifies itself, which may be anywhere below
+//! the chain's PoW limit; whether that target is the expected difficulty is checked by
+//! the chainstate.
+//!
+//! Headers up to the last checkpoint whose id matches the corresponding header are not checked at
+//! all, because the checkpoint id commits to all of its ancestors. Whether such headers are
+//! actually valid is then decided by the checkpoint enforcement in the chainstate.
+
+use std::{num::NonZeroUsize, ops::Range};
+
+use thiserror::Error;
+
+use common::{
+    chain::{
+        block::{consensus_data::ConsensusData, signed_block_header::SignedBlockHeader},
+        Block, ChainConfig, GenBlock,
+    },
+    primitives::{BlockDistance, BlockHeight, Compact, Id, Idable},
+    Uint256,
+};
+use consensus::{check_proof_of_work, ConsensusPoWError};
+use utils::const_nz_usize;
+
+/// Segments shorter than this are not worth a separate thread.
+pub const MIN_SEGMENT_LEN: usize = 64;
+
+/// The maximum number of threads used to verify a single header list.
+pub const MAX_VERIFICATION_THREADS: NonZeroUsize = const_nz_usize!(4);
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HeaderCheckError {
+    #[error("PoW check failed: {0}")]
+    PoW(#[from] ConsensusPoWError),
+    #[error("Block hash doesn't satisfy the target specified in the header")]
+    InsufficientWork,
+    #[error("PoW target {0:?} is above the chain's PoW limit")]
+    TargetAboveLimit(Compact),
+    #[error("PoS block timestamp is not greater than the previous block timestamp")]
+    PoSTimestampNotIncreasing,
+}
+
+/// A contiguous part of a header list, expressed as a range of indices in that list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderSegment {
+    pub range: Range<usize>,
+    /// Whether the segment ends with a header that matches a checkpoint, in which case
+    /// it doesn't need to be verified.
+    pub anchored: bool,
+}
+
+/// Split `headers`, the first of which is at `first_height`, into segments for verification.
+///
+/// The prefix that ends with the last matching checkpoint becomes a single anchored segment;
+/// the rest is split into roughly `parallelism` segments of at least [MIN_SEGMENT_LEN] headers.
+pub fn plan_segments(
+    chain_config: &ChainConfig,
+    first_height: BlockHeight,
+    headers: &[SignedBlockHeader],
+    parallelism: NonZeroUsize,
+) -> Vec<HeaderSegment> {
+    let last_height = (first_height + BlockDistance::new(headers.len() as i64))
+        .expect("BlockHeight limit reached");
+
+    let anchored_len = chain_config
+        .height_checkpoints()
+        .checkpoints_map()
+        .range(first_height..last_height)
+        .rev()
+        .find_map(|(height, checkpoint_id)| {
+            let idx = (*height - first_height)
+                .and_then(|distance| usize::try_from(distance.to_int()).ok())
+                .expect("checkpoint height is within the range");
+            let header_id: Id<GenBlock> = headers[idx].get_id().into();
+            (header_id == *checkpoint_id).then_some(idx + 1)
+        })
+        .unwrap_or(0);
+
+    let mut segments = Vec::new();
+    if anchored_len > 0 {
+        segments.push(HeaderSegment {
+            range: 0..anchored_len,
+            anchored: true,
+        });
+    }
+
+    let remaining = headers.len() - anchored_len;
+    let segment_len = remaining.div_ceil(parallelism.get()).max(MIN_SEGMENT_LEN);
+    let mut start = anchored_len;
+    while start < headers.len() {
+        let end = std::cmp::min(start + segment_len, headers.len());
+        segments.push(HeaderSegment {
+            range: start..end,
+            anchored: false,
+        });
+        start = end;
+    }
+
+    segments
+}
+
+/// Verify a single header given its predecessor, if it's known.
+fn check_header(
+    chain_config: &ChainConfig,
+    header: &SignedBlockHeader,
+    prev_header: Option<&SignedBlockHeader>,
+) -> Result<(), HeaderCheckError> {
+    match header.consensus_data() {
+        ConsensusData::None => {}
+        ConsensusData::PoW(pow_data) => {
+            let target = Uint256::try_from(pow_data.bits())
+                .map_err(|_| ConsensusPoWError::DecodingBitsFailed(pow_data.bits()))?;
+            utils::ensure!(
+                target <= chain_config.get_proof_of_work_config().limit(),
+                HeaderCheckError::TargetAboveLimit(pow_data.bits())
+            );
+            utils::ensure!(
+                check_proof_of_work(header.get_id().to_hash(), pow_data.bits())?,
+                HeaderCheckError::InsufficientWork
+            );
+        }
+        ConsensusData::PoS(_) => {
+            // The timestamp of the first header in the list is checked against the chainstate.
+            if let Some(prev_header) = prev_header {
+                utils::ensure!(
+                    header.timestamp() > prev_header.timestamp(),
+                    HeaderCheckError::PoSTimestampNotIncreasing
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify the headers of a single segment.
+pub fn verify_segment(
+    chain_config: &ChainConfig,
+    headers: &[SignedBlockHeader],
+    segment: &HeaderSegment,
+) -> Result<(), (Id<Block>, HeaderCheckError)> {
+    if segment.anchored {
+        return Ok(());
+    }
+
+    let prev_header = segment.range.start.checked_sub(1).map(|idx| &headers[idx]);
+    std::iter::once(prev_header)
+        .chain(headers[segment.range.clone()].iter().map(Some))
+        .zip(headers[segment.range.clone()].iter())
+        .try_for_each(|(prev_header, header)| {
+            check_header(chain_config, header, prev_header).map_err(|err| (header.get_id(), err))
+        })
+}
+
+/// Verify all segments, each on its own thread.
+///
+/// If several segments are invalid, the error for the earliest one is returned.
+pub fn verify_segments_in_parallel(
+    chain_config: &ChainConfig,
+    headers: &[SignedBlockHeader],
+    segments: &[HeaderSegment],
+) -> Result<(), (Id<Block>, HeaderCheckError)> {
+    std::thread::scope(|scope| {
+        let handles = segments
+            .iter()
+            .filter(|segment| !segment.anchored)
+            .map(|segment| scope.spawn(move || verify_segment(chain_config, headers, segment)))
+            .collect::<Vec<_>>();
+
+        handles.into_iter().try_for_each(|handle| {
+            handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use chainstate_test_framework::TestFramework;
+    use common::chain::{
+        block::consensus_data::PoWData,
+        config::{create_unit_test_config, create_unit_test_config_builder},
+    };
+    use p2p_test_utils::create_n_blocks;
+    use randomness::Rng;
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Seed};
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn segments_cover_all_headers(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+        let mut tf =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+        let count = rng.gen_range(1..500);
+        let headers = create_n_blocks(&mut rng, &mut tf, count)
+            .into_iter()
+            .map(|block| block.header().clone())
+            .collect::<Vec<_>>();
+        let parallelism = NonZeroUsize::new(rng.gen_range(1..16)).unwrap();
+
+        let segments = plan_segments(&chain_config, BlockHeight::new(1), &headers, parallelism);
+
+        let mut next = 0;
+        for segment in &segments {
+            assert_eq!(segment.range.start, next);
+            assert!(!segment.range.is_empty());
+            assert!(!segment.anchored);
+            next = segment.range.end;
+        }
+        assert_eq!(next, headers.len());
+        assert!(segments.len() <= parallelism.get());
+        assert!(segments.len() == 1 || segments.iter().all(|s| s.range.len() >= MIN_SEGMENT_LEN));
+
+        assert_eq!(
+            verify_segments_in_parallel(&chain_config, &headers, &segments),
+            Ok(())
+        );
+    }
+
+    // A header whose hash can't satisfy its own target.
+    fn make_invalid_pow_header(header: &SignedBlockHeader) -> SignedBlockHeader {
+        let mut header = header.header().clone();
+        header.update_consensus_data(ConsensusData::PoW(PoWData::new(Compact(0x03000001), 0)));
+        header.with_no_signature()
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn invalid_pow_header(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let chain_config = create_unit_test_config();
+        let mut tf =
+            TestFramework::builder(&mut rng).with_chain_config(chain_config.clone()).build();
+        let mut headers = create_n_blocks(&mut rng, &mut tf, 200)
+            .into_iter()
+            .map(|block| block.header().clone())
+            .collect::<Vec<_>>();
+        let invalid_idx = rng.gen_range(0..headers.len());
+        headers[invalid_idx] = make_invalid_pow_header(&headers[invalid_idx]);
+        let parallelism = NonZeroUsize::new(rng.gen_range(1..16)).unwrap();
+
+        let segments = plan_segments(&chain_config, BlockHeight::new(1), &headers, parallelism);
+
+        assert_eq!(
+            verify_segments_in_parallel(&chain_config, &headers, &segments),
+            Err((
+                headers[invalid_idx].get_id(),
+                HeaderCheckError::InsufficientWork
+            ))
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn anchored_at_checkpoint(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(create_unit_test_config())
+            .build();
+        let count = rng.gen_range(3..500);
+        let mut headers = create_n_blocks(&mut rng, &mut tf, count)
+            .into_iter()
+            .map(|block| block.header().clone())
+            .collect::<Vec<_>>();
+
+        // The checkpoint is at the height of headers[anchored_len - 1]
+        let anchored_len = rng.gen_range(2..count);
+        let checkpoints = BTreeMap::from([(
+            BlockHeight::new(anchored_len as u64),
+            headers[anchored_len - 1].get_id().into(),
+        )]);
+        let chain_config = create_unit_test_config_builder().checkpoints(checkpoints).build();
+        let parallelism = NonZeroUsize::new(rng.gen_range(1..16)).unwrap();
+
+        // Headers below the checkpoint are not checked
+        let invalid_idx = rng.gen_range(0..anchored_len - 1);
+        headers[invalid_idx] = make_invalid_pow_header(&headers[invalid_idx]);
+
+        let segments = plan_segments(&chain_config, BlockHeight::new(1), &headers, parallelism);
+        assert_eq!(
+            segments[0],
+            HeaderSegment {
+                range: 0..anchored_len,
+                anchored: true,
+            }
+        );
+        assert!(segments[1..].iter().all(|segment| !segment.anchored));
+        assert_eq!(
+            verify_segments_in_parallel(&chain_config, &headers, &segments),
+            Ok(())
+        );
+
+        // Headers above the checkpoint still are
+        let invalid_idx = rng.gen_range(anchored_len..count);
+        headers[invalid_idx] = make_invalid_pow_header(&headers[invalid_idx]);
+        let segments = plan_segments(&chain_config, BlockHeight::new(1), &headers, parallelism);
+        assert_eq!(
+            verify_segments_in_parallel(&chain_config, &headers, &segments),
+            Err((
+                headers[invalid_idx].get_id(),
+                HeaderCheckError::InsufficientWork
+            ))
+        );
+    }
+}
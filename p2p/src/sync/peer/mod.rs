@@ -14,6 +14,7 @@
 // limitations under the License.
 
 pub mod block_manager;
+pub mod header_verification;
 pub mod requested_transactions;
 pub mod transaction_manager;
 
@@ -13,39 +13,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cmp::Reverse, collections::BinaryHeap};
+use std::time::Duration;
+
 use tokio::time::Instant;
 
-use common::{chain::Transaction, primitives::Id};
+use randomness::{Rng, SliceRandom};
+
+use crate::message::TransactionAnnouncement;
 
+/// Transactions that are waiting to be announced to a peer.
+///
+/// Transactions aren't announced one by one; instead, they are accumulated and announced
+/// in batches at random moments ("trickles"), where the intervals between the trickles are
+/// exponentially distributed. This way, all transactions that were received during one interval
+/// are announced at the same time, in random order, so the announcement timing and ordering
+/// don't reveal which of them was received first. Since each peer has its own independent
+/// trickle times and ordering, it also becomes harder to find the origin of a transaction
+/// by comparing announcements from different peers.
 pub struct PendingTransactions {
-    txs: BinaryHeap<Reverse<(Instant, Id<Transaction>)>>,
+    txs: Vec<TransactionAnnouncement>,
+    trickle_interval: Duration,
+    next_trickle_time: Option<Instant>,
 }
 
 impl PendingTransactions {
-    pub fn new() -> Self {
+    pub fn new(trickle_interval: Duration) -> Self {
         Self {
-            txs: Default::default(),
+            txs: Vec::new(),
+            trickle_interval,
+            next_trickle_time: None,
         }
     }
 
-    pub fn push(&mut self, tx: Id<Transaction>, due_time: Instant) {
-        self.txs.push(Reverse((due_time, tx)));
+    /// Add a transaction to the current batch, scheduling the next trickle if there is none.
+    pub fn push(&mut self, tx: TransactionAnnouncement, now: Instant, rng: &mut impl Rng) {
+        self.txs.push(tx);
+
+        if self.next_trickle_time.is_none() {
+            let delay = self.trickle_interval.mul_f64(utils::exp_rand::exponential_rand(rng));
+            self.next_trickle_time = Some(now + delay);
+        }
     }
 
-    pub fn pop(&mut self) -> Option<Id<Transaction>> {
-        self.txs.pop().map(|item| {
-            let (_, tx) = item.0;
-            tx
-        })
+    /// Take all the pending transactions, in random order.
+    pub fn take_batch(&mut self, rng: &mut impl Rng) -> Vec<TransactionAnnouncement> {
+        self.next_trickle_time = None;
+        let mut txs = std::mem::take(&mut self.txs);
+        txs.shuffle(rng);
+        txs
     }
 
+    /// Wait until the next trickle time; never returns if there are no pending transactions.
     pub async fn due(&self) {
-        match self.txs.peek() {
-            Some(item) => {
-                let (due, _) = item.0;
-                tokio::time::sleep_until(due).await;
-            }
+        match self.next_trickle_time {
+            Some(due) => tokio::time::sleep_until(due).await,
             None => std::future::pending().await,
         }
     }
@@ -53,81 +74,95 @@ impl PendingTransactions {
 
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
+    use std::collections::BTreeSet;
 
     use super::*;
 
-    use common::primitives::H256;
+    use common::primitives::{Id, H256};
     use rstest::rstest;
     use test_utils::random::{make_seedable_rng, Rng, Seed};
 
+    fn random_announcement(rng: &mut impl Rng) -> TransactionAnnouncement {
+        TransactionAnnouncement::new(
+            Id::new(H256::random_using(rng)),
+            Id::new(H256::random_using(rng)),
+        )
+    }
+
     #[rstest]
     #[trace]
     #[case(Seed::from_entropy())]
-    fn modification_test(#[case] seed: Seed) {
+    fn batch_test(#[case] seed: Seed) {
         let mut rng = make_seedable_rng(seed);
 
-        let tx1 = Id::<Transaction>::new(H256::random_using(&mut rng));
-        let tx2 = Id::<Transaction>::new(H256::random_using(&mut rng));
-        let tx3 = Id::<Transaction>::new(H256::random_using(&mut rng));
-
-        let instant1 = Instant::now() + Duration::from_secs(1);
-        let instant2 = Instant::now() + Duration::from_secs(2);
-        let instant3 = Instant::now() + Duration::from_secs(3);
+        let mut txs = PendingTransactions::new(Duration::from_secs(5));
+        assert!(txs.take_batch(&mut rng).is_empty());
 
-        let mut txs = PendingTransactions::new();
-        assert_eq!(None, txs.pop());
-
-        txs.push(tx3, instant3);
-        txs.push(tx1, instant1);
-        txs.push(tx2, instant2);
+        let tx_ids = (0..rng.gen_range(1..100))
+            .map(|_| random_announcement(&mut rng))
+            .collect::<Vec<_>>();
+        for tx_id in &tx_ids {
+            txs.push(*tx_id, Instant::now(), &mut rng);
+        }
+        assert_eq!(txs.txs.len(), tx_ids.len());
 
-        assert_eq!(Some(tx1), txs.pop());
-        assert_eq!(Some(tx2), txs.pop());
-        assert_eq!(Some(tx3), txs.pop());
-        assert_eq!(None, txs.pop());
+        // All transactions are announced together
+        let batch = txs.take_batch(&mut rng);
+        assert_eq!(
+            batch.iter().collect::<BTreeSet<_>>(),
+            tx_ids.iter().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(batch.len(), tx_ids.len());
+        assert!(txs.txs.is_empty());
+        assert!(txs.take_batch(&mut rng).is_empty());
     }
 
-    #[tokio::test]
-    async fn due_test() {
-        let before = Instant::now();
-
-        let tx = Id::<Transaction>::new(H256::zero());
-        let due_instant = Instant::now() + Duration::from_secs(1);
+    // The order of announcements must not depend on the order in which transactions were received.
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn batch_order_is_random(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
 
-        let mut txs = PendingTransactions::new();
-        txs.push(tx, due_instant);
+        let tx_ids = (0..20).map(|_| random_announcement(&mut rng)).collect::<Vec<_>>();
 
-        tokio::time::pause();
-        tokio::spawn(async {
-            tokio::time::advance(Duration::from_secs(1)).await;
-        });
-        txs.due().await;
+        let mut orders = BTreeSet::new();
+        for _ in 0..10 {
+            let mut txs = PendingTransactions::new(Duration::from_secs(5));
+            for tx_id in &tx_ids {
+                txs.push(*tx_id, Instant::now(), &mut rng);
+            }
+            orders.insert(txs.take_batch(&mut rng));
+        }
 
-        let after = Instant::now();
-        assert!(after.duration_since(before) >= Duration::from_secs(1));
+        // The probability of getting the same order of 20 items twice is negligible.
+        assert_eq!(orders.len(), 10);
     }
 
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
     #[tokio::test]
-    async fn due_from_the_past_test() {
-        let before = Instant::now();
+    async fn due_test(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
 
-        let tx = Id::<Transaction>::new(H256::zero());
-        let due_instant = Instant::now();
+        tokio::time::pause();
 
-        let mut txs = PendingTransactions::new();
-        txs.push(tx, due_instant);
+        let mut txs = PendingTransactions::new(Duration::from_secs(5));
 
-        tokio::time::pause();
-        tokio::time::advance(Duration::from_secs(1)).await;
+        // Transactions pushed before the trickle time don't postpone it.
+        let first_push = Instant::now();
+        txs.push(random_announcement(&mut rng), first_push, &mut rng);
+        let due_time = txs.next_trickle_time.unwrap();
 
-        // due is in the past now
-        assert!(due_instant < Instant::now());
+        tokio::time::advance(Duration::from_millis(rng.gen_range(0..100))).await;
+        txs.push(random_announcement(&mut rng), Instant::now(), &mut rng);
+        assert_eq!(txs.next_trickle_time, Some(due_time));
 
         txs.due().await;
-
-        let after = Instant::now();
-        assert!(after.duration_since(before) >= Duration::from_secs(1));
+        assert!(Instant::now() >= due_time);
+        assert_eq!(txs.take_batch(&mut rng).len(), 2);
+        assert_eq!(txs.next_trickle_time, None);
     }
 
     #[rstest]
@@ -142,7 +177,7 @@ mod tests {
         // Spawn a task with a timeout
         let timeout_duration = Duration::from_secs(rng.gen_range(1..120));
         let test_task = tokio::spawn(async move {
-            let txs = PendingTransactions::new();
+            let txs = PendingTransactions::new(Duration::from_secs(5));
             tokio::time::timeout(timeout_duration, txs.due()).await
         });
 
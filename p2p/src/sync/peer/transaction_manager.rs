@@ -22,23 +22,24 @@ use tokio::{
 };
 
 use common::{
-    chain::Transaction,
+    chain::{SignedTransaction, Transaction},
     primitives::{Id, Idable},
     time_getter::TimeGetter,
 };
 use logging::log;
-use mempool::{MempoolHandle, TxOptions};
+use mempool::{MempoolHandle, SeenTransactions, TxOptions};
 use utils::const_value::ConstValue;
 use utils::sync::Arc;
 
 use crate::{
     config::P2pConfig,
     error::{P2pError, ProtocolError},
-    message::{TransactionResponse, TransactionSyncMessage},
+    message::{TransactionAnnouncement, TransactionResponse, TransactionSyncMessage},
     net::{
         types::services::{Service, Services},
         NetworkingService,
     },
+    protocol::SupportedProtocolVersion,
     sync::{
         chainstate_handle::ChainstateHandle,
         peer_common::{handle_message_processing_result, KnownTransactions},
@@ -52,9 +53,6 @@ use super::{
     pending_transactions::PendingTransactions, requested_transactions::RequestedTransactions,
 };
 
-// TODO: add smaller interval for outbound connections
-pub const TX_RELAY_DELAY_INTERVAL: Duration = Duration::from_secs(5);
-
 // TODO: Take into account the chain work when syncing.
 /// Transaction sync manager.
 ///
@@ -63,20 +61,27 @@ pub struct PeerTransactionSyncManager<T: NetworkingService> {
     id: ConstValue<PeerId>,
     p2p_config: Arc<P2pConfig>,
     common_services: Services,
+    protocol_version: SupportedProtocolVersion,
     chainstate_handle: ChainstateHandle,
     mempool_handle: MempoolHandle,
+    /// The mempool's filter of recently processed transactions, which allows to ignore
+    /// announcements of such transactions without calling the mempool.
+    seen_transactions: SeenTransactions,
     peer_mgr_event_sender: UnboundedSender<PeerManagerEvent>,
     messaging_handle: T::MessagingHandle,
     sync_msg_receiver: Receiver<TransactionSyncMessage>,
     local_event_receiver: UnboundedReceiver<LocalEvent>,
-    /// A rolling filter of all known transactions (sent to us or sent by us)
+    /// A rolling filter of all known transactions (sent to us or sent by us); if the peer
+    /// supports witness ids, the transactions are identified by them.
     known_transactions: KnownTransactions,
     /// This tracks transactions that we've requested from this peer but for which we haven't
     /// received a response yet.
     requested_transactions: RequestedTransactions,
     /// Txs aren't relayed immediately but rather put into a collection to be propagated later
-    /// with random delay to make tracing transactions' origin harder
+    /// in a batch with random delay to make tracing transactions' origin harder
     pending_transactions: PendingTransactions,
+    /// If true, txs are announced to the peer immediately, without batching.
+    relay_txs_immediately: bool,
     /// SyncManager's observer for use by tests.
     observer: Option<BoxedObserver>,
 }
@@ -90,9 +95,12 @@ where
     pub fn new(
         id: PeerId,
         common_services: Services,
+        protocol_version: SupportedProtocolVersion,
+        is_whitelisted: bool,
         p2p_config: Arc<P2pConfig>,
         chainstate_handle: ChainstateHandle,
         mempool_handle: MempoolHandle,
+        seen_transactions: SeenTransactions,
         peer_mgr_event_sender: UnboundedSender<PeerManagerEvent>,
         sync_msg_receiver: Receiver<TransactionSyncMessage>,
         messaging_handle: T::MessagingHandle,
@@ -101,20 +109,27 @@ where
         observer: Option<BoxedObserver>,
     ) -> Self {
         let known_transactions = KnownTransactions::new();
+        // TODO: add smaller interval for outbound connections
+        let pending_transactions = PendingTransactions::new(*p2p_config.tx_relay_trickle_interval);
+        let relay_txs_immediately =
+            is_whitelisted && *p2p_config.disable_tx_trickle_for_whitelisted_peers;
 
         Self {
             id: id.into(),
             p2p_config,
             common_services,
+            protocol_version,
             chainstate_handle,
             mempool_handle,
+            seen_transactions,
             peer_mgr_event_sender,
             messaging_handle,
             sync_msg_receiver,
             local_event_receiver,
             known_transactions,
             requested_transactions: RequestedTransactions::new(time_getter),
-            pending_transactions: PendingTransactions::new(),
+            pending_transactions,
+            relay_txs_immediately,
             observer,
         }
     }
@@ -159,8 +174,8 @@ where
                 }
 
                 _ = self.pending_transactions.due() => {
-                    if let Some(new_tx) = self.pending_transactions.pop(){
-                        self.send_message(TransactionSyncMessage::NewTransaction(new_tx))?;
+                    for new_tx in self.pending_transactions.take_batch(&mut make_pseudo_rng()) {
+                        self.announce_transaction(new_tx)?;
                     }
                 }
 
@@ -175,34 +190,76 @@ where
         self.messaging_handle.send_transaction_sync_message(self.id(), message)
     }
 
+    /// Whether the peer's protocol version supports announcements with witness ids.
+    fn wtxid_announcements_supported(&self) -> bool {
+        self.protocol_version >= SupportedProtocolVersion::V5
+    }
+
+    fn announce_transaction(&mut self, announcement: TransactionAnnouncement) -> Result<()> {
+        let message = if self.wtxid_announcements_supported() {
+            TransactionSyncMessage::NewTransactionWithWtxid(announcement)
+        } else {
+            TransactionSyncMessage::NewTransaction(*announcement.tx_id())
+        };
+        self.send_message(message)
+    }
+
+    fn is_known_transaction(&self, announcement: &TransactionAnnouncement) -> bool {
+        if self.wtxid_announcements_supported() {
+            self.known_transactions.contains(announcement.wtxid())
+        } else {
+            self.known_transactions.contains(announcement.tx_id())
+        }
+    }
+
     fn handle_local_event(&mut self, event: LocalEvent) -> Result<()> {
         log::debug!("Handling local peer mgr event: {event:?}");
 
         match event {
             LocalEvent::ChainstateNewTip(_) => Ok(()),
-            LocalEvent::MempoolNewTx(txid) => {
-                if !self.known_transactions.contains(&txid)
-                    && self.common_services.has_service(Service::Transactions)
-                {
-                    self.add_known_transaction(txid);
-
-                    // TODO: whitelisted peers should get txs without delay
-                    let now = Instant::now();
-                    let delay = TX_RELAY_DELAY_INTERVAL
-                        .mul_f64(utils::exp_rand::exponential_rand(&mut make_pseudo_rng()));
-                    self.pending_transactions.push(txid, now + delay);
+            LocalEvent::MempoolNewTx(announcement) => {
+                if !self.is_known_transaction(&announcement) {
+                    self.enqueue_announcement(announcement)?;
                 }
                 Ok(())
             }
+            // The peer may have dropped the transaction since it learned about it,
+            // so it's announced again even if it's known.
+            LocalEvent::MempoolTxRebroadcast(announcement) => {
+                self.enqueue_announcement(announcement)
+            }
         }
     }
 
+    fn enqueue_announcement(&mut self, announcement: TransactionAnnouncement) -> Result<()> {
+        if !self.common_services.has_service(Service::Transactions) {
+            return Ok(());
+        }
+
+        if self.wtxid_announcements_supported() {
+            self.known_transactions.insert(announcement.wtxid());
+        } else {
+            self.known_transactions.insert(announcement.tx_id());
+        }
+
+        if self.relay_txs_immediately {
+            self.announce_transaction(announcement)?;
+        } else {
+            self.pending_transactions
+                .push(announcement, Instant::now(), &mut make_pseudo_rng());
+        }
+        Ok(())
+    }
+
     async fn handle_message(&mut self, message: TransactionSyncMessage) -> Result<()> {
         log::trace!("Handling tx sync message from the peer: {message:?}");
 
         let res = match message {
             TransactionSyncMessage::NewTransaction(id) => {
-                self.handle_transaction_announcement(id).await
+                self.handle_transaction_announcement(id, None).await
+            }
+            TransactionSyncMessage::NewTransactionWithWtxid(announcement) => {
+                self.handle_transaction_announcement_with_wtxid(announcement).await
             }
             TransactionSyncMessage::TransactionRequest(id) => {
                 self.handle_transaction_request(id).await
@@ -229,8 +286,19 @@ where
         }
 
         let tx = self.mempool_handle.call(move |m| m.transaction(&id)).await?;
+        // A transaction that doesn't fit into a message (together with the message header)
+        // would be rejected by the peer, so it's reported as missing instead.
+        let max_tx_size = *self.p2p_config.protocol_config.max_message_size;
         let res = match tx {
-            Some(tx) => TransactionResponse::Found(tx),
+            Some(tx) if tx.tx_size().serialized() < max_tx_size => TransactionResponse::Found(tx),
+            Some(tx) => {
+                log::warn!(
+                    "Transaction {id} of size {} is too big to be sent to peer {}",
+                    tx.tx_size().serialized(),
+                    self.id()
+                );
+                TransactionResponse::NotFound(id)
+            }
             None => TransactionResponse::NotFound(id),
         };
 
@@ -293,14 +361,32 @@ where
         Ok(())
     }
 
-    fn add_known_transaction(&mut self, txid: Id<Transaction>) {
-        self.known_transactions.insert(&txid);
+    async fn handle_transaction_announcement_with_wtxid(
+        &mut self,
+        announcement: TransactionAnnouncement,
+    ) -> Result<()> {
+        utils::ensure!(
+            self.wtxid_announcements_supported(),
+            P2pError::ProtocolError(ProtocolError::UnexpectedMessage(
+                "A transaction announcement with wtxid is received, but the peer's protocol version doesn't support it".to_owned()
+            ))
+        );
+
+        self.handle_transaction_announcement(*announcement.tx_id(), Some(*announcement.wtxid()))
+            .await
     }
 
-    async fn handle_transaction_announcement(&mut self, tx: Id<Transaction>) -> Result<()> {
+    async fn handle_transaction_announcement(
+        &mut self,
+        tx: Id<Transaction>,
+        wtxid: Option<Id<SignedTransaction>>,
+    ) -> Result<()> {
         log::debug!("Handling transaction announcement: {tx}");
 
-        self.add_known_transaction(tx);
+        match &wtxid {
+            Some(wtxid) => self.known_transactions.insert(wtxid),
+            None => self.known_transactions.insert(&tx),
+        }
 
         if self.chainstate_handle.is_initial_block_download().await? {
             log::debug!(
@@ -315,6 +401,21 @@ where
             )));
         }
 
+        let seen_outcome = match &wtxid {
+            Some(wtxid) => self.seen_transactions.outcome_by_wtxid(&tx, wtxid),
+            None => self.seen_transactions.outcome(&tx),
+        };
+        if let Some(outcome) = seen_outcome {
+            // The mempool has already processed this transaction recently, either accepting
+            // or rejecting it, so there is no point in downloading it again. This check doesn't
+            // involve the mempool subsystem, so a storm of such announcements won't clog it.
+            // If the witness id is known, only the rejection of this exact version counts,
+            // so a version with malleated signatures can't prevent the valid one from being
+            // downloaded.
+            log::debug!("Ignoring announcement for already seen tx {tx} ({outcome:?})");
+            return Ok(());
+        }
+
         if self.requested_transactions.contains(&tx) {
             // Ignore duplicate announcements.
             //
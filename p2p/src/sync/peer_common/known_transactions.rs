@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common::{chain::Transaction, primitives::Id};
+use common::primitives::{Id, H256};
 use randomness::make_pseudo_rng;
 use utils::bloom_filters::rolling_bloom_filter::RollingBloomFilter;
 
@@ -21,8 +21,8 @@ use utils::bloom_filters::rolling_bloom_filter::RollingBloomFilter;
 const ROLLING_BLOOM_FILTER_SIZE: usize = 50000;
 const ROLLING_BLOOM_FPP: f64 = 0.000001;
 
-/// Helper to use with `RollingBloomFilter` because `Id` does not implement `Hash`
-struct TxIdWrapper(Id<Transaction>);
+/// Helper to use with `RollingBloomFilter` because `H256` does not implement `Hash`
+struct TxIdWrapper(H256);
 
 impl std::hash::Hash for TxIdWrapper {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -31,6 +31,9 @@ impl std::hash::Hash for TxIdWrapper {
 }
 
 /// A rolling filter of all known transactions for use by Peer implementations.
+///
+/// Transactions are identified either by their ids or by their witness ids, depending on
+/// what the peer's protocol version supports.
 pub struct KnownTransactions {
     filter: RollingBloomFilter<TxIdWrapper>,
 }
@@ -46,11 +49,11 @@ impl KnownTransactions {
         }
     }
 
-    pub fn insert(&mut self, tx_id: &Id<Transaction>) {
-        self.filter.insert(&TxIdWrapper(*tx_id), &mut make_pseudo_rng())
+    pub fn insert<T>(&mut self, tx_id: &Id<T>) {
+        self.filter.insert(&TxIdWrapper(tx_id.to_hash()), &mut make_pseudo_rng())
     }
 
-    pub fn contains(&self, tx_id: &Id<Transaction>) -> bool {
-        self.filter.contains(&TxIdWrapper(*tx_id))
+    pub fn contains<T>(&self, tx_id: &Id<T>) -> bool {
+        self.filter.contains(&TxIdWrapper(tx_id.to_hash()))
     }
 }
@@ -522,6 +522,12 @@ async fn send_headers_connected_to_previously_sent_headers(#[case] seed: Seed) {
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
                 max_peer_tx_announcements: Default::default(),
             },
 
@@ -531,6 +537,7 @@ async fn send_headers_connected_to_previously_sent_headers(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -541,6 +548,9 @@ async fn send_headers_connected_to_previously_sent_headers(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
 
@@ -625,6 +635,12 @@ async fn send_headers_connected_to_block_which_is_being_downloaded(#[case] seed:
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
                 max_peer_tx_announcements: Default::default(),
             },
 
@@ -634,6 +650,7 @@ async fn send_headers_connected_to_block_which_is_being_downloaded(#[case] seed:
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -644,6 +661,9 @@ async fn send_headers_connected_to_block_which_is_being_downloaded(#[case] seed:
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
 
@@ -725,6 +745,12 @@ async fn correct_pending_headers_update(#[case] seed: Seed) {
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
                 max_peer_tx_announcements: Default::default(),
             },
 
@@ -734,6 +760,7 @@ async fn correct_pending_headers_update(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -744,6 +771,9 @@ async fn correct_pending_headers_update(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
 
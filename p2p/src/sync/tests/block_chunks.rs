@@ -0,0 +1,429 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chainstate::{ban_score::BanScore, BlockSource};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::{config::create_unit_test_config, Block},
+    primitives::{Id, Idable},
+};
+use p2p_test_utils::create_n_blocks;
+use serialization::Encode;
+use test_utils::random::Seed;
+
+use crate::{
+    error::ProtocolError,
+    message::{
+        BlockChunk, BlockChunkManifest, BlockChunkRequest, BlockListRequest, BlockSyncMessage,
+        HeaderListRequest,
+    },
+    protocol::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersion},
+    sync::{
+        block_chunks::{get_chunk, make_manifest},
+        tests::helpers::{get_random_hash, TestNode, TestPeer},
+    },
+    test_helpers::test_p2p_config,
+    types::peer_id::PeerId,
+    P2pConfig, P2pError,
+};
+
+const CHUNK_SIZE: usize = 16;
+const MAX_CHUNKS_PER_REQUEST: usize = 2;
+
+fn make_p2p_config() -> Arc<P2pConfig> {
+    Arc::new(P2pConfig {
+        protocol_config: ProtocolConfig {
+            block_chunking_threshold: 0.into(),
+            block_chunk_size: CHUNK_SIZE.into(),
+            max_block_chunks_per_request: MAX_CHUNKS_PER_REQUEST.into(),
+
+            msg_header_count_limit: Default::default(),
+            max_request_blocks_count: Default::default(),
+            max_addr_list_response_address_count: Default::default(),
+            msg_max_locator_count: Default::default(),
+            max_message_size: Default::default(),
+            max_peer_tx_announcements: Default::default(),
+            max_chunked_block_size: Default::default(),
+            max_block_filter_headers_per_request: Default::default(),
+            max_block_filters_per_request: Default::default(),
+        },
+        ..test_p2p_config()
+    })
+}
+
+fn block_manifest(block: &Block) -> BlockChunkManifest {
+    make_manifest(block.get_id(), &block.encode(), CHUNK_SIZE)
+}
+
+fn block_chunk(block: &Block, chunk_index: u32) -> BlockChunk {
+    let encoded_block = block.encode();
+    let data = get_chunk(&encoded_block, CHUNK_SIZE, chunk_index).unwrap();
+    BlockChunk::new(block.get_id(), chunk_index, data.to_vec())
+}
+
+fn protocol_v4() -> ProtocolVersion {
+    SupportedProtocolVersion::V4.into()
+}
+
+// Expect a chunk request from the node to the specified peer and return the requested indices.
+async fn expect_chunk_request(
+    node: &mut TestNode,
+    peer: &TestPeer,
+    block_id: Id<Block>,
+) -> Vec<u32> {
+    let (sent_to, message) = node.get_sent_block_sync_message().await;
+    assert_eq!(sent_to, peer.get_id());
+    match message {
+        BlockSyncMessage::BlockChunkRequest(request) => {
+            assert_eq!(*request.block_id(), block_id);
+            assert!(!request.chunk_indices().is_empty());
+            assert!(request.chunk_indices().len() <= MAX_CHUNKS_PER_REQUEST);
+            request.chunk_indices().to_vec()
+        }
+        message => panic!("Unexpected message: {message:?}"),
+    }
+}
+
+// Serve the chunk requests of the node until it reports that the block has been obtained.
+async fn serve_chunk_requests(
+    node: &mut TestNode,
+    peer: &TestPeer,
+    block: &Block,
+    mut chunk_indices: Vec<u32>,
+) {
+    loop {
+        for chunk_index in chunk_indices {
+            peer.send_block_sync_message(BlockSyncMessage::BlockChunk(block_chunk(
+                block,
+                chunk_index,
+            )))
+            .await;
+        }
+
+        let (sent_to, message) = node.get_sent_block_sync_message().await;
+        assert_eq!(sent_to, peer.get_id());
+        match message {
+            BlockSyncMessage::BlockChunkRequest(request) => {
+                assert_eq!(*request.block_id(), block.get_id());
+                chunk_indices = request.chunk_indices().to_vec();
+            }
+            BlockSyncMessage::BlockChunksReceived(block_id) => {
+                assert_eq!(block_id, block.get_id());
+                break;
+            }
+            message => panic!("Unexpected message: {message:?}"),
+        }
+    }
+}
+
+// The peer requests two blocks; the node sends the first one in chunks and only starts sending
+// the second one after the peer reports that it has obtained the first one.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn send_chunked_block(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let blocks = create_n_blocks(&mut rng, &mut tf, 2);
+    for block in blocks.clone() {
+        tf.process_block(block, BlockSource::Local).unwrap().unwrap();
+    }
+
+    let mut node = TestNode::builder(protocol_v4())
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_v4()).await;
+
+    let ids = blocks.iter().map(|b| b.get_id()).collect();
+    peer.send_block_sync_message(BlockSyncMessage::BlockListRequest(BlockListRequest::new(
+        ids,
+    )))
+    .await;
+
+    let (sent_to, message) = node.get_sent_block_sync_message().await;
+    assert_eq!(sent_to, peer.get_id());
+    assert_eq!(
+        message,
+        BlockSyncMessage::BlockChunkManifest(block_manifest(&blocks[0]))
+    );
+    node.assert_no_sync_message().await;
+
+    let chunk_count = block_manifest(&blocks[0]).chunk_hashes().len() as u32;
+    let chunk_indices = vec![chunk_count - 1, 0];
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunkRequest(BlockChunkRequest::new(
+        blocks[0].get_id(),
+        chunk_indices.clone(),
+    )))
+    .await;
+    for chunk_index in chunk_indices {
+        let (sent_to, message) = node.get_sent_block_sync_message().await;
+        assert_eq!(sent_to, peer.get_id());
+        assert_eq!(
+            message,
+            BlockSyncMessage::BlockChunk(block_chunk(&blocks[0], chunk_index))
+        );
+    }
+    node.assert_no_sync_message().await;
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunksReceived(blocks[0].get_id()))
+        .await;
+    let (sent_to, message) = node.get_sent_block_sync_message().await;
+    assert_eq!(sent_to, peer.get_id());
+    assert_eq!(
+        message,
+        BlockSyncMessage::BlockChunkManifest(block_manifest(&blocks[1]))
+    );
+
+    // Requesting chunks of a block that is not being transferred is an error.
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunkRequest(BlockChunkRequest::new(
+        blocks[0].get_id(),
+        vec![0],
+    )))
+    .await;
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::InvalidBlockChunkRequest(blocks[0].get_id()))
+            .ban_score(),
+    )
+    .await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn receive_chunked_block(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block = tf.make_block_builder().build(&mut rng);
+
+    let mut node = TestNode::builder(protocol_v4())
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_v4()).await;
+
+    peer.send_headers(vec![block.header().clone()]).await;
+    let (sent_to, message) = node.get_sent_block_sync_message().await;
+    assert_eq!(sent_to, peer.get_id());
+    assert_eq!(
+        message,
+        BlockSyncMessage::BlockListRequest(BlockListRequest::new(vec![block.get_id()]))
+    );
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunkManifest(block_manifest(&block)))
+        .await;
+    let chunk_indices = expect_chunk_request(&mut node, &peer, block.get_id()).await;
+    serve_chunk_requests(&mut node, &peer, &block, chunk_indices).await;
+
+    // A peer would request headers after the last block.
+    assert!(matches!(
+        node.get_sent_block_sync_message().await.1,
+        BlockSyncMessage::HeaderListRequest(HeaderListRequest { .. })
+    ));
+    assert_eq!(node.get_block(block.get_id()).await, Some(block));
+
+    node.assert_no_error().await;
+    node.assert_no_peer_manager_event().await;
+
+    node.join_subsystem_manager().await;
+}
+
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_chunk(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block = tf.make_block_builder().build(&mut rng);
+
+    let mut node = TestNode::builder(protocol_v4())
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_v4()).await;
+
+    peer.send_headers(vec![block.header().clone()]).await;
+    let _ = node.get_sent_block_sync_message().await;
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunkManifest(block_manifest(&block)))
+        .await;
+    let chunk_indices = expect_chunk_request(&mut node, &peer, block.get_id()).await;
+
+    let chunk_index = chunk_indices[0];
+    let mut chunk_data = block_chunk(&block, chunk_index).into_data();
+    chunk_data[0] = chunk_data[0].wrapping_add(1);
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunk(BlockChunk::new(
+        block.get_id(),
+        chunk_index,
+        chunk_data,
+    )))
+    .await;
+
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::InvalidBlockChunk(
+            block.get_id(),
+            chunk_index,
+        ))
+        .ban_score(),
+    )
+    .await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+// Peers that don't support chunked transfers are not allowed to send chunk messages.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn manifest_from_old_peer(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block = tf.make_block_builder().build(&mut rng);
+
+    let protocol_version = SupportedProtocolVersion::V3.into();
+    let mut node = TestNode::builder(protocol_version)
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+    peer.send_headers(vec![block.header().clone()]).await;
+    let _ = node.get_sent_block_sync_message().await;
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockChunkManifest(block_manifest(&block)))
+        .await;
+
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::UnexpectedMessage("".to_owned())).ban_score(),
+    )
+    .await;
+    node.assert_no_sync_message().await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+// The first peer sends a part of the chunks and disconnects; the download is resumed
+// by the second peer without requesting the already received chunks again.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resume_download_from_another_peer(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    let block = tf.make_block_builder().build(&mut rng);
+
+    let mut node = TestNode::builder(protocol_v4())
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .build()
+        .await;
+
+    let peer1 = node.connect_peer(PeerId::new(), protocol_v4()).await;
+    let peer2 = node.connect_peer(PeerId::new(), protocol_v4()).await;
+
+    for peer in [&peer1, &peer2] {
+        peer.send_headers(vec![block.header().clone()]).await;
+        let (sent_to, message) = node.get_sent_block_sync_message().await;
+        assert_eq!(sent_to, peer.get_id());
+        assert_eq!(
+            message,
+            BlockSyncMessage::BlockListRequest(BlockListRequest::new(vec![block.get_id()]))
+        );
+    }
+
+    peer1
+        .send_block_sync_message(BlockSyncMessage::BlockChunkManifest(block_manifest(&block)))
+        .await;
+    let peer1_chunk_indices = expect_chunk_request(&mut node, &peer1, block.get_id()).await;
+    assert_eq!(peer1_chunk_indices, vec![0, 1]);
+
+    peer1
+        .send_block_sync_message(BlockSyncMessage::BlockChunk(block_chunk(&block, 0)))
+        .await;
+    // Make sure the chunk has been processed.
+    let sentinel_id = get_random_hash(&mut rng).into();
+    peer1.send_block_sync_message(BlockSyncMessage::TestSentinel(sentinel_id)).await;
+    assert_eq!(
+        node.get_sent_block_sync_message().await,
+        (peer1.get_id(), BlockSyncMessage::TestSentinel(sentinel_id))
+    );
+
+    node.disconnect_peer(peer1.get_id());
+    // Connecting another peer makes sure that the disconnection has been handled.
+    let _peer3 = node.connect_peer(PeerId::new(), protocol_v4()).await;
+
+    peer2
+        .send_block_sync_message(BlockSyncMessage::BlockChunkManifest(block_manifest(&block)))
+        .await;
+    let peer2_chunk_indices = expect_chunk_request(&mut node, &peer2, block.get_id()).await;
+    assert_eq!(peer2_chunk_indices, vec![1, 2]);
+    serve_chunk_requests(&mut node, &peer2, &block, peer2_chunk_indices).await;
+
+    assert_eq!(node.get_block(block.get_id()).await, Some(block));
+
+    node.join_subsystem_manager().await;
+}
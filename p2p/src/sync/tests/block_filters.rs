@@ -0,0 +1,227 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chainstate::{ban_score::BanScore, BlockSource, ChainstateConfig};
+use chainstate_test_framework::TestFramework;
+use common::{
+    chain::{config::create_unit_test_config, Block},
+    primitives::{BlockHeight, Id, Idable, H256},
+};
+use p2p_test_utils::create_n_blocks;
+use test_utils::random::Seed;
+
+use crate::{
+    error::ProtocolError,
+    message::{BlockFilterHeaders, BlockFilterRequest, BlockFilterResponse, BlockSyncMessage},
+    protocol::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersion},
+    sync::tests::helpers::TestNode,
+    test_helpers::test_p2p_config,
+    types::peer_id::PeerId,
+    P2pConfig, P2pError,
+};
+
+const BLOCK_COUNT: usize = 5;
+const MAX_FILTERS_PER_REQUEST: usize = 3;
+
+fn make_p2p_config() -> Arc<P2pConfig> {
+    Arc::new(P2pConfig {
+        protocol_config: ProtocolConfig {
+            max_block_filter_headers_per_request: MAX_FILTERS_PER_REQUEST.into(),
+            max_block_filters_per_request: MAX_FILTERS_PER_REQUEST.into(),
+
+            msg_header_count_limit: Default::default(),
+            max_request_blocks_count: Default::default(),
+            max_addr_list_response_address_count: Default::default(),
+            msg_max_locator_count: Default::default(),
+            max_message_size: Default::default(),
+            max_peer_tx_announcements: Default::default(),
+            block_chunking_threshold: Default::default(),
+            block_chunk_size: Default::default(),
+            max_block_chunks_per_request: Default::default(),
+            max_chunked_block_size: Default::default(),
+        },
+        ..test_p2p_config()
+    })
+}
+
+fn protocol_v6() -> ProtocolVersion {
+    SupportedProtocolVersion::V6.into()
+}
+
+// Make a node whose chainstate indexes the filters of `BLOCK_COUNT` blocks; return the node,
+// the ids of the blocks, their filters and the filter header of the first block.
+async fn make_node(
+    seed: Seed,
+    protocol_version: ProtocolVersion,
+) -> (TestNode, Vec<Id<Block>>, Vec<BlockFilterResponse>, H256) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .with_chainstate_config(ChainstateConfig::new().with_block_filter_index_enabled(true))
+        .build();
+    let blocks = create_n_blocks(&mut rng, &mut tf, BLOCK_COUNT);
+    for block in blocks.clone() {
+        tf.process_block(block, BlockSource::Local).unwrap().unwrap();
+    }
+
+    let block_ids = blocks.iter().map(|b| b.get_id()).collect::<Vec<_>>();
+    let filters = block_ids
+        .iter()
+        .map(|id| {
+            let filter = tf.chainstate.get_block_filter(id).unwrap().unwrap();
+            BlockFilterResponse::new(*id, filter)
+        })
+        .collect::<Vec<_>>();
+    let first_header = *tf
+        .chainstate
+        .get_block_filter_commitment(&block_ids[0])
+        .unwrap()
+        .unwrap()
+        .filter_header();
+
+    let node = TestNode::builder(protocol_version)
+        .with_chain_config(chain_config)
+        .with_p2p_config(make_p2p_config())
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    (node, block_ids, filters, first_header)
+}
+
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn serve_filters(#[case] seed: Seed) {
+    let (mut node, block_ids, filters, first_header) = make_node(seed, protocol_v6()).await;
+    let peer = node.connect_peer(PeerId::new(), protocol_v6()).await;
+
+    // Blocks 2..=4, so the previous filter header is the one of the first block.
+    let request = BlockFilterRequest::new(BlockHeight::new(2), block_ids[3]);
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockFilterHeadersRequest(request.clone()))
+        .await;
+    let (sent_to, message) = node.get_sent_block_sync_message().await;
+    assert_eq!(sent_to, peer.get_id());
+    let expected_hashes = filters[1..4].iter().map(|f| f.filter().filter_hash()).collect();
+    assert_eq!(
+        message,
+        BlockSyncMessage::BlockFilterHeaders(BlockFilterHeaders::new(
+            block_ids[3],
+            first_header,
+            expected_hashes
+        ))
+    );
+
+    peer.send_block_sync_message(BlockSyncMessage::BlockFiltersRequest(request))
+        .await;
+    for filter in &filters[1..4] {
+        let (sent_to, message) = node.get_sent_block_sync_message().await;
+        assert_eq!(sent_to, peer.get_id());
+        assert_eq!(message, BlockSyncMessage::BlockFilter(filter.clone()));
+    }
+    node.assert_no_sync_message().await;
+
+    // The filters of unknown blocks are not available.
+    let unknown_block_id = Id::new(H256::from_low_u64_be(1));
+    let request = BlockFilterRequest::new(BlockHeight::new(1), unknown_block_id);
+    peer.send_block_sync_message(BlockSyncMessage::BlockFilterHeadersRequest(request))
+        .await;
+    assert_eq!(
+        node.get_sent_block_sync_message().await,
+        (
+            peer.get_id(),
+            BlockSyncMessage::BlockFilterHeaders(BlockFilterHeaders::new(
+                unknown_block_id,
+                H256::zero(),
+                Vec::new()
+            ))
+        )
+    );
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_requests(#[case] seed: Seed) {
+    let (mut node, block_ids, _, _) = make_node(seed, protocol_v6()).await;
+    let peer = node.connect_peer(PeerId::new(), protocol_v6()).await;
+
+    let request = BlockFilterRequest::new(BlockHeight::new(1), block_ids[BLOCK_COUNT - 1]);
+    peer.send_block_sync_message(BlockSyncMessage::BlockFiltersRequest(request))
+        .await;
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::BlockFiltersRequestLimitExceeded(
+            BLOCK_COUNT,
+            MAX_FILTERS_PER_REQUEST,
+        ))
+        .ban_score(),
+    )
+    .await;
+
+    // The start height is above the stop block.
+    let request = BlockFilterRequest::new(BlockHeight::new(3), block_ids[0]);
+    peer.send_block_sync_message(BlockSyncMessage::BlockFilterHeadersRequest(request))
+        .await;
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::InvalidBlockFilterRequest(block_ids[0])).ban_score(),
+    )
+    .await;
+    node.assert_no_sync_message().await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+// Peers that don't support block filters are not allowed to request them.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn request_from_old_peer(#[case] seed: Seed) {
+    let protocol_version = SupportedProtocolVersion::V5.into();
+    let (mut node, block_ids, _, _) = make_node(seed, protocol_version).await;
+    let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+    let request = BlockFilterRequest::new(BlockHeight::new(1), block_ids[0]);
+    peer.send_block_sync_message(BlockSyncMessage::BlockFiltersRequest(request))
+        .await;
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::UnexpectedMessage("".to_owned())).ban_score(),
+    )
+    .await;
+    node.assert_no_sync_message().await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
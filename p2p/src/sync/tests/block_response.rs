@@ -30,7 +30,7 @@ use p2p_test_utils::create_n_blocks;
 use randomness::Rng;
 use test_utils::{
     random::{shuffle_until_different, Seed},
-    BasicTestTimeGetter,
+    BasicTestTimeGetter, VirtualTimeGetter,
 };
 
 use crate::{
@@ -165,6 +165,7 @@ async fn block_responses_in_wrong_order(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
             ping_timeout: Default::default(),
@@ -174,6 +175,9 @@ async fn block_responses_in_wrong_order(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: "test".try_into().unwrap(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
             protocol_config: Default::default(),
         });
@@ -269,14 +273,18 @@ async fn block_responses_in_wrong_order(#[case] seed: Seed) {
     .await;
 }
 
+// The peer doesn't respond to the block request and is disconnected after the stalling timeout.
+// The test runs on the virtual clock, so a realistic timeout can be used.
 #[tracing::instrument(skip(seed))]
 #[rstest::rstest]
 #[trace]
 #[case(Seed::from_entropy())]
-#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[tokio::test(start_paused = true)]
 async fn disconnect(#[case] seed: Seed) {
     for_each_protocol_version(|protocol_version| async move {
         let mut rng = test_utils::random::make_seedable_rng(seed);
+        const STALLING_TIMEOUT: Duration = Duration::from_secs(60);
+        let time_getter = VirtualTimeGetter::new();
 
         let chain_config = Arc::new(create_unit_test_config());
         let mut tf = TestFramework::builder(&mut rng)
@@ -285,7 +293,10 @@ async fn disconnect(#[case] seed: Seed) {
         let block = tf.make_block_builder().build(&mut rng);
 
         let p2p_config = Arc::new(P2pConfig {
-            sync_stalling_timeout: Duration::from_millis(100).into(),
+            sync_stalling_timeout: STALLING_TIMEOUT.into(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -293,6 +304,7 @@ async fn disconnect(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -308,6 +320,7 @@ async fn disconnect(#[case] seed: Seed) {
         let mut node = TestNode::builder(protocol_version)
             .with_chain_config(chain_config)
             .with_p2p_config(Arc::clone(&p2p_config))
+            .with_time_getter(time_getter.get_time_getter())
             .with_chainstate(tf.into_chainstate())
             .build()
             .await;
@@ -326,7 +339,9 @@ async fn disconnect(#[case] seed: Seed) {
             BlockSyncMessage::BlockListRequest(BlockListRequest::new(vec![block.get_id()]))
         );
 
-        tokio::time::sleep(Duration::from_millis(300)).await;
+        time_getter.advance_time(STALLING_TIMEOUT - Duration::from_secs(1)).await;
+        node.assert_no_disconnect_peer_event(peer.get_id()).await;
+
         node.receive_disconnect_peer_event(peer.get_id()).await;
 
         node.join_subsystem_manager().await;
@@ -353,6 +368,9 @@ async fn slow_response(#[case] seed: Seed) {
         let chain_config = Arc::new(create_unit_test_config());
         let p2p_config = Arc::new(P2pConfig {
             sync_stalling_timeout: STALLING_TIMEOUT.into(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -360,6 +378,7 @@ async fn slow_response(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
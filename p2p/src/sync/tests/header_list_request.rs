@@ -134,6 +134,9 @@ async fn respond_with_empty_header_list_when_in_ibd() {
         let chain_config = Arc::new(create_unit_test_config());
         let p2p_config = Arc::new(P2pConfig {
             sync_stalling_timeout: STALLING_TIMEOUT.into(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -141,6 +144,7 @@ async fn respond_with_empty_header_list_when_in_ibd() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -17,14 +17,20 @@ use std::{iter, sync::Arc, time::Duration};
 
 use chainstate::ban_score::BanScore;
 use chainstate_test_framework::TestFramework;
-use common::{chain::config::create_unit_test_config, primitives::Idable};
+use common::{
+    chain::{
+        block::consensus_data::{ConsensusData, PoWData},
+        config::create_unit_test_config,
+    },
+    primitives::{Compact, Idable},
+};
 use p2p_test_utils::create_n_blocks;
-use test_utils::random::Seed;
+use test_utils::{random::Seed, VirtualTimeGetter};
 
 use crate::{
     error::ProtocolError,
     message::{BlockListRequest, BlockSyncMessage, HeaderList},
-    sync::tests::helpers::TestNode,
+    sync::{tests::helpers::TestNode, HeaderCheckError},
     test_helpers::{for_each_protocol_version, test_p2p_config},
     types::peer_id::PeerId,
     P2pConfig, P2pError,
@@ -164,6 +170,57 @@ async fn disconnected_headers(#[case] seed: Seed) {
     .await;
 }
 
+// A header that fails the context-free checks gets the peer banned.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_header(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        let block = tf.make_block_builder().build(&mut rng);
+        // The block hash can't satisfy this target.
+        let mut header = block.header().header().clone();
+        header.update_consensus_data(ConsensusData::PoW(PoWData::new(Compact(0x03000001), 0)));
+        let header = header.with_no_signature();
+
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        peer.send_block_sync_message(BlockSyncMessage::HeaderList(HeaderList::new(vec![
+            header.clone()
+        ])))
+        .await;
+
+        let (adjusted_peer, score) = node.receive_adjust_peer_score_event().await;
+        assert_eq!(peer.get_id(), adjusted_peer);
+        assert_eq!(
+            score,
+            P2pError::ProtocolError(ProtocolError::InvalidHeader(
+                header.get_id(),
+                HeaderCheckError::InsufficientWork
+            ))
+            .ban_score()
+        );
+        assert_eq!(score, 100);
+        node.assert_no_sync_message().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
 #[tracing::instrument(skip(seed))]
 #[rstest::rstest]
 #[trace]
@@ -207,12 +264,20 @@ async fn valid_headers(#[case] seed: Seed) {
     .await;
 }
 
+// The peer doesn't respond to the header request and is disconnected after the stalling timeout.
+// The test runs on the virtual clock, so a realistic timeout can be used.
 #[tracing::instrument]
-#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[tokio::test(start_paused = true)]
 async fn disconnect() {
     for_each_protocol_version(|protocol_version| async move {
+        const STALLING_TIMEOUT: Duration = Duration::from_secs(60);
+        let time_getter = VirtualTimeGetter::new();
+
         let p2p_config = Arc::new(P2pConfig {
-            sync_stalling_timeout: Duration::from_millis(100).into(),
+            sync_stalling_timeout: STALLING_TIMEOUT.into(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
 
             bind_addresses: Default::default(),
             socks5_proxy: Default::default(),
@@ -220,6 +285,7 @@ async fn disconnect() {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -234,12 +300,15 @@ async fn disconnect() {
         });
         let mut node = TestNode::builder(protocol_version)
             .with_p2p_config(Arc::clone(&p2p_config))
+            .with_time_getter(time_getter.get_time_getter())
             .build()
             .await;
 
         let peer = node.connect_peer(PeerId::new(), protocol_version).await;
 
-        tokio::time::sleep(Duration::from_millis(300)).await;
+        time_getter.advance_time(STALLING_TIMEOUT - Duration::from_secs(1)).await;
+        node.assert_no_disconnect_peer_event(peer.get_id()).await;
+
         node.receive_disconnect_peer_event(peer.get_id()).await;
 
         node.join_subsystem_manager().await;
@@ -195,6 +195,17 @@ impl TestNode {
         &mut self,
         peer_id: PeerId,
         protocol_version: ProtocolVersion,
+    ) -> TestPeer {
+        self.try_connect_peer_with_whitelisting(peer_id, protocol_version, false)
+    }
+
+    /// Same as `try_connect_peer`, but also specifies whether the peer is whitelisted.
+    #[must_use]
+    pub fn try_connect_peer_with_whitelisting(
+        &mut self,
+        peer_id: PeerId,
+        protocol_version: ProtocolVersion,
+        is_whitelisted: bool,
     ) -> TestPeer {
         let (block_sync_msg_sender, block_sync_msg_receiver) = mpsc::channel(20);
         let (transaction_sync_msg_sender, transaction_sync_msg_receiver) = mpsc::channel(20);
@@ -205,6 +216,7 @@ impl TestNode {
                 peer_id,
                 common_services: (*self.p2p_config.node_type).into(),
                 protocol_version: common_protocol_version,
+                is_whitelisted,
                 block_sync_msg_receiver,
                 transaction_sync_msg_receiver,
             })
@@ -372,6 +384,7 @@ impl TestNode {
                     | PeerManagerEvent::GetPeerCount(_)
                     | PeerManagerEvent::GetBindAddresses(_)
                     | PeerManagerEvent::GetConnectedPeers(_)
+                    | PeerManagerEvent::GetReachability(_)
                     | PeerManagerEvent::AdjustPeerScore { .. }
                     | PeerManagerEvent::GetReserved(_)
                     | PeerManagerEvent::AddReserved(_, _)
@@ -387,6 +400,7 @@ impl TestNode {
                         panic!("Unexpected peer manager event: {peer_event:?}");
                     }
                     PeerManagerEvent::NewTipReceived { .. }
+                    | PeerManagerEvent::DuplicateBlockReceived { .. }
                     | PeerManagerEvent::NewChainstateTip(_)
                     | PeerManagerEvent::NewValidTransactionReceived { .. }
                     | PeerManagerEvent::PeerBlockSyncStatusUpdate { .. } => {
@@ -638,6 +652,7 @@ pub enum PeerManagerEventDesc {
     GetPeerCount,
     GetBindAddresses,
     GetConnectedPeers,
+    GetReachability,
     AdjustPeerScore {
         peer_id: PeerId,
         score: u32,
@@ -646,6 +661,10 @@ pub enum PeerManagerEventDesc {
         peer_id: PeerId,
         block_id: Id<Block>,
     },
+    DuplicateBlockReceived {
+        peer_id: PeerId,
+        block_id: Id<Block>,
+    },
     NewChainstateTip(Id<Block>),
     NewValidTransactionReceived {
         peer_id: PeerId,
@@ -683,6 +702,7 @@ impl From<&PeerManagerEvent> for PeerManagerEventDesc {
             PeerManagerEvent::GetPeerCount(_) => PeerManagerEventDesc::GetPeerCount,
             PeerManagerEvent::GetBindAddresses(_) => PeerManagerEventDesc::GetBindAddresses,
             PeerManagerEvent::GetConnectedPeers(_) => PeerManagerEventDesc::GetConnectedPeers,
+            PeerManagerEvent::GetReachability(_) => PeerManagerEventDesc::GetReachability,
             PeerManagerEvent::AdjustPeerScore {
                 peer_id,
                 adjust_by,
@@ -698,6 +718,12 @@ impl From<&PeerManagerEvent> for PeerManagerEventDesc {
                     block_id: *block_id,
                 }
             }
+            PeerManagerEvent::DuplicateBlockReceived { peer_id, block_id } => {
+                PeerManagerEventDesc::DuplicateBlockReceived {
+                    peer_id: *peer_id,
+                    block_id: *block_id,
+                }
+            }
             PeerManagerEvent::NewChainstateTip(block_id) => {
                 PeerManagerEventDesc::NewChainstateTip(*block_id)
             }
@@ -316,6 +316,7 @@ impl TestNodeGroup {
                         | PeerManagerEvent::GetPeerCount(_)
                         | PeerManagerEvent::GetBindAddresses(_)
                         | PeerManagerEvent::GetConnectedPeers(_)
+                        | PeerManagerEvent::GetReachability(_)
                         | PeerManagerEvent::AdjustPeerScore { .. }
                         | PeerManagerEvent::GetReserved(_)
                         | PeerManagerEvent::AddReserved(_, _)
@@ -331,6 +332,7 @@ impl TestNodeGroup {
                             panic!("Unexpected peer manager event: {peer_event:?}");
                         }
                         PeerManagerEvent::NewTipReceived { .. }
+                        | PeerManagerEvent::DuplicateBlockReceived { .. }
                         | PeerManagerEvent::NewChainstateTip(_)
                         | PeerManagerEvent::NewValidTransactionReceived { .. }
                         | PeerManagerEvent::PeerBlockSyncStatusUpdate { .. } => {
@@ -15,6 +15,8 @@
 
 mod ban_scores;
 mod block_announcement;
+mod block_chunks;
+mod block_filters;
 mod block_list_request;
 mod block_response;
 mod header_list_request;
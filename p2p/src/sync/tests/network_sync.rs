@@ -60,6 +60,12 @@ async fn basic(#[case] seed: Seed) {
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
                 max_peer_tx_announcements: Default::default(),
             },
 
@@ -69,6 +75,7 @@ async fn basic(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -79,6 +86,9 @@ async fn basic(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
 
@@ -302,6 +312,12 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
                 max_peer_tx_announcements: Default::default(),
             },
 
@@ -311,6 +327,7 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -321,6 +338,9 @@ async fn block_announcement_disconnected_headers(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
 
@@ -430,6 +450,7 @@ async fn send_block_from_the_future_again(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -438,6 +459,9 @@ async fn send_block_from_the_future_again(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: mintlayer_core_user_agent(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
             protocol_config: Default::default(),
         });
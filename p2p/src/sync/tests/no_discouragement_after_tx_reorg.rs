@@ -280,6 +280,7 @@ async fn no_discouragement_after_tx_reorg(#[case] seed: Seed) {
 
         let mempool_config = MempoolConfig {
             min_tx_relay_fee_rate: FeeRate::from_amount_per_kb(Amount::ZERO).into(),
+            ..MempoolConfig::new()
         };
         let mut node = TestNode::builder(protocol_version)
             .with_chain_config(Arc::clone(tfxt.tfrm.chain_config()))
@@ -23,11 +23,11 @@ use common::{
         signature::inputsig::InputWitness, timelock::OutputTimeLock, GenBlock, OutPointSourceId,
         SignedTransaction, Transaction, TxInput, TxOutput,
     },
-    primitives::{Amount, Id, Idable},
+    primitives::{Amount, Id, Idable, H256},
 };
 use mempool::{
     error::{Error as MempoolError, MempoolPolicyError},
-    tx_origin::RemoteTxOrigin,
+    tx_origin::{LocalTxOrigin, RemoteTxOrigin},
     FeeRate, MempoolConfig,
 };
 use serialization::Encode;
@@ -36,8 +36,10 @@ use test_utils::{random::Seed, BasicTestTimeGetter};
 use crate::{
     config::NodeType,
     error::ProtocolError,
-    message::{TransactionResponse, TransactionSyncMessage},
-    protocol::ProtocolConfig,
+    message::{
+        BlockSyncMessage, TransactionAnnouncement, TransactionResponse, TransactionSyncMessage,
+    },
+    protocol::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersion},
     sync::{
         peer::requested_transactions::REQUESTED_TX_EXPIRY_PERIOD,
         tests::helpers::{PeerManagerEventDesc, SyncManagerNotification, TestNode},
@@ -159,6 +161,7 @@ async fn no_transaction_service(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -168,6 +171,9 @@ async fn no_transaction_service(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: "test".try_into().unwrap(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
             protocol_config: Default::default(),
         });
@@ -226,6 +232,12 @@ async fn too_many_announcements(#[case] seed: Seed) {
                 max_addr_list_response_address_count: Default::default(),
                 msg_max_locator_count: Default::default(),
                 max_message_size: Default::default(),
+                max_block_chunks_per_request: Default::default(),
+                block_chunking_threshold: Default::default(),
+                block_chunk_size: Default::default(),
+                max_chunked_block_size: Default::default(),
+                max_block_filter_headers_per_request: Default::default(),
+                max_block_filters_per_request: Default::default(),
             },
 
             bind_addresses: Default::default(),
@@ -234,6 +246,7 @@ async fn too_many_announcements(#[case] seed: Seed) {
             boot_nodes: Default::default(),
             reserved_nodes: Default::default(),
             whitelisted_addresses: Default::default(),
+            external_addresses: Default::default(),
             ban_config: Default::default(),
             outbound_connection_timeout: Default::default(),
             ping_check_period: Default::default(),
@@ -244,6 +257,9 @@ async fn too_many_announcements(#[case] seed: Seed) {
             allow_discover_private_ips: Default::default(),
             user_agent: "test".try_into().unwrap(),
             sync_stalling_timeout: Default::default(),
+            tx_relay_trickle_interval: Default::default(),
+            disable_tx_trickle_for_whitelisted_peers: Default::default(),
+            tx_rebroadcast_interval: Default::default(),
             peer_manager_config: Default::default(),
         });
         let mut node = TestNode::builder(protocol_version)
@@ -361,6 +377,180 @@ async fn duplicated_announcement(#[case] seed: Seed) {
     .await;
 }
 
+// A transaction that has been rejected by the mempool recently is not requested again when
+// another peer announces it.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejected_transaction_announcement(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        // Process a block to finish the initial block download.
+        tf.make_block_builder().build_and_process(&mut rng).unwrap().unwrap();
+
+        let p2p_config = Arc::new(test_p2p_config());
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(chain_config)
+            .with_p2p_config(Arc::clone(&p2p_config))
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let peer1 = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        let tx = Transaction::new(0x00, vec![], vec![]).unwrap();
+        let tx = SignedTransaction::new(tx, vec![]).unwrap();
+        let tx_id = tx.transaction().get_id();
+        peer1
+            .send_transaction_sync_message(TransactionSyncMessage::NewTransaction(tx_id))
+            .await;
+
+        let (sent_to, message) = node.get_sent_transaction_sync_message().await;
+        assert_eq!(peer1.get_id(), sent_to);
+        assert_eq!(message, TransactionSyncMessage::TransactionRequest(tx_id));
+
+        peer1
+            .send_transaction_sync_message(TransactionSyncMessage::TransactionResponse(
+                TransactionResponse::Found(tx),
+            ))
+            .await;
+
+        let (adjusted_peer, _) = node.receive_adjust_peer_score_event().await;
+        assert_eq!(peer1.get_id(), adjusted_peer);
+        assert_eq!(
+            node.mempool()
+                .call(move |m| m.seen_transactions().outcome(&tx_id))
+                .await
+                .unwrap(),
+            Some(mempool::SeenTxOutcome::Rejected)
+        );
+
+        let peer2 = node.connect_peer(PeerId::new(), protocol_version).await;
+        peer2
+            .send_transaction_sync_message(TransactionSyncMessage::NewTransaction(tx_id))
+            .await;
+
+        node.assert_no_sync_message().await;
+        node.assert_no_peer_manager_event().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
+// If a version of a transaction with malleated signatures has been rejected, the version
+// with a different witness id is still requested when announced by a peer that supports wtxids.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn malleated_transaction_announcement(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    // Process a block to finish the initial block download.
+    tf.make_block_builder().build_and_process(&mut rng).unwrap().unwrap();
+
+    let protocol_version = SupportedProtocolVersion::V5.into();
+    let mut node = TestNode::builder(protocol_version)
+        .with_chain_config(Arc::clone(&chain_config))
+        .with_p2p_config(Arc::new(test_p2p_config()))
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    let tx = transaction(chain_config.genesis_block_id());
+    let tx_id = tx.transaction().get_id();
+    let malleated_wtxid = Id::new(H256::random_using(&mut rng));
+
+    // Emulate the rejection of the malleated version.
+    node.mempool()
+        .call(move |m| {
+            m.seen_transactions()
+                .record(&tx_id, &malleated_wtxid, mempool::SeenTxOutcome::Rejected)
+        })
+        .await
+        .unwrap();
+
+    let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+    // The malleated version isn't requested again.
+    peer.send_transaction_sync_message(TransactionSyncMessage::NewTransactionWithWtxid(
+        TransactionAnnouncement::new(tx_id, malleated_wtxid),
+    ))
+    .await;
+    node.assert_no_sync_message().await;
+
+    // But the original one is.
+    peer.send_transaction_sync_message(TransactionSyncMessage::NewTransactionWithWtxid(
+        TransactionAnnouncement::from_tx(&tx),
+    ))
+    .await;
+    let (sent_to, message) = node.get_sent_transaction_sync_message().await;
+    assert_eq!(peer.get_id(), sent_to);
+    assert_eq!(message, TransactionSyncMessage::TransactionRequest(tx_id));
+
+    node.assert_no_peer_manager_event().await;
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
+// Announcements with wtxids are a protocol violation for peers with older protocol versions.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn wtxid_announcement_from_old_peer(#[case] seed: Seed) {
+    let mut rng = test_utils::random::make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut tf = TestFramework::builder(&mut rng)
+        .with_chain_config(chain_config.as_ref().clone())
+        .build();
+    // Process a block to finish the initial block download.
+    tf.make_block_builder().build_and_process(&mut rng).unwrap().unwrap();
+
+    let protocol_version = SupportedProtocolVersion::V4.into();
+    let mut node = TestNode::builder(protocol_version)
+        .with_chain_config(Arc::clone(&chain_config))
+        .with_p2p_config(Arc::new(test_p2p_config()))
+        .with_chainstate(tf.into_chainstate())
+        .build()
+        .await;
+
+    let peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+    let tx = transaction(chain_config.genesis_block_id());
+    peer.send_transaction_sync_message(TransactionSyncMessage::NewTransactionWithWtxid(
+        TransactionAnnouncement::from_tx(&tx),
+    ))
+    .await;
+
+    node.assert_peer_score_adjustment(
+        peer.get_id(),
+        P2pError::ProtocolError(ProtocolError::UnexpectedMessage("".to_owned())).ban_score(),
+    )
+    .await;
+    node.assert_no_sync_message().await;
+
+    node.assert_no_error().await;
+
+    node.join_subsystem_manager().await;
+}
+
 #[tracing::instrument(skip(seed))]
 #[rstest::rstest]
 #[trace]
@@ -444,6 +634,7 @@ async fn valid_transaction_with_fee_below_minimum(#[case] seed: Seed) {
         let p2p_config = Arc::new(test_p2p_config());
         let mempool_config = MempoolConfig {
             min_tx_relay_fee_rate: min_fee_rate.into(),
+            ..MempoolConfig::new()
         };
         let mut node = TestNode::builder(protocol_version)
             .with_p2p_config(Arc::clone(&p2p_config))
@@ -542,6 +733,7 @@ async fn transaction_sequence_via_orphan_pool(#[case] seed: Seed) {
             .with_mempool_config(MempoolConfig {
                 min_tx_relay_fee_rate: FeeRate::from_amount_per_kb(Amount::from_atoms(100_000_000))
                     .into(),
+                ..MempoolConfig::new()
             })
             .with_p2p_config(Arc::clone(&p2p_config))
             .with_chainstate(tf.into_chainstate())
@@ -608,6 +800,9 @@ async fn transaction_sequence_via_orphan_pool(#[case] seed: Seed) {
             logging::log::error!("Msg new: {msg:?}");
             let tx_id = match msg {
                 TransactionSyncMessage::NewTransaction(tx_id) => tx_id,
+                TransactionSyncMessage::NewTransactionWithWtxid(announcement) => {
+                    *announcement.tx_id()
+                }
                 msg => panic!("Unexpected message {msg:?}"),
             };
 
@@ -622,6 +817,67 @@ async fn transaction_sequence_via_orphan_pool(#[case] seed: Seed) {
     .await;
 }
 
+// Transactions are announced to a whitelisted peer immediately if trickling is disabled for
+// whitelisted peers, while other peers get them in a batch later.
+#[tracing::instrument(skip(seed))]
+#[rstest::rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_trickle_for_whitelisted_peer(#[case] seed: Seed) {
+    for_each_protocol_version(|protocol_version| async move {
+        let mut rng = test_utils::random::make_seedable_rng(seed);
+
+        let chain_config = Arc::new(create_unit_test_config());
+        let mut tf = TestFramework::builder(&mut rng)
+            .with_chain_config(chain_config.as_ref().clone())
+            .build();
+        // Process a block to finish the initial block download.
+        tf.make_block_builder().build_and_process(&mut rng).unwrap().unwrap();
+
+        let p2p_config = Arc::new(P2pConfig {
+            tx_relay_trickle_interval: Duration::from_secs(60 * 60).into(),
+            disable_tx_trickle_for_whitelisted_peers: true.into(),
+            tx_rebroadcast_interval: Default::default(),
+
+            ..test_p2p_config()
+        });
+        let mut node = TestNode::builder(protocol_version)
+            .with_chain_config(Arc::clone(&chain_config))
+            .with_p2p_config(Arc::clone(&p2p_config))
+            .with_chainstate(tf.into_chainstate())
+            .build()
+            .await;
+
+        let whitelisted_peer =
+            node.try_connect_peer_with_whitelisting(PeerId::new(), protocol_version, true);
+        let (_, message) = node.get_sent_block_sync_message().await;
+        assert!(matches!(message, BlockSyncMessage::HeaderListRequest(_)));
+        let _regular_peer = node.connect_peer(PeerId::new(), protocol_version).await;
+
+        let tx = transaction(chain_config.genesis_block_id());
+        let expected_message = expected_announcement(protocol_version, &tx);
+        let origin = LocalTxOrigin::P2p;
+        let options = mempool::TxOptions::default_for(origin.into());
+        let res = node
+            .mempool()
+            .call_mut(move |m| m.add_transaction_local(tx, origin, options))
+            .await
+            .unwrap();
+        assert_eq!(res, Ok(()));
+
+        let (sent_to, message) = node.get_sent_transaction_sync_message().await;
+        assert_eq!(sent_to, whitelisted_peer.get_id());
+        assert_eq!(message, expected_message);
+
+        // The regular peer will only get the announcement at the next trickle.
+        node.assert_no_sync_message().await;
+
+        node.join_subsystem_manager().await;
+    })
+    .await;
+}
+
 /// Creates a simple transaction.
 fn transaction_with_amount(out_point: Id<GenBlock>, amount_atoms: u128) -> SignedTransaction {
     let tx = Transaction::new(
@@ -636,3 +892,16 @@ fn transaction_with_amount(out_point: Id<GenBlock>, amount_atoms: u128) -> Signe
 fn transaction(out_point: Id<GenBlock>) -> SignedTransaction {
     transaction_with_amount(out_point, 1)
 }
+
+/// The announcement of the transaction that a peer with the specified protocol version
+/// is expected to receive.
+fn expected_announcement(
+    protocol_version: ProtocolVersion,
+    tx: &SignedTransaction,
+) -> TransactionSyncMessage {
+    if protocol_version >= SupportedProtocolVersion::V5.into() {
+        TransactionSyncMessage::NewTransactionWithWtxid(TransactionAnnouncement::from_tx(tx))
+    } else {
+        TransactionSyncMessage::NewTransaction(tx.transaction().get_id())
+    }
+}
@@ -486,6 +486,9 @@ pub fn make_p2p_config(peer_manager_config: PeerManagerConfig) -> P2pConfig {
         ping_timeout: millenium.into(),
         max_clock_diff: millenium.into(),
         sync_stalling_timeout: millenium.into(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
 
         peer_manager_config,
         bind_addresses: Default::default(),
@@ -494,6 +497,7 @@ pub fn make_p2p_config(peer_manager_config: PeerManagerConfig) -> P2pConfig {
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         // Note: peer_handshake_timeout specifies real time rather than mocked time (it's passed
@@ -13,21 +13,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use accounting::{DataDelta, DataDeltaUndo};
 use common::{
-    chain::DelegationId,
+    chain::{DelegationId, Destination, PoolId},
     primitives::{Amount, H256},
 };
 use randomness::Rng;
 
-use crate::{pool::operations::DelegateStakingUndo, PoSAccountingUndo};
+use crate::{
+    pool::{
+        delegation::DelegationData,
+        operations::{
+            CreateDelegationIdUndo, DelegateStakingUndo, DeleteDelegationIdUndo, SpendFromShareUndo,
+        },
+    },
+    PoSAccountingUndo,
+};
 
 pub fn random_undo_for_test(rng: &mut impl Rng) -> PoSAccountingUndo {
-    let delegation_target: DelegationId = H256::random_using(rng).into();
-    let amount_to_delegate = Amount::from_atoms(rng.gen_range(0..100_000));
+    let delegation_id: DelegationId = H256::random_using(rng).into();
+    let amount = Amount::from_atoms(rng.gen_range(0..100_000));
+    let pool_id: PoolId = H256::random_using(rng).into();
+    let delegation_data = DelegationData::new(pool_id, Destination::AnyoneCanSpend);
 
-    // TODO: return other undo types
-    PoSAccountingUndo::DelegateStaking(DelegateStakingUndo {
-        delegation_target,
-        amount_to_delegate,
-    })
+    // TODO: return pool related undo types
+    match rng.gen_range(0..4) {
+        0 => PoSAccountingUndo::CreateDelegationId(CreateDelegationIdUndo {
+            delegation_id,
+            data_undo: DataDeltaUndo::new(DataDelta::new(Some(delegation_data), None)),
+        }),
+        1 => PoSAccountingUndo::DeleteDelegationId(DeleteDelegationIdUndo {
+            delegation_id,
+            data_undo: DataDeltaUndo::new(DataDelta::new(None, Some(delegation_data))),
+        }),
+        2 => PoSAccountingUndo::DelegateStaking(DelegateStakingUndo {
+            delegation_target: delegation_id,
+            amount_to_delegate: amount,
+        }),
+        _ => PoSAccountingUndo::SpendFromShare(SpendFromShareUndo {
+            delegation_id,
+            amount,
+        }),
+    }
 }
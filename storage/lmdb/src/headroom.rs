@@ -0,0 +1,60 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+/// The state of the memory map at the time a write transaction with a size estimate was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapHeadroomInfo {
+    /// The estimated size of the data the caller is about to write
+    pub estimated_size: usize,
+
+    /// The size of the memory map before it was grown to fit the estimate, if it was
+    pub map_size: usize,
+
+    /// The part of the memory map occupied by the data
+    pub used_size: usize,
+}
+
+impl MapHeadroomInfo {
+    pub fn free_size(&self) -> usize {
+        self.map_size.saturating_sub(self.used_size)
+    }
+
+    pub fn has_enough_space(&self) -> bool {
+        self.free_size() >= self.estimated_size
+    }
+}
+
+pub type MapHeadroomCallbackFn = dyn Fn(MapHeadroomInfo) + Send + Sync;
+
+/// A function wrapper that represents the callback invoked when the headroom of the map
+/// is checked before a write transaction
+#[derive(Clone, Default)]
+pub struct MapHeadroomCallback {
+    f: Option<Arc<MapHeadroomCallbackFn>>,
+}
+
+impl MapHeadroomCallback {
+    pub fn new(f: Box<MapHeadroomCallbackFn>) -> Self {
+        Self { f: Some(f.into()) }
+    }
+
+    pub fn call(&self, info: MapHeadroomInfo) {
+        if let Some(f) = &self.f {
+            f(info)
+        }
+    }
+}
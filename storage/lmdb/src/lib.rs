@@ -17,6 +17,7 @@
 compile_error!("LMDB only compiles for 64-bit targets due to the way memory mapping works");
 
 mod error;
+pub mod headroom;
 pub mod initial_map_size;
 pub mod memsize;
 pub mod resize_callback;
@@ -27,15 +28,40 @@ pub mod resize_callback;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::{borrow::Cow, path::PathBuf};
 
+use headroom::{MapHeadroomCallback, MapHeadroomInfo};
 use initial_map_size::InitialMapSize;
 use lmdb::Cursor;
+use logging::log;
 use resize_callback::MapResizeCallback;
-use storage_core::{backend, Data, DbDesc, DbMapDesc, DbMapId, DbMapsData};
+use storage_core::{backend, error::Fatal, Data, DbDesc, DbMapDesc, DbMapId, DbMapsData};
 use utils::const_value::ConstValue;
 use utils::sync::Arc;
 
 pub use lmdb::{DatabaseResizeInfo, DatabaseResizeSettings};
 
+/// How durable the commits are, see the LMDB documentation of `MDB_NOSYNC` and `MDB_NOMETASYNC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncMode {
+    /// Flush the data and the metadata on every commit.
+    #[default]
+    Full,
+    /// Don't flush the metadata on commit; the last transaction may be lost on a system crash,
+    /// but the database stays consistent.
+    NoMetaSync,
+    /// Don't flush anything on commit; a system crash may corrupt the database.
+    NoSync,
+}
+
+impl SyncMode {
+    fn flags(self) -> lmdb::EnvironmentFlags {
+        match self {
+            SyncMode::Full => lmdb::EnvironmentFlags::empty(),
+            SyncMode::NoMetaSync => lmdb::EnvironmentFlags::NO_META_SYNC,
+            SyncMode::NoSync => lmdb::EnvironmentFlags::NO_SYNC,
+        }
+    }
+}
+
 /// LMDB iterator over entries with given key prefix
 pub struct PrefixIter<'tx, C> {
     /// Underlying iterator
@@ -156,6 +182,9 @@ pub struct LmdbImpl {
 
     /// Schedule a database resize of the database map
     map_resize_scheduled: Arc<AtomicBool>,
+
+    /// Called after the map headroom has been checked for a write transaction
+    headroom_callback: MapHeadroomCallback,
 }
 
 impl LmdbImpl {
@@ -209,6 +238,63 @@ impl LmdbImpl {
         }
         err
     }
+
+    fn map_headroom(&self, estimated_size: usize) -> storage_core::Result<MapHeadroomInfo> {
+        let info = self.env.info().or_else(error::process_with_err)?;
+        let stat = self.env.stat().or_else(error::process_with_err)?;
+        Ok(MapHeadroomInfo {
+            estimated_size,
+            map_size: info.map_size(),
+            used_size: (info.last_pgno() + 1) * stat.page_size() as usize,
+        })
+    }
+
+    /// Grow the map in advance if the estimated size of the data that is about to be written
+    /// doesn't fit into it, so that a large transaction doesn't run into MDB_MAP_FULL midway.
+    /// Fail if the map can't be grown enough.
+    fn ensure_map_headroom(&self, estimated_size: usize) -> storage_core::Result<()> {
+        let initial = self.map_headroom(estimated_size)?;
+        log::debug!(
+            "LMDB map headroom check: {} bytes estimated, {} of {} bytes used",
+            initial.estimated_size,
+            initial.used_size,
+            initial.map_size,
+        );
+        self.headroom_callback.call(initial);
+
+        let mut current = initial;
+        while !current.has_enough_space() {
+            let missing_size = current.estimated_size - current.free_size();
+            if let Err(err) = self.env.do_resize(Some(missing_size)) {
+                log::error!("Failed to grow the LMDB map by {missing_size} bytes: {err}");
+                return Err(Fatal::OutOfSpace.into());
+            }
+            self.unschedule_map_resize();
+
+            let prev_map_size = current.map_size;
+            current = self.map_headroom(estimated_size)?;
+            if current.map_size <= prev_map_size {
+                log::error!(
+                    "The LMDB map of {} bytes with {} bytes used cannot be grown to fit {} bytes",
+                    current.map_size,
+                    current.used_size,
+                    current.estimated_size,
+                );
+                return Err(Fatal::OutOfSpace.into());
+            }
+        }
+
+        if current.map_size != initial.map_size {
+            log::info!(
+                "LMDB map grown from {} to {} bytes in advance to fit {} bytes",
+                initial.map_size,
+                current.map_size,
+                estimated_size,
+            );
+        }
+
+        Ok(())
+    }
 }
 
 impl utils::shallow_clone::ShallowClone for LmdbImpl {
@@ -217,6 +303,7 @@ impl utils::shallow_clone::ShallowClone for LmdbImpl {
             env: self.env.shallow_clone(),
             dbs: self.dbs.shallow_clone(),
             map_resize_scheduled: self.map_resize_scheduled.shallow_clone(),
+            headroom_callback: self.headroom_callback.clone(),
         }
     }
 }
@@ -238,6 +325,9 @@ impl backend::BackendImpl for LmdbImpl {
 impl backend::SharedBackendImpl for LmdbImpl {
     fn transaction_rw(&self, size: Option<usize>) -> storage_core::Result<Self::TxRw<'_>> {
         self.resize_if_resize_scheduled();
+        if let Some(size) = size {
+            self.ensure_map_headroom(size)?;
+        }
         self.start_transaction(|env| lmdb::Environment::begin_rw_txn(env, size))
     }
 }
@@ -248,6 +338,8 @@ pub struct Lmdb {
     initial_map_size: InitialMapSize,
     resize_settings: DatabaseResizeSettings,
     resize_callback: MapResizeCallback,
+    headroom_callback: MapHeadroomCallback,
+    max_readers: Option<u32>,
 }
 
 impl Lmdb {
@@ -264,9 +356,30 @@ impl Lmdb {
             initial_map_size,
             resize_settings,
             resize_callback,
+            headroom_callback: MapHeadroomCallback::default(),
+            max_readers: None,
         }
     }
 
+    /// Set the callback that is invoked when a write transaction is started with a size
+    /// estimate, before the map is grown to fit it.
+    pub fn with_headroom_callback(mut self, headroom_callback: MapHeadroomCallback) -> Self {
+        self.headroom_callback = headroom_callback;
+        self
+    }
+
+    /// Set the maximum number of simultaneous read transactions; LMDB's default is used otherwise.
+    pub fn with_max_readers(mut self, max_readers: u32) -> Self {
+        self.max_readers = Some(max_readers);
+        self
+    }
+
+    /// Set how durable the commits are.
+    pub fn with_sync_mode(mut self, sync_mode: SyncMode) -> Self {
+        self.flags |= sync_mode.flags();
+        self
+    }
+
     /// Use a writable memory map.
     ///
     /// This disables some protections in exchange for better performance.
@@ -325,6 +438,12 @@ impl backend::Backend for Lmdb {
             environment.set_map_size(sz)
         } else {
             environment
+        };
+
+        let environment = if let Some(max_readers) = self.max_readers {
+            environment.set_max_readers(max_readers)
+        } else {
+            environment
         }
         .set_resize_settings(self.resize_settings)
         .set_resize_callback(self.resize_callback.take())
@@ -343,6 +462,7 @@ impl backend::Backend for Lmdb {
             env: Arc::new(environment),
             dbs,
             map_resize_scheduled: Arc::new(AtomicBool::new(false)),
+            headroom_callback: self.headroom_callback,
         })
     }
 }
@@ -216,3 +216,64 @@ fn auto_map_resize_between_puts(#[case] seed: Seed) {
     );
     })
 }
+
+#[rstest]
+#[trace]
+#[case(test_utils::random::Seed::from_entropy())]
+fn map_grown_in_advance_for_size_estimate(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+
+        let resize_actions = Arc::new(Mutex::new(Vec::new()));
+        let resize_actions_for_check = Arc::clone(&resize_actions);
+        let resize_callback = Box::new(move |v| resize_actions.lock().unwrap().push(v));
+
+        let headroom_checks = Arc::new(Mutex::new(Vec::new()));
+        let headroom_checks_for_check = Arc::clone(&headroom_checks);
+        let headroom_callback = Box::new(move |v| headroom_checks.lock().unwrap().push(v));
+
+        let initial_map_size = 1 << 20;
+
+        let resize_settings = DatabaseResizeSettings {
+            min_resize_step: 1 << 16,
+            max_resize_step: 1 << 20,
+            default_resize_ratio_percentage: 10,
+            resize_trigger_percentage: 0.9,
+        };
+
+        let data_dir = tempfile::Builder::new().prefix("lmdb_resize").tempdir().unwrap();
+        let lmdb = Lmdb::new(
+            data_dir.path().to_owned(),
+            MemSize::from_bytes(initial_map_size).into(),
+            resize_settings,
+            MapResizeCallback::new(resize_callback),
+        )
+        .with_headroom_callback(MapHeadroomCallback::new(headroom_callback));
+
+        let desc = storage_core::types::construct::db_desc([DbMapDesc::new("SomeDb")].into_iter());
+        let lmdb_impl = lmdb.open(desc).unwrap();
+
+        let data_size = (initial_map_size * 3) as usize;
+        let data = create_random_data_map_with_target_byte_size(&mut rng, data_size, 500, 10000);
+
+        // With a generous estimate, all the data fits into a single transaction
+        let estimated_size = data_size * 2;
+        let mut rw_tx = lmdb_impl.transaction_rw(Some(estimated_size)).unwrap();
+        for (key, val) in &data {
+            rw_tx.put(DbMapId::new(0), key.clone(), val.clone()).unwrap();
+        }
+        rw_tx.commit().unwrap();
+
+        let headroom_checks = headroom_checks_for_check.lock().unwrap().clone();
+        assert_eq!(headroom_checks.len(), 1);
+        assert_eq!(headroom_checks[0].estimated_size, estimated_size);
+        assert_eq!(headroom_checks[0].map_size, initial_map_size as usize);
+        assert!(!headroom_checks[0].has_enough_space());
+        assert!(!resize_actions_for_check.lock().unwrap().is_empty());
+
+        let ro_tx = lmdb_impl.transaction_ro().unwrap();
+        for (key, val) in data {
+            assert_eq!(ro_tx.get(DbMapId::new(0), &key).unwrap().unwrap(), val);
+        }
+    })
+}
@@ -13,7 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use rusqlite::Error as SqlError;
+use rusqlite::{Error as SqlError, ErrorCode};
 use std::io::{Error as IoError, ErrorKind};
 
 use storage_core::error::{Fatal, Recoverable};
@@ -32,6 +32,10 @@ pub fn process_io_error(err: IoError) -> storage_core::Error {
 /// Map Sqlite error into a storage error
 pub fn process_sqlite_error(err: rusqlite::Error) -> storage_core::Error {
     // TODO Improve error conversions
+    if is_busy_error(&err) {
+        return Recoverable::TemporarilyUnavailable.into();
+    }
+
     match err {
         SqlError::SqliteFailure(err, err_str) => {
             Fatal::InternalError(err_str.unwrap_or_else(|| err.to_string())).into()
@@ -39,3 +43,12 @@ pub fn process_sqlite_error(err: rusqlite::Error) -> storage_core::Error {
         _ => Fatal::InternalError(err.to_string()).into(),
     }
 }
+
+/// Whether the operation failed because another connection holds a conflicting lock, i.e.
+/// whether it makes sense to retry it later.
+pub fn is_busy_error(err: &rusqlite::Error) -> bool {
+    match err.sqlite_error_code() {
+        Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked) => true,
+        Some(_) | None => false,
+    }
+}
@@ -20,21 +20,25 @@ extern crate core;
 
 mod error;
 mod queries;
+mod shared;
 
 use std::{
     borrow::Cow,
     cmp::max,
     path::{Path, PathBuf},
     sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
 };
 
 use rusqlite::{Connection, OpenFlags, OptionalExtension};
 
-use error::process_sqlite_error;
+use error::{is_busy_error, process_sqlite_error};
 use storage_core::{backend, Data, DbDesc, DbMapId};
 
 use crate::queries::SqliteQueries;
 
+pub use shared::{SharedDbTx, SharedDbTxRo, SharedDbTxRw, SharedSqlite, SharedSqliteImpl};
+
 // Note: DbTx holds the mutex itself and locks it on every operation instead of just holding a lock
 // all the time. This is because we want it to be Send, and locks are not.
 pub struct DbTx<'m, const IS_READONLY: bool> {
@@ -77,10 +81,20 @@ impl<'m, const IS_READONLY: bool> DbTx<'m, IS_READONLY> {
         };
 
         if need_start_actual_tx {
-            let _res = conn_lock
-                .connection
-                .execute("BEGIN TRANSACTION", ())
-                .map_err(process_sqlite_error)?;
+            if IS_READONLY {
+                // A deferred transaction doesn't take any locks until the first read, so
+                // starting it can't fail because the database is busy.
+                conn_lock
+                    .connection
+                    .execute("BEGIN TRANSACTION", ())
+                    .map_err(process_sqlite_error)?;
+            } else {
+                // Rw transactions take the write lock immediately, so that a conflict with
+                // another connection is detected (and retried) here and not in the middle
+                // of the transaction.
+                drop(conn_lock);
+                self.execute_with_busy_retries("BEGIN IMMEDIATE TRANSACTION")?;
+            }
         };
 
         Ok(())
@@ -89,21 +103,41 @@ impl<'m, const IS_READONLY: bool> DbTx<'m, IS_READONLY> {
     fn lock_connection(&self) -> MutexGuard<'_, SqliteConnection> {
         self.connection.lock().expect("poisoned mutex")
     }
+
+    /// Execute the statement, retrying it with an increasing delay if the database is busy
+    /// even after sqlite's own busy timeout has expired.
+    ///
+    /// The connection is only locked while the statement is being executed, not while waiting
+    /// for the next attempt.
+    fn execute_with_busy_retries(&self, query: &str) -> storage_core::Result<()> {
+        let mut delay = INITIAL_BUSY_RETRY_DELAY;
+        let mut retries_left = self.lock_connection().busy_retries;
+
+        loop {
+            let res = self.lock_connection().connection.execute(query, ());
+            match res {
+                Ok(_) => return Ok(()),
+                Err(err) if is_busy_error(&err) && retries_left > 0 => {
+                    logging::log::debug!(
+                        "Database is busy, retrying \"{query}\" in {}ms",
+                        delay.as_millis()
+                    );
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    retries_left -= 1;
+                }
+                Err(err) => return Err(process_sqlite_error(err)),
+            }
+        }
+    }
 }
 
 impl<'m> DbTx<'m, false> {
     fn commit_transaction(self) -> storage_core::Result<()> {
-        let conn_lock = self.lock_connection();
-
         // Sanity check
-        assert!(conn_lock.read_only_tx_count == 0);
-
-        let _res = conn_lock
-            .connection
-            .execute("COMMIT TRANSACTION", ())
-            .map_err(process_sqlite_error)?;
+        assert!(self.lock_connection().read_only_tx_count == 0);
 
-        Ok(())
+        self.execute_with_busy_retries("COMMIT TRANSACTION")
     }
 }
 
@@ -264,17 +298,23 @@ struct SqliteConnection {
     /// created, we'll increment this counter and we'll create a real transaction only if it's
     /// the first increment.
     read_only_tx_count: usize,
+
+    /// How many times to retry a statement that failed because the database was busy.
+    busy_retries: u32,
 }
 
 impl SqliteConnection {
-    fn new(connection: Connection) -> Self {
+    fn new(connection: Connection, busy_retries: u32) -> Self {
         Self {
             connection,
             read_only_tx_count: 0,
+            busy_retries,
         }
     }
 }
 
+const INITIAL_BUSY_RETRY_DELAY: Duration = Duration::from_millis(50);
+
 // Note: this struct is deliberately non-clonable even though it's technically trivial to clone
 // (especially if `SqliteQueries` was put inside `SqliteConnection`); as a result, `Sqlite`
 // doesn't implement `SharedBackend`.
@@ -283,6 +323,7 @@ impl SqliteConnection {
 // locked for the entire lifetime of the transaction (which was done in the original implementation
 // of this backend). But this would make `DbTx` non-`Send` (and also dangerous to use, because
 // creating two rw transactions on the same thread would lead to a deadlock).
+// `SharedSqlite` is a wrapper that makes this trade-off for the users that need a shared backend.
 pub struct SqliteImpl {
     /// The current connection.
     connection: Arc<Mutex<SqliteConnection>>,
@@ -310,13 +351,44 @@ pub struct Options {
     /// If enabled, sets synchronous pragma to OFF, see <https://www.sqlite.org/pragma.html#pragma_synchronous>.
     /// It should normally only be used in unit tests.
     pub disable_fsync: bool,
+
+    /// If enabled, the write-ahead log is used for journaling, see <https://www.sqlite.org/wal.html>.
+    /// This allows readers on other connections to proceed while a write transaction is in
+    /// progress, provided that `exclusive_locking` is disabled. Ignored for in-memory databases.
+    ///
+    /// Note that this changes the database on disk: the WAL journal mode is persistent, i.e.
+    /// it stays in effect when the database is opened later with this option disabled, and
+    /// the database is accompanied by the `-wal` and `-shm` files while it's open (no `-shm` file
+    /// is used if `exclusive_locking` is enabled). Such a database can't be opened by sqlite
+    /// versions older than 3.7.0 or from a read-only location. To go back, the journal mode must
+    /// be switched explicitly, e.g. via `PRAGMA journal_mode=DELETE`.
+    ///
+    /// Disabled by default.
+    pub enable_wal: bool,
+
+    /// If enabled, the database file is locked for as long as the connection exists, so no other
+    /// connection (e.g. from another process) can access it at all.
+    /// It must be disabled for other connections to be able to read the database concurrently.
+    pub exclusive_locking: bool,
+
+    /// How long sqlite waits for a lock held by another connection before failing the statement,
+    /// see <https://www.sqlite.org/c3ref/busy_timeout.html>.
+    pub busy_timeout: Duration,
+
+    /// How many times starting or committing a transaction is retried, with a doubling delay,
+    /// if the database is still busy after `busy_timeout`. When the retries are exhausted,
+    /// `Recoverable::TemporarilyUnavailable` is returned.
+    pub busy_retries: u32,
 }
 
-#[allow(clippy::derivable_impls)]
 impl Default for Options {
     fn default() -> Self {
         Self {
             disable_fsync: false,
+            enable_wal: false,
+            exclusive_locking: true,
+            busy_timeout: Duration::from_secs(5),
+            busy_retries: 3,
         }
     }
 }
@@ -373,27 +445,50 @@ impl Sqlite {
         }
     }
 
-    fn open_db(self, desc: DbDesc) -> rusqlite::Result<Connection> {
+    fn open_db(&self, desc: DbDesc) -> rusqlite::Result<Connection> {
         let flags = OpenFlags::from_iter([
             OpenFlags::SQLITE_OPEN_FULL_MUTEX,
             OpenFlags::SQLITE_OPEN_READ_WRITE,
             OpenFlags::SQLITE_OPEN_CREATE,
         ]);
 
-        let path = match self.backend {
-            SqliteStorageMode::File(path) => path,
-            SqliteStorageMode::InMemory(None) => ":memory:".into(),
-            SqliteStorageMode::InMemory(Some(name)) => {
-                format!("file:{name}?mode=memory&cache=shared").into()
-            }
+        let (path, is_file) = match &self.backend {
+            SqliteStorageMode::File(path) => (path.clone(), true),
+            SqliteStorageMode::InMemory(None) => (":memory:".into(), false),
+            SqliteStorageMode::InMemory(Some(name)) => (
+                format!("file:{name}?mode=memory&cache=shared").into(),
+                false,
+            ),
         };
 
         let connection = Connection::open_with_flags(path, flags)?;
 
-        let Options { disable_fsync } = self.options;
+        let Options {
+            disable_fsync,
+            enable_wal,
+            exclusive_locking,
+            busy_timeout,
+            busy_retries: _,
+        } = self.options;
 
-        // Set the locking mode to exclusive
-        connection.pragma_update(None, "locking_mode", "exclusive")?;
+        connection.busy_timeout(busy_timeout)?;
+
+        // Note: the locking mode must be set before switching to WAL, so that an exclusively
+        // locked database doesn't need the shared memory index.
+        if exclusive_locking {
+            connection.pragma_update(None, "locking_mode", "exclusive")?;
+        }
+
+        if enable_wal && is_file {
+            let journal_mode: String =
+                connection
+                    .pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+            if !journal_mode.eq_ignore_ascii_case("wal") {
+                logging::log::warn!(
+                    "Failed to enable WAL for the database, the journal mode is {journal_mode}"
+                );
+            }
+        }
 
         if disable_fsync {
             connection.pragma_update(None, "synchronous", "OFF")?;
@@ -402,9 +497,11 @@ impl Sqlite {
             connection.pragma_update(None, "fullfsync", "true")?;
         }
 
-        // Begin a transaction to acquire the exclusive lock
-        connection.execute("BEGIN EXCLUSIVE TRANSACTION", ())?;
-        connection.execute("COMMIT", ())?;
+        if exclusive_locking {
+            // Begin a transaction to acquire the exclusive lock
+            connection.execute("BEGIN EXCLUSIVE TRANSACTION", ())?;
+            connection.execute("COMMIT", ())?;
+        }
 
         // Create a table check sql statement
         let mut exists_stmt = connection
@@ -454,9 +551,10 @@ impl backend::Backend for Sqlite {
         let queries = desc.db_maps().transform(queries::SqliteQuery::from_desc);
 
         let connection = self.open_db(desc).map_err(process_sqlite_error)?;
+        let connection = SqliteConnection::new(connection, self.options.busy_retries);
 
         Ok(SqliteImpl {
-            connection: Arc::new(Mutex::new(SqliteConnection::new(connection))),
+            connection: Arc::new(Mutex::new(connection)),
             queries,
         })
     }
@@ -0,0 +1,153 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A version of the sqlite backend that implements `SharedBackend`.
+
+use std::{
+    borrow::Cow,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use storage_core::{
+    backend::{self, ReadOps, WriteOps},
+    Data, DbDesc, DbMapId,
+};
+
+use crate::{DbTx, Sqlite, SqliteImpl};
+
+/// The sqlite backend that can be shared, e.g. by the chainstate.
+///
+/// All clones of the opened database use the same connection. To make this safe, every
+/// transaction holds a lock for its entire lifetime: read-only transactions may co-exist,
+/// while a read-write one is exclusive. As a result, the transactions of this backend are not
+/// `Send`, and starting a read-write transaction on a thread that still holds another
+/// transaction of the same database will deadlock.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SharedSqlite(Sqlite);
+
+impl SharedSqlite {
+    pub fn new(sqlite: Sqlite) -> Self {
+        Self(sqlite)
+    }
+}
+
+#[derive(Clone)]
+pub struct SharedSqliteImpl {
+    sqlite: Arc<SqliteImpl>,
+    tx_lock: Arc<RwLock<()>>,
+}
+
+impl utils::shallow_clone::ShallowClone for SharedSqliteImpl {
+    fn shallow_clone(&self) -> Self {
+        Self {
+            sqlite: self.sqlite.shallow_clone(),
+            tx_lock: self.tx_lock.shallow_clone(),
+        }
+    }
+}
+
+/// A transaction together with the guard that ensures that it's not used concurrently
+/// with a conflicting one.
+pub struct SharedDbTx<'m, G, const IS_READONLY: bool> {
+    // Note: the transaction must be dropped (and so rolled back if not committed) before
+    // the guard is released, which is ensured by the order of the fields.
+    tx: DbTx<'m, IS_READONLY>,
+    _guard: G,
+}
+
+pub type SharedDbTxRo<'m> = SharedDbTx<'m, RwLockReadGuard<'m, ()>, true>;
+pub type SharedDbTxRw<'m> = SharedDbTx<'m, RwLockWriteGuard<'m, ()>, false>;
+
+impl<G, const IS_READONLY: bool> ReadOps for SharedDbTx<'_, G, IS_READONLY> {
+    fn get(&self, map_id: DbMapId, key: &[u8]) -> storage_core::Result<Option<Cow<'_, [u8]>>> {
+        self.tx.get(map_id, key)
+    }
+
+    fn prefix_iter(
+        &self,
+        map_id: DbMapId,
+        prefix: Data,
+    ) -> storage_core::Result<impl Iterator<Item = (Data, Data)> + '_> {
+        self.tx.prefix_iter(map_id, prefix)
+    }
+
+    fn greater_equal_iter(
+        &self,
+        map_id: DbMapId,
+        key: Data,
+    ) -> storage_core::Result<impl Iterator<Item = (Data, Data)> + '_> {
+        self.tx.greater_equal_iter(map_id, key)
+    }
+}
+
+impl<G> WriteOps for SharedDbTx<'_, G, false> {
+    fn put(&mut self, map_id: DbMapId, key: Data, val: Data) -> storage_core::Result<()> {
+        self.tx.put(map_id, key, val)
+    }
+
+    fn del(&mut self, map_id: DbMapId, key: &[u8]) -> storage_core::Result<()> {
+        self.tx.del(map_id, key)
+    }
+}
+
+impl<G, const IS_READONLY: bool> backend::TxRo for SharedDbTx<'_, G, IS_READONLY> {}
+
+impl<G> backend::TxRw for SharedDbTx<'_, G, false> {
+    fn commit(self) -> storage_core::Result<()> {
+        let Self { tx, _guard } = self;
+        tx.commit_transaction()
+    }
+}
+
+impl backend::BackendImpl for SharedSqliteImpl {
+    type TxRo<'a> = SharedDbTxRo<'a>;
+
+    type TxRw<'a> = SharedDbTxRw<'a>;
+
+    fn transaction_ro(&self) -> storage_core::Result<Self::TxRo<'_>> {
+        let guard = self.tx_lock.read().expect("poisoned lock");
+        let tx = DbTx::start_transaction(&self.sqlite)?;
+        Ok(SharedDbTx { tx, _guard: guard })
+    }
+
+    fn transaction_rw(&mut self, size: Option<usize>) -> storage_core::Result<Self::TxRw<'_>> {
+        <Self as backend::SharedBackendImpl>::transaction_rw(self, size)
+    }
+}
+
+impl backend::SharedBackendImpl for SharedSqliteImpl {
+    fn transaction_rw(&self, _size: Option<usize>) -> storage_core::Result<Self::TxRw<'_>> {
+        let guard = self.tx_lock.write().expect("poisoned lock");
+        let tx = DbTx::start_transaction(&self.sqlite)?;
+        Ok(SharedDbTx { tx, _guard: guard })
+    }
+}
+
+impl backend::Backend for SharedSqlite {
+    type Impl = SharedSqliteImpl;
+
+    fn open(self, desc: DbDesc) -> storage_core::Result<Self::Impl> {
+        let sqlite = backend::Backend::open(self.0, desc)?;
+
+        Ok(SharedSqliteImpl {
+            sqlite: Arc::new(sqlite),
+            tx_lock: Arc::new(RwLock::new(())),
+        })
+    }
+}
+
+impl backend::SharedBackend for SharedSqlite {
+    type ImplHelper = SharedSqliteImpl;
+}
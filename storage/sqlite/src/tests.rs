@@ -13,7 +13,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use storage::error::Fatal;
+use std::time::Duration;
+
+use storage::error::{Fatal, Recoverable};
 use storage_backend_test_suite::prelude::{desc, MAPID};
 use storage_core::{
     backend::{BackendImpl as _, ReadOps as _, TxRw as _, WriteOps as _},
@@ -21,7 +23,7 @@ use storage_core::{
 };
 use test_utils::assert_matches_return_val;
 
-use crate::Sqlite;
+use crate::{Options, Sqlite};
 
 // Force tx creation to produce an error and check the error.
 // This checks for a regression where an error during tx creation would cause a deadlock:
@@ -222,3 +224,118 @@ fn db_open_in_memory_named() {
         assert!(dbtx.get(MAPID.0, b"hello").unwrap().is_none());
     }
 }
+
+fn non_exclusive_wal_options() -> Options {
+    Options {
+        enable_wal: true,
+        exclusive_locking: false,
+        ..Default::default()
+    }
+}
+
+// Check that the journal mode of the database on disk is left alone by default.
+#[test]
+fn wal_disabled_by_default() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("database.sqlite");
+
+    let db = Sqlite::new(&db_path).open(desc(1)).unwrap();
+    let conn_lock = db.connection.lock().unwrap();
+    let journal_mode: String = conn_lock
+        .connection
+        .pragma_query_value(None, "journal_mode", |row| row.get(0))
+        .unwrap();
+    assert_eq!(journal_mode, "delete");
+}
+
+// Check that with WAL enabled and non-exclusive locking, a reader on another connection
+// can proceed while a write transaction is in progress.
+#[test]
+fn wal_reader_alongside_writer() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("database.sqlite");
+
+    let mut writer_db = Sqlite::new(&db_path)
+        .with_options(non_exclusive_wal_options())
+        .open(desc(1))
+        .unwrap();
+    let reader_db = Sqlite::new(&db_path)
+        .with_options(non_exclusive_wal_options())
+        .open(desc(1))
+        .unwrap();
+
+    {
+        let conn_lock = reader_db.connection.lock().unwrap();
+        let journal_mode: String = conn_lock
+            .connection
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+    }
+
+    let mut dbtx = writer_db.transaction_rw(None).unwrap();
+    dbtx.put(MAPID.0, b"hello".to_vec(), b"world".to_vec()).unwrap();
+    dbtx.commit().unwrap();
+
+    // Start another write, but don't commit it yet.
+    let mut dbtx = writer_db.transaction_rw(None).unwrap();
+    dbtx.put(MAPID.0, b"hello".to_vec(), b"there".to_vec()).unwrap();
+
+    // The reader sees the last committed state.
+    let reader_tx1 = reader_db.transaction_ro().unwrap();
+    assert_eq!(
+        reader_tx1.get(MAPID.0, b"hello").unwrap().as_ref().map(|v| v.as_ref()).unwrap(),
+        b"world"
+    );
+
+    dbtx.commit().unwrap();
+
+    // The existing read transaction still sees its snapshot.
+    assert_eq!(
+        reader_tx1.get(MAPID.0, b"hello").unwrap().as_ref().map(|v| v.as_ref()).unwrap(),
+        b"world"
+    );
+    drop(reader_tx1);
+
+    // A new one sees the new data.
+    let reader_tx2 = reader_db.transaction_ro().unwrap();
+    assert_eq!(
+        reader_tx2.get(MAPID.0, b"hello").unwrap().as_ref().map(|v| v.as_ref()).unwrap(),
+        b"there"
+    );
+}
+
+// Check that failing to start a write transaction because another connection is writing
+// produces a recoverable error.
+#[test]
+fn busy_writer_is_recoverable() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("database.sqlite");
+    let options = Options {
+        busy_timeout: Duration::from_millis(10),
+        busy_retries: 1,
+        ..non_exclusive_wal_options()
+    };
+
+    let mut db1 = Sqlite::new(&db_path).with_options(options.clone()).open(desc(1)).unwrap();
+    let mut db2 = Sqlite::new(&db_path).with_options(options).open(desc(1)).unwrap();
+
+    let mut dbtx1 = db1.transaction_rw(None).unwrap();
+    dbtx1.put(MAPID.0, b"hello".to_vec(), b"world".to_vec()).unwrap();
+
+    let err = match db2.transaction_rw(None) {
+        Ok(_) => panic!("Got Ok while expecting an error"),
+        Err(err) => err,
+    };
+    assert_eq!(
+        err,
+        storage::Error::Recoverable(Recoverable::TemporarilyUnavailable)
+    );
+
+    dbtx1.commit().unwrap();
+
+    // Now the second connection can write.
+    let mut dbtx2 = db2.transaction_rw(None).unwrap();
+    dbtx2.put(MAPID.0, b"hello".to_vec(), b"there".to_vec()).unwrap();
+    dbtx2.commit().unwrap();
+}
@@ -13,8 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use storage_backend_test_suite::prelude::BogusSharedBackend;
-use storage_sqlite::Sqlite;
+use storage_sqlite::{SharedSqlite, Sqlite};
 
 fn main() {
     let test_root = test_utils::test_root!("backend-tests").unwrap();
@@ -31,15 +30,18 @@ fn main() {
                 .join("database.sqlite");
             Sqlite::new(db_file_path).with_options(storage_sqlite::Options {
                 disable_fsync: true,
+                ..Default::default()
             })
         }
     };
 
+    let create_shared_backend = {
+        let create_backend = create_backend.clone();
+        move || SharedSqlite::new(create_backend())
+    };
+
     // Now run the tests
-    let result = storage_backend_test_suite::main(
-        create_backend,
-        None as Option<fn() -> BogusSharedBackend<Sqlite>>,
-    );
+    let result = storage_backend_test_suite::main(create_backend, Some(create_shared_backend));
 
     // Remove the test directory unless there was a failure.
     // In case of failure, it is kept to give us the opportunity to inspect database contents.
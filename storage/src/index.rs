@@ -0,0 +1,243 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secondary indices
+//!
+//! A secondary index maps keys derived from the entries of a primary map back to the keys of
+//! those entries. The index entries live in a separate map whose keys are
+//! `(index_key, primary_key)` pairs and whose values are empty, so all primary keys for the
+//! given index key can be found via prefix iteration
+//! (see [prefix_iter_keys](crate::MapRef::prefix_iter_keys)).
+//!
+//! The indices of a map are declared by implementing [IndexedDbMap] for it. Writes and deletes
+//! done via [TransactionRw::put_indexed] and [TransactionRw::del_indexed] then update the
+//! primary map together with all of its indices within the same transaction, so they are
+//! committed or aborted atomically. [TransactionRw::rebuild_indices] re-creates the indices
+//! from the primary map contents, e.g. when an index is added to a map that already has data.
+//!
+//! # Example
+//!
+//! ```
+//! # use storage::{index::{IndexedDbMap, SecondaryIndex}, MakeMapRef, Storage};
+//! storage::decl_schema! {
+//!     Schema {
+//!         // Primary map: user id -> user name
+//!         Users: Map<u32, String>,
+//!         // Index: user name length -> user id
+//!         UsersByNameLen: Map<(u8, u32), ()>,
+//!     }
+//! }
+//!
+//! struct NameLenIndex;
+//!
+//! impl SecondaryIndex for NameLenIndex {
+//!     type Primary = Users;
+//!     type IndexKey = u8;
+//!     type IndexMap = UsersByNameLen;
+//!
+//!     fn index_keys(_id: &u32, name: &String) -> Vec<u8> {
+//!         vec![name.len() as u8]
+//!     }
+//! }
+//!
+//! impl IndexedDbMap for Users {
+//!     type Indices = (NameLenIndex, ());
+//! }
+//!
+//! let mut store = Storage::<_, Schema>::new(storage_inmemory::InMemory::new()).unwrap();
+//! let mut tx = store.transaction_rw(None).unwrap();
+//! tx.put_indexed::<Users, _, _>(&1, &"foo".to_owned()).unwrap();
+//! tx.put_indexed::<Users, _, _>(&2, &"quux".to_owned()).unwrap();
+//! tx.commit().unwrap();
+//!
+//! let tx = store.transaction_ro().unwrap();
+//! let ids: Vec<_> = tx.get::<UsersByNameLen, _>().prefix_iter_keys(&(3u8,)).unwrap().collect();
+//! assert_eq!(ids, vec![(3, 1)]);
+//! ```
+
+use serialization::{Codec, EncodeLike};
+use storage_core::Backend;
+
+use crate::{
+    schema::{DbMap, HasDbMap, Schema},
+    MakeMapRef, TransactionRw,
+};
+
+/// Describes a secondary index of the `Primary` map
+pub trait SecondaryIndex: 'static {
+    /// The indexed map
+    type Primary: DbMap;
+
+    /// The key the primary entries are looked up by
+    type IndexKey: Codec + EncodeLike;
+
+    /// The map holding the index entries
+    type IndexMap: DbMap<Key = (Self::IndexKey, <Self::Primary as DbMap>::Key), Value = ()>;
+
+    /// Derive the index keys of a primary entry. An entry may have any number of index keys,
+    /// including none. The result must only depend on the entry itself.
+    fn index_keys(
+        key: &<Self::Primary as DbMap>::Key,
+        value: &<Self::Primary as DbMap>::Value,
+    ) -> Vec<Self::IndexKey>;
+}
+
+/// A map with secondary indices
+pub trait IndexedDbMap: DbMap {
+    /// The list of the indices in the form of `(Index1, (Index2, ... ()))`
+    type Indices;
+}
+
+/// A list of secondary indices of the map `M` that are all present in the schema `Sch`.
+///
+/// The `Is` parameter is used to disambiguate the map indices in the schema, similar to
+/// [HasDbMap]; it's always inferred.
+pub trait IndexList<M: DbMap, Sch: Schema, Is> {
+    /// Add the index entries for the given primary entry
+    fn add_entry<B: Backend>(
+        tx: &mut TransactionRw<'_, B, Sch>,
+        key: &M::Key,
+        value: &M::Value,
+    ) -> crate::Result<()>;
+
+    /// Remove the index entries for the given primary entry
+    fn remove_entry<B: Backend>(
+        tx: &mut TransactionRw<'_, B, Sch>,
+        key: &M::Key,
+        value: &M::Value,
+    ) -> crate::Result<()>;
+
+    /// Remove all the index entries
+    fn clear<B: Backend>(tx: &mut TransactionRw<'_, B, Sch>) -> crate::Result<()>;
+}
+
+impl<M: DbMap, Sch: Schema> IndexList<M, Sch, ()> for () {
+    fn add_entry<B: Backend>(
+        _tx: &mut TransactionRw<'_, B, Sch>,
+        _key: &M::Key,
+        _value: &M::Value,
+    ) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn remove_entry<B: Backend>(
+        _tx: &mut TransactionRw<'_, B, Sch>,
+        _key: &M::Key,
+        _value: &M::Value,
+    ) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn clear<B: Backend>(_tx: &mut TransactionRw<'_, B, Sch>) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+impl<M, Sch, Head, Tail, I, ITail> IndexList<M, Sch, (I, ITail)> for (Head, Tail)
+where
+    M: DbMap,
+    M::Key: EncodeLike,
+    Sch: HasDbMap<Head::IndexMap, I>,
+    Head: SecondaryIndex<Primary = M>,
+    Tail: IndexList<M, Sch, ITail>,
+{
+    fn add_entry<B: Backend>(
+        tx: &mut TransactionRw<'_, B, Sch>,
+        key: &M::Key,
+        value: &M::Value,
+    ) -> crate::Result<()> {
+        let mut index_map = tx.get_mut::<Head::IndexMap, I>();
+        for index_key in Head::index_keys(key, value) {
+            index_map.put((index_key, key), ())?;
+        }
+        Tail::add_entry(tx, key, value)
+    }
+
+    fn remove_entry<B: Backend>(
+        tx: &mut TransactionRw<'_, B, Sch>,
+        key: &M::Key,
+        value: &M::Value,
+    ) -> crate::Result<()> {
+        let mut index_map = tx.get_mut::<Head::IndexMap, I>();
+        for index_key in Head::index_keys(key, value) {
+            index_map.del((index_key, key))?;
+        }
+        Tail::remove_entry(tx, key, value)
+    }
+
+    fn clear<B: Backend>(tx: &mut TransactionRw<'_, B, Sch>) -> crate::Result<()> {
+        let index_entries =
+            tx.get::<Head::IndexMap, I>().prefix_iter_keys(&())?.collect::<Vec<_>>();
+        let mut index_map = tx.get_mut::<Head::IndexMap, I>();
+        for (index_key, key) in &index_entries {
+            index_map.del((index_key, key))?;
+        }
+        Tail::clear(tx)
+    }
+}
+
+impl<B: Backend, Sch: Schema> TransactionRw<'_, B, Sch> {
+    /// Put a new value into the indexed map `M` and update its indices accordingly.
+    /// Overwrites the previous value.
+    pub fn put_indexed<M: IndexedDbMap, I, Is>(
+        &mut self,
+        key: &M::Key,
+        value: &M::Value,
+    ) -> crate::Result<()>
+    where
+        M::Key: EncodeLike,
+        M::Value: EncodeLike,
+        Sch: HasDbMap<M, I>,
+        M::Indices: IndexList<M, Sch, Is>,
+    {
+        let old_value = self.get::<M, I>().get(key)?.map(|value| value.decode());
+        if let Some(old_value) = old_value {
+            M::Indices::remove_entry(self, key, &old_value)?;
+        }
+        M::Indices::add_entry(self, key, value)?;
+        self.get_mut::<M, I>().put(key, value)
+    }
+
+    /// Remove the value associated with the given key from the indexed map `M`, together
+    /// with its index entries.
+    pub fn del_indexed<M: IndexedDbMap, I, Is>(&mut self, key: &M::Key) -> crate::Result<()>
+    where
+        M::Key: EncodeLike,
+        Sch: HasDbMap<M, I>,
+        M::Indices: IndexList<M, Sch, Is>,
+    {
+        let old_value = self.get::<M, I>().get(key)?.map(|value| value.decode());
+        if let Some(old_value) = old_value {
+            M::Indices::remove_entry(self, key, &old_value)?;
+            self.get_mut::<M, I>().del(key)?;
+        }
+        Ok(())
+    }
+
+    /// Re-create all the indices of the map `M` from its contents.
+    pub fn rebuild_indices<M: IndexedDbMap, I, Is>(&mut self) -> crate::Result<()>
+    where
+        Sch: HasDbMap<M, I>,
+        M::Indices: IndexList<M, Sch, Is>,
+    {
+        M::Indices::clear(self)?;
+
+        let entries = self.get::<M, I>().prefix_iter_decoded(&())?.collect::<Vec<_>>();
+        for (key, value) in &entries {
+            M::Indices::add_entry(self, key, value)?;
+        }
+        Ok(())
+    }
+}
@@ -94,6 +94,7 @@
 //! ```
 
 mod database;
+pub mod index;
 mod order_preserving_value;
 pub mod schema;
 
@@ -201,3 +201,91 @@ fn iteration() {
         }
     });
 }
+
+decl_schema! {
+    // Schema with a secondary index
+    Indexed {
+        Values: Map<u32, String>,
+        ValuesByChar: Map<(u8, u32), ()>,
+    }
+}
+
+struct CharIndex;
+
+impl index::SecondaryIndex for CharIndex {
+    type Primary = Values;
+    type IndexKey = u8;
+    type IndexMap = ValuesByChar;
+
+    fn index_keys(_key: &u32, value: &String) -> Vec<u8> {
+        let mut chars = value.as_bytes().to_vec();
+        chars.sort();
+        chars.dedup();
+        chars
+    }
+}
+
+impl index::IndexedDbMap for Values {
+    type Indices = (CharIndex, ());
+}
+
+fn keys_by_char<B: Backend>(tx: &TransactionRo<'_, B, Indexed>, c: u8) -> Vec<u32> {
+    tx.get::<ValuesByChar, _>()
+        .prefix_iter_keys(&(c,))
+        .unwrap()
+        .map(|(_, key)| key)
+        .collect()
+}
+
+#[test]
+fn secondary_index() {
+    utils::concurrency::model(|| {
+        let store = Storage::<_, Indexed>::new(storage_inmemory::InMemory::new()).unwrap();
+
+        let mut dbtx = store.transaction_rw(None).unwrap();
+        dbtx.put_indexed::<Values, _, _>(&1, &"abc".to_owned()).unwrap();
+        dbtx.put_indexed::<Values, _, _>(&2, &"bcd".to_owned()).unwrap();
+        dbtx.put_indexed::<Values, _, _>(&3, &"xyz".to_owned()).unwrap();
+        dbtx.commit().unwrap();
+
+        let dbtx = store.transaction_ro().unwrap();
+        assert_eq!(keys_by_char(&dbtx, b'a'), vec![1]);
+        assert_eq!(keys_by_char(&dbtx, b'b'), vec![1, 2]);
+        assert_eq!(keys_by_char(&dbtx, b'x'), vec![3]);
+        dbtx.close();
+
+        // Overwriting and deleting update the index
+        let mut dbtx = store.transaction_rw(None).unwrap();
+        dbtx.put_indexed::<Values, _, _>(&1, &"xaa".to_owned()).unwrap();
+        dbtx.del_indexed::<Values, _, _>(&2).unwrap();
+        dbtx.commit().unwrap();
+
+        let dbtx = store.transaction_ro().unwrap();
+        assert_eq!(keys_by_char(&dbtx, b'a'), vec![1]);
+        assert_eq!(keys_by_char(&dbtx, b'b'), Vec::<u32>::new());
+        assert_eq!(keys_by_char(&dbtx, b'x'), vec![1, 3]);
+        dbtx.close();
+
+        // Aborted changes don't touch the index
+        let mut dbtx = store.transaction_rw(None).unwrap();
+        dbtx.del_indexed::<Values, _, _>(&3).unwrap();
+        dbtx.abort();
+
+        let dbtx = store.transaction_ro().unwrap();
+        assert_eq!(keys_by_char(&dbtx, b'x'), vec![1, 3]);
+        dbtx.close();
+
+        // Entries written without maintaining the index are picked up by a rebuild
+        let mut dbtx = store.transaction_rw(None).unwrap();
+        dbtx.get_mut::<Values, _>().put(4u32, "b".to_owned()).unwrap();
+        dbtx.get_mut::<ValuesByChar, _>().put((b'q', 42u32), ()).unwrap();
+        dbtx.rebuild_indices::<Values, _, _>().unwrap();
+        dbtx.commit().unwrap();
+
+        let dbtx = store.transaction_ro().unwrap();
+        assert_eq!(keys_by_char(&dbtx, b'b'), vec![4]);
+        assert_eq!(keys_by_char(&dbtx, b'q'), Vec::<u32>::new());
+        assert_eq!(keys_by_char(&dbtx, b'x'), vec![1, 3]);
+        dbtx.close();
+    });
+}
@@ -0,0 +1,128 @@
+// Copyright (c) 2022-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subsystem health reporting
+//!
+//! Each subsystem task publishes a health report whenever it processes an event and, if the
+//! `time` feature is enabled, periodically while idle. A subsystem that is stuck processing
+//! a call stops reporting, which shows up as a stale last activity time and a growing queue.
+
+use std::time::{Duration, SystemTime};
+
+use utils::sync::{
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+/// How often an idle subsystem refreshes its health report.
+pub const HEALTH_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Snapshot of the health of a single subsystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubsystemHealth {
+    /// Full name of the subsystem
+    pub name: String,
+
+    /// Whether the subsystem task is still running
+    pub alive: bool,
+
+    /// Time of the last health report as seconds since the Unix epoch, `None` if the subsystem
+    /// hasn't started yet
+    pub last_activity: Option<u64>,
+
+    /// Number of calls waiting to be processed by the subsystem at the time of the last report
+    pub queue_depth: usize,
+}
+
+/// Health state shared between the subsystem task and the [HealthMonitor]
+pub(crate) struct HealthState {
+    alive: AtomicBool,
+    last_activity: AtomicU64,
+    queue_depth: AtomicUsize,
+}
+
+impl HealthState {
+    pub(crate) fn new() -> Self {
+        Self {
+            alive: AtomicBool::new(false),
+            last_activity: AtomicU64::new(0),
+            queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// Publish a fresh health report
+    pub(crate) fn report(&self, queue_depth: usize) {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.queue_depth.store(queue_depth, Ordering::Relaxed);
+        self.last_activity.store(now, Ordering::Relaxed);
+        self.alive.store(true, Ordering::Release);
+    }
+
+    /// Mark the subsystem as terminated
+    pub(crate) fn set_terminated(&self) {
+        self.alive.store(false, Ordering::Release);
+    }
+
+    fn snapshot(&self, name: &str) -> SubsystemHealth {
+        let alive = self.alive.load(Ordering::Acquire);
+        let last_activity = match self.last_activity.load(Ordering::Relaxed) {
+            0 => None,
+            time => Some(time),
+        };
+        SubsystemHealth {
+            name: name.to_owned(),
+            alive,
+            last_activity,
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Collects health reports of the subsystems registered in a [crate::Manager].
+///
+/// Subsystems added to the manager after the monitor has been created are included too.
+#[derive(Clone)]
+pub struct HealthMonitor {
+    subsystems: Arc<Mutex<Vec<(String, Arc<HealthState>)>>>,
+}
+
+impl HealthMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            subsystems: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub(crate) fn register(&self, full_name: String) -> Arc<HealthState> {
+        let state = Arc::new(HealthState::new());
+        self.subsystems
+            .lock()
+            .expect("poisoned mutex")
+            .push((full_name, Arc::clone(&state)));
+        state
+    }
+
+    /// Get the latest health report of each subsystem, in the order of registration.
+    pub fn subsystem_health(&self) -> Vec<SubsystemHealth> {
+        self.subsystems
+            .lock()
+            .expect("poisoned mutex")
+            .iter()
+            .map(|(name, state)| state.snapshot(name))
+            .collect()
+    }
+}
@@ -32,8 +32,14 @@
 //! 2. The main task broadcasts the shutdown request to all subsystems. The subsystems react to the
 //!    request by shutting themselves down.
 //! 3. The main task waits for all subsystems to terminate.
+//!
+//! ## Health reporting
+//!
+//! Each subsystem task keeps a health report (liveness, last activity time and call queue depth)
+//! up to date. The reports can be collected using a [HealthMonitor] obtained from the [Manager].
 
 mod calls;
+mod health;
 mod manager;
 mod subsystem;
 mod task;
@@ -43,6 +49,7 @@ pub mod error;
 
 pub use crate::{
     calls::{blocking, CallResponse, CallResult, Handle, SubmitOnlyHandle},
+    health::{HealthMonitor, SubsystemHealth},
     manager::{Manager, ManagerConfig, ManagerJoinHandle, ShutdownTrigger},
     subsystem::Subsystem,
 };
@@ -24,7 +24,7 @@ use tokio::{
 use logging::log;
 use utils::{const_value::ConstValue, shallow_clone::ShallowClone};
 
-use crate::{task, Handle, ManagerConfig, SubmitOnlyHandle, Subsystem};
+use crate::{task, Handle, HealthMonitor, ManagerConfig, SubmitOnlyHandle, Subsystem};
 
 use super::shutdown_signal::shutdown_signal;
 
@@ -46,6 +46,9 @@ pub struct Manager {
 
     // List of subsystem tasks
     subsystems: Vec<SubsystemData<BoxFuture<'static, ()>>>,
+
+    // Health reports of the subsystems
+    health_monitor: HealthMonitor,
 }
 
 impl Manager {
@@ -65,6 +68,7 @@ impl Manager {
             shutting_down_tx,
             shutting_down_rx,
             subsystems,
+            health_monitor: HealthMonitor::new(),
         }
     }
 
@@ -98,6 +102,8 @@ impl Manager {
 
         log::info!("Registering subsystem {full_name}");
 
+        let health = self.health_monitor.register(full_name.clone());
+
         let task = Box::pin(task::subsystem(
             full_name.clone(),
             subsys_init,
@@ -105,6 +111,7 @@ impl Manager {
             action_rx,
             shutdown_rx,
             self.shutting_down_tx.clone(),
+            health,
         ));
 
         self.subsystems.push(SubsystemData {
@@ -139,6 +146,11 @@ impl Manager {
         ShutdownTrigger::new(&self.shutting_down_tx)
     }
 
+    /// Create a monitor that can be used to collect the health reports of the subsystems
+    pub fn make_health_monitor(&self) -> HealthMonitor {
+        self.health_monitor.clone()
+    }
+
     /// Run the application main task.
     ///
     /// Completes when all the subsystems are fully shut down.
@@ -24,7 +24,7 @@ use tracing::Instrument;
 use logging::log;
 use utils::{once_destructor::OnceDestructor, sync::Arc};
 
-use crate::{calls::Action, SubmitOnlyHandle, Subsystem};
+use crate::{calls::Action, health::HealthState, SubmitOnlyHandle, Subsystem};
 
 /// Handle a task completion result
 pub fn handle_result(full_name: &str, task_type: &str, res: Result<(), tokio::task::JoinError>) {
@@ -45,6 +45,7 @@ pub async fn subsystem<S, IF, SF, E>(
     mut action_rx: mpsc::UnboundedReceiver<Action<S::Interface>>,
     mut shutdown_rx: oneshot::Receiver<()>,
     shutting_down_tx: mpsc::UnboundedSender<()>,
+    health: Arc<HealthState>,
 ) where
     IF: FnOnce(SubmitOnlyHandle<S::Interface>) -> SF + Send + 'static,
     SF: std::future::IntoFuture<Output = Result<S, E>> + Send,
@@ -57,7 +58,9 @@ pub async fn subsystem<S, IF, SF, E>(
     // Make sure that we send the shutdown signal even in case of a panic.
     let _shutdown_sender = OnceDestructor::new({
         let full_name = &full_name;
+        let health = Arc::clone(&health);
         move || {
+            health.set_terminated();
             let _ = shutting_down_tx.send(());
             log::info!("Subsystem {full_name} terminated");
         }
@@ -82,7 +85,19 @@ pub async fn subsystem<S, IF, SF, E>(
         }
     };
 
+    // Set up a closure to wait until the next periodic health report is due.
+    let health_report_signal = || async {
+        cfg_if::cfg_if! {
+            if #[cfg(all(feature = "time", not(loom)))] {
+                tokio::time::sleep(crate::health::HEALTH_REPORT_INTERVAL).await
+            } else {
+                std::future::pending::<()>().await
+            }
+        }
+    };
+
     log::info!("Subsystem {full_name} started");
+    health.report(action_rx.len());
 
     // Main event loop
     loop {
@@ -122,7 +137,12 @@ pub async fn subsystem<S, IF, SF, E>(
             () = background_work_signal() => {
                 subsys.write().await.perform_background_work_unit();
             }
+
+            // Nothing happened for a while, refresh the health report anyway.
+            () = health_report_signal() => {}
         }
+
+        health.report(action_rx.len());
     }
 
     while let Some(task_result) = worker_tasks.join_next().await {
@@ -0,0 +1,60 @@
+// Copyright (c) 2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod helpers;
+mod sample_subsystems;
+
+use sample_subsystems::{Counter, Substringer};
+
+#[test]
+fn health_reports() {
+    let runtime = helpers::init_test_runtime();
+    utils::concurrency::model(move || {
+        runtime.block_on(async {
+            let mut app = subsystem::Manager::new("app");
+            let shutdown = app.make_shutdown_trigger();
+            let monitor = app.make_health_monitor();
+
+            let _substr = app.add_direct_subsystem("substr", Substringer::new("abc".into()));
+            let counter = app.add_direct_subsystem("counter", Counter::new());
+
+            // Nothing is running yet
+            let health = monitor.subsystem_health();
+            let names: Vec<_> = health.iter().map(|h| h.name.as_str()).collect();
+            assert_eq!(names, ["app/substr", "app/counter"]);
+            assert!(health.iter().all(|h| !h.alive && h.last_activity.is_none()));
+
+            let monitor_for_task = monitor.clone();
+            tokio::task::spawn(async move {
+                // Once the call has been processed, the subsystem has reported at least once
+                assert_eq!(counter.call_mut(|c| c.add_and_get(1)).await.unwrap(), 1);
+
+                let health = monitor_for_task.subsystem_health();
+                let counter_health = health.iter().find(|h| h.name == "app/counter").unwrap();
+                assert!(counter_health.alive);
+                assert!(counter_health.last_activity.is_some());
+
+                shutdown.initiate();
+            });
+
+            app.main().await;
+
+            // All subsystems are down after the manager terminates
+            let health = monitor.subsystem_health();
+            assert_eq!(health.len(), 2);
+            assert!(health.iter().all(|h| !h.alive));
+        })
+    })
+}
@@ -19,6 +19,8 @@ pub mod random;
 pub mod test_dir;
 pub mod threading;
 pub mod token_utils;
+#[cfg(feature = "simulation")]
+mod virtual_time_getter;
 
 use std::collections::BTreeMap;
 
@@ -29,6 +31,8 @@ use randomness::distributions::uniform::SampleRange;
 use randomness::Rng;
 
 pub use basic_test_time_getter::BasicTestTimeGetter;
+#[cfg(feature = "simulation")]
+pub use virtual_time_getter::VirtualTimeGetter;
 
 /// Assert that the encoded object matches the expected hex string.
 pub fn assert_encoded_eq<E: serialization::Encode>(to_encode: &E, expected_hex: &str) {
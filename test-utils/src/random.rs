@@ -20,20 +20,38 @@ use randomness::SliceRandom as _;
 
 pub use randomness::{self, seq::IteratorRandom, CryptoRng, Rng, RngCore, SeedableRng};
 
+/// If set, tests that obtain their seed via [Seed::from_entropy] use this value instead,
+/// which makes it possible to reproduce a failure with the seed printed by the failed test.
+///
+/// Only the randomness derived from the seed is reproduced, e.g. the one used by the chainstate
+/// test framework and the block builders. The node code itself (such as the p2p peer selection
+/// or the mempool work queue) still uses `make_pseudo_rng` and isn't affected by the seed.
+pub const SEED_ENV_VAR: &str = "ML_TEST_SEED";
+
 #[derive(Debug, Copy, Clone)]
 pub struct Seed(pub u64);
 
 impl Seed {
     pub fn from_entropy() -> Self {
-        Seed(randomness::make_true_rng().gen::<u64>())
+        Self::from_env().unwrap_or_else(|| Seed(randomness::make_true_rng().gen::<u64>()))
     }
 
     pub fn from_entropy_and_print(test_name: &str) -> Self {
-        let result = Seed(randomness::make_true_rng().gen::<u64>());
+        let result = Self::from_entropy();
         result.print_with_decoration(test_name);
         result
     }
 
+    /// The seed specified via [SEED_ENV_VAR], if any.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(SEED_ENV_VAR).ok()?;
+        let seed = value
+            .trim()
+            .parse()
+            .unwrap_or_else(|err| panic!("Invalid {SEED_ENV_VAR} value '{value}': {err}"));
+        Some(seed)
+    }
+
     pub fn from_u64(v: u64) -> Self {
         Seed(v)
     }
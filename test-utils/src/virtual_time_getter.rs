@@ -0,0 +1,104 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A time getter driven by the tokio clock, for the deterministic simulation of time-dependent
+//! behavior.
+//!
+//! The intended usage is to run the test via `#[tokio::test(start_paused = true)]`, i.e. on a
+//! single-threaded executor with the tokio clock paused, and to pass
+//! [VirtualTimeGetter::get_time_getter] to the tested subsystems. In this mode the clock
+//! automatically jumps to the next pending timer once all the tasks are idle, so the timestamps
+//! produced by the time getter always agree with tokio's sleeps, intervals and timeouts,
+//! and waiting for minutes of timeouts takes milliseconds of real time. Since no real time is
+//! involved, the outcome doesn't depend on how loaded the machine running the test is.
+
+use std::{sync::Arc, time::Duration};
+
+use common::{
+    primitives::time::{self, Time},
+    time_getter::{TimeGetter, TimeGetterFn},
+};
+
+#[derive(Clone)]
+pub struct VirtualTimeGetter {
+    start_time: Time,
+    start_instant: tokio::time::Instant,
+}
+
+impl VirtualTimeGetter {
+    /// Create the time getter starting at the current system time.
+    ///
+    /// Must be called from within the tokio runtime whose clock is going to drive it.
+    pub fn new() -> Self {
+        Self::new_at(time::get_time())
+    }
+
+    /// Create the time getter starting at the specified time.
+    pub fn new_at(start_time: Time) -> Self {
+        Self {
+            start_time,
+            start_instant: tokio::time::Instant::now(),
+        }
+    }
+
+    pub fn get_time_getter(&self) -> TimeGetter {
+        TimeGetter::new(Arc::new(self.clone()))
+    }
+
+    /// Advance the tokio clock, firing the timers that become due.
+    ///
+    /// Panics if the clock is not paused.
+    pub async fn advance_time(&self, duration: Duration) {
+        tokio::time::advance(duration).await
+    }
+}
+
+impl Default for VirtualTimeGetter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeGetterFn for VirtualTimeGetter {
+    fn get_time(&self) -> Time {
+        let elapsed = tokio::time::Instant::now().duration_since(self.start_instant);
+        (self.start_time + elapsed).expect("time overflow")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn follows_tokio_clock() {
+        let start_time = Time::from_secs_since_epoch(1_000_000);
+        let time_getter = VirtualTimeGetter::new_at(start_time).get_time_getter();
+        assert_eq!(time_getter.get_time(), start_time);
+
+        VirtualTimeGetter::new().advance_time(Duration::from_secs(10)).await;
+        assert_eq!(
+            time_getter.get_time(),
+            Time::from_secs_since_epoch(1_000_010)
+        );
+
+        // The clock auto-advances when the runtime is idle
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+        assert_eq!(
+            time_getter.get_time(),
+            Time::from_secs_since_epoch(1_003_610)
+        );
+    }
+}
@@ -15,14 +15,20 @@
 
 //! Broadcaster is a reliable version of [tokio::sync::broadcast].
 
-use tokio::sync::mpsc;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tokio::sync::{mpsc, Notify};
 
 /// A reliable version of [tokio::sync::broadcast], sender part.
 ///
 /// It does not have capacity limits so no messages are lost. It is achieved by using unbounded
-/// channels.
+/// channels. Subscribers that may not keep up with the sender can opt in to a bounded queue
+/// instead, see [Broadcaster::subscribe_bounded].
 pub struct Broadcaster<T> {
-    senders: Vec<mpsc::UnboundedSender<T>>,
+    senders: Vec<Sender<T>>,
     auto_purge_ticks: u32,
 }
 
@@ -46,8 +52,32 @@ impl<T> Broadcaster<T> {
 
     /// Add a new subscription that emits the events to given channel
     pub fn subscribe_using(&mut self, tx: mpsc::UnboundedSender<T>) {
-        self.senders.push(tx);
+        self.senders.push(Sender::Unbounded(tx));
+        self.auto_purge();
+    }
+
+    /// Add a new subscriber that queues at most `capacity` values, the `overflow_policy`
+    /// determines what happens to the values that don't fit.
+    pub fn subscribe_bounded(
+        &mut self,
+        capacity: usize,
+        overflow_policy: OverflowPolicy,
+    ) -> BoundedReceiver<T> {
+        assert!(capacity > 0, "the queue capacity must be positive");
+
+        let queue = Arc::new(BoundedQueue {
+            state: Mutex::new(BoundedQueueState {
+                values: VecDeque::new(),
+                capacity,
+                overflow_policy,
+                closed: false,
+                overflowed: false,
+            }),
+            notify: Notify::new(),
+        });
+        self.senders.push(Sender::Bounded(BoundedSender(Arc::clone(&queue))));
         self.auto_purge();
+        BoundedReceiver(queue)
     }
 
     /// Get the number of subscribers
@@ -80,7 +110,7 @@ impl<T> Broadcaster<T> {
         // Since the broadcast has to iterate over the whole sender list, we also purge the dead
         // connections as we go and reset the purge tick counter.
         self.auto_purge_ticks = 0;
-        self.senders.retain(|sender| sender.send(value.clone()).is_ok());
+        self.senders.retain(|sender| sender.send(value.clone()));
     }
 }
 
@@ -99,6 +129,131 @@ impl<T> Receiver<T> {
     }
 }
 
+/// What a bounded subscriber does with a new value when its queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued value to make room for the new one
+    DropOldest,
+    /// Discard the new value
+    DropNewest,
+    /// Discard all the queued values and close the subscription
+    Disconnect,
+}
+
+enum Sender<T> {
+    Unbounded(mpsc::UnboundedSender<T>),
+    Bounded(BoundedSender<T>),
+}
+
+impl<T> Sender<T> {
+    /// Send the value, return false if the subscriber is gone
+    fn send(&self, value: T) -> bool {
+        match self {
+            Sender::Unbounded(tx) => tx.send(value).is_ok(),
+            Sender::Bounded(tx) => tx.send(value),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        match self {
+            Sender::Unbounded(tx) => tx.is_closed(),
+            Sender::Bounded(tx) => tx.is_closed(),
+        }
+    }
+}
+
+struct BoundedQueue<T> {
+    state: Mutex<BoundedQueueState<T>>,
+    notify: Notify,
+}
+
+struct BoundedQueueState<T> {
+    values: VecDeque<T>,
+    capacity: usize,
+    overflow_policy: OverflowPolicy,
+    closed: bool,
+    overflowed: bool,
+}
+
+impl<T> BoundedQueue<T> {
+    fn lock(&self) -> MutexGuard<'_, BoundedQueueState<T>> {
+        self.state.lock().expect("poisoned mutex")
+    }
+}
+
+struct BoundedSender<T>(Arc<BoundedQueue<T>>);
+
+impl<T> BoundedSender<T> {
+    fn send(&self, value: T) -> bool {
+        if self.is_closed() {
+            return false;
+        }
+
+        let mut state = self.0.lock();
+        if state.values.len() >= state.capacity {
+            match state.overflow_policy {
+                OverflowPolicy::DropOldest => {
+                    state.values.pop_front();
+                }
+                OverflowPolicy::DropNewest => return true,
+                OverflowPolicy::Disconnect => {
+                    state.values.clear();
+                    state.closed = true;
+                    state.overflowed = true;
+                    drop(state);
+                    self.0.notify.notify_one();
+                    return false;
+                }
+            }
+        }
+        state.values.push_back(value);
+        drop(state);
+
+        self.0.notify.notify_one();
+        true
+    }
+
+    /// The subscriber is gone if the receiver has been dropped
+    fn is_closed(&self) -> bool {
+        Arc::strong_count(&self.0) == 1
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        self.0.lock().closed = true;
+        self.0.notify.notify_one();
+    }
+}
+
+/// Receiver of a bounded subscription
+pub struct BoundedReceiver<T>(Arc<BoundedQueue<T>>);
+
+impl<T> BoundedReceiver<T> {
+    /// Receive a value, `None` means that the broadcaster is gone or that the subscription has
+    /// been closed due to an overflow.
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            {
+                let mut state = self.0.lock();
+                if let Some(value) = state.values.pop_front() {
+                    return Some(value);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+
+            self.0.notify.notified().await;
+        }
+    }
+
+    /// Whether the subscription has been closed due to an overflow
+    pub fn is_overflowed(&self) -> bool {
+        self.0.lock().overflowed
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +297,54 @@ mod tests {
 
         assert!(bcast.num_subscribers() < num_inserts as usize);
     }
+
+    #[tokio::test]
+    async fn bounded() {
+        let mut bcast = Broadcaster::<u32>::new();
+        let mut drop_oldest = bcast.subscribe_bounded(3, OverflowPolicy::DropOldest);
+        let mut drop_newest = bcast.subscribe_bounded(3, OverflowPolicy::DropNewest);
+        let mut disconnect = bcast.subscribe_bounded(3, OverflowPolicy::Disconnect);
+        let mut unbounded = bcast.subscribe();
+
+        (1..=3).for_each(|x| bcast.broadcast(&x));
+        assert_eq!(drop_oldest.recv().await, Some(1));
+        assert_eq!(drop_newest.recv().await, Some(1));
+        assert_eq!(disconnect.recv().await, Some(1));
+        assert_eq!(bcast.num_subscribers(), 4);
+
+        (4..=6).for_each(|x| bcast.broadcast(&x));
+        assert_eq!(bcast.num_subscribers(), 3);
+        std::mem::drop(bcast);
+
+        for expected in [4, 5, 6] {
+            assert_eq!(drop_oldest.recv().await, Some(expected));
+        }
+        assert_eq!(drop_oldest.recv().await, None);
+        assert!(!drop_oldest.is_overflowed());
+
+        for expected in [2, 3, 4] {
+            assert_eq!(drop_newest.recv().await, Some(expected));
+        }
+        assert_eq!(drop_newest.recv().await, None);
+        assert!(!drop_newest.is_overflowed());
+
+        assert_eq!(disconnect.recv().await, None);
+        assert!(disconnect.is_overflowed());
+
+        for expected in 1..=6 {
+            assert_eq!(unbounded.recv().await, Some(expected));
+        }
+        assert_eq!(unbounded.recv().await, None);
+    }
+
+    #[test]
+    fn bounded_purging() {
+        let mut bcast = Broadcaster::<()>::new();
+        let subscriber = bcast.subscribe_bounded(1, OverflowPolicy::DropOldest);
+        assert_eq!(bcast.num_subscribers(), 1);
+
+        std::mem::drop(subscriber);
+        bcast.purge();
+        assert_eq!(bcast.num_subscribers(), 0);
+    }
 }
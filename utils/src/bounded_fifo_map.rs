@@ -0,0 +1,137 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+/// A map that holds at most `max_len` entries; when it's full, the entries that were inserted
+/// first are evicted.
+///
+/// Updating an existing entry doesn't change its position in the eviction order.
+#[derive(Debug, Clone)]
+pub struct BoundedFifoMap<K, V> {
+    entries: BTreeMap<K, (u64, V)>,
+    insertion_order: BTreeMap<u64, K>,
+    next_seq: u64,
+    max_len: usize,
+}
+
+impl<K: Ord + Clone, V> BoundedFifoMap<K, V> {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            insertion_order: BTreeMap::new(),
+            next_seq: 0,
+            max_len,
+        }
+    }
+
+    /// A map without a limit on the number of entries, for the cases where the caller evicts
+    /// the oldest entries itself, e.g. based on their memory usage.
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key).map(|(_, value)| value)
+    }
+
+    /// Insert or update the entry, returning the old value if there was one.
+    ///
+    /// If a new entry makes the map exceed its limit, the oldest entries are evicted.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some((_, old_value)) = self.entries.get_mut(&key) {
+            return Some(std::mem::replace(old_value, value));
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.insertion_order.insert(seq, key.clone());
+        self.entries.insert(key, (seq, value));
+
+        while self.entries.len() > self.max_len {
+            self.pop_oldest();
+        }
+
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (seq, value) = self.entries.remove(key)?;
+        self.insertion_order.remove(&seq);
+        Some(value)
+    }
+
+    /// Remove and return the entry that was inserted first.
+    pub fn pop_oldest(&mut self) -> Option<(K, V)> {
+        let (_, key) = self.insertion_order.pop_first()?;
+        let (_, value) = self.entries.remove(&key).expect("entry must be present");
+        Some((key, value))
+    }
+
+    /// Iterate over the entries in the order of their keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries.iter().map(|(key, (_, value))| (key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_evicted_first() {
+        let mut map = BoundedFifoMap::new(3);
+        for key in [5, 1, 3] {
+            assert_eq!(map.insert(key, key * 10), None);
+        }
+
+        // Updating an entry doesn't make it newer
+        assert_eq!(map.insert(5, 55), Some(50));
+        assert_eq!(map.len(), 3);
+
+        map.insert(2, 20);
+        assert_eq!(map.len(), 3);
+        assert!(!map.contains_key(&5));
+
+        assert_eq!(map.remove(&3), Some(30));
+        map.insert(4, 40);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [(&1, &10), (&2, &20), (&4, &40)]
+        );
+
+        assert_eq!(map.pop_oldest(), Some((1, 10)));
+        assert_eq!(map.pop_oldest(), Some((2, 20)));
+        assert_eq!(map.pop_oldest(), Some((4, 40)));
+        assert_eq!(map.pop_oldest(), None);
+        assert!(map.is_empty());
+    }
+}
@@ -17,6 +17,7 @@ pub mod array_2d;
 pub mod atomics;
 pub mod blockuntilzero;
 pub mod bloom_filters;
+pub mod bounded_fifo_map;
 pub mod clap_utils;
 pub mod concatln;
 pub mod config_setting;
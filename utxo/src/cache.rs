@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use crate::{
+    clean_entries::CleanEntries,
     utxo_entry::{IsDirty, IsFresh, UtxoEntry},
     Error, FlushableUtxoView, Utxo, UtxoSource, UtxosBlockRewardUndo, UtxosTxUndo,
     UtxosTxUndoWithSources, UtxosView,
@@ -31,20 +32,48 @@ use std::{
     fmt::{Debug, Formatter},
 };
 
+/// The approximate amount of memory taken by a single cache entry.
+pub const UTXO_CACHE_ENTRY_MEMORY_USAGE: usize =
+    std::mem::size_of::<UtxoOutPoint>() + std::mem::size_of::<UtxoEntry>();
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ConsumedUtxoCache {
     pub(crate) container: BTreeMap<UtxoOutPoint, UtxoEntry>,
     pub(crate) best_block: Id<GenBlock>,
 }
 
+/// Memory limits of a `UtxosCache`.
+///
+/// Only the clean entries can be evicted, the modified ones stay in the cache until it's flushed,
+/// so the limit may still be exceeded by them. The chainstate only sets the limits on the
+/// verifiers that check a chain of blocks in memory (e.g. a reorg), which can't be flushed to
+/// the db; the verifiers used to connect blocks, including during the initial block download,
+/// are flushed after every block and don't need a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtxosCacheLimits {
+    /// When the memory usage of the cache exceeds this value, clean entries (i.e. the ones that
+    /// were only read from the parent and haven't been modified) are evicted.
+    pub max_memory_usage: usize,
+}
+
+/// Statistics of a `UtxosCache`, meant for monitoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UtxosCacheCounters {
+    /// The number of entries loaded from the parent
+    pub loaded_entries: u64,
+    /// The number of clean entries evicted due to the memory limit
+    pub evicted_entries: u64,
+}
+
 pub struct UtxosCache<P> {
     parent: P,
     current_block_hash: Id<GenBlock>,
     // pub(crate) visibility is required for tests that are in a different mod
     pub(crate) utxos: BTreeMap<UtxoOutPoint, UtxoEntry>,
-    // TODO: calculate memory usage (mintlayer/mintlayer-core#354)
-    #[allow(dead_code)]
-    memory_usage: usize,
+    // Outpoints of the entries that are neither fresh nor dirty, i.e. can be evicted at any time
+    clean_entries: CleanEntries,
+    limits: Option<UtxosCacheLimits>,
+    counters: UtxosCacheCounters,
 }
 
 impl<P: UtxosView> UtxosCache<P> {
@@ -53,6 +82,7 @@ impl<P: UtxosView> UtxosCache<P> {
     // when the utxo was actually retrieved from the parent.
     fn fetch_utxo_entry(&mut self, outpoint: &UtxoOutPoint) -> Result<Option<UtxoEntry>, Error> {
         if let Some(res) = self.utxos.get(outpoint) {
+            self.clean_entries.touch(outpoint);
             return Ok(Some(res.clone()));
         }
 
@@ -66,7 +96,10 @@ impl<P: UtxosView> UtxosCache<P> {
             .map_err(|_| Error::ViewRead)?
             .map(|utxo| UtxoEntry::new(Some(utxo), IsFresh::No, IsDirty::No));
         if let Some(entry) = &entry {
-            self.utxos.insert(outpoint.clone(), entry.clone());
+            self.counters.loaded_entries += 1;
+            // Make room for the new entry first, so that it's not evicted immediately
+            self.evict_clean_entries(UTXO_CACHE_ENTRY_MEMORY_USAGE);
+            self.insert_entry(outpoint.clone(), entry.clone());
         }
         Ok(entry)
     }
@@ -77,19 +110,82 @@ impl<P: UtxosView> UtxosCache<P> {
             parent,
             current_block_hash,
             utxos: BTreeMap::new(),
-            memory_usage: 0,
+            clean_entries: CleanEntries::default(),
+            limits: None,
+            counters: UtxosCacheCounters::default(),
         })
     }
 
     pub fn from_data(parent: P, utxos: ConsumedUtxoCache) -> Result<Self, P::Error> {
+        let mut clean_entries = CleanEntries::default();
+        utxos
+            .container
+            .iter()
+            .filter(|(_, entry)| is_clean(entry))
+            .for_each(|(outpoint, _)| clean_entries.insert(outpoint.clone()));
         Ok(UtxosCache {
             parent,
             current_block_hash: utxos.best_block,
             utxos: utxos.container,
-            memory_usage: 0,
+            clean_entries,
+            limits: None,
+            counters: UtxosCacheCounters::default(),
         })
     }
 
+    /// Set the memory limits of the cache; `None` means that the cache is unbounded.
+    pub fn set_limits(&mut self, limits: Option<UtxosCacheLimits>) {
+        self.limits = limits;
+        self.evict_clean_entries(0);
+    }
+
+    pub fn limits(&self) -> Option<UtxosCacheLimits> {
+        self.limits
+    }
+
+    pub fn counters(&self) -> UtxosCacheCounters {
+        self.counters
+    }
+
+    /// The approximate memory usage of the cache entries.
+    pub fn memory_usage(&self) -> usize {
+        self.utxos.len() * UTXO_CACHE_ENTRY_MEMORY_USAGE
+    }
+
+    /// Evict the least recently used clean entries until there is enough room for `extra_memory`
+    /// more bytes or there are no clean entries left.
+    fn evict_clean_entries(&mut self, extra_memory: usize) {
+        let max_memory_usage = match self.limits {
+            Some(limits) => limits.max_memory_usage,
+            None => return,
+        };
+
+        while self.memory_usage() + extra_memory > max_memory_usage {
+            let outpoint = match self.clean_entries.pop_least_recently_used() {
+                Some(outpoint) => outpoint,
+                None => break,
+            };
+            if self.utxos.get(&outpoint).is_some_and(is_clean) {
+                self.utxos.remove(&outpoint);
+                self.counters.evicted_entries += 1;
+            }
+        }
+    }
+
+    fn insert_entry(&mut self, outpoint: UtxoOutPoint, entry: UtxoEntry) {
+        if is_clean(&entry) {
+            self.clean_entries.insert(outpoint.clone());
+        } else {
+            self.clean_entries.remove(&outpoint);
+        }
+        self.utxos.insert(outpoint, entry);
+    }
+
+    fn remove_entry(&mut self, outpoint: &UtxoOutPoint) {
+        self.clean_entries.remove(outpoint);
+        self.utxos.remove(outpoint);
+    }
+
     pub fn set_best_block(&mut self, block_hash: Id<GenBlock>) {
         self.current_block_hash = block_hash;
     }
@@ -287,9 +383,6 @@ impl<P: UtxosView> UtxosCache<P> {
         utxo: Utxo,
         possible_overwrite: bool, // TODO: change this to an enum that explains what happens
     ) -> Result<(), Error> {
-        // TODO: update the memory usage
-        // self.memory_usage should be deducted based on this current entry.
-
         let is_fresh = match self.utxos.get(outpoint) {
             None => {
                 // An insert can be done. This utxo doesn't exist yet, so it's fresh.
@@ -323,10 +416,7 @@ impl<P: UtxosView> UtxosCache<P> {
         // create a new entry
         let new_entry = UtxoEntry::new(Some(utxo), IsFresh::from(is_fresh), IsDirty::Yes);
 
-        // TODO: update the memory usage
-        // self.memory_usage should be added based on this new entry.
-
-        self.utxos.insert(outpoint.clone(), new_entry);
+        self.insert_entry(outpoint.clone(), new_entry);
 
         Ok(())
     }
@@ -335,17 +425,15 @@ impl<P: UtxosView> UtxosCache<P> {
     /// Returns the Utxo if an update was performed.
     pub fn spend_utxo(&mut self, outpoint: &UtxoOutPoint) -> Result<Utxo, Error> {
         let entry = self.fetch_utxo_entry(outpoint)?.ok_or(Error::NoUtxoFound)?;
-        // TODO: update the memory usage
-        // self.memory_usage must be deducted from this entry's size
 
         // check whether this entry is fresh
         if entry.is_fresh() {
             // This is only available in this view. Remove immediately.
-            self.utxos.remove(outpoint);
+            self.remove_entry(outpoint);
         } else {
             // mark this as 'spent'
             let new_entry = UtxoEntry::new(None, IsFresh::No, IsDirty::Yes);
-            self.utxos.insert(outpoint.clone(), new_entry);
+            self.insert_entry(outpoint.clone(), new_entry);
         }
 
         entry.take_utxo().ok_or_else(|| Error::UtxoAlreadySpent(outpoint.source_id()))
@@ -367,15 +455,16 @@ impl<P: UtxosView> UtxosCache<P> {
             None => return Ok(None),
         };
 
-        let utxo: &mut UtxoEntry = self.utxos.entry(outpoint.clone()).or_insert_with(|| {
-            //TODO: update the memory storage here
-            UtxoEntry::new(
+        if !self.utxos.contains_key(outpoint) {
+            let entry = UtxoEntry::new(
                 Some(utxo.clone()),
                 IsFresh::from(entry.is_fresh()),
                 IsDirty::from(entry.is_dirty()),
-            )
-        });
+            );
+            self.insert_entry(outpoint.clone(), entry);
+        }
 
+        let utxo = self.utxos.get_mut(outpoint).expect("entry must be present");
         Ok(utxo.utxo_mut())
     }
 
@@ -384,9 +473,8 @@ impl<P: UtxosView> UtxosCache<P> {
         let key = outpoint;
         if let Some(entry) = self.utxos.get(key) {
             // see bitcoin's Uncache.
-            if !entry.is_fresh() && !entry.is_dirty() {
-                //todo: decrement the memory usage
-                self.utxos.remove(key);
+            if is_clean(entry) {
+                self.remove_entry(key);
                 return Ok(());
             }
         }
@@ -417,6 +505,7 @@ impl<P: UtxosView> UtxosView for UtxosCache<P> {
     fn utxo(&self, outpoint: &UtxoOutPoint) -> Result<Option<Utxo>, Self::Error> {
         let key = outpoint;
         if let Some(res) = self.utxos.get(key) {
+            self.clean_entries.touch(key);
             return Ok(res.utxo().cloned());
         }
 
@@ -458,8 +547,7 @@ impl<P> FlushableUtxoView for UtxosCache<P> {
                                 IsDirty::Yes,
                             );
 
-                            self.utxos.insert(key, entry_copy);
-                            // TODO: increase the memory usage
+                            self.insert_entry(key, entry_copy);
                         }
                     }
                     // found entry in the parent cache
@@ -475,7 +563,7 @@ impl<P> FlushableUtxoView for UtxosCache<P> {
                         if parent_entry.is_fresh() && entry.is_spent() {
                             // The grandparent cache does not have an entry, and the utxo
                             // has been spent. We can just delete it from the parent cache.
-                            self.utxos.remove(&key);
+                            self.remove_entry(&key);
                         } else {
                             // A normal modification.
                             let entry_copy = UtxoEntry::new(
@@ -483,8 +571,7 @@ impl<P> FlushableUtxoView for UtxosCache<P> {
                                 IsFresh::from(parent_entry.is_fresh()),
                                 IsDirty::Yes,
                             );
-                            self.utxos.insert(key, entry_copy);
-                            // TODO: update the memory usage
+                            self.insert_entry(key, entry_copy);
 
                             // NOTE: It isn't safe to mark the utxo as FRESH in the parent
                             // cache. If it already existed and was spent in the parent
@@ -497,12 +584,17 @@ impl<P> FlushableUtxoView for UtxosCache<P> {
         }
 
         self.current_block_hash = utxo_entries.best_block;
+        self.evict_clean_entries(0);
         Ok(())
     }
 }
 
+fn is_clean(entry: &UtxoEntry) -> bool {
+    !entry.is_fresh() && !entry.is_dirty()
+}
+
 /// Returns true if the given output type should be included in the utxo set
-fn should_include_in_utxo_set(output: &TxOutput) -> bool {
+pub fn should_include_in_utxo_set(output: &TxOutput) -> bool {
     match output {
         TxOutput::Transfer(_, _)
         | TxOutput::LockThenTransfer(..)
@@ -639,4 +731,115 @@ mod unit_test {
         assert!(cache1.has_utxo_in_cache(&outpoint));
         assert!(cache2.has_utxo_in_cache(&outpoint));
     }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn limits(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let test_view = empty_test_utxos_view(H256::zero().into());
+        let mut cache1 = UtxosCache::new(&test_view).unwrap_infallible();
+        let outpoints = (0..6)
+            .map(|_| {
+                insert_single_entry(
+                    &mut rng,
+                    &mut cache1,
+                    Presence::Present,
+                    Some((IsFresh::Yes, IsDirty::Yes)),
+                    None,
+                )
+                .1
+            })
+            .collect::<Vec<_>>();
+
+        let entry_size = UTXO_CACHE_ENTRY_MEMORY_USAGE;
+        let mut cache2 = UtxosCache::new(&cache1).unwrap_infallible();
+        cache2.set_limits(Some(UtxosCacheLimits {
+            max_memory_usage: entry_size * 3,
+        }));
+
+        // Clean entries are evicted to stay within the limit
+        for outpoint in &outpoints[..5] {
+            cache2.fetch_utxo_entry(outpoint).unwrap();
+            assert!(cache2.has_utxo_in_cache(outpoint));
+        }
+        assert_eq!(cache2.memory_usage(), entry_size * 3);
+        assert_eq!(
+            cache2.counters(),
+            UtxosCacheCounters {
+                loaded_entries: 5,
+                evicted_entries: 2,
+            }
+        );
+
+        // Modified entries are never evicted
+        for outpoint in &outpoints[..3] {
+            cache2.spend_utxo(outpoint).unwrap();
+        }
+
+        cache2.fetch_utxo_entry(&outpoints[5]).unwrap();
+        assert_eq!(cache2.memory_usage(), entry_size * 4);
+        for outpoint in &outpoints[..3] {
+            assert!(cache2.has_utxo_in_cache(outpoint));
+            assert_eq!(cache2.utxo(outpoint).unwrap_infallible(), None);
+        }
+
+        // Evicted entries are still available via the parent
+        for outpoint in &outpoints[3..] {
+            assert!(cache2.utxo(outpoint).unwrap_infallible().is_some());
+        }
+
+        let consumed = cache2.consume();
+        cache1.batch_write(consumed).unwrap();
+        for outpoint in &outpoints[..3] {
+            assert!(!cache1.has_utxo(outpoint).unwrap_infallible());
+        }
+        for outpoint in &outpoints[3..] {
+            assert!(cache1.has_utxo(outpoint).unwrap_infallible());
+        }
+    }
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn least_recently_used_evicted_first(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+        let test_view = empty_test_utxos_view(H256::zero().into());
+        let mut cache1 = UtxosCache::new(&test_view).unwrap_infallible();
+        let outpoints = (0..4)
+            .map(|_| {
+                insert_single_entry(
+                    &mut rng,
+                    &mut cache1,
+                    Presence::Present,
+                    Some((IsFresh::Yes, IsDirty::Yes)),
+                    None,
+                )
+                .1
+            })
+            .collect::<Vec<_>>();
+
+        let mut cache2 = UtxosCache::new(&cache1).unwrap_infallible();
+        cache2.set_limits(Some(UtxosCacheLimits {
+            max_memory_usage: UTXO_CACHE_ENTRY_MEMORY_USAGE * 3,
+        }));
+        for outpoint in &outpoints[..3] {
+            cache2.fetch_utxo_entry(outpoint).unwrap();
+        }
+
+        // Reading the oldest entry makes it the most recently used one
+        assert!(cache2.utxo(&outpoints[0]).unwrap_infallible().is_some());
+        cache2.fetch_utxo_entry(&outpoints[3]).unwrap();
+        assert!(!cache2.has_utxo_in_cache(&outpoints[1]));
+        for outpoint in [&outpoints[0], &outpoints[2], &outpoints[3]] {
+            assert!(cache2.has_utxo_in_cache(outpoint));
+        }
+
+        // An entry accessed for modification is tracked as well
+        assert!(cache2.get_mut_utxo(&outpoints[2]).unwrap().is_some());
+        cache2.fetch_utxo_entry(&outpoints[1]).unwrap();
+        assert!(!cache2.has_utxo_in_cache(&outpoints[0]));
+        for outpoint in [&outpoints[1], &outpoints[2], &outpoints[3]] {
+            assert!(cache2.has_utxo_in_cache(outpoint));
+        }
+    }
 }
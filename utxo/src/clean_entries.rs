@@ -0,0 +1,123 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use common::chain::UtxoOutPoint;
+
+struct UseTicks {
+    // The tick under which the outpoint is stored in `CleanEntries::by_last_use`
+    queued: u64,
+    // The tick of the last use, may be newer than `queued` if the entry was used via
+    // a shared reference
+    last_used: AtomicU64,
+}
+
+/// Outpoints of the clean cache entries (i.e. the ones that can be evicted at any time),
+/// ordered by the time they were last used.
+///
+/// An entry may be used via a shared reference, in which case only its last use tick is updated;
+/// the eviction order is fixed lazily, when the least recently used entry is looked up.
+#[derive(Default)]
+pub struct CleanEntries {
+    ticks: BTreeMap<UtxoOutPoint, UseTicks>,
+    by_last_use: BTreeMap<u64, UtxoOutPoint>,
+    next_tick: AtomicU64,
+}
+
+impl CleanEntries {
+    fn next_tick(&self) -> u64 {
+        self.next_tick.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Add the outpoint as the most recently used one.
+    pub fn insert(&mut self, outpoint: UtxoOutPoint) {
+        self.remove(&outpoint);
+
+        let tick = self.next_tick();
+        self.by_last_use.insert(tick, outpoint.clone());
+        self.ticks.insert(
+            outpoint,
+            UseTicks {
+                queued: tick,
+                last_used: AtomicU64::new(tick),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, outpoint: &UtxoOutPoint) {
+        if let Some(ticks) = self.ticks.remove(outpoint) {
+            self.by_last_use.remove(&ticks.queued);
+        }
+    }
+
+    /// Mark the outpoint as the most recently used one, if it's there.
+    pub fn touch(&self, outpoint: &UtxoOutPoint) {
+        if let Some(ticks) = self.ticks.get(outpoint) {
+            ticks.last_used.store(self.next_tick(), Ordering::Relaxed);
+        }
+    }
+
+    /// Remove and return the least recently used outpoint.
+    pub fn pop_least_recently_used(&mut self) -> Option<UtxoOutPoint> {
+        loop {
+            let (queued, outpoint) = self.by_last_use.pop_first()?;
+            let ticks = self.ticks.get_mut(&outpoint).expect("outpoint must be present");
+            let last_used = *ticks.last_used.get_mut();
+
+            if last_used == queued {
+                self.ticks.remove(&outpoint);
+                return Some(outpoint);
+            }
+
+            // The entry has been used since it was queued, move it to its proper place
+            ticks.queued = last_used;
+            self.by_last_use.insert(last_used, outpoint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{chain::OutPointSourceId, primitives::H256};
+
+    use super::*;
+
+    fn outpoint(index: u32) -> UtxoOutPoint {
+        UtxoOutPoint::new(OutPointSourceId::Transaction(H256::zero().into()), index)
+    }
+
+    #[test]
+    fn least_recently_used_first() {
+        let mut entries = CleanEntries::default();
+        for index in [3, 1, 2, 0] {
+            entries.insert(outpoint(index));
+        }
+
+        entries.touch(&outpoint(3));
+        entries.insert(outpoint(1));
+        entries.remove(&outpoint(2));
+        // Touching an absent entry does nothing
+        entries.touch(&outpoint(2));
+
+        assert_eq!(entries.pop_least_recently_used(), Some(outpoint(0)));
+        assert_eq!(entries.pop_least_recently_used(), Some(outpoint(3)));
+        assert_eq!(entries.pop_least_recently_used(), Some(outpoint(1)));
+        assert_eq!(entries.pop_least_recently_used(), None);
+    }
+}
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 mod cache;
+mod clean_entries;
 mod error;
 mod storage;
 mod undo;
@@ -22,7 +23,10 @@ mod utxo_entry;
 mod view;
 
 pub use crate::{
-    cache::{ConsumedUtxoCache, UtxosCache},
+    cache::{
+        should_include_in_utxo_set, ConsumedUtxoCache, UtxosCache, UtxosCacheCounters,
+        UtxosCacheLimits, UTXO_CACHE_ENTRY_MEMORY_USAGE,
+    },
     error::Error,
     storage::{in_memory::UtxosDBInMemoryImpl, UtxosDB, UtxosStorageRead, UtxosStorageWrite},
     undo::{
@@ -15,6 +15,7 @@
 
 pub mod currency_grouper;
 mod output_cache;
+mod output_privacy;
 pub mod transaction_list;
 mod utxo_selector;
 
@@ -22,6 +23,7 @@ use common::address::pubkeyhash::PublicKeyHash;
 use common::chain::block::timestamp::BlockTimestamp;
 use common::chain::classic_multisig::ClassicMultisigChallenge;
 use common::chain::htlc::HashedTimelockContract;
+use common::chain::timelock::OutputTimeLock;
 use common::chain::{
     AccountCommand, AccountOutPoint, AccountSpending, OrderAccountCommand, OrderId, OrdersVersion,
     RpcOrderInfo,
@@ -33,21 +35,23 @@ use common::size_estimation::{
     tx_size_with_num_inputs_and_outputs, DestinationInfoProvider,
 };
 use common::Uint256;
-use crypto::key::extended::ExtendedPublicKey;
+use crypto::key::extended::{ExtendedPrivateKey, ExtendedPublicKey};
 use crypto::key::hdkd::child_number::ChildNumber;
 use mempool::FeeRate;
 use serialization::hex_encoded::HexEncoded;
 use utils::ensure;
 use utxo_selector::SelectionResult;
 pub use utxo_selector::UtxoSelectorError;
-use wallet_types::account_id::AccountPrefixedId;
+use wallet_types::account_id::{AccountPrefixedId, AccountVrfPublicKey};
 use wallet_types::account_info::{StandaloneAddressDetails, StandaloneAddresses};
 use wallet_types::partially_signed_transaction::{PartiallySignedTransaction, PtxAdditionalInfo};
 use wallet_types::with_locked::WithLocked;
 
 use crate::account::utxo_selector::{select_coins, OutputGroup};
 use crate::destination_getters::{get_tx_output_destination, HtlcSpendingCondition};
-use crate::key_chain::{AccountKeyChains, KeyChainError, VRFAccountKeyChains};
+use crate::key_chain::{
+    AccountKeyChains, KeyChainError, KeyDerivationInfo, KeysVerificationReport, VRFAccountKeyChains,
+};
 use crate::send_request::{
     make_address_output, make_address_output_from_delegation, make_address_output_token,
     make_decommission_stake_pool_output, make_mint_token_outputs, make_stake_output,
@@ -70,7 +74,7 @@ use common::primitives::{Amount, BlockHeight, Id};
 use consensus::PoSGenerateBlockInputData;
 use crypto::key::hdkd::u31::U31;
 use crypto::key::{PrivateKey, PublicKey};
-use crypto::vrf::VRFPublicKey;
+use crypto::vrf::{VRFPrivateKey, VRFPublicKey};
 use itertools::{izip, Itertools};
 use std::cmp::Reverse;
 use std::collections::btree_map::Entry;
@@ -84,8 +88,8 @@ use wallet_storage::{
 use wallet_types::utxo_types::{get_utxo_type, UtxoState, UtxoStates, UtxoType, UtxoTypes};
 use wallet_types::wallet_tx::{BlockData, TxData, TxState};
 use wallet_types::{
-    AccountId, AccountInfo, AccountWalletCreatedTxId, AccountWalletTxId, BlockInfo, Currency,
-    KeyPurpose, KeychainUsageState, WalletTx,
+    AccountId, AccountInfo, AccountSpendingPolicy, AccountWalletCreatedTxId, AccountWalletTxId,
+    BlockInfo, Currency, KeyPurpose, KeychainUsageState, WalletTx,
 };
 
 pub use self::output_cache::{
@@ -93,9 +97,12 @@ pub use self::output_cache::{
     UtxoWithTxOutput,
 };
 use self::output_cache::{OutputCache, TokenIssuanceData};
-use self::transaction_list::{get_transaction_list, TransactionList};
+use self::transaction_list::{
+    get_transaction_info, get_transaction_list, TransactionInfo, TransactionList,
+};
 use self::utxo_selector::PayFee;
 
+pub use self::output_privacy::{OutputOrdering, OutputPrivacyPolicy};
 pub use self::utxo_selector::CoinSelectionAlgo;
 
 pub struct CurrentFeeRate {
@@ -123,6 +130,7 @@ pub struct Account<K> {
     key_chain: K,
     output_cache: OutputCache,
     account_info: AccountInfo,
+    spending_policy: AccountSpendingPolicy,
 }
 
 impl<K: AccountKeyChains> Account<K> {
@@ -153,6 +161,7 @@ impl<K: AccountKeyChains> Account<K> {
             key_chain,
             output_cache,
             account_info,
+            spending_policy: AccountSpendingPolicy::default(),
         };
 
         account.scan_genesis(db_tx, &WalletEventsNoOp)?;
@@ -175,11 +184,14 @@ impl<K: AccountKeyChains> Account<K> {
         let output_cache =
             OutputCache::new(chain_config.as_ref(), account_info.best_block_height(), txs)?;
 
+        let spending_policy = db_tx.get_account_spending_policy(id)?.unwrap_or_default();
+
         Ok(Account {
             chain_config,
             key_chain,
             output_cache,
             account_info,
+            spending_policy,
         })
     }
 
@@ -251,12 +263,7 @@ impl<K: AccountKeyChains> Account<K> {
 
         let (utxos, selection_algo) = if input_utxos.is_empty() {
             (
-                self.get_utxos(
-                    UtxoType::Transfer | UtxoType::LockThenTransfer | UtxoType::IssueNft,
-                    median_time,
-                    UtxoState::Confirmed | UtxoState::InMempool | UtxoState::Inactive,
-                    WithLocked::Unlocked,
-                ),
+                self.get_spendable_utxos(median_time),
                 selection_algo.unwrap_or(CoinSelectionAlgo::Randomize),
             )
         } else {
@@ -280,6 +287,9 @@ impl<K: AccountKeyChains> Account<K> {
         };
 
         let current_fee_rate = fee_rates.current_fee_rate;
+        let max_tx_size = self
+            .chain_config
+            .max_tx_size_for_mempool(self.account_info.best_block_height().next_height());
         let mut utxos_by_currency =
             self.utxo_output_groups_by_currency(fee_rates, &pay_fee_with_currency, utxos)?;
 
@@ -323,7 +333,7 @@ impl<K: AccountKeyChains> Account<K> {
                     // when we allow paying fees with different currency
                     Amount::ZERO,
                     selection_algo,
-                    self.chain_config.max_tx_size_for_mempool(),
+                    max_tx_size,
                 )?;
 
                 total_tx_size += selection_result.get_weight();
@@ -405,7 +415,7 @@ impl<K: AccountKeyChains> Account<K> {
             PayFee::PayFeeWithThisCurrency,
             cost_of_change,
             selection_algo,
-            self.chain_config.max_tx_size_for_mempool(),
+            max_tx_size,
         )?;
 
         let mut selection_result = selection_result.add_change(
@@ -670,8 +680,11 @@ impl<K: AccountKeyChains> Account<K> {
         change_addresses: BTreeMap<Currency, Address<Destination>>,
         median_time: BlockTimestamp,
         fee_rate: CurrentFeeRate,
+        output_privacy_policy: OutputPrivacyPolicy,
         ptx_additional_info: PtxAdditionalInfo,
     ) -> WalletResult<(PartiallySignedTransaction, BTreeMap<Currency, Amount>)> {
+        let num_requested_outputs = request.outputs().len();
+        let current_fee_rate = fee_rate.current_fee_rate;
         let mut request = self.select_inputs_for_send_request(
             request,
             inputs,
@@ -682,6 +695,12 @@ impl<K: AccountKeyChains> Account<K> {
             fee_rate,
             None,
         )?;
+        apply_output_privacy_policy(
+            &mut request,
+            num_requested_outputs,
+            output_privacy_policy,
+            current_fee_rate,
+        )?;
 
         let fees = request.get_fees();
         let ptx = request.into_partially_signed_tx(ptx_additional_info)?;
@@ -697,8 +716,11 @@ impl<K: AccountKeyChains> Account<K> {
         change_addresses: BTreeMap<Currency, Address<Destination>>,
         median_time: BlockTimestamp,
         fee_rate: CurrentFeeRate,
+        output_privacy_policy: OutputPrivacyPolicy,
     ) -> WalletResult<SendRequest> {
-        self.select_inputs_for_send_request(
+        let num_requested_outputs = request.outputs().len();
+        let current_fee_rate = fee_rate.current_fee_rate;
+        let mut request = self.select_inputs_for_send_request(
             request,
             inputs,
             None,
@@ -707,8 +729,15 @@ impl<K: AccountKeyChains> Account<K> {
             median_time,
             fee_rate,
             None,
-        )
-        // TODO: Randomize inputs and outputs
+        )?;
+        // TODO: Randomize inputs
+        apply_output_privacy_policy(
+            &mut request,
+            num_requested_outputs,
+            output_privacy_policy,
+            current_fee_rate,
+        )?;
+        Ok(request)
     }
 
     fn decommission_stake_pool_impl(
@@ -1709,6 +1738,22 @@ impl<K: AccountKeyChains> Account<K> {
         self.account_info.account_key()
     }
 
+    pub fn get_key_derivation_info(
+        &self,
+        destination: &Destination,
+    ) -> WalletResult<KeyDerivationInfo> {
+        self.key_chain
+            .get_key_derivation_info(destination)?
+            .ok_or(WalletError::AddressNotFound)
+    }
+
+    pub fn verify_keys_from_root_key(
+        &self,
+        root_key: &ExtendedPrivateKey,
+    ) -> WalletResult<KeysVerificationReport> {
+        Ok(self.key_chain.verify_keys_from_root_key(root_key)?)
+    }
+
     pub fn get_all_issued_addresses(
         &self,
         key_purpose: KeyPurpose,
@@ -1946,6 +1991,24 @@ impl<K: AccountKeyChains> Account<K> {
         Ok(amounts_by_currency)
     }
 
+    /// The balance that can be spent by the automatic coin selection, i.e. the unlocked outputs
+    /// that satisfy the spending policy of the account
+    pub fn get_spendable_balance(
+        &self,
+        median_time: BlockTimestamp,
+    ) -> WalletResult<BTreeMap<Currency, Amount>> {
+        let amounts_by_currency = currency_grouper::group_utxos_for_input(
+            self.get_spendable_utxos(median_time).into_iter(),
+            |(_, tx_output)| tx_output,
+            |total: &mut Amount, _, amount| -> WalletResult<()> {
+                *total = (*total + amount).ok_or(WalletError::OutputAmountOverflow)?;
+                Ok(())
+            },
+            Amount::ZERO,
+        )?;
+        Ok(amounts_by_currency)
+    }
+
     pub fn get_address_coin_balances(
         &self,
         utxo_states: UtxoStates,
@@ -2003,6 +2066,54 @@ impl<K: AccountKeyChains> Account<K> {
         })
     }
 
+    /// The utxos that can be picked by the automatic coin selection
+    fn get_spendable_utxos(&self, median_time: BlockTimestamp) -> Vec<(UtxoOutPoint, &TxOutput)> {
+        self.get_utxos(
+            UtxoType::Transfer | UtxoType::LockThenTransfer | UtxoType::IssueNft,
+            median_time,
+            UtxoState::Confirmed | UtxoState::InMempool | UtxoState::Inactive,
+            WithLocked::Unlocked,
+        )
+        .into_iter()
+        .filter(|(outpoint, _)| self.satisfies_spending_policy(outpoint))
+        .collect()
+    }
+
+    /// Check whether the output has as many confirmations as the spending policy requires.
+    /// The output is considered change if the transaction that created it spends any
+    /// of the account's own outputs.
+    fn satisfies_spending_policy(&self, outpoint: &UtxoOutPoint) -> bool {
+        let Some(tx) = self.output_cache.txs_with_unconfirmed().get(&outpoint.source_id()) else {
+            return false;
+        };
+
+        let confirmations = match tx.state() {
+            TxState::Confirmed(height, _, _) => {
+                let best_block_height = self.account_info.best_block_height();
+                best_block_height.into_int().saturating_sub(height.into_int()) + 1
+            }
+            TxState::InMempool(_)
+            | TxState::Inactive(_)
+            | TxState::Conflicted(_)
+            | TxState::Abandoned => 0,
+        };
+
+        let is_change = tx.inputs().iter().any(|input| match input {
+            TxInput::Utxo(outpoint) => {
+                self.output_cache.get_txo(outpoint).is_some_and(|txo| self.is_mine(txo))
+            }
+            TxInput::Account(_)
+            | TxInput::AccountCommand(_, _)
+            | TxInput::OrderAccountCommand(_) => false,
+        });
+
+        confirmations >= u64::from(self.spending_policy.min_confirmations(is_change))
+    }
+
+    pub fn get_utxo_unlock_point(&self, outpoint: &UtxoOutPoint) -> Option<OutputTimeLock> {
+        self.output_cache.utxo_unlock_point(outpoint)
+    }
+
     pub fn get_transaction_list(&self, skip: usize, count: usize) -> WalletResult<TransactionList> {
         get_transaction_list(&self.key_chain, &self.output_cache, skip, count)
     }
@@ -2011,6 +2122,13 @@ impl<K: AccountKeyChains> Account<K> {
         self.output_cache.get_transaction(transaction_id)
     }
 
+    pub fn get_transaction_info(
+        &self,
+        transaction_id: Id<Transaction>,
+    ) -> WalletResult<TransactionInfo> {
+        get_transaction_info(&self.key_chain, &self.output_cache, transaction_id)
+    }
+
     pub fn reset_to_height<B: storage::Backend>(
         &mut self,
         db_tx: &mut StoreTxRw<B>,
@@ -2155,7 +2273,12 @@ impl<K: AccountKeyChains> Account<K> {
                             TxState::Confirmed(block_height, block.timestamp(), idx as u64);
                         let wallet_tx = WalletTx::Tx(TxData::new(signed_tx.clone(), tx_state));
 
-                        self.update_conflicting_txs(signed_tx.transaction(), block, db_tx)?;
+                        self.update_conflicting_txs(
+                            signed_tx.transaction(),
+                            block,
+                            db_tx,
+                            wallet_events,
+                        )?;
 
                         new_tx_was_added |= self
                             .add_wallet_tx_if_relevant_and_remove_from_user_txs(
@@ -2180,12 +2303,13 @@ impl<K: AccountKeyChains> Account<K> {
         Ok(new_tx_was_added)
     }
 
-    /// Check for any conflicting txs and update the new state in the DB
+    /// Check for any conflicting txs, update the new state in the DB and notify about it
     fn update_conflicting_txs<B: storage::Backend>(
         &mut self,
         confirmed_tx: &Transaction,
         block: &Block,
         db_tx: &mut StoreTxRw<B>,
+        wallet_events: &impl WalletEvents,
     ) -> WalletResult<()> {
         let acc_id = self.get_account_id();
         let conflicting_txs = self.output_cache.update_conflicting_txs(
@@ -2195,8 +2319,17 @@ impl<K: AccountKeyChains> Account<K> {
         )?;
 
         for (tx_id, tx) in conflicting_txs {
+            logging::log::warn!(
+                "Transaction {} in account {} has been double-spent by transaction {} in block {}",
+                tx_id,
+                self.account_index(),
+                confirmed_tx.get_id(),
+                block.get_id(),
+            );
+
             db_tx.set_transaction(&AccountWalletTxId::new(acc_id.clone(), tx.id()), &tx)?;
             db_tx.del_user_transaction(&AccountWalletCreatedTxId::new(acc_id.clone(), tx_id))?;
+            wallet_events.set_transaction(self.account_index(), &tx);
         }
 
         Ok(())
@@ -2374,6 +2507,20 @@ impl<K: AccountKeyChains> Account<K> {
         Ok(())
     }
 
+    pub fn spending_policy(&self) -> AccountSpendingPolicy {
+        self.spending_policy
+    }
+
+    pub fn set_spending_policy(
+        &mut self,
+        policy: AccountSpendingPolicy,
+        db_tx: &mut impl WalletStorageWriteLocked,
+    ) -> WalletResult<()> {
+        db_tx.set_account_spending_policy(&self.get_account_id(), &policy)?;
+        self.spending_policy = policy;
+        Ok(())
+    }
+
     pub fn get_created_blocks(&self) -> Vec<(BlockHeight, Id<GenBlock>, PoolId)> {
         self.output_cache
             .get_created_blocks(|destination| self.is_destination_mine(destination))
@@ -2447,6 +2594,59 @@ impl<K: AccountKeyChains + VRFAccountKeyChains> Account<K> {
         self.key_chain.get_legacy_vrf_public_key()
     }
 
+    /// Find the VRF private key for the public key, either derived from this account's VRF key
+    /// chain or imported as a standalone key
+    pub fn get_vrf_private_key(
+        &self,
+        db_tx: &impl WalletStorageReadUnlocked,
+        public_key: &VRFPublicKey,
+    ) -> WalletResult<Option<VRFPrivateKey>> {
+        if let Some(xpriv) = self.key_chain.get_vrf_private_key_for_public_key(public_key, db_tx)? {
+            return Ok(Some(xpriv.private_key()));
+        }
+
+        let id = AccountVrfPublicKey::new(self.get_account_id(), public_key.clone());
+        Ok(db_tx.get_account_standalone_vrf_private_key(&id)?)
+    }
+
+    /// Add a standalone VRF private key not derived from this account's VRF key chain,
+    /// e.g. the key of a pool that was created by another wallet
+    pub fn add_standalone_vrf_private_key(
+        &mut self,
+        db_tx: &mut impl WalletStorageWriteUnlocked,
+        private_key: VRFPrivateKey,
+        label: Option<String>,
+    ) -> WalletResult<Address<VRFPublicKey>> {
+        let public_key = VRFPublicKey::from_private_key(&private_key);
+        let address = Address::new(&self.chain_config, public_key.clone()).expect("addressable");
+
+        ensure!(
+            self.get_vrf_private_key(db_tx, &public_key)?.is_none(),
+            WalletError::VrfKeyAlreadyExists(address)
+        );
+
+        let id = AccountVrfPublicKey::new(self.get_account_id(), public_key);
+        db_tx.set_standalone_vrf_private_key(&id, &private_key, label)?;
+
+        Ok(address)
+    }
+
+    pub fn get_standalone_vrf_public_keys(
+        &self,
+        db_tx: &impl WalletStorageReadLocked,
+    ) -> WalletResult<Vec<(Address<VRFPublicKey>, Option<String>)>> {
+        let keys = db_tx.get_account_standalone_vrf_private_keys(&self.get_account_id())?;
+
+        Ok(keys
+            .into_iter()
+            .map(|(id, label)| {
+                let address =
+                    Address::new(&self.chain_config, id.into_item_id()).expect("addressable");
+                (address, label)
+            })
+            .collect())
+    }
+
     /// Get a new vrf key that hasn't been used before
     pub fn get_new_vrf_key(
         &mut self,
@@ -2479,13 +2679,9 @@ impl<K: AccountKeyChains + VRFAccountKeyChains> Account<K> {
             .get_private_key_for_destination(stake_destination, db_tx)?
             .ok_or(WalletError::KeyChainError(KeyChainError::NoPrivateKeyFound))?;
 
-        let vrf_private_key = self
-            .key_chain
-            .get_vrf_private_key_for_public_key(&pool_data.vrf_public_key, db_tx)?
-            .ok_or(WalletError::KeyChainError(
-                KeyChainError::NoVRFPrivateKeyFound,
-            ))?
-            .private_key();
+        let vrf_private_key = self.get_vrf_private_key(db_tx, &pool_data.vrf_public_key)?.ok_or(
+            WalletError::KeyChainError(KeyChainError::NoVRFPrivateKeyFound),
+        )?;
 
         let data = PoSGenerateBlockInputData::new(
             stake_private_key,
@@ -2777,6 +2973,21 @@ fn handle_conclude_order(
     Ok(())
 }
 
+fn apply_output_privacy_policy(
+    request: &mut SendRequest,
+    num_requested_outputs: usize,
+    output_privacy_policy: OutputPrivacyPolicy,
+    current_fee_rate: FeeRate,
+) -> WalletResult<()> {
+    // Rounding the change is only worth it if it costs less than the change output itself
+    let (_, max_change_rounding) = output_change_size_and_fees(
+        current_fee_rate,
+        &Currency::Coin,
+        Destination::AnyoneCanSpend,
+    )?;
+    output_privacy_policy.apply(request, num_requested_outputs, max_change_rounding)
+}
+
 /// Calculate the encoded size and fee that needs to be paid to add a change output
 fn output_change_size_and_fees(
     feerate: mempool::FeeRate,
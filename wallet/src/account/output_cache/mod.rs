@@ -25,6 +25,7 @@ use common::{
         make_delegation_id, make_order_id, make_token_id, make_token_id_with_version,
         output_value::OutputValue,
         stakelock::StakePoolData,
+        timelock::OutputTimeLock,
         tokens::{
             get_referenced_token_ids_ignore_issuance, IsTokenFreezable, IsTokenUnfreezable,
             RPCFungibleTokenInfo, RPCIsTokenFrozen, RPCNonFungibleTokenInfo, RPCTokenTotalSupply,
@@ -625,6 +626,26 @@ impl OutputCache {
             .and_then(|tx| tx.outputs().get(outpoint.output_index() as usize))
     }
 
+    /// Returns the absolute height or time at which the timelock of the output expires.
+    /// Returns None if the output has no timelock or if it has a relative timelock
+    /// and its transaction is not confirmed yet.
+    pub fn utxo_unlock_point(&self, outpoint: &UtxoOutPoint) -> Option<OutputTimeLock> {
+        let tx = self.txs.get(&outpoint.source_id())?;
+        let output = tx.outputs().get(outpoint.output_index() as usize)?;
+        let timelock = output.timelock()?;
+        match timelock {
+            OutputTimeLock::UntilHeight(_) | OutputTimeLock::UntilTime(_) => Some(*timelock),
+            OutputTimeLock::ForBlockCount(block_count) => {
+                let tx_block_info = get_block_info(tx)?;
+                tx_block_info.height.checked_add(*block_count).map(OutputTimeLock::UntilHeight)
+            }
+            OutputTimeLock::ForSeconds(seconds) => {
+                let tx_block_info = get_block_info(tx)?;
+                tx_block_info.timestamp.add_int_seconds(*seconds).map(OutputTimeLock::UntilTime)
+            }
+        }
+    }
+
     pub fn pool_ids(&self) -> Vec<(PoolId, PoolData)> {
         self.pools
             .iter()
@@ -758,44 +779,43 @@ impl OutputCache {
         struct ConflictCheck {
             frozen_token_id: Option<TokenId>,
             confirmed_account_nonce: Option<(AccountType, AccountNonce)>,
+            spent_utxo: Option<UtxoOutPoint>,
         }
 
         let conflict_checks = confirmed_tx
             .inputs()
             .iter()
-            .filter_map(|input| {
-                match input {
-                    TxInput::Utxo(_) => {
-                        // TODO: check conflicting utxo spends
-                        // See https://github.com/mintlayer/mintlayer-core/issues/1875
-                        None
-                    }
-                    TxInput::Account(outpoint) => Some(ConflictCheck {
+            .filter_map(|input| match input {
+                TxInput::Utxo(outpoint) => Some(ConflictCheck {
+                    frozen_token_id: None,
+                    confirmed_account_nonce: None,
+                    spent_utxo: Some(outpoint.clone()),
+                }),
+                TxInput::Account(outpoint) => Some(ConflictCheck {
+                    frozen_token_id: None,
+                    confirmed_account_nonce: Some((outpoint.account().into(), outpoint.nonce())),
+                    spent_utxo: None,
+                }),
+                TxInput::AccountCommand(nonce, cmd) => match cmd {
+                    AccountCommand::MintTokens(_, _)
+                    | AccountCommand::UnmintTokens(_)
+                    | AccountCommand::LockTokenSupply(_)
+                    | AccountCommand::ChangeTokenMetadataUri(_, _)
+                    | AccountCommand::ChangeTokenAuthority(_, _)
+                    | AccountCommand::UnfreezeToken(_)
+                    | AccountCommand::ConcludeOrder(_)
+                    | AccountCommand::FillOrder(_, _, _) => Some(ConflictCheck {
                         frozen_token_id: None,
-                        confirmed_account_nonce: Some((
-                            outpoint.account().into(),
-                            outpoint.nonce(),
-                        )),
+                        confirmed_account_nonce: Some((cmd.into(), *nonce)),
+                        spent_utxo: None,
                     }),
-                    TxInput::AccountCommand(nonce, cmd) => match cmd {
-                        AccountCommand::MintTokens(_, _)
-                        | AccountCommand::UnmintTokens(_)
-                        | AccountCommand::LockTokenSupply(_)
-                        | AccountCommand::ChangeTokenMetadataUri(_, _)
-                        | AccountCommand::ChangeTokenAuthority(_, _)
-                        | AccountCommand::UnfreezeToken(_)
-                        | AccountCommand::ConcludeOrder(_)
-                        | AccountCommand::FillOrder(_, _, _) => Some(ConflictCheck {
-                            frozen_token_id: None,
-                            confirmed_account_nonce: Some((cmd.into(), *nonce)),
-                        }),
-                        | AccountCommand::FreezeToken(token_id, _) => Some(ConflictCheck {
-                            frozen_token_id: Some(*token_id),
-                            confirmed_account_nonce: Some((cmd.into(), *nonce)),
-                        }),
-                    },
-                    TxInput::OrderAccountCommand(_) => None,
-                }
+                    | AccountCommand::FreezeToken(token_id, _) => Some(ConflictCheck {
+                        frozen_token_id: Some(*token_id),
+                        confirmed_account_nonce: Some((cmd.into(), *nonce)),
+                        spent_utxo: None,
+                    }),
+                },
+                TxInput::OrderAccountCommand(_) => None,
             })
             .collect::<Vec<_>>();
 
@@ -829,6 +849,19 @@ impl OutputCache {
                                 continue;
                             }
                         }
+
+                        if let Some(spent_utxo) = &conflict_check.spent_utxo {
+                            if confirmed_tx.get_id() != tx.get_transaction().get_id()
+                                && tx
+                                    .get_transaction()
+                                    .inputs()
+                                    .iter()
+                                    .any(|input| input.utxo_outpoint() == Some(spent_utxo))
+                            {
+                                conflicting_txs.insert(tx.get_transaction().get_id());
+                                continue;
+                            }
+                        }
                     }
                     WalletTx::Block(_) => {
                         utils::debug_panic_or_log!("Cannot be block reward");
@@ -280,6 +280,131 @@ fn update_conflicting_txs_parent_and_child(#[case] seed: Seed) {
     );
 }
 
+// Create an unconfirmed tx B that spends a confirmed utxo of A, and its unconfirmed child C.
+//
+// A-->B-->C
+//
+// Confirm another tx D that spends the same utxo of A.
+// Check that both B and C got marked as conflicted.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn update_conflicting_txs_utxo_double_spend(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = create_unit_test_config();
+    let best_block_height = BlockHeight::new(rng.gen());
+    let mut output_cache = OutputCache::empty();
+
+    // A
+    let genesis_tx_id = Id::<Transaction>::random_using(&mut rng);
+    let tx_a = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(genesis_tx_id.into(), 0),
+            InputWitness::NoSignature(None),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen())),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_a_id = tx_a.transaction().get_id();
+    output_cache
+        .add_tx(
+            &chain_config,
+            best_block_height,
+            tx_a_id.into(),
+            WalletTx::Tx(TxData::new(
+                tx_a,
+                TxState::Confirmed(
+                    BlockHeight::new(rng.gen()),
+                    BlockTimestamp::from_int_seconds(0),
+                    0,
+                ),
+            )),
+        )
+        .unwrap();
+
+    // B
+    let tx_b = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(tx_a_id.into(), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen())),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_b_id = tx_b.transaction().get_id();
+    output_cache
+        .add_tx(
+            &chain_config,
+            best_block_height,
+            tx_b_id.into(),
+            WalletTx::Tx(TxData::new(tx_b.clone(), TxState::InMempool(0))),
+        )
+        .unwrap();
+
+    // C
+    let tx_c = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(tx_b_id.into(), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen())),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+    let tx_c_id = tx_c.transaction().get_id();
+    output_cache
+        .add_tx(
+            &chain_config,
+            best_block_height,
+            tx_c_id.into(),
+            WalletTx::Tx(TxData::new(tx_c.clone(), TxState::InMempool(0))),
+        )
+        .unwrap();
+
+    let block_id = Id::random_using(&mut rng);
+
+    // Confirming B itself doesn't make anything conflicted
+    let result = output_cache
+        .update_conflicting_txs(&chain_config, tx_b.transaction(), block_id)
+        .unwrap();
+    assert_eq!(result, vec![]);
+
+    // D
+    let tx_d = TransactionBuilder::new()
+        .add_input(
+            TxInput::from_utxo(tx_a_id.into(), 0),
+            empty_witness(&mut rng),
+        )
+        .add_output(TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(rng.gen())),
+            Destination::AnyoneCanSpend,
+        ))
+        .build();
+
+    let result = output_cache
+        .update_conflicting_txs(&chain_config, tx_d.transaction(), block_id)
+        .unwrap();
+    assert_eq!(
+        result,
+        vec![
+            (
+                tx_c_id,
+                WalletTx::Tx(TxData::new(tx_c, TxState::Conflicted(block_id)))
+            ),
+            (
+                tx_b_id,
+                WalletTx::Tx(TxData::new(tx_b, TxState::Conflicted(block_id)))
+            ),
+        ]
+    );
+}
+
 // Create unconfirmed txs Bi that use a token in their outputs only:
 // a) by transferring zero amount of the token (a legit situation which doesn't require the token
 // to be present in the inputs);
@@ -741,3 +866,113 @@ fn abandon_transaction(#[case] seed: Seed) {
         ])
     );
 }
+
+// Check that relative timelocks are turned into absolute unlock points once the transaction
+// is confirmed, and absolute timelocks are reported as is.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn utxo_unlock_point(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = create_unit_test_config();
+    let best_block_height = BlockHeight::new(rng.gen_range(0..1_000_000));
+    let mut output_cache = OutputCache::empty();
+
+    let lock_height = BlockHeight::new(rng.gen_range(0..1_000_000));
+    let lock_time = BlockTimestamp::from_int_seconds(rng.gen_range(0..1_000_000));
+    let block_count = rng.gen_range(0..1_000);
+    let seconds = rng.gen_range(0..1_000);
+    let timelocks = [
+        OutputTimeLock::UntilHeight(lock_height),
+        OutputTimeLock::UntilTime(lock_time),
+        OutputTimeLock::ForBlockCount(block_count),
+        OutputTimeLock::ForSeconds(seconds),
+    ];
+
+    let genesis_tx_id = Id::<Transaction>::random_using(&mut rng);
+    let tx = timelocks
+        .iter()
+        .fold(
+            TransactionBuilder::new()
+                .add_input(
+                    TxInput::from_utxo(genesis_tx_id.into(), 0),
+                    InputWitness::NoSignature(None),
+                )
+                .add_output(TxOutput::Transfer(
+                    OutputValue::Coin(Amount::from_atoms(rng.gen())),
+                    Destination::AnyoneCanSpend,
+                )),
+            |builder, timelock| {
+                builder.add_output(TxOutput::LockThenTransfer(
+                    OutputValue::Coin(Amount::from_atoms(rng.gen())),
+                    Destination::AnyoneCanSpend,
+                    *timelock,
+                ))
+            },
+        )
+        .build();
+    let tx_id = tx.transaction().get_id();
+    let outpoint = |index| UtxoOutPoint::new(tx_id.into(), index);
+
+    output_cache
+        .add_tx(
+            &chain_config,
+            best_block_height,
+            tx_id.into(),
+            WalletTx::Tx(TxData::new(tx.clone(), TxState::InMempool(0))),
+        )
+        .unwrap();
+
+    assert_eq!(output_cache.utxo_unlock_point(&outpoint(0)), None);
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(1)),
+        Some(OutputTimeLock::UntilHeight(lock_height))
+    );
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(2)),
+        Some(OutputTimeLock::UntilTime(lock_time))
+    );
+    assert_eq!(output_cache.utxo_unlock_point(&outpoint(3)), None);
+    assert_eq!(output_cache.utxo_unlock_point(&outpoint(4)), None);
+
+    let tx_block_height = best_block_height.next_height();
+    let tx_block_timestamp = BlockTimestamp::from_int_seconds(rng.gen_range(0..1_000_000));
+    output_cache
+        .add_tx(
+            &chain_config,
+            tx_block_height,
+            tx_id.into(),
+            WalletTx::Tx(TxData::new(
+                tx,
+                TxState::Confirmed(tx_block_height, tx_block_timestamp, 0),
+            )),
+        )
+        .unwrap();
+
+    assert_eq!(output_cache.utxo_unlock_point(&outpoint(0)), None);
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(1)),
+        Some(OutputTimeLock::UntilHeight(lock_height))
+    );
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(2)),
+        Some(OutputTimeLock::UntilTime(lock_time))
+    );
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(3)),
+        Some(OutputTimeLock::UntilHeight(
+            tx_block_height.checked_add(block_count).unwrap()
+        ))
+    );
+    assert_eq!(
+        output_cache.utxo_unlock_point(&outpoint(4)),
+        Some(OutputTimeLock::UntilTime(
+            tx_block_timestamp.add_int_seconds(seconds).unwrap()
+        ))
+    );
+    assert_eq!(
+        output_cache.utxo_unlock_point(&UtxoOutPoint::new(tx_id.into(), 5)),
+        None
+    );
+}
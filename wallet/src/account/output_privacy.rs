@@ -0,0 +1,297 @@
+// Copyright (c) 2021-2023 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Privacy-preserving placement and amounts of the change outputs.
+//!
+//! Putting the change last and leaving its amount with arbitrary precision makes it easy to tell
+//! which output of a transaction belongs to the sender. The policy below is only applied to
+//! transactions whose requested outputs are all plain transfers; other outputs may be referenced
+//! by their index by the caller, so their order is never changed.
+
+use common::{
+    chain::{output_value::OutputValue, TxOutput},
+    primitives::{id::hash_encoded, Amount},
+};
+use serialization::Encode;
+
+use crate::{send_request::SendRequest, WalletError, WalletResult};
+use wallet_types::Currency;
+
+/// How the outputs of the transactions created by the wallet are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputOrdering {
+    /// Keep the requested outputs in order and put the change outputs last.
+    ChangeLast,
+    /// Sort the outputs by amount and then by their encoding, similar to BIP69.
+    Sorted,
+    /// Shuffle the outputs using the transaction inputs as the seed, so that the order is random
+    /// but the same transaction always gets the same order.
+    #[default]
+    Shuffled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OutputPrivacyPolicy {
+    pub ordering: OutputOrdering,
+
+    /// Round the coin change down to the precision of the payment amounts, e.g. if all payments
+    /// are whole coins, so should be the change. The rounded off amount is added to the fee,
+    /// but it never exceeds the fee of the change output itself; if it would, a finer precision
+    /// is used.
+    pub match_change_precision: bool,
+}
+
+impl OutputPrivacyPolicy {
+    /// Apply the policy to a request whose first `num_requested_outputs` outputs are the ones
+    /// requested by the user and the rest are the change outputs.
+    pub(crate) fn apply(
+        &self,
+        request: &mut SendRequest,
+        num_requested_outputs: usize,
+        max_change_rounding: Amount,
+    ) -> WalletResult<()> {
+        let (requested_outputs, change_outputs) = request.outputs().split_at(num_requested_outputs);
+        if !requested_outputs.iter().all(is_plain_transfer) || change_outputs.is_empty() {
+            return Ok(());
+        }
+
+        if self.match_change_precision {
+            let payment_precision =
+                requested_outputs.iter().filter_map(coin_amount).map(decimal_precision).min();
+
+            if let Some(payment_precision) = payment_precision {
+                let mut extra_fee = Amount::ZERO;
+                for output in &mut request.get_outputs_mut()[num_requested_outputs..] {
+                    if let Some(value) = coin_value_mut(output) {
+                        let remainder = change_rounding_remainder(
+                            *value,
+                            payment_precision,
+                            max_change_rounding,
+                        );
+                        *value = (*value - remainder).ok_or(WalletError::OutputAmountOverflow)?;
+                        extra_fee =
+                            (extra_fee + remainder).ok_or(WalletError::OutputAmountOverflow)?;
+                    }
+                }
+                request.add_fee(Currency::Coin, extra_fee)?;
+            }
+        }
+
+        match self.ordering {
+            OutputOrdering::ChangeLast => {}
+            OutputOrdering::Sorted => {
+                request
+                    .get_outputs_mut()
+                    .sort_by_cached_key(|output| (output_amount(output), output.encode()));
+            }
+            OutputOrdering::Shuffled => {
+                let seed = hash_encoded(&request.inputs());
+                request
+                    .get_outputs_mut()
+                    .sort_by_cached_key(|output| hash_encoded(&(seed, output)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_plain_transfer(output: &TxOutput) -> bool {
+    match output {
+        TxOutput::Transfer(_, _) | TxOutput::LockThenTransfer(_, _, _) => true,
+        TxOutput::Burn(_)
+        | TxOutput::CreateStakePool(_, _)
+        | TxOutput::ProduceBlockFromStake(_, _)
+        | TxOutput::CreateDelegationId(_, _)
+        | TxOutput::DelegateStaking(_, _)
+        | TxOutput::IssueFungibleToken(_)
+        | TxOutput::IssueNft(_, _, _)
+        | TxOutput::DataDeposit(_)
+        | TxOutput::Htlc(_, _)
+        | TxOutput::CreateOrder(_) => false,
+    }
+}
+
+fn output_value(output: &TxOutput) -> Option<&OutputValue> {
+    match output {
+        TxOutput::Transfer(value, _) | TxOutput::LockThenTransfer(value, _, _) => Some(value),
+        _ => None,
+    }
+}
+
+fn output_amount(output: &TxOutput) -> Amount {
+    match output_value(output) {
+        Some(OutputValue::Coin(amount) | OutputValue::TokenV1(_, amount)) => *amount,
+        Some(OutputValue::TokenV0(_)) | None => Amount::ZERO,
+    }
+}
+
+fn coin_amount(output: &TxOutput) -> Option<Amount> {
+    match output_value(output) {
+        Some(OutputValue::Coin(amount)) => Some(*amount),
+        _ => None,
+    }
+}
+
+fn coin_value_mut(output: &mut TxOutput) -> Option<&mut Amount> {
+    match output {
+        TxOutput::Transfer(OutputValue::Coin(amount), _)
+        | TxOutput::LockThenTransfer(OutputValue::Coin(amount), _, _) => Some(amount),
+        _ => None,
+    }
+}
+
+/// The number of trailing decimal zeros of the amount in atoms.
+fn decimal_precision(amount: Amount) -> u32 {
+    let mut atoms = amount.into_atoms();
+    let mut precision = 0;
+    while atoms != 0 && atoms % 10 == 0 {
+        atoms /= 10;
+        precision += 1;
+    }
+    precision
+}
+
+/// The amount that should be removed from the change to round it to the specified precision,
+/// or to the coarsest precision that doesn't exceed `max_rounding`. The change is never rounded
+/// to zero.
+fn change_rounding_remainder(change: Amount, precision: u32, max_rounding: Amount) -> Amount {
+    let change = change.into_atoms();
+    (1..=precision)
+        .rev()
+        .map(|precision| change % 10u128.pow(precision))
+        .find(|remainder| *remainder <= max_rounding.into_atoms() && *remainder < change)
+        .map_or(Amount::ZERO, Amount::from_atoms)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{
+        chain::{Destination, OutPointSourceId, Transaction, TxInput},
+        primitives::{Id, H256},
+    };
+    use rstest::rstest;
+    use test_utils::random::{make_seedable_rng, Rng, Seed};
+
+    use super::*;
+
+    fn transfer(atoms: u128) -> TxOutput {
+        TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(atoms)),
+            Destination::AnyoneCanSpend,
+        )
+    }
+
+    fn make_request(rng: &mut impl Rng, outputs: Vec<TxOutput>) -> SendRequest {
+        let input = TxInput::from_utxo(
+            OutPointSourceId::Transaction(Id::<Transaction>::new(H256::random_using(rng))),
+            0,
+        );
+        SendRequest::new()
+            .with_inputs_and_destinations([(input, Destination::AnyoneCanSpend)])
+            .with_outputs(outputs)
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(decimal_precision(Amount::ZERO), 0);
+        assert_eq!(decimal_precision(Amount::from_atoms(123)), 0);
+        assert_eq!(decimal_precision(Amount::from_atoms(1_000)), 3);
+        assert_eq!(decimal_precision(Amount::from_atoms(1_020_000)), 4);
+
+        // Round to the full precision if it's cheap enough
+        assert_eq!(
+            change_rounding_remainder(Amount::from_atoms(12_345), 3, Amount::from_atoms(500)),
+            Amount::from_atoms(345)
+        );
+        // Otherwise use a finer precision
+        assert_eq!(
+            change_rounding_remainder(Amount::from_atoms(12_345), 3, Amount::from_atoms(100)),
+            Amount::from_atoms(45)
+        );
+        assert_eq!(
+            change_rounding_remainder(Amount::from_atoms(12_345), 3, Amount::from_atoms(1)),
+            Amount::ZERO
+        );
+        // Never round the change to zero
+        assert_eq!(
+            change_rounding_remainder(Amount::from_atoms(345), 3, Amount::from_atoms(1_000)),
+            Amount::from_atoms(45)
+        );
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn ordering(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let outputs = vec![transfer(300), transfer(100), transfer(200), transfer(12_345)];
+        let request = make_request(&mut rng, outputs.clone());
+
+        let apply = |ordering| {
+            let mut request = request.clone();
+            let policy = OutputPrivacyPolicy {
+                ordering,
+                match_change_precision: false,
+            };
+            policy.apply(&mut request, 3, Amount::ZERO).unwrap();
+            request.outputs().to_vec()
+        };
+
+        assert_eq!(apply(OutputOrdering::ChangeLast), outputs);
+        assert_eq!(
+            apply(OutputOrdering::Sorted),
+            vec![transfer(100), transfer(200), transfer(300), transfer(12_345)]
+        );
+
+        let shuffled = apply(OutputOrdering::Shuffled);
+        assert_eq!(shuffled, apply(OutputOrdering::Shuffled));
+        let mut sorted = shuffled.clone();
+        sorted.sort_by_key(output_amount);
+        assert_eq!(sorted, apply(OutputOrdering::Sorted));
+    }
+
+    #[rstest]
+    #[trace]
+    #[case(Seed::from_entropy())]
+    fn match_change_precision(#[case] seed: Seed) {
+        let mut rng = make_seedable_rng(seed);
+
+        let policy = OutputPrivacyPolicy {
+            ordering: OutputOrdering::ChangeLast,
+            match_change_precision: true,
+        };
+
+        let mut request = make_request(&mut rng, vec![transfer(5_000), transfer(12_300)]);
+        request.add_outputs([transfer(98_765)]);
+        policy.apply(&mut request, 2, Amount::from_atoms(1_000)).unwrap();
+        assert_eq!(
+            request.outputs(),
+            [transfer(5_000), transfer(12_300), transfer(98_700)]
+        );
+        assert_eq!(
+            request.get_fees(),
+            [(Currency::Coin, Amount::from_atoms(65))].into()
+        );
+
+        // Outputs other than plain transfers are left untouched
+        let burn = TxOutput::Burn(OutputValue::Coin(Amount::from_atoms(1_000)));
+        let mut request = make_request(&mut rng, vec![transfer(5_000), burn.clone()]);
+        request.add_outputs([transfer(98_765)]);
+        policy.apply(&mut request, 2, Amount::from_atoms(1_000)).unwrap();
+        assert_eq!(request.outputs(), [transfer(5_000), burn, transfer(98_765)]);
+    }
+}
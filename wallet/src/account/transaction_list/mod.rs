@@ -215,6 +215,15 @@ fn get_transaction(
     })
 }
 
+pub fn get_transaction_info(
+    key_chain: &impl AccountKeyChains,
+    output_cache: &OutputCache,
+    transaction_id: Id<Transaction>,
+) -> WalletResult<TransactionInfo> {
+    let tx_data = output_cache.get_transaction(transaction_id)?;
+    get_transaction(key_chain, output_cache, tx_data)
+}
+
 pub fn get_transaction_list(
     key_chain: &impl AccountKeyChains,
     output_cache: &OutputCache,
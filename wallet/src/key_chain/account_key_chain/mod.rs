@@ -42,7 +42,8 @@ use wallet_types::{AccountId, AccountInfo, KeychainUsageState};
 
 use super::vrf_key_chain::{EmptyVrfKeyChain, VrfKeyChain, VrfKeySoftChain};
 use super::{
-    make_path_to_vrf_key, AccountKeyChains, MasterKeyChain, VRFAccountKeyChains, VRF_INDEX,
+    make_path_to_vrf_key, AccountKeyChains, KeyDerivationInfo, KeysVerificationReport,
+    MasterKeyChain, VRFAccountKeyChains, VRF_INDEX,
 };
 
 /// This key chain contains a pool of pre-generated keys and addresses for the usage in a wallet
@@ -699,6 +700,45 @@ impl<V: VrfKeyChain> AccountKeyChains for AccountKeyChainImpl<V> {
     fn get_addresses_usage_state(&self, key_purpose: KeyPurpose) -> &KeychainUsageState {
         self.get_leaf_key_chain(key_purpose).usage_state()
     }
+
+    fn get_key_derivation_info(
+        &self,
+        destination: &Destination,
+    ) -> KeyChainResult<Option<KeyDerivationInfo>> {
+        for purpose in KeyPurpose::ALL {
+            let leaf_key = self.get_leaf_key_chain(purpose);
+            if let Some(child_num) = leaf_key.get_child_num_from_destination(destination) {
+                return leaf_key.get_key_derivation_info(child_num);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn verify_keys_from_root_key(
+        &self,
+        root_key: &ExtendedPrivateKey,
+    ) -> KeyChainResult<KeysVerificationReport> {
+        let account_path = make_account_path(&self.chain_config, self.account_index);
+        let account_private_key = root_key.clone().derive_absolute_path(&account_path)?;
+        let account_public_key_matches =
+            &account_private_key.to_public_key() == self.account_public_key.as_ref();
+
+        let mut checked_keys = 0;
+        let mut mismatches = Vec::new();
+        for purpose in KeyPurpose::ALL {
+            let (checked, purpose_mismatches) =
+                self.get_leaf_key_chain(purpose).verify_derived_keys(&account_private_key)?;
+            checked_keys += checked;
+            mismatches.extend(purpose_mismatches);
+        }
+
+        Ok(KeysVerificationReport {
+            account_public_key_matches,
+            checked_keys,
+            mismatches,
+        })
+    }
 }
 
 impl VRFAccountKeyChains for AccountKeyChainImpl<VrfKeySoftChain> {
@@ -14,16 +14,18 @@
 // limitations under the License.
 
 use crate::key_chain::with_purpose::WithPurpose;
-use crate::key_chain::{get_purpose_and_index, KeyChainError, KeyChainResult};
+use crate::key_chain::{
+    get_purpose_and_index, KeyChainError, KeyChainResult, KeyDerivationInfo, KeyDerivationMismatch,
+};
 use common::address::pubkeyhash::PublicKeyHash;
 use common::address::Address;
 use common::chain::{ChainConfig, Destination};
-use crypto::key::extended::ExtendedPublicKey;
+use crypto::key::extended::{ExtendedPrivateKey, ExtendedPublicKey};
 use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::derivable::Derivable;
 use crypto::key::hdkd::u31::U31;
 use crypto::key::PublicKey;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use utils::const_value::ConstValue;
 use utils::ensure;
@@ -502,6 +504,81 @@ impl LeafKeySoftChain {
     pub fn usage_state(&self) -> &KeychainUsageState {
         &self.usage_state
     }
+
+    fn make_address(&self, public_key: &PublicKey) -> KeyChainResult<Address<Destination>> {
+        Ok(Address::new(
+            &self.chain_config,
+            Destination::PublicKeyHash(PublicKeyHash::from(public_key)),
+        )?)
+    }
+
+    /// Get the derivation details of a stored key, re-deriving it from the parent public key
+    pub fn get_key_derivation_info(
+        &self,
+        child_num: ChildNumber,
+    ) -> KeyChainResult<Option<KeyDerivationInfo>> {
+        let stored_key = match self.derived_public_keys.get(&child_num) {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let derived_key = self.parent_pubkey.clone().take().derive_child(child_num)?;
+        let derived_address = self.make_address(&derived_key.clone().into_public_key())?;
+        let stored_address = self.addresses.get(&child_num).cloned();
+
+        let is_consistent =
+            stored_key == &derived_key && stored_address.as_ref() == Some(&derived_address);
+
+        Ok(Some(KeyDerivationInfo {
+            derivation_path: derived_key.get_derivation_path().clone(),
+            public_key: stored_key.clone().into_public_key(),
+            stored_address,
+            derived_address,
+            is_consistent,
+        }))
+    }
+
+    /// Re-derive all the stored keys from the account private key. Returns the number of checked
+    /// keys and the keys whose stored public key or address don't match the re-derived ones.
+    pub fn verify_derived_keys(
+        &self,
+        account_private_key: &ExtendedPrivateKey,
+    ) -> KeyChainResult<(usize, Vec<KeyDerivationMismatch>)> {
+        let purpose_private_key = account_private_key
+            .clone()
+            .derive_child(self.purpose.get_deterministic_index())?;
+
+        let child_numbers = self
+            .derived_public_keys
+            .keys()
+            .chain(self.addresses.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+
+        let mut mismatches = Vec::new();
+        for child_num in child_numbers.iter().copied() {
+            let expected_key = purpose_private_key.clone().derive_child(child_num)?.to_public_key();
+            let expected_address = self.make_address(&expected_key.clone().into_public_key())?;
+
+            let stored_key = self.derived_public_keys.get(&child_num);
+            let stored_address = self.addresses.get(&child_num);
+
+            if stored_key != Some(&expected_key) || stored_address != Some(&expected_address) {
+                let stored_public_key_address = stored_key
+                    .map(|key| self.make_address(&key.clone().into_public_key()))
+                    .transpose()?;
+
+                mismatches.push(KeyDerivationMismatch {
+                    derivation_path: expected_key.get_derivation_path().clone(),
+                    stored_public_key_address,
+                    stored_address: stored_address.cloned(),
+                    expected_address,
+                });
+            }
+        }
+
+        Ok((child_numbers.len(), mismatches))
+    }
 }
 
 // TODO: tests
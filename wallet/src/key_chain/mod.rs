@@ -46,7 +46,7 @@ use common::address::pubkeyhash::{PublicKeyHash, PublicKeyHashError};
 use common::address::{Address, AddressError, RpcAddress};
 use common::chain::config::BIP44_PATH;
 use common::chain::{ChainConfig, Destination};
-use crypto::key::extended::{ExtendedKeyKind, ExtendedPublicKey};
+use crypto::key::extended::{ExtendedKeyKind, ExtendedPrivateKey, ExtendedPublicKey};
 use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::derivable::DerivationError;
 use crypto::key::hdkd::derivation_path::DerivationPath;
@@ -132,6 +132,45 @@ impl FoundPubKey {
     }
 }
 
+/// The derivation details of a key from an account's key hierarchy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyDerivationInfo {
+    /// The full derivation path of the key
+    pub derivation_path: DerivationPath,
+    /// The public key stored in the wallet
+    pub public_key: PublicKey,
+    /// The address stored in the wallet
+    pub stored_address: Option<Address<Destination>>,
+    /// The address re-derived from the account extended public key
+    pub derived_address: Address<Destination>,
+    /// Whether the stored public key and address match the re-derived ones
+    pub is_consistent: bool,
+}
+
+/// A stored key whose records don't match the key re-derived from the seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyDerivationMismatch {
+    /// The full derivation path of the key
+    pub derivation_path: DerivationPath,
+    /// The address of the stored public key, if the public key is stored
+    pub stored_public_key_address: Option<Address<Destination>>,
+    /// The stored address, if any
+    pub stored_address: Option<Address<Destination>>,
+    /// The address re-derived from the seed
+    pub expected_address: Address<Destination>,
+}
+
+/// The result of re-deriving the keys of an account from the seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeysVerificationReport {
+    /// Whether the stored account extended public key matches the one derived from the seed
+    pub account_public_key_matches: bool,
+    /// The number of derived keys that were checked
+    pub checked_keys: usize,
+    /// The keys whose stored records don't match
+    pub mismatches: Vec<KeyDerivationMismatch>,
+}
+
 pub type AccountKeyChainImplSoftware = AccountKeyChainImpl<VrfKeySoftChain>;
 pub type AccountKeyChainImplHardware = AccountKeyChainImpl<EmptyVrfKeyChain>;
 
@@ -289,6 +328,20 @@ where
     ) -> Option<(Destination, StandaloneAddressDetails)>;
 
     fn get_addresses_usage_state(&self, key_purpose: KeyPurpose) -> &KeychainUsageState;
+
+    /// Get the derivation details of the key behind the destination, if the key belongs to
+    /// this account's hierarchy, with the address re-derived from the account public key
+    fn get_key_derivation_info(
+        &self,
+        destination: &Destination,
+    ) -> KeyChainResult<Option<KeyDerivationInfo>>;
+
+    /// Re-derive the account public key and all the derived keys of this account from
+    /// the root key and compare them with the stored ones
+    fn verify_keys_from_root_key(
+        &self,
+        root_key: &ExtendedPrivateKey,
+    ) -> KeyChainResult<KeysVerificationReport>;
 }
 
 pub trait VRFAccountKeyChains {
@@ -303,3 +303,58 @@ fn top_up_and_lookahead(#[case] purpose: KeyPurpose) {
         );
     }
 }
+
+#[test]
+fn key_derivation_info_and_verification() {
+    let chain_config = Arc::new(create_unit_test_config());
+    let mut db = Store::new(DefaultBackend::new_in_memory()).unwrap();
+    let mut db_tx = db.transaction_rw_unlocked(None).unwrap();
+    let master_key_chain = MasterKeyChain::new_from_mnemonic(
+        chain_config,
+        &mut db_tx,
+        MNEMONIC,
+        None,
+        StoreSeedPhrase::DoNotStore,
+    )
+    .unwrap();
+
+    let mut key_chain = master_key_chain
+        .create_account_key_chain(&mut db_tx, DEFAULT_ACCOUNT_INDEX, LOOKAHEAD_SIZE)
+        .unwrap();
+    key_chain.top_up_all(&mut db_tx).unwrap();
+    let (child_number, address) =
+        key_chain.issue_address(&mut db_tx, KeyPurpose::ReceiveFunds).unwrap();
+    db_tx.commit().unwrap();
+
+    let info = key_chain.get_key_derivation_info(address.as_object()).unwrap().unwrap();
+    let path = info.derivation_path.as_slice();
+    assert_eq!(path.len(), BIP44_PATH_LENGTH);
+    assert_eq!(
+        path[BIP44_KEY_PURPOSE_INDEX],
+        KeyPurpose::ReceiveFunds.get_deterministic_index()
+    );
+    assert_eq!(path[BIP44_KEY_INDEX], child_number);
+    assert_eq!(info.stored_address.as_ref(), Some(&address));
+    assert_eq!(info.derived_address, address);
+    assert!(info.is_consistent);
+
+    assert_eq!(
+        key_chain.get_key_derivation_info(&Destination::AnyoneCanSpend).unwrap(),
+        None
+    );
+
+    // Re-deriving from the same seed finds no mismatches
+    let (root_key, _, _) = MasterKeyChain::mnemonic_to_root_key(MNEMONIC, None).unwrap();
+    let report = key_chain.verify_keys_from_root_key(&root_key).unwrap();
+    assert!(report.account_public_key_matches);
+    assert_eq!(report.checked_keys, 2 * LOOKAHEAD_SIZE as usize);
+    assert_eq!(report.mismatches, vec![]);
+
+    // A different seed doesn't match anything
+    let (root_key, _, _) =
+        MasterKeyChain::mnemonic_to_root_key(MNEMONIC, Some("passphrase")).unwrap();
+    let report = key_chain.verify_keys_from_root_key(&root_key).unwrap();
+    assert!(!report.account_public_key_matches);
+    assert_eq!(report.checked_keys, 2 * LOOKAHEAD_SIZE as usize);
+    assert_eq!(report.mismatches.len(), report.checked_keys);
+}
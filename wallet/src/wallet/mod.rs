@@ -18,13 +18,14 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::account::{
-    transaction_list::TransactionList, CoinSelectionAlgo, CurrentFeeRate, DelegationData,
-    OrderData, PoolData, TxInfo, UnconfirmedTokenInfo, UtxoSelectorError,
+    transaction_list::{TransactionInfo, TransactionList},
+    CoinSelectionAlgo, CurrentFeeRate, DelegationData, OrderData, OutputPrivacyPolicy, PoolData,
+    TxInfo, UnconfirmedTokenInfo, UtxoSelectorError,
 };
 use crate::destination_getters::HtlcSpendingCondition;
 use crate::key_chain::{
     make_account_path, make_path_to_vrf_key, AccountKeyChainImplSoftware, KeyChainError,
-    MasterKeyChain, LOOKAHEAD_SIZE, VRF_INDEX,
+    KeyDerivationInfo, KeysVerificationReport, MasterKeyChain, LOOKAHEAD_SIZE, VRF_INDEX,
 };
 use crate::send_request::{
     make_issue_token_outputs, IssueNftArguments, SelectedInputs, StakePoolCreationArguments,
@@ -46,6 +47,7 @@ use common::chain::signature::inputsig::arbitrary_message::{
     ArbitraryMessageSignature, SignArbitraryMessageError,
 };
 use common::chain::signature::DestinationSigError;
+use common::chain::timelock::OutputTimeLock;
 use common::chain::tokens::{
     IsTokenUnfreezable, Metadata, RPCFungibleTokenInfo, TokenId, TokenIssuance,
 };
@@ -65,7 +67,7 @@ use crypto::key::hdkd::child_number::ChildNumber;
 use crypto::key::hdkd::derivable::Derivable;
 use crypto::key::hdkd::u31::U31;
 use crypto::key::{PrivateKey, PublicKey};
-use crypto::vrf::VRFPublicKey;
+use crypto::vrf::{VRFPrivateKey, VRFPublicKey};
 use mempool::FeeRate;
 use tx_verifier::error::TokenIssuanceError;
 use tx_verifier::{check_transaction, CheckTransactionError};
@@ -90,8 +92,8 @@ use wallet_types::wallet_tx::{TxData, TxState};
 use wallet_types::wallet_type::{WalletControllerMode, WalletType};
 use wallet_types::with_locked::WithLocked;
 use wallet_types::{
-    AccountId, AccountKeyPurposeId, BlockInfo, Currency, KeyPurpose, KeychainUsageState,
-    SignedTxWithFees,
+    AccountId, AccountKeyPurposeId, AccountSpendingPolicy, BlockInfo, Currency, KeyPurpose,
+    KeychainUsageState, SignedTxWithFees, TokenDisplayInfo,
 };
 
 pub const WALLET_VERSION_UNINITIALIZED: u32 = 0;
@@ -269,6 +271,8 @@ pub enum WalletError {
         "A VRF public key must be specified when creating a staking pool using a hardware wallet"
     )]
     VrfKeyMustBeProvided,
+    #[error("The VRF key {0} already exists in this account")]
+    VrfKeyAlreadyExists(Address<VRFPublicKey>),
     #[error("Cannot change a Trezor wallet type")]
     CannotChangeTrezorWalletType,
     #[error("Missing additional data for Pool {0}")]
@@ -283,6 +287,8 @@ pub enum WalletError {
     ConfirmedTxAmongUnconfirmedDescendants(OutPointSourceId),
     #[error("Id creation error: {0}")]
     IdCreationError(#[from] IdCreationError),
+    #[error("No partially signed transactions to combine")]
+    NoPartiallySignedTransactionsToCombine,
 }
 
 /// Result type used for the wallet
@@ -303,6 +309,7 @@ pub struct Wallet<B: storage::Backend + 'static, P: SignerProvider> {
     latest_median_time: BlockTimestamp,
     next_unused_account: (U31, Account<P::K>),
     signer_provider: P,
+    output_privacy_policy: OutputPrivacyPolicy,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -433,6 +440,7 @@ where
             latest_median_time,
             next_unused_account,
             signer_provider,
+            output_privacy_policy: OutputPrivacyPolicy::default(),
         };
 
         Ok(WalletCreation::Wallet(wallet))
@@ -901,9 +909,16 @@ where
             latest_median_time,
             next_unused_account,
             signer_provider,
+            output_privacy_policy: OutputPrivacyPolicy::default(),
         }))
     }
 
+    /// Set the policy for the order of the outputs and the change amounts of the created
+    /// transactions.
+    pub fn set_output_privacy_policy(&mut self, output_privacy_policy: OutputPrivacyPolicy) {
+        self.output_privacy_policy = output_privacy_policy;
+    }
+
     pub fn seed_phrase(&self) -> WalletResult<Option<SerializableSeedPhrase>> {
         self.db.transaction_ro_unlocked()?.get_seed_phrase().map_err(WalletError::from)
     }
@@ -932,6 +947,12 @@ where
         self.db.lock_private_keys().map_err(WalletError::from)
     }
 
+    /// Write a copy of the wallet database into a new wallet file
+    pub fn backup_to_file(&self, path: &Path) -> WalletResult<()> {
+        self.db.backup(DefaultBackend::new(path))?;
+        Ok(())
+    }
+
     pub fn unlock_wallet(&mut self, password: &String) -> WalletResult<()> {
         self.db.unlock_private_keys(password).map_err(WalletError::from)
     }
@@ -985,6 +1006,25 @@ where
         self.signer_provider.get_hardware_wallet_info()
     }
 
+    /// Get the stored display information of a token, if it has been stored before.
+    pub fn get_token_display_info(
+        &self,
+        token_id: &TokenId,
+    ) -> WalletResult<Option<TokenDisplayInfo>> {
+        Ok(self.db.transaction_ro()?.get_token_display_info(token_id)?)
+    }
+
+    pub fn set_token_display_info(
+        &mut self,
+        token_id: &TokenId,
+        display_info: &TokenDisplayInfo,
+    ) -> WalletResult<()> {
+        let mut db_tx = self.db.transaction_rw(None)?;
+        db_tx.set_token_display_info(token_id, display_info)?;
+        db_tx.commit()?;
+        Ok(())
+    }
+
     fn create_next_unused_account(
         next_account_index: U31,
         chain_config: Arc<ChainConfig>,
@@ -1072,6 +1112,23 @@ where
         })
     }
 
+    pub fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<AccountSpendingPolicy> {
+        Ok(self.get_account(account_index)?.spending_policy())
+    }
+
+    pub fn set_account_spending_policy(
+        &mut self,
+        account_index: U31,
+        policy: AccountSpendingPolicy,
+    ) -> WalletResult<()> {
+        self.for_account_rw(account_index, |acc, db_tx| {
+            acc.set_spending_policy(policy, db_tx)
+        })
+    }
+
     pub fn database(&self) -> &Store<B> {
         &self.db
     }
@@ -1302,6 +1359,13 @@ where
         )
     }
 
+    pub fn get_spendable_balance(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<BTreeMap<Currency, Amount>> {
+        self.get_account(account_index)?.get_spendable_balance(self.latest_median_time)
+    }
+
     pub fn get_address_coin_balances(
         &self,
         account_index: U31,
@@ -1351,6 +1415,15 @@ where
         Ok(utxos)
     }
 
+    pub fn get_utxo_unlock_point(
+        &self,
+        account_index: U31,
+        outpoint: &UtxoOutPoint,
+    ) -> WalletResult<Option<OutputTimeLock>> {
+        let account = self.get_account(account_index)?;
+        Ok(account.get_utxo_unlock_point(outpoint))
+    }
+
     pub fn find_account_destination(&self, acc_outpoint: &AccountOutPoint) -> Option<Destination> {
         self.accounts
             .values()
@@ -1529,6 +1602,14 @@ where
         Ok(self.get_account(account_index)?.get_extended_public_key())
     }
 
+    pub fn get_key_derivation_info(
+        &self,
+        account_index: U31,
+        address: Destination,
+    ) -> WalletResult<KeyDerivationInfo> {
+        self.get_account(account_index)?.get_key_derivation_info(&address)
+    }
+
     pub fn get_transaction_list(
         &self,
         account_index: U31,
@@ -1548,6 +1629,15 @@ where
         account.get_transaction(transaction_id)
     }
 
+    pub fn get_transaction_info(
+        &self,
+        account_index: U31,
+        transaction_id: Id<Transaction>,
+    ) -> WalletResult<TransactionInfo> {
+        let account = self.get_account(account_index)?;
+        account.get_transaction_info(transaction_id)
+    }
+
     pub fn get_transactions_to_be_broadcast(&self) -> WalletResult<Vec<SignedTransaction>> {
         self.db
             .transaction_ro()?
@@ -1705,6 +1795,7 @@ where
     ) -> WalletResult<(SignedTxWithFees, AddlData)> {
         let request = SendRequest::new().with_outputs(outputs);
         let latest_median_time = self.latest_median_time;
+        let output_privacy_policy = self.output_privacy_policy;
         self.async_for_account_rw_unlocked_and_check_tx_custom_error(
             account_index,
             additional_info,
@@ -1719,6 +1810,7 @@ where
                         current_fee_rate,
                         consolidate_fee_rate,
                     },
+                    output_privacy_policy,
                 )?;
 
                 let additional_data = additional_data_getter(&send_request);
@@ -1743,6 +1835,7 @@ where
     ) -> WalletResult<(PartiallySignedTransaction, BTreeMap<Currency, Amount>)> {
         let request = SendRequest::new().with_outputs(outputs);
         let latest_median_time = self.latest_median_time;
+        let output_privacy_policy = self.output_privacy_policy;
         self.for_account_rw(account_index, |account, db_tx| {
             account.process_send_request(
                 db_tx,
@@ -1755,6 +1848,7 @@ where
                     current_fee_rate,
                     consolidate_fee_rate,
                 },
+                output_privacy_policy,
                 ptx_additional_info,
             )
         })
@@ -2436,6 +2530,25 @@ where
         .await
     }
 
+    /// Combine several partially signed versions of the same transaction, e.g. the ones signed
+    /// in parallel by different cosigners of a multisig, into one.
+    pub fn combine_partially_signed_transactions(
+        &self,
+        account_index: U31,
+        ptxs: Vec<PartiallySignedTransaction>,
+    ) -> WalletResult<PartiallySignedTransaction> {
+        let (_, best_block_height) = self.get_best_block_for_account(account_index)?;
+        let next_block_height = best_block_height.next_height();
+
+        let mut ptxs = ptxs.into_iter();
+        let first = ptxs.next().ok_or(WalletError::NoPartiallySignedTransactionsToCombine)?;
+
+        ptxs.try_fold(first, |combined, ptx| {
+            combined.combine(ptx, &self.chain_config, next_block_height)
+        })
+        .map_err(Into::into)
+    }
+
     pub async fn sign_challenge(
         &mut self,
         account_index: U31,
@@ -2653,6 +2766,39 @@ where
         Ok(account.get_all_issued_vrf_public_keys())
     }
 
+    /// Import a VRF private key that was not derived from the account's VRF key chain,
+    /// so that the account can stake with a pool that uses it
+    pub fn add_standalone_vrf_private_key(
+        &mut self,
+        account_index: U31,
+        private_key: VRFPrivateKey,
+        label: Option<String>,
+    ) -> WalletResult<Address<VRFPublicKey>> {
+        self.for_account_rw_unlocked(account_index, |account, db_tx, _, _| {
+            account.add_standalone_vrf_private_key(db_tx, private_key, label)
+        })
+    }
+
+    pub fn get_standalone_vrf_public_keys(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<Vec<(Address<VRFPublicKey>, Option<String>)>> {
+        let db_tx = self.db.transaction_ro()?;
+        self.get_account(account_index)?.get_standalone_vrf_public_keys(&db_tx)
+    }
+
+    /// Export the VRF private key corresponding to the public key, either derived or imported
+    pub fn get_vrf_private_key(
+        &self,
+        account_index: U31,
+        public_key: &VRFPublicKey,
+    ) -> WalletResult<VRFPrivateKey> {
+        let db_tx = self.db.transaction_ro_unlocked()?;
+        self.get_account(account_index)?.get_vrf_private_key(&db_tx, public_key)?.ok_or(
+            WalletError::KeyChainError(KeyChainError::NoVRFPrivateKeyFound),
+        )
+    }
+
     pub fn get_legacy_vrf_public_key(
         &self,
         account_index: U31,
@@ -2661,6 +2807,25 @@ where
         Ok(account.get_legacy_vrf_public_key())
     }
 
+    /// Re-derive the keys of the account from the seed and compare them with the stored ones.
+    ///
+    /// If the mnemonic is provided, the seed is computed from it, so that a tampered stored root
+    /// key is detected as well; otherwise the stored root key is used, which requires the private
+    /// keys to be unlocked.
+    pub fn verify_account_keys(
+        &self,
+        account_index: U31,
+        mnemonic: Option<&str>,
+        passphrase: Option<&str>,
+    ) -> WalletResult<KeysVerificationReport> {
+        let root_key = match mnemonic {
+            Some(mnemonic) => MasterKeyChain::mnemonic_to_root_key(mnemonic, passphrase)?.0,
+            None => MasterKeyChain::load_root_key(&self.db.transaction_ro_unlocked()?)?,
+        };
+
+        self.get_account(account_index)?.verify_keys_from_root_key(&root_key)
+    }
+
     pub async fn create_stake_pool(
         &mut self,
         account_index: U31,
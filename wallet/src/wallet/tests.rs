@@ -40,7 +40,7 @@ use crypto::{
         hdkd::{child_number::ChildNumber, derivable::Derivable, derivation_path::DerivationPath},
         KeyKind,
     },
-    vrf::transcript::no_rng::VRFTranscript,
+    vrf::{transcript::no_rng::VRFTranscript, VRFKeyKind},
 };
 use randomness::{CryptoRng, Rng, SliceRandom};
 use serialization::{extras::non_empty_vec::DataOrNoVec, hex::HexEncode, Encode};
@@ -60,7 +60,7 @@ use wallet_types::{
     },
     seed_phrase::{PassPhrase, StoreSeedPhrase},
     utxo_types::{UtxoState, UtxoType},
-    AccountWalletTxId, Currency, WalletTx,
+    AccountSpendingPolicy, AccountWalletTxId, Currency, WalletTx,
 };
 
 use crate::{
@@ -1483,6 +1483,110 @@ async fn locked_wallet_standalone_keys(
         .unwrap();
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn standalone_vrf_keys(
+    #[case] seed: Seed,
+    #[values(true, false)] insert_before_encrypt: bool,
+) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let mut wallet = create_wallet(chain_config.clone());
+
+    let (standalone_vrf_sk, standalone_vrf_pk) =
+        VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+    let label = Some("imported pool key".to_owned());
+    let password = Some(gen_random_password(&mut rng));
+
+    if insert_before_encrypt {
+        wallet
+            .add_standalone_vrf_private_key(
+                DEFAULT_ACCOUNT_INDEX,
+                standalone_vrf_sk.clone(),
+                label.clone(),
+            )
+            .unwrap();
+        wallet.encrypt_wallet(&password).unwrap();
+    } else {
+        wallet.encrypt_wallet(&password).unwrap();
+        wallet
+            .add_standalone_vrf_private_key(
+                DEFAULT_ACCOUNT_INDEX,
+                standalone_vrf_sk.clone(),
+                label.clone(),
+            )
+            .unwrap();
+    }
+
+    let standalone_vrf_address = Address::new(&chain_config, standalone_vrf_pk.clone()).unwrap();
+    assert_eq!(
+        wallet.get_standalone_vrf_public_keys(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        vec![(standalone_vrf_address.clone(), label.clone())]
+    );
+
+    // The same key cannot be imported twice
+    assert_eq!(
+        wallet.add_standalone_vrf_private_key(
+            DEFAULT_ACCOUNT_INDEX,
+            standalone_vrf_sk.clone(),
+            None
+        ),
+        Err(WalletError::VrfKeyAlreadyExists(standalone_vrf_address))
+    );
+
+    // Derived keys cannot be imported as standalone ones either
+    let derived_vrf_pk = wallet.get_vrf_key(DEFAULT_ACCOUNT_INDEX).unwrap().1.into_object();
+    let derived_vrf_sk =
+        wallet.get_vrf_private_key(DEFAULT_ACCOUNT_INDEX, &derived_vrf_pk).unwrap();
+    assert_eq!(
+        VRFPublicKey::from_private_key(&derived_vrf_sk),
+        derived_vrf_pk
+    );
+    assert!(matches!(
+        wallet.add_standalone_vrf_private_key(DEFAULT_ACCOUNT_INDEX, derived_vrf_sk, None),
+        Err(WalletError::VrfKeyAlreadyExists(_))
+    ));
+
+    // The private keys cannot be exported while the wallet is locked
+    wallet.lock_wallet().unwrap();
+    assert_eq!(
+        wallet.get_vrf_private_key(DEFAULT_ACCOUNT_INDEX, &standalone_vrf_pk),
+        Err(WalletError::DatabaseError(
+            wallet_storage::Error::WalletLocked
+        ))
+    );
+    // but the public keys are still available
+    assert_eq!(
+        wallet.get_standalone_vrf_public_keys(DEFAULT_ACCOUNT_INDEX).unwrap().len(),
+        1
+    );
+
+    wallet.unlock_wallet(&password.unwrap()).unwrap();
+    assert_eq!(
+        wallet.get_vrf_private_key(DEFAULT_ACCOUNT_INDEX, &standalone_vrf_pk).unwrap(),
+        standalone_vrf_sk
+    );
+
+    // Unknown keys are not found
+    let (_, unknown_vrf_pk) = VRFPrivateKey::new_from_rng(&mut rng, VRFKeyKind::Schnorrkel);
+    assert_eq!(
+        wallet.get_vrf_private_key(DEFAULT_ACCOUNT_INDEX, &unknown_vrf_pk),
+        Err(WalletError::KeyChainError(
+            KeyChainError::NoVRFPrivateKeyFound
+        ))
+    );
+
+    // Removing the password keeps the keys
+    wallet.encrypt_wallet(&None).unwrap();
+    assert_eq!(
+        wallet.get_vrf_private_key(DEFAULT_ACCOUNT_INDEX, &standalone_vrf_pk).unwrap(),
+        standalone_vrf_sk
+    );
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -4547,6 +4651,121 @@ async fn wallet_multiple_transactions_in_single_block(#[case] seed: Seed) {
     assert_eq!(coin_balance, total_change);
 }
 
+// Check that the automatic coin selection and the spendable balance only take into account
+// the outputs that have as many confirmations as the account's spending policy requires
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn spending_policy_min_confirmations(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_mainnet());
+
+    let db_name = random_ascii_alphanumeric_string(&mut rng, 10..20);
+    let mut wallet =
+        create_wallet_with_mnemonic_and_named_db(chain_config.clone(), MNEMONIC, &db_name);
+
+    let get_spendable_coins = |wallet: &DefaultWallet| {
+        wallet
+            .get_spendable_balance(DEFAULT_ACCOUNT_INDEX)
+            .unwrap()
+            .remove(&Currency::Coin)
+            .unwrap_or(Amount::ZERO)
+    };
+
+    assert_eq!(
+        wallet.get_account_spending_policy(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        AccountSpendingPolicy::default()
+    );
+    let external_min_confirmations = rng.gen_range(2..10);
+    let policy = AccountSpendingPolicy::new(0, external_min_confirmations);
+    wallet.set_account_spending_policy(DEFAULT_ACCOUNT_INDEX, policy).unwrap();
+
+    // The policy is persisted
+    let loaded_wallet = Wallet::load_wallet(
+        Arc::clone(&chain_config),
+        create_named_in_memory_store(&db_name),
+        None,
+        |_| Ok(()),
+        WalletControllerMode::Hot,
+        false,
+        |db_tx| SoftwareSignerProvider::load_from_database(chain_config.clone(), db_tx),
+    )
+    .unwrap()
+    .wallet()
+    .unwrap();
+    assert_eq!(
+        loaded_wallet.get_account_spending_policy(DEFAULT_ACCOUNT_INDEX).unwrap(),
+        policy
+    );
+
+    // Receive some coins; they don't have enough confirmations yet
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 1..NETWORK_FEE + 10000));
+    let _ = create_block(&chain_config, &mut wallet, vec![], block1_amount, 0);
+    assert_eq!(get_coin_balance(&wallet), block1_amount);
+    assert_eq!(get_spendable_coins(&wallet), Amount::ZERO);
+
+    let new_output = TxOutput::Transfer(
+        OutputValue::Coin(Amount::from_atoms(1)),
+        Destination::PublicKey(
+            crypto::key::PrivateKey::new_from_rng(&mut rng, KeyKind::Secp256k1Schnorr).1,
+        ),
+    );
+    let err = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [new_output.clone()],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            TxAdditionalInfo::new(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        WalletError::CoinSelectionError(UtxoSelectorError::NoUtxos)
+    );
+
+    // Add empty blocks until the received coins have enough confirmations
+    for height in 1..external_min_confirmations {
+        assert_eq!(get_spendable_coins(&wallet), Amount::ZERO);
+        let _ = create_block(
+            &chain_config,
+            &mut wallet,
+            vec![],
+            Amount::ZERO,
+            height as u64,
+        );
+    }
+    assert_eq!(get_spendable_coins(&wallet), block1_amount);
+
+    let transaction = wallet
+        .create_transaction_to_addresses(
+            DEFAULT_ACCOUNT_INDEX,
+            [new_output],
+            SelectedInputs::Utxos(vec![]),
+            BTreeMap::new(),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            FeeRate::from_amount_per_kb(Amount::ZERO),
+            TxAdditionalInfo::new(),
+        )
+        .await
+        .unwrap()
+        .tx;
+    wallet.add_unconfirmed_tx(transaction, &WalletEventsNoOp).unwrap();
+
+    // The unconfirmed change can be spent right away, unless the policy requires confirmations
+    // for change as well
+    let change = (block1_amount - Amount::from_atoms(1)).unwrap();
+    assert_eq!(get_spendable_coins(&wallet), change);
+
+    let policy = AccountSpendingPolicy::new(1, external_min_confirmations);
+    wallet.set_account_spending_policy(DEFAULT_ACCOUNT_INDEX, policy).unwrap();
+    assert_eq!(get_spendable_coins(&wallet), Amount::ZERO);
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
@@ -5825,6 +6044,121 @@ async fn test_add_standalone_multisig(#[case] seed: Seed) {
     assert!(statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
 }
 
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn combine_multisig_signatures(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+    let chain_config = Arc::new(create_regtest());
+
+    let mut wallet1 = create_wallet_with_mnemonic(chain_config.clone(), MNEMONIC);
+    let mut wallet2 = create_wallet_with_mnemonic(chain_config.clone(), MNEMONIC2);
+
+    let (_, address1) = wallet1.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    let pub_key1 = wallet1.find_public_key(DEFAULT_ACCOUNT_INDEX, address1.into_object()).unwrap();
+    let (_, address2) = wallet2.get_new_address(DEFAULT_ACCOUNT_INDEX).unwrap();
+    let pub_key2 = wallet2.find_public_key(DEFAULT_ACCOUNT_INDEX, address2.into_object()).unwrap();
+
+    let challenge = ClassicMultisigChallenge::new(
+        &chain_config,
+        NonZeroU8::new(2).unwrap(),
+        vec![pub_key1, pub_key2],
+    )
+    .unwrap();
+    let multisig_hash = wallet1
+        .add_standalone_multisig(DEFAULT_ACCOUNT_INDEX, challenge.clone(), None)
+        .unwrap();
+    wallet2.add_standalone_multisig(DEFAULT_ACCOUNT_INDEX, challenge, None).unwrap();
+
+    let multisig_address =
+        Address::new(&chain_config, Destination::ClassicMultisig(multisig_hash)).unwrap();
+
+    let block1_amount = Amount::from_atoms(rng.gen_range(NETWORK_FEE + 100..NETWORK_FEE + 10000));
+    let output = make_address_output(multisig_address.clone().into_object(), block1_amount);
+    let tx =
+        SignedTransaction::new(Transaction::new(0, vec![], vec![output]).unwrap(), vec![]).unwrap();
+    let block1 = Block::new(
+        vec![tx.clone()],
+        chain_config.genesis_block_id(),
+        chain_config.genesis_block().timestamp(),
+        ConsensusData::None,
+        BlockReward::new(vec![]),
+    )
+    .unwrap();
+    scan_wallet(&mut wallet1, BlockHeight::new(0), vec![block1.clone()]);
+    scan_wallet(&mut wallet2, BlockHeight::new(0), vec![block1]);
+
+    let spend_multisig_tx = Transaction::new(
+        0,
+        vec![TxInput::from_utxo(OutPointSourceId::Transaction(tx.transaction().get_id()), 0)],
+        vec![TxOutput::Transfer(
+            OutputValue::Coin(Amount::from_atoms(1)),
+            Destination::AnyoneCanSpend,
+        )],
+    )
+    .unwrap();
+    let spend_multisig_tx = PartiallySignedTransaction::new_for_wallet(
+        spend_multisig_tx,
+        vec![None; 1],
+        vec![Some(tx.outputs()[0].clone())],
+        vec![Some(multisig_address.as_object().clone())],
+        None,
+        PtxAdditionalInfo::new(),
+    )
+    .unwrap();
+    let tokens_additional_info = TokensAdditionalInfo::new();
+
+    // Both cosigners sign the unsigned transaction in parallel
+    let (ptx1, _, statuses) = wallet1
+        .sign_raw_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            spend_multisig_tx.clone(),
+            &tokens_additional_info,
+        )
+        .await
+        .unwrap();
+    assert!(!statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
+    let (ptx2, _, statuses) = wallet2
+        .sign_raw_transaction(
+            DEFAULT_ACCOUNT_INDEX,
+            spend_multisig_tx.clone(),
+            &tokens_additional_info,
+        )
+        .await
+        .unwrap();
+    assert!(!statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
+
+    // Combining the unsigned transaction with the partially signed ones changes nothing
+    assert_eq!(
+        wallet1
+            .combine_partially_signed_transactions(
+                DEFAULT_ACCOUNT_INDEX,
+                vec![spend_multisig_tx, ptx1.clone()],
+            )
+            .unwrap(),
+        ptx1
+    );
+
+    let combined = wallet1
+        .combine_partially_signed_transactions(DEFAULT_ACCOUNT_INDEX, vec![ptx1, ptx2])
+        .unwrap();
+
+    // The combined transaction is fully signed, so there is nothing left to sign
+    let (combined, prev_statuses, statuses) = wallet2
+        .sign_raw_transaction(DEFAULT_ACCOUNT_INDEX, combined, &tokens_additional_info)
+        .await
+        .unwrap();
+    assert!(prev_statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
+    assert!(statuses.iter().all(|s| *s == SignatureStatus::FullySigned));
+    combined.into_signed_tx().unwrap();
+
+    assert!(matches!(
+        wallet1.combine_partially_signed_transactions(DEFAULT_ACCOUNT_INDEX, vec![]),
+        Err(WalletError::NoPartiallySignedTransactionsToCombine)
+    ));
+}
+
 #[rstest]
 #[trace]
 #[case(Seed::from_entropy())]
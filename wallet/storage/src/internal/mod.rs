@@ -103,6 +103,7 @@ impl<B: storage::Backend> Store<B> {
         tx.encrypt_root_keys(&sym_key)?;
         tx.encrypt_seed_phrase(&sym_key)?;
         tx.encrypt_standalone_private_keys(&sym_key)?;
+        tx.encrypt_standalone_vrf_private_keys(&sym_key)?;
         tx.commit()?;
 
         self.encryption_state = EncryptionState::Unlocked(sym_key);
@@ -152,6 +153,14 @@ impl<B: storage::Backend> Store<B> {
     pub fn dump_raw(&self) -> crate::Result<storage::raw::StorageContents<Schema>> {
         self.storage.transaction_ro()?.dump_raw().map_err(crate::Error::from)
     }
+
+    /// Copy the database contents into a new storage with the given backend.
+    ///
+    /// The data is copied as is, so the private keys in the copy are encrypted with the same
+    /// password, if any.
+    pub fn backup<B2: storage::Backend>(&self, backend: B2) -> crate::Result<Store<B2>> {
+        Store::new_from_dump(backend, self.dump_raw()?)
+    }
 }
 
 impl<'tx, B: storage::Backend + 'tx> Transactional<'tx> for Store<B> {
@@ -22,12 +22,13 @@ use crate::{
 };
 use common::{
     address::Address,
-    chain::{block::timestamp::BlockTimestamp, Destination, SignedTransaction},
+    chain::{block::timestamp::BlockTimestamp, tokens::TokenId, Destination, SignedTransaction},
 };
 use crypto::{
     kdf::KdfChallenge,
     key::{extended::ExtendedPublicKey, PrivateKey},
     symkey::SymmetricKey,
+    vrf::VRFPrivateKey,
 };
 use serialization::{Codec, DecodeAll, Encode, EncodeLike};
 use storage::{schema, MakeMapRef};
@@ -36,16 +37,17 @@ use utils::{
     maybe_encrypted::{MaybeEncrypted, MaybeEncryptedError},
 };
 use wallet_types::{
-    account_id::{AccountAddress, AccountPublicKey},
+    account_id::{AccountAddress, AccountPublicKey, AccountVrfPublicKey},
     account_info::{
-        AccountVrfKeys, StandaloneMultisig, StandalonePrivateKey, StandaloneWatchOnlyKey,
+        AccountVrfKeys, StandaloneMultisig, StandalonePrivateKey, StandaloneVrfPrivateKey,
+        StandaloneWatchOnlyKey,
     },
     chain_info::ChainInfo,
     keys::{RootKeyConstant, RootKeys},
     seed_phrase::{SeedPhraseConstant, SerializableSeedPhrase},
     wallet_type::WalletType,
-    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountWalletCreatedTxId,
-    AccountWalletTxId, KeychainUsageState, WalletTx,
+    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountSpendingPolicy,
+    AccountWalletCreatedTxId, AccountWalletTxId, KeychainUsageState, TokenDisplayInfo, WalletTx,
 };
 
 use wallet_types::hw_data;
@@ -290,6 +292,17 @@ macro_rules! impl_read_ops {
                     .map(|iter| iter.map(|(key, value)| (key, value.label)).collect())
             }
 
+            fn get_account_standalone_vrf_private_keys(
+                &self,
+                account_id: &AccountId,
+            ) -> crate::Result<Vec<(AccountVrfPublicKey, Option<String>)>> {
+                self.storage
+                    .get::<db::DBStandaloneVrfPrivateKeys, _>()
+                    .prefix_iter_decoded(account_id)
+                    .map_err(crate::Error::from)
+                    .map(|iter| iter.map(|(key, value)| (key, value.label)).collect())
+            }
+
             fn get_keychain_usage_state(
                 &self,
                 id: &AccountKeyPurposeId,
@@ -347,6 +360,20 @@ macro_rules! impl_read_ops {
             ) -> crate::Result<Option<hw_data::HardwareWalletData>> {
                 self.read_value::<well_known::HardwareWalletData>()
             }
+
+            fn get_token_display_info(
+                &self,
+                token_id: &TokenId,
+            ) -> crate::Result<Option<TokenDisplayInfo>> {
+                self.read::<db::DBTokenDisplayInfos, _, _>(token_id)
+            }
+
+            fn get_account_spending_policy(
+                &self,
+                account_id: &AccountId,
+            ) -> crate::Result<Option<AccountSpendingPolicy>> {
+                self.read::<db::DBAccountSpendingPolicies, _, _>(account_id)
+            }
         }
 
         impl<'st, B: storage::Backend> $TxType<'st, B> {
@@ -472,6 +499,13 @@ where
         (**self).get_account_standalone_private_keys(account_id)
     }
 
+    fn get_account_standalone_vrf_private_keys(
+        &self,
+        account_id: &AccountId,
+    ) -> crate::Result<Vec<(AccountVrfPublicKey, Option<String>)>> {
+        (**self).get_account_standalone_vrf_private_keys(account_id)
+    }
+
     fn get_keychain_usage_state(
         &self,
         id: &AccountKeyPurposeId,
@@ -518,6 +552,20 @@ where
     fn get_hardware_wallet_data(&self) -> crate::Result<Option<hw_data::HardwareWalletData>> {
         (**self).get_hardware_wallet_data()
     }
+
+    fn get_token_display_info(
+        &self,
+        token_id: &TokenId,
+    ) -> crate::Result<Option<TokenDisplayInfo>> {
+        (**self).get_token_display_info(token_id)
+    }
+
+    fn get_account_spending_policy(
+        &self,
+        account_id: &AccountId,
+    ) -> crate::Result<Option<AccountSpendingPolicy>> {
+        (**self).get_account_spending_policy(account_id)
+    }
 }
 
 impl<B: storage::Backend> WalletStorageEncryptionRead for StoreTxRo<'_, B> {
@@ -580,6 +628,21 @@ macro_rules! impl_read_unlocked_ops {
                     }),
                 )
             }
+
+            fn get_account_standalone_vrf_private_key(
+                &self,
+                account_vrf_pubkey: &AccountVrfPublicKey,
+            ) -> crate::Result<Option<VRFPrivateKey>> {
+                Ok(
+                    self.read::<db::DBStandaloneVrfPrivateKeys, _, _>(account_vrf_pubkey)?.map(
+                        |v| {
+                            v.private_key
+                                .try_take(self.encryption_key)
+                                .expect("key was checked when unlocked")
+                        },
+                    ),
+                )
+            }
         }
     };
 }
@@ -606,6 +669,13 @@ where
     ) -> crate::Result<Option<PrivateKey>> {
         (**self).get_account_standalone_private_key(account_pubkey)
     }
+
+    fn get_account_standalone_vrf_private_key(
+        &self,
+        account_vrf_pubkey: &AccountVrfPublicKey,
+    ) -> crate::Result<Option<VRFPrivateKey>> {
+        (**self).get_account_standalone_vrf_private_key(account_vrf_pubkey)
+    }
 }
 
 macro_rules! impl_write_ops {
@@ -780,6 +850,22 @@ macro_rules! impl_write_ops {
             ) -> crate::Result<()> {
                 self.write_value::<well_known::HardwareWalletData>(&data)
             }
+
+            fn set_token_display_info(
+                &mut self,
+                token_id: &TokenId,
+                display_info: &TokenDisplayInfo,
+            ) -> crate::Result<()> {
+                self.write::<db::DBTokenDisplayInfos, _, _, _>(token_id, display_info)
+            }
+
+            fn set_account_spending_policy(
+                &mut self,
+                id: &AccountId,
+                policy: &AccountSpendingPolicy,
+            ) -> crate::Result<()> {
+                self.write::<db::DBAccountSpendingPolicies, _, _, _>(id, policy)
+            }
         }
 
         impl<'st, B: storage::Backend> $TxType<'st, B> {
@@ -880,6 +966,34 @@ impl<B: storage::Backend> WalletStorageEncryptionWrite for StoreTxRwUnlocked<'_,
             .into_iter()
             .try_for_each(|(k, v)| self.write::<db::DBStandalonePrivateKeys, _, _, _>(k, v))
     }
+
+    fn encrypt_standalone_vrf_private_keys(
+        &mut self,
+        new_encryption_key: &Option<SymmetricKey>,
+    ) -> crate::Result<()> {
+        let encrypted_standalone_vrf_private_keys: Vec<_> = self
+            .storage
+            .get::<db::DBStandaloneVrfPrivateKeys, _>()
+            .prefix_iter_decoded(&())?
+            .map(|(k, v)| {
+                let decrypted = v
+                    .private_key
+                    .try_take(self.encryption_key)
+                    .expect("key was checked when unlocked");
+                (
+                    k,
+                    StandaloneVrfPrivateKey {
+                        label: v.label,
+                        private_key: MaybeEncrypted::new(&decrypted, new_encryption_key),
+                    },
+                )
+            })
+            .collect();
+
+        encrypted_standalone_vrf_private_keys
+            .into_iter()
+            .try_for_each(|(k, v)| self.write::<db::DBStandaloneVrfPrivateKeys, _, _, _>(k, v))
+    }
 }
 
 /// Wallet data storage transaction
@@ -911,6 +1025,21 @@ impl<B: storage::Backend> WalletStorageWriteUnlocked for StoreTxRwUnlocked<'_, B
         )
     }
 
+    fn set_standalone_vrf_private_key(
+        &mut self,
+        id: &AccountVrfPublicKey,
+        key: &VRFPrivateKey,
+        label: Option<String>,
+    ) -> crate::Result<()> {
+        self.write::<db::DBStandaloneVrfPrivateKeys, _, _, _>(
+            id,
+            StandaloneVrfPrivateKey {
+                label,
+                private_key: MaybeEncrypted::new(key, self.encryption_key),
+            },
+        )
+    }
+
     fn set_seed_phrase(&mut self, seed_phrase: SerializableSeedPhrase) -> crate::Result<()> {
         let value = MaybeEncrypted::new(&seed_phrase, self.encryption_key);
         self.write::<db::DBSeedPhrase, _, _, _>(SeedPhraseConstant, value)
@@ -19,12 +19,13 @@ use crate::{
     WalletStorageReadUnlocked, WalletStorageWriteLocked, WalletStorageWriteUnlocked,
 };
 
+use common::chain::tokens::TokenId;
 use crypto::key::extended::{ExtendedKeyKind, ExtendedPrivateKey};
 use crypto::vrf::ExtendedVRFPrivateKey;
 use randomness::{CryptoRng, Rng};
 use rstest::rstest;
 use test_utils::random::{make_seedable_rng, Seed};
-use wallet_types::keys::RootKeys;
+use wallet_types::{keys::RootKeys, TokenDisplayInfo};
 
 fn gen_random_password(rng: &mut (impl Rng + CryptoRng)) -> String {
     (0..rng.gen_range(1..100)).map(|_| rng.gen::<char>()).collect()
@@ -117,3 +118,57 @@ fn compare_encrypt_and_decrypt_root_key(#[case] seed: Seed) {
         }
     })
 }
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn backup_copies_everything(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut store = Store::new(DefaultBackend::new_in_memory()).unwrap();
+
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        db_tx.set_storage_version(rng.gen()).unwrap();
+        db_tx
+            .set_token_display_info(
+                &TokenId::random_using(&mut rng),
+                &TokenDisplayInfo::new(b"TKN".to_vec(), rng.gen_range(0..20)),
+            )
+            .unwrap();
+        db_tx.commit().unwrap();
+
+        let password = Some(gen_random_password(&mut rng));
+        store.encrypt_private_keys(&password).unwrap();
+
+        let backup = store.backup(DefaultBackend::new_in_memory()).unwrap();
+        assert_eq!(backup.dump_raw().unwrap(), store.dump_raw().unwrap());
+        assert!(backup.is_encrypted());
+        assert!(backup.is_locked());
+    })
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn token_display_info_roundtrip(#[case] seed: Seed) {
+    utils::concurrency::model(move || {
+        let mut rng = make_seedable_rng(seed);
+        let mut store = Store::new(DefaultBackend::new_in_memory()).unwrap();
+        let token_id = TokenId::random_using(&mut rng);
+        let display_info = TokenDisplayInfo::new(b"TKN".to_vec(), rng.gen_range(0..20));
+
+        assert_eq!(
+            store.transaction_ro().unwrap().get_token_display_info(&token_id).unwrap(),
+            None
+        );
+
+        let mut db_tx = store.transaction_rw(None).unwrap();
+        db_tx.set_token_display_info(&token_id, &display_info).unwrap();
+        db_tx.commit().unwrap();
+
+        assert_eq!(
+            store.transaction_ro().unwrap().get_token_display_info(&token_id).unwrap(),
+            Some(display_info)
+        );
+    })
+}
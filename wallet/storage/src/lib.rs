@@ -21,26 +21,27 @@ pub mod schema;
 
 use common::{
     address::{Address, AddressError},
-    chain::{block::timestamp::BlockTimestamp, Destination, SignedTransaction},
+    chain::{block::timestamp::BlockTimestamp, tokens::TokenId, Destination, SignedTransaction},
 };
 use crypto::{
     kdf::KdfChallenge,
     key::{extended::ExtendedPublicKey, PrivateKey},
     symkey::SymmetricKey,
+    vrf::VRFPrivateKey,
 };
 pub use internal::{Store, StoreTxRo, StoreTxRoUnlocked, StoreTxRw, StoreTxRwUnlocked};
 use std::collections::BTreeMap;
 
 use wallet_types::{
-    account_id::{AccountAddress, AccountPublicKey},
+    account_id::{AccountAddress, AccountPublicKey, AccountVrfPublicKey},
     account_info::{AccountVrfKeys, StandaloneMultisig, StandaloneWatchOnlyKey},
     chain_info::ChainInfo,
     hw_data::HardwareWalletData,
     keys::RootKeys,
     seed_phrase::SerializableSeedPhrase,
     wallet_type::WalletType,
-    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountWalletCreatedTxId,
-    AccountWalletTxId, KeychainUsageState, WalletTx,
+    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountSpendingPolicy,
+    AccountWalletCreatedTxId, AccountWalletTxId, KeychainUsageState, TokenDisplayInfo, WalletTx,
 };
 
 /// Wallet Errors
@@ -96,6 +97,10 @@ pub trait WalletStorageReadLocked {
         &self,
         account_id: &AccountId,
     ) -> Result<Vec<(AccountPublicKey, Option<String>)>>;
+    fn get_account_standalone_vrf_private_keys(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<(AccountVrfPublicKey, Option<String>)>>;
     fn get_accounts_info(&self) -> crate::Result<BTreeMap<AccountId, AccountInfo>>;
     fn get_address(&self, id: &AccountDerivationPathId) -> Result<Option<String>>;
     fn get_addresses(
@@ -120,6 +125,11 @@ pub trait WalletStorageReadLocked {
     fn get_median_time(&self) -> Result<Option<BlockTimestamp>>;
     fn get_lookahead_size(&self) -> Result<u32>;
     fn get_hardware_wallet_data(&self) -> Result<Option<HardwareWalletData>>;
+    fn get_token_display_info(&self, token_id: &TokenId) -> Result<Option<TokenDisplayInfo>>;
+    fn get_account_spending_policy(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Option<AccountSpendingPolicy>>;
 }
 
 /// Queries on persistent wallet data with access to encrypted data
@@ -130,6 +140,10 @@ pub trait WalletStorageReadUnlocked: WalletStorageReadLocked {
         &self,
         account_pubkey: &AccountPublicKey,
     ) -> Result<Option<PrivateKey>>;
+    fn get_account_standalone_vrf_private_key(
+        &self,
+        account_vrf_pubkey: &AccountVrfPublicKey,
+    ) -> Result<Option<VRFPrivateKey>>;
 }
 
 /// Queries on persistent wallet data for encryption
@@ -200,6 +214,16 @@ pub trait WalletStorageWriteLocked: WalletStorageReadLocked {
     fn clear_public_keys(&mut self) -> Result<()>;
     fn clear_addresses(&mut self) -> Result<()>;
     fn set_hardware_wallet_data(&mut self, data: HardwareWalletData) -> Result<()>;
+    fn set_token_display_info(
+        &mut self,
+        token_id: &TokenId,
+        display_info: &TokenDisplayInfo,
+    ) -> Result<()>;
+    fn set_account_spending_policy(
+        &mut self,
+        id: &AccountId,
+        policy: &AccountSpendingPolicy,
+    ) -> Result<()>;
 }
 
 /// Modifying operations on persistent wallet data with access to encrypted data
@@ -214,6 +238,12 @@ pub trait WalletStorageWriteUnlocked: WalletStorageReadUnlocked + WalletStorageW
         key: &PrivateKey,
         label: Option<String>,
     ) -> Result<()>;
+    fn set_standalone_vrf_private_key(
+        &mut self,
+        id: &AccountVrfPublicKey,
+        key: &VRFPrivateKey,
+        label: Option<String>,
+    ) -> Result<()>;
 }
 
 /// Modifying operations on persistent wallet data for encryption
@@ -226,6 +256,10 @@ pub trait WalletStorageEncryptionWrite {
         &mut self,
         new_encryption_key: &Option<SymmetricKey>,
     ) -> Result<()>;
+    fn encrypt_standalone_vrf_private_keys(
+        &mut self,
+        new_encryption_key: &Option<SymmetricKey>,
+    ) -> Result<()>;
 }
 
 /// Marker trait for types where read/write operations are run in a transaction
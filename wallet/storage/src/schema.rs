@@ -15,18 +15,19 @@
 
 //! Wallet database schema
 
-use common::chain::SignedTransaction;
+use common::chain::{tokens::TokenId, SignedTransaction};
 use crypto::key::extended::ExtendedPublicKey;
 use utils::maybe_encrypted::MaybeEncrypted;
 use wallet_types::{
-    account_id::{AccountAddress, AccountPublicKey},
+    account_id::{AccountAddress, AccountPublicKey, AccountVrfPublicKey},
     account_info::{
-        AccountVrfKeys, StandaloneMultisig, StandalonePrivateKey, StandaloneWatchOnlyKey,
+        AccountVrfKeys, StandaloneMultisig, StandalonePrivateKey, StandaloneVrfPrivateKey,
+        StandaloneWatchOnlyKey,
     },
     keys::{RootKeyConstant, RootKeys},
     seed_phrase::{SeedPhraseConstant, SerializableSeedPhrase},
-    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountWalletCreatedTxId,
-    AccountWalletTxId, KeychainUsageState, WalletTx,
+    AccountDerivationPathId, AccountId, AccountInfo, AccountKeyPurposeId, AccountSpendingPolicy,
+    AccountWalletCreatedTxId, AccountWalletTxId, KeychainUsageState, TokenDisplayInfo, WalletTx,
 };
 
 storage::decl_schema! {
@@ -62,5 +63,11 @@ storage::decl_schema! {
         pub DBStandaloneMultisigKeys: Map<AccountAddress, StandaloneMultisig>,
         /// Store for standalone private keys added to accounts
         pub DBStandalonePrivateKeys: Map<AccountPublicKey, StandalonePrivateKey>,
+        /// Store for standalone VRF private keys imported into accounts
+        pub DBStandaloneVrfPrivateKeys: Map<AccountVrfPublicKey, StandaloneVrfPrivateKey>,
+        /// Store for the display information (ticker, decimals) of the tokens known to the wallet
+        pub DBTokenDisplayInfos: Map<TokenId, TokenDisplayInfo>,
+        /// Store for the minimum confirmation policy of each account's automatic coin selection
+        pub DBAccountSpendingPolicies: Map<AccountId, AccountSpendingPolicy>,
     }
 }
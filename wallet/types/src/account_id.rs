@@ -21,6 +21,7 @@ use common::{
 };
 use crypto::key::hdkd::derivation_path::DerivationPath;
 use crypto::key::{extended::ExtendedPublicKey, PublicKey};
+use crypto::vrf::VRFPublicKey;
 use serialization::{Decode, Encode};
 use storage::HasPrefix;
 
@@ -83,3 +84,4 @@ pub type AccountDerivationPathId = AccountPrefixedId<DerivationPath>;
 pub type AccountKeyPurposeId = AccountPrefixedId<KeyPurpose>;
 pub type AccountAddress = AccountPrefixedId<Destination>;
 pub type AccountPublicKey = AccountPrefixedId<PublicKey>;
+pub type AccountVrfPublicKey = AccountPrefixedId<VRFPublicKey>;
@@ -19,7 +19,7 @@ use common::{
 };
 use crypto::{
     key::{extended::ExtendedPublicKey, hdkd::u31::U31, PrivateKey, PublicKey},
-    vrf::ExtendedVRFPublicKey,
+    vrf::{ExtendedVRFPublicKey, VRFPrivateKey},
 };
 use serialization::{Decode, Encode};
 use utils::maybe_encrypted::MaybeEncrypted;
@@ -102,6 +102,42 @@ impl AccountInfo {
     }
 }
 
+/// The minimum number of confirmations the outputs of an account must have before they can be
+/// picked by the automatic coin selection. The outputs of the transactions created by the account
+/// itself (i.e. change) and the ones received from others can have different thresholds.
+///
+/// Zero confirmations means that unconfirmed outputs can also be spent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+pub struct AccountSpendingPolicy {
+    change_min_confirmations: u32,
+    external_min_confirmations: u32,
+}
+
+impl AccountSpendingPolicy {
+    pub fn new(change_min_confirmations: u32, external_min_confirmations: u32) -> Self {
+        Self {
+            change_min_confirmations,
+            external_min_confirmations,
+        }
+    }
+
+    pub fn change_min_confirmations(&self) -> u32 {
+        self.change_min_confirmations
+    }
+
+    pub fn external_min_confirmations(&self) -> u32 {
+        self.external_min_confirmations
+    }
+
+    pub fn min_confirmations(&self, is_change: bool) -> u32 {
+        if is_change {
+            self.change_min_confirmations
+        } else {
+            self.external_min_confirmations
+        }
+    }
+}
+
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct AccountVrfKeys {
     pub account_vrf_key: ExtendedVRFPublicKey,
@@ -140,6 +176,14 @@ pub struct StandalonePrivateKey {
     pub private_key: MaybeEncrypted<PrivateKey>,
 }
 
+/// A VRF private key that was imported into an account, as opposed to the ones derived
+/// from the account's VRF key chain
+#[derive(Encode, Decode)]
+pub struct StandaloneVrfPrivateKey {
+    pub label: Option<String>,
+    pub private_key: MaybeEncrypted<VRFPrivateKey>,
+}
+
 #[derive(Debug, Clone)]
 pub enum StandaloneAddressDetails {
     WatchOnly(StandaloneWatchOnlyKey),
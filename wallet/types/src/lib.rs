@@ -23,6 +23,7 @@ pub mod partially_signed_transaction;
 pub mod scan_blockchain;
 pub mod seed_phrase;
 pub mod signature_status;
+pub mod token_display_info;
 pub mod utxo_types;
 pub mod wallet_tx;
 pub mod wallet_type;
@@ -32,9 +33,10 @@ pub use account_id::{
     AccountDerivationPathId, AccountId, AccountKeyPurposeId, AccountWalletCreatedTxId,
     AccountWalletTxId,
 };
-pub use account_info::AccountInfo;
+pub use account_info::{AccountInfo, AccountSpendingPolicy};
 pub use currency::Currency;
 pub use keys::{KeyPurpose, KeychainUsageState, RootKeys};
+pub use token_display_info::TokenDisplayInfo;
 pub use wallet_tx::{BlockInfo, WalletTx};
 
 use std::collections::BTreeMap;
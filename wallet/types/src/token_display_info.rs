@@ -0,0 +1,79 @@
+// Copyright (c) 2021-2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common::primitives::Amount;
+use serialization::{Decode, Encode};
+
+/// The information needed to display a token's amounts to the user.
+///
+/// Both the ticker and the number of decimals are set when the token is issued and cannot be
+/// changed afterwards, so it's safe to store them in the wallet once they are fetched from
+/// the node.
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq)]
+pub struct TokenDisplayInfo {
+    ticker: Vec<u8>,
+    num_decimals: u8,
+}
+
+impl TokenDisplayInfo {
+    pub fn new(ticker: Vec<u8>, num_decimals: u8) -> Self {
+        Self {
+            ticker,
+            num_decimals,
+        }
+    }
+
+    pub fn ticker(&self) -> &[u8] {
+        &self.ticker
+    }
+
+    pub fn num_decimals(&self) -> u8 {
+        self.num_decimals
+    }
+
+    /// Format the amount of atoms as a decimal string, e.g. "1.5" for 150 atoms and 2 decimals.
+    pub fn format_amount(&self, amount: Amount) -> String {
+        amount.into_fixedpoint_str(self.num_decimals)
+    }
+
+    /// Parse a decimal string into the amount of atoms; fails if the string has more
+    /// fractional digits than the token supports.
+    pub fn parse_amount(&self, amount_str: &str) -> Option<Amount> {
+        Amount::from_fixedpoint_str(amount_str, self.num_decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse() {
+        let info = TokenDisplayInfo::new(b"XYZ".to_vec(), 2);
+
+        assert_eq!(info.format_amount(Amount::from_atoms(150)), "1.5");
+        assert_eq!(info.format_amount(Amount::from_atoms(7)), "0.07");
+        assert_eq!(info.parse_amount("1.5"), Some(Amount::from_atoms(150)));
+        assert_eq!(info.parse_amount("0.07"), Some(Amount::from_atoms(7)));
+        assert_eq!(info.parse_amount("3"), Some(Amount::from_atoms(300)));
+        assert_eq!(info.parse_amount("0.001"), None);
+        assert_eq!(info.parse_amount("abc"), None);
+
+        let info = TokenDisplayInfo::new(b"NODEC".to_vec(), 0);
+        assert_eq!(info.format_amount(Amount::from_atoms(150)), "150");
+        assert_eq!(info.parse_amount("150"), Some(Amount::from_atoms(150)));
+        assert_eq!(info.parse_amount("1.5"), None);
+    }
+}
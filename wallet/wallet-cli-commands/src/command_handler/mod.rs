@@ -18,7 +18,7 @@ mod local_state;
 use std::{fmt::Write, str::FromStr};
 
 use common::{
-    address::Address,
+    address::{payment_uri::PaymentUri, Address},
     chain::{
         config::checkpoints_data::print_block_heights_ids_as_checkpoints_data, ChainConfig,
         Destination, SignedTransaction, TxOutput, UtxoOutPoint,
@@ -40,7 +40,7 @@ use wallet_controller::types::{GenericTokenTransfer, WalletExtraInfo};
 use wallet_rpc_client::wallet_rpc_traits::{PartialOrSignedTx, WalletInterface};
 use wallet_rpc_lib::types::{
     Balances, ComposedTransaction, ControllerConfig, HardwareWalletType, MnemonicInfo,
-    NewSubmittedTransaction, NftMetadata, RpcInspectTransaction, RpcNewTransaction,
+    NewSubmittedTransaction, NftMetadata, RpcInspectTransaction, RpcNewTransaction, RpcSendPreview,
     RpcSignatureStats, RpcSignatureStatus, RpcStandaloneAddressDetails, RpcValidatedSignatures,
     TokenMetadata,
 };
@@ -383,6 +383,15 @@ where
                 ))
             }
 
+            ColdWalletCommand::BackupWallet { path } => {
+                self.non_empty_wallet().await?.backup_wallet(path.clone()).await?;
+
+                Ok(ConsoleCommand::Print(format!(
+                    "Successfully written the wallet backup to {}",
+                    path.display()
+                )))
+            }
+
             ColdWalletCommand::ShowSeedPhrase => {
                 let phrase = self.non_empty_wallet().await?.get_seed_phrase().await?;
 
@@ -453,6 +462,51 @@ where
                 Ok(ConsoleCommand::Print(qr_code_string))
             }
 
+            ColdWalletCommand::CreatePaymentUri {
+                address,
+                amount,
+                token_id,
+                label,
+                message,
+            } => {
+                let destination = Address::from_string(chain_config, address).map_err(|_| {
+                    WalletCliCommandError::InvalidInput("Invalid address".to_string())
+                })?;
+                let mut payment_uri = PaymentUri::new(chain_config, destination);
+                if let Some(amount) = amount {
+                    payment_uri = payment_uri.with_amount(amount);
+                }
+                if let Some(token_id) = token_id {
+                    let token_id = Address::from_string(chain_config, token_id).map_err(|_| {
+                        WalletCliCommandError::InvalidInput("Invalid token id".to_string())
+                    })?;
+                    payment_uri = payment_uri.with_token_id(token_id);
+                }
+                if let Some(label) = label {
+                    payment_uri = payment_uri.with_label(label);
+                }
+                if let Some(message) = message {
+                    payment_uri = payment_uri.with_message(message);
+                }
+
+                // Make sure that the URI will be accepted, e.g. that the amount doesn't have
+                // too many decimals.
+                let payment_uri = payment_uri.to_string();
+                PaymentUri::from_string(chain_config, &payment_uri)
+                    .map_err(|err| WalletCliCommandError::InvalidInput(err.to_string()))?;
+
+                let qr_code_string = if self.no_qr {
+                    String::new()
+                } else {
+                    let qr_code = qrcode_or_error_string(&payment_uri);
+                    format!("\n\nOr contained in the QR code:\n{qr_code}")
+                };
+
+                Ok(ConsoleCommand::Print(format!(
+                    "{payment_uri}{qr_code_string}"
+                )))
+            }
+
             ColdWalletCommand::NewAddress => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let address = wallet.issue_address(selected_account).await?;
@@ -477,6 +531,66 @@ where
                 ))
             }
 
+            ColdWalletCommand::AddressDerivationInfo { address } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let info = wallet.get_address_derivation_info(selected_account, address).await?;
+
+                let stored_address =
+                    info.stored_address.as_ref().map_or("<missing>", |address| address.as_str());
+                let consistency = if info.is_consistent {
+                    "The stored records match the re-derived ones."
+                } else {
+                    "WARNING: the stored records don't match the re-derived ones, the wallet file may have been tampered with or corrupted!"
+                };
+
+                Ok(ConsoleCommand::Print(format!(
+                    "Derivation path: {}\nPublic key: {}\nStored address: {}\nRe-derived address: {}\n{}",
+                    info.derivation_path,
+                    info.public_key_hex.hex_encode(),
+                    stored_address,
+                    info.derived_address,
+                    consistency,
+                )))
+            }
+
+            ColdWalletCommand::VerifyAddresses {
+                mnemonic,
+                passphrase,
+            } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let report =
+                    wallet.verify_addresses(selected_account, mnemonic, passphrase).await?;
+
+                if report.is_consistent() {
+                    return Ok(ConsoleCommand::Print(format!(
+                        "Success. All {} keys and addresses match the ones derived from the seed.",
+                        report.checked_keys
+                    )));
+                }
+
+                let missing = "<missing>";
+                let mut output = format!(
+                    "WARNING: the wallet file doesn't match the seed, it may have been tampered with or corrupted!\nChecked keys: {}",
+                    report.checked_keys
+                );
+                if !report.account_public_key_matches {
+                    output.push_str(
+                        "\nThe account extended public key doesn't match the one derived from the seed.",
+                    );
+                }
+                for mismatch in &report.mismatches {
+                    output.push_str(&format!(
+                        "\nPath {}: expected address {}, stored public key address {}, stored address {}",
+                        mismatch.derivation_path,
+                        mismatch.expected_address,
+                        mismatch.stored_public_key_address.as_ref().map_or(missing, |a| a.as_str()),
+                        mismatch.stored_address.as_ref().map_or(missing, |a| a.as_str()),
+                    ));
+                }
+
+                Ok(ConsoleCommand::Print(output))
+            }
+
             ColdWalletCommand::AccountExtendedPublicKey => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 let key = wallet.get_account_extended_public_key(selected_account).await?;
@@ -925,6 +1039,35 @@ where
                 })
             }
 
+            WalletCommand::SetAccountSpendingPolicy {
+                min_confirmations_change,
+                min_confirmations_external,
+            } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                wallet
+                    .set_account_spending_policy(
+                        selected_account,
+                        min_confirmations_change,
+                        min_confirmations_external,
+                    )
+                    .await?;
+
+                Ok(ConsoleCommand::Print(
+                    "Success, the spending policy has been updated".to_owned(),
+                ))
+            }
+
+            WalletCommand::ShowAccountSpendingPolicy => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let policy = wallet.get_account_spending_policy(selected_account).await?;
+
+                Ok(ConsoleCommand::Print(format!(
+                    "Minimum confirmations for change: {}\n\
+                     Minimum confirmations for external payments: {}",
+                    policy.min_confirmations_change, policy.min_confirmations_external,
+                )))
+            }
+
             WalletCommand::StandaloneAddressLabelRename { address, label } => {
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
                 wallet.standalone_address_label_rename(selected_account, address, label).await?;
@@ -1328,6 +1471,24 @@ where
                 Ok(ConsoleCommand::Print(output))
             }
 
+            WalletCommand::GetSpendableBalance => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let (coins, tokens) =
+                    wallet.get_spendable_balance(selected_account).await?.into_coins_and_tokens();
+
+                let coins = coins.decimal();
+                let mut output = format!("Spendable coins amount: {coins}\n");
+
+                for (token_id, amount) in tokens {
+                    let amount = amount.decimal();
+                    writeln!(&mut output, "Token: {token_id} spendable amount: {amount}")
+                        .expect("Writing to a memory buffer should not fail");
+                }
+                output.pop();
+
+                Ok(ConsoleCommand::Print(output))
+            }
+
             WalletCommand::ListUtxo {
                 utxo_type,
                 utxo_states,
@@ -1413,6 +1574,8 @@ where
                 address,
                 amount,
                 utxos,
+                dry_run,
+                unsigned,
             } => {
                 let input_utxos: Vec<UtxoOutPoint> = utxos
                     .iter()
@@ -1420,12 +1583,33 @@ where
                     .collect::<Result<Vec<_>, WalletCliCommandError<N>>>(
                 )?;
                 let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                if dry_run {
+                    let preview = wallet
+                        .preview_send_coins(
+                            selected_account,
+                            address,
+                            amount,
+                            input_utxos,
+                            !unsigned,
+                            self.config,
+                        )
+                        .await?;
+                    return Ok(ConsoleCommand::Print(format_send_preview(preview)));
+                }
+
                 let new_tx = wallet
                     .send_coins(selected_account, address, amount, input_utxos, self.config)
                     .await?;
                 Ok(Self::new_tx_command(new_tx, chain_config))
             }
 
+            WalletCommand::PayPaymentUri { uri, amount } => {
+                let (wallet, selected_account) = wallet_and_selected_acc(&mut self.wallet).await?;
+                let new_tx =
+                    wallet.pay_payment_uri(selected_account, uri, amount, self.config).await?;
+                Ok(Self::new_tx_command(new_tx, chain_config))
+            }
+
             WalletCommand::SweepFromAddress {
                 destination_address,
                 addresses,
@@ -1989,6 +2173,38 @@ fn format_tx_to_be_broadcasted(
     format!("{summary}\nThe transaction was created and is ready to be submitted:\n{hex}")
 }
 
+fn format_send_preview(preview: RpcSendPreview) -> String {
+    let RpcSendPreview {
+        tx_id,
+        tx,
+        is_signed,
+        fees,
+        fee_rate,
+        inputs,
+        change,
+    } = preview;
+
+    let mut output = format!(
+        "Transaction ID: {}\nFee rate: {} coins per 1000 bytes\n",
+        id_to_hex_string(*tx_id.as_hash()),
+        fee_rate.decimal()
+    );
+    format_fees(&mut output, &fees);
+    writeln!(
+        output,
+        "\nSelected inputs:\n{}\nChange outputs:\n{}",
+        serde_json::to_string(&inputs).expect("ok"),
+        serde_json::to_string(&change).expect("ok")
+    )
+    .expect("Writing to a memory buffer should not fail");
+
+    let tx_kind = if is_signed { "signed" } else { "unsigned" };
+    output.push_str(&format!(
+        "The transaction was not broadcast; the hex encoded {tx_kind} transaction is:\n{tx}"
+    ));
+    output
+}
+
 fn format_signature_status((idx, status): (usize, &RpcSignatureStatus)) -> String {
     let status = match status {
         RpcSignatureStatus::FullySigned => "FullySigned".to_owned(),
@@ -299,6 +299,14 @@ pub enum ColdWalletCommand {
     #[clap(name = "wallet-lock-private-keys")]
     LockPrivateKeys,
 
+    /// Write a copy of the wallet into a new wallet file.
+    /// The private keys in the copy stay encrypted with the current password, if any.
+    #[clap(name = "wallet-backup")]
+    BackupWallet {
+        /// The path of the backup file, which must not exist
+        path: PathBuf,
+    },
+
     #[clap(name = "wallet-show-seed-phrase")]
     ShowSeedPhrase,
 
@@ -321,6 +329,25 @@ pub enum ColdWalletCommand {
         address: String,
     },
 
+    /// Creates a payment URI requesting a payment to the provided address
+    #[clap(name = "payment-uri-create")]
+    CreatePaymentUri {
+        /// The address that should receive the payment
+        address: String,
+        /// The requested amount, in decimal format
+        #[arg(long = "amount")]
+        amount: Option<DecimalAmount>,
+        /// The token id of the requested tokens; if not specified, coins are requested
+        #[arg(long = "token-id")]
+        token_id: Option<String>,
+        /// A label for the receiver, e.g. their name
+        #[arg(long = "label")]
+        label: Option<String>,
+        /// A message describing the purpose of the payment
+        #[arg(long = "message")]
+        message: Option<String>,
+    },
+
     #[clap(name = "address-new")]
     NewAddress,
 
@@ -339,6 +366,28 @@ pub enum ColdWalletCommand {
         public_key_hash: String,
     },
 
+    /// Show the derivation path and the public key behind the specified address,
+    /// together with the address re-derived from the account extended public key.
+    #[clap(name = "address-derivation-info")]
+    AddressDerivationInfo {
+        address: String,
+    },
+
+    /// Re-derive all the keys and addresses of the selected account from the seed and compare
+    /// them with the ones stored in the wallet, to detect a tampered or corrupted wallet file.
+    ///
+    /// If the mnemonic is not specified, the root key stored in the wallet is used,
+    /// which requires the wallet to be unlocked.
+    #[clap(name = "address-verify-derivation")]
+    VerifyAddresses {
+        /// Mnemonic (seed) phrase (12, 15, or 24 words as a single quoted argument).
+        mnemonic: Option<String>,
+
+        /// Passphrase along the mnemonic
+        #[arg(long = "passphrase")]
+        passphrase: Option<String>,
+    },
+
     #[clap(name = "address-show")]
     ShowAddresses {
         /// Include the change addresses along with the receiving addresses
@@ -449,6 +498,21 @@ pub enum WalletCommand {
     #[clap(name = "account-rename")]
     RenameAccount { name: Option<String> },
 
+    /// Set the minimum number of confirmations the outputs of the selected account must have
+    /// before they can be picked by the automatic coin selection.
+    /// Zero means that unconfirmed outputs can be spent as well.
+    #[clap(name = "account-set-spending-policy")]
+    SetAccountSpendingPolicy {
+        /// The minimum confirmations of the change outputs of the account's own transactions
+        min_confirmations_change: u32,
+        /// The minimum confirmations of the outputs received from others
+        min_confirmations_external: u32,
+    },
+
+    /// Show the minimum confirmation policy of the selected account's automatic coin selection
+    #[clap(name = "account-spending-policy")]
+    ShowAccountSpendingPolicy,
+
     /// Switch to a given wallet account.
     #[clap(name = "account-select")]
     SelectAccount { account_index: U31 },
@@ -476,6 +540,11 @@ pub enum WalletCommand {
         utxo_states: Vec<CliUtxoState>,
     },
 
+    /// Show the balance that can be spent by the automatic coin selection, i.e. the unlocked
+    /// coins and tokens that satisfy the spending policy of the selected account
+    #[clap(name = "account-spendable-balance")]
+    GetSpendableBalance,
+
     #[clap(name = "standalone-address-label-rename")]
     StandaloneAddressLabelRename {
         /// The existing standalone address
@@ -687,6 +756,25 @@ pub enum WalletCommand {
         /// block(000000000000000000059fa50103b9683e51e5aba83b8a34c9b98ce67d66136c,2)
         #[arg(default_values_t = Vec::<String>::new())]
         utxos: Vec<String>,
+        /// Only build the transaction and show its id, fees, selected inputs and change,
+        /// without broadcasting it
+        #[arg(long = "dry-run", default_value_t = false)]
+        dry_run: bool,
+        /// Don't sign the transaction built in the dry-run mode, so that the wallet doesn't need
+        /// to be unlocked
+        #[arg(long = "unsigned", default_value_t = false, requires = "dry_run")]
+        unsigned: bool,
+    },
+
+    /// Pay according to the provided payment URI, sending coins or tokens to the address it specifies.
+    /// The wallet will automatically calculate the required fees.
+    #[clap(name = "payment-uri-pay")]
+    PayPaymentUri {
+        /// The payment URI
+        uri: String,
+        /// The amount to be paid, in decimal format; can only be specified if the URI doesn't contain it
+        #[arg(long = "amount")]
+        amount: Option<DecimalAmount>,
     },
 
     /// Sweep all spendable coins or tokens from the specified (or all) addresses to the given destination address.
@@ -43,7 +43,7 @@ use wallet_types::{
         OrderAdditionalInfo, PartiallySignedTransaction, PartiallySignedTransactionWalletExt as _,
         PoolAdditionalInfo, PtxAdditionalInfo, TokenAdditionalInfo, TokensAdditionalInfo,
     },
-    Currency,
+    Currency, TokenDisplayInfo,
 };
 
 use crate::{runtime_wallet::RuntimeWallet, types::Balances, ControllerError};
@@ -61,6 +61,29 @@ pub async fn fetch_token_info<T: NodeInterface>(
         )))
 }
 
+/// Get the display information of a token, preferring the one stored in the wallet;
+/// only ask the node if the token is not known to the wallet yet.
+pub async fn fetch_token_display_info<
+    T: NodeInterface,
+    B: storage::BackendWithSendableTransactions,
+>(
+    rpc_client: &T,
+    wallet: &RuntimeWallet<B>,
+    token_id: TokenId,
+) -> Result<TokenDisplayInfo, ControllerError<T>> {
+    if let Some(display_info) =
+        wallet.get_token_display_info(&token_id).map_err(ControllerError::WalletError)?
+    {
+        return Ok(display_info);
+    }
+
+    let token_info = fetch_token_info(rpc_client, token_id).await?;
+    Ok(TokenDisplayInfo::new(
+        token_info.token_ticker().to_vec(),
+        token_info.token_number_of_decimals(),
+    ))
+}
+
 pub async fn fetch_token_infos_into<T: NodeInterface>(
     rpc_client: &T,
     token_ids: &BTreeSet<TokenId>,
@@ -220,8 +243,9 @@ where
     }
 }
 
-pub async fn into_balances<T: NodeInterface>(
+pub async fn into_balances<T: NodeInterface, B: storage::BackendWithSendableTransactions>(
     rpc_client: &T,
+    wallet: &RuntimeWallet<B>,
     chain_config: &ChainConfig,
     mut balances: BTreeMap<Currency, Amount>,
 ) -> Result<Balances, ControllerError<T>> {
@@ -236,9 +260,8 @@ pub async fn into_balances<T: NodeInterface>(
                 Currency::Token(token_id) => token_id,
             };
 
-            fetch_token_info(rpc_client, token_id).await.map(|info| {
-                let decimals = info.token_number_of_decimals();
-                let amount = RpcAmountOut::from_amount_no_padding(amount, decimals);
+            fetch_token_display_info(rpc_client, wallet, token_id).await.map(|info| {
+                let amount = RpcAmountOut::from_amount_no_padding(amount, info.num_decimals());
                 let token_id = RpcAddress::new(chain_config, token_id).expect("addressable");
                 (token_id, amount)
             })
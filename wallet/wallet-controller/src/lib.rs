@@ -29,8 +29,11 @@ const NORMAL_DELAY: Duration = Duration::from_secs(1);
 const ERROR_DELAY: Duration = Duration::from_secs(10);
 
 use blockprod::BlockProductionError;
-use chainstate::tx_verifier::{
-    self, error::ScriptError, input_check::signature_only_check::SignatureOnlyVerifiable,
+use chainstate::{
+    tx_verifier::{
+        self, error::ScriptError, input_check::signature_only_check::SignatureOnlyVerifiable,
+    },
+    ChainInfo,
 };
 use futures::{never::Never, stream::FuturesOrdered, TryStreamExt};
 use helpers::{
@@ -94,7 +97,7 @@ use wallet::signer::SignerError;
 use wallet::{
     account::{
         currency_grouper::{self},
-        TransactionToSign,
+        OutputPrivacyPolicy, TransactionToSign,
     },
     destination_getters::{get_tx_output_destination, HtlcSpendingCondition},
     signer::{software_signer::SoftwareSignerProvider, trezor_signer::SelectedDevice},
@@ -117,7 +120,7 @@ use wallet_types::{
     signature_status::SignatureStatus,
     wallet_type::{WalletControllerMode, WalletType},
     with_locked::WithLocked,
-    Currency,
+    AccountSpendingPolicy, Currency, TokenDisplayInfo,
 };
 
 #[cfg(feature = "trezor")]
@@ -193,6 +196,20 @@ pub struct ControllerConfig {
     pub broadcast_to_mempool: bool,
 }
 
+/// The number of seconds to check for staking on top of the tip at `now`, in addition to the first
+/// one after the tip's timestamp.
+///
+/// Returns `None` if there is no such timestamp yet or the node is in the initial block download.
+fn staking_seconds_to_check(chain_info: &ChainInfo, now: BlockTimestamp) -> Option<u64> {
+    if chain_info.is_initial_block_download {
+        return None;
+    }
+
+    now.as_int_seconds()
+        .checked_sub(chain_info.best_block_timestamp.as_int_seconds())
+        .and_then(|seconds| seconds.checked_sub(1))
+}
+
 pub struct Controller<T, W, B: storage::Backend + 'static> {
     chain_config: Arc<ChainConfig>,
 
@@ -526,6 +543,24 @@ where
         self.wallet.encrypt_wallet(password).map_err(ControllerError::WalletError)
     }
 
+    /// Writes a copy of the wallet into a new wallet file at `file_path`, which must not exist.
+    ///
+    /// The copy can be opened as a regular wallet; its private keys are encrypted with the same
+    /// password as the ones of this wallet, if any.
+    pub fn backup_wallet(&self, file_path: impl AsRef<Path>) -> Result<(), ControllerError<N>> {
+        utils::ensure!(
+            !file_path.as_ref().exists(),
+            ControllerError::WalletFileError(
+                file_path.as_ref().to_owned(),
+                "File already exists".to_owned()
+            )
+        );
+
+        self.wallet
+            .backup_to_file(file_path.as_ref())
+            .map_err(ControllerError::WalletError)
+    }
+
     /// Unlocks the wallet using the specified password.
     ///
     /// # Arguments
@@ -569,6 +604,12 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    /// Set the policy for the order of the outputs and the change amounts of the transactions
+    /// created by the wallet.
+    pub fn set_output_privacy_policy(&mut self, output_privacy_policy: OutputPrivacyPolicy) {
+        self.wallet.set_output_privacy_policy(output_privacy_policy)
+    }
+
     pub fn wallet_info(&self) -> WalletInfo {
         let (wallet_id, account_names) = self.wallet.wallet_info();
         let hw_wallet_info = self.wallet.hardware_wallet_info();
@@ -592,17 +633,57 @@ where
     }
 
     pub async fn get_token_number_of_decimals(
-        &self,
+        &mut self,
         token_id: TokenId,
     ) -> Result<u8, ControllerError<N>> {
-        Ok(self.get_token_info(token_id).await?.token_number_of_decimals())
+        Ok(self.get_token_display_info(token_id).await?.num_decimals())
     }
 
+    /// Get the ticker and the number of decimals of a token; the values are taken from
+    /// the wallet if they're stored there, otherwise they are fetched from the node and stored.
+    pub async fn get_token_display_info(
+        &mut self,
+        token_id: TokenId,
+    ) -> Result<TokenDisplayInfo, ControllerError<N>> {
+        if let Some(display_info) = self
+            .wallet
+            .get_token_display_info(&token_id)
+            .map_err(ControllerError::WalletError)?
+        {
+            return Ok(display_info);
+        }
+
+        let token_info = self.get_token_info(token_id).await?;
+        Ok(TokenDisplayInfo::new(
+            token_info.token_ticker().to_vec(),
+            token_info.token_number_of_decimals(),
+        ))
+    }
+
+    /// Fetch the token info from the node, storing the token's display info in the wallet
+    /// if it's not there yet.
     pub async fn get_token_info(
-        &self,
+        &mut self,
         token_id: TokenId,
     ) -> Result<RPCTokenInfo, ControllerError<N>> {
-        fetch_token_info(&self.rpc_client, token_id).await
+        let token_info = fetch_token_info(&self.rpc_client, token_id).await?;
+
+        if self
+            .wallet
+            .get_token_display_info(&token_id)
+            .map_err(ControllerError::WalletError)?
+            .is_none()
+        {
+            let display_info = TokenDisplayInfo::new(
+                token_info.token_ticker().to_vec(),
+                token_info.token_number_of_decimals(),
+            );
+            self.wallet
+                .set_token_display_info(&token_id, &display_info)
+                .map_err(ControllerError::WalletError)?;
+        }
+
+        Ok(token_info)
     }
 
     pub async fn generate_block_by_pool(
@@ -677,6 +758,85 @@ where
         Err(last_error)
     }
 
+    /// Check locally whether the pool can produce a block at `height` with a timestamp within
+    /// `seconds_to_check + 1` seconds after the tip's timestamp.
+    ///
+    /// The kernel hash is evaluated in the wallet using the pool's VRF key, the sealed epoch
+    /// randomness and the pool balances provided by the node, so the key is only sent to the
+    /// node once the pool is known to be eligible.
+    async fn can_pool_stake_at(
+        &self,
+        pool_id: PoolId,
+        height: BlockHeight,
+        seconds_to_check: u64,
+    ) -> Result<bool, ControllerError<N>> {
+        let timestamps = self
+            .find_timestamps_for_staking(pool_id, height, Some(height), seconds_to_check, false)
+            .await?;
+
+        Ok(timestamps.get(&height).is_some_and(|timestamps| !timestamps.is_empty()))
+    }
+
+    /// Try to produce a block with any of the account's pools that is eligible to stake at
+    /// the moment. Returns `None` if no pool is eligible.
+    ///
+    /// Pools for which the local eligibility check fails are skipped, the node is only asked
+    /// to generate a block by a pool that is known to be eligible.
+    async fn try_stake_block(
+        &self,
+        account_index: U31,
+    ) -> Result<Option<Block>, ControllerError<N>> {
+        let pools = self
+            .wallet
+            .get_pool_ids(account_index, WalletPoolsFilter::Stake)
+            .map_err(ControllerError::WalletError)?;
+        if pools.is_empty() {
+            return Ok(None);
+        }
+
+        let chain_info = self
+            .rpc_client
+            .chainstate_info()
+            .await
+            .map_err(ControllerError::NodeCallError)?;
+        let now = BlockTimestamp::from_time(get_time());
+        let seconds_to_check = match staking_seconds_to_check(&chain_info, now) {
+            Some(seconds_to_check) => seconds_to_check,
+            None => return Ok(None),
+        };
+        let next_height = chain_info.best_block_height.next_height();
+
+        for (pool_id, _) in pools {
+            match self.can_pool_stake_at(pool_id, next_height, seconds_to_check).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
+                    log::warn!(
+                        "Skipping pool {pool_id}, its staking eligibility check failed: {err}"
+                    );
+                    continue;
+                }
+            }
+
+            let block_res = self
+                .generate_block_by_pool(
+                    account_index,
+                    pool_id,
+                    vec![],
+                    vec![],
+                    PackingStrategy::FillSpaceFromMempool,
+                )
+                .await;
+
+            match block_res {
+                Ok(block) => return Ok(Some(block)),
+                Err(err) => log::debug!("Block generation by pool {pool_id} failed: {err}"),
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Try to generate the `block_count` number of blocks.
     /// The function may return an error early if some attempt fails.
     pub async fn generate_blocks(
@@ -764,6 +924,16 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn update_account_spending_policy(
+        &mut self,
+        account_index: U31,
+        policy: AccountSpendingPolicy,
+    ) -> Result<(), ControllerError<N>> {
+        self.wallet
+            .set_account_spending_policy(account_index, policy)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn stop_staking(&mut self, account_index: U31) -> Result<(), ControllerError<N>> {
         log::info!("Stop staking, account_index: {}", account_index);
         self.staking_started.remove(&account_index);
@@ -1251,7 +1421,7 @@ where
         // add any leftover inputs
         fees.extend(inputs);
 
-        into_balances(&self.rpc_client, &self.chain_config, fees).await
+        into_balances(&self.rpc_client, &self.wallet, &self.chain_config, fees).await
     }
 
     fn group_outputs(
@@ -1328,16 +1498,9 @@ where
             }
 
             for account_index in staking_started.iter() {
-                let generate_res = self
-                    .generate_block(
-                        *account_index,
-                        vec![],
-                        vec![],
-                        PackingStrategy::FillSpaceFromMempool,
-                    )
-                    .await;
+                let generate_res = self.try_stake_block(*account_index).await;
 
-                if let Ok(block) = generate_res {
+                if let Ok(Some(block)) = generate_res {
                     log::info!(
                         "New block generated successfully, with block id: {:x}",
                         block.get_id()
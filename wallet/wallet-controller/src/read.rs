@@ -19,7 +19,10 @@ use std::collections::BTreeMap;
 
 use common::{
     address::Address,
-    chain::{ChainConfig, DelegationId, Destination, PoolId, Transaction, TxOutput, UtxoOutPoint},
+    chain::{
+        timelock::OutputTimeLock, ChainConfig, DelegationId, Destination, PoolId, Transaction,
+        TxOutput, UtxoOutPoint,
+    },
     primitives::{id::WithId, Amount, Id},
 };
 use crypto::{
@@ -27,13 +30,17 @@ use crypto::{
         extended::ExtendedPublicKey,
         hdkd::{child_number::ChildNumber, u31::U31},
     },
-    vrf::VRFPublicKey,
+    vrf::{VRFPrivateKey, VRFPublicKey},
 };
 use futures::{stream::FuturesUnordered, FutureExt, TryStreamExt};
 use node_comm::node_traits::NodeInterface;
 use utils::tap_log::TapLog;
 use wallet::{
-    account::{transaction_list::TransactionList, DelegationData, PoolData, TxInfo},
+    account::{
+        transaction_list::{TransactionInfo, TransactionList},
+        DelegationData, PoolData, TxInfo,
+    },
+    key_chain::{KeyDerivationInfo, KeysVerificationReport},
     wallet::WalletPoolsFilter,
 };
 use wallet_types::{
@@ -41,7 +48,7 @@ use wallet_types::{
     utxo_types::{UtxoStates, UtxoTypes},
     wallet_tx::TxData,
     with_locked::WithLocked,
-    Currency, KeyPurpose, KeychainUsageState,
+    AccountSpendingPolicy, Currency, KeyPurpose, KeychainUsageState,
 };
 
 use crate::{
@@ -115,7 +122,23 @@ where
         with_locked: WithLocked,
     ) -> Result<Balances, ControllerError<T>> {
         let balances = self.get_balance(utxo_states, with_locked)?;
-        super::into_balances(&self.rpc_client, self.chain_config, balances).await
+        super::into_balances(&self.rpc_client, self.wallet, self.chain_config, balances).await
+    }
+
+    pub fn get_spending_policy(&self) -> Result<AccountSpendingPolicy, ControllerError<T>> {
+        self.wallet
+            .get_account_spending_policy(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// The balance that can be spent by the automatic coin selection according to the spending
+    /// policy of the account
+    pub async fn get_spendable_balance(&self) -> Result<Balances, ControllerError<T>> {
+        let balances = self
+            .wallet
+            .get_spendable_balance(self.account_index)
+            .map_err(ControllerError::WalletError)?;
+        super::into_balances(&self.rpc_client, self.wallet, self.chain_config, balances).await
     }
 
     pub fn get_multisig_utxos(
@@ -141,6 +164,17 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    /// Returns the absolute height or time at which the given utxo becomes spendable,
+    /// if it is time locked
+    pub fn get_utxo_unlock_point(
+        &self,
+        outpoint: &UtxoOutPoint,
+    ) -> Result<Option<OutputTimeLock>, ControllerError<T>> {
+        self.wallet
+            .get_utxo_unlock_point(self.account_index, outpoint)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn pending_transactions(&self) -> Result<Vec<WithId<&'a Transaction>>, ControllerError<T>> {
         self.wallet
             .pending_transactions(self.account_index)
@@ -176,6 +210,15 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn get_transaction_info(
+        &self,
+        transaction_id: Id<Transaction>,
+    ) -> Result<TransactionInfo, ControllerError<T>> {
+        self.wallet
+            .get_transaction_info(self.account_index, transaction_id)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_all_issued_addresses(
         &self,
         key_purpose: KeyPurpose,
@@ -207,6 +250,43 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn get_standalone_vrf_public_keys(
+        &self,
+    ) -> Result<Vec<(Address<VRFPublicKey>, Option<String>)>, ControllerError<T>> {
+        self.wallet
+            .get_standalone_vrf_public_keys(self.account_index)
+            .map_err(ControllerError::WalletError)
+    }
+
+    /// Export the VRF private key of the account; requires the wallet to be unlocked
+    pub fn get_vrf_private_key(
+        &self,
+        public_key: &VRFPublicKey,
+    ) -> Result<VRFPrivateKey, ControllerError<T>> {
+        self.wallet
+            .get_vrf_private_key(self.account_index, public_key)
+            .map_err(ControllerError::WalletError)
+    }
+
+    pub fn get_key_derivation_info(
+        &self,
+        address: Destination,
+    ) -> Result<KeyDerivationInfo, ControllerError<T>> {
+        self.wallet
+            .get_key_derivation_info(self.account_index, address)
+            .map_err(ControllerError::WalletError)
+    }
+
+    pub fn verify_account_keys(
+        &self,
+        mnemonic: Option<&str>,
+        passphrase: Option<&str>,
+    ) -> Result<KeysVerificationReport, ControllerError<T>> {
+        self.wallet
+            .verify_account_keys(self.account_index, mnemonic, passphrase)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub fn get_addresses_usage(
         &self,
         key_purpose: KeyPurpose,
@@ -273,7 +353,9 @@ where
             .get_all_standalone_address_details(self.account_index, address)
             .map_err(ControllerError::WalletError)?;
 
-        let balances = super::into_balances(&self.rpc_client, self.chain_config, balances).await?;
+        let balances =
+            super::into_balances(&self.rpc_client, self.wallet, self.chain_config, balances)
+                .await?;
 
         Ok(AccountStandaloneKeyDetails {
             address,
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 
 use common::{
     address::{pubkeyhash::PublicKeyHash, Address},
@@ -22,6 +23,7 @@ use common::{
         htlc::HashedTimelockContract,
         output_value::OutputValue,
         signature::inputsig::arbitrary_message::ArbitraryMessageSignature,
+        timelock::OutputTimeLock,
         tokens::{IsTokenUnfreezable, Metadata, RPCFungibleTokenInfo, TokenId, TokenIssuance},
         AccountCommand, AccountOutPoint, DelegationId, Destination, GenBlock, OrderAccountCommand,
         OrderId, PoolId, RpcOrderInfo, SignedTransaction, SignedTransactionIntent, Transaction,
@@ -35,15 +37,17 @@ use crypto::{
         hdkd::{child_number::ChildNumber, u31::U31},
         PrivateKey, PublicKey,
     },
-    vrf::VRFPublicKey,
+    vrf::{VRFPrivateKey, VRFPublicKey},
 };
 use mempool::FeeRate;
 use wallet::{
     account::{
-        transaction_list::TransactionList, CoinSelectionAlgo, DelegationData, PoolData, TxInfo,
+        transaction_list::{TransactionInfo, TransactionList},
+        CoinSelectionAlgo, DelegationData, OutputPrivacyPolicy, PoolData, TxInfo,
         UnconfirmedTokenInfo,
     },
     destination_getters::HtlcSpendingCondition,
+    key_chain::{KeyDerivationInfo, KeysVerificationReport},
     send_request::{SelectedInputs, StakePoolCreationArguments},
     signer::software_signer::SoftwareSignerProvider,
     wallet::WalletPoolsFilter,
@@ -61,7 +65,8 @@ use wallet_types::{
     utxo_types::{UtxoState, UtxoStates, UtxoTypes},
     wallet_tx::TxData,
     with_locked::WithLocked,
-    Currency, KeyPurpose, KeychainUsageState, SignedTxWithFees,
+    AccountSpendingPolicy, Currency, KeyPurpose, KeychainUsageState, SignedTxWithFees,
+    TokenDisplayInfo,
 };
 
 #[cfg(feature = "trezor")]
@@ -153,6 +158,14 @@ where
         }
     }
 
+    pub fn backup_to_file(&self, path: &Path) -> Result<(), WalletError> {
+        match self {
+            RuntimeWallet::Software(w) => w.backup_to_file(path),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.backup_to_file(path),
+        }
+    }
+
     pub fn unlock_wallet(&mut self, password: &String) -> Result<(), WalletError> {
         match self {
             RuntimeWallet::Software(w) => w.unlock_wallet(password),
@@ -169,6 +182,14 @@ where
         }
     }
 
+    pub fn set_output_privacy_policy(&mut self, output_privacy_policy: OutputPrivacyPolicy) {
+        match self {
+            RuntimeWallet::Software(w) => w.set_output_privacy_policy(output_privacy_policy),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.set_output_privacy_policy(output_privacy_policy),
+        }
+    }
+
     pub fn set_lookahead_size(
         &mut self,
         lookahead_size: u32,
@@ -197,6 +218,29 @@ where
         }
     }
 
+    pub fn get_token_display_info(
+        &self,
+        token_id: &TokenId,
+    ) -> WalletResult<Option<TokenDisplayInfo>> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_token_display_info(token_id),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_token_display_info(token_id),
+        }
+    }
+
+    pub fn set_token_display_info(
+        &mut self,
+        token_id: &TokenId,
+        display_info: &TokenDisplayInfo,
+    ) -> WalletResult<()> {
+        match self {
+            RuntimeWallet::Software(w) => w.set_token_display_info(token_id, display_info),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.set_token_display_info(token_id, display_info),
+        }
+    }
+
     pub fn create_next_account(
         &mut self,
         name: Option<String>,
@@ -220,6 +264,29 @@ where
         }
     }
 
+    pub fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<AccountSpendingPolicy> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_account_spending_policy(account_index),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_account_spending_policy(account_index),
+        }
+    }
+
+    pub fn set_account_spending_policy(
+        &mut self,
+        account_index: U31,
+        policy: AccountSpendingPolicy,
+    ) -> WalletResult<()> {
+        match self {
+            RuntimeWallet::Software(w) => w.set_account_spending_policy(account_index, policy),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.set_account_spending_policy(account_index, policy),
+        }
+    }
+
     pub fn get_pos_gen_block_data(
         &self,
         account_index: U31,
@@ -300,6 +367,18 @@ where
         }
     }
 
+    pub fn get_utxo_unlock_point(
+        &self,
+        account_index: U31,
+        outpoint: &UtxoOutPoint,
+    ) -> WalletResult<Option<OutputTimeLock>> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_utxo_unlock_point(account_index, outpoint),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_utxo_unlock_point(account_index, outpoint),
+        }
+    }
+
     pub fn get_transactions_to_be_broadcast(
         &mut self,
     ) -> Result<Vec<SignedTransaction>, WalletError> {
@@ -323,6 +402,17 @@ where
         }
     }
 
+    pub fn get_spendable_balance(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<BTreeMap<Currency, Amount>> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_spendable_balance(account_index),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_spendable_balance(account_index),
+        }
+    }
+
     pub fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -392,6 +482,18 @@ where
         }
     }
 
+    pub fn get_transaction_info(
+        &self,
+        account_index: U31,
+        transaction_id: Id<Transaction>,
+    ) -> WalletResult<TransactionInfo> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_transaction_info(account_index, transaction_id),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_transaction_info(account_index, transaction_id),
+        }
+    }
+
     pub fn get_all_issued_addresses(
         &self,
         account_index: U31,
@@ -445,6 +547,29 @@ where
         }
     }
 
+    pub fn get_standalone_vrf_public_keys(
+        &self,
+        account_index: U31,
+    ) -> WalletResult<Vec<(Address<VRFPublicKey>, Option<String>)>> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_standalone_vrf_public_keys(account_index),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(_) => Err(WalletError::UnsupportedHardwareWalletOperation),
+        }
+    }
+
+    pub fn get_vrf_private_key(
+        &self,
+        account_index: U31,
+        public_key: &VRFPublicKey,
+    ) -> WalletResult<VRFPrivateKey> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_vrf_private_key(account_index, public_key),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(_) => Err(WalletError::UnsupportedHardwareWalletOperation),
+        }
+    }
+
     pub fn get_addresses_usage(
         &self,
         account_index: U31,
@@ -631,6 +756,33 @@ where
         }
     }
 
+    pub fn get_key_derivation_info(
+        &self,
+        account_index: U31,
+        address: Destination,
+    ) -> WalletResult<KeyDerivationInfo> {
+        match self {
+            RuntimeWallet::Software(w) => w.get_key_derivation_info(account_index, address),
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(w) => w.get_key_derivation_info(account_index, address),
+        }
+    }
+
+    pub fn verify_account_keys(
+        &self,
+        account_index: U31,
+        mnemonic: Option<&str>,
+        passphrase: Option<&str>,
+    ) -> WalletResult<KeysVerificationReport> {
+        match self {
+            RuntimeWallet::Software(w) => {
+                w.verify_account_keys(account_index, mnemonic, passphrase)
+            }
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(_) => Err(WalletError::UnsupportedHardwareWalletOperation),
+        }
+    }
+
     pub fn get_vrf_key(
         &mut self,
         account_index: U31,
@@ -642,6 +794,21 @@ where
         }
     }
 
+    pub fn add_standalone_vrf_private_key(
+        &mut self,
+        account_index: U31,
+        private_key: VRFPrivateKey,
+        label: Option<String>,
+    ) -> WalletResult<Address<VRFPublicKey>> {
+        match self {
+            RuntimeWallet::Software(w) => {
+                w.add_standalone_vrf_private_key(account_index, private_key, label)
+            }
+            #[cfg(feature = "trezor")]
+            RuntimeWallet::Trezor(_) => Err(WalletError::UnsupportedHardwareWalletOperation),
+        }
+    }
+
     pub async fn issue_new_token(
         &mut self,
         account_index: U31,
@@ -40,7 +40,7 @@ use crypto::{
         hdkd::{child_number::ChildNumber, u31::U31},
         PrivateKey, PublicKey,
     },
-    vrf::VRFPublicKey,
+    vrf::{VRFPrivateKey, VRFPublicKey},
 };
 use logging::log;
 use mempool::FeeRate;
@@ -76,7 +76,8 @@ use crate::{
     },
     runtime_wallet::RuntimeWallet,
     types::{
-        Balances, GenericCurrencyTransfer, NewTransaction, PreparedTransaction, SweepFromAddresses,
+        Balances, GenericCurrencyTransfer, NewTransaction, PreparedTransaction,
+        PreviewedTransaction, SendPreview, SweepFromAddresses,
     },
     ControllerConfig, ControllerError,
 };
@@ -287,6 +288,16 @@ where
             .map_err(ControllerError::WalletError)
     }
 
+    pub fn add_standalone_vrf_private_key(
+        &mut self,
+        private_key: VRFPrivateKey,
+        label: Option<String>,
+    ) -> Result<Address<VRFPublicKey>, ControllerError<T>> {
+        self.wallet
+            .add_standalone_vrf_private_key(self.account_index, private_key, label)
+            .map_err(ControllerError::WalletError)
+    }
+
     pub async fn issue_new_token(
         &mut self,
         address: Address<Destination>,
@@ -609,6 +620,74 @@ where
         .await
     }
 
+    /// Create the same transaction as `send_to_address` would, but don't broadcast it and don't
+    /// add it to the wallet.
+    /// If `sign` is false, the transaction is left unsigned, so the wallet doesn't need to
+    /// be unlocked.
+    pub async fn preview_send_to_address(
+        &mut self,
+        address: Address<Destination>,
+        amount: Amount,
+        selected_utxos: Vec<UtxoOutPoint>,
+        sign: bool,
+    ) -> Result<SendPreview, ControllerError<T>> {
+        self.check_tokens_in_selected_utxo(&selected_utxos).await?;
+
+        let output = make_address_output(address.into_object(), amount);
+        let (current_fee_rate, consolidate_fee_rate) =
+            self.get_current_and_consolidation_fee_rate().await?;
+
+        let (tx, fees) = if sign {
+            let SignedTxWithFees { tx, fees } = self
+                .wallet
+                .create_transaction_to_addresses(
+                    self.account_index,
+                    [output.clone()],
+                    SelectedInputs::Utxos(selected_utxos),
+                    BTreeMap::new(),
+                    current_fee_rate,
+                    consolidate_fee_rate,
+                    TxAdditionalInfo::new(),
+                )
+                .await?;
+            (PreviewedTransaction::Signed(tx), fees)
+        } else {
+            let (ptx, fees) = self.wallet.create_unsigned_transaction_to_addresses(
+                self.account_index,
+                [output.clone()],
+                SelectedInputs::Utxos(selected_utxos),
+                None,
+                BTreeMap::new(),
+                current_fee_rate,
+                consolidate_fee_rate,
+                PtxAdditionalInfo::new(),
+            )?;
+            (PreviewedTransaction::Unsigned(ptx), fees)
+        };
+
+        let mut inputs = Vec::with_capacity(tx.transaction().inputs().len());
+        for outpoint in tx.transaction().inputs().iter().filter_map(|input| input.utxo_outpoint()) {
+            let utxo = fetch_utxo(&self.rpc_client, self.wallet, outpoint).await?;
+            inputs.push((outpoint.clone(), utxo));
+        }
+
+        // The outputs may be shuffled, so the requested one is found by value.
+        let mut change = tx.transaction().outputs().to_vec();
+        if let Some(pos) = change.iter().position(|change_output| *change_output == output) {
+            change.remove(pos);
+        }
+
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
+
+        Ok(SendPreview {
+            tx,
+            fees,
+            fee_rate: current_fee_rate,
+            inputs,
+            change,
+        })
+    }
+
     /// Create a transaction that transfers all the coins and tokens to the destination address
     /// and broadcast it to the mempool.
     pub async fn sweep_addresses(
@@ -744,7 +823,7 @@ where
             )
             .map_err(ControllerError::WalletError)?;
 
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok((req, fees))
     }
@@ -887,7 +966,7 @@ where
             PtxAdditionalInfo::new(),
         )?;
 
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok((tx, fees))
     }
@@ -1198,7 +1277,7 @@ where
             )
             .await?;
 
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok(PreparedTransaction { tx, fees })
     }
@@ -1552,7 +1631,7 @@ where
         .await?;
 
         let (tx, broadcasted) = self.broadcast_to_mempool_if_needed(tx).await?;
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok(NewTransaction {
             tx,
@@ -1612,7 +1691,7 @@ where
     {
         let SignedTxWithFees { tx, fees } = self.create_token_tx(token_info, tx_maker).await?;
         let (tx, broadcasted) = self.broadcast_to_mempool_if_needed(tx).await?;
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok(NewTransaction {
             tx,
@@ -1663,7 +1742,7 @@ where
         .map_err(ControllerError::WalletError)?;
 
         let (tx, broadcasted) = self.broadcast_to_mempool_if_needed(tx).await?;
-        let fees = into_balances(&self.rpc_client, self.chain_config, fees).await?;
+        let fees = into_balances(&self.rpc_client, self.wallet, self.chain_config, fees).await?;
 
         Ok((
             NewTransaction {
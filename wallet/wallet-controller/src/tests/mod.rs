@@ -14,6 +14,7 @@
 // limitations under the License.
 
 mod compose_transaction_tests;
+mod staking_tests;
 pub mod test_utils;
 
 #[ctor::ctor]
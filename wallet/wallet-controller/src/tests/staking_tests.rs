@@ -0,0 +1,208 @@
+// Copyright (c) 2021-2025 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use rstest::rstest;
+
+use chainstate::ChainInfo;
+use common::{
+    chain::{block::timestamp::BlockTimestamp, config::create_regtest, Destination, GenBlock},
+    primitives::{per_thousand::PerThousand, Amount, BlockHeight, Id},
+};
+use mempool::FeeRate;
+use node_comm::{mock::ClonableMockNodeInterface, node_traits::MockNodeInterface};
+use randomness::{CryptoRng, Rng};
+use test_utils::random::{make_seedable_rng, Seed};
+use wallet::{
+    send_request::StakePoolCreationArguments,
+    wallet::{test_helpers::create_wallet_with_mnemonic, WalletPoolsFilter},
+    wallet_events::WalletEventsNoOp,
+    DefaultWallet,
+};
+use wallet_types::account_info::DEFAULT_ACCOUNT_INDEX;
+
+use crate::{
+    runtime_wallet::RuntimeWallet,
+    staking_seconds_to_check,
+    tests::test_utils::{create_block_scan_wallet, random_pub_key, wallet_new_dest, MNEMONIC},
+    Controller,
+};
+
+fn make_chain_info(
+    best_block_timestamp: BlockTimestamp,
+    is_initial_block_download: bool,
+    rng: &mut impl Rng,
+) -> ChainInfo {
+    ChainInfo {
+        best_block_height: BlockHeight::new(rng.gen_range(1..1000)),
+        best_block_id: Id::<GenBlock>::random_using(rng),
+        best_block_timestamp,
+        median_time: best_block_timestamp,
+        is_initial_block_download,
+    }
+}
+
+// Create a wallet with the specified number of stake pools that it can stake with.
+async fn create_wallet_with_pools(
+    pool_count: usize,
+    rng: &mut (impl Rng + CryptoRng),
+) -> DefaultWallet {
+    let chain_config = Arc::new(create_regtest());
+    let mut wallet = create_wallet_with_mnemonic(Arc::clone(&chain_config), MNEMONIC);
+
+    let mut txs = vec![];
+    for height in 0..=pool_count {
+        let reward_dest = wallet_new_dest(&mut wallet);
+        let reward = Amount::from_atoms(rng.gen_range(1000..2000));
+        create_block_scan_wallet(
+            &chain_config,
+            &mut wallet,
+            std::mem::take(&mut txs),
+            reward,
+            reward_dest,
+            height as u64,
+        );
+
+        if height < pool_count {
+            let tx = wallet
+                .create_stake_pool(
+                    DEFAULT_ACCOUNT_INDEX,
+                    FeeRate::from_amount_per_kb(Amount::ZERO),
+                    FeeRate::from_amount_per_kb(Amount::ZERO),
+                    StakePoolCreationArguments {
+                        amount: reward,
+                        margin_ratio_per_thousand: PerThousand::new_from_rng(rng),
+                        cost_per_block: Amount::ZERO,
+                        decommission_key: Destination::PublicKey(random_pub_key(rng)),
+                        staker_key: None,
+                        vrf_public_key: None,
+                    },
+                )
+                .await
+                .unwrap()
+                .tx;
+            txs.push(tx);
+        }
+    }
+
+    let pools = wallet.get_pool_ids(DEFAULT_ACCOUNT_INDEX, WalletPoolsFilter::Stake).unwrap();
+    assert_eq!(pools.len(), pool_count);
+
+    wallet
+}
+
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+fn seconds_to_check(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let tip_time = rng.gen_range(1000..1_000_000);
+    let tip_timestamp = BlockTimestamp::from_int_seconds(tip_time);
+    let chain_info = make_chain_info(tip_timestamp, false, &mut rng);
+
+    // The tip is in the future or has just been produced
+    let now = BlockTimestamp::from_int_seconds(rng.gen_range(0..=tip_time));
+    assert_eq!(staking_seconds_to_check(&chain_info, now), None);
+
+    // Only the timestamp right after the tip's one can be used
+    let now = BlockTimestamp::from_int_seconds(tip_time + 1);
+    assert_eq!(staking_seconds_to_check(&chain_info, now), Some(0));
+
+    let elapsed = rng.gen_range(1..1000);
+    let now = BlockTimestamp::from_int_seconds(tip_time + elapsed);
+    assert_eq!(
+        staking_seconds_to_check(&chain_info, now),
+        Some(elapsed - 1)
+    );
+
+    // Nothing is checked during the initial block download
+    let chain_info = make_chain_info(tip_timestamp, true, &mut rng);
+    assert_eq!(staking_seconds_to_check(&chain_info, now), None);
+}
+
+// The node is not asked to generate a block if the pool eligibility can't be checked locally;
+// the chain info is only requested once for all the pools.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn pools_skipped_if_eligibility_check_fails(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_regtest());
+    let pool_count = rng.gen_range(2..5);
+    let wallet = create_wallet_with_pools(pool_count, &mut rng).await;
+
+    let tip_timestamp = BlockTimestamp::from_int_seconds(rng.gen_range(1000..1_000_000));
+    let chain_info = make_chain_info(tip_timestamp, false, &mut rng);
+
+    let mut node_mock = MockNodeInterface::new();
+    node_mock
+        .expect_chainstate_info()
+        .times(1)
+        .returning(move || Ok(chain_info.clone()));
+    node_mock
+        .expect_collect_timestamp_search_data()
+        .times(pool_count)
+        .returning(|_, _, _, _, _| Err(anyhow::anyhow!("search data unavailable")));
+    node_mock.expect_generate_block().never();
+    node_mock.expect_generate_block_e2e().never();
+
+    let controller = Controller::new_unsynced(
+        chain_config,
+        ClonableMockNodeInterface::from_mock(node_mock),
+        RuntimeWallet::Software(wallet),
+        WalletEventsNoOp,
+    );
+
+    let block = controller.try_stake_block(DEFAULT_ACCOUNT_INDEX).await.unwrap();
+    assert_eq!(block, None);
+}
+
+// No pool is checked during the initial block download.
+#[rstest]
+#[trace]
+#[case(Seed::from_entropy())]
+#[tokio::test]
+async fn no_staking_in_ibd(#[case] seed: Seed) {
+    let mut rng = make_seedable_rng(seed);
+
+    let chain_config = Arc::new(create_regtest());
+    let pool_count = rng.gen_range(1..5);
+    let wallet = create_wallet_with_pools(pool_count, &mut rng).await;
+
+    let chain_info = make_chain_info(BlockTimestamp::from_int_seconds(0), true, &mut rng);
+
+    let mut node_mock = MockNodeInterface::new();
+    node_mock
+        .expect_chainstate_info()
+        .times(1)
+        .returning(move || Ok(chain_info.clone()));
+    node_mock.expect_collect_timestamp_search_data().never();
+    node_mock.expect_generate_block().never();
+    node_mock.expect_generate_block_e2e().never();
+
+    let controller = Controller::new_unsynced(
+        chain_config,
+        ClonableMockNodeInterface::from_mock(node_mock),
+        RuntimeWallet::Software(wallet),
+        WalletEventsNoOp,
+    );
+
+    let block = controller.try_stake_block(DEFAULT_ACCOUNT_INDEX).await.unwrap();
+    assert_eq!(block, None);
+}
@@ -13,7 +13,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use common::chain::{SignedTransaction, Transaction};
+use common::chain::{SignedTransaction, Transaction, TxOutput, UtxoOutPoint};
+use mempool::FeeRate;
 use serialization::hex_encoded::HexEncoded;
 use wallet_types::{
     partially_signed_transaction::PartiallySignedTransaction, signature_status::SignatureStatus,
@@ -81,3 +82,31 @@ pub struct PreparedTransaction {
     pub tx: SignedTransaction,
     pub fees: Balances,
 }
+
+/// The transaction of a send preview, which is only signed if requested
+pub enum PreviewedTransaction {
+    Signed(SignedTransaction),
+    Unsigned(PartiallySignedTransaction),
+}
+
+impl PreviewedTransaction {
+    pub fn transaction(&self) -> &Transaction {
+        match self {
+            Self::Signed(tx) => tx.transaction(),
+            Self::Unsigned(ptx) => ptx.tx(),
+        }
+    }
+}
+
+/// A transaction that would be created by a send, along with the details of the coin selection;
+/// it's neither broadcast nor stored in the wallet
+pub struct SendPreview {
+    pub tx: PreviewedTransaction,
+    pub fees: Balances,
+    /// The fee rate the inputs were selected for
+    pub fee_rate: FeeRate,
+    /// The selected inputs together with the outputs they spend
+    pub inputs: Vec<(UtxoOutPoint, TxOutput)>,
+    /// The outputs of the transaction other than the requested one
+    pub change: Vec<TxOutput>,
+}
@@ -40,12 +40,13 @@ use wallet_controller::{
 };
 use wallet_rpc_lib::{
     types::{
-        AccountExtendedPublicKey, AddressInfo, AddressWithUsageInfo, Balances, BlockInfo,
+        AccountExtendedPublicKey, AccountSpendingPolicyInfo, AddressDerivationInfo, AddressInfo,
+        AddressWithUsageInfo, AddressesVerificationReport, Balances, BlockInfo,
         ComposedTransaction, CreatedWallet, DelegationInfo, HardwareWalletType,
         LegacyVrfPublicKeyInfo, NewAccountInfo, NewDelegationTransaction, NewOrderTransaction,
         NewSubmittedTransaction, NewTokenTransaction, NftMetadata, NodeVersion, OpenedWallet,
         PoolInfo, PublicKeyInfo, RpcHashedTimelockContract, RpcInspectTransaction,
-        RpcNewTransaction, RpcPreparedTransaction, RpcStandaloneAddresses,
+        RpcNewTransaction, RpcPreparedTransaction, RpcSendPreview, RpcStandaloneAddresses,
         SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
         StandaloneAddressWithDetails, TokenMetadata, TxOptionsOverrides, UtxoInfo,
         VrfPublicKeyInfo,
@@ -255,6 +256,13 @@ where
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn backup_wallet(&self, path: PathBuf) -> Result<(), Self::Error> {
+        self.wallet_rpc
+            .backup_wallet(path)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn best_block(&self) -> Result<BlockInfo, Self::Error> {
         self.wallet_rpc
             .best_block()
@@ -280,6 +288,32 @@ where
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn set_account_spending_policy(
+        &self,
+        account_index: U31,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> Result<(), Self::Error> {
+        self.wallet_rpc
+            .set_account_spending_policy(
+                account_index,
+                min_confirmations_change,
+                min_confirmations_external,
+            )
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> Result<AccountSpendingPolicyInfo, Self::Error> {
+        self.wallet_rpc
+            .get_account_spending_policy(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn standalone_address_label_rename(
         &self,
         account_index: U31,
@@ -388,6 +422,29 @@ where
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn get_address_derivation_info(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<AddressDerivationInfo, Self::Error> {
+        self.wallet_rpc
+            .get_address_derivation_info(account_index, address.into())
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn verify_addresses(
+        &self,
+        account_index: U31,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> Result<AddressesVerificationReport, Self::Error> {
+        self.wallet_rpc
+            .verify_addresses(account_index, mnemonic, passphrase)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn get_balance(
         &self,
         account_index: U31,
@@ -404,6 +461,13 @@ where
             .map_err(WalletRpcHandlesClientError::WalletRpcError)
     }
 
+    async fn get_spendable_balance(&self, account_index: U31) -> Result<Balances, Self::Error> {
+        self.wallet_rpc
+            .get_spendable_balance(account_index)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -424,8 +488,13 @@ where
 
         utxos
             .into_iter()
-            .map(|(utxo_outpoint, tx_ouput)| {
-                UtxoInfo::new(utxo_outpoint, tx_ouput, self.wallet_rpc.chain_config())
+            .map(|(utxo_outpoint, tx_ouput, unlock_point)| {
+                UtxoInfo::new(
+                    utxo_outpoint,
+                    tx_ouput,
+                    unlock_point,
+                    self.wallet_rpc.chain_config(),
+                )
             })
             .collect::<Result<Vec<_>, _>>()
             .map_err(WalletRpcHandlesClientError::AddressError)
@@ -451,8 +520,13 @@ where
 
         utxos
             .into_iter()
-            .map(|(utxo_outpoint, tx_ouput)| {
-                UtxoInfo::new(utxo_outpoint, tx_ouput, self.wallet_rpc.chain_config())
+            .map(|(utxo_outpoint, tx_ouput, unlock_point)| {
+                UtxoInfo::new(
+                    utxo_outpoint,
+                    tx_ouput,
+                    unlock_point,
+                    self.wallet_rpc.chain_config(),
+                )
             })
             .collect::<Result<Vec<_>, _>>()
             .map_err(WalletRpcHandlesClientError::AddressError)
@@ -603,6 +677,41 @@ where
             .map(RpcNewTransaction::new)
     }
 
+    async fn preview_send_coins(
+        &self,
+        account_index: U31,
+        address: String,
+        amount: DecimalAmount,
+        selected_utxos: Vec<UtxoOutPoint>,
+        sign: bool,
+        config: ControllerConfig,
+    ) -> Result<RpcSendPreview, Self::Error> {
+        self.wallet_rpc
+            .preview_send_coins(
+                account_index,
+                address.into(),
+                amount.into(),
+                selected_utxos,
+                sign,
+                config,
+            )
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
+    async fn pay_payment_uri(
+        &self,
+        account_index: U31,
+        uri: String,
+        amount: Option<DecimalAmount>,
+        config: ControllerConfig,
+    ) -> Result<RpcNewTransaction, Self::Error> {
+        self.wallet_rpc
+            .pay_payment_uri(account_index, uri, amount.map(Into::into), config)
+            .await
+            .map_err(WalletRpcHandlesClientError::WalletRpcError)
+    }
+
     async fn sweep_addresses(
         &self,
         account_index: U31,
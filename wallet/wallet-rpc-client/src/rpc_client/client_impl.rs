@@ -45,12 +45,13 @@ use wallet_controller::{
 };
 use wallet_rpc_lib::{
     types::{
-        AccountExtendedPublicKey, AddressInfo, AddressWithUsageInfo, BlockInfo,
-        ComposedTransaction, CreatedWallet, DelegationInfo, HardwareWalletType,
-        LegacyVrfPublicKeyInfo, NewAccountInfo, NewDelegationTransaction, NewOrderTransaction,
-        NewSubmittedTransaction, NewTokenTransaction, NftMetadata, NodeVersion, OpenedWallet,
-        PoolInfo, PublicKeyInfo, RpcHashedTimelockContract, RpcInspectTransaction,
-        RpcNewTransaction, RpcPreparedTransaction, RpcStandaloneAddresses,
+        AccountExtendedPublicKey, AccountSpendingPolicyInfo, AddressDerivationInfo, AddressInfo,
+        AddressWithUsageInfo, AddressesVerificationReport, BlockInfo, ComposedTransaction,
+        CreatedWallet, DelegationInfo, HardwareWalletType, LegacyVrfPublicKeyInfo, NewAccountInfo,
+        NewDelegationTransaction, NewOrderTransaction, NewSubmittedTransaction,
+        NewTokenTransaction, NftMetadata, NodeVersion, OpenedWallet, PoolInfo, PublicKeyInfo,
+        RpcHashedTimelockContract, RpcInspectTransaction, RpcNewTransaction,
+        RpcPreparedTransaction, RpcSendPreview, RpcStandaloneAddresses,
         SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
         StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TransactionRequestOptions,
         TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
@@ -241,6 +242,12 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn backup_wallet(&self, path: PathBuf) -> Result<(), Self::Error> {
+        ColdWalletRpcClient::backup_wallet(&self.http_client, path.to_string_lossy().to_string())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn best_block(&self) -> Result<BlockInfo, Self::Error> {
         WalletRpcClient::best_block(&self.http_client)
             .await
@@ -263,6 +270,31 @@ impl WalletInterface for ClientWalletRpc {
             .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn set_account_spending_policy(
+        &self,
+        account_index: U31,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> Result<(), Self::Error> {
+        WalletRpcClient::set_account_spending_policy(
+            &self.http_client,
+            account_index.into(),
+            min_confirmations_change,
+            min_confirmations_external,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> Result<AccountSpendingPolicyInfo, Self::Error> {
+        WalletRpcClient::get_account_spending_policy(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn standalone_address_label_rename(
         &self,
         account_index: U31,
@@ -392,6 +424,36 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn get_address_derivation_info(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<AddressDerivationInfo, Self::Error> {
+        ColdWalletRpcClient::get_address_derivation_info(
+            &self.http_client,
+            account_index.into(),
+            address.into(),
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn verify_addresses(
+        &self,
+        account_index: U31,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> Result<AddressesVerificationReport, Self::Error> {
+        ColdWalletRpcClient::verify_addresses(
+            &self.http_client,
+            account_index.into(),
+            mnemonic,
+            passphrase,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn get_balance(
         &self,
         account_index: U31,
@@ -408,6 +470,12 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn get_spendable_balance(&self, account_index: U31) -> Result<Balances, Self::Error> {
+        WalletRpcClient::get_spendable_balance(&self.http_client, account_index.into())
+            .await
+            .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -471,6 +539,49 @@ impl WalletInterface for ClientWalletRpc {
         .map_err(WalletRpcError::ResponseError)
     }
 
+    async fn preview_send_coins(
+        &self,
+        account_index: U31,
+        address: String,
+        amount: DecimalAmount,
+        selected_utxos: Vec<UtxoOutPoint>,
+        sign: bool,
+        config: ControllerConfig,
+    ) -> Result<RpcSendPreview, Self::Error> {
+        let options = TransactionRequestOptions::from_controller_config(&config);
+        let selected_utxos = selected_utxos.into_iter().map(Into::into).collect();
+        WalletRpcClient::preview_send_coins(
+            &self.http_client,
+            account_index.into(),
+            address.into(),
+            amount.into(),
+            selected_utxos,
+            sign,
+            options,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
+    async fn pay_payment_uri(
+        &self,
+        account_index: U31,
+        uri: String,
+        amount: Option<DecimalAmount>,
+        config: ControllerConfig,
+    ) -> Result<RpcNewTransaction, Self::Error> {
+        let options = TransactionOptions::from_controller_config(&config);
+        WalletRpcClient::pay_payment_uri(
+            &self.http_client,
+            account_index.into(),
+            uri,
+            amount.map(Into::into),
+            options,
+        )
+        .await
+        .map_err(WalletRpcError::ResponseError)
+    }
+
     async fn sweep_addresses(
         &self,
         account_index: U31,
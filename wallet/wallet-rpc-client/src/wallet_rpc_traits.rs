@@ -35,11 +35,12 @@ use wallet_controller::{
     ConnectedPeer, ControllerConfig, UtxoState, UtxoType,
 };
 use wallet_rpc_lib::types::{
-    AccountExtendedPublicKey, AddressInfo, AddressWithUsageInfo, Balances, BlockInfo,
-    ComposedTransaction, CreatedWallet, DelegationInfo, HardwareWalletType, LegacyVrfPublicKeyInfo,
-    NewAccountInfo, NewDelegationTransaction, NewOrderTransaction, NewSubmittedTransaction,
-    NewTokenTransaction, NftMetadata, NodeVersion, OpenedWallet, PoolInfo, PublicKeyInfo,
-    RpcHashedTimelockContract, RpcInspectTransaction, RpcNewTransaction, RpcPreparedTransaction,
+    AccountExtendedPublicKey, AccountSpendingPolicyInfo, AddressDerivationInfo, AddressInfo,
+    AddressWithUsageInfo, AddressesVerificationReport, Balances, BlockInfo, ComposedTransaction,
+    CreatedWallet, DelegationInfo, HardwareWalletType, LegacyVrfPublicKeyInfo, NewAccountInfo,
+    NewDelegationTransaction, NewOrderTransaction, NewSubmittedTransaction, NewTokenTransaction,
+    NftMetadata, NodeVersion, OpenedWallet, PoolInfo, PublicKeyInfo, RpcHashedTimelockContract,
+    RpcInspectTransaction, RpcNewTransaction, RpcPreparedTransaction, RpcSendPreview,
     RpcSignatureStatus, RpcStandaloneAddresses, SendTokensFromMultisigAddressResult,
     StakePoolBalance, StakingStatus, StandaloneAddressWithDetails, TokenMetadata,
     TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
@@ -118,6 +119,8 @@ pub trait WalletInterface {
 
     async fn lock_private_key_encryption(&self) -> Result<(), Self::Error>;
 
+    async fn backup_wallet(&self, path: PathBuf) -> Result<(), Self::Error>;
+
     async fn best_block(&self) -> Result<BlockInfo, Self::Error>;
 
     async fn create_account(&self, name: Option<String>) -> Result<NewAccountInfo, Self::Error>;
@@ -128,6 +131,18 @@ pub trait WalletInterface {
         name: Option<String>,
     ) -> Result<NewAccountInfo, Self::Error>;
 
+    async fn set_account_spending_policy(
+        &self,
+        account_index: U31,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> Result<(), Self::Error>;
+
+    async fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> Result<AccountSpendingPolicyInfo, Self::Error>;
+
     async fn standalone_address_label_rename(
         &self,
         account_index: U31,
@@ -185,6 +200,19 @@ pub trait WalletInterface {
         address: String,
     ) -> Result<PublicKeyInfo, Self::Error>;
 
+    async fn get_address_derivation_info(
+        &self,
+        account_index: U31,
+        address: String,
+    ) -> Result<AddressDerivationInfo, Self::Error>;
+
+    async fn verify_addresses(
+        &self,
+        account_index: U31,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> Result<AddressesVerificationReport, Self::Error>;
+
     async fn get_account_extended_public_key(
         &self,
         account_index: U31,
@@ -197,6 +225,8 @@ pub trait WalletInterface {
         with_locked: WithLocked,
     ) -> Result<Balances, Self::Error>;
 
+    async fn get_spendable_balance(&self, account_index: U31) -> Result<Balances, Self::Error>;
+
     async fn get_multisig_utxos(
         &self,
         account_index: U31,
@@ -265,6 +295,24 @@ pub trait WalletInterface {
         config: ControllerConfig,
     ) -> Result<RpcNewTransaction, Self::Error>;
 
+    async fn preview_send_coins(
+        &self,
+        account_index: U31,
+        address: String,
+        amount: DecimalAmount,
+        selected_utxos: Vec<UtxoOutPoint>,
+        sign: bool,
+        config: ControllerConfig,
+    ) -> Result<RpcSendPreview, Self::Error>;
+
+    async fn pay_payment_uri(
+        &self,
+        account_index: U31,
+        uri: String,
+        amount: Option<DecimalAmount>,
+        config: ControllerConfig,
+    ) -> Result<RpcNewTransaction, Self::Error>;
+
     async fn sweep_addresses(
         &self,
         account_index: U31,
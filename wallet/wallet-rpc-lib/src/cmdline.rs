@@ -26,9 +26,11 @@ use utils::{
     clap_utils, cookie::COOKIE_FILENAME, default_data_dir::default_data_dir_for_chain, ensure,
 };
 use utils_networking::NetworkAddressWithPort;
+use wallet::account::{OutputOrdering, OutputPrivacyPolicy};
 
 use crate::{
     config::{WalletRpcConfig, WalletServiceConfig},
+    service::notifications::{NotificationAction, NotificationConfig},
     types::HardwareWalletType,
 };
 
@@ -97,6 +99,26 @@ impl From<CliHardwareWalletType> for HardwareWalletType {
     }
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum CliOutputOrdering {
+    /// Keep the outputs in the requested order and put the change last
+    ChangeLast,
+    /// Sort the outputs by amount, similar to BIP69
+    Sorted,
+    /// Shuffle the outputs, the order is derived from the transaction inputs
+    Shuffled,
+}
+
+impl From<CliOutputOrdering> for OutputOrdering {
+    fn from(value: CliOutputOrdering) -> Self {
+        match value {
+            CliOutputOrdering::ChangeLast => Self::ChangeLast,
+            CliOutputOrdering::Sorted => Self::Sorted,
+            CliOutputOrdering::Shuffled => Self::Shuffled,
+        }
+    }
+}
+
 #[derive(clap::Args)]
 #[command(
     version,
@@ -172,6 +194,34 @@ pub struct WalletRpcDaemonChainArgs {
     #[arg(long, conflicts_with_all(["rpc_password", "rpc_username", "rpc_cookie_file"]))]
     rpc_no_authentication: bool,
 
+    /// Execute a command on wallet events (new transaction, confirmation, staking reward,
+    /// conflict). Any `%s` in the command is replaced by the transaction or block id and the
+    /// full notification is passed as JSON in the ML_WALLET_NOTIFICATION environment variable.
+    /// Can be specified multiple times.
+    #[arg(long, value_name("CMD"))]
+    notify_command: Vec<String>,
+
+    /// Send wallet event notifications as JSON in a POST request to the given URL.
+    /// Can be specified multiple times.
+    #[arg(long, value_name("URL"))]
+    notify_webhook: Vec<String>,
+
+    /// Number of confirmations after which a transaction confirmed notification is sent.
+    /// Zero disables confirmation notifications.
+    #[arg(long, value_name("NUM"), default_value_t = 1)]
+    notify_confirmations: u64,
+
+    /// The order of the outputs of the created transactions. Only applies to transactions
+    /// that consist of plain transfers.
+    #[arg(long, value_name("ORDERING"), default_value = "shuffled")]
+    output_ordering: CliOutputOrdering,
+
+    /// Round the change down to the precision of the payment amounts, so that it can't be told
+    /// apart from the payments. The rounded off amount, which never exceeds the fee of the change
+    /// output, is added to the fee.
+    #[arg(long)]
+    match_change_precision: bool,
+
     #[clap(flatten)]
     force_allow_run_as_root: utils::root_user::ForceRunAsRootOptions,
 }
@@ -196,6 +246,11 @@ impl WalletRpcDaemonChainArgs {
             rpc_password,
             rpc_no_authentication,
             cold_wallet,
+            notify_command,
+            notify_webhook,
+            notify_confirmations,
+            output_ordering,
+            match_change_precision,
             force_allow_run_as_root,
         } = self;
 
@@ -203,6 +258,15 @@ impl WalletRpcDaemonChainArgs {
             .ensure_not_running_as_root_user()
             .map_err(ConfigError::RunningAsRoot)?;
 
+        let notifications = NotificationConfig {
+            actions: notify_command
+                .into_iter()
+                .map(NotificationAction::Command)
+                .chain(notify_webhook.into_iter().map(NotificationAction::Webhook))
+                .collect(),
+            confirmations: notify_confirmations,
+        };
+
         let ws_config = {
             let service = WalletServiceConfig::new(
                 chain_type,
@@ -210,7 +274,12 @@ impl WalletRpcDaemonChainArgs {
                 force_change_wallet_type,
                 start_staking_for_account,
                 hardware_wallet.map(Into::into),
-            );
+            )
+            .with_notifications(notifications)
+            .with_output_privacy_policy(OutputPrivacyPolicy {
+                ordering: output_ordering.into(),
+                match_change_precision,
+            });
 
             if cold_wallet {
                 service
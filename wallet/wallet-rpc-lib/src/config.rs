@@ -21,8 +21,9 @@ use common::chain::config::{
 };
 use crypto::key::hdkd::u31::U31;
 use rpc::{rpc_creds::RpcCreds, RpcAuthData};
+use wallet::account::OutputPrivacyPolicy;
 
-use crate::types::HardwareWalletType;
+use crate::{service::notifications::NotificationConfig, types::HardwareWalletType};
 
 #[derive(Clone)]
 pub enum NodeRpc {
@@ -55,6 +56,12 @@ pub struct WalletServiceConfig {
 
     /// Node rpc settings
     pub node_rpc: NodeRpc,
+
+    /// Actions executed on wallet events
+    pub notifications: NotificationConfig,
+
+    /// Order of the outputs and the change amounts of the created transactions
+    pub output_privacy_policy: OutputPrivacyPolicy,
 }
 
 impl WalletServiceConfig {
@@ -72,6 +79,8 @@ impl WalletServiceConfig {
             start_staking_for_account,
             node_rpc: NodeRpc::ColdWallet,
             hardware_wallet_type,
+            notifications: NotificationConfig::default(),
+            output_privacy_policy: OutputPrivacyPolicy::default(),
         }
     }
 
@@ -126,6 +135,19 @@ impl WalletServiceConfig {
         self
     }
 
+    pub fn with_notifications(mut self, notifications: NotificationConfig) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    pub fn with_output_privacy_policy(
+        mut self,
+        output_privacy_policy: OutputPrivacyPolicy,
+    ) -> Self {
+        self.output_privacy_policy = output_privacy_policy;
+        self
+    }
+
     pub fn apply_option<T>(self, f: impl FnOnce(Self, T) -> Self, opt: Option<T>) -> Self {
         match opt {
             None => self,
@@ -24,7 +24,10 @@ pub use rpc::{
     types, ColdWalletRpcClient, ColdWalletRpcDescription, ColdWalletRpcServer, RpcCreds, RpcError,
     WalletEventsRpcServer, WalletRpc, WalletRpcClient, WalletRpcDescription, WalletRpcServer,
 };
-pub use service::{Event, EventStream, TxState, WalletHandle, /* WalletResult, */ WalletService,};
+pub use service::{
+    notifications::{NotificationAction, NotificationConfig},
+    Event, EventStream, TxState, WalletHandle, /* WalletResult, */ WalletService,
+};
 use wallet_controller::{NodeInterface, NodeRpcClient};
 #[cfg(feature = "trezor")]
 use wallet_types::wallet_type::WalletType;
@@ -110,16 +113,23 @@ where
             HardwareWalletType::Trezor { device_id: _ } => WalletType::Trezor,
         },
     );
+    let notifications = wallet_config.notifications;
+
     // Start the wallet service
     let wallet_service = WalletService::start(
         wallet_config.chain_config,
         wallet_config.wallet_file.map(|file| (file, wallet_type)),
         wallet_config.force_change_wallet_type,
         wallet_config.start_staking_for_account,
+        wallet_config.output_privacy_policy,
         node_rpc,
     )
     .await?;
 
+    if !notifications.is_empty() {
+        service::notifications::spawn(wallet_service.handle(), notifications);
+    }
+
     // Start the RPC server
     let rpc_server = {
         let wallet_handle = wallet_service.handle().shallow_clone();
@@ -42,15 +42,17 @@ use wallet_types::{
 };
 
 use crate::types::{
-    AccountArg, AccountExtendedPublicKey, AddressInfo, AddressWithUsageInfo, Balances, ChainInfo,
+    AccountArg, AccountExtendedPublicKey, AccountSpendingPolicyInfo, AddressDerivationInfo,
+    AddressInfo, AddressWithUsageInfo, AddressesVerificationReport, Balances, ChainInfo,
     ComposedTransaction, CreatedWallet, DelegationInfo, HardwareWalletType, HexEncoded,
     LegacyVrfPublicKeyInfo, MaybeSignedTransaction, NewAccountInfo, NewDelegationTransaction,
     NewOrderTransaction, NewSubmittedTransaction, NewTokenTransaction, NftMetadata, NodeVersion,
     OpenedWallet, PoolInfo, PublicKeyInfo, RpcAmountIn, RpcHashedTimelockContract,
-    RpcInspectTransaction, RpcNewTransaction, RpcPreparedTransaction, RpcStandaloneAddresses,
-    RpcUtxoOutpoint, RpcUtxoState, RpcUtxoType, SendTokensFromMultisigAddressResult,
-    StakePoolBalance, StakingStatus, StandaloneAddressWithDetails, TokenMetadata,
-    TransactionOptions, TransactionRequestOptions, TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
+    RpcInspectTransaction, RpcNewTransaction, RpcPreparedTransaction, RpcSendPreview,
+    RpcStandaloneAddresses, RpcUtxoOutpoint, RpcUtxoState, RpcUtxoType,
+    SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
+    StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TransactionRequestOptions,
+    TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
 };
 
 #[rpc::rpc(server)]
@@ -155,6 +157,12 @@ trait ColdWalletRpc {
     #[method(name = "wallet_lock_private_keys")]
     async fn lock_private_key_encryption(&self) -> rpc::RpcResult<()>;
 
+    /// Write a copy of the loaded wallet into a new wallet file at the given path.
+    /// The file must not exist. The private keys in the copy stay encrypted with the current
+    /// password, if any.
+    #[method(name = "wallet_backup")]
+    async fn backup_wallet(&self, path: String) -> rpc::RpcResult<()>;
+
     /// Show the seed phrase for the loaded wallet if it has been stored.
     #[method(name = "wallet_show_seed_phrase")]
     async fn get_seed_phrase(&self) -> rpc::RpcResult<Option<SeedWithPassPhrase>>;
@@ -219,6 +227,32 @@ trait ColdWalletRpc {
         address: RpcAddress<Destination>,
     ) -> rpc::RpcResult<PublicKeyInfo>;
 
+    /// Show the derivation path and the public key behind an address of this account,
+    /// together with the address re-derived from the account extended public key.
+    ///
+    /// If the stored public key or address doesn't match the re-derived one, `is_consistent`
+    /// is false, which means that the wallet database has been tampered with or corrupted.
+    #[method(name = "address_derivation_info")]
+    async fn get_address_derivation_info(
+        &self,
+        account: AccountArg,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<AddressDerivationInfo>;
+
+    /// Re-derive all the keys and addresses of this account from the seed and compare them
+    /// with the ones stored in the wallet, reporting any mismatches.
+    ///
+    /// If the mnemonic (and the passphrase, if one was used) is provided, the seed is computed
+    /// from it, so that a tampered root key is detected as well. Otherwise, the root key stored
+    /// in the wallet is used, which requires the wallet to be unlocked.
+    #[method(name = "address_verify_derivation")]
+    async fn verify_addresses(
+        &self,
+        account: AccountArg,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> rpc::RpcResult<AddressesVerificationReport>;
+
     /// Issue a new staking VRF (Verifiable Random Function) key for this account.
     ///
     /// VRF keys are used as a trustless mechanism to ensure the randomness of the staking process,
@@ -341,6 +375,25 @@ trait WalletRpc {
         name: Option<String>,
     ) -> rpc::RpcResult<NewAccountInfo>;
 
+    /// Set the minimum number of confirmations the outputs of the selected account must have
+    /// before they can be picked by the automatic coin selection. The thresholds for the change
+    /// of the account's own transactions and for the outputs received from others are separate.
+    /// Zero means that unconfirmed outputs can be spent as well.
+    #[method(name = "account_set_spending_policy")]
+    async fn set_account_spending_policy(
+        &self,
+        account: AccountArg,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> rpc::RpcResult<()>;
+
+    /// Get the minimum confirmation policy of the selected account's automatic coin selection
+    #[method(name = "account_spending_policy")]
+    async fn get_account_spending_policy(
+        &self,
+        account: AccountArg,
+    ) -> rpc::RpcResult<AccountSpendingPolicyInfo>;
+
     /// Add, rename or delete a label to an already added standalone address.
     ///
     /// Specifying a label will add or replace the existing one,
@@ -405,6 +458,11 @@ trait WalletRpc {
         with_locked: Option<WithLocked>,
     ) -> rpc::RpcResult<Balances>;
 
+    /// Get the balance in the selected account that can be spent by the automatic coin selection,
+    /// i.e. the unlocked coins and tokens that satisfy the account's spending policy
+    #[method(name = "account_spendable_balance")]
+    async fn get_spendable_balance(&self, account: AccountArg) -> rpc::RpcResult<Balances>;
+
     /// Lists all the utxos owned by this account
     #[method(name = "account_utxos")]
     async fn get_utxos(&self, account: AccountArg) -> rpc::RpcResult<Vec<UtxoInfo>>;
@@ -431,6 +489,37 @@ trait WalletRpc {
         options: TransactionOptions,
     ) -> rpc::RpcResult<RpcNewTransaction>;
 
+    /// Build the transaction that `address_send` would create with the same arguments,
+    /// without broadcasting it or adding it to the wallet.
+    ///
+    /// Returns the transaction together with its id, fees, the fee rate used for coin selection,
+    /// the selected inputs and the change outputs.
+    /// If `sign` is false, the transaction is returned unsigned, in the partially signed form,
+    /// and the wallet doesn't need to be unlocked.
+    #[method(name = "wallet_preview_send")]
+    async fn preview_send_coins(
+        &self,
+        account: AccountArg,
+        address: RpcAddress<Destination>,
+        amount: RpcAmountIn,
+        selected_utxos: Vec<RpcUtxoOutpoint>,
+        sign: bool,
+        options: TransactionRequestOptions,
+    ) -> rpc::RpcResult<RpcSendPreview>;
+
+    /// Pay according to a payment URI, sending either coins or tokens to the address it specifies.
+    /// The wallet will automatically calculate the required fees.
+    ///
+    /// If the URI doesn't specify the amount, it must be provided separately.
+    #[method(name = "payment_uri_pay")]
+    async fn pay_payment_uri(
+        &self,
+        account: AccountArg,
+        uri: String,
+        amount: Option<RpcAmountIn>,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<RpcNewTransaction>;
+
     /// Sweep all spendable coins or tokens from an address or addresses specified in `from_addresses`
     /// or all addresses from this account if `all` is set to true, to the given destination address.
     /// Either 1 or more addresses need to be specified in `from_addresses` with `all` set to false, or
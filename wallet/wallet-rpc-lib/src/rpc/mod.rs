@@ -39,7 +39,7 @@ use p2p_types::{bannable_address::BannableAddress, socket_address::SocketAddress
 use serialization::{hex_encoded::HexEncoded, Decode, DecodeAll};
 use types::{
     AccountExtendedPublicKey, NewOrderTransaction, NewSubmittedTransaction, NewTokenTransaction,
-    RpcHashedTimelockContract, RpcNewTransaction, RpcPreparedTransaction,
+    RpcHashedTimelockContract, RpcNewTransaction, RpcPreparedTransaction, RpcSendPreview,
 };
 use utils::{ensure, shallow_clone::ShallowClone};
 use utils_networking::IpOrSocketAddress;
@@ -49,7 +49,7 @@ use wallet::{
 };
 
 use common::{
-    address::Address,
+    address::{payment_uri::PaymentUri, Address},
     chain::{
         block::timestamp::BlockTimestamp,
         classic_multisig::ClassicMultisigChallenge,
@@ -58,6 +58,7 @@ use common::{
         signature::inputsig::arbitrary_message::{
             produce_message_challenge, ArbitraryMessageSignature,
         },
+        timelock::OutputTimeLock,
         tokens::{IsTokenFreezable, IsTokenUnfreezable, Metadata, TokenId, TokenTotalSupply},
         Block, ChainConfig, DelegationId, Destination, GenBlock, OrderId, PoolId,
         SignedTransaction, SignedTransactionIntent, Transaction, TxOutput, UtxoOutPoint,
@@ -83,8 +84,8 @@ use wallet_controller::{
 use wallet_types::{
     account_info::StandaloneAddressDetails,
     partially_signed_transaction::PartiallySignedTransaction, scan_blockchain::ScanBlockchain,
-    signature_status::SignatureStatus, wallet_tx::TxData, with_locked::WithLocked, Currency,
-    SignedTxWithFees,
+    signature_status::SignatureStatus, wallet_tx::TxData, with_locked::WithLocked,
+    AccountSpendingPolicy, Currency, SignedTxWithFees,
 };
 
 use crate::{WalletHandle, WalletRpcConfig};
@@ -94,7 +95,8 @@ use wallet_types::wallet_type::WalletType;
 
 pub use self::types::RpcError;
 use self::types::{
-    AddressInfo, AddressWithUsageInfo, DelegationInfo, HardwareWalletType, LegacyVrfPublicKeyInfo,
+    AccountSpendingPolicyInfo, AddressDerivationInfo, AddressInfo, AddressWithUsageInfo,
+    AddressesVerificationReport, DelegationInfo, HardwareWalletType, LegacyVrfPublicKeyInfo,
     NewAccountInfo, PoolInfo, PublicKeyInfo, RpcAddress, RpcAmountIn, RpcHexString,
     RpcStandaloneAddress, RpcStandaloneAddressDetails, RpcStandaloneAddresses,
     RpcStandalonePrivateKeyAddress, RpcUtxoOutpoint, StakingStatus, StandaloneAddressWithDetails,
@@ -208,6 +210,10 @@ where
         self.wallet.call(|w| w.encrypt_wallet(&None)).await?
     }
 
+    pub async fn backup_wallet(&self, path: PathBuf) -> WRpcResult<(), N> {
+        self.wallet.call(move |w| w.backup_wallet(path)).await?
+    }
+
     pub async fn unlock_private_keys(&self, password: String) -> WRpcResult<(), N> {
         self.wallet.call(move |w| w.unlock_wallet(&password)).await?
     }
@@ -291,6 +297,31 @@ where
         Ok(NewAccountInfo::new(num, name))
     }
 
+    pub async fn set_account_spending_policy(
+        &self,
+        account_index: U31,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> WRpcResult<(), N> {
+        let policy =
+            AccountSpendingPolicy::new(min_confirmations_change, min_confirmations_external);
+        self.wallet
+            .call(move |w| w.update_account_spending_policy(account_index, policy))
+            .await??;
+        Ok(())
+    }
+
+    pub async fn get_account_spending_policy(
+        &self,
+        account_index: U31,
+    ) -> WRpcResult<AccountSpendingPolicyInfo, N> {
+        let policy = self
+            .wallet
+            .call(move |w| w.readonly_controller(account_index).get_spending_policy())
+            .await??;
+        Ok(AccountSpendingPolicyInfo::new(policy))
+    }
+
     pub async fn standalone_address_label_rename(
         &self,
         account_index: U31,
@@ -492,6 +523,41 @@ where
         Ok(PublicKeyInfo::new(public_key, &self.chain_config))
     }
 
+    pub async fn get_address_derivation_info(
+        &self,
+        account_index: U31,
+        address: RpcAddress<Destination>,
+    ) -> WRpcResult<AddressDerivationInfo, N> {
+        let address = address
+            .decode_object(&self.chain_config)
+            .map_err(|_| RpcError::InvalidAddress)?;
+
+        let info = self
+            .wallet
+            .call(move |controller| {
+                controller.readonly_controller(account_index).get_key_derivation_info(address)
+            })
+            .await??;
+        Ok(AddressDerivationInfo::new(info))
+    }
+
+    pub async fn verify_addresses(
+        &self,
+        account_index: U31,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> WRpcResult<AddressesVerificationReport, N> {
+        let report = self
+            .wallet
+            .call(move |controller| {
+                controller
+                    .readonly_controller(account_index)
+                    .verify_account_keys(mnemonic.as_deref(), passphrase.as_deref())
+            })
+            .await??;
+        Ok(AddressesVerificationReport::new(report))
+    }
+
     pub async fn get_account_extended_public_key(
         &self,
         account_index: U31,
@@ -723,20 +789,37 @@ where
         Ok(balances)
     }
 
+    pub async fn get_spendable_balance(&self, account_index: U31) -> WRpcResult<Balances, N> {
+        let balances: Balances = self
+            .wallet
+            .call_async(move |w| {
+                Box::pin(async move {
+                    let c = w.readonly_controller(account_index);
+                    c.get_spendable_balance().await
+                })
+            })
+            .await??;
+        Ok(balances)
+    }
+
     pub async fn get_multisig_utxos(
         &self,
         account_index: U31,
         utxo_types: UtxoTypes,
         utxo_states: UtxoStates,
         with_locked: WithLocked,
-    ) -> WRpcResult<Vec<(UtxoOutPoint, TxOutput)>, N> {
+    ) -> WRpcResult<Vec<(UtxoOutPoint, TxOutput, Option<OutputTimeLock>)>, N> {
         self.wallet
             .call(move |w| {
-                w.readonly_controller(account_index).get_multisig_utxos(
-                    utxo_types,
-                    utxo_states,
-                    with_locked,
-                )
+                let controller = w.readonly_controller(account_index);
+                controller
+                    .get_multisig_utxos(utxo_types, utxo_states, with_locked)?
+                    .into_iter()
+                    .map(|(outpoint, output)| {
+                        let unlock_point = controller.get_utxo_unlock_point(&outpoint)?;
+                        Ok((outpoint, output, unlock_point))
+                    })
+                    .collect::<WRpcResult<Vec<_>, N>>()
             })
             .await?
     }
@@ -747,11 +830,18 @@ where
         utxo_types: UtxoTypes,
         utxo_states: UtxoStates,
         with_locked: WithLocked,
-    ) -> WRpcResult<Vec<(UtxoOutPoint, TxOutput)>, N> {
+    ) -> WRpcResult<Vec<(UtxoOutPoint, TxOutput, Option<OutputTimeLock>)>, N> {
         self.wallet
             .call(move |w| {
-                w.readonly_controller(account_index)
-                    .get_utxos(utxo_types, utxo_states, with_locked)
+                let controller = w.readonly_controller(account_index);
+                controller
+                    .get_utxos(utxo_types, utxo_states, with_locked)?
+                    .into_iter()
+                    .map(|(outpoint, output)| {
+                        let unlock_point = controller.get_utxo_unlock_point(&outpoint)?;
+                        Ok((outpoint, output, unlock_point))
+                    })
+                    .collect::<WRpcResult<Vec<_>, N>>()
             })
             .await?
     }
@@ -1026,6 +1116,74 @@ where
             .await?
     }
 
+    /// Build the transaction `send_coins` would create, without broadcasting it.
+    pub async fn preview_send_coins(
+        &self,
+        account_index: U31,
+        address: RpcAddress<Destination>,
+        amount: RpcAmountIn,
+        selected_utxos: Vec<UtxoOutPoint>,
+        sign: bool,
+        config: ControllerConfig,
+    ) -> WRpcResult<RpcSendPreview, N> {
+        let decimals = self.chain_config.coin_decimals();
+        let amount = amount.to_amount(decimals).ok_or(RpcError::InvalidCoinAmount)?;
+        let address =
+            address.into_address(&self.chain_config).map_err(|_| RpcError::InvalidAddress)?;
+
+        let preview = self
+            .wallet
+            .call_async(move |controller| {
+                Box::pin(async move {
+                    controller
+                        .synced_controller(account_index, config)
+                        .await?
+                        .preview_send_to_address(address, amount, selected_utxos, sign)
+                        .await
+                        .map_err(RpcError::Controller)
+                })
+            })
+            .await??;
+
+        Ok(RpcSendPreview::new(preview, &self.chain_config)?)
+    }
+
+    /// Pay according to the payment URI; the amount must be specified either in the URI
+    /// or explicitly, but not in both.
+    pub async fn pay_payment_uri(
+        &self,
+        account_index: U31,
+        uri: String,
+        amount: Option<RpcAmountIn>,
+        config: ControllerConfig,
+    ) -> WRpcResult<RpcNewTransaction, N> {
+        let payment_uri = PaymentUri::from_string(&self.chain_config, &uri)?;
+        let amount = match (payment_uri.amount(), amount) {
+            (Some(amount), None) => amount.into(),
+            (None, Some(amount)) => amount,
+            (Some(_), Some(_)) => return Err(RpcError::PaymentUriAmountAlreadySpecified),
+            (None, None) => return Err(RpcError::PaymentUriAmountMissing),
+        };
+        let address = payment_uri.destination().clone().into();
+
+        match payment_uri.token_id() {
+            Some(token_id) => {
+                self.send_tokens(
+                    account_index,
+                    token_id.clone().into(),
+                    address,
+                    amount,
+                    config,
+                )
+                .await
+            }
+            None => self
+                .send_coins(account_index, address, amount, vec![], config)
+                .await
+                .map(RpcNewTransaction::new),
+        }
+    }
+
     pub async fn request_send_coins(
         &self,
         account_index: U31,
@@ -1207,7 +1365,7 @@ where
 
         let inputs = inputs
             .into_iter()
-            .filter(|(_, txo)| {
+            .filter(|(_, txo, _)| {
                 let (val, dest) = match txo {
                     TxOutput::Transfer(val, dest) | TxOutput::LockThenTransfer(val, dest, _) => {
                         (val, dest)
@@ -46,21 +46,23 @@ use wallet_types::{
 use crate::{
     rpc::{ColdWalletRpcServer, WalletEventsRpcServer, WalletRpc, WalletRpcServer},
     types::{
-        AccountArg, AddressInfo, AddressWithUsageInfo, Balances, ChainInfo, ComposedTransaction,
-        CreatedWallet, DelegationInfo, HardwareWalletType, HexEncoded, LegacyVrfPublicKeyInfo,
-        MaybeSignedTransaction, NewAccountInfo, NewDelegationTransaction, NewSubmittedTransaction,
-        NftMetadata, NodeVersion, OpenedWallet, PoolInfo, PublicKeyInfo, RpcAddress, RpcAmountIn,
-        RpcHexString, RpcInspectTransaction, RpcStandaloneAddresses, RpcUtxoOutpoint, RpcUtxoState,
-        RpcUtxoType, SendTokensFromMultisigAddressResult, StakePoolBalance, StakingStatus,
-        StandaloneAddressWithDetails, TokenMetadata, TransactionOptions, TransactionRequestOptions,
-        TxOptionsOverrides, UtxoInfo, VrfPublicKeyInfo,
+        AccountArg, AccountSpendingPolicyInfo, AddressDerivationInfo, AddressInfo,
+        AddressWithUsageInfo, AddressesVerificationReport, Balances, ChainInfo,
+        ComposedTransaction, CreatedWallet, DelegationInfo, HardwareWalletType, HexEncoded,
+        LegacyVrfPublicKeyInfo, MaybeSignedTransaction, NewAccountInfo, NewDelegationTransaction,
+        NewSubmittedTransaction, NftMetadata, NodeVersion, OpenedWallet, PoolInfo, PublicKeyInfo,
+        RpcAddress, RpcAmountIn, RpcHexString, RpcInspectTransaction, RpcStandaloneAddresses,
+        RpcUtxoOutpoint, RpcUtxoState, RpcUtxoType, SendTokensFromMultisigAddressResult,
+        StakePoolBalance, StakingStatus, StandaloneAddressWithDetails, TokenMetadata,
+        TransactionOptions, TransactionRequestOptions, TxOptionsOverrides, UtxoInfo,
+        VrfPublicKeyInfo,
     },
     RpcError,
 };
 
 use super::types::{
     AccountExtendedPublicKey, NewOrderTransaction, NewTokenTransaction, RpcHashedTimelockContract,
-    RpcNewTransaction, RpcPreparedTransaction,
+    RpcNewTransaction, RpcPreparedTransaction, RpcSendPreview,
 };
 
 #[async_trait::async_trait]
@@ -204,6 +206,10 @@ where
         rpc::handle_result(self.lock_private_keys().await)
     }
 
+    async fn backup_wallet(&self, path: String) -> rpc::RpcResult<()> {
+        rpc::handle_result(self.backup_wallet(path.into()).await)
+    }
+
     async fn issue_address(&self, account_arg: AccountArg) -> rpc::RpcResult<AddressInfo> {
         rpc::handle_result(self.issue_address(account_arg.index::<N>()?).await)
     }
@@ -216,6 +222,27 @@ where
         rpc::handle_result(self.find_public_key(account_arg.index::<N>()?, address).await)
     }
 
+    async fn get_address_derivation_info(
+        &self,
+        account_arg: AccountArg,
+        address: RpcAddress<Destination>,
+    ) -> rpc::RpcResult<AddressDerivationInfo> {
+        rpc::handle_result(
+            self.get_address_derivation_info(account_arg.index::<N>()?, address).await,
+        )
+    }
+
+    async fn verify_addresses(
+        &self,
+        account_arg: AccountArg,
+        mnemonic: Option<String>,
+        passphrase: Option<String>,
+    ) -> rpc::RpcResult<AddressesVerificationReport> {
+        rpc::handle_result(
+            self.verify_addresses(account_arg.index::<N>()?, mnemonic, passphrase).await,
+        )
+    }
+
     async fn get_standalone_addresses(
         &self,
         account_arg: AccountArg,
@@ -380,6 +407,29 @@ where
         rpc::handle_result(self.update_account_name(account_arg.index::<N>()?, name).await)
     }
 
+    async fn set_account_spending_policy(
+        &self,
+        account_arg: AccountArg,
+        min_confirmations_change: u32,
+        min_confirmations_external: u32,
+    ) -> rpc::RpcResult<()> {
+        rpc::handle_result(
+            self.set_account_spending_policy(
+                account_arg.index::<N>()?,
+                min_confirmations_change,
+                min_confirmations_external,
+            )
+            .await,
+        )
+    }
+
+    async fn get_account_spending_policy(
+        &self,
+        account_arg: AccountArg,
+    ) -> rpc::RpcResult<AccountSpendingPolicyInfo> {
+        rpc::handle_result(self.get_account_spending_policy(account_arg.index::<N>()?).await)
+    }
+
     async fn standalone_address_label_rename(
         &self,
         account_arg: AccountArg,
@@ -468,6 +518,10 @@ where
         )
     }
 
+    async fn get_spendable_balance(&self, account_arg: AccountArg) -> rpc::RpcResult<Balances> {
+        rpc::handle_result(self.get_spendable_balance(account_arg.index::<N>()?).await)
+    }
+
     async fn get_multisig_utxos(
         &self,
         account_arg: AccountArg,
@@ -494,8 +548,9 @@ where
 
         let result = utxos
             .into_iter()
-            .map(|(utxo_outpoint, tx_ouput)| {
-                let result = UtxoInfo::new(utxo_outpoint, tx_ouput, &self.chain_config);
+            .map(|(utxo_outpoint, tx_ouput, unlock_point)| {
+                let result =
+                    UtxoInfo::new(utxo_outpoint, tx_ouput, unlock_point, &self.chain_config);
                 rpc::handle_result(result)
             })
             .collect::<Result<Vec<_>, _>>();
@@ -515,8 +570,9 @@ where
 
         let result = utxos
             .into_iter()
-            .map(|(utxo_outpoint, tx_ouput)| {
-                let result = UtxoInfo::new(utxo_outpoint, tx_ouput, &self.chain_config);
+            .map(|(utxo_outpoint, tx_ouput, unlock_point)| {
+                let result =
+                    UtxoInfo::new(utxo_outpoint, tx_ouput, unlock_point, &self.chain_config);
                 rpc::handle_result(result)
             })
             .collect::<Result<Vec<_>, _>>();
@@ -554,6 +610,41 @@ where
         )
     }
 
+    async fn preview_send_coins(
+        &self,
+        account_arg: AccountArg,
+        address: RpcAddress<Destination>,
+        amount: RpcAmountIn,
+        selected_utxos: Vec<RpcUtxoOutpoint>,
+        sign: bool,
+        options: TransactionRequestOptions,
+    ) -> rpc::RpcResult<RpcSendPreview> {
+        rpc::handle_result(
+            self.preview_send_coins(
+                account_arg.index::<N>()?,
+                address,
+                amount,
+                selected_utxos.into_iter().map(|o| o.into_outpoint()).collect(),
+                sign,
+                options.into(),
+            )
+            .await,
+        )
+    }
+
+    async fn pay_payment_uri(
+        &self,
+        account_arg: AccountArg,
+        uri: String,
+        amount: Option<RpcAmountIn>,
+        options: TransactionOptions,
+    ) -> rpc::RpcResult<RpcNewTransaction> {
+        rpc::handle_result(
+            self.pay_payment_uri(account_arg.index::<N>()?, uri, amount, options.into())
+                .await,
+        )
+    }
+
     async fn sweep_addresses(
         &self,
         account: AccountArg,
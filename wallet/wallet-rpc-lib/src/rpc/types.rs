@@ -16,7 +16,7 @@
 //! Types supporting the RPC interface
 
 use common::{
-    address::{pubkeyhash::PublicKeyHash, Address, AddressError},
+    address::{payment_uri::PaymentUriError, pubkeyhash::PublicKeyHash, Address, AddressError},
     chain::{
         block::timestamp::BlockTimestamp,
         classic_multisig::ClassicMultisigChallengeError,
@@ -39,6 +39,7 @@ use crypto::{
 use rpc::description::HasValueHint;
 use utils::ensure;
 use wallet::account::PoolData;
+use wallet::key_chain::{KeyDerivationInfo, KeyDerivationMismatch, KeysVerificationReport};
 
 pub use chainstate::{
     rpc::{
@@ -57,11 +58,14 @@ pub use serialization::hex_encoded::HexEncoded;
 pub use wallet_controller::types::{
     Balances, BlockInfo, InspectTransaction, SignatureStats, ValidatedSignatures,
 };
-use wallet_controller::{types::WalletTypeArgs, UtxoState, UtxoType};
+use wallet_controller::{
+    types::{PreviewedTransaction, SendPreview, WalletTypeArgs},
+    UtxoState, UtxoType,
+};
 pub use wallet_controller::{ControllerConfig, NodeInterface};
 use wallet_types::{
     partially_signed_transaction::PartiallySignedTransaction, seed_phrase::StoreSeedPhrase,
-    signature_status::SignatureStatus, ImportOrCreate, KeyPurpose,
+    signature_status::SignatureStatus, AccountSpendingPolicy, ImportOrCreate, KeyPurpose,
 };
 
 use crate::service::SubmitError;
@@ -147,6 +151,15 @@ pub enum RpcError<N: NodeInterface> {
     #[error(transparent)]
     Address(#[from] AddressError),
 
+    #[error("Invalid payment URI: {0}")]
+    InvalidPaymentUri(#[from] PaymentUriError),
+
+    #[error("The payment URI doesn't specify the amount and no amount is provided")]
+    PaymentUriAmountMissing,
+
+    #[error("The amount is already specified in the payment URI")]
+    PaymentUriAmountAlreadySpecified,
+
     #[error("The specified address {0} is not a multisig address")]
     NotMultisigAddress(String),
 
@@ -338,6 +351,88 @@ impl PublicKeyInfo {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct AddressDerivationInfo {
+    pub derivation_path: String,
+    pub public_key_hex: PublicKey,
+    pub stored_address: Option<RpcAddress<Destination>>,
+    pub derived_address: RpcAddress<Destination>,
+    pub is_consistent: bool,
+}
+
+impl AddressDerivationInfo {
+    pub fn new(info: KeyDerivationInfo) -> Self {
+        let KeyDerivationInfo {
+            derivation_path,
+            public_key,
+            stored_address,
+            derived_address,
+            is_consistent,
+        } = info;
+
+        Self {
+            derivation_path: derivation_path.to_string(),
+            public_key_hex: public_key,
+            stored_address: stored_address.map(Into::into),
+            derived_address: derived_address.into(),
+            is_consistent,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct AddressDerivationMismatch {
+    pub derivation_path: String,
+    pub stored_public_key_address: Option<RpcAddress<Destination>>,
+    pub stored_address: Option<RpcAddress<Destination>>,
+    pub expected_address: RpcAddress<Destination>,
+}
+
+impl AddressDerivationMismatch {
+    pub fn new(mismatch: KeyDerivationMismatch) -> Self {
+        let KeyDerivationMismatch {
+            derivation_path,
+            stored_public_key_address,
+            stored_address,
+            expected_address,
+        } = mismatch;
+
+        Self {
+            derivation_path: derivation_path.to_string(),
+            stored_public_key_address: stored_public_key_address.map(Into::into),
+            stored_address: stored_address.map(Into::into),
+            expected_address: expected_address.into(),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct AddressesVerificationReport {
+    pub account_public_key_matches: bool,
+    pub checked_keys: u64,
+    pub mismatches: Vec<AddressDerivationMismatch>,
+}
+
+impl AddressesVerificationReport {
+    pub fn new(report: KeysVerificationReport) -> Self {
+        let KeysVerificationReport {
+            account_public_key_matches,
+            checked_keys,
+            mismatches,
+        } = report;
+
+        Self {
+            account_public_key_matches,
+            checked_keys: checked_keys as u64,
+            mismatches: mismatches.into_iter().map(AddressDerivationMismatch::new).collect(),
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.account_public_key_matches && self.mismatches.is_empty()
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct AccountExtendedPublicKey {
     pub public_key: HexEncoded<PublicKey>,
@@ -380,17 +475,21 @@ impl VrfPublicKeyInfo {
 pub struct UtxoInfo {
     pub outpoint: RpcUtxoOutpoint,
     pub output: RpcTxOutput,
+    /// The absolute height or time at which a time locked output becomes spendable
+    pub unlock: Option<OutputTimeLock>,
 }
 
 impl UtxoInfo {
     pub fn new(
         outpoint: UtxoOutPoint,
         output: TxOutput,
+        unlock: Option<OutputTimeLock>,
         chain_config: &ChainConfig,
     ) -> Result<Self, AddressError> {
         Ok(Self {
             output: RpcTxOutput::new(chain_config, output)?,
             outpoint: RpcUtxoOutpoint::new(outpoint),
+            unlock,
         })
     }
 }
@@ -408,6 +507,21 @@ impl NewAccountInfo {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct AccountSpendingPolicyInfo {
+    pub min_confirmations_change: u32,
+    pub min_confirmations_external: u32,
+}
+
+impl AccountSpendingPolicyInfo {
+    pub fn new(policy: AccountSpendingPolicy) -> Self {
+        Self {
+            min_confirmations_change: policy.change_min_confirmations(),
+            min_confirmations_external: policy.external_min_confirmations(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct TransactionOptions {
     pub in_top_x_mb: Option<usize>,
@@ -776,6 +890,61 @@ impl RpcNewTransaction {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
+pub struct RpcSendPreview {
+    pub tx_id: Id<Transaction>,
+    /// The hex encoded transaction; it's a signed transaction if signing was requested
+    /// and a partially signed one otherwise.
+    pub tx: String,
+    pub is_signed: bool,
+    pub fees: Balances,
+    /// The fee rate the inputs were selected for, in coins per 1000 bytes.
+    pub fee_rate: RpcAmountOut,
+    pub inputs: Vec<UtxoInfo>,
+    /// The outputs other than the one paying to the requested address.
+    pub change: Vec<RpcTxOutput>,
+}
+
+impl RpcSendPreview {
+    pub fn new(preview: SendPreview, chain_config: &ChainConfig) -> Result<Self, AddressError> {
+        let SendPreview {
+            tx,
+            fees,
+            fee_rate,
+            inputs,
+            change,
+        } = preview;
+
+        let tx_id = tx.transaction().get_id();
+        let (tx, is_signed) = match tx {
+            PreviewedTransaction::Signed(tx) => (HexEncoded::new(tx).to_string(), true),
+            PreviewedTransaction::Unsigned(ptx) => (HexEncoded::new(ptx).to_string(), false),
+        };
+        let fee_rate = RpcAmountOut::from_amount_no_padding(
+            Amount::from_atoms(fee_rate.atoms_per_kb()),
+            chain_config.coin_decimals(),
+        );
+        let inputs = inputs
+            .into_iter()
+            .map(|(outpoint, output)| UtxoInfo::new(outpoint, output, None, chain_config))
+            .collect::<Result<_, _>>()?;
+        let change = change
+            .into_iter()
+            .map(|output| RpcTxOutput::new(chain_config, output))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            tx_id,
+            tx,
+            is_signed,
+            fees,
+            fee_rate,
+            inputs,
+            change,
+        })
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, HasValueHint)]
 pub struct RpcPreparedTransaction {
     pub tx_id: Id<Transaction>,
@@ -15,6 +15,7 @@
 
 mod events;
 mod handle;
+pub mod notifications;
 mod worker;
 
 use std::{path::PathBuf, sync::Arc};
@@ -22,6 +23,7 @@ use std::{path::PathBuf, sync::Arc};
 use common::chain::ChainConfig;
 use crypto::key::hdkd::u31::U31;
 use utils::shallow_clone::ShallowClone;
+use wallet::account::OutputPrivacyPolicy;
 
 pub use events::{Event, TxState};
 pub use handle::{EventStream, SubmitError, WalletHandle};
@@ -60,6 +62,7 @@ where
         wallet_file: Option<(PathBuf, WalletType)>,
         force_change_wallet_type: bool,
         start_staking_for_account: Vec<U31>,
+        output_privacy_policy: OutputPrivacyPolicy,
         node_rpc: N,
     ) -> Result<Self, InitError<N>> {
         let (wallet_events, events_rx) = WalletServiceEvents::new();
@@ -88,6 +91,7 @@ where
                 wallet_events.clone(),
             )
             .await?;
+            controller.set_output_privacy_policy(output_privacy_policy);
 
             for account_index in start_staking_for_account {
                 // Irrelevant for staking
@@ -107,6 +111,7 @@ where
             controller,
             chain_config.clone(),
             node_rpc.clone(),
+            output_privacy_policy,
             command_rx,
             events_rx,
             wallet_events,
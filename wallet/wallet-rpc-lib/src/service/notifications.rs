@@ -0,0 +1,618 @@
+// Copyright (c) 2024 RBB S.r.l
+// opensource@mintlayer.org
+// SPDX-License-Identifier: MIT
+// Licensed under the MIT License;
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://github.com/mintlayer/mintlayer-core/blob/master/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notification hooks executed on wallet events.
+//!
+//! Each configured action is run for every notification produced by the wallet. An action is
+//! either a shell command or an HTTP webhook. Commands get the notification as JSON in the
+//! `ML_WALLET_NOTIFICATION` environment variable and any `%s` in the command line is replaced by
+//! the id of the transaction or block the notification refers to. Webhooks receive the
+//! notification as a JSON body of a POST request.
+//!
+//! The actions are queued and at most `MAX_CONCURRENT_ACTIONS` of them are executed at the same
+//! time; when the queue is full, the new ones are dropped.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use common::{
+    chain::{GenBlock, Transaction},
+    primitives::{BlockHeight, Id},
+};
+use crypto::key::hdkd::u31::U31;
+use logging::log;
+use tokio::{
+    sync::{mpsc, Semaphore},
+    task::JoinHandle,
+};
+use utils::bounded_fifo_map::BoundedFifoMap;
+use wallet::account::transaction_list::TxType;
+use wallet_controller::NodeInterface;
+
+use super::{Event, TxState, WalletHandle};
+use crate::types::RpcError;
+
+/// Environment variable that carries the JSON-encoded notification to command actions
+pub const NOTIFICATION_ENV_VAR: &str = "ML_WALLET_NOTIFICATION";
+
+/// Placeholder in command actions that is substituted by the transaction or block id
+const ID_PLACEHOLDER: &str = "%s";
+
+/// Maximum number of transaction ids remembered to report each new transaction only once
+const MAX_SEEN_TXS: usize = 10_000;
+
+/// Timeout of a single webhook request
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of actions executed at the same time
+const MAX_CONCURRENT_ACTIONS: usize = 4;
+
+/// Maximum number of actions waiting to be executed
+const MAX_QUEUED_ACTIONS: usize = 1000;
+
+/// Action performed when a notification is triggered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// Execute the given command line using the system shell
+    Command(String),
+
+    /// Send a POST request with the notification as JSON body to the given URL
+    Webhook(String),
+}
+
+/// Notification hooks configuration
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NotificationConfig {
+    /// Actions to execute for each notification
+    pub actions: Vec<NotificationAction>,
+
+    /// Number of confirmations after which [Notification::TxConfirmed] is emitted.
+    /// Zero means the confirmation notification is disabled.
+    pub confirmations: u64,
+}
+
+impl NotificationConfig {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// Notification passed to the configured actions
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", content = "content")]
+pub enum Notification {
+    /// An incoming payment to the wallet has been seen for the first time
+    NewTransaction {
+        account_idx: u32,
+        tx_id: Id<Transaction>,
+    },
+
+    /// A transaction reached the configured number of confirmations
+    TxConfirmed {
+        account_idx: u32,
+        tx_id: Id<Transaction>,
+        block_height: BlockHeight,
+        confirmations: u64,
+    },
+
+    /// A staking reward from the given block has been received
+    StakingReward {
+        account_idx: u32,
+        block_id: Id<GenBlock>,
+    },
+
+    /// A transaction conflicts with a transaction in the given confirmed block
+    TxConflicted {
+        account_idx: u32,
+        tx_id: Id<Transaction>,
+        with_block: Id<GenBlock>,
+    },
+}
+
+impl Notification {
+    fn subject_id(&self) -> String {
+        match self {
+            Self::NewTransaction { tx_id, .. }
+            | Self::TxConfirmed { tx_id, .. }
+            | Self::TxConflicted { tx_id, .. } => format!("{tx_id:x}"),
+            Self::StakingReward { block_id, .. } => format!("{block_id:x}"),
+        }
+    }
+}
+
+/// Turns the stream of wallet events into notifications.
+///
+/// [Notification::NewTransaction] is produced for every transaction seen for the first time;
+/// the ones that aren't incoming payments are filtered out before dispatching.
+struct NotificationTracker {
+    confirmations: u64,
+    seen_txs: BoundedFifoMap<Id<Transaction>, ()>,
+    pending_confirmation: BTreeMap<Id<Transaction>, (u32, BlockHeight)>,
+}
+
+impl NotificationTracker {
+    fn new(confirmations: u64) -> Self {
+        Self {
+            confirmations,
+            seen_txs: BoundedFifoMap::new(MAX_SEEN_TXS),
+            pending_confirmation: BTreeMap::new(),
+        }
+    }
+
+    fn mark_seen(&mut self, tx_id: Id<Transaction>) -> bool {
+        self.seen_txs.insert(tx_id, ()).is_none()
+    }
+
+    fn forget_seen(&mut self, tx_id: &Id<Transaction>) {
+        self.seen_txs.remove(tx_id);
+    }
+
+    fn process_event(&mut self, event: Event) -> Vec<Notification> {
+        let mut notifications = Vec::new();
+
+        match event {
+            Event::NewBlock {} => {}
+            Event::TxUpdated {
+                account_idx,
+                tx_id,
+                state,
+            } => {
+                if self.mark_seen(tx_id) {
+                    notifications.push(Notification::NewTransaction { account_idx, tx_id });
+                }
+
+                match state {
+                    TxState::Confirmed {
+                        block_height,
+                        block_timestamp: _,
+                    } => {
+                        if self.confirmations > 0 {
+                            self.pending_confirmation.insert(tx_id, (account_idx, block_height));
+                        }
+                    }
+                    TxState::Conflicted { with_block } => {
+                        self.pending_confirmation.remove(&tx_id);
+                        notifications.push(Notification::TxConflicted {
+                            account_idx,
+                            tx_id,
+                            with_block,
+                        });
+                    }
+                    TxState::InMempool {} | TxState::Inactive {} | TxState::Abandoned {} => {
+                        self.pending_confirmation.remove(&tx_id);
+                    }
+                }
+            }
+            Event::TxDropped {
+                account_idx: _,
+                tx_id,
+            } => {
+                self.forget_seen(&tx_id);
+                self.pending_confirmation.remove(&tx_id);
+            }
+            Event::RewardAdded { account_idx, data } => {
+                notifications.push(Notification::StakingReward {
+                    account_idx,
+                    block_id: *data.block_id(),
+                });
+            }
+            Event::RewardDropped {
+                account_idx: _,
+                block_id: _,
+            } => {}
+        }
+
+        notifications
+    }
+
+    fn process_tip(&mut self, tip_height: BlockHeight) -> Vec<Notification> {
+        let confirmations = self.confirmations;
+        let mut notifications = Vec::new();
+
+        self.pending_confirmation.retain(|tx_id, (account_idx, block_height)| {
+            let depth = (tip_height - *block_height)
+                .and_then(|distance| u64::try_from(distance.to_int()).ok())
+                .map_or(0, |distance| distance + 1);
+
+            if depth >= confirmations {
+                notifications.push(Notification::TxConfirmed {
+                    account_idx: *account_idx,
+                    tx_id: *tx_id,
+                    block_height: *block_height,
+                    confirmations: depth,
+                });
+                false
+            } else {
+                true
+            }
+        });
+
+        notifications
+    }
+}
+
+/// Check whether the transaction is an incoming payment to the account
+async fn is_incoming_payment<N>(
+    handle: &WalletHandle<N>,
+    account_idx: u32,
+    tx_id: Id<Transaction>,
+) -> bool
+where
+    N: NodeInterface + Clone + Send + Sync + 'static,
+{
+    let tx_info = handle
+        .call(move |controller| {
+            let account_index = U31::from_u32(account_idx).ok_or(RpcError::AcctIndexOutOfRange)?;
+            controller
+                .readonly_controller(account_index)
+                .get_transaction_info(tx_id)
+                .map_err(RpcError::<N>::from)
+        })
+        .await;
+
+    match tx_info {
+        Ok(Ok(tx_info)) => match tx_info.tx_type {
+            TxType::Received { .. } => true,
+            TxType::Redeposit {} | TxType::Sent { .. } | TxType::Other {} => false,
+        },
+        Ok(Err(err)) => {
+            log::debug!("Cannot query wallet transaction {tx_id:x}: {err}");
+            false
+        }
+        Err(err) => {
+            log::debug!("Cannot query wallet transaction {tx_id:x}: {err}");
+            false
+        }
+    }
+}
+
+/// Drop the [Notification::NewTransaction] notifications that aren't incoming payments
+async fn retain_incoming<N>(
+    handle: &WalletHandle<N>,
+    notifications: Vec<Notification>,
+) -> Vec<Notification>
+where
+    N: NodeInterface + Clone + Send + Sync + 'static,
+{
+    let mut result = Vec::with_capacity(notifications.len());
+    for notification in notifications {
+        let keep = match notification {
+            Notification::NewTransaction { account_idx, tx_id } => {
+                is_incoming_payment(handle, account_idx, tx_id).await
+            }
+            Notification::TxConfirmed { .. }
+            | Notification::StakingReward { .. }
+            | Notification::TxConflicted { .. } => true,
+        };
+        if keep {
+            result.push(notification);
+        }
+    }
+    result
+}
+
+async fn execute_action(
+    http_client: &reqwest::Client,
+    action: &NotificationAction,
+    notification: &Notification,
+) {
+    let json = match serde_json::to_string(notification) {
+        Ok(json) => json,
+        Err(err) => {
+            log::error!("Failed to encode wallet notification: {err}");
+            return;
+        }
+    };
+
+    match action {
+        NotificationAction::Command(command) => {
+            let command = command.replace(ID_PLACEHOLDER, &notification.subject_id());
+
+            #[cfg(not(windows))]
+            let mut cmd = {
+                let mut cmd = tokio::process::Command::new("sh");
+                cmd.arg("-c").arg(&command);
+                cmd
+            };
+            #[cfg(windows)]
+            let mut cmd = {
+                let mut cmd = tokio::process::Command::new("cmd");
+                cmd.arg("/C").arg(&command);
+                cmd
+            };
+
+            match cmd.env(NOTIFICATION_ENV_VAR, json).status().await {
+                Ok(status) if status.success() => {}
+                Ok(status) => log::warn!("Notification command `{command}` failed: {status}"),
+                Err(err) => log::warn!("Failed to execute notification command `{command}`: {err}"),
+            }
+        }
+        NotificationAction::Webhook(url) => {
+            let result = http_client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(json)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            if let Err(err) = result {
+                log::warn!("Notification webhook {url} failed: {err}");
+            }
+        }
+    }
+}
+
+type QueuedAction = (NotificationAction, Notification);
+
+/// Execute the queued actions, at most `MAX_CONCURRENT_ACTIONS` at a time
+async fn run_actions(http_client: reqwest::Client, mut queue: mpsc::Receiver<QueuedAction>) {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_ACTIONS));
+    while let Some((action, notification)) = queue.recv().await {
+        let permit = Arc::clone(&semaphore)
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        let http_client = http_client.clone();
+        tokio::spawn(async move {
+            execute_action(&http_client, &action, &notification).await;
+            drop(permit);
+        });
+    }
+}
+
+/// Queue the actions for the notifications, dropping the ones that don't fit into the queue
+fn dispatch(
+    queue: &mpsc::Sender<QueuedAction>,
+    actions: &[NotificationAction],
+    notifications: Vec<Notification>,
+) {
+    for notification in notifications {
+        log::debug!("Wallet notification: {notification:?}");
+        for action in actions {
+            match queue.try_send((action.clone(), notification.clone())) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    log::warn!(
+                        "Too many pending notification actions, dropping {action:?} for {notification:?}"
+                    );
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    log::error!("Notification actions are no longer executed");
+                }
+            }
+        }
+    }
+}
+
+/// Spawn a task executing the notification actions for the events emitted by the wallet service.
+///
+/// The task terminates once the wallet service shuts down.
+pub fn spawn<N>(handle: WalletHandle<N>, config: NotificationConfig) -> JoinHandle<()>
+where
+    N: NodeInterface + Clone + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut events = match handle.subscribe().await {
+            Ok(events) => events,
+            Err(err) => {
+                log::error!("Failed to subscribe to wallet events for notifications: {err}");
+                return;
+            }
+        };
+
+        let http_client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+            Ok(http_client) => http_client,
+            Err(err) => {
+                log::error!("Failed to create the HTTP client for notifications: {err}");
+                return;
+            }
+        };
+
+        let NotificationConfig {
+            actions,
+            confirmations,
+        } = config;
+        let mut tracker = NotificationTracker::new(confirmations);
+
+        // The executor stops once the queue is dropped
+        let (queue, queue_receiver) = mpsc::channel(MAX_QUEUED_ACTIONS);
+        tokio::spawn(run_actions(http_client, queue_receiver));
+
+        while let Some(event) = events.recv().await {
+            let is_new_block = event == Event::NewBlock {};
+            let notifications = retain_incoming(&handle, tracker.process_event(event)).await;
+            dispatch(&queue, &actions, notifications);
+
+            if is_new_block && !tracker.pending_confirmation.is_empty() {
+                let tip =
+                    handle.call(|controller| Ok::<_, RpcError<N>>(controller.best_block().1)).await;
+                match tip {
+                    Ok(Ok(tip_height)) => {
+                        dispatch(&queue, &actions, tracker.process_tip(tip_height))
+                    }
+                    Ok(Err(err)) => log::debug!("Cannot query wallet best block: {err}"),
+                    Err(err) => log::debug!("Cannot query wallet best block: {err}"),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::{chain::block::timestamp::BlockTimestamp, primitives::H256};
+
+    use super::*;
+
+    fn tx_id(n: u64) -> Id<Transaction> {
+        Id::new(H256::from_low_u64_be(n))
+    }
+
+    fn confirmed(height: u64) -> TxState {
+        TxState::Confirmed {
+            block_height: BlockHeight::new(height),
+            block_timestamp: BlockTimestamp::from_int_seconds(0),
+        }
+    }
+
+    #[test]
+    fn new_transaction_reported_once() {
+        let mut tracker = NotificationTracker::new(0);
+        let tx_id = tx_id(1);
+
+        let event = Event::TxUpdated {
+            account_idx: 0,
+            tx_id,
+            state: TxState::InMempool {},
+        };
+        assert_eq!(
+            tracker.process_event(event.clone()),
+            vec![Notification::NewTransaction {
+                account_idx: 0,
+                tx_id
+            }]
+        );
+        assert_eq!(tracker.process_event(event), vec![]);
+
+        assert_eq!(
+            tracker.process_event(Event::TxUpdated {
+                account_idx: 0,
+                tx_id,
+                state: confirmed(5),
+            }),
+            vec![]
+        );
+        assert!(tracker.pending_confirmation.is_empty());
+    }
+
+    #[test]
+    fn seen_txs_bounded() {
+        let mut tracker = NotificationTracker::new(0);
+
+        for n in 0..(MAX_SEEN_TXS as u64 + 10) {
+            tracker.process_event(Event::TxUpdated {
+                account_idx: 0,
+                tx_id: tx_id(n),
+                state: TxState::InMempool {},
+            });
+        }
+        assert_eq!(tracker.seen_txs.len(), MAX_SEEN_TXS);
+        assert!(!tracker.seen_txs.contains_key(&tx_id(0)));
+        assert!(tracker.seen_txs.contains_key(&tx_id(MAX_SEEN_TXS as u64 + 9)));
+
+        tracker.process_event(Event::TxDropped {
+            account_idx: 0,
+            tx_id: tx_id(20),
+        });
+        assert_eq!(tracker.seen_txs.len(), MAX_SEEN_TXS - 1);
+    }
+
+    #[test]
+    fn confirmation_depth() {
+        let mut tracker = NotificationTracker::new(3);
+        let tx_id = tx_id(2);
+
+        tracker.process_event(Event::TxUpdated {
+            account_idx: 1,
+            tx_id,
+            state: confirmed(10),
+        });
+
+        assert_eq!(tracker.process_tip(BlockHeight::new(10)), vec![]);
+        assert_eq!(tracker.process_tip(BlockHeight::new(11)), vec![]);
+        assert_eq!(
+            tracker.process_tip(BlockHeight::new(12)),
+            vec![Notification::TxConfirmed {
+                account_idx: 1,
+                tx_id,
+                block_height: BlockHeight::new(10),
+                confirmations: 3,
+            }]
+        );
+        assert_eq!(tracker.process_tip(BlockHeight::new(13)), vec![]);
+    }
+
+    #[test]
+    fn reorg_cancels_pending_confirmation() {
+        let mut tracker = NotificationTracker::new(2);
+        let tx_id = tx_id(3);
+
+        tracker.process_event(Event::TxUpdated {
+            account_idx: 0,
+            tx_id,
+            state: confirmed(10),
+        });
+        tracker.process_event(Event::TxUpdated {
+            account_idx: 0,
+            tx_id,
+            state: TxState::InMempool {},
+        });
+        assert_eq!(tracker.process_tip(BlockHeight::new(20)), vec![]);
+    }
+
+    #[test]
+    fn full_queue_drops_actions() {
+        let actions = [
+            NotificationAction::Command("true".to_owned()),
+            NotificationAction::Webhook("http://127.0.0.1:1".to_owned()),
+        ];
+        let notifications = (0..3)
+            .map(|n| Notification::NewTransaction {
+                account_idx: 0,
+                tx_id: tx_id(n),
+            })
+            .collect::<Vec<_>>();
+
+        let (queue, mut queue_receiver) = mpsc::channel(5);
+        dispatch(&queue, &actions, notifications.clone());
+
+        // The last action doesn't fit into the queue
+        let expected = notifications
+            .iter()
+            .flat_map(|notification| {
+                actions.iter().map(|action| (action.clone(), notification.clone()))
+            })
+            .take(5);
+        for queued_action in expected {
+            assert_eq!(queue_receiver.try_recv().unwrap(), queued_action);
+        }
+        assert!(queue_receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn conflicted() {
+        let mut tracker = NotificationTracker::new(0);
+        let tx_id = tx_id(4);
+        let with_block = Id::new(H256::from_low_u64_be(5));
+
+        tracker.process_event(Event::TxUpdated {
+            account_idx: 0,
+            tx_id,
+            state: TxState::InMempool {},
+        });
+        assert_eq!(
+            tracker.process_event(Event::TxUpdated {
+                account_idx: 0,
+                tx_id,
+                state: TxState::Conflicted { with_block },
+            }),
+            vec![Notification::TxConflicted {
+                account_idx: 0,
+                tx_id,
+                with_block
+            }]
+        );
+    }
+}
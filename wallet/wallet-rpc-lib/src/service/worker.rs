@@ -18,6 +18,7 @@ use std::{ops::ControlFlow, path::PathBuf, sync::Arc};
 use common::chain::ChainConfig;
 use futures::{future::BoxFuture, never::Never};
 use tokio::{sync::mpsc, task::JoinHandle};
+use wallet::account::OutputPrivacyPolicy;
 
 use logging::log;
 use utils_networking::broadcaster::Broadcaster;
@@ -61,6 +62,7 @@ pub struct WalletWorker<N> {
     command_rx: CommandReceiver<N>,
     chain_config: Arc<ChainConfig>,
     node_rpc: N,
+    output_privacy_policy: OutputPrivacyPolicy,
     events_bcast: Broadcaster<Event>,
     events_rx: mpsc::UnboundedReceiver<Event>,
     wallet_events: WalletServiceEvents,
@@ -74,6 +76,7 @@ where
         controller: Option<WalletController<N>>,
         chain_config: Arc<ChainConfig>,
         node_rpc: N,
+        output_privacy_policy: OutputPrivacyPolicy,
         command_rx: CommandReceiver<N>,
         events_rx: mpsc::UnboundedReceiver<Event>,
         wallet_events: WalletServiceEvents,
@@ -84,6 +87,7 @@ where
             command_rx,
             chain_config,
             node_rpc,
+            output_privacy_policy,
             events_bcast,
             events_rx,
             wallet_events,
@@ -182,7 +186,7 @@ where
             }
         };
 
-        let controller = if scan_blockchain.should_wait_for_blockchain_scanning() {
+        let mut controller = if scan_blockchain.should_wait_for_blockchain_scanning() {
             WalletController::new(
                 self.chain_config.clone(),
                 self.node_rpc.clone(),
@@ -198,6 +202,7 @@ where
                 self.wallet_events.clone(),
             )
         };
+        controller.set_output_privacy_policy(self.output_privacy_policy);
         self.controller.replace(controller);
 
         Ok(OpenedWallet::Opened)
@@ -245,7 +250,7 @@ where
             }
         };
 
-        let controller = if options.scan_blockchain.should_wait_for_blockchain_scanning() {
+        let mut controller = if options.scan_blockchain.should_wait_for_blockchain_scanning() {
             WalletController::new(
                 self.chain_config.clone(),
                 self.node_rpc.clone(),
@@ -263,6 +268,7 @@ where
             )
         };
 
+        controller.set_output_privacy_policy(self.output_privacy_policy);
         self.controller.replace(controller);
 
         Ok(wallet_created)
@@ -290,6 +296,7 @@ where
         controller: Option<WalletController<N>>,
         chain_config: Arc<ChainConfig>,
         node_rpc: N,
+        output_privacy_policy: OutputPrivacyPolicy,
         command_rx: CommandReceiver<N>,
         events_rx: mpsc::UnboundedReceiver<Event>,
         wallet_events: WalletServiceEvents,
@@ -298,6 +305,7 @@ where
             controller,
             chain_config,
             node_rpc,
+            output_privacy_policy,
             command_rx,
             events_rx,
             wallet_events,
@@ -106,6 +106,8 @@ impl TestFramework {
             chain_max_future_block_time_offset: None,
             chain_max_block_size_with_standard_txs: None,
             chain_max_block_size_with_smart_contracts: None,
+            chain_epoch_length: None,
+            chain_sealed_epoch_distance_from_tip: None,
             chain_chainstate_orders_v1_upgrade_height: None,
         };
 
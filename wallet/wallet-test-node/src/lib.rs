@@ -126,6 +126,8 @@ pub fn default_chain_config_options() -> ChainConfigOptions {
         chain_max_block_header_size: None,
         chain_max_block_size_with_standard_txs: None,
         chain_max_block_size_with_smart_contracts: None,
+        chain_epoch_length: None,
+        chain_sealed_epoch_distance_from_tip: None,
         chain_initial_difficulty: None,
         chain_pos_netupgrades: None,
         chain_pos_netupgrades_v0_to_v1: None,
@@ -169,6 +171,7 @@ pub async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager,
         boot_nodes: Default::default(),
         reserved_nodes: Default::default(),
         whitelisted_addresses: Default::default(),
+        external_addresses: Default::default(),
         ban_config: Default::default(),
         outbound_connection_timeout: Default::default(),
         ping_check_period: Default::default(),
@@ -179,6 +182,9 @@ pub async fn start_node(chain_config: Arc<ChainConfig>) -> (subsystem::Manager,
         allow_discover_private_ips: Default::default(),
         user_agent: common::primitives::user_agent::mintlayer_core_user_agent(),
         sync_stalling_timeout: Default::default(),
+        tx_relay_trickle_interval: Default::default(),
+        disable_tx_trickle_for_whitelisted_peers: Default::default(),
+        tx_rebroadcast_interval: Default::default(),
         peer_manager_config: Default::default(),
         protocol_config: Default::default(),
     };